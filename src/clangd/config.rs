@@ -3,7 +3,10 @@
 //! Provides ClangdConfig for session configuration with builder pattern,
 //! validation, and support for different LSP and resource settings.
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -44,6 +47,9 @@ pub const MEMORY_TO_RESULTS_FACTOR: u64 = 1000;
 /// reasonable performance. This is applied via the --limit-results clangd argument.
 pub const DEFAULT_WORKSPACE_SYMBOL_LIMIT: u32 = 1000;
 
+/// Workspace symbol limit clangd applies without a `--limit-results` argument
+pub const CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT: u32 = 100;
+
 /// Default timeout for waiting for indexing completion (20 seconds)
 ///
 /// This is the default time to wait for clangd to complete indexing before
@@ -67,9 +73,15 @@ pub struct ClangdConfig {
     /// Build directory with compile_commands.json
     pub build_directory: PathBuf,
 
+    /// Directory containing compile_commands.json when it is not in the build directory
+    pub compile_commands_dir: Option<PathBuf>,
+
     /// Additional clangd command-line arguments
     pub extra_args: Vec<String>,
 
+    /// Environment variables set for the clangd process on top of the inherited ones
+    pub env: BTreeMap<String, String>,
+
     /// LSP initialization options
     pub lsp_config: LspConfig,
 
@@ -86,7 +98,9 @@ impl std::fmt::Debug for ClangdConfig {
             .field("working_directory", &self.working_directory)
             .field("clangd_path", &self.clangd_path)
             .field("build_directory", &self.build_directory)
+            .field("compile_commands_dir", &self.compile_commands_dir)
             .field("extra_args", &self.extra_args)
+            .field("env", &self.env)
             .field("lsp_config", &self.lsp_config)
             .field("resource_config", &self.resource_config)
             .field(
@@ -158,7 +172,9 @@ pub struct ClangdConfigBuilder {
     working_directory: Option<PathBuf>,
     clangd_path: Option<String>,
     build_directory: Option<PathBuf>,
+    compile_commands_dir: Option<PathBuf>,
     extra_args: Vec<String>,
+    env: BTreeMap<String, String>,
     lsp_config: LspConfigBuilder,
     resource_config: ResourceConfigBuilder,
     stderr_handler: Option<Arc<dyn Fn(String) + Send + Sync>>,
@@ -225,7 +241,9 @@ impl ClangdConfigBuilder {
             working_directory: None,
             clangd_path: None,
             build_directory: None,
+            compile_commands_dir: None,
             extra_args: Vec::new(),
+            env: BTreeMap::new(),
             lsp_config: LspConfigBuilder::default(),
             resource_config: ResourceConfigBuilder::default(),
             stderr_handler: None,
@@ -250,6 +268,14 @@ impl ClangdConfigBuilder {
         self
     }
 
+    /// Set an explicit directory containing compile_commands.json
+    ///
+    /// Overrides the build directory as clangd's `--compile-commands-dir`.
+    pub fn compile_commands_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.compile_commands_dir = Some(path.into());
+        self
+    }
+
     /// Add an extra command-line argument for clangd
     pub fn add_arg(mut self, arg: impl Into<String>) -> Self {
         self.extra_args.push(arg.into());
@@ -263,6 +289,19 @@ impl ClangdConfigBuilder {
         self
     }
 
+    /// Set environment variables for the clangd process, e.g. a `PATH` that
+    /// contains the compilers matched by `--query-driver`
+    pub fn envs(
+        mut self,
+        env: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.env.extend(
+            env.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
     /// Set the LSP root URI
     pub fn root_uri(mut self, uri: impl Into<String>) -> Self {
         self.lsp_config.root_uri = Some(uri.into());
@@ -361,6 +400,11 @@ impl ClangdConfigBuilder {
         // Validate paths
         Self::validate_working_directory(&working_directory)?;
         Self::validate_build_directory(&build_directory)?;
+        Self::validate_compile_commands_dir(
+            self.compile_commands_dir
+                .as_deref()
+                .unwrap_or(&build_directory),
+        )?;
         Self::validate_clangd_path(&clangd_path)?;
 
         // Validate timeouts
@@ -373,7 +417,9 @@ impl ClangdConfigBuilder {
             working_directory,
             clangd_path,
             build_directory,
+            compile_commands_dir: self.compile_commands_dir,
             extra_args: self.extra_args,
+            env: self.env,
             lsp_config,
             resource_config,
             stderr_handler: self.stderr_handler,
@@ -427,13 +473,18 @@ impl ClangdConfigBuilder {
             });
         }
 
+        Ok(())
+    }
+
+    /// Validate the directory passed to clangd contains compile_commands.json
+    fn validate_compile_commands_dir(path: &Path) -> Result<(), ClangdConfigError> {
         let compile_commands = path.join("compile_commands.json");
         if !compile_commands.exists() {
             return Err(ClangdConfigError::BuildDirectoryValidation {
                 build_dir: path.to_path_buf(),
                 source: std::io::Error::new(
                     std::io::ErrorKind::NotFound,
-                    "compile_commands.json not found in build directory",
+                    "compile_commands.json not found in compile commands directory",
                 ),
             });
         }
@@ -557,9 +608,13 @@ impl Default for ClangdConfigBuilder {
 impl ClangdConfig {
     /// Get the full command-line arguments for clangd
     pub fn get_clangd_args(&self) -> Vec<String> {
+        let compile_commands_dir = self
+            .compile_commands_dir
+            .as_ref()
+            .unwrap_or(&self.build_directory);
         let mut args = vec![
             "--compile-commands-dir".to_string(),
-            self.build_directory.to_string_lossy().to_string(),
+            compile_commands_dir.to_string_lossy().to_string(),
         ];
 
         // Add background indexing control
@@ -585,10 +640,7 @@ impl ClangdConfig {
     pub fn get_root_uri(&self) -> Option<String> {
         self.lsp_config.root_uri.clone().or_else(|| {
             // Auto-generate from working directory if not specified
-            Some(format!(
-                "file://{}",
-                self.working_directory.to_string_lossy()
-            ))
+            Some(crate::symbol::uri_from_pathbuf(&self.working_directory).to_string())
         })
     }
 
@@ -596,6 +648,153 @@ impl ClangdConfig {
     pub fn is_verbose_tracing(&self) -> bool {
         self.lsp_config.verbose_tracing
     }
+
+    /// Get the number of threads clangd uses for background indexing
+    ///
+    /// Taken from the last `-j` argument; without one clangd uses one thread
+    /// per hardware thread.
+    pub fn indexing_threads(&self) -> u32 {
+        let mut threads = None;
+        let mut args = self.extra_args.iter();
+        while let Some(arg) = args.next() {
+            let value = match arg.as_str() {
+                "-j" | "--j" => args.next().map(String::as_str),
+                _ => arg.strip_prefix("-j=").or_else(|| arg.strip_prefix("--j=")),
+            };
+            if let Some(count) = value.and_then(|value| value.parse().ok()) {
+                threads = Some(count);
+            }
+        }
+        threads.unwrap_or_else(default_indexing_threads)
+    }
+
+    /// Get where clangd stores the preambles of open files
+    ///
+    /// Taken from the last valid `--pch-storage` argument; clangd stores them
+    /// on disk by default.
+    pub fn pch_storage(&self) -> PchStorage {
+        self.extra_args
+            .iter()
+            .filter_map(|arg| arg.strip_prefix("--pch-storage="))
+            .filter_map(|value| value.parse().ok())
+            .next_back()
+            .unwrap_or(PchStorage::Disk)
+    }
+
+    /// Get the compiler driver globs clangd may run to query system includes
+    ///
+    /// Collected from all `--query-driver` arguments, which take comma-separated lists.
+    pub fn query_driver_globs(&self) -> Vec<String> {
+        self.extra_args
+            .iter()
+            .filter_map(|arg| arg.strip_prefix("--query-driver="))
+            .flat_map(|globs| globs.split(','))
+            .filter(|glob| !glob.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Get the maximum number of results clangd returns for `workspace/symbol`
+    ///
+    /// Taken from the last `--limit-results` argument; `None` when the limit is
+    /// disabled with `--limit-results=0`.
+    pub fn workspace_symbol_limit(&self) -> Option<u32> {
+        let limit = self
+            .get_clangd_args()
+            .iter()
+            .filter_map(|arg| arg.strip_prefix("--limit-results="))
+            .filter_map(|value| value.parse::<u32>().ok())
+            .next_back()
+            .unwrap_or(CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT);
+        (limit > 0).then_some(limit)
+    }
+}
+
+/// Build the clangd argument limiting background indexing to `threads` threads
+pub fn indexing_threads_arg(threads: u32) -> String {
+    format!("-j={threads}")
+}
+
+/// Number of indexing threads clangd uses by default (one per hardware thread)
+pub fn default_indexing_threads() -> u32 {
+    std::thread::available_parallelism().map_or(1, |count| count.get() as u32)
+}
+
+/// Available memory from which preambles are kept in memory by default (16 GiB)
+///
+/// A preamble of a translation unit including large headers takes hundreds of
+/// megabytes, and clangd keeps one per open file.
+pub const PCH_MEMORY_MIN_AVAILABLE_BYTES: u64 = 16 * 1024 * 1024 * 1024;
+
+/// Where clangd keeps the preambles (precompiled `#include`s) of open files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PchStorage {
+    /// Temporary files, memory-mapped when used (clangd's default)
+    Disk,
+    /// Process memory: faster to rebuild and read, but grows with open files
+    Memory,
+}
+
+impl PchStorage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PchStorage::Disk => "disk",
+            PchStorage::Memory => "memory",
+        }
+    }
+}
+
+impl FromStr for PchStorage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disk" => Ok(PchStorage::Disk),
+            "memory" => Ok(PchStorage::Memory),
+            other => Err(format!(
+                "invalid PCH storage '{other}', expected \"disk\" or \"memory\""
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for PchStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Build the clangd argument selecting where preambles are stored
+pub fn pch_storage_arg(storage: PchStorage) -> String {
+    format!("--pch-storage={storage}")
+}
+
+/// PCH storage for a machine with `available_memory` bytes available
+///
+/// Memory storage is only chosen with plenty of memory to spare; when the
+/// available memory is unknown clangd's disk default is kept.
+pub fn default_pch_storage(available_memory: Option<u64>) -> PchStorage {
+    match available_memory {
+        Some(bytes) if bytes >= PCH_MEMORY_MIN_AVAILABLE_BYTES => PchStorage::Memory,
+        _ => PchStorage::Disk,
+    }
+}
+
+/// Memory available to new processes without swapping, if the OS reports it
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_available_memory(&meminfo)
+}
+
+/// Extract `MemAvailable` from `/proc/meminfo` content, in bytes
+fn parse_available_memory(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kilobytes| kilobytes.trim().parse::<u64>().ok())
+        .map(|kilobytes| kilobytes * 1024)
 }
 
 // ============================================================================
@@ -625,10 +824,12 @@ mod tests {
             .verbose_tracing(true)
             .max_memory_mb(2048)
             .process_priority(ProcessPriority::High)
+            .envs([("PATH", "/opt/cross/bin")])
             .build()
             .unwrap();
 
         assert_eq!(config.clangd_path, "/usr/bin/clangd");
+        assert_eq!(config.env["PATH"], "/opt/cross/bin");
         assert_eq!(config.extra_args, vec!["--log=verbose", "--pretty"]);
         assert_eq!(
             config.lsp_config.root_uri,
@@ -699,6 +900,147 @@ mod tests {
         assert!(args.iter().any(|arg| arg.starts_with("--limit-results=")));
     }
 
+    #[test]
+    fn test_indexing_threads() {
+        let temp_dir = tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(build_dir.join("compile_commands.json"), "[]").unwrap();
+        let builder = || {
+            ClangdConfigBuilder::new()
+                .working_directory(temp_dir.path())
+                .build_directory(&build_dir)
+        };
+
+        let config = builder().build().unwrap();
+        assert_eq!(config.indexing_threads(), default_indexing_threads());
+
+        let config = builder().add_arg(indexing_threads_arg(2)).build().unwrap();
+        assert!(config.get_clangd_args().contains(&"-j=2".to_string()));
+        assert_eq!(config.indexing_threads(), 2);
+
+        // The last occurrence wins, as in clangd's option parsing
+        let config = builder().add_args(["-j=2", "-j", "6"]).build().unwrap();
+        assert_eq!(config.indexing_threads(), 6);
+    }
+
+    #[test]
+    fn test_pch_storage() {
+        let temp_dir = tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(build_dir.join("compile_commands.json"), "[]").unwrap();
+        let builder = || {
+            ClangdConfigBuilder::new()
+                .working_directory(temp_dir.path())
+                .build_directory(&build_dir)
+        };
+
+        let config = builder().build().unwrap();
+        assert_eq!(config.pch_storage(), PchStorage::Disk);
+
+        // An explicit clangd argument after the selected default wins
+        let config = builder()
+            .add_arg(pch_storage_arg(PchStorage::Memory))
+            .add_args(["--pch-storage=disk", "--pch-storage=bogus"])
+            .build()
+            .unwrap();
+        assert_eq!(config.pch_storage(), PchStorage::Disk);
+
+        assert_eq!(default_pch_storage(None), PchStorage::Disk);
+        assert_eq!(
+            default_pch_storage(Some(4 * 1024 * 1024 * 1024)),
+            PchStorage::Disk
+        );
+        assert_eq!(
+            default_pch_storage(Some(PCH_MEMORY_MIN_AVAILABLE_BYTES)),
+            PchStorage::Memory
+        );
+        assert_eq!(
+            parse_available_memory(
+                "MemTotal:       32768000 kB\nMemFree:         1000 kB\nMemAvailable:   20971520 kB\n"
+            ),
+            Some(20 * 1024 * 1024 * 1024)
+        );
+        assert_eq!(parse_available_memory("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_workspace_symbol_limit() {
+        let temp_dir = tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(build_dir.join("compile_commands.json"), "[]").unwrap();
+        let builder = || {
+            ClangdConfigBuilder::new()
+                .working_directory(temp_dir.path())
+                .build_directory(&build_dir)
+        };
+
+        let config = builder().build().unwrap();
+        assert_eq!(
+            config.workspace_symbol_limit(),
+            Some(CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT)
+        );
+
+        let config = builder()
+            .add_args(["--limit-results=1000", "--limit-results=250"])
+            .build()
+            .unwrap();
+        assert_eq!(config.workspace_symbol_limit(), Some(250));
+
+        let config = builder().add_arg("--limit-results=0").build().unwrap();
+        assert_eq!(config.workspace_symbol_limit(), None);
+    }
+
+    #[test]
+    fn test_query_driver_globs() {
+        let temp_dir = tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(build_dir.join("compile_commands.json"), "[]").unwrap();
+
+        let config = ClangdConfigBuilder::new()
+            .working_directory(temp_dir.path())
+            .build_directory(&build_dir)
+            .add_args([
+                "--query-driver=/opt/arm/bin/*-g++,/usr/bin/gcc",
+                "--query-driver=**",
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.query_driver_globs(),
+            vec!["/opt/arm/bin/*-g++", "/usr/bin/gcc", "**"]
+        );
+    }
+
+    #[test]
+    fn test_explicit_compile_commands_dir() {
+        let temp_dir = tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        std::fs::create_dir(&build_dir).unwrap();
+        std::fs::write(temp_dir.path().join("compile_commands.json"), "[]").unwrap();
+
+        // Build directory alone lacks compile_commands.json
+        let result = ClangdConfigBuilder::new()
+            .working_directory(temp_dir.path())
+            .build_directory(&build_dir)
+            .build();
+        assert!(result.is_err());
+
+        let config = ClangdConfigBuilder::new()
+            .working_directory(temp_dir.path())
+            .build_directory(&build_dir)
+            .compile_commands_dir(temp_dir.path())
+            .build()
+            .unwrap();
+
+        let args = config.get_clangd_args();
+        assert_eq!(args[0], "--compile-commands-dir");
+        assert_eq!(args[1], temp_dir.path().to_string_lossy());
+    }
+
     #[test]
     fn test_root_uri_auto_generation() {
         let temp_dir = tempdir().unwrap();
@@ -0,0 +1,194 @@
+//! Diagnostics collection for clangd sessions
+//!
+//! Caches `textDocument/publishDiagnostics` notifications per document so tools
+//! can query the latest compiler and clang-tidy diagnostics for an open file.
+
+use crate::lsp::protocol::JsonRpcNotification;
+use lsp_types::notification::Notification;
+use lsp_types::{Diagnostic, PublishDiagnosticsParams};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{trace, warn};
+
+// ============================================================================
+// DiagnosticsMonitor
+// ============================================================================
+
+/// Monitor for diagnostics published by clangd
+///
+/// Keeps only the most recent diagnostics set for each document, mirroring the
+/// LSP semantics where every publish replaces the previous one.
+#[derive(Clone, Default)]
+pub struct DiagnosticsMonitor {
+    /// Latest diagnostics keyed by document URI
+    diagnostics: Arc<Mutex<HashMap<String, Vec<Diagnostic>>>>,
+    /// Signalled whenever a new diagnostics set arrives
+    updated: Arc<Notify>,
+}
+
+impl DiagnosticsMonitor {
+    /// Create a new diagnostics monitor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a notification handler that can be registered with LSP client
+    pub fn create_handler(&self) -> impl Fn(JsonRpcNotification) + Send + Sync + 'static {
+        let monitor = self.clone();
+        move |notification| monitor.handle_notification(&notification)
+    }
+
+    /// Record diagnostics from a `textDocument/publishDiagnostics` notification
+    ///
+    /// Other notifications are ignored.
+    pub fn handle_notification(&self, notification: &JsonRpcNotification) {
+        if notification.method != lsp_types::notification::PublishDiagnostics::METHOD {
+            return;
+        }
+
+        let Some(params) = notification.params.clone() else {
+            return;
+        };
+
+        match serde_json::from_value::<PublishDiagnosticsParams>(params) {
+            Ok(published) => {
+                trace!(
+                    "DiagnosticsMonitor: {} diagnostics for {}",
+                    published.diagnostics.len(),
+                    published.uri.as_str()
+                );
+                self.diagnostics
+                    .lock()
+                    .unwrap()
+                    .insert(published.uri.to_string(), published.diagnostics);
+                self.updated.notify_waiters();
+            }
+            Err(e) => warn!(
+                "DiagnosticsMonitor: Invalid publishDiagnostics params: {}",
+                e
+            ),
+        }
+    }
+
+    /// Get the latest diagnostics published for a document
+    pub fn get_diagnostics(&self, uri: &lsp_types::Uri) -> Option<Vec<Diagnostic>> {
+        self.diagnostics.lock().unwrap().get(uri.as_str()).cloned()
+    }
+
+    /// Wait until diagnostics for a document are available
+    ///
+    /// Returns immediately if diagnostics were already published, otherwise waits
+    /// up to `timeout` for clangd to publish them. Returns `None` on timeout.
+    pub async fn wait_for_diagnostics(
+        &self,
+        uri: &lsp_types::Uri,
+        timeout: Duration,
+    ) -> Option<Vec<Diagnostic>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let notified = self.updated.notified();
+            if let Some(diagnostics) = self.get_diagnostics(uri) {
+                return Some(diagnostics);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return None;
+            }
+        }
+    }
+
+    /// Drop cached diagnostics for a document (e.g. after its content changed)
+    pub fn invalidate(&self, uri: &lsp_types::Uri) {
+        self.diagnostics.lock().unwrap().remove(uri.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn publish_notification(uri: &str, codes: &[&str]) -> JsonRpcNotification {
+        let diagnostics: Vec<serde_json::Value> = codes
+            .iter()
+            .map(|code| {
+                serde_json::json!({
+                    "range": {
+                        "start": {"line": 0, "character": 0},
+                        "end": {"line": 0, "character": 1}
+                    },
+                    "severity": 2,
+                    "code": code,
+                    "source": "clang-tidy",
+                    "message": "test"
+                })
+            })
+            .collect();
+
+        JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: "textDocument/publishDiagnostics".to_string(),
+            params: Some(serde_json::json!({
+                "uri": uri,
+                "diagnostics": diagnostics
+            })),
+        }
+    }
+
+    #[test]
+    fn test_publish_replaces_previous_diagnostics() {
+        let monitor = DiagnosticsMonitor::new();
+        let uri = lsp_types::Uri::from_str("file:///test/main.cpp").unwrap();
+
+        monitor.handle_notification(&publish_notification(
+            "file:///test/main.cpp",
+            &["a-check", "b-check"],
+        ));
+        assert_eq!(monitor.get_diagnostics(&uri).unwrap().len(), 2);
+
+        monitor.handle_notification(&publish_notification("file:///test/main.cpp", &[]));
+        assert!(monitor.get_diagnostics(&uri).unwrap().is_empty());
+
+        monitor.invalidate(&uri);
+        assert!(monitor.get_diagnostics(&uri).is_none());
+    }
+
+    #[test]
+    fn test_ignores_other_notifications() {
+        let monitor = DiagnosticsMonitor::new();
+        let mut notification = publish_notification("file:///test/main.cpp", &["a-check"]);
+        notification.method = "$/progress".to_string();
+        monitor.handle_notification(&notification);
+
+        let uri = lsp_types::Uri::from_str("file:///test/main.cpp").unwrap();
+        assert!(monitor.get_diagnostics(&uri).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_diagnostics() {
+        let monitor = DiagnosticsMonitor::new();
+        let uri = lsp_types::Uri::from_str("file:///test/main.cpp").unwrap();
+
+        assert!(
+            monitor
+                .wait_for_diagnostics(&uri, Duration::from_millis(10))
+                .await
+                .is_none()
+        );
+
+        let publisher = monitor.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            publisher
+                .handle_notification(&publish_notification("file:///test/main.cpp", &["a-check"]));
+        });
+
+        let diagnostics = monitor
+            .wait_for_diagnostics(&uri, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+    }
+}
@@ -0,0 +1,361 @@
+//! Discovery and selection of installed clangd binaries
+//!
+//! Distributions install several clangd releases side by side (`clangd-17`,
+//! `clangd-20`) with `clangd` pointing at one of them, often not the newest.
+//! This module finds the `clangd*` executables on `PATH`, detects their
+//! versions and picks one satisfying a version requirement such as `>= 20`.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tracing::debug;
+
+use crate::clangd::version::ClangdVersion;
+
+/// Oldest clangd major release that does not degrade any feature
+///
+/// Older releases work, but lack parts of the index and LSP extensions the
+/// tools use. When the default `clangd` is older, a newer discovered binary is
+/// preferred.
+pub const RECOMMENDED_CLANGD_MAJOR: u32 = 20;
+
+/// Comparison of a [`VersionRequirement`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionOp {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+/// A clangd version requirement, e.g. `>= 20`, `<19` or `18.1`
+///
+/// Missing minor and patch numbers are wildcards for `=` (and a bare version):
+/// `20` matches every 20.x.y release. For the other comparisons they count as
+/// zero, so `> 18` accepts 18.1.0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    pub op: VersionOp,
+    pub major: u32,
+    pub minor: Option<u32>,
+    pub patch: Option<u32>,
+}
+
+impl VersionRequirement {
+    /// Whether `version` satisfies the requirement
+    pub fn matches(&self, version: &ClangdVersion) -> bool {
+        if self.op == VersionOp::Equal {
+            return version.major == self.major
+                && self.minor.is_none_or(|minor| version.minor == minor)
+                && self.patch.is_none_or(|patch| version.patch == patch);
+        }
+
+        let actual = (version.major, version.minor, version.patch);
+        let wanted = (self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0));
+        match self.op {
+            VersionOp::Greater => actual > wanted,
+            VersionOp::GreaterOrEqual => actual >= wanted,
+            VersionOp::Less => actual < wanted,
+            VersionOp::LessOrEqual => actual <= wanted,
+            VersionOp::Equal => unreachable!(),
+        }
+    }
+}
+
+impl FromStr for VersionRequirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let (op, version) = [
+            (">=", VersionOp::GreaterOrEqual),
+            ("<=", VersionOp::LessOrEqual),
+            ("==", VersionOp::Equal),
+            (">", VersionOp::Greater),
+            ("<", VersionOp::Less),
+            ("=", VersionOp::Equal),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| trimmed.strip_prefix(prefix).map(|rest| (op, rest)))
+        .unwrap_or((VersionOp::Equal, trimmed));
+
+        let invalid = || {
+            format!("invalid clangd version requirement '{s}', expected e.g. \">= 20\" or \"18.1\"")
+        };
+        let mut numbers = version.trim().split('.').map(|part| part.parse::<u32>());
+        let major = numbers.next().and_then(Result::ok).ok_or_else(invalid)?;
+        let minor = numbers.next().transpose().map_err(|_| invalid())?;
+        let patch = numbers.next().transpose().map_err(|_| invalid())?;
+        if numbers.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            op,
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.op {
+            VersionOp::Greater => ">",
+            VersionOp::GreaterOrEqual => ">=",
+            VersionOp::Less => "<",
+            VersionOp::LessOrEqual => "<=",
+            VersionOp::Equal => "=",
+        };
+        write!(f, "{op} {}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A clangd executable found on `PATH`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClangdCandidate {
+    pub path: PathBuf,
+    pub version: ClangdVersion,
+}
+
+impl ClangdCandidate {
+    /// Whether this is the plain `clangd` command rather than a versioned one
+    pub fn is_default(&self) -> bool {
+        self.path
+            .file_stem()
+            .is_some_and(|stem| stem == OsStr::new("clangd"))
+    }
+}
+
+/// Find the clangd executables in the directories of a `PATH` value
+///
+/// Executables named `clangd`, `clangd-<version>` or `clangd<version>` are run
+/// with `--version`; those whose output cannot be parsed are skipped. Binaries
+/// reached through several names (`clangd` linking to `clangd-20`) are listed
+/// once, under the name found first in `PATH` order, which is also the order of
+/// the result.
+pub fn discover_clangd_binaries(path_var: &OsStr) -> Vec<ClangdCandidate> {
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+
+    for directory in std::env::split_paths(path_var) {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| is_clangd_name(path) && is_executable(path))
+            .collect();
+        // `clangd` first, so a default shared with a versioned name keeps its name
+        paths.sort_by_key(|path| (path.file_stem() != Some(OsStr::new("clangd")), path.clone()));
+
+        for path in paths {
+            let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !seen.insert(canonical) {
+                continue;
+            }
+            match ClangdVersion::detect(&path) {
+                Ok(version) => candidates.push(ClangdCandidate { path, version }),
+                Err(e) => debug!("Skipping {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Pick the clangd to run from the discovered binaries
+///
+/// Without a requirement the default `clangd` (the first one on `PATH`) is
+/// used unless it is older than [`RECOMMENDED_CLANGD_MAJOR`] and a newer
+/// binary was found; the newest binary is preferred then. With a requirement
+/// the default is used when it satisfies it, otherwise the newest binary that
+/// does. Returns `None` when nothing qualifies.
+pub fn select_clangd<'a>(
+    candidates: &'a [ClangdCandidate],
+    requirement: Option<&VersionRequirement>,
+) -> Option<&'a ClangdCandidate> {
+    let default = candidates.iter().find(|candidate| candidate.is_default());
+    let newest = |accept: &dyn Fn(&ClangdCandidate) -> bool| {
+        candidates
+            .iter()
+            .filter(|candidate| accept(candidate))
+            .max_by_key(|candidate| version_key(&candidate.version))
+    };
+
+    match requirement {
+        Some(requirement) => default
+            .filter(|candidate| requirement.matches(&candidate.version))
+            .or_else(|| newest(&|candidate| requirement.matches(&candidate.version))),
+        None => match default {
+            Some(default) if default.version.major < RECOMMENDED_CLANGD_MAJOR => newest(&|_| true)
+                .filter(|newest| version_key(&newest.version) > version_key(&default.version))
+                .or(Some(default)),
+            Some(default) => Some(default),
+            None => newest(&|_| true),
+        },
+    }
+}
+
+fn version_key(version: &ClangdVersion) -> (u32, u32, u32) {
+    (version.major, version.minor, version.patch)
+}
+
+/// Whether a file name looks like a clangd binary: `clangd`, `clangd-20`, `clangd20.1`
+fn is_clangd_name(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(OsStr::to_str) else {
+        return false;
+    };
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    let Some(suffix) = name.strip_prefix("clangd") else {
+        return false;
+    };
+    let suffix = suffix.strip_prefix('-').unwrap_or(suffix);
+    suffix.is_empty()
+        || (suffix.starts_with(|c: char| c.is_ascii_digit())
+            && suffix.chars().all(|c| c.is_ascii_digit() || c == '.'))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(path: &str, major: u32, minor: u32) -> ClangdCandidate {
+        ClangdCandidate {
+            path: PathBuf::from(path),
+            version: ClangdVersion {
+                major,
+                minor,
+                patch: 0,
+                variant: None,
+                date: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_version_requirement() {
+        let requirement: VersionRequirement = ">= 20".parse().unwrap();
+        assert_eq!(requirement.op, VersionOp::GreaterOrEqual);
+        assert_eq!((requirement.major, requirement.minor), (20, None));
+        assert_eq!(requirement.to_string(), ">= 20");
+
+        let version = |major, minor| candidate("clangd", major, minor).version;
+        assert!(requirement.matches(&version(20, 0)));
+        assert!(!requirement.matches(&version(19, 1)));
+
+        let exact: VersionRequirement = "18.1".parse().unwrap();
+        assert!(exact.matches(&version(18, 1)));
+        assert!(!exact.matches(&version(18, 0)));
+        assert!(
+            ">18"
+                .parse::<VersionRequirement>()
+                .unwrap()
+                .matches(&version(18, 1))
+        );
+        assert!(
+            "<19"
+                .parse::<VersionRequirement>()
+                .unwrap()
+                .matches(&version(18, 1))
+        );
+
+        for invalid in ["", ">=", "twenty", "20.x", "1.2.3.4", "~20"] {
+            assert!(invalid.parse::<VersionRequirement>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_select_clangd() {
+        let candidates = vec![
+            candidate("/usr/bin/clangd", 17, 0),
+            candidate("/usr/bin/clangd-17", 17, 0),
+            candidate("/usr/bin/clangd-20", 20, 1),
+            candidate("/usr/bin/clangd-19", 19, 1),
+        ];
+
+        // The old default is replaced by the newest binary
+        let selected = select_clangd(&candidates, None).unwrap();
+        assert_eq!(selected.path, PathBuf::from("/usr/bin/clangd-20"));
+
+        // A default satisfying the requirement is kept
+        let requirement: VersionRequirement = "<= 19".parse().unwrap();
+        let selected = select_clangd(&candidates, Some(&requirement)).unwrap();
+        assert_eq!(selected.path, PathBuf::from("/usr/bin/clangd"));
+
+        let requirement: VersionRequirement = "19".parse().unwrap();
+        let selected = select_clangd(&candidates, Some(&requirement)).unwrap();
+        assert_eq!(selected.path, PathBuf::from("/usr/bin/clangd-19"));
+
+        let requirement: VersionRequirement = ">= 21".parse().unwrap();
+        assert!(select_clangd(&candidates, Some(&requirement)).is_none());
+
+        // A recent default is kept even when a newer binary exists
+        let candidates = vec![
+            candidate("/usr/bin/clangd", 20, 0),
+            candidate("/usr/bin/clangd-21", 21, 0),
+        ];
+        let selected = select_clangd(&candidates, None).unwrap();
+        assert_eq!(selected.path, PathBuf::from("/usr/bin/clangd"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_clangd_binaries() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+
+        let write_binary = |path: &Path, output: &str| {
+            std::fs::write(path, format!("#!/bin/sh\necho '{output}'\n")).unwrap();
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        write_binary(&first.join("clangd-20"), "clangd version 20.1.8");
+        std::os::unix::fs::symlink(first.join("clangd-20"), first.join("clangd")).unwrap();
+        write_binary(&second.join("clangd-17"), "Ubuntu clangd version 17.0.6");
+        write_binary(&second.join("clangd-indexer"), "clangd-indexer");
+        write_binary(&second.join("clangd-broken"), "no version here");
+        std::fs::write(second.join("clangd-18"), "not executable").unwrap();
+
+        let path_var = std::env::join_paths([&first, &second]).unwrap();
+        let candidates = discover_clangd_binaries(&path_var);
+
+        let found: Vec<_> = candidates
+            .iter()
+            .map(|candidate| (candidate.path.clone(), candidate.version.major))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(first.join("clangd"), 20), (second.join("clangd-17"), 17)]
+        );
+        assert!(candidates[0].is_default());
+    }
+}
@@ -1,12 +1,16 @@
 //! File management for clangd sessions
 //!
 //! Tracks open files, detects changes, and manages file lifecycle through LSP notifications.
+//!
+//! Every open document carries its own LSP version: it starts at 1 with `didOpen`
+//! and grows by one with each `didChange`, so clangd never drops an update as stale.
 
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::io::file_buffer::read_source_file;
 use crate::lsp::traits::LspClientTrait;
 
 // ============================================================================
@@ -41,19 +45,22 @@ struct FileEntry {
 
     /// LSP document version number
     version: i32,
+
+    /// Whether clangd holds edited content that is not on disk
+    modified_in_memory: bool,
 }
 
 // ============================================================================
 // Clangd File Manager
 // ============================================================================
 
+/// Version of a document when it is opened
+const INITIAL_DOCUMENT_VERSION: i32 = 1;
+
 /// Manages open files in a clangd session
 pub struct ClangdFileManager {
     /// Map of open files by their absolute path
     opened_files: HashMap<PathBuf, FileEntry>,
-
-    /// Counter for document versions
-    next_version: i32,
 }
 
 impl ClangdFileManager {
@@ -61,7 +68,6 @@ impl ClangdFileManager {
     pub fn new() -> Self {
         Self {
             opened_files: HashMap::new(),
-            next_version: 1,
         }
     }
 
@@ -71,11 +77,14 @@ impl ClangdFileManager {
     /// - Open the file if not already open
     /// - Send a change notification if the file content has changed
     /// - Do nothing if the file is already open and unchanged
+    ///
+    /// Returns whether an open or change notification was sent, i.e. whether
+    /// clangd has to parse the file again.
     pub async fn ensure_file_ready(
         &mut self,
         path: &Path,
         client: &mut impl LspClientTrait,
-    ) -> Result<(), FileManagerError> {
+    ) -> Result<bool, FileManagerError> {
         // Check if client is ready for operations
         if !client.is_initialized() {
             return Err(FileManagerError::LspError(
@@ -89,21 +98,18 @@ impl ClangdFileManager {
             .map_err(|e| FileManagerError::InvalidPath(format!("{}: {}", path.display(), e)))?;
 
         // Read current file content
-        let content = std::fs::read_to_string(&abs_path)?;
+        let content = read_source_file(&abs_path)?;
         let content_hash = Self::compute_hash(&content);
 
         // Generate file URI
-        let uri_string = format!("file://{}", abs_path.display());
-        let uri: lsp_types::Uri = uri_string
-            .parse()
-            .map_err(|e| FileManagerError::InvalidPath(format!("Invalid URI: {}", e)))?;
+        let uri = crate::symbol::uri_from_pathbuf(&abs_path);
 
         // Check if file is already open
         if let Some(entry) = self.opened_files.get(&abs_path) {
             if entry.content_hash == content_hash {
                 // File is open and unchanged
                 debug!("File {} is already open and unchanged", abs_path.display());
-                return Ok(());
+                return Ok(false);
             }
 
             // File has changed, send change notification
@@ -112,8 +118,7 @@ impl ClangdFileManager {
                 abs_path.display()
             );
 
-            let new_version = self.next_version;
-            self.next_version += 1;
+            let new_version = entry.version + 1;
 
             client
                 .change_text_document(uri.clone(), new_version, content)
@@ -123,37 +128,120 @@ impl ClangdFileManager {
             self.opened_files.insert(
                 abs_path,
                 FileEntry {
-                    uri: uri_string.clone(),
+                    uri: uri.to_string(),
                     content_hash,
                     version: new_version,
+                    modified_in_memory: false,
                 },
             );
         } else {
             // File is not open, send open notification
             info!("Opening file {}", abs_path.display());
 
-            let version = self.next_version;
-            self.next_version += 1;
+            let version = INITIAL_DOCUMENT_VERSION;
 
             // Determine language ID based on file extension
             let language_id = Self::get_language_id(&abs_path);
 
             client
-                .open_text_document(uri, language_id.to_string(), version, content)
+                .open_text_document(uri.clone(), language_id.to_string(), version, content)
                 .await?;
 
             // Track the opened file
             self.opened_files.insert(
                 abs_path,
                 FileEntry {
-                    uri: uri_string.clone(),
+                    uri: uri.to_string(),
                     content_hash,
                     version,
+                    modified_in_memory: false,
                 },
             );
         }
 
-        Ok(())
+        Ok(true)
+    }
+
+    /// Apply ranged edits to a file in the LSP server without touching it on disk
+    ///
+    /// Opens the file first if needed. The edited content stays in clangd until
+    /// `restore_file()` is called or the file changes on disk; `ensure_file_ready()`
+    /// leaves it in place as long as the on-disk content is unchanged.
+    ///
+    /// Returns the document version of the edited content.
+    pub async fn apply_changes(
+        &mut self,
+        path: &Path,
+        changes: Vec<lsp_types::TextDocumentContentChangeEvent>,
+        client: &mut impl LspClientTrait,
+    ) -> Result<i32, FileManagerError> {
+        self.ensure_file_ready(path, client).await?;
+
+        let abs_path = path
+            .canonicalize()
+            .map_err(|e| FileManagerError::InvalidPath(format!("{}: {}", path.display(), e)))?;
+        let uri = crate::symbol::uri_from_pathbuf(&abs_path);
+
+        let version = self.document_version(&abs_path).unwrap_or_default() + 1;
+
+        info!(
+            "Applying {} in-memory changes to {} (version {})",
+            changes.len(),
+            abs_path.display(),
+            version
+        );
+        client
+            .change_text_document_incremental(uri, version, changes)
+            .await?;
+
+        if let Some(entry) = self.opened_files.get_mut(&abs_path) {
+            entry.version = version;
+            entry.modified_in_memory = true;
+        }
+        Ok(version)
+    }
+
+    /// Replace in-memory edits of a file with its on-disk content
+    ///
+    /// Returns whether the file had in-memory edits to discard.
+    pub async fn restore_file(
+        &mut self,
+        path: &Path,
+        client: &mut impl LspClientTrait,
+    ) -> Result<bool, FileManagerError> {
+        let abs_path = path
+            .canonicalize()
+            .map_err(|e| FileManagerError::InvalidPath(format!("{}: {}", path.display(), e)))?;
+
+        let Some(entry) = self
+            .opened_files
+            .get(&abs_path)
+            .filter(|entry| entry.modified_in_memory)
+        else {
+            return Ok(false);
+        };
+        let uri: lsp_types::Uri = entry
+            .uri
+            .parse()
+            .map_err(|e| FileManagerError::InvalidPath(format!("Invalid URI: {}", e)))?;
+
+        let content = read_source_file(&abs_path)?;
+        let content_hash = Self::compute_hash(&content);
+        let version = entry.version + 1;
+
+        info!(
+            "Restoring on-disk content of {} (version {})",
+            abs_path.display(),
+            version
+        );
+        client.change_text_document(uri, version, content).await?;
+
+        if let Some(entry) = self.opened_files.get_mut(&abs_path) {
+            entry.content_hash = content_hash;
+            entry.version = version;
+            entry.modified_in_memory = false;
+        }
+        Ok(true)
     }
 
     /// Close a file in the LSP server
@@ -190,6 +278,17 @@ impl ClangdFileManager {
         }
     }
 
+    /// Get the LSP version of an open document, `None` when it is not open
+    pub fn document_version(&self, path: &Path) -> Option<i32> {
+        let abs_path = path.canonicalize().ok()?;
+        self.opened_files.get(&abs_path).map(|entry| entry.version)
+    }
+
+    /// Get the paths of all currently open files
+    pub fn open_files(&self) -> Vec<PathBuf> {
+        self.opened_files.keys().cloned().collect()
+    }
+
     /// Get the number of currently open files
     pub fn get_open_files_count(&self) -> usize {
         self.opened_files.len()
@@ -228,6 +327,8 @@ impl ClangdFileManager {
             Some("c") => "c",
             Some("cpp") | Some("cc") | Some("cxx") | Some("c++") => "cpp",
             Some("h") | Some("hpp") | Some("hh") | Some("hxx") | Some("h++") => "cpp",
+            Some("m") => "objective-c",
+            Some("mm") => "objective-cpp",
             _ => "cpp", // Default to C++ for clangd
         }
     }
@@ -291,6 +392,14 @@ mod tests {
             ClangdFileManager::get_language_id(&PathBuf::from("test.hpp")),
             "cpp"
         );
+        assert_eq!(
+            ClangdFileManager::get_language_id(&PathBuf::from("test.m")),
+            "objective-c"
+        );
+        assert_eq!(
+            ClangdFileManager::get_language_id(&PathBuf::from("test.mm")),
+            "objective-cpp"
+        );
         assert_eq!(
             ClangdFileManager::get_language_id(&PathBuf::from("test.txt")),
             "cpp"
@@ -0,0 +1,430 @@
+//! Per-file readiness tracking for clangd sessions
+//!
+//! clangd reports the state of each open document through the
+//! `textDocument/clangd.fileStatus` extension notification (enabled with the
+//! `clangdFileStatus` initialization option). A document is parsed once clangd
+//! goes back to the `idle` state after building its AST. This is independent
+//! of background indexing: a file can be queried for hover, document symbols or
+//! definitions long before the whole project is indexed.
+//!
+//! Diagnostics are published after every AST build, so a
+//! `textDocument/publishDiagnostics` notification is also treated as a parse
+//! completion. This keeps the barrier working with servers that do not send
+//! file status notifications.
+//!
+//! clangd 17 and later also publish the preprocessor branches skipped in each
+//! parse through the `textDocument/inactiveRegions` extension notification
+//! (enabled with the `inactiveRegionsCapabilities` client capability). The
+//! latest regions of every document are kept alongside its parse state.
+//!
+//! Before its first parse clangd builds the preamble of a document: the
+//! precompiled form of its leading `#include`s, reported as the "parsing
+//! includes" state. For files including large headers this is where the first
+//! query spends its time, so preamble builds and their duration are tracked
+//! too.
+
+use crate::lsp::protocol::JsonRpcNotification;
+use lsp_types::notification::Notification;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tracing::{trace, warn};
+
+/// clangd extension notification reporting the state of a document
+pub const FILE_STATUS_METHOD: &str = "textDocument/clangd.fileStatus";
+
+/// clangd extension notification listing the inactive preprocessor regions of a document
+pub const INACTIVE_REGIONS_METHOD: &str = "textDocument/inactiveRegions";
+
+/// File status state reported by clangd once a document has no pending work
+const IDLE_STATE: &str = "idle";
+
+/// Parts of a file status state reported while the preamble is built
+const PREAMBLE_STATES: &[&str] = &["parsing includes", "building preamble"];
+
+/// Parameters of the `textDocument/clangd.fileStatus` notification
+#[derive(Debug, Deserialize)]
+struct FileStatusParams {
+    uri: String,
+    state: String,
+}
+
+/// Parameters of the `textDocument/inactiveRegions` notification
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InactiveRegionsParams {
+    text_document: lsp_types::TextDocumentIdentifier,
+    regions: Vec<lsp_types::Range>,
+}
+
+/// Readiness of a single document
+#[derive(Debug, Clone, Default)]
+struct FileStatus {
+    /// Latest state reported by clangd (e.g. "parsing includes", "idle")
+    state: Option<String>,
+    /// Number of completed parses observed since the monitor was created
+    parse_count: u64,
+    /// Inactive preprocessor regions of the latest parse
+    inactive_regions: Option<Vec<lsp_types::Range>>,
+    /// Start of the preamble build in progress
+    preamble_started: Option<Instant>,
+    /// Duration of the latest completed preamble build
+    last_preamble_build: Option<Duration>,
+    /// Whether a preamble build was seen completing
+    preamble_built: bool,
+}
+
+/// Preamble (precompiled `#include`s) state of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PreambleStatus {
+    /// Whether clangd is building the preamble right now
+    pub building: bool,
+    /// Whether the preamble is built; a completed parse implies it is
+    pub ready: bool,
+    /// Duration of the latest preamble build observed
+    pub last_build: Option<Duration>,
+}
+
+// ============================================================================
+// FileStatusMonitor
+// ============================================================================
+
+/// Monitor for per-file parse completion reported by clangd
+#[derive(Clone, Default)]
+pub struct FileStatusMonitor {
+    /// Readiness keyed by document URI
+    files: Arc<Mutex<HashMap<String, FileStatus>>>,
+    /// Signalled whenever a document finishes parsing
+    parsed: Arc<Notify>,
+}
+
+impl FileStatusMonitor {
+    /// Create a new file status monitor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a notification handler that can be registered with LSP client
+    pub fn create_handler(&self) -> impl Fn(JsonRpcNotification) + Send + Sync + 'static {
+        let monitor = self.clone();
+        move |notification| monitor.handle_notification(&notification)
+    }
+
+    /// Record file status, inactive region and diagnostics notifications
+    ///
+    /// Other notifications are ignored.
+    pub fn handle_notification(&self, notification: &JsonRpcNotification) {
+        let Some(params) = notification.params.clone() else {
+            return;
+        };
+
+        if notification.method == FILE_STATUS_METHOD {
+            match serde_json::from_value::<FileStatusParams>(params) {
+                Ok(status) => {
+                    trace!("FileStatusMonitor: {} is {}", status.uri, status.state);
+                    let parsed = status.state == IDLE_STATE;
+                    let building_preamble = PREAMBLE_STATES
+                        .iter()
+                        .any(|preamble| status.state.contains(preamble));
+                    self.record_preamble(&status.uri, building_preamble);
+                    self.record(status.uri, Some(status.state), parsed);
+                }
+                Err(e) => warn!("FileStatusMonitor: Invalid fileStatus params: {}", e),
+            }
+        } else if notification.method == INACTIVE_REGIONS_METHOD {
+            match serde_json::from_value::<InactiveRegionsParams>(params) {
+                Ok(inactive) => {
+                    let uri = inactive.text_document.uri.to_string();
+                    trace!(
+                        "FileStatusMonitor: {} inactive regions in {}",
+                        inactive.regions.len(),
+                        uri
+                    );
+                    self.files
+                        .lock()
+                        .unwrap()
+                        .entry(uri)
+                        .or_default()
+                        .inactive_regions = Some(inactive.regions);
+                    self.parsed.notify_waiters();
+                }
+                Err(e) => warn!("FileStatusMonitor: Invalid inactiveRegions params: {}", e),
+            }
+        } else if notification.method == lsp_types::notification::PublishDiagnostics::METHOD
+            && let Some(uri) = params.get("uri").and_then(|uri| uri.as_str())
+        {
+            self.record(uri.to_string(), None, true);
+        }
+    }
+
+    /// Number of completed parses of a document observed so far
+    pub fn parse_count(&self, uri: &lsp_types::Uri) -> u64 {
+        self.files
+            .lock()
+            .unwrap()
+            .get(uri.as_str())
+            .map_or(0, |status| status.parse_count)
+    }
+
+    /// Latest state reported by clangd for a document
+    pub fn state(&self, uri: &lsp_types::Uri) -> Option<String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(uri.as_str())
+            .and_then(|status| status.state.clone())
+    }
+
+    /// Preamble state of a document
+    pub fn preamble_status(&self, uri: &lsp_types::Uri) -> PreambleStatus {
+        self.files.lock().unwrap().get(uri.as_str()).map_or_else(
+            PreambleStatus::default,
+            |status| {
+                let building = status.preamble_started.is_some();
+                PreambleStatus {
+                    building,
+                    ready: !building && (status.preamble_built || status.parse_count > 0),
+                    last_build: status.last_preamble_build,
+                }
+            },
+        )
+    }
+
+    /// Inactive preprocessor regions clangd reported for the latest parse of a document
+    ///
+    /// `None` until clangd reports them, which servers older than clangd 17
+    /// never do.
+    pub fn inactive_regions(&self, uri: &lsp_types::Uri) -> Option<Vec<lsp_types::Range>> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(uri.as_str())
+            .and_then(|status| status.inactive_regions.clone())
+    }
+
+    /// Wait until inactive regions of a document are available
+    ///
+    /// Returns `None` on timeout.
+    pub async fn wait_for_inactive_regions(
+        &self,
+        uri: &lsp_types::Uri,
+        timeout: Duration,
+    ) -> Option<Vec<lsp_types::Range>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let notified = self.parsed.notified();
+            if let Some(regions) = self.inactive_regions(uri) {
+                return Some(regions);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return None;
+            }
+        }
+    }
+
+    /// Wait until a document completed a parse after the `after`-th one
+    ///
+    /// Pass the `parse_count()` read before sending `didOpen`/`didChange` to wait
+    /// for the parse of that content, or 0 to accept any completed parse.
+    /// Returns `false` on timeout.
+    pub async fn wait_until_parsed(
+        &self,
+        uri: &lsp_types::Uri,
+        after: u64,
+        timeout: Duration,
+    ) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let notified = self.parsed.notified();
+            if self.parse_count(uri) > after {
+                return true;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return false;
+            }
+        }
+    }
+
+    /// Track the start and end of preamble builds
+    fn record_preamble(&self, uri: &str, building: bool) {
+        let mut files = self.files.lock().unwrap();
+        let status = files.entry(uri.to_string()).or_default();
+        match (building, status.preamble_started) {
+            (true, None) => status.preamble_started = Some(Instant::now()),
+            (false, Some(started)) => {
+                status.preamble_started = None;
+                status.preamble_built = true;
+                status.last_preamble_build = Some(started.elapsed());
+            }
+            _ => {}
+        }
+    }
+
+    fn record(&self, uri: String, state: Option<String>, parsed: bool) {
+        {
+            let mut files = self.files.lock().unwrap();
+            let status = files.entry(uri).or_default();
+            if state.is_some() {
+                status.state = state;
+            }
+            if parsed {
+                status.parse_count += 1;
+            }
+        }
+        if parsed {
+            self.parsed.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const URI: &str = "file:///test/main.cpp";
+
+    fn notification(method: &str, params: serde_json::Value) -> JsonRpcNotification {
+        JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: Some(params),
+        }
+    }
+
+    fn file_status(state: &str) -> JsonRpcNotification {
+        notification(
+            FILE_STATUS_METHOD,
+            serde_json::json!({"uri": URI, "state": state}),
+        )
+    }
+
+    #[test]
+    fn test_idle_state_completes_parse() {
+        let monitor = FileStatusMonitor::new();
+        let uri = lsp_types::Uri::from_str(URI).unwrap();
+
+        monitor.handle_notification(&file_status("parsing includes, running Update"));
+        monitor.handle_notification(&file_status("building AST"));
+        assert_eq!(monitor.parse_count(&uri), 0);
+        assert_eq!(monitor.state(&uri).as_deref(), Some("building AST"));
+
+        monitor.handle_notification(&file_status("idle"));
+        assert_eq!(monitor.parse_count(&uri), 1);
+
+        // Diagnostics also mark a completed parse without changing the state
+        monitor.handle_notification(&notification(
+            "textDocument/publishDiagnostics",
+            serde_json::json!({"uri": URI, "diagnostics": []}),
+        ));
+        assert_eq!(monitor.parse_count(&uri), 2);
+        assert_eq!(monitor.state(&uri).as_deref(), Some("idle"));
+    }
+
+    #[test]
+    fn test_preamble_status() {
+        let monitor = FileStatusMonitor::new();
+        let uri = lsp_types::Uri::from_str(URI).unwrap();
+        assert_eq!(monitor.preamble_status(&uri), PreambleStatus::default());
+
+        monitor.handle_notification(&file_status("parsing includes, file is queued"));
+        let status = monitor.preamble_status(&uri);
+        assert!(status.building);
+        assert!(!status.ready);
+
+        monitor.handle_notification(&file_status("parsing includes, running Update"));
+        monitor.handle_notification(&file_status("parsing main file"));
+        let status = monitor.preamble_status(&uri);
+        assert!(!status.building);
+        assert!(status.ready);
+        assert!(status.last_build.is_some());
+
+        // Servers without file status: a completed parse needed a preamble
+        let other = lsp_types::Uri::from_str("file:///test/other.cpp").unwrap();
+        monitor.handle_notification(&notification(
+            "textDocument/publishDiagnostics",
+            serde_json::json!({"uri": other.as_str(), "diagnostics": []}),
+        ));
+        let status = monitor.preamble_status(&other);
+        assert!(status.ready);
+        assert_eq!(status.last_build, None);
+    }
+
+    #[tokio::test]
+    async fn test_inactive_regions_replaced_by_latest_parse() {
+        let monitor = FileStatusMonitor::new();
+        let uri = lsp_types::Uri::from_str(URI).unwrap();
+        let regions = |lines: &[u32]| {
+            let regions: Vec<serde_json::Value> = lines
+                .iter()
+                .map(|line| {
+                    serde_json::json!({
+                        "start": {"line": line, "character": 0},
+                        "end": {"line": line + 1, "character": 0}
+                    })
+                })
+                .collect();
+            notification(
+                INACTIVE_REGIONS_METHOD,
+                serde_json::json!({"textDocument": {"uri": URI}, "regions": regions}),
+            )
+        };
+
+        assert!(
+            monitor
+                .wait_for_inactive_regions(&uri, Duration::from_millis(10))
+                .await
+                .is_none()
+        );
+
+        monitor.handle_notification(&regions(&[3, 10]));
+        assert_eq!(monitor.inactive_regions(&uri).unwrap().len(), 2);
+
+        monitor.handle_notification(&regions(&[]));
+        let latest = monitor
+            .wait_for_inactive_regions(&uri, Duration::from_millis(10))
+            .await
+            .unwrap();
+        assert!(latest.is_empty());
+        // Inactive regions do not count as a parse
+        assert_eq!(monitor.parse_count(&uri), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_parsed_after_change() {
+        let monitor = FileStatusMonitor::new();
+        let uri = lsp_types::Uri::from_str(URI).unwrap();
+        monitor.handle_notification(&file_status("idle"));
+
+        // Already parsed once
+        assert!(
+            monitor
+                .wait_until_parsed(&uri, 0, Duration::from_millis(10))
+                .await
+        );
+        // A reparse has not happened yet
+        let before = monitor.parse_count(&uri);
+        assert!(
+            !monitor
+                .wait_until_parsed(&uri, before, Duration::from_millis(10))
+                .await
+        );
+
+        let publisher = monitor.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            publisher.handle_notification(&file_status("building AST"));
+            publisher.handle_notification(&file_status("idle"));
+        });
+
+        assert!(
+            monitor
+                .wait_until_parsed(&uri, before, Duration::from_secs(5))
+                .await
+        );
+    }
+}
@@ -98,16 +98,7 @@ impl ComponentIndex {
         compilation_db: &CompilationDatabase,
         clangd_version: &ClangdVersion,
     ) -> Result<Self, ComponentIndexError> {
-        let compilation_db_path = compilation_db.path();
-        let compilation_db_dir = compilation_db_path
-            .parent()
-            .unwrap_or_else(|| Path::new("."));
-
-        // Index directory is .cache/clangd/index/ relative to compilation database
-        let index_dir = compilation_db_dir
-            .join(".cache")
-            .join("clangd")
-            .join("index");
+        let index_dir = Self::index_dir_for(compilation_db);
 
         let format_version = clangd_version.index_format_version();
         let mut file_to_index = HashMap::new();
@@ -155,6 +146,31 @@ impl ComponentIndex {
         })
     }
 
+    /// Create a ComponentIndex that tracks no files yet
+    ///
+    /// Stands in for the real index while the model of a large compilation
+    /// database is built in the background.
+    pub fn empty(compilation_db: &CompilationDatabase, clangd_version: &ClangdVersion) -> Self {
+        ComponentIndex {
+            index_dir: Self::index_dir_for(compilation_db),
+            file_to_index: HashMap::new(),
+            file_states: HashMap::new(),
+            cdb_files: HashSet::new(),
+            format_version: clangd_version.index_format_version(),
+        }
+    }
+
+    /// Index directory is .cache/clangd/index/ relative to compilation database
+    fn index_dir_for(compilation_db: &CompilationDatabase) -> PathBuf {
+        compilation_db
+            .path()
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(".cache")
+            .join("clangd")
+            .join("index")
+    }
+
     /// Create a new ComponentIndex for testing without filesystem dependencies
     #[cfg(test)]
     pub fn new_for_test(
@@ -107,6 +107,16 @@ impl IdxParser {
         parser.extract_index_data()
     }
 
+    /// Read only the format version of an index file
+    ///
+    /// Versions this parser cannot decode are still reported, as
+    /// `IdxParseError::UnsupportedVersion`.
+    pub fn parse_format_version(data: &[u8]) -> Result<u32, IdxParseError> {
+        let mut parser = Self::new();
+        parser.parse_riff_container(data)?;
+        parser.parse_meta_chunk()
+    }
+
     /// Create a new parser instance
     fn new() -> Self {
         Self {
@@ -2,11 +2,15 @@
 //!
 //! Monitors clangd's stderr output for indexing progress messages and emits
 //! structured progress events. This complements the LSP progress notifications
-//! with more detailed file-level progress information.
+//! with more detailed file-level progress information. The most recent lines
+//! are also kept so that clangd's complaints can be reported to clients.
 
 use crate::clangd::index::ProgressEvent;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
@@ -163,10 +167,75 @@ impl LogParser for ClangdLogParser {
     }
 }
 
+/// Number of most recent stderr lines kept by `LogMonitor`
+pub const LOG_TAIL_CAPACITY: usize = 2000;
+
+/// Severity of a clangd log line, from its `E[..]`, `I[..]` style prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSeverity {
+    Error,
+    Warning,
+    Info,
+    Verbose,
+    Debug,
+}
+
+impl LogSeverity {
+    /// Severity of a log line, `None` for lines without a severity prefix
+    /// (e.g. continuation lines of multi-line messages)
+    pub fn of_line(line: &str) -> Option<Self> {
+        let severity = match line.as_bytes() {
+            [b'E', b'[', ..] => Self::Error,
+            [b'W', b'[', ..] => Self::Warning,
+            [b'I', b'[', ..] => Self::Info,
+            [b'V', b'[', ..] => Self::Verbose,
+            [b'D', b'[', ..] => Self::Debug,
+            _ => return None,
+        };
+        Some(severity)
+    }
+}
+
+impl std::str::FromStr for LogSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" | "e" => Ok(Self::Error),
+            "warning" | "w" => Ok(Self::Warning),
+            "info" | "i" => Ok(Self::Info),
+            "verbose" | "v" => Ok(Self::Verbose),
+            "debug" | "d" => Ok(Self::Debug),
+            _ => Err(format!(
+                "Unknown log severity '{}' (expected error, warning, info, verbose or debug)",
+                s
+            )),
+        }
+    }
+}
+
+/// Bounded buffer of the most recent clangd stderr lines
+#[derive(Debug, Clone, Default)]
+struct LogTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl LogTail {
+    fn push(&self, line: &str) {
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == LOG_TAIL_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+}
+
 /// Log monitor that processes clangd stderr output
 pub struct LogMonitor {
     parser: ClangdLogParser,
     event_sender: Option<mpsc::Sender<ProgressEvent>>,
+    tail: LogTail,
 }
 
 impl LogMonitor {
@@ -175,6 +244,7 @@ impl LogMonitor {
         Self {
             parser: ClangdLogParser::default(),
             event_sender: None,
+            tail: LogTail::default(),
         }
     }
 
@@ -183,6 +253,7 @@ impl LogMonitor {
         Self {
             parser: ClangdLogParser::default(),
             event_sender: Some(sender),
+            tail: LogTail::default(),
         }
     }
 
@@ -194,12 +265,14 @@ impl LogMonitor {
         Self {
             parser,
             event_sender: Some(sender),
+            tail: LogTail::default(),
         }
     }
 
     /// Process a single log line
     pub fn process_line(&self, line: &str) {
         trace!("LogMonitor: Processing stderr line: {}", line);
+        self.tail.push(line);
 
         if let Some(event) = self.parser.parse_line(line)
             && let Some(ref sender) = self.event_sender
@@ -216,8 +289,10 @@ impl LogMonitor {
         // Clone the existing parser instead of creating a duplicate
         let parser = self.parser.clone();
         let sender = self.event_sender.clone();
+        let tail = self.tail.clone();
 
         move |line: String| {
+            tail.push(&line);
             if let Some(event) = parser.parse_line(&line) {
                 trace!("LogMonitor: Parsed event from stderr: {:?}", event);
 
@@ -231,6 +306,34 @@ impl LogMonitor {
         }
     }
 
+    /// The last `limit` stderr lines, oldest first
+    ///
+    /// With `min_severity`, only lines at least that severe are returned;
+    /// lines without a severity prefix belong to the preceding line and are
+    /// kept or dropped with it.
+    pub fn recent_lines(&self, limit: usize, min_severity: Option<LogSeverity>) -> Vec<String> {
+        let lines = self.tail.lines.lock().unwrap();
+        let mut included = true;
+        let mut selected: Vec<String> = lines
+            .iter()
+            .filter(|line| {
+                if let Some(severity) = LogSeverity::of_line(line) {
+                    included = min_severity.is_none_or(|min| severity <= min);
+                }
+                included
+            })
+            .cloned()
+            .collect();
+        let skip = selected.len().saturating_sub(limit);
+        selected.drain(..skip);
+        selected
+    }
+
+    /// Number of stderr lines currently kept
+    pub fn buffered_lines(&self) -> usize {
+        self.tail.lines.lock().unwrap().len()
+    }
+
     /// Process stderr stream asynchronously
     pub async fn monitor_stream<R>(&self, reader: R) -> Result<(), std::io::Error>
     where
@@ -541,4 +644,43 @@ mod tests {
         let line4 = "V[14:23:45.123] Indexing incomplete line";
         assert!(parser.parse_line(line4).is_none());
     }
+
+    #[test]
+    fn test_recent_lines_tail_and_severity_filter() {
+        let monitor = LogMonitor::new();
+        monitor.process_line("I[10:00:00.000] clangd version 18.1.3");
+        monitor.process_line("E[10:00:01.000] Failed to find compilation database for /src/a.cpp");
+        monitor.process_line("  continuation of the error");
+        monitor.process_line("V[10:00:02.000] ASTWorker building file /src/a.cpp");
+        let processor = monitor.create_stderr_processor();
+        processor("I[10:00:03.000] Indexed /src/a.cpp (3 symbols, 1 refs, 1 files)".to_string());
+
+        assert_eq!(monitor.buffered_lines(), 5);
+        assert_eq!(
+            monitor.recent_lines(2, None),
+            vec![
+                "V[10:00:02.000] ASTWorker building file /src/a.cpp",
+                "I[10:00:03.000] Indexed /src/a.cpp (3 symbols, 1 refs, 1 files)",
+            ]
+        );
+        assert_eq!(
+            monitor.recent_lines(10, Some(LogSeverity::Error)),
+            vec![
+                "E[10:00:01.000] Failed to find compilation database for /src/a.cpp",
+                "  continuation of the error",
+            ]
+        );
+        assert_eq!(monitor.recent_lines(10, Some(LogSeverity::Info)).len(), 4);
+        assert_eq!("Warning".parse(), Ok(LogSeverity::Warning));
+        assert!("fatal".parse::<LogSeverity>().is_err());
+
+        for i in 0..LOG_TAIL_CAPACITY {
+            monitor.process_line(&format!("D[10:00:04.000] line {}", i));
+        }
+        assert_eq!(monitor.buffered_lines(), LOG_TAIL_CAPACITY);
+        assert_eq!(
+            monitor.recent_lines(1, Some(LogSeverity::Verbose)),
+            Vec::<String>::new()
+        );
+    }
 }
@@ -34,8 +34,11 @@
 //! ```
 
 pub mod config;
+pub mod diagnostics;
+pub mod discovery;
 pub mod error;
 pub mod file_manager;
+pub mod file_status;
 pub mod index;
 pub mod log_monitor;
 pub mod session;
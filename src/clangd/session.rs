@@ -9,7 +9,9 @@ use std::time::Instant;
 use tracing::{debug, info, warn};
 
 use crate::clangd::config::ClangdConfig;
+use crate::clangd::diagnostics::DiagnosticsMonitor;
 use crate::clangd::error::ClangdSessionError;
+use crate::clangd::file_status::FileStatusMonitor;
 use crate::clangd::index::IndexProgressMonitor;
 use crate::clangd::log_monitor::LogMonitor;
 use crate::clangd::session_builder::ClangdSessionBuilder;
@@ -87,6 +89,12 @@ where
     /// Log monitor for stderr parsing
     log_monitor: LogMonitor,
 
+    /// Diagnostics published by clangd
+    diagnostics_monitor: DiagnosticsMonitor,
+
+    /// Parse completion of open files reported by clangd
+    file_status_monitor: FileStatusMonitor,
+
     /// Session start timestamp
     started_at: Instant,
 }
@@ -106,6 +114,8 @@ where
         lsp_client: C,
         index_progress_monitor: IndexProgressMonitor,
         log_monitor: LogMonitor,
+        diagnostics_monitor: DiagnosticsMonitor,
+        file_status_monitor: FileStatusMonitor,
     ) -> Self {
         let started_at = Instant::now();
 
@@ -115,6 +125,8 @@ where
             lsp_client: Box::new(lsp_client),
             index_progress_monitor,
             log_monitor,
+            diagnostics_monitor,
+            file_status_monitor,
             started_at,
         }
     }
@@ -143,6 +155,14 @@ where
     /// Performs orderly shutdown: LSP client shutdown, then process termination.
     /// Prefer this over letting Drop trait handle cleanup.
     pub async fn close(mut self) -> Result<(), ClangdSessionError> {
+        self.shutdown().await
+    }
+
+    /// Graceful shutdown of a session that cannot be consumed (e.g. behind a shared lock)
+    ///
+    /// Performs the same steps as `close()`. Once the process has stopped, dropping the
+    /// session no longer force kills anything.
+    pub async fn shutdown(&mut self) -> Result<(), ClangdSessionError> {
         info!("Gracefully shutting down clangd session");
 
         // Step 1: Shutdown LSP client gracefully
@@ -190,6 +210,16 @@ where
         &self.log_monitor
     }
 
+    /// Get reference to the diagnostics monitor
+    pub fn diagnostics_monitor(&self) -> &DiagnosticsMonitor {
+        &self.diagnostics_monitor
+    }
+
+    /// Get reference to the file status monitor
+    pub fn file_status_monitor(&self) -> &FileStatusMonitor {
+        &self.file_status_monitor
+    }
+
     /// Setup stderr processing for the log monitor
     /// This must be called after session creation to wire stderr to log monitor
     pub fn setup_stderr_monitoring(&mut self)
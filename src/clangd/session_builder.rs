@@ -1,16 +1,22 @@
 //! Session builder for ClangdSession creation
 
+use async_trait::async_trait;
 use lsp_types::request::Request;
 use std::marker::PhantomData;
+use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
 use crate::clangd::config::ClangdConfig;
+use crate::clangd::diagnostics::DiagnosticsMonitor;
 use crate::clangd::error::ClangdSessionError;
+use crate::clangd::file_status::FileStatusMonitor;
 use crate::clangd::index::{IndexProgressMonitor, ProgressEvent};
 use crate::clangd::log_monitor::LogMonitor;
 use crate::clangd::session::ClangdSession;
+use crate::io::process::{ProcessExitEvent, ProcessExitHandler};
 use crate::io::{ChildProcessManager, ProcessManager, StderrMonitor, StdioTransport};
+use crate::lsp::request_history::RequestHistory;
 use crate::lsp::{LspClient, traits::LspClientTrait};
 
 /// Phantom type markers for builder state
@@ -137,7 +143,13 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
         } else {
             LogMonitor::new()
         };
-        let mut process_manager = Self::create_process_manager_without_start(&config).await?;
+        // Shared so that an unexpected clangd exit can be reported with the requests leading to it
+        let request_history = Arc::new(RequestHistory::new());
+        let mut process_manager = Self::create_process_manager_without_start(&config)
+            .await?
+            .with_exit_handler(Arc::new(ClangdExitReporter {
+                request_history: Arc::clone(&request_history),
+            }));
 
         let stderr_processor = log_monitor.create_stderr_processor();
         process_manager.on_stderr_line(move |line: String| {
@@ -146,10 +158,21 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
 
         process_manager.start().await?;
 
-        let mut lsp_client =
-            Self::create_lsp_client(&config, process_manager.create_stdio_transport()?).await?;
-        let index_progress_monitor =
-            Self::setup_monitoring(&mut lsp_client, self.progress_sender.clone()).await;
+        let mut lsp_client = Self::create_lsp_client(
+            &config,
+            process_manager.create_stdio_transport()?,
+            request_history,
+        )
+        .await?;
+        let diagnostics_monitor = DiagnosticsMonitor::new();
+        let file_status_monitor = FileStatusMonitor::new();
+        let index_progress_monitor = Self::setup_monitoring(
+            &mut lsp_client,
+            self.progress_sender.clone(),
+            &diagnostics_monitor,
+            &file_status_monitor,
+        )
+        .await;
 
         Self::finalize_session(
             config,
@@ -157,6 +180,8 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
             lsp_client,
             index_progress_monitor,
             log_monitor,
+            diagnostics_monitor,
+            file_status_monitor,
         )
     }
 }
@@ -189,6 +214,8 @@ where
             lsp_client,
             index_progress_monitor,
             log_monitor,
+            DiagnosticsMonitor::new(),
+            FileStatusMonitor::new(),
         );
 
         Ok(session)
@@ -209,7 +236,8 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
             config.clangd_path.clone(),
             args,
             Some(config.working_directory.clone()),
-        );
+        )
+        .with_env(config.env.clone());
 
         debug!("Starting clangd process");
         process_manager.start().await?;
@@ -229,7 +257,8 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
             config.clangd_path.clone(),
             args,
             Some(config.working_directory.clone()),
-        );
+        )
+        .with_env(config.env.clone());
 
         Ok(process_manager)
     }
@@ -238,9 +267,10 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
     async fn create_lsp_client(
         config: &ClangdConfig,
         transport: StdioTransport,
+        request_history: Arc<RequestHistory>,
     ) -> Result<LspClient<StdioTransport>, ClangdSessionError> {
         debug!("Creating LSP client");
-        let mut lsp_client = LspClient::new(transport);
+        let mut lsp_client = LspClient::new(transport, request_history);
 
         debug!("Initializing LSP connection");
         let root_uri = config.get_root_uri();
@@ -268,6 +298,8 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
     async fn setup_monitoring(
         lsp_client: &mut LspClient<StdioTransport>,
         progress_sender: Option<mpsc::Sender<ProgressEvent>>,
+        diagnostics_monitor: &DiagnosticsMonitor,
+        file_status_monitor: &FileStatusMonitor,
     ) -> IndexProgressMonitor {
         debug!("Creating and wiring IndexProgressMonitor");
         let index_progress_monitor = if let Some(sender) = progress_sender {
@@ -275,9 +307,15 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
         } else {
             IndexProgressMonitor::new()
         };
-        let notification_handler = index_progress_monitor.create_handler();
+        let progress_handler = index_progress_monitor.create_handler();
+        let diagnostics_handler = diagnostics_monitor.create_handler();
+        let file_status_handler = file_status_monitor.create_handler();
         lsp_client
-            .register_notification_handler(notification_handler)
+            .register_notification_handler(move |notification| {
+                diagnostics_handler(notification.clone());
+                file_status_handler(notification.clone());
+                progress_handler(notification);
+            })
             .await;
 
         lsp_client
@@ -318,6 +356,8 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
         lsp_client: LspClient<StdioTransport>,
         index_progress_monitor: IndexProgressMonitor,
         log_monitor: LogMonitor,
+        diagnostics_monitor: DiagnosticsMonitor,
+        file_status_monitor: FileStatusMonitor,
     ) -> Result<ClangdSession<ChildProcessManager, LspClient<StdioTransport>>, ClangdSessionError>
     {
         info!("Clangd session started successfully");
@@ -329,8 +369,36 @@ impl ClangdSessionBuilder<HasConfig, NoProcessManager, NoLspClient> {
             lsp_client,
             index_progress_monitor,
             log_monitor,
+            diagnostics_monitor,
+            file_status_monitor,
         );
 
         Ok(session)
     }
 }
+
+/// Logs unexpected clangd exits together with the requests sent before
+///
+/// Crashes are often reproducible with the request that triggered them, e.g. a
+/// hover in a template-heavy file; the pending requests point at it.
+struct ClangdExitReporter {
+    request_history: Arc<RequestHistory>,
+}
+
+#[async_trait]
+impl ProcessExitHandler for ClangdExitReporter {
+    async fn on_process_exit(&self, event: ProcessExitEvent) {
+        // clangd exits on its own after the shutdown handshake
+        if self.request_history.shutdown_requested() {
+            return;
+        }
+        let status = event
+            .exit_status
+            .map_or_else(|| "unknown status".to_string(), |status| status.to_string());
+        error!(
+            "clangd exited unexpectedly ({}). Recent requests, oldest first:\n{}",
+            status,
+            self.request_history.report()
+        );
+    }
+}
@@ -4,15 +4,28 @@
 //! unit testing without external dependencies.
 
 use async_trait::async_trait;
+use lsp_types::request::Request;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use tokio::sync::mpsc;
 
 use crate::clangd::config::ClangdConfig;
+use crate::clangd::diagnostics::DiagnosticsMonitor;
 use crate::clangd::error::ClangdSessionError;
-use crate::clangd::session::ClangdSessionTrait;
+use crate::clangd::file_status::FileStatusMonitor;
+use crate::clangd::index::{IndexProgressMonitor, ProgressEvent};
+use crate::clangd::log_monitor::LogMonitor;
+use crate::clangd::session::{ClangdSession, ClangdSessionTrait};
+use crate::clangd::version::ClangdVersion;
+use crate::io::process::ChildProcessManager;
+use crate::io::transport::StdioTransport;
+use crate::lsp::LspClient;
+use crate::lsp::request_history::RequestHistory;
 use crate::lsp::traits::{LspClientTrait, MockLspClientTrait};
-use crate::project::{ProjectComponent, ProjectError, ProjectWorkspace};
+use crate::project::component_session::ClangdProcessOptions;
+use crate::project::{ComponentSession, ProjectComponent, ProjectError, ProjectWorkspace};
 
 // ============================================================================
 // Mock Session Implementation
@@ -199,6 +212,7 @@ impl MockProjectWorkspace {
             generator: generator.to_string(),
             build_type: build_type.to_string(),
             build_options: std::collections::HashMap::new(),
+            preset: None,
         };
 
         self.components.push(component);
@@ -219,6 +233,273 @@ impl MockProjectWorkspace {
     }
 }
 
+// ============================================================================
+// Scripted clangd Server
+// ============================================================================
+
+/// Handler producing the result of a scripted LSP request from its params
+type ScriptedResponse = Arc<dyn Fn(Option<serde_json::Value>) -> serde_json::Value + Send + Sync>;
+
+/// Method and params of a message received from the client
+type ReceivedMessage = (String, Option<serde_json::Value>);
+
+/// In-process clangd stand-in answering LSP requests with canned results
+///
+/// Serves a real `LspClient` over in-memory channels, so tools and LSP helpers can
+/// be unit tested end to end without a clangd binary. Requests without a scripted
+/// response are answered with a `null` result; every request and notification
+/// received from the client is recorded for assertions.
+#[derive(Clone, Default)]
+pub struct MockClangd {
+    responses: Arc<Mutex<HashMap<String, ScriptedResponse>>>,
+    errors: Arc<Mutex<HashMap<String, (i32, String)>>>,
+    received: Arc<Mutex<Vec<ReceivedMessage>>>,
+    server_output: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>,
+}
+
+impl MockClangd {
+    /// Create a scripted server answering `initialize` with typical clangd capabilities
+    pub fn new() -> Self {
+        let server = Self::default();
+        server.respond(
+            lsp_types::request::Initialize::METHOD,
+            serde_json::json!({
+                "capabilities": {
+                    "textDocumentSync": 2,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "declarationProvider": true,
+                    "referencesProvider": true,
+                    "documentSymbolProvider": true,
+                    "workspaceSymbolProvider": true,
+                    "callHierarchyProvider": true,
+                    "typeHierarchyProvider": true
+                },
+                "serverInfo": { "name": "mock-clangd", "version": "18.1.8" }
+            }),
+        );
+        server
+    }
+
+    /// Answer every request for `method` with a fixed result
+    pub fn respond(&self, method: &str, result: serde_json::Value) {
+        self.respond_with(method, move |_| result.clone());
+    }
+
+    /// Answer requests for `method` with a result computed from the request params
+    pub fn respond_with<F>(&self, method: &str, handler: F)
+    where
+        F: Fn(Option<serde_json::Value>) -> serde_json::Value + Send + Sync + 'static,
+    {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), Arc::new(handler));
+    }
+
+    /// Answer every request for `method` with a JSON-RPC error
+    pub fn fail(&self, method: &str, code: i32, message: &str) {
+        self.errors
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), (code, message.to_string()));
+    }
+
+    /// Methods of all requests and notifications received so far, in arrival order
+    pub fn received_methods(&self) -> Vec<String> {
+        self.received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(method, _)| method.clone())
+            .collect()
+    }
+
+    /// Params of all messages received for `method`, in arrival order
+    pub fn received_params(&self, method: &str) -> Vec<Option<serde_json::Value>> {
+        self.received
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(received, _)| received == method)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+
+    /// Send a server notification (e.g. `$/progress`) to the connected client
+    pub fn notify(&self, method: &str, params: serde_json::Value) {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        });
+        if let Some(output) = self.server_output.lock().unwrap().as_ref() {
+            let _ = output.send(Self::frame(&notification));
+        }
+    }
+
+    /// Create a transport connected to this server
+    ///
+    /// Each call starts a new connection; notifications go to the latest one.
+    pub fn transport(&self) -> StdioTransport {
+        let (client_tx, mut client_rx) = mpsc::unbounded_channel::<String>();
+        let (server_tx, server_rx) = mpsc::unbounded_channel::<String>();
+        *self.server_output.lock().unwrap() = Some(server_tx.clone());
+
+        let server = self.clone();
+        tokio::spawn(async move {
+            while let Some(framed) = client_rx.recv().await {
+                if let Some(response) = server.handle_message(&framed) {
+                    let _ = server_tx.send(response);
+                }
+            }
+        });
+
+        StdioTransport::from_channels(client_tx, server_rx)
+    }
+
+    /// Start an initialized `ClangdSession` talking to this server
+    ///
+    /// Progress reported through `$/progress` notifications is forwarded to
+    /// `progress_sender` when given.
+    pub async fn start_session(
+        &self,
+        config: ClangdConfig,
+        progress_sender: Option<mpsc::Sender<ProgressEvent>>,
+    ) -> Result<ClangdSession, ClangdSessionError> {
+        let mut lsp_client = LspClient::new(self.transport(), Arc::new(RequestHistory::new()));
+        lsp_client.initialize(config.get_root_uri()).await?;
+
+        let (index_progress_monitor, log_monitor) = match progress_sender {
+            Some(sender) => (
+                IndexProgressMonitor::with_sender(sender.clone()),
+                LogMonitor::with_sender(sender),
+            ),
+            None => (IndexProgressMonitor::new(), LogMonitor::new()),
+        };
+        let diagnostics_monitor = DiagnosticsMonitor::new();
+        let file_status_monitor = FileStatusMonitor::new();
+
+        let progress_handler = index_progress_monitor.create_handler();
+        let diagnostics_handler = diagnostics_monitor.create_handler();
+        let file_status_handler = file_status_monitor.create_handler();
+        lsp_client
+            .register_notification_handler(move |notification| {
+                diagnostics_handler(notification.clone());
+                file_status_handler(notification.clone());
+                progress_handler(notification);
+            })
+            .await;
+
+        let process_manager = ChildProcessManager::new(
+            config.clangd_path.clone(),
+            Vec::new(),
+            Some(config.working_directory.clone()),
+        );
+
+        Ok(ClangdSession::with_dependencies(
+            config,
+            process_manager,
+            lsp_client,
+            index_progress_monitor,
+            log_monitor,
+            diagnostics_monitor,
+            file_status_monitor,
+        ))
+    }
+
+    /// Start a `ComponentSession` for `component` backed by this server
+    pub async fn component_session(
+        &self,
+        component: &ProjectComponent,
+    ) -> Result<Arc<ComponentSession>, ProjectError> {
+        self.component_session_with_options(component, &ClangdProcessOptions::default())
+            .await
+    }
+
+    /// Start a `ComponentSession` backed by this server with process options
+    ///
+    /// Only options affecting the session itself apply (e.g. quiet indexing);
+    /// arguments and environment are meaningless without a clangd process.
+    pub async fn component_session_with_options(
+        &self,
+        component: &ProjectComponent,
+        options: &ClangdProcessOptions,
+    ) -> Result<Arc<ComponentSession>, ProjectError> {
+        let config = test_helpers::create_test_config(
+            &component.source_root_path,
+            &component.build_dir_path,
+            test_helpers::TestConfigType::Mock,
+        )
+        .map_err(|e| ProjectError::SessionCreation(e.to_string()))?;
+
+        let (progress_tx, progress_rx) = mpsc::channel(1024);
+        let session = self
+            .start_session(config, Some(progress_tx))
+            .await
+            .map_err(|e| ProjectError::SessionCreation(e.to_string()))?;
+
+        let clangd_version = ClangdVersion {
+            major: 18,
+            minor: 1,
+            patch: 8,
+            variant: None,
+            date: None,
+        };
+
+        let component_session = ComponentSession::with_clangd_session(
+            component.clone(),
+            &clangd_version,
+            session,
+            progress_rx,
+            options.quiet_indexing,
+        )
+        .await?;
+        Ok(Arc::new(component_session))
+    }
+
+    /// Record a framed client message and build the framed response to it, if any
+    fn handle_message(&self, framed: &str) -> Option<String> {
+        let body = framed
+            .split_once("\r\n\r\n")
+            .map_or(framed, |(_, body)| body);
+        let message: serde_json::Value = serde_json::from_str(body).ok()?;
+
+        // Responses to server-initiated requests carry no method
+        let method = message.get("method")?.as_str()?.to_string();
+        let params = message.get("params").cloned();
+        self.received
+            .lock()
+            .unwrap()
+            .push((method.clone(), params.clone()));
+
+        // Notifications carry no id and get no response
+        let id = message.get("id")?.clone();
+        if let Some((code, error)) = self.errors.lock().unwrap().get(&method).cloned() {
+            return Some(Self::frame(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": error },
+            })));
+        }
+
+        let handler = self.responses.lock().unwrap().get(&method).cloned();
+        let result = handler.map_or(serde_json::Value::Null, |handler| handler(params));
+
+        Some(Self::frame(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result,
+        })))
+    }
+
+    /// Frame a JSON-RPC message with its Content-Length header
+    fn frame(message: &serde_json::Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+}
+
 // ============================================================================
 // Test Utilities
 // ============================================================================
@@ -282,6 +563,103 @@ pub mod test_helpers {
             .build()
     }
 
+    /// Create a single-component project with the given sources on disk
+    ///
+    /// Every source gets a compile_commands.json entry in `<root>/build`, so the
+    /// workspace can back a `MockClangd` component session.
+    pub fn create_mock_project(sources: &[(&str, &str)]) -> (tempfile::TempDir, ProjectWorkspace) {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("mock-project")
+            .tempdir()
+            .unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        let build_dir = project_root.join("build");
+        std::fs::create_dir_all(&build_dir).unwrap();
+
+        let mut entries = Vec::new();
+        for (relative_path, content) in sources {
+            let path = project_root.join(relative_path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+            entries.push(serde_json::json!({
+                "directory": build_dir,
+                "file": path,
+                "command": format!("c++ -c {}", path.display()),
+            }));
+        }
+
+        let compilation_database_path = build_dir.join("compile_commands.json");
+        std::fs::write(
+            &compilation_database_path,
+            serde_json::to_string_pretty(&entries).unwrap(),
+        )
+        .unwrap();
+
+        let component = ProjectComponent {
+            build_dir_path: build_dir,
+            source_root_path: project_root.clone(),
+            compilation_database_path,
+            provider_type: "cmake".to_string(),
+            generator: "Ninja".to_string(),
+            build_type: "Debug".to_string(),
+            build_options: std::collections::HashMap::new(),
+            preset: None,
+        };
+
+        let workspace = ProjectWorkspace::new(project_root, vec![component], 1);
+        (temp_dir, workspace)
+    }
+
+    /// LSP range JSON from `(line, character)` start and end positions
+    pub fn span(start: (u32, u32), end: (u32, u32)) -> serde_json::Value {
+        serde_json::json!({
+            "start": {"line": start.0, "character": start.1},
+            "end": {"line": end.0, "character": end.1}
+        })
+    }
+
+    /// LSP range JSON covering `start..end` on a single line
+    pub fn range(line: u32, start: u32, end: u32) -> serde_json::Value {
+        span((line, start), (line, end))
+    }
+
+    /// LSP location JSON covering `start..end` on a single line of `uri`
+    pub fn location(uri: &str, line: u32, start: u32, end: u32) -> serde_json::Value {
+        serde_json::json!({"uri": uri, "range": range(line, start, end)})
+    }
+
+    /// Document symbol JSON spanning characters 0..10 of `line`
+    pub fn symbol(
+        name: &str,
+        kind: u32,
+        line: u32,
+        children: serde_json::Value,
+    ) -> serde_json::Value {
+        let range = range(line, 0, 10);
+        serde_json::json!({
+            "name": name,
+            "kind": kind,
+            "range": range,
+            "selectionRange": range,
+            "children": children
+        })
+    }
+
+    /// Line of the position in text document position params
+    pub fn line_of(params: &Option<serde_json::Value>) -> Option<u64> {
+        params.as_ref()?["position"]["line"].as_u64()
+    }
+
+    /// Deserialize the JSON text content of a tool result
+    pub fn tool_result<T: serde::de::DeserializeOwned>(
+        result: &rust_mcp_sdk::schema::CallToolResult,
+    ) -> T {
+        let rust_mcp_sdk::schema::ContentBlock::TextContent(text) = &result.content[0] else {
+            panic!("expected text content");
+        };
+        serde_json::from_str(&text.text).unwrap()
+    }
+
     /// Create a MockClangdSession for trait-level testing
     pub fn create_mock_session(
         project_root: &PathBuf,
@@ -328,6 +706,8 @@ pub mod test_helpers {
             mock_lsp,
             index_progress_monitor,
             log_monitor,
+            crate::clangd::diagnostics::DiagnosticsMonitor::new(),
+            crate::clangd::file_status::FileStatusMonitor::new(),
         )
     }
 
@@ -464,6 +844,126 @@ mod tests {
         // Session cleanup happens automatically when dropped
     }
 
+    #[tokio::test]
+    async fn test_mock_clangd_scripted_responses() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int main() { return 0; }\n")]);
+        let component = &workspace.components[0];
+        let main_cpp = component.source_root_path.join("src/main.cpp");
+
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/hover",
+            serde_json::json!({
+                "contents": { "kind": "markdown", "value": "### function `main`" }
+            }),
+        );
+        let component_session = server.component_session(component).await.unwrap();
+
+        component_session
+            .ensure_file_ready(&main_cpp)
+            .await
+            .unwrap();
+        let uri = crate::symbol::uri_from_pathbuf(&main_cpp);
+        let mut session = component_session.lsp_session().await;
+        let hover = session
+            .client_mut()
+            .text_document_hover(uri.clone(), lsp_types::Position::new(0, 4))
+            .await
+            .unwrap()
+            .unwrap();
+        match hover.contents {
+            lsp_types::HoverContents::Markup(markup) => {
+                assert_eq!(markup.value, "### function `main`")
+            }
+            other => panic!("Unexpected hover contents: {:?}", other),
+        }
+
+        // Unscripted requests are answered with null
+        let hierarchy = session
+            .client_mut()
+            .text_document_prepare_type_hierarchy(uri, lsp_types::Position::new(0, 4))
+            .await
+            .unwrap();
+        assert!(hierarchy.is_none());
+
+        let methods = server.received_methods();
+        assert_eq!(methods[0], "initialize");
+        assert!(methods.contains(&"textDocument/didOpen".to_string()));
+        assert_eq!(server.received_params("textDocument/hover").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_file_parsed() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int main() { return 0; }\n")]);
+        let component = &workspace.components[0];
+        let main_cpp = component.source_root_path.join("src/main.cpp");
+
+        let server = MockClangd::new();
+        let component_session = server.component_session(component).await.unwrap();
+
+        // Opened, but clangd has not reported the parse yet
+        let timeout = std::time::Duration::from_millis(50);
+        assert!(
+            !component_session
+                .wait_until_file_parsed(&main_cpp, timeout)
+                .await
+                .unwrap()
+        );
+
+        let uri = crate::symbol::uri_from_pathbuf(&main_cpp.canonicalize().unwrap());
+        server.notify(
+            "textDocument/clangd.fileStatus",
+            serde_json::json!({"uri": uri.as_str(), "state": "idle"}),
+        );
+        assert!(
+            component_session
+                .wait_until_file_parsed(&main_cpp, std::time::Duration::from_secs(5))
+                .await
+                .unwrap()
+        );
+
+        let initialize = &server.received_params("initialize")[0];
+        assert_eq!(
+            initialize.as_ref().unwrap()["initializationOptions"]["clangdFileStatus"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn test_position_encoding_negotiation() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let component = &workspace.components[0];
+
+        // Servers that do not pick an encoding use the UTF-16 default
+        let server = MockClangd::new();
+        let component_session = server.component_session(component).await.unwrap();
+        assert_eq!(
+            component_session.position_encoding().await,
+            crate::lsp::PositionEncoding::Utf16
+        );
+        let params = server.received_params("initialize")[0].clone().unwrap();
+        assert_eq!(
+            params["capabilities"]["general"]["positionEncodings"],
+            serde_json::json!(["utf-8", "utf-16", "utf-32"])
+        );
+
+        let server = MockClangd::new();
+        server.respond(
+            "initialize",
+            serde_json::json!({
+                "capabilities": { "positionEncoding": "utf-8" },
+                "serverInfo": { "name": "mock-clangd", "version": "18.1.8" }
+            }),
+        );
+        let component_session = server.component_session(component).await.unwrap();
+        assert_eq!(
+            component_session.position_encoding().await,
+            crate::lsp::PositionEncoding::Utf8
+        );
+    }
+
     #[test]
     fn test_mock_meta_project() {
         let project_root = PathBuf::from("/test/project");
@@ -122,6 +122,12 @@ impl ClangdVersion {
     }
 }
 
+impl std::fmt::Display for ClangdVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,12 +2,22 @@
 //!
 //! Provides efficient file content management with UTF-8 code point positioning,
 //! automatic change detection, and comprehensive encoding support.
+//!
+//! Source files are not always valid UTF-8: legacy Latin-1 sources, files
+//! starting with a byte order mark and mojibake in comments are common. All
+//! source text is decoded with one fallback policy (see `decode_source`) so
+//! that context extraction degrades to replacement characters on the affected
+//! bytes instead of returning nothing for the whole file.
 #![allow(dead_code)]
 
 use crate::io::file_system::FileSystemTrait;
+use crate::lsp::PositionEncoding;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::SystemTime;
+use tracing::debug;
 
 // ============================================================================
 // File Position
@@ -45,11 +55,147 @@ pub enum FileBufferError {
     #[error("Unsupported encoding in file: {0}")]
     UnsupportedEncoding(String),
 
-    #[error("Invalid UTF-8 sequence: {0}")]
-    InvalidUtf8(#[from] std::string::FromUtf8Error),
-
     #[error("File operation failed: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("File of {size} bytes exceeds the size limit of {limit} bytes")]
+    TooLarge { size: u64, limit: u64 },
+}
+
+// ============================================================================
+// Source Decoding
+// ============================================================================
+
+/// UTF-8 byte order mark
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Decode source file bytes with the fallback policy used for all source text
+///
+/// A leading UTF-8 byte order mark is dropped. Byte sequences that are not
+/// valid UTF-8 are each replaced with U+FFFD; valid text is returned
+/// unchanged. Line endings are kept.
+pub fn decode_source(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(e) => {
+            debug!(
+                "Source is not valid UTF-8 (first invalid byte at offset {}), decoding with replacement characters",
+                e.valid_up_to()
+            );
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Unsaved editor buffers of the tool call running in this task, by file
+    static SOURCE_OVERLAY: Arc<HashMap<PathBuf, String>>;
+}
+
+/// Run a tool call reading the given buffer content in place of those files on disk
+///
+/// Applies to `read_source_file` and `read_source_file_async` within `call`, so
+/// source text matches the ranges clangd computes from the same buffers.
+pub async fn with_source_overlay<F: Future>(
+    buffers: impl IntoIterator<Item = (PathBuf, String)>,
+    call: F,
+) -> F::Output {
+    let overlay = buffers
+        .into_iter()
+        .map(|(path, content)| (path.canonicalize().unwrap_or(path), content))
+        .collect();
+    SOURCE_OVERLAY.scope(Arc::new(overlay), call).await
+}
+
+/// Carry the unsaved buffers of the running tool call into a future spawned as a new task
+///
+/// Task-local values are not inherited by `tokio::spawn`, so without this the
+/// spawned work would read the on-disk content.
+pub fn propagate_source_overlay<F: Future>(call: F) -> impl Future<Output = F::Output> {
+    let overlay = SOURCE_OVERLAY.try_with(Arc::clone).ok();
+    async move {
+        match overlay {
+            Some(overlay) => SOURCE_OVERLAY.scope(overlay, call).await,
+            None => call.await,
+        }
+    }
+}
+
+/// Carry the unsaved buffers of the running tool call into a `spawn_blocking` closure
+pub fn propagate_source_overlay_blocking<R>(f: impl FnOnce() -> R) -> impl FnOnce() -> R {
+    let overlay = SOURCE_OVERLAY.try_with(Arc::clone).ok();
+    move || match overlay {
+        Some(overlay) => SOURCE_OVERLAY.sync_scope(overlay, f),
+        None => f(),
+    }
+}
+
+/// Buffer content replacing a file in the running tool call
+fn overlay_source(path: &Path) -> Option<String> {
+    SOURCE_OVERLAY
+        .try_with(|overlay| {
+            overlay
+                .get(path)
+                .or_else(|| overlay.get(&path.canonicalize().ok()?))
+                .map(|content| decode_source(content.as_bytes()))
+        })
+        .ok()
+        .flatten()
+}
+
+/// Read a source file, decoding it with `decode_source`
+pub fn read_source_file(path: impl AsRef<Path>) -> std::io::Result<String> {
+    if let Some(content) = overlay_source(path.as_ref()) {
+        return Ok(content);
+    }
+    std::fs::read(path).map(|bytes| decode_source(&bytes))
+}
+
+/// Read a source file without blocking, decoding it with `decode_source`
+pub async fn read_source_file_async(path: impl AsRef<Path>) -> std::io::Result<String> {
+    if let Some(content) = overlay_source(path.as_ref()) {
+        return Ok(content);
+    }
+    tokio::fs::read(path)
+        .await
+        .map(|bytes| decode_source(&bytes))
+}
+
+/// Fail with `FileBufferError::TooLarge` when `size` exceeds `limit` bytes
+pub fn check_file_size(size: u64, limit: u64) -> Result<(), FileBufferError> {
+    if size > limit {
+        return Err(FileBufferError::TooLarge { size, limit });
+    }
+    Ok(())
+}
+
+/// Read a source file quoted as context, refusing files over `limit` bytes
+///
+/// Tools showing source around a result read through this instead of
+/// `read_source_file`, so an oversized file is skipped rather than loaded whole.
+pub fn read_context_source(path: impl AsRef<Path>, limit: u64) -> Result<String, FileBufferError> {
+    let path = path.as_ref();
+    if let Some(content) = overlay_source(path) {
+        check_file_size(content.len() as u64, limit)?;
+        return Ok(content);
+    }
+    check_file_size(std::fs::metadata(path)?.len(), limit)?;
+    Ok(read_source_file(path)?)
+}
+
+/// Read a source file quoted as context without blocking, as in `read_context_source`
+pub async fn read_context_source_async(
+    path: impl AsRef<Path>,
+    limit: u64,
+) -> Result<String, FileBufferError> {
+    let path = path.as_ref();
+    if let Some(content) = overlay_source(path) {
+        check_file_size(content.len() as u64, limit)?;
+        return Ok(content);
+    }
+    check_file_size(tokio::fs::metadata(path).await?.len(), limit)?;
+    Ok(read_source_file_async(path).await?)
 }
 
 // ============================================================================
@@ -83,8 +229,7 @@ impl<F: FileSystemTrait> FileBuffer<F> {
         let metadata = filesystem.metadata(&path)?;
         let last_modified = metadata.modified;
 
-        let bytes = filesystem.read(&path)?;
-        let content = Self::normalize_encoding(&bytes)?;
+        let content = Self::read_content(&filesystem, &path)?;
         let line_starts = Self::build_line_index(&content);
         let content_hash = Self::compute_hash(&content);
 
@@ -106,6 +251,19 @@ impl<F: FileSystemTrait> FileBuffer<F> {
         &mut self,
         start: FilePosition,
         end: FilePosition,
+    ) -> Result<String, FileBufferError> {
+        self.text_between_encoded(start, end, PositionEncoding::Utf32)
+    }
+
+    /// Extract text between two positions whose columns use `encoding`
+    ///
+    /// Use this with positions reported by the language server, passing the
+    /// position encoding negotiated with it.
+    pub fn text_between_encoded(
+        &mut self,
+        start: FilePosition,
+        end: FilePosition,
+        encoding: PositionEncoding,
     ) -> Result<String, FileBufferError> {
         // Check for file changes and refresh if needed using stored filesystem
         self.refresh_if_changed()?;
@@ -124,8 +282,8 @@ impl<F: FileSystemTrait> FileBuffer<F> {
             return Err(FileBufferError::InvalidRange { start, end });
         }
 
-        let start_offset = self.position_to_offset(start)?;
-        let end_offset = self.position_to_offset(end)?;
+        let start_offset = self.position_to_offset(start, encoding)?;
+        let end_offset = self.position_to_offset(end, encoding)?;
 
         Ok(self.content[start_offset..end_offset].to_string())
     }
@@ -163,6 +321,18 @@ impl<F: FileSystemTrait> FileBuffer<F> {
     // Internal Methods
     // ========================================================================
 
+    /// Read the normalized content of a file
+    ///
+    /// The unsaved buffer of the running tool call replaces the content on disk,
+    /// as in `read_source_file`.
+    fn read_content(filesystem: &F, path: &Path) -> Result<String, FileBufferError> {
+        let bytes = match overlay_source(path) {
+            Some(content) => content.into_bytes(),
+            None => filesystem.read(path)?,
+        };
+        Ok(Self::normalize_encoding(&bytes))
+    }
+
     /// Refresh file content if it has been modified
     fn refresh_if_changed(&mut self) -> Result<(), FileBufferError> {
         // Skip refresh for test paths that don't exist on filesystem
@@ -174,8 +344,7 @@ impl<F: FileSystemTrait> FileBuffer<F> {
         let current_modified = metadata.modified;
 
         if current_modified != self.last_modified {
-            let bytes = self.filesystem.read(&self.path)?;
-            let new_content = Self::normalize_encoding(&bytes)?;
+            let new_content = Self::read_content(&self.filesystem, &self.path)?;
             let new_hash = Self::compute_hash(&new_content);
 
             // Only update if content actually changed
@@ -192,12 +361,15 @@ impl<F: FileSystemTrait> FileBuffer<F> {
     }
 
     /// Convert file position to byte offset in UTF-8 content
-    fn position_to_offset(&self, pos: FilePosition) -> Result<usize, FileBufferError> {
+    fn position_to_offset(
+        &self,
+        pos: FilePosition,
+        encoding: PositionEncoding,
+    ) -> Result<usize, FileBufferError> {
         use tracing::error;
 
         // Positions are already 0-based
         let line_index = pos.line as usize;
-        let column_index = pos.column as usize;
 
         // Check line bounds
         if line_index >= self.line_starts.len() {
@@ -218,21 +390,17 @@ impl<F: FileSystemTrait> FileBuffer<F> {
             self.content.len()
         };
 
-        // Calculate target position in UTF-8 code points
+        // Calculate byte offset for the column position in the requested encoding
         let line_content = &self.content[line_start..line_end];
-        let chars: Vec<char> = line_content.chars().collect();
-
-        if column_index > chars.len() {
+        let Some(column_offset) = encoding.byte_offset(line_content, pos.column) else {
             error!(
-                "File position out of bounds: {:?}, chars count: {}",
+                "File position out of bounds: {:?}, line length: {} ({:?})",
                 pos,
-                chars.len()
+                encoding.column(line_content, line_content.len()),
+                encoding
             );
             return Err(FileBufferError::PositionOutOfBounds { pos });
-        }
-
-        // Calculate byte offset for the column position
-        let column_offset: usize = chars.iter().take(column_index).map(|c| c.len_utf8()).sum();
+        };
 
         Ok(line_start + column_offset)
     }
@@ -251,21 +419,10 @@ impl<F: FileSystemTrait> FileBuffer<F> {
     }
 
     /// Normalize encoding and line endings
-    fn normalize_encoding(bytes: &[u8]) -> Result<String, FileBufferError> {
-        // Handle UTF-8 BOM
-        let content_bytes = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-            &bytes[3..]
-        } else {
-            bytes
-        };
-
-        // Convert to UTF-8 string
-        let mut content = String::from_utf8(content_bytes.to_vec())?;
-
-        // Normalize line endings to \n
-        content = content.replace("\r\n", "\n").replace('\r', "\n");
-
-        Ok(content)
+    fn normalize_encoding(bytes: &[u8]) -> String {
+        decode_source(bytes)
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
     }
 
     /// Compute SHA256 hash of content for change detection
@@ -362,18 +519,139 @@ mod tests {
         assert_eq!(result, "📝");
     }
 
+    #[test]
+    fn test_text_between_with_lsp_position_encodings() {
+        let filesystem = TestFileSystem::new();
+        let test_path = PathBuf::from("/test/encoded.cpp");
+        let content = "// größe 🌍
+int größe = 1; // 🚀 ok";
+        let time = UNIX_EPOCH + Duration::from_secs(1000);
+
+        filesystem.set_file_content(&test_path, content, time);
+
+        let mut buffer = FileBuffer::new_with_filesystem(&test_path, filesystem).unwrap();
+
+        // Identifier "größe" on line 1: same start, end differs in UTF-8 bytes
+        let extract = |buffer: &mut FileBuffer<TestFileSystem>, start, end, encoding| {
+            buffer
+                .text_between_encoded(
+                    FilePosition::new(1, start),
+                    FilePosition::new(1, end),
+                    encoding,
+                )
+                .unwrap()
+        };
+        assert_eq!(extract(&mut buffer, 4, 11, PositionEncoding::Utf8), "größe");
+        assert_eq!(extract(&mut buffer, 4, 9, PositionEncoding::Utf16), "größe");
+
+        // After the umlauts and the emoji each encoding counts differently
+        assert_eq!(extract(&mut buffer, 25, 27, PositionEncoding::Utf8), "ok");
+        assert_eq!(extract(&mut buffer, 21, 23, PositionEncoding::Utf16), "ok");
+        assert_eq!(extract(&mut buffer, 20, 22, PositionEncoding::Utf32), "ok");
+
+        // Columns past the end of the line are rejected
+        let start = FilePosition::new(1, 0);
+        assert!(
+            buffer
+                .text_between_encoded(start, FilePosition::new(1, 23), PositionEncoding::Utf32)
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_encoding_detection_utf8_bom() {
         let content_with_bom = [0xEF, 0xBB, 0xBF, b'H', b'e', b'l', b'l', b'o'];
-        let normalized =
-            FileBuffer::<TestFileSystem>::normalize_encoding(&content_with_bom).unwrap();
+        let normalized = FileBuffer::<TestFileSystem>::normalize_encoding(&content_with_bom);
         assert_eq!(normalized, "Hello");
     }
 
+    #[test]
+    fn test_non_utf8_content_decoded_lossily() {
+        // Latin-1 "café" in a comment, followed by valid code
+        let latin1 = b"// caf\xe9\nint x;\n";
+        let normalized = FileBuffer::<TestFileSystem>::normalize_encoding(latin1);
+        assert_eq!(normalized, "// caf\u{FFFD}\nint x;\n");
+        assert_eq!(decode_source(b"\xEF\xBB\xBFint y;"), "int y;");
+
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("latin1.cpp");
+        std::fs::write(&file_path, latin1).unwrap();
+        let mut buffer =
+            FileBuffer::new_with_filesystem(&file_path, crate::io::file_system::RealFileSystem)
+                .unwrap();
+        assert_eq!(buffer.get_line(1).unwrap(), "int x;");
+        assert_eq!(read_source_file(&file_path).unwrap(), normalized);
+    }
+
+    #[tokio::test]
+    async fn test_source_overlay() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("main.cpp");
+        std::fs::write(&file_path, "int on_disk;\n").unwrap();
+
+        // Paths are matched whichever way they are spelled
+        let spelled = temp_dir.path().join(".").join("main.cpp");
+        let buffers = [(spelled, "int in_editor;\n".to_string())];
+        let (content, async_content, spawned, blocking) = with_source_overlay(buffers, async {
+            let spawned_path = file_path.clone();
+            let blocking_path = file_path.clone();
+            (
+                read_source_file(&file_path).unwrap(),
+                read_source_file_async(&file_path).await.unwrap(),
+                tokio::spawn(propagate_source_overlay(async move {
+                    read_source_file_async(spawned_path).await.unwrap()
+                }))
+                .await
+                .unwrap(),
+                tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+                    read_source_file(blocking_path).unwrap()
+                }))
+                .await
+                .unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(content, "int in_editor;\n");
+        assert_eq!(async_content, "int in_editor;\n");
+        // Spawned work sees the buffers when they are passed on
+        assert_eq!(spawned, "int in_editor;\n");
+        assert_eq!(blocking, "int in_editor;\n");
+
+        // Outside the call the file is read from disk
+        assert_eq!(read_source_file(&file_path).unwrap(), "int on_disk;\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_context_source_size_limit() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("main.cpp");
+        std::fs::write(&file_path, "int on_disk;\n").unwrap();
+
+        assert_eq!(
+            read_context_source(&file_path, 13).unwrap(),
+            "int on_disk;\n"
+        );
+        assert!(matches!(
+            read_context_source_async(&file_path, 12).await,
+            Err(FileBufferError::TooLarge {
+                size: 13,
+                limit: 12
+            })
+        ));
+
+        // Unsaved buffers are measured instead of the file on disk
+        let buffers = [(file_path.clone(), "int in_editor_buffer;\n".to_string())];
+        let result = with_source_overlay(buffers, read_context_source_async(&file_path, 13)).await;
+        assert!(matches!(
+            result,
+            Err(FileBufferError::TooLarge { size: 22, .. })
+        ));
+    }
+
     #[test]
     fn test_line_ending_normalization() {
         let content_crlf = b"Line1\r\nLine2\rLine3\nLine4";
-        let normalized = FileBuffer::<TestFileSystem>::normalize_encoding(content_crlf).unwrap();
+        let normalized = FileBuffer::<TestFileSystem>::normalize_encoding(content_crlf);
         assert_eq!(normalized, "Line1\nLine2\nLine3\nLine4");
     }
 
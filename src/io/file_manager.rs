@@ -4,7 +4,7 @@
 //! using the manager-owned filesystem pattern for testability.
 #![allow(dead_code)]
 
-use crate::io::file_buffer::{FileBuffer, FileBufferError};
+use crate::io::file_buffer::{FileBuffer, FileBufferError, check_file_size};
 use crate::io::file_system::FileSystemTrait;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -23,6 +23,8 @@ pub struct FileBufferManager<F: FileSystemTrait> {
     buffers: HashMap<PathBuf, FileBuffer<F>>,
     /// Filesystem instance that gets cloned to buffers
     filesystem: F,
+    /// Files larger than this many bytes are not loaded
+    max_file_size: Option<u64>,
 }
 
 impl<F: FileSystemTrait + Clone> FileBufferManager<F> {
@@ -31,13 +33,24 @@ impl<F: FileSystemTrait + Clone> FileBufferManager<F> {
         Self {
             buffers: HashMap::new(),
             filesystem,
+            max_file_size: None,
         }
     }
 
+    /// Refuse to load files larger than `limit` bytes, or any size when `None`
+    ///
+    /// Loading an oversized file fails with `FileBufferError::TooLarge`, so
+    /// callers extracting context can skip it.
+    pub fn with_max_file_size(mut self, limit: Option<u64>) -> Self {
+        self.max_file_size = limit;
+        self
+    }
+
     /// Get or create a file buffer for the given path
     ///
     /// Returns a mutable reference to the cached buffer, creating it if necessary.
     /// The filesystem instance is cloned to the buffer for dependency injection.
+    /// Filesystem access blocks, so async callers run this in `spawn_blocking`.
     pub fn get_buffer(
         &mut self,
         path: impl AsRef<Path>,
@@ -45,6 +58,9 @@ impl<F: FileSystemTrait + Clone> FileBufferManager<F> {
         let path_buf = path.as_ref().to_path_buf();
 
         if !self.buffers.contains_key(&path_buf) {
+            if let Some(limit) = self.max_file_size {
+                check_file_size(self.filesystem.metadata(&path_buf)?.size, limit)?;
+            }
             let buffer = FileBuffer::new_with_filesystem(&path_buf, self.filesystem.clone())?;
             self.buffers.insert(path_buf.clone(), buffer);
         }
@@ -166,6 +182,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_buffer_manager_max_file_size() {
+        let filesystem = TestFileSystem::new();
+        let small = PathBuf::from("/test/small.txt");
+        let large = PathBuf::from("/test/large.txt");
+        let time = UNIX_EPOCH + Duration::from_secs(1000);
+        filesystem.set_file_content(&small, "tiny", time);
+        filesystem.set_file_content(&large, "much larger content", time);
+
+        let mut manager = FileBufferManager::new(filesystem).with_max_file_size(Some(10));
+
+        assert!(manager.get_buffer(&small).is_ok());
+        assert!(matches!(
+            manager.get_buffer(&large),
+            Err(FileBufferError::TooLarge {
+                size: 19,
+                limit: 10
+            })
+        ));
+    }
+
     #[test]
     fn test_file_buffer_manager_with_real_filesystem() {
         let temp_dir = tempdir().unwrap();
@@ -0,0 +1,596 @@
+//! File watching abstraction
+//!
+//! Watchers report changes of watched files as debounced batches of events.
+//! `NotifyFileWatcher` wakes up on platform notifications (inotify, FSEvents,
+//! ReadDirectoryChangesW) through the `notify` crate; `PollingFileWatcher`
+//! polls instead where notifications are unavailable. Both decide what changed
+//! by comparing modification times and sizes read through `FileSystemTrait`,
+//! so the change detection runs against the in-memory `TestFileSystem`.
+//! Features depending on file changes take a `FileWatcher` so tests can
+//! inject `TestFileWatcher` and emit events deterministically.
+
+use notify::Watcher;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, broadcast};
+use tokio::task::JoinHandle;
+use tracing::{trace, warn};
+
+use crate::io::file_system::{FileMetadata, FileSystemTrait};
+
+/// Default interval between two polls of the watched files
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Default time a file has to stay unchanged before its change is reported
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Number of undelivered batches kept per subscriber
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+// ============================================================================
+// Change Events
+// ============================================================================
+
+/// How a watched file changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Change of a watched file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub kind: FileChangeKind,
+}
+
+impl FileChange {
+    pub fn new(path: impl Into<PathBuf>, kind: FileChangeKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+        }
+    }
+}
+
+// ============================================================================
+// File Watcher Trait
+// ============================================================================
+
+/// Trait for watching files for changes
+///
+/// Enables dependency injection and testing through `TestFileWatcher`.
+pub trait FileWatcher: Send + Sync {
+    /// Start watching a file; files that do not exist yet are reported when created
+    fn watch(&self, path: &Path);
+
+    /// Stop watching a file
+    // Sessions keep their files open, so nothing stops watching outside tests yet
+    #[allow(dead_code)]
+    fn unwatch(&self, path: &Path);
+
+    /// Receive the debounced changes of all watched files, in batches
+    fn subscribe(&self) -> broadcast::Receiver<Vec<FileChange>>;
+}
+
+// ============================================================================
+// Modification Time Tracking
+// ============================================================================
+
+/// State of a watched file
+#[derive(Debug, Default)]
+struct WatchedFile {
+    /// State last reported (or found when watching started); `None` if missing
+    reported: Option<FileMetadata>,
+    /// Newer state and when it was first seen, until it has settled
+    pending: Option<(Option<FileMetadata>, Instant)>,
+}
+
+/// Change detection by comparing file metadata between polls
+///
+/// A change is reported once the file has kept its new state for the debounce
+/// time, so a burst of writes (an editor saving, a build regenerating a file)
+/// results in a single event. The caller drives the polls, which makes the
+/// tracker deterministic to test.
+pub struct MtimeTracker<F: FileSystemTrait> {
+    file_system: F,
+    debounce: Duration,
+    files: BTreeMap<PathBuf, WatchedFile>,
+}
+
+impl<F: FileSystemTrait> MtimeTracker<F> {
+    /// Create a tracker reporting changes after they settled for `debounce`
+    pub fn new(file_system: F, debounce: Duration) -> Self {
+        Self {
+            file_system,
+            debounce,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Start watching a file from its current state
+    pub fn watch(&mut self, path: &Path) {
+        let reported = self.file_system.metadata(path).ok();
+        self.files.entry(path.to_path_buf()).or_insert(WatchedFile {
+            reported,
+            pending: None,
+        });
+    }
+
+    /// Stop watching a file
+    pub fn unwatch(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+
+    /// Watched files
+    #[cfg(test)]
+    pub fn watched(&self) -> Vec<PathBuf> {
+        self.files.keys().cloned().collect()
+    }
+
+    /// Check whether a file is watched
+    pub fn is_watched(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Check whether changes were seen that have not settled yet
+    pub fn has_pending(&self) -> bool {
+        self.files.values().any(|file| file.pending.is_some())
+    }
+
+    /// Check the watched files, returning the changes that settled by `now`
+    pub fn poll(&mut self, now: Instant) -> Vec<FileChange> {
+        let mut changes = Vec::new();
+        for (path, file) in &mut self.files {
+            let current = self.file_system.metadata(path).ok();
+            let latest = match &file.pending {
+                Some((state, _)) => state,
+                None => &file.reported,
+            };
+            if current != *latest {
+                // Changed, or changed again: the debounce time starts over
+                file.pending = Some((current, now));
+                continue;
+            }
+            let Some((state, _)) = file
+                .pending
+                .take_if(|(_, since)| now.saturating_duration_since(*since) >= self.debounce)
+            else {
+                continue;
+            };
+            let kind = match (&file.reported, &state) {
+                (None, Some(_)) => Some(FileChangeKind::Created),
+                (Some(_), None) => Some(FileChangeKind::Removed),
+                (Some(reported), Some(state)) if reported != state => {
+                    Some(FileChangeKind::Modified)
+                }
+                // Changed and changed back before settling
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                trace!("File {} {:?}", path.display(), kind);
+                changes.push(FileChange::new(path.clone(), kind));
+            }
+            file.reported = state;
+        }
+        changes
+    }
+}
+
+// ============================================================================
+// Notification File Watcher
+// ============================================================================
+
+/// File watcher driven by platform file notifications
+///
+/// Files are watched through their parent directories, so files replaced by
+/// a rename (as editors and build tools save them) and files created later
+/// are still seen. A notification only wakes the tracker up; it checks the
+/// watched files and reports their changes once they settled.
+pub struct NotifyFileWatcher<F: FileSystemTrait + 'static> {
+    tracker: Arc<Mutex<MtimeTracker<F>>>,
+    watcher: Mutex<notify::RecommendedWatcher>,
+    /// Watched directories with the number of watched files in each
+    directories: Mutex<BTreeMap<PathBuf, usize>>,
+    sender: broadcast::Sender<Vec<FileChange>>,
+    task: JoinHandle<()>,
+}
+
+impl<F: FileSystemTrait + 'static> NotifyFileWatcher<F> {
+    /// Start watching, reporting changes settled for `debounce`
+    ///
+    /// Must be called within a tokio runtime. Fails when the platform
+    /// notification API cannot be initialized.
+    pub fn new(file_system: F, debounce: Duration) -> notify::Result<Self> {
+        let tracker = Arc::new(Mutex::new(MtimeTracker::new(file_system, debounce)));
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let wake = Arc::new(Notify::new());
+
+        let tracker_for_watcher = Arc::clone(&tracker);
+        let wake_for_watcher = Arc::clone(&wake);
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let relevant = match event {
+                Ok(event) => {
+                    let tracker = tracker_for_watcher.lock().unwrap();
+                    event.paths.iter().any(|path| tracker.is_watched(path))
+                }
+                // Events may have been lost, so check every watched file
+                Err(_) => true,
+            };
+            if relevant {
+                wake_for_watcher.notify_one();
+            }
+        })?;
+
+        let tracker_for_task = Arc::clone(&tracker);
+        let sender_for_task = sender.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let pending = tracker_for_task.lock().unwrap().has_pending();
+                if pending {
+                    // Check again once the changes seen so far settled
+                    tokio::select! {
+                        _ = wake.notified() => {}
+                        _ = tokio::time::sleep(debounce) => {}
+                    }
+                } else {
+                    wake.notified().await;
+                }
+                let changes = tracker_for_task.lock().unwrap().poll(Instant::now());
+                if !changes.is_empty() {
+                    // Nobody subscribed yet: the changes are dropped
+                    let _ = sender_for_task.send(changes);
+                }
+            }
+        });
+
+        Ok(Self {
+            tracker,
+            watcher: Mutex::new(watcher),
+            directories: Mutex::new(BTreeMap::new()),
+            sender,
+            task,
+        })
+    }
+
+    /// Directory watched for changes of `path`
+    fn directory_of(path: &Path) -> PathBuf {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+}
+
+impl<F: FileSystemTrait + 'static> FileWatcher for NotifyFileWatcher<F> {
+    fn watch(&self, path: &Path) {
+        let mut tracker = self.tracker.lock().unwrap();
+        if tracker.is_watched(path) {
+            return;
+        }
+        tracker.watch(path);
+
+        let directory = Self::directory_of(path);
+        let mut directories = self.directories.lock().unwrap();
+        let count = directories.entry(directory.clone()).or_insert(0);
+        if *count == 0
+            && let Err(e) = self
+                .watcher
+                .lock()
+                .unwrap()
+                .watch(&directory, notify::RecursiveMode::NonRecursive)
+        {
+            warn!(
+                "Cannot watch {}, changes of {} are not reported: {}",
+                directory.display(),
+                path.display(),
+                e
+            );
+        }
+        *count += 1;
+    }
+
+    fn unwatch(&self, path: &Path) {
+        let mut tracker = self.tracker.lock().unwrap();
+        if !tracker.is_watched(path) {
+            return;
+        }
+        tracker.unwatch(path);
+
+        let directory = Self::directory_of(path);
+        let mut directories = self.directories.lock().unwrap();
+        let Some(count) = directories.get_mut(&directory) else {
+            return;
+        };
+        *count -= 1;
+        if *count == 0 {
+            directories.remove(&directory);
+            // Fails when the directory was never watched or has been removed
+            let _ = self.watcher.lock().unwrap().unwatch(&directory);
+        }
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Vec<FileChange>> {
+        self.sender.subscribe()
+    }
+}
+
+impl<F: FileSystemTrait + 'static> Drop for NotifyFileWatcher<F> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Create a file watcher using platform notifications, polling where they are unavailable
+///
+/// Must be called within a tokio runtime.
+pub fn file_watcher<F: FileSystemTrait + 'static>(file_system: F) -> Box<dyn FileWatcher> {
+    match NotifyFileWatcher::new(file_system.clone(), DEFAULT_DEBOUNCE) {
+        Ok(watcher) => Box::new(watcher),
+        Err(e) => {
+            warn!("File notifications unavailable, polling instead: {}", e);
+            Box::new(PollingFileWatcher::with_defaults(file_system))
+        }
+    }
+}
+
+// ============================================================================
+// Polling File Watcher
+// ============================================================================
+
+/// File watcher polling modification times in a background task
+pub struct PollingFileWatcher<F: FileSystemTrait + 'static> {
+    tracker: Arc<Mutex<MtimeTracker<F>>>,
+    sender: broadcast::Sender<Vec<FileChange>>,
+    task: JoinHandle<()>,
+}
+
+impl<F: FileSystemTrait + 'static> PollingFileWatcher<F> {
+    /// Start polling every `interval`, reporting changes settled for `debounce`
+    ///
+    /// Must be called within a tokio runtime. Polling stops when the watcher is
+    /// dropped.
+    pub fn new(file_system: F, interval: Duration, debounce: Duration) -> Self {
+        let tracker = Arc::new(Mutex::new(MtimeTracker::new(file_system, debounce)));
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        let tracker_for_task = Arc::clone(&tracker);
+        let sender_for_task = sender.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let changes = tracker_for_task.lock().unwrap().poll(Instant::now());
+                if !changes.is_empty() {
+                    // Nobody subscribed yet: the changes are dropped
+                    let _ = sender_for_task.send(changes);
+                }
+            }
+        });
+
+        Self {
+            tracker,
+            sender,
+            task,
+        }
+    }
+
+    /// Watcher with the default poll interval and debounce time
+    pub fn with_defaults(file_system: F) -> Self {
+        Self::new(file_system, DEFAULT_POLL_INTERVAL, DEFAULT_DEBOUNCE)
+    }
+}
+
+impl<F: FileSystemTrait + 'static> FileWatcher for PollingFileWatcher<F> {
+    fn watch(&self, path: &Path) {
+        self.tracker.lock().unwrap().watch(path);
+    }
+
+    fn unwatch(&self, path: &Path) {
+        self.tracker.lock().unwrap().unwatch(path);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Vec<FileChange>> {
+        self.sender.subscribe()
+    }
+}
+
+impl<F: FileSystemTrait + 'static> Drop for PollingFileWatcher<F> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ============================================================================
+// Test File Watcher Implementation
+// ============================================================================
+
+#[cfg(test)]
+mod test_file_watcher {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// File watcher whose changes are emitted by the test
+    #[derive(Clone)]
+    pub struct TestFileWatcher {
+        watched: Arc<Mutex<BTreeSet<PathBuf>>>,
+        sender: broadcast::Sender<Vec<FileChange>>,
+    }
+
+    impl TestFileWatcher {
+        pub fn new() -> Self {
+            let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+            Self {
+                watched: Arc::new(Mutex::new(BTreeSet::new())),
+                sender,
+            }
+        }
+
+        /// Emit the changes of watched files to the subscribers
+        pub fn emit(&self, changes: Vec<FileChange>) {
+            let watched = self.watched.lock().unwrap();
+            let changes: Vec<_> = changes
+                .into_iter()
+                .filter(|change| watched.contains(&change.path))
+                .collect();
+            if !changes.is_empty() {
+                let _ = self.sender.send(changes);
+            }
+        }
+
+        /// Files currently watched
+        pub fn watched(&self) -> Vec<PathBuf> {
+            self.watched.lock().unwrap().iter().cloned().collect()
+        }
+    }
+
+    impl FileWatcher for TestFileWatcher {
+        fn watch(&self, path: &Path) {
+            self.watched.lock().unwrap().insert(path.to_path_buf());
+        }
+
+        fn unwatch(&self, path: &Path) {
+            self.watched.lock().unwrap().remove(path);
+        }
+
+        fn subscribe(&self) -> broadcast::Receiver<Vec<FileChange>> {
+            self.sender.subscribe()
+        }
+    }
+}
+
+#[cfg(test)]
+pub use test_file_watcher::TestFileWatcher;
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::file_system::{RealFileSystem, TestFileSystem};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn time(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_changes_are_debounced() {
+        let fs = TestFileSystem::new();
+        let compile_commands = PathBuf::from("/build/compile_commands.json");
+        let generated = PathBuf::from("/build/config.h");
+        fs.set_file_content(&compile_commands, "[]", time(1));
+
+        let debounce = Duration::from_millis(100);
+        let mut tracker = MtimeTracker::new(fs.clone(), debounce);
+        tracker.watch(&compile_commands);
+        tracker.watch(&generated);
+        let start = Instant::now();
+        assert!(tracker.poll(start).is_empty());
+
+        // A burst of writes is reported once, after it settled
+        fs.update_file_content(&compile_commands, "[{}]", time(2));
+        assert!(tracker.poll(start).is_empty());
+        fs.update_file_content(&compile_commands, "[{}, {}]", time(3));
+        assert!(tracker.poll(start + debounce / 2).is_empty());
+        assert!(tracker.poll(start + debounce).is_empty());
+        assert_eq!(
+            tracker.poll(start + debounce * 2),
+            vec![FileChange::new(&compile_commands, FileChangeKind::Modified)]
+        );
+        assert!(tracker.poll(start + debounce * 3).is_empty());
+
+        // Files missing when watching started are reported when created
+        fs.set_file_content(&generated, "#define X 1", time(4));
+        assert!(tracker.poll(start + debounce * 3).is_empty());
+        assert_eq!(
+            tracker.poll(start + debounce * 4),
+            vec![FileChange::new(&generated, FileChangeKind::Created)]
+        );
+
+        tracker.unwatch(&generated);
+        assert_eq!(tracker.watched(), vec![compile_commands]);
+    }
+
+    #[test]
+    fn test_removed_files_and_reverted_changes() {
+        let fs = TestFileSystem::new();
+        let header = PathBuf::from("/src/a.h");
+        fs.set_file_content(&header, "int a;", time(1));
+
+        let debounce = Duration::from_millis(100);
+        let mut tracker = MtimeTracker::new(fs.clone(), debounce);
+        tracker.watch(&header);
+        let start = Instant::now();
+
+        // Changed and changed back before settling: nothing to report
+        fs.update_file_content(&header, "int a; int b;", time(2));
+        assert!(tracker.poll(start).is_empty());
+        fs.update_file_content(&header, "int a;", time(1));
+        assert!(tracker.poll(start).is_empty());
+        assert!(tracker.poll(start + debounce * 2).is_empty());
+
+        // Removed files are reported like any other change
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("a.cpp");
+        std::fs::write(&source, "int main() {}").unwrap();
+        let mut tracker = MtimeTracker::new(RealFileSystem, Duration::ZERO);
+        tracker.watch(&source);
+        std::fs::remove_file(&source).unwrap();
+        assert!(tracker.poll(start).is_empty());
+        assert_eq!(
+            tracker.poll(start),
+            vec![FileChange::new(&source, FileChangeKind::Removed)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_file_watcher_reports_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.h");
+        let other = dir.path().join("other.h");
+        let debounce = Duration::from_millis(50);
+        let watcher = NotifyFileWatcher::new(RealFileSystem, debounce).unwrap();
+        let mut changes = watcher.subscribe();
+        watcher.watch(&config);
+
+        // Files created after watching started are reported, unwatched ones are not
+        std::fs::write(&other, "int b;").unwrap();
+        std::fs::write(&config, "#define X 1").unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), changes.recv())
+            .await
+            .expect("change was not reported")
+            .unwrap();
+        assert_eq!(
+            change,
+            vec![FileChange::new(&config, FileChangeKind::Created)]
+        );
+
+        watcher.unwatch(&config);
+        assert!(watcher.directories.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_test_file_watcher_emits_watched_changes() {
+        let watcher = TestFileWatcher::new();
+        let mut changes = watcher.subscribe();
+        watcher.watch(Path::new("/src/a.cpp"));
+        assert_eq!(watcher.watched(), vec![PathBuf::from("/src/a.cpp")]);
+
+        watcher.emit(vec![
+            FileChange::new("/src/a.cpp", FileChangeKind::Modified),
+            FileChange::new("/src/b.cpp", FileChangeKind::Modified),
+        ]);
+        assert_eq!(
+            changes.recv().await.unwrap(),
+            vec![FileChange::new("/src/a.cpp", FileChangeKind::Modified)]
+        );
+    }
+}
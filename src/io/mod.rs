@@ -5,12 +5,14 @@
 //! - **Transport**: Pure I/O layer for bidirectional message exchange
 //! - **Process**: External process lifecycle management with stdio integration
 //! - **File Buffer**: UTF-8 file content management with position-based text extraction
+//! - **File Watcher**: Debounced change events for watched files
 //!
 //! These abstractions can be used by any protocol layer (LSP, MCP, etc.)
 
 pub mod file_buffer;
 pub mod file_manager;
 pub mod file_system;
+pub mod file_watcher;
 pub mod process;
 pub mod transport;
 
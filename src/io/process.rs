@@ -5,6 +5,7 @@
 
 use crate::io::transport::{StdioTransport, Transport};
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::io;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -64,9 +65,14 @@ impl ProcessState {
 // ============================================================================
 
 /// Event fired when process exits unexpectedly
+///
+/// Exits following `stop()` or `kill_sync()` are expected and fire no event.
 #[derive(Debug, Clone)]
 
-pub struct ProcessExitEvent {}
+pub struct ProcessExitEvent {
+    /// Exit status, `None` if waiting for the process failed
+    pub exit_status: Option<std::process::ExitStatus>,
+}
 
 // ============================================================================
 // Process Restart Handler Trait
@@ -162,6 +168,9 @@ pub struct ChildProcessManager {
     /// Working directory for the process (optional)
     working_directory: Option<PathBuf>,
 
+    /// Environment variables set on top of the inherited environment
+    env: BTreeMap<String, String>,
+
     /// Thread-safe process state
     state: Arc<Mutex<ProcessState>>,
 
@@ -193,6 +202,7 @@ impl ChildProcessManager {
             command,
             args,
             working_directory: working_dir,
+            env: BTreeMap::new(),
             state: Arc::new(Mutex::new(ProcessState::NotStarted)),
             stdio_transport: None,
             stderr_handler: None,
@@ -202,6 +212,20 @@ impl ChildProcessManager {
         }
     }
 
+    /// Set environment variables for the process, overriding inherited ones
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Install a handler notified when the process exits unexpectedly
+    ///
+    /// Must be installed before `start()`.
+    pub fn with_exit_handler(mut self, handler: Arc<dyn ProcessExitHandler>) -> Self {
+        self.exit_handler = Some(handler);
+        self
+    }
+
     /// Get current process state (thread-safe)
     pub fn get_state(&self) -> ProcessState {
         // Intentional .unwrap() - poisoned mutex indicates serious bug, panic is appropriate
@@ -285,40 +309,36 @@ impl ChildProcessManager {
             );
 
             // Wait for the child process to exit
-            match child.wait().await {
+            let exit_status = match child.wait().await {
                 Ok(exit_status) => {
                     info!(
                         "Process PID {:?} exited with status: {}",
                         current_pid, exit_status
                     );
-
-                    // Transition state to Stopped
-                    if let Ok(mut process_state) = state.lock() {
-                        *process_state = ProcessState::Stopped;
-                    }
-
-                    // Fire exit event if handler is present
-                    if let Some(handler) = &exit_handler {
-                        let event = ProcessExitEvent {};
-
-                        handler.on_process_exit(event).await;
-                    }
+                    Some(exit_status)
                 }
                 Err(e) => {
                     error!("Error waiting for child process: {}", e);
+                    None
+                }
+            };
+
+            // Transition state to Stopped; stop() and kill_sync() already did so
+            // for exits they caused
+            let unexpected = match state.lock() {
+                Ok(mut process_state) => {
+                    let was_running = process_state.is_running();
+                    *process_state = ProcessState::Stopped;
+                    was_running
+                }
+                Err(_) => false,
+            };
 
-                    // Transition state to Stopped even on error
-                    if let Ok(mut process_state) = state.lock() {
-                        *process_state = ProcessState::Stopped;
-                    }
-
-                    // Fire exit event for error case too
-                    if let Some(handler) = &exit_handler {
-                        let event = ProcessExitEvent {};
+            // Fire exit event if handler is present
+            if unexpected && let Some(handler) = &exit_handler {
+                let event = ProcessExitEvent { exit_status };
 
-                        handler.on_process_exit(event).await;
-                    }
-                }
+                handler.on_process_exit(event).await;
             }
 
             trace!(
@@ -343,10 +363,14 @@ impl ProcessManager for ChildProcessManager {
         }
 
         info!("Starting process: {} {:?}", self.command, self.args);
+        if !self.env.is_empty() {
+            info!("Process environment overrides: {:?}", self.env);
+        }
 
         let mut command_builder = Command::new(&self.command);
         command_builder
             .args(&self.args)
+            .envs(&self.env)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
@@ -638,6 +662,71 @@ mod tests {
         assert_eq!(lines[0], "error message");
     }
 
+    #[tokio::test]
+    async fn test_environment_overrides() {
+        let mut manager = ChildProcessManager::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "echo \"$MCP_CPP_TEST_VAR\" >&2; sleep 1".to_string(),
+            ],
+            None,
+        )
+        .with_env(BTreeMap::from([(
+            "MCP_CPP_TEST_VAR".to_string(),
+            "from override".to_string(),
+        )]));
+
+        let stderr_lines = Arc::new(Mutex::new(Vec::<String>::new()));
+        let stderr_lines_clone = Arc::clone(&stderr_lines);
+        manager.on_stderr_line(move |line| {
+            if let Ok(mut lines) = stderr_lines_clone.lock() {
+                lines.push(line);
+            }
+        });
+
+        manager.start().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        manager.stop(StopMode::Graceful).await.unwrap();
+
+        assert_eq!(*stderr_lines.lock().unwrap(), vec!["from override"]);
+    }
+
+    /// Exit handler collecting the exit codes it is notified of
+    struct RecordingExitHandler(Mutex<Vec<Option<i32>>>);
+
+    #[async_trait]
+    impl ProcessExitHandler for RecordingExitHandler {
+        async fn on_process_exit(&self, event: ProcessExitEvent) {
+            let code = event.exit_status.and_then(|status| status.code());
+            self.0.lock().unwrap().push(code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exit_handler_reports_unexpected_exits_only() {
+        let handler = Arc::new(RecordingExitHandler(Mutex::new(Vec::new())));
+
+        let mut crashing = ChildProcessManager::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "exit 3".to_string()],
+            None,
+        )
+        .with_exit_handler(handler.clone());
+        crashing.start().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!crashing.is_running());
+
+        let mut stopped =
+            ChildProcessManager::new("sleep".to_string(), vec!["5".to_string()], None)
+                .with_exit_handler(handler.clone());
+        stopped.start().await.unwrap();
+        stopped.stop(StopMode::Force).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        assert_eq!(*handler.0.lock().unwrap(), vec![Some(3)]);
+    }
+
     #[tokio::test]
     async fn test_process_state_transitions() {
         let mut manager =
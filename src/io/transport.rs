@@ -8,10 +8,11 @@ use async_trait::async_trait;
 use std::collections::VecDeque;
 use std::io;
 use std::sync::{Arc, Mutex};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::process::{ChildStdin, ChildStdout};
-use tokio::sync::mpsc;
-use tracing::{error, trace};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, trace, warn};
 
 // ============================================================================
 // Constants
@@ -23,6 +24,80 @@ const READ_BUFFER_SIZE: usize = 4096;
 /// Default capacity for UTF-8 accumulation buffer
 const UTF8_ACCUMULATION_BUFFER_CAPACITY: usize = 8192;
 
+/// Number of times a write is retried after a transient I/O error
+const MAX_WRITE_RETRIES: u32 = 3;
+
+/// Delay before retrying a write, multiplied by the attempt number
+const WRITE_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Check whether an I/O error is transient, so the operation can be retried
+fn is_transient_io_error(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+/// Check whether an I/O error means the other end closed the stream
+fn is_closed_io_error(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// A failed write, with the number of bytes that reached the writer before it failed
+#[derive(Debug)]
+struct WriteFailure {
+    written: usize,
+    error: io::Error,
+}
+
+/// Write all bytes and flush, retrying transient errors
+///
+/// Partial writes continue with the remaining bytes. Each write is retried at
+/// most `MAX_WRITE_RETRIES` times in a row before its error is returned.
+async fn write_all_with_retry<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    bytes: &[u8],
+) -> Result<(), WriteFailure> {
+    let mut written = 0;
+    let mut retries = 0;
+    let failure = |written, error| Err(WriteFailure { written, error });
+    while written < bytes.len() {
+        match writer.write(&bytes[written..]).await {
+            Ok(0) => return failure(written, io::ErrorKind::WriteZero.into()),
+            Ok(n) => {
+                written += n;
+                retries = 0;
+            }
+            Err(e) if is_transient_io_error(&e) && retries < MAX_WRITE_RETRIES => {
+                retries += 1;
+                warn!(
+                    "Transient write error, retrying ({}/{}): {}",
+                    retries, MAX_WRITE_RETRIES, e
+                );
+                tokio::time::sleep(WRITE_RETRY_DELAY * retries).await;
+            }
+            Err(e) => return failure(written, e),
+        }
+    }
+    loop {
+        match writer.flush().await {
+            Ok(()) => return Ok(()),
+            Err(e) if is_transient_io_error(&e) && retries < MAX_WRITE_RETRIES => {
+                retries += 1;
+                warn!(
+                    "Transient flush error, retrying ({}/{}): {}",
+                    retries, MAX_WRITE_RETRIES, e
+                );
+                tokio::time::sleep(WRITE_RETRY_DELAY * retries).await;
+            }
+            Err(e) => return failure(written, e),
+        }
+    }
+}
+
 /// Core transport trait for bidirectional message exchange
 #[async_trait]
 pub trait Transport: Send + Sync {
@@ -39,6 +114,14 @@ pub trait Transport: Send + Sync {
 
     /// Check if transport is still active
     fn is_connected(&self) -> bool;
+
+    /// Check whether a send error left the transport unusable
+    ///
+    /// Other errors fail only the message being sent. Transports retry transient
+    /// errors themselves, so callers never send a failed message again.
+    fn is_fatal_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
 }
 
 // ============================================================================
@@ -54,15 +137,23 @@ pub enum StdioTransportError {
     #[error("Transport is disconnected")]
     Disconnected,
 
-    #[error("Channel error: {0}")]
-    Channel(String),
+    /// A message was written only in part, so the stream can no longer be framed
+    #[error("Message written only in part ({written} of {total} bytes): {source}")]
+    PartialWrite {
+        written: usize,
+        total: usize,
+        source: io::Error,
+    },
 }
 
+/// Message queued for the stdin writer task, with the channel receiving its write result
+type StdinMessage = (String, oneshot::Sender<Result<(), StdioTransportError>>);
+
 /// Transport implementation using stdin/stdout streams
 #[derive(Debug)]
 pub struct StdioTransport {
     /// Channel for sending messages to stdin
-    stdin_sender: Option<mpsc::UnboundedSender<String>>,
+    stdin_sender: Option<mpsc::UnboundedSender<StdinMessage>>,
 
     /// Channel for receiving messages from stdout
     stdout_receiver: Option<mpsc::UnboundedReceiver<String>>,
@@ -154,25 +245,89 @@ impl StdioTransport {
         }
     }
 
-    /// Background task that writes messages to stdin
-    async fn stdin_writer_task(
-        mut stdin: ChildStdin,
-        mut receiver: mpsc::UnboundedReceiver<String>,
+    /// Create a StdioTransport over in-process channels instead of child process streams
+    ///
+    /// Messages sent through the transport arrive on `stdin_sender`'s receiver and
+    /// everything pushed into `stdout_receiver`'s sender is read back as server output.
+    #[cfg(test)]
+    pub fn from_channels(
+        stdin_sender: mpsc::UnboundedSender<String>,
+        stdout_receiver: mpsc::UnboundedReceiver<String>,
+    ) -> Self {
+        let (message_sender, mut message_receiver) = mpsc::unbounded_channel::<StdinMessage>();
+        tokio::spawn(async move {
+            while let Some((message, result_sender)) = message_receiver.recv().await {
+                let result = stdin_sender
+                    .send(message)
+                    .map_err(|_| StdioTransportError::Disconnected);
+                let _ = result_sender.send(result);
+            }
+        });
+
+        Self {
+            stdin_sender: Some(message_sender),
+            stdout_receiver: Some(stdout_receiver),
+            connected: true,
+        }
+    }
+
+    /// Create a StdioTransport writing to `writer` and reading from an in-process channel
+    #[cfg(test)]
+    fn from_writer<W: AsyncWrite + Unpin + Send + 'static>(
+        writer: W,
+        stdout_receiver: mpsc::UnboundedReceiver<String>,
+    ) -> Self {
+        let (stdin_sender, stdin_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(Self::stdin_writer_task(writer, stdin_receiver));
+
+        Self {
+            stdin_sender: Some(stdin_sender),
+            stdout_receiver: Some(stdout_receiver),
+            connected: true,
+        }
+    }
+
+    /// Background task that writes messages to stdin, reporting each write result to its sender
+    ///
+    /// A message that could not be written at all fails alone, unless the stream
+    /// was closed. A message written only in part stops the task, because the
+    /// receiving side can no longer find the message boundaries.
+    async fn stdin_writer_task<W: AsyncWrite + Unpin>(
+        mut stdin: W,
+        mut receiver: mpsc::UnboundedReceiver<StdinMessage>,
     ) {
-        while let Some(message) = receiver.recv().await {
+        while let Some((message, result_sender)) = receiver.recv().await {
             trace!(
                 "StdioTransport: Writing message (length: {})",
                 message.len()
             );
 
-            if let Err(e) = stdin.write_all(message.as_bytes()).await {
-                error!("Failed to write to stdin: {}", e);
-                break;
-            }
-
-            if let Err(e) = stdin.flush().await {
-                error!("Failed to flush stdin: {}", e);
-                break;
+            match write_all_with_retry(&mut stdin, message.as_bytes()).await {
+                Ok(()) => {
+                    let _ = result_sender.send(Ok(()));
+                }
+                Err(WriteFailure { written: 0, error }) => {
+                    error!("Failed to write to stdin: {}", error);
+                    let closed = is_closed_io_error(&error);
+                    let _ = result_sender.send(Err(StdioTransportError::Io(error)));
+                    if closed {
+                        break;
+                    }
+                }
+                Err(WriteFailure { written, error }) => {
+                    error!(
+                        "Failed to write to stdin after {} of {} bytes: {}",
+                        written,
+                        message.len(),
+                        error
+                    );
+                    let _ = result_sender.send(Err(StdioTransportError::PartialWrite {
+                        written,
+                        total: message.len(),
+                        source: error,
+                    }));
+                    break;
+                }
             }
         }
 
@@ -275,11 +430,21 @@ impl Transport for StdioTransport {
             .as_ref()
             .ok_or(StdioTransportError::Disconnected)?;
 
+        let (result_sender, result_receiver) = oneshot::channel();
         sender
-            .send(message.to_string())
-            .map_err(|e| StdioTransportError::Channel(e.to_string()))?;
+            .send((message.to_string(), result_sender))
+            .map_err(|_| StdioTransportError::Disconnected)?;
 
-        Ok(())
+        // The writer task drops the result sender only when it stopped
+        let result = result_receiver
+            .await
+            .unwrap_or(Err(StdioTransportError::Disconnected));
+        if let Err(e) = &result
+            && self.is_fatal_error(e)
+        {
+            self.connected = false;
+        }
+        result
     }
 
     async fn receive(&mut self) -> Result<String, Self::Error> {
@@ -308,6 +473,15 @@ impl Transport for StdioTransport {
     fn is_connected(&self) -> bool {
         self.connected
     }
+
+    /// A message that failed without writing anything leaves the writer usable,
+    /// unless the stream was closed
+    fn is_fatal_error(&self, error: &Self::Error) -> bool {
+        match error {
+            StdioTransportError::Disconnected | StdioTransportError::PartialWrite { .. } => true,
+            StdioTransportError::Io(e) => is_closed_io_error(e),
+        }
+    }
 }
 
 // ============================================================================
@@ -556,4 +730,156 @@ mod tests {
 
         assert!(state.byte_buffer.is_empty());
     }
+
+    /// Writer accepting at most 3 bytes per write, taking the queued outcomes first
+    ///
+    /// A queued `None` lets one write through, `Some` fails it with that error.
+    #[derive(Clone, Default)]
+    struct FlakyWriter {
+        outcomes: Arc<Mutex<VecDeque<Option<io::ErrorKind>>>>,
+        written: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl FlakyWriter {
+        fn queue(&self, outcomes: impl IntoIterator<Item = Option<io::ErrorKind>>) {
+            self.outcomes.lock().unwrap().extend(outcomes);
+        }
+
+        fn take_written(&self) -> Vec<u8> {
+            std::mem::take(&mut self.written.lock().unwrap())
+        }
+    }
+
+    impl AsyncWrite for FlakyWriter {
+        fn poll_write(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<io::Result<usize>> {
+            if let Some(Some(kind)) = self.outcomes.lock().unwrap().pop_front() {
+                return std::task::Poll::Ready(Err(kind.into()));
+            }
+            let n = buf.len().min(3);
+            self.written.lock().unwrap().extend_from_slice(&buf[..n]);
+            std::task::Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_retries_transient_errors() {
+        let mut writer = FlakyWriter::default();
+        writer.queue([
+            Some(io::ErrorKind::Interrupted),
+            Some(io::ErrorKind::WouldBlock),
+        ]);
+        write_all_with_retry(&mut writer, b"Content-Length: 2")
+            .await
+            .unwrap();
+        assert_eq!(writer.take_written(), b"Content-Length: 2");
+
+        // Retries are bounded, and other errors are not retried
+        writer.queue(vec![
+            Some(io::ErrorKind::Interrupted);
+            MAX_WRITE_RETRIES as usize + 1
+        ]);
+        let failure = write_all_with_retry(&mut writer, b"{}").await.unwrap_err();
+        assert_eq!(failure.error.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(failure.written, 0);
+
+        writer.queue([Some(io::ErrorKind::BrokenPipe)]);
+        let failure = write_all_with_retry(&mut writer, b"{}").await.unwrap_err();
+        assert!(!is_transient_io_error(&failure.error));
+        assert!(writer.outcomes.lock().unwrap().is_empty());
+
+        // Bytes written before the failure are reported
+        writer.queue([None, Some(io::ErrorKind::BrokenPipe)]);
+        let failure = write_all_with_retry(&mut writer, b"{\"id\":1}")
+            .await
+            .unwrap_err();
+        assert_eq!(failure.written, 3);
+    }
+
+    #[tokio::test]
+    async fn test_stdio_transport_reports_write_failures() {
+        let writer = FlakyWriter::default();
+        let (_stdout_sender, stdout_receiver) = mpsc::unbounded_channel();
+        let mut transport = StdioTransport::from_writer(writer.clone(), stdout_receiver);
+
+        // Transient errors persisting through the writer's retries reach the sender
+        writer.queue(vec![
+            Some(io::ErrorKind::Interrupted);
+            MAX_WRITE_RETRIES as usize + 1
+        ]);
+        let error = transport.send("first").await.unwrap_err();
+        assert!(!transport.is_fatal_error(&error), "{error:?}");
+
+        // A message that failed without writing anything does not stop the writer
+        writer.queue([Some(io::ErrorKind::PermissionDenied)]);
+        let error = transport.send("second").await.unwrap_err();
+        assert!(!transport.is_fatal_error(&error), "{error:?}");
+        transport.send("third").await.unwrap();
+        assert_eq!(writer.take_written(), b"third");
+        assert!(transport.is_connected());
+
+        // A message written in part breaks the framing and closes the transport
+        writer.queue([None, Some(io::ErrorKind::Interrupted)]);
+        writer.queue(vec![
+            Some(io::ErrorKind::Interrupted);
+            MAX_WRITE_RETRIES as usize
+        ]);
+        let error = transport.send("fourth").await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                StdioTransportError::PartialWrite {
+                    written: 3,
+                    total: 6,
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        assert!(transport.is_fatal_error(&error));
+        assert!(!transport.is_connected());
+        assert!(matches!(
+            transport.send("fifth").await,
+            Err(StdioTransportError::Disconnected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stdio_transport_closes_on_closed_pipe() {
+        for kind in [io::ErrorKind::BrokenPipe, io::ErrorKind::ConnectionReset] {
+            let writer = FlakyWriter::default();
+            let (_stdout_sender, stdout_receiver) = mpsc::unbounded_channel();
+            let mut transport = StdioTransport::from_writer(writer.clone(), stdout_receiver);
+
+            writer.queue([Some(kind)]);
+            let error = transport.send("first").await.unwrap_err();
+            assert!(transport.is_fatal_error(&error), "{error:?}");
+            assert!(!transport.is_connected());
+
+            // The writer task stopped, so nothing more reaches the dead process
+            transport.connected = true;
+            assert!(matches!(
+                transport.send("second").await,
+                Err(StdioTransportError::Disconnected)
+            ));
+            assert!(writer.take_written().is_empty());
+        }
+    }
 }
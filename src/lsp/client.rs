@@ -7,6 +7,7 @@ use crate::io::transport::Transport;
 use crate::lsp::protocol::{
     JsonRpcClient, JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse,
 };
+use crate::lsp::request_history::RequestHistory;
 use lsp_types::{
     CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
     CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
@@ -19,6 +20,7 @@ use lsp_types::{
     TypeHierarchySubtypesParams, TypeHierarchySupertypesParams, VersionedTextDocumentIdentifier,
     WorkspaceClientCapabilities, WorkspaceSymbol, WorkspaceSymbolParams,
 };
+use std::sync::Arc;
 use tracing::{debug, info};
 
 // ============================================================================
@@ -62,9 +64,12 @@ pub struct LspClient<T: Transport> {
 
 impl<T: Transport + 'static> LspClient<T> {
     /// Create a new LSP client with a transport
-    pub fn new(transport: T) -> Self {
+    ///
+    /// Requests are recorded into `history`, which is shared with whoever reports
+    /// an unexpected server exit.
+    pub fn new(transport: T, history: Arc<RequestHistory>) -> Self {
         Self {
-            rpc_client: JsonRpcClient::new(transport),
+            rpc_client: JsonRpcClient::new(transport, history),
             initialized: false,
             server_capabilities: None,
         }
@@ -104,6 +109,38 @@ impl<T: Transport + 'static> LspClient<T> {
             lsp_types::SymbolKind::TYPE_PARAMETER, // 26 - C++ template parameters
         ]
     }
+
+    /// Semantic token types requested from the server.
+    /// Covers the standard LSP token types, including `comment`.
+    fn supported_semantic_token_types() -> Vec<lsp_types::SemanticTokenType> {
+        use lsp_types::SemanticTokenType;
+
+        vec![
+            SemanticTokenType::NAMESPACE,
+            SemanticTokenType::TYPE,
+            SemanticTokenType::CLASS,
+            SemanticTokenType::ENUM,
+            SemanticTokenType::INTERFACE,
+            SemanticTokenType::STRUCT,
+            SemanticTokenType::TYPE_PARAMETER,
+            SemanticTokenType::PARAMETER,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::EVENT,
+            SemanticTokenType::FUNCTION,
+            SemanticTokenType::METHOD,
+            SemanticTokenType::MACRO,
+            SemanticTokenType::KEYWORD,
+            SemanticTokenType::MODIFIER,
+            SemanticTokenType::COMMENT,
+            SemanticTokenType::STRING,
+            SemanticTokenType::NUMBER,
+            SemanticTokenType::REGEXP,
+            SemanticTokenType::OPERATOR,
+        ]
+    }
+
     /// Executes typed LSP requests using the lsp-types Request trait.
     /// Provides compile-time method validation and eliminates hardcoded strings,
     /// reducing protocol violation risks and improving maintainability.
@@ -141,6 +178,7 @@ impl<T: Transport + 'static> LspClient<T> {
 // LspClientTrait Implementation
 // ============================================================================
 
+use crate::lsp::position_encoding::PositionEncoding;
 use crate::lsp::traits::LspClientTrait;
 
 #[async_trait::async_trait]
@@ -149,6 +187,32 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         self.initialized
     }
 
+    fn position_encoding(&self) -> PositionEncoding {
+        PositionEncoding::from_server_capability(
+            self.server_capabilities
+                .as_ref()
+                .and_then(|capabilities| capabilities.position_encoding.as_ref()),
+        )
+    }
+
+    fn semantic_tokens_legend(&self) -> Option<lsp_types::SemanticTokensLegend> {
+        use lsp_types::SemanticTokensServerCapabilities;
+
+        match self
+            .server_capabilities
+            .as_ref()?
+            .semantic_tokens_provider
+            .as_ref()?
+        {
+            SemanticTokensServerCapabilities::SemanticTokensOptions(options) => {
+                Some(options.legend.clone())
+            }
+            SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+                Some(options.semantic_tokens_options.legend.clone())
+            }
+        }
+    }
+
     // ========================================================================
     // Lifecycle Management
     // ========================================================================
@@ -170,7 +234,8 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             root_path: None, // Deprecated
             #[allow(deprecated)]
             root_uri: root_uri.map(|uri| uri.parse::<lsp_types::Uri>().unwrap()),
-            initialization_options: None,
+            // Per-file parse state is reported through textDocument/clangd.fileStatus
+            initialization_options: Some(serde_json::json!({ "clangdFileStatus": true })),
             work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
             capabilities: ClientCapabilities {
                 workspace: Some(WorkspaceClientCapabilities {
@@ -215,6 +280,27 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
                         hierarchical_document_symbol_support: Some(true),
                         tag_support: None,
                     }),
+                    selection_range: Some(lsp_types::SelectionRangeClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    document_link: Some(lsp_types::DocumentLinkClientCapabilities {
+                        dynamic_registration: Some(false),
+                        tooltip_support: Some(false),
+                    }),
+                    semantic_tokens: Some(lsp_types::SemanticTokensClientCapabilities {
+                        dynamic_registration: Some(false),
+                        requests: lsp_types::SemanticTokensClientCapabilitiesRequests {
+                            range: Some(false),
+                            full: Some(lsp_types::SemanticTokensFullOptions::Bool(true)),
+                        },
+                        token_types: Self::supported_semantic_token_types(),
+                        token_modifiers: Vec::new(),
+                        formats: vec![lsp_types::TokenFormat::RELATIVE],
+                        overlapping_token_support: Some(false),
+                        multiline_token_support: Some(false),
+                        server_cancel_support: None,
+                        augments_syntax_tokens: None,
+                    }),
                     ..Default::default()
                 }),
                 window: Some(
@@ -223,7 +309,11 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
                     }))
                     .unwrap(),
                 ),
-                general: None,
+                general: Some(lsp_types::GeneralClientCapabilities {
+                    // LSP 3.17: prefer UTF-8 columns, which index source text directly
+                    position_encodings: Some(PositionEncoding::client_preferences()),
+                    ..Default::default()
+                }),
                 experimental: None,
                 notebook_document: None,
             },
@@ -236,13 +326,31 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             locale: None,
         };
 
+        // clangd extension capabilities have no lsp-types field
+        let mut params = serde_json::to_value(params)
+            .map_err(|e| LspError::Protocol(format!("Invalid initialize params: {}", e)))?;
+        params["capabilities"]["textDocument"]["inactiveRegionsCapabilities"] =
+            serde_json::json!({ "inactiveRegions": true });
+
         // Send initialize request
-        let result = self
-            .request::<lsp_types::request::Initialize>(params)
-            .await?;
+        let method = <lsp_types::request::Initialize as lsp_types::request::Request>::METHOD;
+        let result: lsp_types::InitializeResult =
+            match self.rpc_client.request(method, Some(params)).await {
+                Ok(result) => result,
+                Err(JsonRpcError::Timeout) => {
+                    return Err(LspError::RequestTimeout {
+                        method: method.to_string(),
+                    });
+                }
+                Err(e) => return Err(LspError::JsonRpc(e)),
+            };
 
         debug!("LSP server capabilities: {:?}", result.capabilities);
         self.server_capabilities = Some(result.capabilities.clone());
+        info!(
+            "Negotiated position encoding: {:?}",
+            self.position_encoding()
+        );
 
         // Complete initialization
         let initialized_params = InitializedParams {};
@@ -341,6 +449,24 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         uri: lsp_types::Uri,
         version: i32,
         text: String,
+    ) -> Result<(), LspError> {
+        self.change_text_document_incremental(
+            uri,
+            version,
+            vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text,
+            }],
+        )
+        .await
+    }
+
+    async fn change_text_document_incremental(
+        &mut self,
+        uri: lsp_types::Uri,
+        version: i32,
+        changes: Vec<TextDocumentContentChangeEvent>,
     ) -> Result<(), LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
@@ -348,16 +474,14 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
 
         let params = DidChangeTextDocumentParams {
             text_document: VersionedTextDocumentIdentifier { uri, version },
-            content_changes: vec![TextDocumentContentChangeEvent {
-                range: None,
-                range_length: None,
-                text,
-            }],
+            content_changes: changes,
         };
 
         debug!(
-            "Changing text document: {:?} (version {})",
-            params.text_document.uri, params.text_document.version
+            "Changing text document: {:?} (version {}, {} changes)",
+            params.text_document.uri,
+            params.text_document.version,
+            params.content_changes.len()
         );
         self.notify::<lsp_types::notification::DidChangeTextDocument>(params)
             .await?;
@@ -365,11 +489,49 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         Ok(())
     }
 
+    // ========================================================================
+    // Extension Methods
+    // ========================================================================
+
+    async fn raw_request(
+        &mut self,
+        method: String,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        debug!("Sending raw request: {}", method);
+        match self.rpc_client.request(&method, Some(params)).await {
+            Ok(result) => Ok(result),
+            // A null result is reported as missing by the JSON-RPC layer
+            Err(JsonRpcError::MissingResult) => Ok(serde_json::Value::Null),
+            Err(JsonRpcError::Timeout) => Err(LspError::RequestTimeout { method }),
+            Err(e) => Err(LspError::JsonRpc(e)),
+        }
+    }
+
+    async fn change_configuration(&mut self, settings: serde_json::Value) -> Result<(), LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        debug!("Changing configuration: {}", settings);
+        self.notify::<lsp_types::notification::DidChangeConfiguration>(
+            lsp_types::DidChangeConfigurationParams { settings },
+        )
+        .await
+    }
+
     // ========================================================================
     // Symbol and Navigation Methods
     // ========================================================================
 
-    async fn workspace_symbols(&mut self, query: String) -> Result<Vec<WorkspaceSymbol>, LspError> {
+    async fn workspace_symbols(
+        &mut self,
+        query: String,
+    ) -> Result<Option<Vec<WorkspaceSymbol>>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -402,12 +564,12 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
                         data: None,
                     })
                     .collect();
-                Ok(workspace_symbols)
+                Ok(Some(workspace_symbols))
             }
             Some(lsp_types::WorkspaceSymbolResponse::Nested(workspace_symbols)) => {
-                Ok(workspace_symbols)
+                Ok(Some(workspace_symbols))
             }
-            None => Ok(vec![]),
+            None => Ok(None),
         }
     }
 
@@ -415,7 +577,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         &mut self,
         uri: lsp_types::Uri,
         position: Position,
-    ) -> Result<GotoDefinitionResponse, LspError> {
+    ) -> Result<Option<GotoDefinitionResponse>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -438,14 +600,14 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::GotoDefinition>(params)
             .await?;
 
-        Ok(result.unwrap_or(lsp_types::GotoDefinitionResponse::Array(vec![])))
+        Ok(result)
     }
 
     async fn text_document_declaration(
         &mut self,
         uri: lsp_types::Uri,
         position: Position,
-    ) -> Result<lsp_types::request::GotoDeclarationResponse, LspError> {
+    ) -> Result<Option<lsp_types::request::GotoDeclarationResponse>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -468,7 +630,67 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::GotoDeclaration>(params)
             .await?;
 
-        Ok(result.unwrap_or(lsp_types::request::GotoDeclarationResponse::Array(vec![])))
+        Ok(result)
+    }
+
+    async fn text_document_type_definition(
+        &mut self,
+        uri: lsp_types::Uri,
+        position: Position,
+    ) -> Result<Option<lsp_types::request::GotoTypeDefinitionResponse>, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        let params = lsp_types::request::GotoTypeDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        debug!(
+            "Requesting type definition at {:?}:{:?}",
+            params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position
+        );
+        let result = self
+            .request::<lsp_types::request::GotoTypeDefinition>(params)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn text_document_implementation(
+        &mut self,
+        uri: lsp_types::Uri,
+        position: Position,
+    ) -> Result<Option<lsp_types::request::GotoImplementationResponse>, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        let params = lsp_types::request::GotoImplementationParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        debug!(
+            "Requesting implementation at {:?}:{:?}",
+            params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position
+        );
+        let result = self
+            .request::<lsp_types::request::GotoImplementation>(params)
+            .await?;
+
+        Ok(result)
     }
 
     async fn text_document_references(
@@ -476,7 +698,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         uri: lsp_types::Uri,
         position: Position,
         include_declaration: bool,
-    ) -> Result<Vec<Location>, LspError> {
+    ) -> Result<Option<Vec<Location>>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -503,7 +725,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::References>(params)
             .await?;
 
-        Ok(result.unwrap_or_default())
+        Ok(result)
     }
 
     async fn text_document_hover(
@@ -535,10 +757,87 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         Ok(result)
     }
 
+    async fn text_document_selection_range(
+        &mut self,
+        uri: lsp_types::Uri,
+        positions: Vec<Position>,
+    ) -> Result<Option<Vec<lsp_types::SelectionRange>>, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        let params = lsp_types::SelectionRangeParams {
+            text_document: TextDocumentIdentifier { uri },
+            positions,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        debug!(
+            "Requesting selection ranges for {:?} at {:?}",
+            params.text_document.uri, params.positions
+        );
+        let result = self
+            .request::<lsp_types::request::SelectionRangeRequest>(params)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn text_document_document_link(
+        &mut self,
+        uri: lsp_types::Uri,
+    ) -> Result<Option<Vec<lsp_types::DocumentLink>>, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        let params = lsp_types::DocumentLinkParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        debug!(
+            "Requesting document links for {:?}",
+            params.text_document.uri
+        );
+        let result = self
+            .request::<lsp_types::request::DocumentLinkRequest>(params)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn text_document_semantic_tokens_full(
+        &mut self,
+        uri: lsp_types::Uri,
+    ) -> Result<Option<lsp_types::SemanticTokensResult>, LspError> {
+        if !self.initialized {
+            return Err(LspError::NotInitialized);
+        }
+
+        let params = lsp_types::SemanticTokensParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        debug!(
+            "Requesting semantic tokens for: {:?}",
+            params.text_document.uri
+        );
+        let result = self
+            .request::<lsp_types::request::SemanticTokensFullRequest>(params)
+            .await?;
+
+        Ok(result)
+    }
+
     async fn text_document_document_symbol(
         &mut self,
         uri: lsp_types::Uri,
-    ) -> Result<DocumentSymbolResponse, LspError> {
+    ) -> Result<Option<DocumentSymbolResponse>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -557,7 +856,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::DocumentSymbolRequest>(params)
             .await?;
 
-        Ok(result.unwrap_or(lsp_types::DocumentSymbolResponse::Flat(vec![])))
+        Ok(result)
     }
 
     // ========================================================================
@@ -568,7 +867,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
         &mut self,
         uri: lsp_types::Uri,
         position: Position,
-    ) -> Result<Vec<CallHierarchyItem>, LspError> {
+    ) -> Result<Option<Vec<CallHierarchyItem>>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -590,13 +889,13 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::CallHierarchyPrepare>(params)
             .await?;
 
-        Ok(result.unwrap_or_default())
+        Ok(result)
     }
 
     async fn call_hierarchy_incoming_calls(
         &mut self,
         item: CallHierarchyItem,
-    ) -> Result<Vec<CallHierarchyIncomingCall>, LspError> {
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -612,13 +911,13 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::CallHierarchyIncomingCalls>(params)
             .await?;
 
-        Ok(result.unwrap_or_default())
+        Ok(result)
     }
 
     async fn call_hierarchy_outgoing_calls(
         &mut self,
         item: CallHierarchyItem,
-    ) -> Result<Vec<CallHierarchyOutgoingCall>, LspError> {
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>, LspError> {
         if !self.initialized {
             return Err(LspError::NotInitialized);
         }
@@ -634,7 +933,7 @@ impl<T: Transport + 'static> LspClientTrait for LspClient<T> {
             .request::<lsp_types::request::CallHierarchyOutgoingCalls>(params)
             .await?;
 
-        Ok(result.unwrap_or_default())
+        Ok(result)
     }
 
     // ========================================================================
@@ -736,7 +1035,7 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Box::pin(async {
-                    Ok(vec![
+                    Ok(Some(vec![
                         WorkspaceSymbol {
                             name: "MockFunction".to_string(),
                             kind: SymbolKind::FUNCTION,
@@ -777,14 +1076,14 @@ mod tests {
                             }),
                             data: None,
                         },
-                    ])
+                    ]))
                 })
             });
 
         let result = client.workspace_symbols("test".to_string()).await;
         assert!(result.is_ok());
 
-        let symbols = result.unwrap();
+        let symbols = result.unwrap().unwrap();
         assert_eq!(symbols.len(), 2);
         assert_eq!(symbols[0].name, "MockFunction");
         assert_eq!(symbols[1].name, "MockClass");
@@ -822,7 +1121,7 @@ mod tests {
             .times(1)
             .returning(|_, _| {
                 Box::pin(async {
-                    Ok(GotoDefinitionResponse::Scalar(Location {
+                    Ok(Some(GotoDefinitionResponse::Scalar(Location {
                         uri: "file:///mock/definition.cpp"
                             .parse::<lsp_types::Uri>()
                             .unwrap(),
@@ -836,7 +1135,7 @@ mod tests {
                                 character: 20,
                             },
                         },
-                    }))
+                    })))
                 })
             });
 
@@ -848,7 +1147,7 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        match result.unwrap() {
+        match result.unwrap().unwrap() {
             GotoDefinitionResponse::Scalar(location) => {
                 assert_eq!(location.uri.to_string(), "file:///mock/definition.cpp");
                 assert_eq!(location.range.start.line, 42);
@@ -876,7 +1175,7 @@ mod tests {
             .times(1)
             .returning(|_, _, _| {
                 Box::pin(async {
-                    Ok(vec![
+                    Ok(Some(vec![
                         Location {
                             uri: "file:///mock/usage1.cpp".parse::<lsp_types::Uri>().unwrap(),
                             range: Range {
@@ -903,7 +1202,7 @@ mod tests {
                                 },
                             },
                         },
-                    ])
+                    ]))
                 })
             });
 
@@ -916,7 +1215,7 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        let references = result.unwrap();
+        let references = result.unwrap().unwrap();
         assert_eq!(references.len(), 2);
         assert_eq!(references[0].uri.to_string(), "file:///mock/usage1.cpp");
         assert_eq!(references[1].uri.to_string(), "file:///mock/usage2.cpp");
@@ -980,27 +1279,29 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Box::pin(async {
-                    Ok(DocumentSymbolResponse::Flat(vec![SymbolInformation {
-                        name: "MockSymbol".to_string(),
-                        kind: SymbolKind::FUNCTION,
-                        tags: None,
-                        #[allow(deprecated)]
-                        deprecated: None,
-                        location: Location {
-                            uri: "file:///mock/file.cpp".parse::<lsp_types::Uri>().unwrap(),
-                            range: Range {
-                                start: Position {
-                                    line: 5,
-                                    character: 0,
-                                },
-                                end: Position {
-                                    line: 10,
-                                    character: 0,
+                    Ok(Some(DocumentSymbolResponse::Flat(vec![
+                        SymbolInformation {
+                            name: "MockSymbol".to_string(),
+                            kind: SymbolKind::FUNCTION,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            location: Location {
+                                uri: "file:///mock/file.cpp".parse::<lsp_types::Uri>().unwrap(),
+                                range: Range {
+                                    start: Position {
+                                        line: 5,
+                                        character: 0,
+                                    },
+                                    end: Position {
+                                        line: 10,
+                                        character: 0,
+                                    },
                                 },
                             },
+                            container_name: Some("MockContainer".to_string()),
                         },
-                        container_name: Some("MockContainer".to_string()),
-                    }]))
+                    ])))
                 })
             });
 
@@ -1009,7 +1310,7 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        match result.unwrap() {
+        match result.unwrap().unwrap() {
             DocumentSymbolResponse::Flat(symbols) => {
                 assert_eq!(symbols.len(), 1);
                 assert_eq!(symbols[0].name, "MockSymbol");
@@ -1039,7 +1340,7 @@ mod tests {
             .times(1)
             .returning(|_, _| {
                 Box::pin(async {
-                    Ok(vec![CallHierarchyItem {
+                    Ok(Some(vec![CallHierarchyItem {
                         name: "mockFunction".to_string(),
                         kind: SymbolKind::FUNCTION,
                         tags: None,
@@ -1066,7 +1367,7 @@ mod tests {
                             },
                         },
                         data: None,
-                    }])
+                    }]))
                 })
             });
 
@@ -1078,7 +1379,7 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        let items = result.unwrap();
+        let items = result.unwrap().unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].name, "mockFunction");
         assert_eq!(items[0].kind, SymbolKind::FUNCTION);
@@ -1124,7 +1425,7 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Box::pin(async {
-                    Ok(vec![CallHierarchyIncomingCall {
+                    Ok(Some(vec![CallHierarchyIncomingCall {
                         from: CallHierarchyItem {
                             name: "callerFunction".to_string(),
                             kind: SymbolKind::FUNCTION,
@@ -1163,14 +1464,14 @@ mod tests {
                                 character: 8,
                             },
                         }],
-                    }])
+                    }]))
                 })
             });
 
         let result = client.call_hierarchy_incoming_calls(item).await;
         assert!(result.is_ok());
 
-        let calls = result.unwrap();
+        let calls = result.unwrap().unwrap();
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].from.name, "callerFunction");
     }
@@ -1215,7 +1516,7 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Box::pin(async {
-                    Ok(vec![CallHierarchyOutgoingCall {
+                    Ok(Some(vec![CallHierarchyOutgoingCall {
                         to: CallHierarchyItem {
                             name: "calleeFunction".to_string(),
                             kind: SymbolKind::FUNCTION,
@@ -1254,14 +1555,14 @@ mod tests {
                                 character: 21,
                             },
                         }],
-                    }])
+                    }]))
                 })
             });
 
         let result = client.call_hierarchy_outgoing_calls(item).await;
         assert!(result.is_ok());
 
-        let calls = result.unwrap();
+        let calls = result.unwrap().unwrap();
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].to.name, "calleeFunction");
     }
@@ -196,6 +196,13 @@ impl<T: Transport> Transport for LspFraming<T> {
     fn is_connected(&self) -> bool {
         self.transport.is_connected()
     }
+
+    fn is_fatal_error(&self, error: &Self::Error) -> bool {
+        match error {
+            LspFramingError::Transport(e) => self.transport.is_fatal_error(e),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -13,7 +13,11 @@
 pub mod client;
 pub mod framing;
 pub mod jsonrpc_utils;
+pub mod position_encoding;
 pub mod protocol;
+pub mod reference_cache;
+pub mod request_coalescer;
+pub mod request_history;
 pub mod traits;
 
 #[cfg(test)]
@@ -32,7 +36,7 @@ pub mod testing;
 //
 // // Create transport and client
 // let transport = process.create_stdio_transport()?;
-// let mut client = LspClient::new(transport);
+// let mut client = LspClient::new(transport, Arc::new(RequestHistory::new()));
 //
 // // Initialize LSP
 // client.initialize(Some("file:///path/to/project".to_string())).await?;
@@ -47,3 +51,5 @@ pub mod testing;
 // Re-export main types for convenience
 
 pub use client::{LspClient, LspError};
+pub use position_encoding::PositionEncoding;
+pub use request_coalescer::RequestCoalescer;
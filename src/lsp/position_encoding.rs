@@ -0,0 +1,140 @@
+//! Position encoding negotiated with the language server
+//!
+//! LSP positions count characters in UTF-16 code units unless client and server
+//! agree on another encoding through `positionEncoding` (LSP 3.17). Source text is
+//! handled as UTF-8 here, so every column computed from source text, or applied to
+//! it, has to be converted with the negotiated encoding.
+
+use lsp_types::PositionEncodingKind;
+
+/// Unit in which LSP position characters are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    /// UTF-8 bytes
+    Utf8,
+    /// UTF-16 code units (the LSP default)
+    #[default]
+    Utf16,
+    /// Unicode code points
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Encodings offered to the server, most preferred first
+    pub fn client_preferences() -> Vec<PositionEncodingKind> {
+        vec![
+            PositionEncodingKind::UTF8,
+            PositionEncodingKind::UTF16,
+            PositionEncodingKind::UTF32,
+        ]
+    }
+
+    /// Encoding selected by the server in its capabilities
+    ///
+    /// Servers that do not report an encoding use UTF-16.
+    pub fn from_server_capability(kind: Option<&PositionEncodingKind>) -> Self {
+        match kind.map(PositionEncodingKind::as_str) {
+            Some("utf-8") => Self::Utf8,
+            Some("utf-32") => Self::Utf32,
+            _ => Self::Utf16,
+        }
+    }
+
+    /// Number of position units taken by a character
+    fn char_len(self, c: char) -> usize {
+        match self {
+            Self::Utf8 => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+
+    /// Position column of a byte offset within a line
+    ///
+    /// Offsets inside a multi-byte character count up to the start of that character.
+    pub fn column(self, line: &str, byte_offset: usize) -> u32 {
+        line.char_indices()
+            .take_while(|(index, _)| *index < byte_offset)
+            .map(|(_, c)| self.char_len(c))
+            .sum::<usize>() as u32
+    }
+
+    /// Byte offset of a position column within a line
+    ///
+    /// A column pointing into the middle of a character (e.g. between the two
+    /// UTF-16 units of a surrogate pair) resolves to the start of that character.
+    ///
+    /// # Returns
+    /// * `Some(offset)` - Byte offset in `line`
+    /// * `None` - Column lies past the end of the line
+    pub fn byte_offset(self, line: &str, column: u32) -> Option<usize> {
+        let column = column as usize;
+        let mut units = 0;
+        for (index, c) in line.char_indices() {
+            if units >= column {
+                return Some(index);
+            }
+            units += self.char_len(c);
+            if units > column {
+                return Some(index);
+            }
+        }
+        (units == column).then_some(line.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "ü" is 2 UTF-8 bytes / 1 UTF-16 unit, "😀" is 4 UTF-8 bytes / 2 UTF-16 units
+    const LINE: &str = "int größe😀 = 1;";
+
+    #[test]
+    fn test_column_per_encoding() {
+        let byte_offset = LINE.find(" =").unwrap();
+        assert_eq!(PositionEncoding::Utf8.column(LINE, byte_offset), 15);
+        assert_eq!(PositionEncoding::Utf16.column(LINE, byte_offset), 11);
+        assert_eq!(PositionEncoding::Utf32.column(LINE, byte_offset), 10);
+    }
+
+    #[test]
+    fn test_byte_offset_round_trip() {
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            for (byte_offset, _) in LINE.char_indices() {
+                let column = encoding.column(LINE, byte_offset);
+                assert_eq!(encoding.byte_offset(LINE, column), Some(byte_offset));
+            }
+            let end = encoding.column(LINE, LINE.len());
+            assert_eq!(encoding.byte_offset(LINE, end), Some(LINE.len()));
+            assert_eq!(encoding.byte_offset(LINE, end + 1), None);
+        }
+    }
+
+    #[test]
+    fn test_byte_offset_inside_character() {
+        // Second UTF-16 unit of the emoji
+        let emoji = LINE.find('😀').unwrap();
+        let column = PositionEncoding::Utf16.column(LINE, emoji) + 1;
+        assert_eq!(
+            PositionEncoding::Utf16.byte_offset(LINE, column),
+            Some(emoji)
+        );
+    }
+
+    #[test]
+    fn test_from_server_capability() {
+        assert_eq!(
+            PositionEncoding::from_server_capability(Some(&PositionEncodingKind::UTF8)),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(
+            PositionEncoding::from_server_capability(None),
+            PositionEncoding::Utf16
+        );
+    }
+}
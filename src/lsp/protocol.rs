@@ -5,6 +5,7 @@
 
 use crate::io::transport::Transport;
 use crate::lsp::framing::LspFraming;
+use crate::lsp::request_history::{RequestHistory, RequestOutcome};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -104,6 +105,7 @@ pub enum JsonRpcError {
         data: Option<serde_json::Value>,
     },
 
+    /// The message could not be sent; the connection is still usable
     #[error("Transport error: {0}")]
     Transport(String),
 
@@ -121,6 +123,10 @@ pub enum JsonRpcError {
 
     #[error("Missing result in response")]
     MissingResult,
+
+    /// The server side of the transport is gone; the server must be restarted
+    #[error("Server closed the connection; recent requests, oldest first:\n{recent_requests}")]
+    TransportClosed { recent_requests: String },
 }
 
 // ============================================================================
@@ -133,6 +139,21 @@ type NotificationHandler = Arc<dyn Fn(JsonRpcNotification) + Send + Sync>;
 /// Type alias for request handler to reduce complexity
 type RequestHandler = Arc<dyn Fn(JsonRpcRequest) -> JsonRpcResponse + Send + Sync>;
 
+/// Serialized message waiting to be sent
+struct OutboundMessage {
+    /// Id of the request the message carries, failed when sending gives up
+    request_id: Option<u64>,
+    content: String,
+}
+
+/// Why a message could not be sent
+enum SendFailure {
+    /// Only this message is lost, the transport remains usable
+    Message(String),
+    /// The transport is unusable
+    Closed(String),
+}
+
 // ============================================================================
 // Message Classification
 // ============================================================================
@@ -208,12 +229,16 @@ struct ClientState {
     request_handler: Option<RequestHandler>,
     /// Pending requests waiting for responses
     pending_requests: HashMap<u64, mpsc::UnboundedSender<JsonRpcResponse>>,
+    /// Errors of requests that could not be sent, until their callers pick them up
+    failed_sends: HashMap<u64, String>,
+    /// Whether the server side of the transport is gone
+    connection_closed: bool,
 }
 
 /// JSON-RPC client with request/response correlation
 pub struct JsonRpcClient<T: Transport> {
     /// Channel for sending outbound messages (requests and notifications)
-    outbound_sender: mpsc::UnboundedSender<String>,
+    outbound_sender: mpsc::UnboundedSender<OutboundMessage>,
 
     /// Request ID counter
     request_id: AtomicU64,
@@ -221,16 +246,19 @@ pub struct JsonRpcClient<T: Transport> {
     /// Unified client state (single mutex instead of multiple)
     state: Arc<Mutex<ClientState>>,
 
+    /// Most recent requests, reported when the server closes the connection
+    history: Arc<RequestHistory>,
+
     /// Type parameter marker
     _phantom: std::marker::PhantomData<T>,
 }
 
 impl<T: Transport + 'static> JsonRpcClient<T> {
-    /// Create a new JSON-RPC client
-    pub fn new(transport: T) -> Self {
+    /// Create a new JSON-RPC client recording its requests into `history`
+    pub fn new(transport: T, history: Arc<RequestHistory>) -> Self {
         let framed_transport = LspFraming::new(transport);
         let transport_arc = Arc::new(Mutex::new(framed_transport));
-        let (outbound_sender, mut outbound_receiver) = mpsc::unbounded_channel::<String>();
+        let (outbound_sender, mut outbound_receiver) = mpsc::unbounded_channel::<OutboundMessage>();
 
         // Unified state - single Arc instead of multiple
         let state = Arc::new(Mutex::new(ClientState::default()));
@@ -239,6 +267,7 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
         let transport_for_task = Arc::clone(&transport_arc);
         let state_for_task = Arc::clone(&state);
         let outbound_sender_for_task = outbound_sender.clone();
+        let history_for_task = Arc::clone(&history);
 
         tokio::spawn(async move {
             loop {
@@ -246,12 +275,27 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
                     // Outbound messages (prioritized)
                     Some(message) = outbound_receiver.recv() => {
                         let mut transport = transport_for_task.lock().await;
-                        if let Err(e) = transport.send(&message).await {
-                            error!("Failed to send message: {}", e);
-                            break;
-                        }
+                        let result = Self::send_message(&mut transport, &message.content).await;
                         // Release lock immediately
                         drop(transport);
+                        match result {
+                            Ok(()) => {}
+                            Err(SendFailure::Message(e)) => {
+                                error!("Failed to send message: {}", e);
+                                if let Some(id) = message.request_id {
+                                    let mut state = state_for_task.lock().await;
+                                    // Dropping the sender wakes up the waiting request
+                                    if state.pending_requests.remove(&id).is_some() {
+                                        state.failed_sends.insert(id, e);
+                                    }
+                                }
+                            }
+                            Err(SendFailure::Closed(e)) => {
+                                error!("Failed to send message: {}", e);
+                                Self::connection_closed(&state_for_task, &history_for_task).await;
+                                break;
+                            }
+                        }
                     }
                     // Inbound messages
                     result = async {
@@ -264,6 +308,7 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
                             }
                             Err(e) => {
                                 error!("Failed to receive message: {}", e);
+                                Self::connection_closed(&state_for_task, &history_for_task).await;
                                 break;
                             }
                         }
@@ -277,10 +322,47 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
             outbound_sender,
             request_id: AtomicU64::new(1),
             state,
+            history,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Send a message once; the transport has already retried transient errors
+    async fn send_message(transport: &mut LspFraming<T>, message: &str) -> Result<(), SendFailure> {
+        transport.send(message).await.map_err(|e| {
+            if transport.is_fatal_error(&e) {
+                SendFailure::Closed(e.to_string())
+            } else {
+                SendFailure::Message(e.to_string())
+            }
+        })
+    }
+
+    /// Fail pending and future requests after the server side of the transport is gone
+    ///
+    /// Waiting requests return immediately instead of running into their timeout.
+    async fn connection_closed(state: &Arc<Mutex<ClientState>>, history: &RequestHistory) {
+        {
+            let mut state = state.lock().await;
+            state.connection_closed = true;
+            // Dropping the senders wakes up the waiting requests
+            state.pending_requests.clear();
+        }
+        if !history.shutdown_requested() {
+            error!(
+                "Server closed the connection unexpectedly. Recent requests, oldest first:\n{}",
+                history.report()
+            );
+        }
+    }
+
+    /// Error returned for requests on a closed connection
+    fn connection_closed_error(&self) -> JsonRpcError {
+        JsonRpcError::TransportClosed {
+            recent_requests: self.history.report(),
+        }
+    }
+
     /// Set notification handler
     pub async fn on_notification<F>(&self, handler: F)
     where
@@ -303,7 +385,7 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
     async fn process_inbound_message(
         message: String,
         state: &Arc<Mutex<ClientState>>,
-        outbound_sender: &mpsc::UnboundedSender<String>,
+        outbound_sender: &mpsc::UnboundedSender<OutboundMessage>,
     ) {
         trace!("JsonRpcClient: Received {} bytes", message.len());
 
@@ -337,7 +419,11 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
                         }
                     };
 
-                    if outbound_sender.send(response_json).is_err() {
+                    let message = OutboundMessage {
+                        request_id: None,
+                        content: response_json,
+                    };
+                    if outbound_sender.send(message).is_err() {
                         debug!("Failed to send response back to server");
                     }
                 } else {
@@ -423,6 +509,9 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
         // Register pending request
         {
             let mut state = self.state.lock().await;
+            if state.connection_closed {
+                return Err(self.connection_closed_error());
+            }
             state.pending_requests.insert(id, response_sender);
         }
 
@@ -439,10 +528,14 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
         // Send request
         let request_json = serde_json::to_string(&request).map_err(JsonRpcError::Serialization)?;
         debug!("JsonRpcClient: Sending request: {}", request_json);
+        self.history.record(id, method, request.params.as_ref());
 
         // Send through the channel
         self.outbound_sender
-            .send(request_json)
+            .send(OutboundMessage {
+                request_id: Some(id),
+                content: request_json,
+            })
             .map_err(|_| JsonRpcError::Transport("Outbound channel closed".to_string()))?;
 
         // Wait for response with timeout
@@ -455,17 +548,34 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
                 // Channel closed - clean up pending request
                 let mut state = self.state.lock().await;
                 state.pending_requests.remove(&id);
+                if state.connection_closed {
+                    // Left pending in the history: the server never answered
+                    return Err(self.connection_closed_error());
+                }
+                self.history.finish(id, RequestOutcome::Failed);
+                if let Some(error) = state.failed_sends.remove(&id) {
+                    return Err(JsonRpcError::Transport(error));
+                }
                 return Err(JsonRpcError::RequestCancelled);
             }
             Err(_) => {
                 // Timeout - clean up pending request
                 let mut state = self.state.lock().await;
                 state.pending_requests.remove(&id);
+                self.history.finish(id, RequestOutcome::TimedOut);
                 return Err(JsonRpcError::Timeout);
             }
         };
 
         // Handle response
+        self.history.finish(
+            id,
+            if response.error.is_some() {
+                RequestOutcome::Failed
+            } else {
+                RequestOutcome::Completed
+            },
+        );
         if let Some(error) = response.error {
             return Err(JsonRpcError::Server {
                 code: error.code,
@@ -503,7 +613,10 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
 
         // Send via channel
         self.outbound_sender
-            .send(notification_json)
+            .send(OutboundMessage {
+                request_id: None,
+                content: notification_json,
+            })
             .map_err(|_| JsonRpcError::Transport("Outbound channel closed".to_string()))?;
 
         Ok(())
@@ -538,3 +651,137 @@ impl<T: Transport + 'static> JsonRpcClient<T> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::transport::StdioTransport;
+
+    #[tokio::test]
+    async fn test_requests_fail_with_history_when_server_exits() {
+        let (client_tx, mut client_rx) = mpsc::unbounded_channel::<String>();
+        let (server_tx, server_rx) = mpsc::unbounded_channel::<String>();
+        // The server reads one request and exits without answering
+        tokio::spawn(async move {
+            client_rx.recv().await;
+            drop(server_tx);
+        });
+
+        let history = Arc::new(RequestHistory::new());
+        let mut client = JsonRpcClient::new(
+            StdioTransport::from_channels(client_tx, server_rx),
+            Arc::clone(&history),
+        );
+        let params = serde_json::json!({
+            "textDocument": {"uri": "file:///src/templates.cpp"},
+            "position": {"line": 41, "character": 7}
+        });
+
+        let started = std::time::Instant::now();
+        let error = client
+            .request::<_, Value>("textDocument/hover", Some(params))
+            .await
+            .unwrap_err();
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        let JsonRpcError::TransportClosed { recent_requests } = &error else {
+            panic!("Unexpected error: {error:?}");
+        };
+        assert!(
+            recent_requests.contains("textDocument/hover file:///src/templates.cpp:42:8 (pending")
+        );
+
+        // Later requests fail right away with the same history
+        let error = client
+            .request::<Value, Value>("workspace/symbol", None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("textDocument/hover"));
+        assert_eq!(history.recent().len(), 1);
+    }
+
+    /// Transport whose sends fail without closing it while `failures` is positive
+    struct FlakyTransport {
+        failures: Arc<AtomicU64>,
+        outgoing: mpsc::UnboundedSender<String>,
+        incoming: mpsc::UnboundedReceiver<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for FlakyTransport {
+        type Error = std::io::Error;
+
+        async fn send(&mut self, message: &str) -> Result<(), Self::Error> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            self.outgoing
+                .send(message.to_string())
+                .map_err(|_| std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        async fn receive(&mut self) -> Result<String, Self::Error> {
+            self.incoming
+                .recv()
+                .await
+                .ok_or_else(|| std::io::ErrorKind::UnexpectedEof.into())
+        }
+
+        async fn close(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn is_fatal_error(&self, error: &Self::Error) -> bool {
+            error.kind() == std::io::ErrorKind::BrokenPipe
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_sends_do_not_poison_client() {
+        let (client_tx, mut client_rx) = mpsc::unbounded_channel::<String>();
+        let (server_tx, server_rx) = mpsc::unbounded_channel::<String>();
+        // The server answers every request with its method name
+        tokio::spawn(async move {
+            while let Some(framed) = client_rx.recv().await {
+                let (_, content) = framed.split_once("\r\n\r\n").unwrap();
+                let request: JsonRpcRequest = serde_json::from_str(content).unwrap();
+                let response = serde_json::json!({
+                    "jsonrpc": "2.0", "id": request.id, "result": request.method
+                })
+                .to_string();
+                let framed = format!("Content-Length: {}\r\n\r\n{}", response.len(), response);
+                if server_tx.send(framed).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let failures = Arc::new(AtomicU64::new(1));
+        let mut client = JsonRpcClient::new(
+            FlakyTransport {
+                failures: Arc::clone(&failures),
+                outgoing: client_tx,
+                incoming: server_rx,
+            },
+            Arc::new(RequestHistory::new()),
+        );
+
+        // A failed send fails only the affected request, without sending it again
+        let error = client
+            .request::<Value, String>("first", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, JsonRpcError::Transport(_)), "{error:?}");
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
+
+        let result: String = client.request::<Value, _>("second", None).await.unwrap();
+        assert_eq!(result, "second");
+    }
+}
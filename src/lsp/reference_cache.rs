@@ -0,0 +1,133 @@
+//! Reference results cached per index generation
+//!
+//! Usage analyses of the same hot symbols repeat `textDocument/references`
+//! requests whose answer only changes when the index or a file changes. The
+//! cache keeps each answer together with the index generation it was computed
+//! at; the owner advances the generation whenever indexing results or file
+//! contents change, which invalidates every earlier answer at once.
+
+use lsp_types::{Location, Position, Uri};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::trace;
+
+/// Maximum number of cached reference results; the cache is cleared when full
+const MAX_CACHED_REFERENCES: usize = 512;
+
+/// Identifies a references request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReferenceKey {
+    pub uri: String,
+    pub line: u32,
+    pub character: u32,
+    pub include_declaration: bool,
+}
+
+impl ReferenceKey {
+    pub fn new(uri: &Uri, position: Position, include_declaration: bool) -> Self {
+        Self {
+            uri: uri.to_string(),
+            line: position.line,
+            character: position.character,
+            include_declaration,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// Generation the cached results were computed at
+    generation: u64,
+    results: HashMap<ReferenceKey, Vec<Location>>,
+}
+
+/// Reference results of the current index generation
+#[derive(Default)]
+pub struct ReferenceCache {
+    state: Mutex<CacheState>,
+}
+
+impl ReferenceCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the result cached for a request at the given generation
+    pub fn get(&self, generation: u64, key: &ReferenceKey) -> Option<Vec<Location>> {
+        let state = self.state.lock().unwrap();
+        if state.generation != generation {
+            return None;
+        }
+        let cached = state.results.get(key).cloned();
+        if cached.is_some() {
+            trace!(
+                "Reference cache hit for {:?} (generation {})",
+                key, generation
+            );
+        }
+        cached
+    }
+
+    /// Store the result of a request computed at the given generation
+    ///
+    /// Results of an older generation than the cached ones are dropped; a newer
+    /// generation discards everything cached before it.
+    pub fn insert(&self, generation: u64, key: ReferenceKey, locations: Vec<Location>) {
+        let mut state = self.state.lock().unwrap();
+        if generation < state.generation {
+            return;
+        }
+        if generation > state.generation || state.results.len() >= MAX_CACHED_REFERENCES {
+            state.results.clear();
+            state.generation = generation;
+        }
+        state.results.insert(key, locations);
+    }
+
+    /// Number of cached results
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().results.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn key(line: u32) -> ReferenceKey {
+        ReferenceKey::new(
+            &Uri::from_str("file:///src/a.cpp").unwrap(),
+            Position { line, character: 4 },
+            false,
+        )
+    }
+
+    fn location(line: u32) -> Location {
+        Location {
+            uri: Uri::from_str("file:///src/b.cpp").unwrap(),
+            range: lsp_types::Range::new(Position::new(line, 0), Position::new(line, 3)),
+        }
+    }
+
+    #[test]
+    fn test_results_are_invalidated_by_newer_generation() {
+        let cache = ReferenceCache::new();
+        cache.insert(1, key(1), vec![location(10)]);
+        cache.insert(1, key(2), vec![]);
+
+        assert_eq!(cache.get(1, &key(1)), Some(vec![location(10)]));
+        assert_eq!(cache.get(1, &key(2)), Some(vec![]));
+        assert_eq!(cache.get(1, &key(3)), None);
+        assert_eq!(cache.get(2, &key(1)), None);
+
+        // A result computed before the generation advanced must not be kept
+        cache.insert(2, key(1), vec![location(11)]);
+        cache.insert(1, key(2), vec![location(12)]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(2, &key(1)), Some(vec![location(11)]));
+        assert_eq!(cache.get(2, &key(2)), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! In-flight request coalescing
+//!
+//! Agents often explore the same symbol from several parallel tool calls, each of
+//! which runs the same hover, references and workspace symbol requests. The
+//! coalescer lets identical requests that overlap in time share a single round-trip
+//! to the language server: the first caller sends the request and every caller
+//! that joins before it completes receives a clone of its result.
+//!
+//! This is not a cache. A request issued after the shared one completed is sent
+//! again. Failures are not shared either: a waiting caller retries the request
+//! itself when the one it joined fails.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use tracing::trace;
+
+/// Shared slot of an in-flight request, type-erased `OnceCell<R>`
+type InFlightSlot = Arc<dyn Any + Send + Sync>;
+
+/// Coalesces identical concurrent requests into one
+#[derive(Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<String, InFlightSlot>>,
+}
+
+impl RequestCoalescer {
+    /// Create a coalescer without in-flight requests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run a request, sharing the result with identical concurrent requests
+    ///
+    /// Requests are identical when their method and parameters are equal. Only
+    /// one `request` among the overlapping identical calls is executed.
+    ///
+    /// # Arguments
+    /// * `method` - LSP method (or composite operation name) of the request
+    /// * `params` - Request parameters identifying the request
+    /// * `request` - Sends the request when this caller is the one to execute it
+    pub async fn run<P, R, E, F, Fut>(&self, method: &str, params: &P, request: F) -> Result<R, E>
+    where
+        P: Serialize + ?Sized,
+        R: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        let Ok(params) = serde_json::to_value(params) else {
+            return request().await;
+        };
+        let key = format!("{method} {}", canonical_json(&params));
+
+        let Some(slot) = self.join::<R>(&key) else {
+            return request().await;
+        };
+        let _guard = InFlightGuard {
+            coalescer: self,
+            key: &key,
+            slot: &slot,
+        };
+
+        slot.get_or_try_init(request).await.cloned()
+    }
+
+    /// Number of requests currently in flight
+    #[cfg(test)]
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+
+    /// Get the slot of an identical in-flight request, or register a new one
+    ///
+    /// Returns None when the key is in flight with a different result type.
+    fn join<R: Send + Sync + 'static>(&self, key: &str) -> Option<Arc<OnceCell<R>>> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        match in_flight.get(key) {
+            Some(slot) => {
+                trace!("Joining in-flight request {}", key);
+                Arc::clone(slot).downcast::<OnceCell<R>>().ok()
+            }
+            None => {
+                let slot = Arc::new(OnceCell::new());
+                in_flight.insert(key.to_string(), slot.clone() as InFlightSlot);
+                Some(slot)
+            }
+        }
+    }
+
+    /// Unregister a completed request so later calls are sent again
+    fn leave<R: Send + Sync + 'static>(&self, key: &str, slot: &Arc<OnceCell<R>>) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight
+            .get(key)
+            .is_some_and(|entry| std::ptr::addr_eq(Arc::as_ptr(entry), Arc::as_ptr(slot)))
+        {
+            in_flight.remove(key);
+        }
+    }
+}
+
+/// Unregisters the slot when its caller finishes or is cancelled
+struct InFlightGuard<'a, R: Send + Sync + 'static> {
+    coalescer: &'a RequestCoalescer,
+    key: &'a str,
+    slot: &'a Arc<OnceCell<R>>,
+}
+
+impl<R: Send + Sync + 'static> Drop for InFlightGuard<'_, R> {
+    fn drop(&mut self) {
+        self.coalescer.leave(self.key, self.slot);
+    }
+}
+
+/// JSON text with object keys sorted, so equal parameters map to equal keys
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonical_json(value))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    async fn slow_request(calls: &AtomicUsize, result: u32) -> Result<u32, String> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(result)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_requests_share_round_trip() {
+        let coalescer = RequestCoalescer::new();
+        let calls = AtomicUsize::new(0);
+        let params = serde_json::json!({"query": "Calculator", "limit": 10});
+        let reordered = serde_json::json!({"limit": 10, "query": "Calculator"});
+
+        let (a, b) = tokio::join!(
+            coalescer.run("workspace/symbol", &params, || slow_request(&calls, 1)),
+            coalescer.run("workspace/symbol", &reordered, || slow_request(&calls, 2)),
+        );
+        assert_eq!((a, b), (Ok(1), Ok(1)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(coalescer.in_flight_count(), 0);
+
+        // Completed requests are not cached
+        let c = coalescer
+            .run("workspace/symbol", &params, || slow_request(&calls, 3))
+            .await;
+        assert_eq!(c, Ok(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_different_requests_are_not_coalesced() {
+        let coalescer = RequestCoalescer::new();
+        let calls = AtomicUsize::new(0);
+
+        let (a, b) = tokio::join!(
+            coalescer.run("textDocument/hover", &("a.cpp", 1), || slow_request(
+                &calls, 1
+            )),
+            coalescer.run("textDocument/hover", &("a.cpp", 2), || slow_request(
+                &calls, 2
+            )),
+        );
+        assert_eq!((a, b), (Ok(1), Ok(2)));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_retried_by_waiting_callers() {
+        let coalescer = RequestCoalescer::new();
+        let calls = AtomicUsize::new(0);
+
+        let failing = async {
+            coalescer
+                .run("textDocument/references", "x", || async {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Err::<u32, String>("timeout".to_string())
+                })
+                .await
+        };
+        let waiting = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            coalescer
+                .run("textDocument/references", "x", || slow_request(&calls, 7))
+                .await
+        };
+
+        let (a, b) = tokio::join!(failing, waiting);
+        assert_eq!(a, Err("timeout".to_string()));
+        assert_eq!(b, Ok(7));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(coalescer.in_flight_count(), 0);
+    }
+}
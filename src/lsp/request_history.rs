@@ -0,0 +1,189 @@
+//! Recent request history
+//!
+//! Keeps the last requests sent to the language server with their outcome. When
+//! the server exits unexpectedly, the requests still pending at that moment are
+//! the likely trigger; reporting them lets users reproduce the crash and report
+//! it upstream, or avoid the operation.
+
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Number of requests kept in the history
+pub const REQUEST_HISTORY_SIZE: usize = 16;
+
+/// Outcome of a recorded request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// No response yet
+    Pending,
+    /// Response received
+    Completed,
+    /// Error response received
+    Failed,
+    /// Given up waiting for the response
+    TimedOut,
+}
+
+/// A request sent to the language server
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestRecord {
+    pub id: u64,
+    pub method: String,
+    /// Document position or query the request targets, if any
+    pub target: Option<String>,
+    pub outcome: RequestOutcome,
+    pub sent_at: Instant,
+}
+
+impl fmt::Display for RequestRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}", self.id, self.method)?;
+        if let Some(target) = &self.target {
+            write!(f, " {target}")?;
+        }
+        let outcome = match self.outcome {
+            RequestOutcome::Pending => "pending",
+            RequestOutcome::Completed => "completed",
+            RequestOutcome::Failed => "failed",
+            RequestOutcome::TimedOut => "timed out",
+        };
+        write!(
+            f,
+            " ({outcome}, sent {:.1}s ago)",
+            self.sent_at.elapsed().as_secs_f64()
+        )
+    }
+}
+
+/// Bounded history of the most recent requests, oldest first
+#[derive(Debug, Default)]
+pub struct RequestHistory {
+    records: Mutex<VecDeque<RequestRecord>>,
+}
+
+impl RequestHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a request being sent
+    pub fn record(&self, id: u64, method: &str, params: Option<&Value>) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() == REQUEST_HISTORY_SIZE {
+            records.pop_front();
+        }
+        records.push_back(RequestRecord {
+            id,
+            method: method.to_string(),
+            target: params.and_then(request_target),
+            outcome: RequestOutcome::Pending,
+            sent_at: Instant::now(),
+        });
+    }
+
+    /// Record the outcome of a request, if it is still in the history
+    pub fn finish(&self, id: u64, outcome: RequestOutcome) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().rev().find(|record| record.id == id) {
+            record.outcome = outcome;
+        }
+    }
+
+    /// Recorded requests, oldest first
+    pub fn recent(&self) -> Vec<RequestRecord> {
+        self.records.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Whether the most recent request asked the server to shut down, so that
+    /// its exit is expected
+    pub fn shutdown_requested(&self) -> bool {
+        self.records
+            .lock()
+            .unwrap()
+            .back()
+            .is_some_and(|record| record.method == "shutdown")
+    }
+
+    /// One line per recorded request, oldest first
+    pub fn report(&self) -> String {
+        let records = self.recent();
+        if records.is_empty() {
+            return "no requests sent".to_string();
+        }
+        records
+            .iter()
+            .map(|record| record.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Document position or query of LSP request parameters
+fn request_target(params: &Value) -> Option<String> {
+    let uri = params
+        .pointer("/textDocument/uri")
+        .or_else(|| params.pointer("/item/uri"))
+        .and_then(Value::as_str);
+    let line = params.pointer("/position/line").and_then(Value::as_u64);
+    let character = params
+        .pointer("/position/character")
+        .and_then(Value::as_u64);
+
+    match (uri, line, character) {
+        (Some(uri), Some(line), Some(character)) => {
+            Some(format!("{uri}:{}:{}", line + 1, character + 1))
+        }
+        (Some(uri), _, _) => Some(uri.to_string()),
+        _ => params
+            .get("query")
+            .and_then(Value::as_str)
+            .map(|query| format!("query {query:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_history_keeps_recent_requests_with_outcome() {
+        let history = RequestHistory::new();
+        for id in 0..REQUEST_HISTORY_SIZE as u64 + 2 {
+            history.record(id, "workspace/symbol", Some(&json!({"query": "Foo"})));
+            history.finish(id, RequestOutcome::Completed);
+        }
+        history.record(
+            100,
+            "textDocument/hover",
+            Some(&json!({
+                "textDocument": {"uri": "file:///src/main.cpp"},
+                "position": {"line": 9, "character": 4}
+            })),
+        );
+
+        let recent = history.recent();
+        assert_eq!(recent.len(), REQUEST_HISTORY_SIZE);
+        assert_eq!(recent[0].id, 3);
+        assert_eq!(recent[0].target.as_deref(), Some("query \"Foo\""));
+        let last = recent.last().unwrap();
+        assert_eq!(last.outcome, RequestOutcome::Pending);
+        assert_eq!(last.target.as_deref(), Some("file:///src/main.cpp:10:5"));
+        assert!(
+            history
+                .report()
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("#100 textDocument/hover file:///src/main.cpp:10:5 (pending")
+        );
+        assert!(!history.shutdown_requested());
+
+        history.record(101, "shutdown", None);
+        assert!(history.shutdown_requested());
+    }
+}
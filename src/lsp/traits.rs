@@ -6,6 +6,7 @@
 use async_trait::async_trait;
 
 use crate::lsp::client::LspError;
+use crate::lsp::position_encoding::PositionEncoding;
 use crate::lsp::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
 
 // ============================================================================
@@ -19,6 +20,11 @@ use crate::lsp::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse}
 ///
 /// This trait provides the complete LSP client interface needed by session
 /// management and other components that interact with LSP servers.
+///
+/// Request methods keep the three possible outcomes of a clangd request apart:
+/// `Ok(None)` when the server answered `null` (no result for the request),
+/// `Ok(Some(..))` with a possibly empty list when it searched and found nothing,
+/// and `Err(LspError::JsonRpc(..))` when it answered with a JSON-RPC error.
 #[async_trait]
 #[cfg_attr(test, mockall::automock)]
 // Methods will be used when session management is fully integrated
@@ -30,6 +36,12 @@ pub trait LspClientTrait: Send + Sync {
     /// Check if client is initialized (ready for LSP operations)
     fn is_initialized(&self) -> bool;
 
+    /// Position encoding negotiated during initialization (UTF-16 before that)
+    fn position_encoding(&self) -> PositionEncoding;
+
+    /// Semantic token legend announced by the server (None if unsupported)
+    fn semantic_tokens_legend(&self) -> Option<lsp_types::SemanticTokensLegend>;
+
     // ========================================================================
     // Lifecycle Management
     // ========================================================================
@@ -84,6 +96,32 @@ pub trait LspClientTrait: Send + Sync {
         text: String,
     ) -> Result<(), LspError>;
 
+    /// Notify the server of ranged edits to a text document
+    ///
+    /// Changes are applied in order, each to the content left by the previous one.
+    async fn change_text_document_incremental(
+        &mut self,
+        uri: lsp_types::Uri,
+        version: i32,
+        changes: Vec<lsp_types::TextDocumentContentChangeEvent>,
+    ) -> Result<(), LspError>;
+
+    // ========================================================================
+    // Extension Methods
+    // ========================================================================
+
+    /// Send a request for a server-specific method not modeled by lsp-types
+    ///
+    /// Parameters and result are passed through as raw JSON.
+    async fn raw_request(
+        &mut self,
+        method: String,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, LspError>;
+
+    /// Send `workspace/didChangeConfiguration` with server-specific settings
+    async fn change_configuration(&mut self, settings: serde_json::Value) -> Result<(), LspError>;
+
     // ========================================================================
     // Symbol and Navigation Methods
     // ========================================================================
@@ -92,7 +130,7 @@ pub trait LspClientTrait: Send + Sync {
     async fn workspace_symbols(
         &mut self,
         query: String,
-    ) -> Result<Vec<lsp_types::WorkspaceSymbol>, LspError>;
+    ) -> Result<Option<Vec<lsp_types::WorkspaceSymbol>>, LspError>;
 
     /// Get the definition(s) of a symbol at the given position
     #[allow(dead_code)]
@@ -100,7 +138,7 @@ pub trait LspClientTrait: Send + Sync {
         &mut self,
         uri: lsp_types::Uri,
         position: lsp_types::Position,
-    ) -> Result<lsp_types::GotoDefinitionResponse, LspError>;
+    ) -> Result<Option<lsp_types::GotoDefinitionResponse>, LspError>;
 
     /// Get the declaration(s) of a symbol at the given position
     #[allow(dead_code)]
@@ -108,7 +146,23 @@ pub trait LspClientTrait: Send + Sync {
         &mut self,
         uri: lsp_types::Uri,
         position: lsp_types::Position,
-    ) -> Result<lsp_types::request::GotoDeclarationResponse, LspError>;
+    ) -> Result<Option<lsp_types::request::GotoDeclarationResponse>, LspError>;
+
+    /// Get the declaration(s) of the type of the symbol at the given position
+    async fn text_document_type_definition(
+        &mut self,
+        uri: lsp_types::Uri,
+        position: lsp_types::Position,
+    ) -> Result<Option<lsp_types::request::GotoTypeDefinitionResponse>, LspError>;
+
+    /// Get the implementation(s) of a symbol at the given position
+    ///
+    /// For a virtual method clangd returns the methods overriding it.
+    async fn text_document_implementation(
+        &mut self,
+        uri: lsp_types::Uri,
+        position: lsp_types::Position,
+    ) -> Result<Option<lsp_types::request::GotoImplementationResponse>, LspError>;
 
     /// Find all references to a symbol at the given position
     #[allow(dead_code)]
@@ -117,7 +171,7 @@ pub trait LspClientTrait: Send + Sync {
         uri: lsp_types::Uri,
         position: lsp_types::Position,
         include_declaration: bool,
-    ) -> Result<Vec<lsp_types::Location>, LspError>;
+    ) -> Result<Option<Vec<lsp_types::Location>>, LspError>;
 
     /// Get hover information for a symbol at the given position
     #[allow(dead_code)]
@@ -131,7 +185,26 @@ pub trait LspClientTrait: Send + Sync {
     async fn text_document_document_symbol(
         &mut self,
         uri: lsp_types::Uri,
-    ) -> Result<lsp_types::DocumentSymbolResponse, LspError>;
+    ) -> Result<Option<lsp_types::DocumentSymbolResponse>, LspError>;
+
+    /// Get the chains of nested syntactic ranges around positions
+    async fn text_document_selection_range(
+        &mut self,
+        uri: lsp_types::Uri,
+        positions: Vec<lsp_types::Position>,
+    ) -> Result<Option<Vec<lsp_types::SelectionRange>>, LspError>;
+
+    /// Get the links of a text document, for clangd the targets of its `#include` directives
+    async fn text_document_document_link(
+        &mut self,
+        uri: lsp_types::Uri,
+    ) -> Result<Option<Vec<lsp_types::DocumentLink>>, LspError>;
+
+    /// Get the semantic tokens of a whole text document
+    async fn text_document_semantic_tokens_full(
+        &mut self,
+        uri: lsp_types::Uri,
+    ) -> Result<Option<lsp_types::SemanticTokensResult>, LspError>;
 
     // ========================================================================
     // Call Hierarchy Methods
@@ -143,21 +216,21 @@ pub trait LspClientTrait: Send + Sync {
         &mut self,
         uri: lsp_types::Uri,
         position: lsp_types::Position,
-    ) -> Result<Vec<lsp_types::CallHierarchyItem>, LspError>;
+    ) -> Result<Option<Vec<lsp_types::CallHierarchyItem>>, LspError>;
 
     /// Get incoming calls for a call hierarchy item
     #[allow(dead_code)]
     async fn call_hierarchy_incoming_calls(
         &mut self,
         item: lsp_types::CallHierarchyItem,
-    ) -> Result<Vec<lsp_types::CallHierarchyIncomingCall>, LspError>;
+    ) -> Result<Option<Vec<lsp_types::CallHierarchyIncomingCall>>, LspError>;
 
     /// Get outgoing calls for a call hierarchy item
     #[allow(dead_code)]
     async fn call_hierarchy_outgoing_calls(
         &mut self,
         item: lsp_types::CallHierarchyItem,
-    ) -> Result<Vec<lsp_types::CallHierarchyOutgoingCall>, LspError>;
+    ) -> Result<Option<Vec<lsp_types::CallHierarchyOutgoingCall>>, LspError>;
 
     // ========================================================================
     // Type Hierarchy Methods
@@ -9,13 +9,29 @@ mod symbol;
 #[cfg(test)]
 mod test_utils;
 
+use clangd::config::{PchStorage, available_memory_bytes, default_pch_storage};
+use clangd::discovery::{VersionRequirement, discover_clangd_binaries, select_clangd};
+use clangd::version::ClangdVersion;
 use clap::Parser;
 use logging::{LogConfig, init_logging};
 use mcp_server::CppServerHandler;
-use project::{ProjectScanner, ProjectWorkspace};
+use mcp_server::concurrency::DEFAULT_MAX_CONCURRENT_TOOLS;
+use project::index::location::{
+    IndexStorageOptions, resolve_index_path, validate_static_index_file,
+};
+use project::project_config::{
+    CONFIG_FILE_NAME, MAX_SCAN_DEPTH, expand_clangd_env, resolve_clangd_working_directory,
+    validate_lsp_root_uri,
+};
+use project::scanner::ScanOptions;
+use project::setup_check;
+use project::{
+    CompilationDatabase, ProjectConfig, ProjectScanner, ProjectWorkspace, ResultExclusions,
+    TestFilePatterns,
+};
 use rust_mcp_sdk::schema::{
     Implementation, InitializeResult, LATEST_PROTOCOL_VERSION, ServerCapabilities,
-    ServerCapabilitiesTools,
+    ServerCapabilitiesResources, ServerCapabilitiesTools,
 };
 
 use rust_mcp_sdk::{
@@ -34,10 +50,21 @@ struct Args {
     #[arg(long, value_name = "DIR")]
     root: Option<PathBuf>,
 
+    /// Directory containing compile_commands.json to use for all build directories
+    /// (e.g. a project root with a symlinked compile_commands.json)
+    #[arg(long, value_name = "DIR")]
+    compile_commands_dir: Option<PathBuf>,
+
     /// Path to clangd executable (overrides CLANGD_PATH env var)
     #[arg(long, value_name = "PATH")]
     clangd_path: Option<String>,
 
+    /// Version requirement for the clangd picked from the `clangd*` binaries
+    /// on PATH, e.g. ">= 20" or "18.1". Without it, a default `clangd` older
+    /// than 20 is replaced by the newest one found.
+    #[arg(long, value_name = "REQ")]
+    clangd_version: Option<VersionRequirement>,
+
     /// Log level (overrides RUST_LOG env var)
     #[arg(long, value_name = "LEVEL")]
     log_level: Option<String>,
@@ -45,28 +72,179 @@ struct Args {
     /// Log file path (overrides MCP_LOG_FILE env var)
     #[arg(long, value_name = "FILE")]
     log_file: Option<PathBuf>,
+
+    /// Emit tool results as compact JSON instead of pretty-printed JSON
+    #[arg(long)]
+    compact_json: bool,
+
+    /// Number of threads each clangd uses for background indexing (passed as `-j`).
+    /// Defaults to clangd's own default of one thread per hardware thread.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    clangd_jobs: Option<u32>,
+
+    /// Directory depth scanned for build directories (default: 3)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(..=MAX_SCAN_DEPTH as u64))]
+    scan_depth: Option<u64>,
+
+    /// Build directory used by tool calls that do not specify one
+    #[arg(long, value_name = "DIR")]
+    build_directory: Option<PathBuf>,
+
+    /// Default timeout in seconds tools wait for indexing completion (default: 20)
+    #[arg(long, value_name = "SECONDS")]
+    index_wait_timeout: Option<u64>,
+
+    /// Maximum number of clangd-backed tool calls executed at the same time;
+    /// further calls wait in a queue (default: 8)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    max_concurrent_tools: Option<u64>,
+
+    /// Directory clangd processes run in (defaults to the project root).
+    /// Relative paths are resolved against the project root.
+    #[arg(long, value_name = "DIR")]
+    clangd_working_dir: Option<PathBuf>,
+
+    /// LSP workspace root URI sent to clangd instead of the project root, for
+    /// environments where clangd sees the sources under a different path
+    /// (e.g. file:///workspace/project inside a container)
+    #[arg(long, value_name = "URI")]
+    lsp_root_uri: Option<String>,
+
+    /// Return tool results larger than this many bytes as MCP resources that
+    /// clients read on demand (default: always inline)
+    #[arg(long, value_name = "BYTES")]
+    resource_threshold: Option<usize>,
+
+    /// Skip reading source context from files larger than this many bytes;
+    /// their results carry locations only (default: 1048576)
+    #[arg(long, value_name = "BYTES")]
+    max_context_file_size: Option<u64>,
+
+    /// Directory clangd keeps its background index in instead of
+    /// `.cache/clangd/index` next to each compilation database, e.g. outside a
+    /// read-only tree. Relative paths are resolved against the project root.
+    #[arg(long, value_name = "DIR")]
+    index_dir: Option<PathBuf>,
+
+    /// Static index file clangd loads in addition to its background index
+    /// (passed as `--index-file`), e.g. a shared index of a large code base.
+    /// Relative paths are resolved against the project root.
+    #[arg(long, value_name = "FILE")]
+    index_file: Option<PathBuf>,
+
+    /// Run clangd without background index (`--background-index=false`) and do
+    /// not wait for indexing: fast, low-resource lookups answered from the files
+    /// clangd parses on demand, at the cost of incomplete workspace-wide results
+    #[arg(long)]
+    quiet_indexing: bool,
+
+    /// Where clangd stores the preambles (precompiled headers) of open files:
+    /// "memory" or "disk" (passed as `--pch-storage`). Defaults to memory when
+    /// at least 16 GiB are available at startup, disk otherwise.
+    #[arg(long, value_name = "STORAGE")]
+    pch_storage: Option<PchStorage>,
+
+    /// Validate the setup (project scan, clangd, compilation databases), print a
+    /// JSON report and exit without starting the server. Exits with status 1
+    /// when problems were found.
+    #[arg(long)]
+    check: bool,
 }
 
-/// Resolve clangd path from CLI args and environment
-fn resolve_clangd_path(clangd_path_arg: Option<String>) -> String {
-    // Priority: CLI arg > CLANGD_PATH env var > "clangd" default
-    clangd_path_arg
-        .or_else(|| std::env::var("CLANGD_PATH").ok())
-        .unwrap_or_else(|| "clangd".to_string())
+/// Default directory depth scanned for build directories
+const DEFAULT_SCAN_DEPTH: usize = 3;
+
+/// Pick a setting from the command line, falling back to the project configuration file
+fn choose_setting<T: std::fmt::Debug>(name: &str, cli: Option<T>, file: Option<T>) -> Option<T> {
+    match (cli, file) {
+        (Some(value), _) => {
+            info!("Setting {} = {:?} (from command line)", name, value);
+            Some(value)
+        }
+        (None, Some(value)) => {
+            info!("Setting {} = {:?} (from {})", name, value, CONFIG_FILE_NAME);
+            Some(value)
+        }
+        (None, None) => None,
+    }
+}
+
+/// Resolve clangd path from CLI args, environment and the binaries on PATH
+///
+/// An explicit path is used as is, but must satisfy the version requirement
+/// when one is given. Otherwise the `clangd*` binaries on PATH are discovered
+/// and one is selected by version.
+fn resolve_clangd_path(
+    clangd_path_arg: Option<String>,
+    requirement: Option<&VersionRequirement>,
+) -> Result<String, String> {
+    // Priority: CLI arg > CLANGD_PATH env var > discovered binaries > "clangd" default
+    if let Some(path) = clangd_path_arg.or_else(|| std::env::var("CLANGD_PATH").ok()) {
+        if let Some(requirement) = requirement
+            && let Ok(version) = ClangdVersion::detect(std::path::Path::new(&path))
+            && !requirement.matches(&version)
+        {
+            return Err(format!(
+                "{path} is clangd {version}, which does not satisfy {requirement}"
+            ));
+        }
+        return Ok(path);
+    }
+
+    let candidates = std::env::var_os("PATH")
+        .map(|path_var| discover_clangd_binaries(&path_var))
+        .unwrap_or_default();
+    let found = candidates
+        .iter()
+        .map(|candidate| format!("{} ({})", candidate.path.display(), candidate.version))
+        .collect::<Vec<_>>();
+    info!("Discovered clangd binaries: {:?}", found);
+
+    match select_clangd(&candidates, requirement) {
+        Some(selected) => {
+            if let Some(default) = candidates.iter().find(|candidate| candidate.is_default())
+                && default != selected
+            {
+                info!(
+                    "Default clangd {} is version {}; preferring {} (version {})",
+                    default.path.display(),
+                    default.version,
+                    selected.path.display(),
+                    selected.version
+                );
+            }
+            Ok(selected.path.to_string_lossy().to_string())
+        }
+        None => match requirement {
+            Some(requirement) => Err(format!(
+                "no clangd on PATH satisfies {requirement}, found: [{}]",
+                found.join(", ")
+            )),
+            None => Ok("clangd".to_string()),
+        },
+    }
 }
 
 /// Create ProjectWorkspace with all project setup logic centralized
-fn create_project_workspace(project_root: PathBuf) -> ProjectWorkspace {
+fn create_project_workspace(
+    project_root: PathBuf,
+    scan_depth: usize,
+    ignore_patterns: Vec<String>,
+) -> ProjectWorkspace {
     info!(
-        "Scanning project root for build configurations: {} (depth: 3)",
-        project_root.display()
+        "Scanning project root for build configurations: {} (depth: {})",
+        project_root.display(),
+        scan_depth
     );
 
     // Create project scanner with default providers
     let scanner = ProjectScanner::with_default_providers();
+    let options = ScanOptions {
+        ignore_patterns,
+        ..Default::default()
+    };
 
-    // Scan the project root with depth 3
-    match scanner.scan_project(&project_root, 3, None) {
+    match scanner.scan_project(&project_root, scan_depth, Some(options)) {
         Ok(project_workspace) => {
             info!(
                 "Successfully discovered {} components across {} providers: {:?}",
@@ -83,7 +261,7 @@ fn create_project_workspace(project_root: PathBuf) -> ProjectWorkspace {
                 e
             );
             // Create empty ProjectWorkspace as fallback
-            ProjectWorkspace::new(project_root, Vec::new(), 3)
+            ProjectWorkspace::new(project_root, Vec::new(), scan_depth)
         }
     }
 }
@@ -113,8 +291,213 @@ async fn main() -> SdkResult<()> {
         })
     });
 
+    // Load checked-in project settings; command line arguments take precedence
+    let project_config = match ProjectConfig::load(&project_root) {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to load project configuration: {e}");
+            std::process::exit(1);
+        }
+    };
+    let scan_depth = choose_setting(
+        "scan_depth",
+        args.scan_depth.map(|depth| depth as usize),
+        project_config.scan_depth,
+    )
+    .unwrap_or(DEFAULT_SCAN_DEPTH);
+    let clangd_jobs = choose_setting("clangd_jobs", args.clangd_jobs, project_config.clangd_jobs);
+    let clangd_version = choose_setting(
+        "clangd_version",
+        args.clangd_version,
+        project_config
+            .clangd_version
+            .as_deref()
+            .map(|requirement| requirement.parse().expect("validated when loading")),
+    );
+    let quiet_indexing = choose_setting(
+        "quiet_indexing",
+        args.quiet_indexing.then_some(true),
+        project_config.quiet_indexing,
+    )
+    .unwrap_or(false);
+    let pch_storage = choose_setting("pch_storage", args.pch_storage, project_config.pch_storage)
+        .unwrap_or_else(|| {
+            let available_memory = available_memory_bytes();
+            let storage = default_pch_storage(available_memory);
+            info!(
+                "Setting pch_storage = {} (available memory: {} MiB)",
+                storage,
+                available_memory.map_or("unknown".to_string(), |bytes| (bytes >> 20).to_string())
+            );
+            storage
+        });
+    let build_directory = choose_setting(
+        "build_directory",
+        args.build_directory,
+        project_config.build_directory,
+    );
+    let index_wait_timeout = choose_setting(
+        "index_wait_timeout",
+        args.index_wait_timeout,
+        project_config.index_wait_timeout,
+    );
+    let max_concurrent_tools = choose_setting(
+        "max_concurrent_tools",
+        args.max_concurrent_tools.map(|limit| limit as usize),
+        project_config.max_concurrent_tools,
+    )
+    .unwrap_or(DEFAULT_MAX_CONCURRENT_TOOLS);
+    let resource_threshold = choose_setting(
+        "resource_threshold",
+        args.resource_threshold,
+        project_config.resource_threshold,
+    );
+    let max_context_file_size = choose_setting(
+        "max_context_file_size",
+        args.max_context_file_size,
+        project_config.max_context_file_size,
+    );
+    let clangd_working_directory = choose_setting(
+        "clangd_working_directory",
+        args.clangd_working_dir,
+        project_config.clangd_working_directory,
+    )
+    .map(|directory| {
+        resolve_clangd_working_directory(&directory, &project_root).unwrap_or_else(|e| {
+            eprintln!("Invalid clangd working directory: {e}");
+            std::process::exit(1);
+        })
+    });
+    let lsp_root_uri = choose_setting(
+        "lsp_root_uri",
+        args.lsp_root_uri,
+        project_config.lsp_root_uri,
+    )
+    .map(|uri| {
+        validate_lsp_root_uri(&uri).unwrap_or_else(|e| {
+            eprintln!("Invalid LSP root URI: {e}");
+            std::process::exit(1);
+        })
+    });
+    let index_storage = IndexStorageOptions {
+        directory: choose_setting(
+            "index_directory",
+            args.index_dir,
+            project_config.index_directory,
+        )
+        .map(|directory| resolve_index_path(&directory, &project_root)),
+        static_index_file: choose_setting("index_file", args.index_file, project_config.index_file)
+            .map(|file| resolve_index_path(&file, &project_root)),
+    };
+    if let Some(file) = &index_storage.static_index_file
+        && let Err(e) = validate_static_index_file(file)
+    {
+        eprintln!("Invalid index file: {e}");
+        std::process::exit(1);
+    }
+    if !project_config.ignore.is_empty() {
+        info!(
+            "Setting ignore = {:?} (from {})",
+            project_config.ignore, CONFIG_FILE_NAME
+        );
+    }
+    if !project_config.clangd_args.is_empty() {
+        info!(
+            "Setting clangd_args = {:?} (from {})",
+            project_config.clangd_args, CONFIG_FILE_NAME
+        );
+    }
+    let clangd_env = expand_clangd_env(&project_config.clangd_env);
+    if !clangd_env.is_empty() {
+        info!(
+            "Setting clangd_env = {:?} (from {})",
+            clangd_env, CONFIG_FILE_NAME
+        );
+    }
+    let empty_result_retry = project_config
+        .empty_result_retry
+        .as_ref()
+        .map(|retry| retry.to_retry())
+        .unwrap_or_default();
+    if project_config.empty_result_retry.is_some() {
+        info!(
+            "Setting empty_result_retry = {:?} (from {})",
+            empty_result_retry, CONFIG_FILE_NAME
+        );
+    }
+
     // Create ProjectWorkspace with all project setup
-    let project_workspace = create_project_workspace(project_root);
+    let mut project_workspace =
+        create_project_workspace(project_root, scan_depth, project_config.ignore);
+
+    // Leave generated and vendored files out of tool results
+    match ResultExclusions::from_config(
+        project_config.exclude_results.as_deref(),
+        &project_config.extra_exclude_results,
+        &project_workspace.project_root_path,
+    ) {
+        Ok(exclusions) => {
+            info!("Excluding from results: {:?}", exclusions.patterns());
+            project_workspace.result_exclusions = exclusions;
+        }
+        Err(e) => {
+            eprintln!("Invalid result exclusions: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    match TestFilePatterns::from_config(
+        project_config.test_file_patterns.as_deref(),
+        &project_workspace.project_root_path,
+    ) {
+        Ok(patterns) => project_workspace.test_file_patterns = patterns,
+        Err(e) => {
+            eprintln!("Invalid test file patterns: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(limit) = max_context_file_size {
+        project_workspace.max_context_file_size = limit;
+    }
+
+    // Apply explicit compile_commands.json location
+    if let Some(compile_commands_dir) = args.compile_commands_dir {
+        let database_path = compile_commands_dir.join("compile_commands.json");
+        let applied = CompilationDatabase::new(database_path.clone())
+            .map_err(|e| e.to_string())
+            .and_then(|database| {
+                project_workspace
+                    .set_global_compilation_database(database)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = applied {
+            eprintln!(
+                "Failed to load compilation database {}: {}",
+                database_path.display(),
+                e
+            );
+            std::process::exit(1);
+        }
+        info!("Using compilation database: {}", database_path.display());
+    }
+
+    if args.check {
+        let clangd = match resolve_clangd_path(args.clangd_path, clangd_version.as_ref()) {
+            Ok(path) => setup_check::check_clangd(&path),
+            Err(e) => setup_check::ClangdCheck {
+                path: "clangd".to_string(),
+                version: None,
+                error: Some(e),
+            },
+        };
+        let report = setup_check::check_workspace(&project_workspace, clangd);
+        match serde_json::to_string_pretty(&report) {
+            Ok(output) => println!("{output}"),
+            Err(e) => eprintln!("Failed to serialize setup report: {e}"),
+        }
+        std::process::exit(if report.ok { 0 } else { 1 });
+    }
 
     info!(
         "Starting C++ MCP Server with project root: {}",
@@ -133,6 +516,10 @@ async fn main() -> SdkResult<()> {
         },
         capabilities: ServerCapabilities {
             tools: Some(ServerCapabilitiesTools { list_changed: None }),
+            resources: resource_threshold.map(|_| ServerCapabilitiesResources {
+                list_changed: None,
+                subscribe: None,
+            }),
             ..Default::default()
         },
         meta: None,
@@ -141,7 +528,11 @@ async fn main() -> SdkResult<()> {
     };
 
     // Resolve clangd path
-    let clangd_path = resolve_clangd_path(args.clangd_path);
+    let clangd_path = resolve_clangd_path(args.clangd_path, clangd_version.as_ref())
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to select clangd: {e}");
+            std::process::exit(1);
+        });
     info!("Using clangd: {}", clangd_path);
 
     // Create stdio transport
@@ -149,7 +540,23 @@ async fn main() -> SdkResult<()> {
 
     // Create custom handler with ProjectWorkspace and clangd path
     let handler = match CppServerHandler::new(project_workspace, clangd_path) {
-        Ok(handler) => handler,
+        Ok(handler) => handler
+            .with_compact_json(args.compact_json)
+            .with_clangd_jobs(clangd_jobs)
+            .with_quiet_indexing(quiet_indexing)
+            .with_pch_storage(pch_storage)
+            .with_clangd_args(project_config.clangd_args)
+            .with_clangd_env(clangd_env)
+            .with_clangd_working_directory(clangd_working_directory)
+            .with_lsp_root_uri(lsp_root_uri)
+            .with_index_storage(index_storage)
+            .with_default_build_directory(
+                build_directory.map(|dir| dir.to_string_lossy().to_string()),
+            )
+            .with_index_wait_timeout(index_wait_timeout)
+            .with_max_concurrent_tools(max_concurrent_tools)
+            .with_resource_threshold(resource_threshold)
+            .with_empty_result_retry(empty_result_retry),
         Err(e) => {
             eprintln!("Failed to create server handler: {}", e);
             std::process::exit(1);
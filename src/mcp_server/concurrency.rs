@@ -0,0 +1,159 @@
+//! Concurrency limit for clangd-backed tool calls
+//!
+//! Every session serializes its LSP requests behind a mutex, so dozens of
+//! parallel tool calls only pile up on that lock while holding open files and
+//! pending requests. A semaphore admits a bounded number of calls at a time and
+//! queues the rest in arrival order; calls that had to wait report how long
+//! and behind how many others.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+/// Default number of tool calls executed at the same time
+pub const DEFAULT_MAX_CONCURRENT_TOOLS: usize = 8;
+
+/// Tools that do not send requests to clangd and are never queued
+///
+/// `reset_clangd` and `get_clangd_log` are included so a stuck clangd can be
+/// diagnosed and reset while every slot is taken by calls waiting for it.
+pub const UNLIMITED_TOOLS: &[&str] = &[
+    "get_project_details",
+    "workspace_index_status",
+    "index_snapshot",
+    "reset_clangd",
+    "check_build_freshness",
+    "get_format_style",
+    "list_build_targets",
+    "get_clangd_log",
+    "check_compile_coverage",
+    "get_include_graph",
+    "find_header_includers",
+    "get_active_macros",
+];
+
+/// Queueing information of a tool call that had to wait for a slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueInfo {
+    /// Time spent waiting for a slot in milliseconds
+    pub waited_ms: u64,
+    /// Number of calls queued when this call started waiting, itself included
+    pub queue_depth: usize,
+    /// Maximum number of tool calls executed at the same time
+    pub concurrency_limit: usize,
+}
+
+/// Slot of a running tool call, released when dropped
+pub struct ToolPermit {
+    _permit: OwnedSemaphorePermit,
+    /// Set when the call had to wait for the slot
+    pub queue: Option<QueueInfo>,
+}
+
+/// Limits the number of tool calls executing at the same time
+#[derive(Clone)]
+pub struct ToolConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+    queued: Arc<AtomicUsize>,
+}
+
+impl ToolConcurrencyLimiter {
+    /// Create a limiter admitting `limit` concurrent calls (at least one)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+            queued: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Maximum number of concurrent calls
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Number of calls currently waiting for a slot
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Wait for a free slot; waiting calls are admitted in arrival order
+    pub async fn acquire(&self) -> ToolPermit {
+        if let Ok(permit) = Arc::clone(&self.semaphore).try_acquire_owned() {
+            return ToolPermit {
+                _permit: permit,
+                queue: None,
+            };
+        }
+
+        let queue_depth = self.queued.fetch_add(1, Ordering::SeqCst) + 1;
+        debug!(
+            "All {} tool slots busy, queued at depth {}",
+            self.limit, queue_depth
+        );
+        let start = Instant::now();
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("tool semaphore is never closed");
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+
+        ToolPermit {
+            _permit: permit,
+            queue: Some(QueueInfo {
+                waited_ms: start.elapsed().as_millis() as u64,
+                queue_depth,
+                concurrency_limit: self.limit,
+            }),
+        }
+    }
+}
+
+impl Default for ToolConcurrencyLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_TOOLS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_excess_calls_are_queued() {
+        let limiter = ToolConcurrencyLimiter::new(1);
+
+        let first = limiter.acquire().await;
+        assert!(first.queue.is_none());
+
+        let waiting = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.queue })
+        };
+        while limiter.queued() == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(first);
+
+        let queue = waiting.await.unwrap().unwrap();
+        assert_eq!(queue.queue_depth, 1);
+        assert_eq!(queue.concurrency_limit, 1);
+        assert!(queue.waited_ms >= 20);
+        assert_eq!(limiter.queued(), 0);
+
+        // Slot is free again
+        assert!(limiter.acquire().await.queue.is_none());
+    }
+
+    #[test]
+    fn test_limit_is_at_least_one() {
+        assert_eq!(ToolConcurrencyLimiter::new(0).limit(), 1);
+    }
+}
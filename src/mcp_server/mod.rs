@@ -4,9 +4,14 @@
 //! for C++ code analysis, including the server handler, helper utilities, and all
 //! available tools for semantic analysis.
 
+pub mod concurrency;
+pub mod resources;
+pub mod result_format;
+pub mod schema_version;
 pub mod server;
 pub mod server_helpers;
 pub mod tools;
+pub mod unsaved_files;
 
 // Re-export main components for easier access
 pub use server::CppServerHandler;
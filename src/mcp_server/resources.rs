@@ -0,0 +1,202 @@
+//! Large tool results offered as MCP resources
+//!
+//! Outlines, reference dumps and batch analyses can run to hundreds of
+//! kilobytes, all of which lands in the client's context when returned inline.
+//! With a size threshold configured, text blocks above it are kept on the
+//! server and replaced by a short notice and a `resource_link`; clients fetch
+//! the full text with `resources/read` when they need it. Only the most recent
+//! results are kept.
+
+use rust_mcp_sdk::schema::{
+    CallToolResult, ContentBlock, ReadResourceContent, Resource, ResourceLink, TextContent,
+    TextResourceContents,
+};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// URI prefix of stored tool results
+pub const RESULT_URI_PREFIX: &str = "mcp-cpp://results/";
+
+/// Number of stored results kept before the oldest are dropped
+pub const MAX_STORED_RESULTS: usize = 32;
+
+/// MIME type of stored results; tools render their output as JSON
+const RESULT_MIME_TYPE: &str = "application/json";
+
+/// A tool result kept for later retrieval
+struct StoredResult {
+    uri: String,
+    tool_name: String,
+    text: String,
+}
+
+/// Offloads large tool results into resources clients can read on demand
+#[derive(Clone)]
+pub struct ResultResources {
+    /// Text blocks longer than this many bytes are stored; `None` keeps everything inline
+    threshold: Option<usize>,
+    stored: Arc<Mutex<VecDeque<StoredResult>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ResultResources {
+    /// Store text blocks over `threshold` bytes, or none when `threshold` is `None`
+    pub fn new(threshold: Option<usize>) -> Self {
+        Self {
+            threshold,
+            stored: Arc::new(Mutex::new(VecDeque::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Replace text blocks over the threshold by a notice and a link to the stored text
+    pub fn offload(&self, mut result: CallToolResult, tool_name: &str) -> CallToolResult {
+        let Some(threshold) = self.threshold else {
+            return result;
+        };
+
+        let mut content = Vec::with_capacity(result.content.len());
+        for block in result.content {
+            match block {
+                ContentBlock::TextContent(text) if text.text.len() > threshold => {
+                    let size = text.text.len();
+                    let uri = self.store(tool_name, text.text);
+                    debug!("Stored {} byte result of {} as {}", size, tool_name, uri);
+                    content.push(ContentBlock::from(TextContent::from(format!(
+                        "Result of {size} bytes exceeds the inline limit of {threshold} bytes; \
+                         read resource {uri} to get it."
+                    ))));
+                    content.push(ContentBlock::from(ResourceLink::new(
+                        Vec::new(),
+                        format!("{tool_name} result"),
+                        uri,
+                        None,
+                        None,
+                        None,
+                        Some(RESULT_MIME_TYPE.to_string()),
+                        Some(size as i64),
+                        None,
+                    )));
+                }
+                block => content.push(block),
+            }
+        }
+        result.content = content;
+        result
+    }
+
+    /// Keep a result and return its URI, dropping the oldest beyond the limit
+    fn store(&self, tool_name: &str, text: String) -> String {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let uri = format!("{RESULT_URI_PREFIX}{id}");
+
+        let mut stored = self.stored.lock().unwrap();
+        stored.push_back(StoredResult {
+            uri: uri.clone(),
+            tool_name: tool_name.to_string(),
+            text,
+        });
+        while stored.len() > MAX_STORED_RESULTS {
+            stored.pop_front();
+        }
+        uri
+    }
+
+    /// Contents of a stored result, if it is still kept
+    pub fn read(&self, uri: &str) -> Option<ReadResourceContent> {
+        let stored = self.stored.lock().unwrap();
+        stored
+            .iter()
+            .find(|result| result.uri == uri)
+            .map(|result| {
+                ReadResourceContent::from(TextResourceContents {
+                    meta: None,
+                    mime_type: Some(RESULT_MIME_TYPE.to_string()),
+                    text: result.text.clone(),
+                    uri: result.uri.clone(),
+                })
+            })
+    }
+
+    /// Currently stored results, oldest first
+    pub fn list(&self) -> Vec<Resource> {
+        let stored = self.stored.lock().unwrap();
+        stored
+            .iter()
+            .map(|result| Resource {
+                annotations: None,
+                description: Some(format!("Output of a {} tool call", result.tool_name)),
+                icons: Vec::new(),
+                meta: None,
+                mime_type: Some(RESULT_MIME_TYPE.to_string()),
+                name: format!("{} result", result.tool_name),
+                size: Some(result.text.len() as i64),
+                title: None,
+                uri: result.uri.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Default for ResultResources {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_result(texts: &[&str]) -> CallToolResult {
+        CallToolResult::text_content(
+            texts
+                .iter()
+                .map(|text| TextContent::from(text.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_large_blocks_are_offloaded() {
+        let resources = ResultResources::new(Some(10));
+        let result = resources.offload(text_result(&["short", "a much longer block"]), "ast");
+
+        assert_eq!(result.content.len(), 3);
+        assert!(
+            matches!(&result.content[0], ContentBlock::TextContent(text) if text.text == "short")
+        );
+        let ContentBlock::ResourceLink(link) = &result.content[2] else {
+            panic!("expected resource link");
+        };
+        assert_eq!(link.size, Some(19));
+        assert!(link.uri.starts_with(RESULT_URI_PREFIX));
+
+        let Some(ReadResourceContent::TextResourceContents(contents)) = resources.read(&link.uri)
+        else {
+            panic!("expected stored text");
+        };
+        assert_eq!(contents.text, "a much longer block");
+        assert_eq!(resources.list().len(), 1);
+        assert!(resources.read("mcp-cpp://results/999").is_none());
+    }
+
+    #[test]
+    fn test_disabled_and_eviction() {
+        let disabled = ResultResources::default();
+        let result = disabled.offload(text_result(&["a much longer block"]), "ast");
+        assert!(matches!(&result.content[0], ContentBlock::TextContent(_)));
+        assert!(disabled.list().is_empty());
+
+        let resources = ResultResources::new(Some(0));
+        for _ in 0..MAX_STORED_RESULTS + 2 {
+            resources.offload(text_result(&["x"]), "ast");
+        }
+        let stored = resources.list();
+        assert_eq!(stored.len(), MAX_STORED_RESULTS);
+        assert_eq!(stored[0].uri, format!("{RESULT_URI_PREFIX}3"));
+        assert!(resources.read(&format!("{RESULT_URI_PREFIX}1")).is_none());
+    }
+}
@@ -0,0 +1,469 @@
+//! Result format selected per tool call
+//!
+//! Every tool accepts a `format` argument: `json` (the default) returns the
+//! full JSON result, `summary` a terse text rendering of it for agents that
+//! need to save tokens. Symbol analyses and searches get a hand-written
+//! one-line-per-symbol summary; every other result is rendered generically as
+//! indented `key: value` lines with empty fields left out.
+//!
+//! Like `unsaved_files`, the argument is handled here rather than by each
+//! tool: it is taken out of the arguments before they are deserialized, and
+//! added to the input schema of every tool.
+
+use rust_mcp_sdk::schema::{CallToolResult, ContentBlock, Tool, schema_utils::CallToolError};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerResult;
+use crate::mcp_server::tools::analyze_symbols_batch::BatchAnalyzerResult;
+use crate::mcp_server::tools::search_symbols::SearchResult;
+use crate::symbol::FileLocation;
+
+/// Name of the tool argument selecting the result format
+pub const FORMAT_ARGUMENT: &str = "format";
+
+/// Longest string value kept in a generic summary
+const MAX_SUMMARY_STRING_CHARS: usize = 200;
+
+/// Format of a tool result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultFormat {
+    /// Full JSON result
+    #[default]
+    Json,
+    /// Terse text rendering of the result
+    Summary,
+}
+
+/// Add the `format` argument to the input schema of every tool
+pub fn with_format_schema(mut tools: Vec<Tool>) -> Vec<Tool> {
+    let schema = serde_json::json!({
+        "type": "string",
+        "enum": ["json", "summary"],
+        "description": "Result format. OPTIONAL, default \"json\".\n\n\
+                        \"json\" returns the full result. \"summary\" returns a terse text rendering \
+                        (e.g. \"MyClass (class) defined at src/foo.h:12, 8 members, 3 derived classes\") \
+                        that trades detail for far fewer tokens."
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema literal is an object");
+    };
+
+    for tool in &mut tools {
+        tool.input_schema
+            .properties
+            .get_or_insert_with(Default::default)
+            .insert(FORMAT_ARGUMENT.to_string(), schema.clone());
+    }
+    tools
+}
+
+/// Remove the `format` argument from tool arguments and parse it
+pub fn take_format(
+    arguments: &mut Option<serde_json::Map<String, Value>>,
+) -> Result<ResultFormat, CallToolError> {
+    let Some(value) = arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(FORMAT_ARGUMENT))
+    else {
+        return Ok(ResultFormat::Json);
+    };
+    if value.is_null() {
+        return Ok(ResultFormat::Json);
+    }
+
+    serde_json::from_value(value).map_err(|e| {
+        CallToolError::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid {FORMAT_ARGUMENT}: {e}"),
+        ))
+    })
+}
+
+/// Replace the JSON text of a tool result by its summary
+///
+/// Paths under `project_root` are shown relative to it. Text that is not JSON
+/// is left unchanged.
+pub fn summarize(
+    mut result: CallToolResult,
+    tool_name: &str,
+    project_root: &Path,
+) -> CallToolResult {
+    for block in &mut result.content {
+        if let ContentBlock::TextContent(text) = block
+            && let Ok(value) = serde_json::from_str::<Value>(&text.text)
+        {
+            text.text = summarize_value(tool_name, value, project_root);
+        }
+    }
+    result
+}
+
+/// Summary of a JSON tool result
+fn summarize_value(tool_name: &str, value: Value, project_root: &Path) -> String {
+    let summary = match tool_name {
+        "analyze_symbol_context" => serde_json::from_value::<AnalyzerResult>(value.clone())
+            .ok()
+            .map(|analysis| analysis_summary(&analysis)),
+        "analyze_symbols_batch" => serde_json::from_value::<BatchAnalyzerResult>(value.clone())
+            .ok()
+            .map(|batch| batch_summary(&batch)),
+        "search_symbols" => serde_json::from_value::<SearchResult>(value.clone())
+            .ok()
+            .map(|search| search_summary(&search)),
+        _ => None,
+    };
+
+    let summary = summary.unwrap_or_else(|| {
+        let mut lines = Vec::new();
+        render_value(&value, 0, &mut lines);
+        lines.join("\n")
+    });
+    let root_prefix = format!("{}/", project_root.display());
+    summary.replace(&root_prefix, "")
+}
+
+// ============================================================================
+// Symbol Summaries
+// ============================================================================
+
+/// Lowercase name of a symbol kind, e.g. "class"
+fn kind_name(kind: lsp_types::SymbolKind) -> String {
+    format!("{kind:?}").to_lowercase()
+}
+
+/// `file:line` of a location, 1-based
+fn short_location(location: &FileLocation) -> String {
+    format!(
+        "{}:{}",
+        location.file_path.display(),
+        location.range.start.line + 1
+    )
+}
+
+/// Count with a singular or plural noun, e.g. "1 member", "8 members"
+fn counted(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else if noun.ends_with("ss") || noun.ends_with("ch") {
+        format!("{count} {noun}es")
+    } else if let Some(stem) = noun.strip_suffix('y') {
+        format!("{count} {stem}ies")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Summary of one symbol analysis: a headline plus signature and brief
+fn analysis_summary(analysis: &AnalyzerResult) -> String {
+    let symbol = &analysis.symbol;
+    let name = match &symbol.container_name {
+        Some(container) if !container.is_empty() => format!("{container}::{}", symbol.name),
+        _ => symbol.name.clone(),
+    };
+    let place = match analysis.definitions.first() {
+        Some(definition) => format!("defined at {}", short_location(definition)),
+        None => format!("declared at {}", short_location(&symbol.location)),
+    };
+
+    let mut facts = vec![format!("{name} ({}) {place}", kind_name(symbol.kind))];
+    if let Some(members) = &analysis.members {
+        let count = serde_json::to_value(members)
+            .ok()
+            .and_then(|members| {
+                members.as_object().map(|groups| {
+                    groups
+                        .values()
+                        .filter_map(Value::as_array)
+                        .map(Vec::len)
+                        .sum::<usize>()
+                })
+            })
+            .unwrap_or_default();
+        facts.push(counted(count, "member"));
+    }
+    if let Some(enumerators) = &analysis.enumerators {
+        facts.push(counted(enumerators.len(), "enumerator"));
+    }
+    if let Some(hierarchy) = &analysis.type_hierarchy {
+        if !hierarchy.supertypes.is_empty() {
+            facts.push(counted(hierarchy.supertypes.len(), "base class"));
+        }
+        facts.push(counted(hierarchy.subtypes.len(), "derived class"));
+    }
+    if let Some(calls) = &analysis.call_hierarchy {
+        facts.push(counted(calls.callers.len(), "caller"));
+        facts.push(counted(calls.callees.len(), "callee"));
+    }
+    if !analysis.references_by_file.is_empty() {
+        let references = analysis
+            .references_by_file
+            .iter()
+            .map(|file| file.reference_count)
+            .sum();
+        facts.push(format!(
+            "{} in {}",
+            counted(references, "reference"),
+            counted(analysis.references_by_file.len(), "file")
+        ));
+    }
+
+    let mut lines = vec![facts.join(", ")];
+    if let Some(detail) = analysis
+        .detail
+        .as_deref()
+        .filter(|detail| !detail.is_empty())
+    {
+        lines.push(format!("  signature: {detail}"));
+    }
+    if let Some(brief) = analysis
+        .documentation
+        .as_ref()
+        .and_then(|documentation| documentation.brief.as_deref())
+    {
+        lines.push(format!("  brief: {}", single_line(brief)));
+    }
+    lines.join("\n")
+}
+
+/// Summary of a batch analysis: one block per requested symbol
+fn batch_summary(batch: &BatchAnalyzerResult) -> String {
+    let mut lines = vec![format!(
+        "{} analyzed, {} failed",
+        batch.succeeded, batch.failed
+    )];
+    for entry in &batch.results {
+        match (&entry.analysis, &entry.error) {
+            (Some(analysis), _) => lines.push(analysis_summary(analysis)),
+            (None, Some(error)) => lines.push(format!("{}: {}", entry.query, error)),
+            (None, None) => lines.push(format!("{}: no result", entry.query)),
+        }
+    }
+    lines.join("\n")
+}
+
+/// Summary of a symbol search: a count and one line per symbol
+fn search_summary(search: &SearchResult) -> String {
+    let mut lines = vec![format!(
+        "{} for \"{}\"",
+        counted(search.total_matches, "match"),
+        search.query
+    )];
+    if search.symbols.len() < search.total_matches {
+        lines[0].push_str(&format!(" ({} shown)", search.symbols.len()));
+    }
+    for symbol in &search.symbols {
+        let container = symbol
+            .container_name
+            .as_deref()
+            .filter(|container| !container.is_empty())
+            .map(|container| format!(" in {container}"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{} ({}){} at {}",
+            symbol.name,
+            kind_name(symbol.kind),
+            container,
+            short_location(&symbol.location)
+        ));
+    }
+    lines.join("\n")
+}
+
+// ============================================================================
+// Generic Summary
+// ============================================================================
+
+/// Collapse a string to one line, shortening it beyond the summary limit
+fn single_line(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > MAX_SUMMARY_STRING_CHARS {
+        let shortened: String = line.chars().take(MAX_SUMMARY_STRING_CHARS).collect();
+        format!("{shortened}…")
+    } else {
+        line
+    }
+}
+
+/// Whether a value carries no information worth a line
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        Value::String(text) => text.is_empty(),
+        _ => false,
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(single_line(text)),
+        Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// One-line rendering of a value inside a list
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => fields
+            .iter()
+            .filter(|(_, value)| !is_empty(value))
+            .map(|(key, value)| match value {
+                Value::Array(items) if items.iter().all(|item| scalar(item).is_some()) => {
+                    format!("{key}=[{}]", inline(value))
+                }
+                Value::Array(items) => format!("{key}=[{}]", counted(items.len(), "item")),
+                Value::Object(_) => format!("{key}={{{}}}", inline(value)),
+                _ => format!("{key}={}", inline(value)),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Array(items) => items.iter().map(inline).collect::<Vec<_>>().join(", "),
+        _ => scalar(value).unwrap_or_default(),
+    }
+}
+
+/// Render a value as indented `key: value` lines, leaving out empty fields
+fn render_value(value: &Value, depth: usize, lines: &mut Vec<String>) {
+    let pad = "  ".repeat(depth);
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields.iter().filter(|(_, value)| !is_empty(value)) {
+                match value {
+                    Value::Object(_) => {
+                        lines.push(format!("{pad}{key}:"));
+                        render_value(value, depth + 1, lines);
+                    }
+                    Value::Array(items) if items.iter().any(|item| scalar(item).is_none()) => {
+                        lines.push(format!("{pad}{key} ({}):", items.len()));
+                        render_value(value, depth + 1, lines);
+                    }
+                    _ => lines.push(format!("{pad}{key}: {}", inline(value))),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                lines.push(format!("{pad}- {}", inline(item)));
+            }
+        }
+        _ => lines.push(format!("{pad}{}", inline(value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_server::tools::ast::AstTool;
+    use crate::mcp_server::tools::project_tools::GetProjectDetailsTool;
+    use rust_mcp_sdk::schema::TextContent;
+
+    #[test]
+    fn test_take_format() {
+        let mut arguments = serde_json::json!({"symbol": "Foo", "format": "summary"})
+            .as_object()
+            .cloned();
+        assert_eq!(take_format(&mut arguments).unwrap(), ResultFormat::Summary);
+        assert!(!arguments.unwrap().contains_key(FORMAT_ARGUMENT));
+
+        assert_eq!(take_format(&mut None).unwrap(), ResultFormat::Json);
+        let mut invalid = serde_json::json!({"format": "yaml"}).as_object().cloned();
+        assert!(take_format(&mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_schema_added_to_every_tool() {
+        let tools = with_format_schema(vec![AstTool::tool(), GetProjectDetailsTool::tool()]);
+        assert!(tools.iter().all(|tool| {
+            tool.input_schema
+                .properties
+                .as_ref()
+                .is_some_and(|properties| properties.contains_key(FORMAT_ARGUMENT))
+        }));
+    }
+
+    fn summary_of(tool_name: &str, value: Value) -> String {
+        let result = CallToolResult::text_content(vec![TextContent::from(value.to_string())]);
+        let result = summarize(result, tool_name, Path::new("/project"));
+        let ContentBlock::TextContent(TextContent { text, .. }) = &result.content[0] else {
+            panic!("Expected TextContent in result");
+        };
+        text.clone()
+    }
+
+    #[test]
+    fn test_analysis_summary() {
+        let summary = summary_of(
+            "analyze_symbol_context",
+            serde_json::json!({
+                "symbol": {
+                    "name": "Circle",
+                    "kind": 5,
+                    "container_name": "shapes",
+                    "location": "/project/src/circle.h:12:7"
+                },
+                "query": "Circle",
+                "definitions": ["/project/src/circle.h:12:7-30:2"],
+                "detail": "class Circle : public Shape",
+                "examples": [],
+                "references_by_file": [
+                    {"file": "/project/src/main.cpp", "reference_count": 3, "lines": [4, 8, 9]},
+                    {"file": "/project/src/circle.cpp", "reference_count": 1, "lines": [2]}
+                ],
+                "type_hierarchy": {"supertypes": ["Shape"], "subtypes": ["Ring", "Disc", "Dot"]},
+                "members": {
+                    "methods": [
+                        {"name": "area", "member_type": "method", "signature": "double () const"},
+                        {"name": "radius", "member_type": "method", "signature": "double () const"}
+                    ],
+                    "fields": [{"name": "radius_", "member_type": "field", "signature": "double"}],
+                    "constructors": [],
+                    "destructors": [],
+                    "operators": [],
+                    "types": []
+                }
+            }),
+        );
+
+        let mut lines = summary.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "shapes::Circle (class) defined at src/circle.h:12, 3 members, 1 base class, \
+             3 derived classes, 4 references in 2 files"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "  signature: class Circle : public Shape"
+        );
+    }
+
+    #[test]
+    fn test_generic_summary_skips_empty_fields() {
+        let summary = summary_of(
+            "get_document_links",
+            serde_json::json!({
+                "file": "/project/src/main.cpp",
+                "links": [
+                    {"location": "/project/src/main.cpp:1:10", "target": "/project/src/a.h"},
+                    {"location": "/project/src/main.cpp:2:10", "target": "/usr/include/stdio.h"}
+                ],
+                "unresolved": [],
+                "document_version": 3,
+                "note": null
+            }),
+        );
+
+        assert_eq!(
+            summary,
+            "document_version: 3\n\
+             file: src/main.cpp\n\
+             links (2):\n\
+             \x20 - location=src/main.cpp:1:10, target=src/a.h\n\
+             \x20 - location=src/main.cpp:2:10, target=/usr/include/stdio.h"
+        );
+    }
+}
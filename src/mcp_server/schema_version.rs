@@ -0,0 +1,197 @@
+//! Versioning of tool result shapes
+//!
+//! Agents hardcode field paths such as `symbol.inheritance.base_classes`, so a
+//! renamed field breaks them silently. Every tool result therefore carries the
+//! version of its JSON shape: in `_meta.schema_version`, and as a top-level
+//! `schema_version` field of JSON object results. A client can pin the version
+//! it was written against with the `schema_version` argument; asking for a
+//! version the server cannot produce fails the call instead of returning a
+//! different shape.
+//!
+//! Adding fields keeps the version. Renaming or removing a field, or changing
+//! its type, bumps `SCHEMA_VERSION`; the previous shape then stays available to
+//! pinned clients by converting results back to it before stamping, and
+//! `OLDEST_SCHEMA_VERSION` only moves when that conversion is dropped.
+//!
+//! Like `format`, the argument is handled here rather than by each tool.
+
+use rust_mcp_sdk::schema::{CallToolResult, ContentBlock, Tool, schema_utils::CallToolError};
+use serde_json::Value;
+
+/// Version of the result shapes produced by this server
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Oldest result shape version the server can still produce
+pub const OLDEST_SCHEMA_VERSION: u32 = 1;
+
+/// Name of the tool argument and of the result field carrying the version
+pub const SCHEMA_VERSION_ARGUMENT: &str = "schema_version";
+
+/// Add the `schema_version` argument to the input schema of every tool
+pub fn with_schema_version_schema(mut tools: Vec<Tool>) -> Vec<Tool> {
+    let schema = serde_json::json!({
+        "type": "integer",
+        "minimum": OLDEST_SCHEMA_VERSION,
+        "maximum": SCHEMA_VERSION,
+        "description": format!(
+            "Version of the result shape the client expects. OPTIONAL, default {SCHEMA_VERSION} \
+             (the latest).\n\nEvery result reports its shape version in schema_version; pin it \
+             to keep field paths stable across server upgrades."
+        )
+    });
+    let Value::Object(schema) = schema else {
+        unreachable!("schema literal is an object");
+    };
+
+    for tool in &mut tools {
+        tool.input_schema
+            .properties
+            .get_or_insert_with(Default::default)
+            .insert(SCHEMA_VERSION_ARGUMENT.to_string(), schema.clone());
+    }
+    tools
+}
+
+/// Remove the `schema_version` argument from tool arguments and check it
+///
+/// Returns the version the result must be produced in.
+pub fn take_schema_version(
+    arguments: &mut Option<serde_json::Map<String, Value>>,
+) -> Result<u32, CallToolError> {
+    let Some(value) = arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(SCHEMA_VERSION_ARGUMENT))
+    else {
+        return Ok(SCHEMA_VERSION);
+    };
+    if value.is_null() {
+        return Ok(SCHEMA_VERSION);
+    }
+
+    let invalid = |message: String| {
+        CallToolError::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            message,
+        ))
+    };
+    let version: u32 = serde_json::from_value(value)
+        .map_err(|e| invalid(format!("Invalid {SCHEMA_VERSION_ARGUMENT}: {e}")))?;
+    if !(OLDEST_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version) {
+        return Err(invalid(format!(
+            "Unsupported {SCHEMA_VERSION_ARGUMENT} {version}: this server produces versions \
+             {OLDEST_SCHEMA_VERSION} to {SCHEMA_VERSION}"
+        )));
+    }
+    Ok(version)
+}
+
+/// Stamp a tool result with the version of its shape
+///
+/// Sets `_meta.schema_version` and adds `schema_version` as the first field of
+/// JSON object text content, keeping its formatting. Other text is left
+/// unchanged.
+pub fn with_schema_version(mut result: CallToolResult, version: u32) -> CallToolResult {
+    result
+        .meta
+        .get_or_insert_with(serde_json::Map::new)
+        .insert(SCHEMA_VERSION_ARGUMENT.to_string(), Value::from(version));
+    for block in &mut result.content {
+        if let ContentBlock::TextContent(text) = block
+            && let Some(stamped) = insert_version_field(&text.text, version)
+        {
+            text.text = stamped;
+        }
+    }
+    result
+}
+
+/// Insert the version field at the start of a JSON object document
+fn insert_version_field(json: &str, version: u32) -> Option<String> {
+    let Ok(Value::Object(object)) = serde_json::from_str::<Value>(json) else {
+        return None;
+    };
+    if object.contains_key(SCHEMA_VERSION_ARGUMENT) {
+        return None;
+    }
+
+    let (head, body) = json.split_at(json.find('{')? + 1);
+    // Same whitespace as before the first field, which is the indentation of pretty output
+    let indentation = &body[..body.len() - body.trim_start().len()];
+    let separator = if indentation.is_empty() { ":" } else { ": " };
+    let field = format!("\"{SCHEMA_VERSION_ARGUMENT}\"{separator}{version}");
+    if object.is_empty() {
+        return Some(format!("{head}{field}{}", body.trim_start()));
+    }
+    Some(format!("{head}{indentation}{field},{body}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_sdk::schema::TextContent;
+
+    fn texts(result: &CallToolResult) -> Vec<&str> {
+        result
+            .content
+            .iter()
+            .map(|block| match block {
+                ContentBlock::TextContent(text) => text.text.as_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_with_schema_version() {
+        let result = CallToolResult::text_content(vec![
+            TextContent::from("{\n  \"symbol\": \"Math::add\"\n}".to_string()),
+            TextContent::from("{\"count\":2}".to_string()),
+            TextContent::from("{}".to_string()),
+            TextContent::from("[1, 2]".to_string()),
+            TextContent::from("Math::add (function)".to_string()),
+        ]);
+
+        let result = with_schema_version(result, 1);
+        assert_eq!(result.meta.as_ref().unwrap()["schema_version"], 1);
+        assert_eq!(
+            texts(&result),
+            vec![
+                "{\n  \"schema_version\": 1,\n  \"symbol\": \"Math::add\"\n}",
+                "{\"schema_version\":1,\"count\":2}",
+                "{\"schema_version\":1}",
+                "[1, 2]",
+                "Math::add (function)",
+            ]
+        );
+        for text in &texts(&result)[..3] {
+            let value: Value = serde_json::from_str(text).unwrap();
+            assert_eq!(value["schema_version"], 1);
+        }
+    }
+
+    #[test]
+    fn test_take_schema_version() {
+        let mut arguments = None;
+        assert_eq!(take_schema_version(&mut arguments).unwrap(), SCHEMA_VERSION);
+
+        let mut arguments = Some(serde_json::Map::from_iter([
+            ("symbol".to_string(), Value::from("Math")),
+            (SCHEMA_VERSION_ARGUMENT.to_string(), Value::from(1)),
+        ]));
+        assert_eq!(take_schema_version(&mut arguments).unwrap(), 1);
+        assert!(!arguments.unwrap().contains_key(SCHEMA_VERSION_ARGUMENT));
+
+        let mut arguments = Some(serde_json::Map::from_iter([(
+            SCHEMA_VERSION_ARGUMENT.to_string(),
+            Value::from(SCHEMA_VERSION + 1),
+        )]));
+        let error = take_schema_version(&mut arguments).unwrap_err();
+        assert!(error.to_string().contains("Unsupported schema_version"));
+
+        let mut arguments = Some(serde_json::Map::from_iter([(
+            SCHEMA_VERSION_ARGUMENT.to_string(),
+            Value::from("latest"),
+        )]));
+        assert!(take_schema_version(&mut arguments).is_err());
+    }
+}
@@ -1,24 +1,82 @@
 use async_trait::async_trait;
 use rust_mcp_sdk::schema::{
-    CallToolRequestParams, CallToolResult, ListToolsResult, PaginatedRequestParams, RpcError,
+    CallToolRequestParams, CallToolResult, ListResourcesResult, ListToolsResult,
+    PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, RpcError,
     schema_utils::CallToolError,
 };
 use rust_mcp_sdk::{McpServer, mcp_server::ServerHandler};
 use tracing::{Level, info};
 
+use super::concurrency::{ToolConcurrencyLimiter, UNLIMITED_TOOLS};
+use super::resources::ResultResources;
+use super::result_format::{self, ResultFormat};
+use super::schema_version;
 use super::server_helpers::{self, McpToolHandler};
+use super::tools::active_macros::GetActiveMacrosTool;
+use super::tools::analyze_edit::AnalyzeEditTool;
 use super::tools::analyze_symbols::AnalyzeSymbolContextTool;
+use super::tools::analyze_symbols_batch::AnalyzeSymbolsBatchTool;
+use super::tools::ast::AstTool;
+use super::tools::batch_hover::BatchHoverTool;
+use super::tools::blast_radius::GetBlastRadiusTool;
+use super::tools::build_freshness::CheckBuildFreshnessTool;
+use super::tools::build_targets::ListBuildTargetsTool;
+use super::tools::clang_tidy::ClangTidyTool;
+use super::tools::clangd_log::GetClangdLogTool;
+use super::tools::comment_markers::FindCommentMarkersTool;
+use super::tools::compile_coverage::CheckCompileCoverageTool;
+use super::tools::cross_references::FindCrossReferencesTool;
+use super::tools::declarations::FindDeclarationsTool;
+use super::tools::document_links::DocumentLinksTool;
+use super::tools::duplicate_definitions::FindDuplicateDefinitionsTool;
+use super::tools::enclosing_function::GetEnclosingFunctionTool;
+use super::tools::expression_type::GetExpressionTypeTool;
+use super::tools::find_overrides::FindOverridesTool;
+use super::tools::format_style::GetFormatStyleTool;
+use super::tools::function_metrics::FunctionMetricsTool;
+use super::tools::header_includers::FindHeaderIncludersTool;
+use super::tools::inactive_regions::GetInactiveRegionsTool;
+use super::tools::include_graph::GetIncludeGraphTool;
+use super::tools::index_snapshot::IndexSnapshotTool;
+use super::tools::index_status::WorkspaceIndexStatusTool;
+use super::tools::list_symbols_by_kind::ListSymbolsByKindTool;
+use super::tools::namespaces::ListNamespacesTool;
+use super::tools::operators::FindOperatorTool;
 use super::tools::project_tools::GetProjectDetailsTool;
+use super::tools::reset_clangd::ResetClangdTool;
+use super::tools::resolve_include::ResolveIncludeTool;
+use super::tools::resolve_type_alias::ResolveTypeAliasTool;
 use super::tools::search_symbols::SearchSymbolsTool;
-use crate::project::{ProjectError, ProjectWorkspace, WorkspaceSession};
+use super::tools::selection_range::SelectionRangeTool;
+use super::tools::signature_diff::DiffSignaturesTool;
+use super::tools::switch_source_header::SwitchSourceHeaderTool;
+use super::tools::symbol_exists::SymbolExistsTool;
+use super::tools::system_headers::CheckSystemHeadersTool;
+use super::tools::test_locations::FindTestsTool;
+use super::tools::utils;
+use super::tools::warm_up::WarmUpFilesTool;
+use super::unsaved_files::{self, UNSAVED_FILES_ARGUMENT};
+use crate::clangd::config::PchStorage;
+use crate::io::file_buffer::with_source_overlay;
+use crate::project::index::EmptyResultRetry;
+use crate::project::index::location::IndexStorageOptions;
+use crate::project::{ComponentSession, ProjectError, ProjectWorkspace, WorkspaceSession};
 use crate::register_tools;
 use crate::{log_mcp_message, log_timing};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 pub struct CppServerHandler {
     workspace_session: WorkspaceSession,
+    compact_json: bool,
+    default_build_directory: Option<String>,
+    index_wait_timeout: Option<u64>,
+    tool_limiter: ToolConcurrencyLimiter,
+    result_resources: ResultResources,
+    /// Held exclusively by calls with unsaved files, shared by the calls they affect
+    unsaved_files_lock: tokio::sync::RwLock<()>,
 }
 
 impl CppServerHandler {
@@ -27,83 +85,1198 @@ impl CppServerHandler {
         clangd_path: String,
     ) -> Result<Self, ProjectError> {
         let workspace_session = WorkspaceSession::new(project_workspace, clangd_path)?;
-        Ok(Self { workspace_session })
+        Ok(Self::with_workspace_session(workspace_session))
+    }
+
+    fn with_workspace_session(workspace_session: WorkspaceSession) -> Self {
+        Self {
+            workspace_session,
+            compact_json: false,
+            default_build_directory: None,
+            index_wait_timeout: None,
+            tool_limiter: ToolConcurrencyLimiter::default(),
+            result_resources: ResultResources::default(),
+            unsaved_files_lock: tokio::sync::RwLock::new(()),
+        }
+    }
+
+    /// Emit tool results as compact JSON instead of pretty-printed JSON
+    pub fn with_compact_json(mut self, compact_json: bool) -> Self {
+        self.compact_json = compact_json;
+        self
+    }
+
+    /// Limit the number of threads each clangd uses for background indexing
+    pub fn with_clangd_jobs(mut self, jobs: Option<u32>) -> Self {
+        self.workspace_session = self.workspace_session.with_indexing_threads(jobs);
+        self
+    }
+
+    /// Select where each clangd stores the preambles of open files
+    pub fn with_pch_storage(mut self, storage: PchStorage) -> Self {
+        self.workspace_session = self.workspace_session.with_pch_storage(storage);
+        self
+    }
+
+    /// Pass additional arguments to every clangd process
+    pub fn with_clangd_args(mut self, args: Vec<String>) -> Self {
+        self.workspace_session = self.workspace_session.with_clangd_args(args);
+        self
+    }
+
+    /// Set environment variables for every clangd process
+    pub fn with_clangd_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.workspace_session = self.workspace_session.with_clangd_env(env);
+        self
+    }
+
+    /// Run clangd processes in the given directory instead of the project root
+    pub fn with_clangd_working_directory(mut self, directory: Option<PathBuf>) -> Self {
+        self.workspace_session = self
+            .workspace_session
+            .with_clangd_working_directory(directory);
+        self
+    }
+
+    /// Keep the clangd index in a configured directory and load a static index
+    pub fn with_index_storage(mut self, index: IndexStorageOptions) -> Self {
+        self.workspace_session = self.workspace_session.with_index_storage(index);
+        self
+    }
+
+    /// Run clangd without background index and skip waiting for indexing
+    pub fn with_quiet_indexing(mut self, quiet: bool) -> Self {
+        self.workspace_session = self.workspace_session.with_quiet_indexing(quiet);
+        self
+    }
+
+    /// Send the given root URI to clangd instead of the project root
+    pub fn with_lsp_root_uri(mut self, root_uri: Option<String>) -> Self {
+        self.workspace_session = self.workspace_session.with_lsp_root_uri(root_uri);
+        self
+    }
+
+    /// Configure retries of empty workspace query results right after indexing completes
+    pub fn with_empty_result_retry(mut self, retry: EmptyResultRetry) -> Self {
+        self.workspace_session = self.workspace_session.with_empty_result_retry(retry);
+        self
+    }
+
+    /// Use a build directory for tool calls that do not specify one
+    pub fn with_default_build_directory(mut self, build_directory: Option<String>) -> Self {
+        self.default_build_directory = build_directory;
+        self
+    }
+
+    /// Override the default indexing wait timeout (in seconds) of tool calls
+    pub fn with_index_wait_timeout(mut self, timeout: Option<u64>) -> Self {
+        self.index_wait_timeout = timeout;
+        self
+    }
+
+    /// Limit the number of clangd-backed tool calls executed at the same time
+    ///
+    /// Excess calls wait in arrival order for a free slot.
+    pub fn with_max_concurrent_tools(mut self, limit: usize) -> Self {
+        self.tool_limiter = ToolConcurrencyLimiter::new(limit);
+        self
+    }
+
+    /// Return text results longer than `threshold` bytes as MCP resources
+    ///
+    /// `None` keeps every result inline.
+    pub fn with_resource_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.result_resources = ResultResources::new(threshold);
+        self
     }
 
     /// Resolves build directory from optional parameter using the helper function.
+    ///
+    /// Falls back to the configured default build directory when none is requested.
     async fn resolve_build_directory(
         &self,
-        requested_build_dir: Option<&str>,
-    ) -> Result<PathBuf, CallToolError> {
-        let workspace = self.workspace_session.get_workspace().lock().await;
-        server_helpers::resolve_build_directory(&workspace, requested_build_dir)
+        requested_build_dir: Option<&str>,
+    ) -> Result<PathBuf, CallToolError> {
+        let workspace = self.workspace_session.workspace().await;
+        server_helpers::resolve_build_directory(
+            &workspace,
+            requested_build_dir.or(self.default_build_directory.as_deref()),
+        )
+    }
+
+    /// Resolves the component session a tool call targets.
+    ///
+    /// An explicit session id takes precedence; otherwise the build directory is
+    /// resolved and its (possibly newly created) component session returned.
+    async fn resolve_component_session(
+        &self,
+        session_id: Option<&str>,
+        requested_build_dir: Option<&str>,
+    ) -> Result<Arc<ComponentSession>, CallToolError> {
+        if let Some(session_id) = session_id {
+            return self
+                .workspace_session
+                .get_component_session_by_id(session_id)
+                .await
+                .map_err(|e| {
+                    CallToolError::new(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        e.to_string(),
+                    ))
+                });
+        }
+
+        let build_dir = self.resolve_build_directory(requested_build_dir).await?;
+
+        self.workspace_session
+            .get_component_session(build_dir)
+            .await
+            .map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!(
+                    "ComponentSession creation failed: {}",
+                    e
+                )))
+            })
+    }
+
+    /// Dispatches a tool call with the client's unsaved buffers applied.
+    ///
+    /// The buffers are sent to the session the tool targets before the call and
+    /// replaced by the on-disk content afterwards, whether the call succeeded or not.
+    /// Source text the tool reads itself comes from the same buffers. Such calls run
+    /// exclusively of other calls accepting unsaved files or opening documents
+    /// themselves, which would otherwise see the buffers too.
+    async fn dispatch_with_unsaved_files(
+        &self,
+        tool_name: &str,
+        mut arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<CallToolResult, CallToolError> {
+        let unsaved = unsaved_files::take_unsaved_files(&mut arguments)?;
+        if unsaved.is_empty() {
+            let _shared = if unsaved_files::waits_for_unsaved_files(tool_name) {
+                Some(self.unsaved_files_lock.read().await)
+            } else {
+                None
+            };
+            return self.dispatch_tool(tool_name, arguments).await;
+        }
+        if !unsaved_files::accepts_unsaved_files(tool_name)
+            && Self::registered_tools()
+                .iter()
+                .any(|tool| tool.name == tool_name)
+        {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{tool_name} does not accept {UNSAVED_FILES_ARGUMENT}"),
+            )));
+        }
+
+        let argument = |name: &str| {
+            arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get(name))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+        let component_session = self
+            .resolve_component_session(
+                argument("session_id").as_deref(),
+                argument("build_directory").as_deref(),
+            )
+            .await?;
+        let _exclusive = self.unsaved_files_lock.write().await;
+        let applied = {
+            let workspace = self.workspace_session.workspace().await;
+            unsaved_files::apply_unsaved_files(&component_session, &workspace, &unsaved).await?
+        };
+        // CallToolError is not Send, so keep only its message across the restore
+        let buffers = applied
+            .iter()
+            .cloned()
+            .zip(unsaved.into_iter().map(|unsaved_file| unsaved_file.content));
+        let result = with_source_overlay(buffers, self.dispatch_tool(tool_name, arguments))
+            .await
+            .map_err(|e| e.to_string());
+        unsaved_files::restore_unsaved_files(&component_session, &applied).await;
+        result.map_err(|message| CallToolError::new(std::io::Error::other(message)))
+    }
+
+    /// Runs a tool call behind the concurrency limit and post-processes its result.
+    async fn run_tool(
+        &self,
+        tool_name: &str,
+        mut arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<CallToolResult, CallToolError> {
+        // Queue clangd-backed calls beyond the concurrency limit
+        let permit = if UNLIMITED_TOOLS.contains(&tool_name) {
+            None
+        } else {
+            Some(self.tool_limiter.acquire().await)
+        };
+
+        let format = result_format::take_format(&mut arguments)?;
+        let schema_version = schema_version::take_schema_version(&mut arguments)?;
+
+        // Generated dispatch with compile-time safety
+        let mut result = utils::with_compact_json(
+            self.compact_json,
+            self.dispatch_with_unsaved_files(tool_name, arguments),
+        )
+        .await?;
+        if let Some(queue) = permit.as_ref().and_then(|permit| permit.queue.as_ref()) {
+            info!(
+                "Tool {} waited {} ms for one of {} slots (queue depth {}, {} still queued)",
+                tool_name,
+                queue.waited_ms,
+                self.tool_limiter.limit(),
+                queue.queue_depth,
+                self.tool_limiter.queued()
+            );
+            result = server_helpers::with_queue_meta(result, queue);
+        }
+        drop(permit);
+        if format == ResultFormat::Summary {
+            let project_root = self
+                .workspace_session
+                .workspace()
+                .await
+                .project_root_path
+                .clone();
+            result = result_format::summarize(result, tool_name, &project_root);
+        }
+        result = schema_version::with_schema_version(result, schema_version);
+        result = self.result_resources.offload(result, tool_name);
+        Ok(result)
+    }
+}
+
+// Implement McpToolHandler trait for each tool type
+impl McpToolHandler<GetProjectDetailsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_project_details";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetProjectDetailsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let workspace = self.workspace_session.workspace().await;
+        tool.call_tool(&workspace)
+    }
+}
+
+impl McpToolHandler<CheckBuildFreshnessTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "check_build_freshness";
+
+    async fn call_tool_async(
+        &self,
+        tool: CheckBuildFreshnessTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let workspace = self.workspace_session.workspace().await;
+        tool.call_tool(&workspace)
+    }
+}
+
+impl McpToolHandler<GetFormatStyleTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_format_style";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetFormatStyleTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let workspace = self.workspace_session.workspace().await;
+        tool.call_tool(&workspace)
+    }
+}
+
+impl McpToolHandler<ListBuildTargetsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "list_build_targets";
+
+    async fn call_tool_async(
+        &self,
+        tool: ListBuildTargetsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let workspace = self.workspace_session.workspace().await;
+        tool.call_tool(&workspace)
+    }
+}
+
+impl McpToolHandler<SearchSymbolsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "search_symbols";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: SearchSymbolsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<AnalyzeSymbolContextTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "analyze_symbol_context";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: AnalyzeSymbolContextTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<ResolveIncludeTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "resolve_include";
+
+    async fn call_tool_async(
+        &self,
+        tool: ResolveIncludeTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<ResolveTypeAliasTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "resolve_type_alias";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: ResolveTypeAliasTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<ClangTidyTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "clang_tidy";
+
+    async fn call_tool_async(&self, tool: ClangTidyTool) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetEnclosingFunctionTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_enclosing_function";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetEnclosingFunctionTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindCommentMarkersTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_comment_markers";
+
+    async fn call_tool_async(
+        &self,
+        tool: FindCommentMarkersTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetIncludeGraphTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_include_graph";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetIncludeGraphTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<WorkspaceIndexStatusTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "workspace_index_status";
+
+    async fn call_tool_async(
+        &self,
+        tool: WorkspaceIndexStatusTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let sessions = self.workspace_session.active_component_sessions().await;
+        let workspace = self.workspace_session.workspace().await;
+        tool.call_tool(&sessions, &workspace).await
+    }
+}
+
+impl McpToolHandler<ResetClangdTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "reset_clangd";
+
+    async fn call_tool_async(
+        &self,
+        tool: ResetClangdTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        // A session id identifies the build directory to restart
+        let build_dir = match tool.session_id.as_deref() {
+            Some(session_id) => self
+                .resolve_component_session(Some(session_id), None)
+                .await?
+                .build_dir()
+                .clone(),
+            None => {
+                self.resolve_build_directory(tool.build_directory.as_deref())
+                    .await?
+            }
+        };
+
+        let (previous_session_id, component_session) = self
+            .workspace_session
+            .restart_component_session(build_dir)
+            .await
+            .map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!(
+                    "ComponentSession restart failed: {}",
+                    e
+                )))
+            })?;
+
+        let result = tool
+            .call_tool(
+                previous_session_id,
+                Arc::clone(&component_session),
+                self.workspace_session.clangd_version(),
+            )
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<IndexSnapshotTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "index_snapshot";
+
+    async fn call_tool_async(
+        &self,
+        tool: IndexSnapshotTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let result = tool.call_tool(&component_session).await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindOverridesTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_overrides";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: FindOverridesTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<AnalyzeEditTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "analyze_edit";
+
+    async fn call_tool_async(
+        &self,
+        tool: AnalyzeEditTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<AstTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_ast";
+
+    async fn call_tool_async(&self, tool: AstTool) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<AnalyzeSymbolsBatchTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "analyze_symbols_batch";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: AnalyzeSymbolsBatchTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<SelectionRangeTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_selection_range";
+
+    async fn call_tool_async(
+        &self,
+        tool: SelectionRangeTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FunctionMetricsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_function_metrics";
+
+    async fn call_tool_async(
+        &self,
+        tool: FunctionMetricsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindHeaderIncludersTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_header_includers";
+
+    async fn call_tool_async(
+        &self,
+        tool: FindHeaderIncludersTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
     }
 }
 
-// Implement McpToolHandler trait for each tool type
-impl McpToolHandler<GetProjectDetailsTool> for CppServerHandler {
-    const TOOL_NAME: &'static str = "get_project_details";
+impl McpToolHandler<DocumentLinksTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_document_links";
 
     async fn call_tool_async(
         &self,
-        tool: GetProjectDetailsTool,
+        tool: DocumentLinksTool,
     ) -> Result<CallToolResult, CallToolError> {
-        let workspace = self.workspace_session.get_workspace().lock().await;
-        tool.call_tool(&workspace)
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
     }
 }
 
-impl McpToolHandler<SearchSymbolsTool> for CppServerHandler {
-    const TOOL_NAME: &'static str = "search_symbols";
+impl McpToolHandler<FindDuplicateDefinitionsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_duplicate_definitions";
 
     async fn call_tool_async(
         &self,
-        tool: SearchSymbolsTool,
+        mut tool: FindDuplicateDefinitionsTool,
     ) -> Result<CallToolResult, CallToolError> {
-        let build_dir = self
-            .resolve_build_directory(tool.build_directory.as_deref())
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
             .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<SwitchSourceHeaderTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "switch_source_header";
 
+    async fn call_tool_async(
+        &self,
+        tool: SwitchSourceHeaderTool,
+    ) -> Result<CallToolResult, CallToolError> {
         let component_session = self
-            .workspace_session
-            .get_component_session(build_dir)
-            .await
-            .map_err(|e| {
-                CallToolError::new(std::io::Error::other(format!(
-                    "ComponentSession creation failed: {}",
-                    e
-                )))
-            })?;
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
 
-        let workspace = self.workspace_session.get_workspace().lock().await;
-        tool.call_tool(component_session, &workspace).await
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
     }
 }
 
-impl McpToolHandler<AnalyzeSymbolContextTool> for CppServerHandler {
-    const TOOL_NAME: &'static str = "analyze_symbol_context";
+impl McpToolHandler<CheckSystemHeadersTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "check_system_headers";
 
     async fn call_tool_async(
         &self,
-        tool: AnalyzeSymbolContextTool,
+        tool: CheckSystemHeadersTool,
     ) -> Result<CallToolResult, CallToolError> {
-        let build_dir = self
-            .resolve_build_directory(tool.build_directory.as_deref())
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
             .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<BatchHoverTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "batch_hover";
 
+    async fn call_tool_async(&self, tool: BatchHoverTool) -> Result<CallToolResult, CallToolError> {
         let component_session = self
-            .workspace_session
-            .get_component_session(build_dir)
-            .await
-            .map_err(|e| {
-                CallToolError::new(std::io::Error::other(format!(
-                    "ComponentSession creation failed: {}",
-                    e
-                )))
-            })?;
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<ListSymbolsByKindTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "list_symbols_by_kind";
+
+    async fn call_tool_async(
+        &self,
+        tool: ListSymbolsByKindTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<CheckCompileCoverageTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "check_compile_coverage";
+
+    async fn call_tool_async(
+        &self,
+        tool: CheckCompileCoverageTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetActiveMacrosTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_active_macros";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetActiveMacrosTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetInactiveRegionsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_inactive_regions";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetInactiveRegionsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindDeclarationsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_declarations";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: FindDeclarationsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindTestsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_tests";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: FindTestsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetBlastRadiusTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_blast_radius";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: GetBlastRadiusTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool.call_tool(&component_session, &workspace).await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetExpressionTypeTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_expression_type";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetExpressionTypeTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<DiffSignaturesTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "diff_signatures";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: DiffSignaturesTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool.call_tool(&component_session, &workspace).await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<WarmUpFilesTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "warm_up_files";
+
+    async fn call_tool_async(
+        &self,
+        tool: WarmUpFilesTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<GetClangdLogTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "get_clangd_log";
+
+    async fn call_tool_async(
+        &self,
+        tool: GetClangdLogTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let result = tool.call_tool(&component_session).await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<ListNamespacesTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "list_namespaces";
+
+    async fn call_tool_async(
+        &self,
+        tool: ListNamespacesTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindCrossReferencesTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_cross_references";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: FindCrossReferencesTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<FindOperatorTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "find_operator";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: FindOperatorTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
+
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
+    }
+}
+
+impl McpToolHandler<SymbolExistsTool> for CppServerHandler {
+    const TOOL_NAME: &'static str = "symbol_exists";
+
+    async fn call_tool_async(
+        &self,
+        mut tool: SymbolExistsTool,
+    ) -> Result<CallToolResult, CallToolError> {
+        tool.wait_timeout = tool.wait_timeout.or(self.index_wait_timeout);
+        let component_session = self
+            .resolve_component_session(tool.session_id.as_deref(), tool.build_directory.as_deref())
+            .await?;
 
-        let workspace = self.workspace_session.get_workspace().lock().await;
-        tool.call_tool(component_session, &workspace).await
+        let workspace = self.workspace_session.workspace().await;
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await?;
+        Ok(server_helpers::with_session_meta(
+            result,
+            component_session.session_id(),
+            component_session.build_dir(),
+        ))
     }
 }
 
@@ -113,6 +1286,45 @@ register_tools! {
         GetProjectDetailsTool => call_tool_async (async),
         SearchSymbolsTool => call_tool_async (async),
         AnalyzeSymbolContextTool => call_tool_async (async),
+        ResolveIncludeTool => call_tool_async (async),
+        ResolveTypeAliasTool => call_tool_async (async),
+        ClangTidyTool => call_tool_async (async),
+        GetEnclosingFunctionTool => call_tool_async (async),
+        WorkspaceIndexStatusTool => call_tool_async (async),
+        ResetClangdTool => call_tool_async (async),
+        FindCommentMarkersTool => call_tool_async (async),
+        GetIncludeGraphTool => call_tool_async (async),
+        IndexSnapshotTool => call_tool_async (async),
+        FindOverridesTool => call_tool_async (async),
+        AnalyzeEditTool => call_tool_async (async),
+        AstTool => call_tool_async (async),
+        AnalyzeSymbolsBatchTool => call_tool_async (async),
+        SelectionRangeTool => call_tool_async (async),
+        FunctionMetricsTool => call_tool_async (async),
+        FindHeaderIncludersTool => call_tool_async (async),
+        DocumentLinksTool => call_tool_async (async),
+        FindDuplicateDefinitionsTool => call_tool_async (async),
+        SwitchSourceHeaderTool => call_tool_async (async),
+        CheckSystemHeadersTool => call_tool_async (async),
+        BatchHoverTool => call_tool_async (async),
+        ListSymbolsByKindTool => call_tool_async (async),
+        CheckCompileCoverageTool => call_tool_async (async),
+        GetActiveMacrosTool => call_tool_async (async),
+        GetInactiveRegionsTool => call_tool_async (async),
+        FindDeclarationsTool => call_tool_async (async),
+        FindTestsTool => call_tool_async (async),
+        CheckBuildFreshnessTool => call_tool_async (async),
+        GetFormatStyleTool => call_tool_async (async),
+        ListBuildTargetsTool => call_tool_async (async),
+        GetBlastRadiusTool => call_tool_async (async),
+        GetExpressionTypeTool => call_tool_async (async),
+        DiffSignaturesTool => call_tool_async (async),
+        WarmUpFilesTool => call_tool_async (async),
+        GetClangdLogTool => call_tool_async (async),
+        ListNamespacesTool => call_tool_async (async),
+        FindCrossReferencesTool => call_tool_async (async),
+        FindOperatorTool => call_tool_async (async),
+        SymbolExistsTool => call_tool_async (async),
     }
 }
 
@@ -131,7 +1343,9 @@ impl ServerHandler for CppServerHandler {
         let result = ListToolsResult {
             meta: None,
             next_cursor: None,
-            tools: Self::registered_tools(),
+            tools: schema_version::with_schema_version_schema(result_format::with_format_schema(
+                unsaved_files::with_unsaved_files_schema(Self::registered_tools()),
+            )),
         };
 
         log_mcp_message!(Level::INFO, "outgoing", "list_tools", &result);
@@ -151,8 +1365,7 @@ impl ServerHandler for CppServerHandler {
         log_mcp_message!(Level::INFO, "incoming", "call_tool", &params);
         info!("Executing tool: {}", tool_name);
 
-        // Generated dispatch with compile-time safety
-        let result = self.dispatch_tool(&tool_name, params.arguments).await?;
+        let result = self.run_tool(&tool_name, params.arguments).await?;
 
         log_mcp_message!(Level::INFO, "outgoing", "call_tool", &result);
         log_timing!(
@@ -163,4 +1376,155 @@ impl ServerHandler for CppServerHandler {
 
         Ok(result)
     }
+
+    async fn handle_list_resources_request(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<ListResourcesResult, RpcError> {
+        log_mcp_message!(Level::INFO, "incoming", "list_resources", &params);
+
+        let result = ListResourcesResult {
+            meta: None,
+            next_cursor: None,
+            resources: self.result_resources.list(),
+        };
+
+        log_mcp_message!(Level::INFO, "outgoing", "list_resources", &result);
+        Ok(result)
+    }
+
+    async fn handle_read_resource_request(
+        &self,
+        params: ReadResourceRequestParams,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<ReadResourceResult, RpcError> {
+        log_mcp_message!(Level::INFO, "incoming", "read_resource", &params);
+
+        let Some(contents) = self.result_resources.read(&params.uri) else {
+            return Err(RpcError::invalid_params().with_message(format!(
+                "Resource not found: {} (only the latest results are kept)",
+                params.uri
+            )));
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// workspace/symbol result with a single function named `name`
+    fn workspace_symbols(uri: &str, name: &str) -> serde_json::Value {
+        serde_json::json!([{
+            "name": name,
+            "kind": 12,
+            "location": {
+                "uri": uri,
+                "range": {
+                    "start": { "line": 0, "character": 4 },
+                    "end": { "line": 0, "character": 4 + name.len() }
+                }
+            }
+        }])
+    }
+
+    fn symbol_exists_arguments(
+        session: &ComponentSession,
+    ) -> Option<serde_json::Map<String, serde_json::Value>> {
+        serde_json::json!({
+            "symbol": "parse",
+            "session_id": session.session_id(),
+            "wait_timeout": 0
+        })
+        .as_object()
+        .cloned()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limited_calls_run_concurrently() {
+        // Each clangd holds its answer until the other one received its request
+        let started = Arc::new(AtomicUsize::new(0));
+        let overlapped = Arc::new(AtomicUsize::new(0));
+        let mut projects = Vec::new();
+        let mut sessions = Vec::new();
+        for _ in 0..2 {
+            let (temp_dir, workspace) =
+                create_mock_project(&[("src/main.cpp", "int parse() { return 0; }\n")]);
+            let uri =
+                crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join("src/main.cpp"))
+                    .to_string();
+            let server = MockClangd::new();
+            let (started, overlapped) = (Arc::clone(&started), Arc::clone(&overlapped));
+            server.respond_with("workspace/symbol", move |_| {
+                started.fetch_add(1, Ordering::SeqCst);
+                let deadline = Instant::now() + Duration::from_secs(5);
+                while started.load(Ordering::SeqCst) < 2 && Instant::now() < deadline {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                if started.load(Ordering::SeqCst) >= 2 {
+                    overlapped.fetch_add(1, Ordering::SeqCst);
+                }
+                workspace_symbols(&uri, "parse")
+            });
+            sessions.push(
+                server
+                    .component_session(&workspace.components[0])
+                    .await
+                    .unwrap(),
+            );
+            projects.push((temp_dir, workspace));
+        }
+
+        let workspace = projects[0].1.clone();
+        let handler = CppServerHandler::with_workspace_session(
+            WorkspaceSession::with_component_sessions(workspace, sessions.clone()),
+        )
+        .with_max_concurrent_tools(2);
+
+        let (first, second) = tokio::join!(
+            handler.run_tool("symbol_exists", symbol_exists_arguments(&sessions[0])),
+            handler.run_tool("symbol_exists", symbol_exists_arguments(&sessions[1])),
+        );
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(overlapped.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_identical_concurrent_calls_share_lsp_request() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int parse() { return 0; }\n")]);
+        let uri =
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join("src/main.cpp"))
+                .to_string();
+        let server = MockClangd::new();
+        // Slow enough for the second call to join the request in flight
+        server.respond_with("workspace/symbol", move |_| {
+            std::thread::sleep(Duration::from_millis(200));
+            workspace_symbols(&uri, "parse")
+        });
+        let session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let handler = CppServerHandler::with_workspace_session(
+            WorkspaceSession::with_component_sessions(workspace, vec![Arc::clone(&session)]),
+        );
+
+        let (first, second) = tokio::join!(
+            handler.run_tool("symbol_exists", symbol_exists_arguments(&session)),
+            handler.run_tool("symbol_exists", symbol_exists_arguments(&session)),
+        );
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(server.received_params("workspace/symbol").len(), 1);
+    }
 }
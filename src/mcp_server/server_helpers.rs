@@ -2,9 +2,10 @@
 
 use rust_mcp_sdk::schema::{CallToolResult, schema_utils::CallToolError};
 use serde::de::DeserializeOwned;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
+use crate::mcp_server::concurrency::QueueInfo;
 use crate::project::ProjectWorkspace;
 
 /// Resolves build directory from optional parameter.
@@ -133,6 +134,39 @@ pub fn resolve_build_directory(
     }
 }
 
+/// Attaches the serving session to a tool result's `_meta` field.
+///
+/// Every session-backed tool response carries `session_id` and `build_directory`
+/// so clients can target the same clangd session in follow-up calls.
+pub fn with_session_meta(
+    mut result: CallToolResult,
+    session_id: &str,
+    build_dir: &Path,
+) -> CallToolResult {
+    let meta = result.meta.get_or_insert_with(serde_json::Map::new);
+    meta.insert(
+        "session_id".to_string(),
+        serde_json::Value::String(session_id.to_string()),
+    );
+    meta.insert(
+        "build_directory".to_string(),
+        serde_json::Value::String(build_dir.to_string_lossy().to_string()),
+    );
+    result
+}
+
+/// Attaches queueing information to a tool result's `_meta.queue` field.
+///
+/// Only added for calls that waited for a free slot of the concurrency limit.
+pub fn with_queue_meta(mut result: CallToolResult, queue: &QueueInfo) -> CallToolResult {
+    let meta = result.meta.get_or_insert_with(serde_json::Map::new);
+    meta.insert(
+        "queue".to_string(),
+        serde_json::to_value(queue).unwrap_or_default(),
+    );
+    result
+}
+
 /// Extension trait for cleaner tool argument deserialization
 pub trait ToolArguments {
     /// Deserialize MCP tool arguments to a concrete tool type
@@ -241,4 +275,30 @@ mod tests {
         let _result: fn(&ProjectWorkspace, Option<&str>) -> Result<PathBuf, CallToolError> =
             resolve_build_directory;
     }
+
+    #[test]
+    fn test_with_session_meta() {
+        let result = CallToolResult::text_content(vec![]);
+        let result = with_session_meta(result, "abc-123", Path::new("/project/build"));
+
+        let meta = result.meta.unwrap();
+        assert_eq!(meta["session_id"], "abc-123");
+        assert_eq!(meta["build_directory"], "/project/build");
+    }
+
+    #[test]
+    fn test_with_queue_meta() {
+        let result = CallToolResult::text_content(vec![]);
+        let queue = QueueInfo {
+            waited_ms: 120,
+            queue_depth: 3,
+            concurrency_limit: 8,
+        };
+        let result = with_queue_meta(result, &queue);
+
+        let meta = result.meta.unwrap();
+        assert_eq!(meta["queue"]["waited_ms"], 120);
+        assert_eq!(meta["queue"]["queue_depth"], 3);
+        assert_eq!(meta["queue"]["concurrency_limit"], 8);
+    }
 }
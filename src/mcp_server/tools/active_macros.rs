@@ -0,0 +1,273 @@
+//! Active macro definitions tool
+//!
+//! Lists the macros in effect when clangd parses a file: the `-D`/`-U` flags
+//! of its compile command and, where the compiler can be run, the macros the
+//! compiler predefines for the target and language standard. The answer
+//! explains why an `#ifdef` branch is or is not taken.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::find_includers;
+use crate::project::macro_definitions::{
+    MacroDefinition, MacroFlag, apply_macro_flags, macro_flags, query_driver_macros,
+};
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Include depth followed from each translation unit
+const MAX_DEPTH: u32 = 50;
+
+/// Maximum number of files in the include graph of one translation unit
+const MAX_NODES: usize = 2000;
+
+#[mcp_tool(
+    name = "get_active_macros",
+    description = "List the macro definitions active when clangd parses a C++ file: the -D/-U flags \
+                   of its compile command and the macros the compiler predefines for its target and \
+                   language standard. Explains why an #ifdef/#if branch is or is not taken.
+
+                   🎯 USE CASES:
+                   • Explain code shown as inactive (or active) by clangd
+                   • Check the value of configuration macros (NDEBUG, feature flags, platform macros)
+                   • Compare the macro set of a file with what the build is expected to define
+
+                   📋 OUTPUT:
+                   • compile_command_file: File whose compile command was used; for headers without
+                     an entry, a translation unit that includes them
+                   • command_line: -D/-U flags of the compile command, in order
+                   • macros: Effective macro set (name, value, origin: command_line or compiler),
+                     filtered by name_filter
+                   • macros_total: Number of effective macros before filtering
+                   • driver_error: Why the compiler could not be queried; macros then only
+                     contain the command line definitions
+
+                   Macros defined inside source files and headers are not included.
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • name_filter: Optional substring of macro names to keep (e.g. \"DEBUG\")
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetActiveMacrosTool {
+    /// File to inspect. Absolute paths are used as-is, relative paths are
+    /// resolved against the project root.
+    pub file: String,
+
+    /// Keep only macros whose name contains this substring (case-sensitive).
+    /// OPTIONAL: the compiler predefines several hundred macros, so filtering
+    /// is recommended when looking for specific ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_filter: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Where an active macro is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroOrigin {
+    /// A -D flag of the compile command
+    CommandLine,
+    /// Predefined by the compiler or a forced include
+    Compiler,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActiveMacro {
+    pub name: String,
+    pub value: String,
+    pub origin: MacroOrigin,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActiveMacrosResult {
+    /// File that was inspected
+    pub file: PathBuf,
+    /// File whose compile command was used
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub compile_command_file: Option<PathBuf>,
+    /// -D/-U flags of the compile command, in command line order
+    pub command_line: Vec<MacroFlag>,
+    /// Effective macro set, sorted by name
+    pub macros: Vec<ActiveMacro>,
+    /// Number of effective macros before name_filter was applied
+    pub macros_total: usize,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub driver_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+}
+
+impl GetActiveMacrosTool {
+    #[instrument(name = "get_active_macros", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        let compilation_database = component_session.compilation_database();
+        let mut entry = compilation_database
+            .find_entry_for_file(&file_path)
+            .cloned();
+        if entry.is_none() {
+            // Headers are parsed with the command of an including translation unit
+            let entries = compilation_database.entries().to_vec();
+            let project_root = workspace
+                .project_root_path
+                .canonicalize()
+                .unwrap_or_else(|_| workspace.project_root_path.clone());
+            let file = file_path.clone();
+            let search = tokio::task::spawn_blocking(move || {
+                find_includers(&entries, &file, &project_root, MAX_DEPTH, MAX_NODES)
+            })
+            .await
+            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+            entry = search
+                .translation_units
+                .first()
+                .and_then(|unit| compilation_database.find_entry_for_file(unit))
+                .cloned();
+        }
+
+        let Some(entry) = entry else {
+            let result = ActiveMacrosResult {
+                file: file_path,
+                compile_command_file: None,
+                command_line: Vec::new(),
+                macros: Vec::new(),
+                macros_total: 0,
+                driver_error: None,
+                note: Some(
+                    "Not in the compilation database and not included by any translation unit; \
+                     clangd guesses its flags, so no macros are known."
+                        .to_string(),
+                ),
+            };
+            let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+            return Ok(CallToolResult::text_content(vec![TextContent::from(
+                output,
+            )]));
+        };
+
+        let command_line = macro_flags(&entry.arguments);
+        let (mut macros, driver_error) = match query_driver_macros(&entry).await {
+            Ok(macros) => (macros, None),
+            Err(e) => {
+                warn!("Could not query compiler macros: {}", e);
+                (BTreeMap::new(), Some(e))
+            }
+        };
+        // The driver already applied the flags; applying them again keeps the
+        // command line definitions when it could not be run
+        apply_macro_flags(&mut macros, &command_line);
+        info!("{} active macros for {}", macros.len(), file_path.display());
+
+        let macros_total = macros.len();
+        let macros = macros
+            .into_values()
+            .filter(|definition| {
+                self.name_filter
+                    .as_deref()
+                    .is_none_or(|filter| definition.name.contains(filter))
+            })
+            .map(|MacroDefinition { name, value }| {
+                let origin = if command_line.iter().any(|flag| flag.name == name) {
+                    MacroOrigin::CommandLine
+                } else {
+                    MacroOrigin::Compiler
+                };
+                ActiveMacro {
+                    name,
+                    value,
+                    origin,
+                }
+            })
+            .collect();
+
+        let result = ActiveMacrosResult {
+            file: file_path,
+            compile_command_file: Some(entry.file.clone()),
+            command_line,
+            macros,
+            macros_total,
+            driver_error,
+            note: None,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_lists_command_line_macros_of_including_unit() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "#include \"config.h\"\n")]);
+        // Header without a compilation database entry
+        let src = workspace.project_root_path.join("src");
+        std::fs::write(src.join("config.h"), "").unwrap();
+        let database = workspace.components[0].compilation_database_path.clone();
+        let content = std::fs::read_to_string(&database)
+            .unwrap()
+            .replace("c++ -c", "c++ -DFEATURE_X=2 -UNDEBUG -D TRACE -c");
+        std::fs::write(&database, content).unwrap();
+
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool: GetActiveMacrosTool = serde_json::from_value(serde_json::json!({
+            "file": "src/config.h",
+            "name_filter": "FEATURE"
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: ActiveMacrosResult = tool_result(&result);
+
+        assert!(result.compile_command_file.unwrap().ends_with("main.cpp"));
+        assert_eq!(result.command_line.len(), 3);
+        assert!(result.command_line[1].undefine);
+        assert_eq!(result.command_line[2].name, "TRACE");
+        assert_eq!(result.macros.len(), 1);
+        assert_eq!(result.macros[0].name, "FEATURE_X");
+        assert_eq!(result.macros[0].value, "2");
+        assert_eq!(result.macros[0].origin, MacroOrigin::CommandLine);
+    }
+}
@@ -0,0 +1,458 @@
+//! What-if edit analysis tool
+//!
+//! Applies agent-provided edits to a file inside clangd only, reports the
+//! diagnostics and symbols of the edited version, then restores the on-disk
+//! content. Nothing is written to disk.
+
+use lsp_types::TextDocumentContentChangeEvent;
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::diagnostics::{get_file_diagnostics, severity_name};
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    DocumentSymbolIterator, get_document_symbols,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, Symbol, uri_from_pathbuf};
+
+/// Default time to wait for clangd to publish diagnostics of the edited file
+const DEFAULT_DIAGNOSTICS_WAIT_TIMEOUT_SECS: u64 = 10;
+
+#[mcp_tool(
+    name = "analyze_edit",
+    description = "What-if analysis of a C++ file edit without touching the file on disk. Applies the \
+                   given edits (or a full replacement content) to clangd's in-memory copy of the file, \
+                   reports the diagnostics and symbols of the edited version, then restores the \
+                   on-disk content.
+
+                   🎯 PRIMARY USE CASES:
+                   Checking whether a planned change compiles • Previewing new errors or warnings \
+                   • Seeing the declarations a change introduces or removes
+
+                   📋 OUTPUT:
+                   • version: Document version analyzed
+                   • diagnostics: Compiler and clang-tidy diagnostics of the edited file
+                     (severity, source, code, message, location)
+                   • error_count / warning_count: Diagnostic counts by severity
+                   • symbols: Declarations of the edited file with their containers and locations
+                   • diagnostics_received: false when clangd did not publish diagnostics in time
+                   • restored: Whether the on-disk content was sent back to clangd afterwards
+
+                   ⚠️ NOTE: Other requests on the same file see the edited content while the tool runs.
+                   Locations in the output refer to the edited content.
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file to edit (absolute or project-relative)
+                   • edits: Ranged edits with 1-based start/end line and column, applied in order,
+                     each to the result of the previous one
+                   • content: Full replacement content (use either edits or content)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional seconds to wait for diagnostics (default: 10s)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct AnalyzeEditTool {
+    /// File to edit in memory. Absolute paths are used as-is, relative paths are
+    /// resolved against the project root.
+    pub file: String,
+
+    /// Ranged edits to apply, in order. Each edit sees the content left by the
+    /// previous ones. Mutually exclusive with `content`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edits: Option<Vec<TextEdit>>,
+
+    /// Full replacement content of the file. Mutually exclusive with `edits`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for clangd to publish diagnostics (default: 10s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// Replacement of a range of the file
+///
+/// Lines and columns are 1-based like in location strings; the end position is
+/// exclusive. An empty range inserts `new_text`, an empty `new_text` deletes.
+#[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct TextEdit {
+    /// Line of the first replaced character (1-based)
+    pub start_line: u32,
+    /// Column of the first replaced character (1-based)
+    pub start_column: u32,
+    /// Line of the end of the replaced range (1-based)
+    pub end_line: u32,
+    /// Column just after the last replaced character (1-based)
+    pub end_column: u32,
+    /// Text replacing the range
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Convert to an LSP ranged content change
+    fn to_change_event(&self) -> Result<TextDocumentContentChangeEvent, String> {
+        let position = |line: u32, column: u32| -> Result<lsp_types::Position, String> {
+            if line == 0 || column == 0 {
+                return Err(format!(
+                    "lines and columns are 1-based, got {}:{}",
+                    line, column
+                ));
+            }
+            Ok(lsp_types::Position::new(line - 1, column - 1))
+        };
+
+        let start = position(self.start_line, self.start_column)?;
+        let end = position(self.end_line, self.end_column)?;
+        if end < start {
+            return Err(format!(
+                "edit ends at {}:{} before it starts at {}:{}",
+                self.end_line, self.end_column, self.start_line, self.start_column
+            ));
+        }
+
+        Ok(TextDocumentContentChangeEvent {
+            range: Some(lsp_types::Range::new(start, end)),
+            range_length: None,
+            text: self.new_text.clone(),
+        })
+    }
+}
+
+/// A diagnostic of the edited file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditDiagnostic {
+    pub location: FileLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// Diagnostic source, e.g. "clang" or "clang-tidy"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl EditDiagnostic {
+    fn new(file_path: &Path, diagnostic: &lsp_types::Diagnostic) -> Self {
+        Self {
+            location: FileLocation {
+                file_path: file_path.to_path_buf(),
+                range: diagnostic.range.into(),
+            },
+            severity: diagnostic.severity.map(severity_name),
+            source: diagnostic.source.clone(),
+            code: diagnostic.code.as_ref().map(|code| match code {
+                lsp_types::NumberOrString::Number(number) => number.to_string(),
+                lsp_types::NumberOrString::String(name) => name.clone(),
+            }),
+            message: diagnostic.message.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalyzeEditResult {
+    pub file: PathBuf,
+    pub version: i32,
+    pub diagnostics_received: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub diagnostics: Vec<EditDiagnostic>,
+    pub symbols: Vec<Symbol>,
+    pub restored: bool,
+}
+
+impl AnalyzeEditTool {
+    /// Content changes requested by the tool input
+    fn content_changes(&self) -> Result<Vec<TextDocumentContentChangeEvent>, CallToolError> {
+        let invalid = |message: String| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        };
+
+        match (&self.edits, &self.content) {
+            (Some(_), Some(_)) => Err(invalid(
+                "Specify either 'edits' or 'content', not both".to_string(),
+            )),
+            (None, None) => Err(invalid(
+                "Specify the 'edits' or the 'content' to analyze".to_string(),
+            )),
+            (None, Some(content)) => Ok(vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: content.clone(),
+            }]),
+            (Some(edits), None) if edits.is_empty() => {
+                Err(invalid("'edits' must not be empty".to_string()))
+            }
+            (Some(edits), None) => edits
+                .iter()
+                .enumerate()
+                .map(|(index, edit)| {
+                    edit.to_change_event()
+                        .map_err(|e| invalid(format!("Invalid edit #{}: {}", index + 1, e)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Collect diagnostics and symbols of the edited file
+    async fn analyze(
+        &self,
+        file_path: &Path,
+        component_session: &ComponentSession,
+    ) -> Result<(Option<Vec<lsp_types::Diagnostic>>, Vec<Symbol>), AnalyzerError> {
+        let timeout = Duration::from_secs(
+            self.wait_timeout
+                .unwrap_or(DEFAULT_DIAGNOSTICS_WAIT_TIMEOUT_SECS),
+        );
+        let diagnostics = get_file_diagnostics(file_path, component_session, timeout).await?;
+
+        let canonical_path = file_path
+            .canonicalize()
+            .unwrap_or_else(|_| file_path.to_path_buf());
+        let document_symbols =
+            get_document_symbols(component_session, uri_from_pathbuf(&canonical_path)).await?;
+        let symbols = DocumentSymbolIterator::new(&document_symbols)
+            .map(|(symbol, path)| {
+                let mut symbol = Symbol::from((symbol, canonical_path.as_path()));
+                symbol.container_name = (!path.is_empty()).then(|| path.join("::"));
+                symbol
+            })
+            .collect();
+
+        Ok((diagnostics, symbols))
+    }
+
+    #[instrument(name = "analyze_edit", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let changes = self.content_changes()?;
+
+        info!(
+            "Analyzing {} in-memory changes to {}",
+            changes.len(),
+            file_path.display()
+        );
+
+        let version = component_session
+            .apply_in_memory_changes(&file_path, changes)
+            .await
+            .map_err(CallToolError::new)?;
+
+        // Restore the on-disk content whether or not the analysis succeeded
+        let analysis = self.analyze(&file_path, &component_session).await;
+        let restored = match component_session.restore_file(&file_path).await {
+            Ok(restored) => restored,
+            Err(e) => {
+                warn!(
+                    "Failed to restore on-disk content of {}: {}",
+                    file_path.display(),
+                    e
+                );
+                false
+            }
+        };
+        let (diagnostics, symbols) = analysis?;
+
+        let diagnostics_received = diagnostics.is_some();
+        let diagnostics: Vec<EditDiagnostic> = diagnostics
+            .unwrap_or_default()
+            .iter()
+            .map(|diagnostic| EditDiagnostic::new(&file_path, diagnostic))
+            .collect();
+        let count_severity = |severity: &str| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity.as_deref() == Some(severity))
+                .count()
+        };
+
+        let result = AnalyzeEditResult {
+            file: file_path.clone(),
+            version,
+            diagnostics_received,
+            error_count: count_severity("error"),
+            warning_count: count_severity("warning"),
+            diagnostics,
+            symbols,
+            restored,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+        TextEdit {
+            start_line: start.0,
+            start_column: start.1,
+            end_line: end.0,
+            end_column: end.1,
+            new_text: new_text.to_string(),
+        }
+    }
+
+    fn tool(edits: Option<Vec<TextEdit>>, content: Option<&str>) -> AnalyzeEditTool {
+        AnalyzeEditTool {
+            file: "src/main.cpp".to_string(),
+            edits,
+            content: content.map(String::from),
+            build_directory: None,
+            session_id: None,
+            wait_timeout: Some(5),
+        }
+    }
+
+    #[test]
+    fn test_content_changes_validation() {
+        let changes = tool(Some(vec![edit((2, 5), (2, 9), "bar")]), None)
+            .content_changes()
+            .unwrap();
+        assert_eq!(
+            changes[0].range,
+            Some(lsp_types::Range::new(
+                lsp_types::Position::new(1, 4),
+                lsp_types::Position::new(1, 8)
+            ))
+        );
+
+        let changes = tool(None, Some("int x;")).content_changes().unwrap();
+        assert!(changes[0].range.is_none());
+
+        assert!(tool(None, None).content_changes().is_err());
+        assert!(
+            tool(Some(vec![]), Some("int x;"))
+                .content_changes()
+                .is_err()
+        );
+        assert!(
+            tool(Some(vec![edit((0, 1), (1, 1), "")]), None)
+                .content_changes()
+                .is_err()
+        );
+        assert!(
+            tool(Some(vec![edit((3, 1), (2, 1), "")]), None)
+                .content_changes()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_edit_restores_content() {
+        let original = "int foo() { return 0; }\n";
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", original)]);
+        let main_cpp = workspace.project_root_path.join("src/main.cpp");
+        let uri = uri_from_pathbuf(&main_cpp.canonicalize().unwrap());
+
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "bar",
+                "kind": 12,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 23}},
+                "selectionRange": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 7}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // Publish diagnostics once the edit reaches the server
+        let publisher = server.clone();
+        let uri_string = uri.to_string();
+        tokio::spawn(async move {
+            while publisher
+                .received_params("textDocument/didChange")
+                .is_empty()
+            {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            publisher.notify(
+                "textDocument/publishDiagnostics",
+                serde_json::json!({
+                    "uri": uri_string,
+                    "diagnostics": [{
+                        "range": {"start": {"line": 0, "character": 19}, "end": {"line": 0, "character": 20}},
+                        "severity": 1,
+                        "source": "clang",
+                        "message": "use of undeclared identifier 'y'"
+                    }]
+                }),
+            );
+        });
+
+        let tool = tool(Some(vec![edit((1, 5), (1, 8), "bar")]), None);
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: AnalyzeEditResult = tool_result(&result);
+
+        assert!(result.diagnostics_received);
+        assert_eq!(result.version, 2);
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.symbols[0].name, "bar");
+        assert!(result.restored);
+
+        // Notifications are not answered, wait for the restore to arrive
+        let mut changes = server.received_params("textDocument/didChange");
+        for _ in 0..100 {
+            if changes.len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            changes = server.received_params("textDocument/didChange");
+        }
+        assert_eq!(changes.len(), 2);
+        let edit = &changes[0].as_ref().unwrap()["contentChanges"][0];
+        assert_eq!(edit["text"], "bar");
+        assert_eq!(edit["range"]["start"]["character"], 4);
+        let restore = &changes[1].as_ref().unwrap()["contentChanges"][0];
+        assert_eq!(restore["text"], original);
+        assert!(restore.get("range").is_none());
+
+        // Versions count per document: didOpen at 1, then one per didChange
+        let versions: Vec<_> = changes
+            .iter()
+            .map(|change| change.as_ref().unwrap()["textDocument"]["version"].clone())
+            .collect();
+        assert_eq!(versions, [2, 3]);
+
+        assert_eq!(std::fs::read_to_string(&main_cpp).unwrap(), original);
+    }
+}
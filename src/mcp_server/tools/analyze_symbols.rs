@@ -9,24 +9,40 @@
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{error, info, instrument, warn};
 
-use crate::io::file_buffer::FileBufferError;
+use crate::io::file_buffer::{
+    FileBufferError, read_context_source, read_context_source_async, read_source_file_async,
+};
+use crate::lsp::PositionEncoding;
 use crate::mcp_server::tools::lsp_helpers::{
     call_hierarchy::{CallHierarchy, get_call_hierarchy},
     definitions::{get_declarations, get_definitions},
-    document_symbols::{SymbolContext, find_symbol_at_position_with_path, get_document_symbols},
-    examples::get_examples,
-    hover::get_hover_info,
-    members::{Members, get_members_from_document_symbol},
+    document_symbols::{
+        DocumentSymbolIterator, SymbolContext, find_symbol_at_position_with_path,
+        find_symbol_named, find_symbol_on_line, get_document_symbols, symbol_name_matches,
+    },
+    documentation::{Documentation, parse_hover_documentation},
+    enumerators::{Enumerator, get_enumerators},
+    examples::{FileReferences, get_references, group_references_by_file, limit_examples},
+    hover::{get_hover_info, hover_symbol_kind},
+    instantiation::{TemplateInstantiation, find_instantiation, split_template_arguments},
+    members::{
+        ACCESS_LEVELS, MEMBER_KINDS, MemberFilter, Members, get_members_from_document_symbol,
+    },
+    suggestions::{SymbolSuggestion, suggestions_hint},
     symbol_resolution::get_matching_symbol,
     type_hierarchy::{TypeHierarchy, get_type_hierarchy},
 };
 use crate::mcp_server::tools::utils;
 use crate::project::index::IndexStatusView;
-use crate::project::{ComponentSession, ProjectError, ProjectWorkspace};
-use crate::symbol::{FileLocation, Symbol};
+use crate::project::{ComponentSession, ProjectError, ProjectWorkspace, ResultExclusions};
+use crate::symbol::{FileLine, FileLocation, Symbol};
+
+/// Lines of definition source included with include_definition_source
+const MAX_DEFINITION_SOURCE_LINES: usize = 200;
 
 // ============================================================================
 // Analyzer Error Type
@@ -34,10 +50,17 @@ use crate::symbol::{FileLocation, Symbol};
 
 #[derive(Debug, thiserror::Error)]
 pub enum AnalyzerError {
-    #[error("No symbols found for '{0}'")]
-    NoSymbols(String),
+    #[error("No symbols found for '{query}'{}", suggestions_hint(.suggestions))]
+    NoSymbols {
+        query: String,
+        /// Similar symbols to suggest instead
+        suggestions: Vec<SymbolSuggestion>,
+    },
     #[error("No data found for '{0}'")]
     NoData(String),
+    /// clangd answered `null`: the request has no result, as opposed to an empty one
+    #[error("clangd returned no result for {0}")]
+    NoResult(String),
     #[error("File buffer error: {0}")]
     FileBuffer(#[from] FileBufferError),
     #[error("LSP error: {0}")]
@@ -89,12 +112,23 @@ impl From<AnalyzerError> for CallToolError {
                    • Fully qualified names: 'std::vector', 'MyNamespace::MyClass'
                    • Global scope symbols: '::main', '::global_function'
                    • Template specializations and overloaded functions
+                   • Template instantiations: 'std::vector<int>' analyzes the template and reports
+                     instantiation: a use site spelled with these arguments (use_site, use_site_code),
+                     clangd's hover there describing the instantiated type, and the explicit or
+                     partial specialization it selects, if any. clangd only knows instantiations
+                     used in indexed code; instantiation.note reports when none was found
                    • Advanced disambiguation using optional location hints
+                   • Local variables, parameters and macros when a location_hint points at a use or
+                     declaration (resolved through their definition)
+                   • Symbols missing from the workspace index but declared in a file open in clangd
 
                    📊 CORE SEMANTIC ANALYSIS:
                    • Precise symbol kind classification (class, function, variable, etc.)
                    • Complete type information with template parameters
                    • Extracted documentation comments and signatures
+                   • documentation: Comment split into brief, details, params, template_params,
+                     returns, throws, see_also, notes and deprecated from Doxygen/Javadoc tags
+                     (@param, \\return, @see, ...), next to the raw comment
                    • Definition and declaration locations with file mappings
                    • Fully qualified names with namespace resolution
 
@@ -103,12 +137,31 @@ impl From<AnalyzerError> for CallToolError {
                    • Member kind classification with string representation (method, field, constructor, etc.)
                    • Member signatures and documentation extraction
                    • Static vs instance member identification
-                   • Access level determination where available
+                   • Access level determination from access specifiers in the class body
+                   • Optional filters: member_access (public/protected/private), member_kinds
+                     (method/field/constructor/destructor/operator/type), exclude_special_members
+
+                   🧩 TRANSLATION UNIT CONTEXT:
+                   • context_file: Analyze headers in the preprocessor context (-D flags) of a
+                     specific .cpp from the compilation database, for code configured by macros
+
+                   🔢 ENUM ANALYSIS (enums):
+                   • enumerators: Enumerators in declaration order with their integer values
+                   • Values from clangd or integer literal initializers, implicit values counted up
+                   • initializer: Initializer expression as written when a value is not a plain literal
+
+                   🚦 FEATURE STATUS:
+                   • features_used: Status of hover_documentation, examples, type_hierarchy,
+                     call_hierarchy, members, enumerators and definition_source: requested_and_found,
+                     requested_but_empty, requested_but_failed (with error) or not_requested
+                     (does not apply to the symbol kind or was not asked for)
 
                    📈 USAGE EXAMPLES (always included):
                    • Concrete code snippets showing how the symbol is used throughout the codebase
                    • Real usage patterns from actual code references
                    • Automatically collected from all references to the symbol
+                   • references_by_file: All references grouped per file with reference_count and
+                     1-based lines, sorted by count (highest first) - not limited by max_examples
                    • Configurable limit via max_examples parameter (unlimited by default)
 
                    🏗️ INHERITANCE HIERARCHY ANALYSIS (optional):
@@ -116,6 +169,8 @@ impl From<AnalyzerError> for CallToolError {
                    • Derived class discovery and virtual function relationships
                    • Multiple inheritance resolution and abstract interface identification
                    • Essential for understanding polymorphic relationships
+                   • Each hierarchy request is time-limited; partial results carry timed_out: true and
+                     timed_out_steps (prepare, supertypes, subtypes)
 
                    📞 CALL RELATIONSHIP ANALYSIS (optional):
                    • Incoming call discovery (who calls this function)
@@ -138,13 +193,21 @@ impl From<AnalyzerError> for CallToolError {
                    • symbol: Required C++ symbol name to analyze (NOT file paths!)
                    • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
                    • max_examples: Optional number - limits the number of usage examples (unlimited by default)
-                   • location_hint: Optional string - location hint for disambiguating overloaded symbols (format: \"/path/file.cpp:line:column\")
-                   • wait_timeout: Optional number - timeout for indexing completion in seconds (default: 20s, 0 = no wait)
+                   • location_hint: Optional string - location hint for disambiguating overloaded symbols (format: \"/path/file.cpp:line:column\"),
+                     or just \"file.cpp:line\" (e.g. from grep -n) to analyze the symbol declared on that line
+                   • member_access / member_kinds / exclude_special_members: Optional class member filters
+                   • allow_outside_root: Optional boolean - analyze a file outside the project root (dependency,
+                     sibling checkout) given by an absolute location_hint, keeping its results
+                   • include_definition_source: Optional boolean - include the source text of the definition
+                     (up to 200 lines, flagged truncated: true beyond that)
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted file to be parsed when location_hint is given (default: 20s, 0 = no wait)
+                     When indexing has not finished, the analysis is done anyway with what clangd can answer
+                     and flagged indexing_incomplete: true - retry later for complete references and hierarchies
 
                    AUTOMATIC ANALYSIS (no flags required):
                    Inheritance hierarchy, call relationships, and usage patterns are automatically included when applicable based on symbol type."
 )]
-#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+#[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
 pub struct AnalyzeSymbolContextTool {
     /// The C++ SYMBOL NAME to analyze (NOT file paths, component names, or directory names).
     /// This must be the exact name of a C++ code symbol.
@@ -155,6 +218,10 @@ pub struct AnalyzeSymbolContextTool {
     /// • Fully qualified: "std::vector", "MyNamespace::MyClass"
     /// • Global scope: "::main", "::global_var"
     /// • Methods: "MyClass::method" (class context will be analyzed)
+    /// • Objective-C++ methods: "-[MyClass doThing:with:]", "+[MyClass shared]" or the
+    ///   bare selector "doThing:with:"
+    /// • Template instantiations: "std::vector<int>" (the template is analyzed and the
+    ///   instantiation resolved at a use site with these arguments)
     ///
     /// NOT VALID (these are not symbol names):
     /// • File paths: "src/math.cpp", "include/header.h"
@@ -188,6 +255,13 @@ pub struct AnalyzeSymbolContextTool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub build_directory: Option<String>,
 
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
     /// Maximum number of usage examples to include in the analysis. OPTIONAL.
     ///
     /// BEHAVIOR:
@@ -214,13 +288,150 @@ pub struct AnalyzeSymbolContextTool {
     /// • Targeting specific template specializations
     /// • Precise symbol selection in files with multiple symbols of same name
     ///
-    /// NOTE: Column number is required. Use editor or LSP tools to get exact position.
+    /// LINE-ONLY FORM: "file.cpp:line" (e.g. straight from a grep -n result) analyzes the
+    /// symbol declared or defined on that line, with 'symbol' used as a name hint when the
+    /// line declares several symbols. Relative paths are resolved against the project root.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub location_hint: Option<String>,
 
-    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    /// Access levels of class members to report. OPTIONAL.
+    ///
+    /// VALUES: "public", "protected", "private"
+    ///
+    /// BEHAVIOR: Applies to the members of classes and structs. Access levels are read
+    /// from the access specifiers in the class body; members whose access cannot be
+    /// determined are kept. When omitted, members of all access levels are reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_access: Option<Vec<String>>,
+
+    /// Kinds of class members to report. OPTIONAL.
+    ///
+    /// VALUES: "method", "field", "constructor", "destructor", "operator", "type" (nested types)
+    ///
+    /// EXAMPLES:
+    /// • ["method"]: Methods only
+    /// • ["field"]: Data members only
+    ///
+    /// BEHAVIOR: When omitted, members of all kinds are reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_kinds: Option<Vec<String>>,
+
+    /// Exclude constructors, destructors and operator overloads from class members. OPTIONAL (default: false).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_special_members: Option<bool>,
+
+    /// Translation unit defining the preprocessor context of the analysis. OPTIONAL.
+    ///
+    /// FORMAT: Path of a source file from compile_commands.json, e.g. "src/gpu/backend.cpp".
+    /// Relative paths are resolved against the project root.
+    ///
+    /// BEHAVIOR: The file declaring the symbol (and the location_hint file) is parsed with
+    /// the compile command of this translation unit, so symbols that differ per `-D` flag
+    /// resolve as in that translation unit. The context stays in effect for those files
+    /// in this session until another context_file is given for them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_file: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wait_timeout: Option<u64>,
+
+    /// Analyze a symbol in a file outside the project root. OPTIONAL (default: false).
+    ///
+    /// USE CASES: A dependency header, a sibling checkout, a file of a system library.
+    ///
+    /// REQUIRES: location_hint with an absolute path to an existing file.
+    ///
+    /// BEHAVIOR: Definitions, declarations and references outside the project root are
+    /// kept even where result exclusions (e.g. "**/third_party/**") would leave them out.
+    /// Exclusions still apply inside the project, and searches keep their project scoping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_outside_root: Option<bool>,
+
+    /// Include the source text of the symbol's definition. OPTIONAL (default: false).
+    ///
+    /// BEHAVIOR: The full extent of the definition (a function body, a class with its
+    /// members) is read from disk, up to 200 lines. Longer definitions are cut off and
+    /// flagged truncated: true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_definition_source: Option<bool>,
+}
+
+/// Outcome of one optional part of the analysis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureStatus {
+    /// The feature ran and produced results
+    RequestedAndFound,
+    /// The feature ran but clangd had nothing to report
+    RequestedButEmpty,
+    /// The feature ran and failed; see the error
+    RequestedButFailed,
+    /// The feature does not apply to the symbol kind
+    #[default]
+    NotRequested,
+}
+
+/// Status of an analysis feature with the error that made it fail
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureReport {
+    pub status: FeatureStatus,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+impl FeatureReport {
+    /// The feature does not apply to the symbol
+    pub fn not_requested() -> Self {
+        Self::default()
+    }
+
+    /// The feature ran, producing `empty` results or not
+    pub fn ran(empty: bool) -> Self {
+        Self {
+            status: if empty {
+                FeatureStatus::RequestedButEmpty
+            } else {
+                FeatureStatus::RequestedAndFound
+            },
+            error: None,
+        }
+    }
+
+    /// The feature failed with `error`
+    pub fn failed(error: impl std::fmt::Display) -> Self {
+        Self {
+            status: FeatureStatus::RequestedButFailed,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Status of each optional part of the analysis
+///
+/// Tells apart a part that does not apply to the symbol kind, one clangd had
+/// nothing for, and one that failed, which all leave its result field absent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeaturesUsed {
+    pub hover_documentation: FeatureReport,
+    pub examples: FeatureReport,
+    pub type_hierarchy: FeatureReport,
+    pub call_hierarchy: FeatureReport,
+    pub members: FeatureReport,
+    pub enumerators: FeatureReport,
+    pub definition_source: FeatureReport,
+}
+
+/// Source text of a symbol's definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DefinitionSource {
+    /// Extent of the definition the source was read from
+    pub location: FileLocation,
+    pub source: String,
+    /// Whether the definition is longer than the lines included
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -235,6 +446,10 @@ pub struct AnalyzerResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hover_documentation: Option<String>,
 
+    /// Documentation comment from the hover, with Doxygen/Javadoc tags split out
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub documentation: Option<Documentation>,
+
     /// Detail information from DocumentSymbol (signature, type info, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
@@ -242,6 +457,10 @@ pub struct AnalyzerResult {
     /// Usage examples showing how the symbol is used throughout the codebase
     pub examples: Vec<FileLocation>,
 
+    /// All references grouped by file, most referencing files first
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub references_by_file: Vec<FileReferences>,
+
     /// Type hierarchy information for classes, structs, and interfaces
     #[serde(skip_serializing_if = "Option::is_none")]
     pub type_hierarchy: Option<TypeHierarchy>,
@@ -254,9 +473,35 @@ pub struct AnalyzerResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub members: Option<Members>,
 
+    /// Enumerators in declaration order for enums
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub enumerators: Option<Vec<Enumerator>>,
+
+    /// Source text of the definition, when include_definition_source was set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub definition_source: Option<DefinitionSource>,
+
+    /// Status of hover, examples, hierarchies, members, enumerators and definition source
+    #[serde(default)]
+    pub features_used: FeaturesUsed,
+
+    /// Use site of the requested template instantiation, when the symbol carries
+    /// template arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instantiation: Option<TemplateInstantiation>,
+
+    /// Translation unit the analysis was done in, when context_file was given
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub context_file: Option<String>,
+
     /// Index status information when timeout occurred or no indexing wait
     #[serde(skip_serializing_if = "Option::is_none")]
     pub index_status: Option<IndexStatusView>,
+
+    /// Whether clangd was still indexing: the analysis is best effort, references,
+    /// hierarchies and definitions in files not indexed yet may be missing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indexing_incomplete: bool,
 }
 
 impl AnalyzeSymbolContextTool {
@@ -268,33 +513,256 @@ impl AnalyzeSymbolContextTool {
         )
     }
 
+    /// Build the class member filter from the tool parameters
+    fn member_filter(&self) -> Result<MemberFilter, CallToolError> {
+        let validate = |values: &Option<Vec<String>>, allowed: &[&str], what: &str| {
+            for value in values.iter().flatten() {
+                if !allowed.contains(&value.as_str()) {
+                    return Err(CallToolError::new(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!(
+                            "Invalid {}: '{}' (expected one of: {})",
+                            what,
+                            value,
+                            allowed.join(", ")
+                        ),
+                    )));
+                }
+            }
+            Ok(())
+        };
+        validate(&self.member_access, ACCESS_LEVELS, "member access")?;
+        validate(&self.member_kinds, MEMBER_KINDS, "member kind")?;
+
+        Ok(MemberFilter {
+            access: self.member_access.clone(),
+            kinds: self.member_kinds.clone(),
+            exclude_special: self.exclude_special_members.unwrap_or(false),
+        })
+    }
+
+    /// Check the location hint naming the file analyzed with allow_outside_root
+    ///
+    /// The hint has to be explicit: an absolute path to an existing file, since
+    /// relative paths are resolved against the project root.
+    fn check_outside_root_hint(&self) -> Result<(), CallToolError> {
+        let invalid = |message: String| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        };
+        let Some(hint) = &self.location_hint else {
+            return Err(invalid(
+                "allow_outside_root requires a location_hint with the absolute path of the file"
+                    .to_string(),
+            ));
+        };
+        let file = utils::location_hint_path(hint)
+            .ok_or_else(|| invalid(format!("Invalid location format '{}'", hint)))?;
+        if file.is_relative() {
+            return Err(invalid(format!(
+                "allow_outside_root requires an absolute location_hint path, got '{}'",
+                file.display()
+            )));
+        }
+        if !file.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file.display()),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Make clangd parse `file` in the translation unit given as context_file, if any
+    ///
+    /// Returns the resolved context file.
+    async fn apply_context_file(
+        &self,
+        file: &Path,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<Option<std::path::PathBuf>, CallToolError> {
+        let Some(context_file) = &self.context_file else {
+            return Ok(None);
+        };
+        let context_path = utils::resolve_project_path(workspace, context_file);
+        let timeout = std::time::Duration::from_secs(
+            self.wait_timeout
+                .unwrap_or(crate::clangd::config::DEFAULT_INDEX_WAIT_TIMEOUT_SECS),
+        );
+
+        component_session
+            .set_translation_unit_context(file, &context_path, timeout)
+            .await
+            .map_err(|e| {
+                let kind = match e {
+                    ProjectError::PathNotFound { .. } => std::io::ErrorKind::NotFound,
+                    _ => std::io::ErrorKind::InvalidInput,
+                };
+                CallToolError::new(std::io::Error::new(
+                    kind,
+                    format!("Cannot use context_file '{}': {}", context_file, e),
+                ))
+            })?;
+        Ok(Some(context_path))
+    }
+
     /// Extract members from structural types if applicable
     fn extract_members_if_structural(
         symbol: &Symbol,
         matched_document_symbol: &Option<lsp_types::DocumentSymbol>,
         query_name: &str,
-    ) -> Option<Members> {
+        position_encoding: PositionEncoding,
+        filter: &MemberFilter,
+        max_file_size: u64,
+    ) -> (Option<Members>, FeatureReport) {
         if Self::is_structural_type(symbol.kind) {
             if let Some(matched_ds) = matched_document_symbol {
-                let members = get_members_from_document_symbol(matched_ds, &symbol.name);
+                // Source text lets access levels be inferred from access specifiers
+                let source = read_context_source(&symbol.location.file_path, max_file_size)
+                    .inspect_err(|e| {
+                        warn!(
+                            "Cannot read {} for member access levels: {}",
+                            symbol.location.file_path.display(),
+                            e
+                        )
+                    })
+                    .ok();
+                let members = get_members_from_document_symbol(
+                    matched_ds,
+                    &symbol.name,
+                    source.as_deref(),
+                    position_encoding,
+                    filter,
+                );
                 info!(
-                    "Found members for '{}': {} methods, {} constructors, {} destructors, {} operators",
+                    "Found members for '{}': {} methods, {} fields, {} constructors, {} destructors, {} operators, {} types",
                     query_name,
                     members.methods.len(),
+                    members.fields.len(),
                     members.constructors.len(),
                     members.destructors.len(),
-                    members.operators.len()
+                    members.operators.len(),
+                    members.types.len()
                 );
-                Some(members)
+                let empty = members.methods.is_empty()
+                    && members.fields.is_empty()
+                    && members.constructors.is_empty()
+                    && members.destructors.is_empty()
+                    && members.operators.is_empty()
+                    && members.types.is_empty();
+                (Some(members), FeatureReport::ran(empty))
             } else {
                 warn!("No matched document symbol available for member extraction");
-                None
+                (
+                    None,
+                    FeatureReport::failed("No document symbol available for the class"),
+                )
             }
         } else {
-            None
+            (None, FeatureReport::not_requested())
         }
     }
 
+    /// Extract enumerators with their values if the symbol is an enum
+    fn extract_enumerators_if_enum(
+        symbol: &Symbol,
+        document_symbol: &lsp_types::DocumentSymbol,
+        position_encoding: PositionEncoding,
+        max_file_size: u64,
+    ) -> (Option<Vec<Enumerator>>, FeatureReport) {
+        if symbol.kind != lsp_types::SymbolKind::ENUM {
+            return (None, FeatureReport::not_requested());
+        }
+
+        // Source text provides initializers when clangd reports no values
+        let source = read_context_source(&symbol.location.file_path, max_file_size)
+            .inspect_err(|e| {
+                warn!(
+                    "Cannot read {} for enumerator values: {}",
+                    symbol.location.file_path.display(),
+                    e
+                )
+            })
+            .ok();
+        let enumerators = get_enumerators(document_symbol, source.as_deref(), position_encoding);
+        info!(
+            "Found {} enumerators for '{}'",
+            enumerators.len(),
+            symbol.name
+        );
+        let report = FeatureReport::ran(enumerators.is_empty());
+        (Some(enumerators), report)
+    }
+
+    /// Read the source of the definition at `location`, capped at MAX_DEFINITION_SOURCE_LINES
+    ///
+    /// The extent of the definition is the range of the innermost document symbol
+    /// whose name is at `location`. Files over `max_file_size` bytes are not read.
+    async fn get_definition_source(
+        location: &FileLocation,
+        component_session: &ComponentSession,
+        max_file_size: u64,
+    ) -> (Option<DefinitionSource>, FeatureReport) {
+        let document_symbols =
+            match get_document_symbols(component_session, location.get_uri()).await {
+                Ok(document_symbols) => document_symbols,
+                Err(e) => {
+                    warn!(
+                        "Failed to get document symbols for definition source: {}",
+                        e
+                    );
+                    return (None, FeatureReport::failed(e));
+                }
+            };
+        let position: lsp_types::Position = location.range.start.into();
+        let Some(range) = DocumentSymbolIterator::new(&document_symbols)
+            .filter(|(document_symbol, _)| {
+                document_symbol.selection_range.start <= position
+                    && position <= document_symbol.selection_range.end
+            })
+            .last()
+            .map(|(document_symbol, _)| document_symbol.range)
+        else {
+            return (
+                None,
+                FeatureReport::failed("No document symbol at the definition"),
+            );
+        };
+
+        let contents = match read_context_source_async(&location.file_path, max_file_size).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                warn!(
+                    "Cannot read {} for definition source: {}",
+                    location.file_path.display(),
+                    e
+                );
+                return (None, FeatureReport::failed(e));
+            }
+        };
+        let start = range.start.line as usize;
+        let end = range.end.line as usize;
+        let line_count = end.saturating_sub(start) + 1;
+        let lines: Vec<&str> = contents
+            .lines()
+            .skip(start)
+            .take(line_count.min(MAX_DEFINITION_SOURCE_LINES))
+            .collect();
+        let source = DefinitionSource {
+            location: FileLocation {
+                file_path: location.file_path.clone(),
+                range: range.into(),
+            },
+            source: lines.join("\n"),
+            truncated: line_count > MAX_DEFINITION_SOURCE_LINES,
+        };
+        let report = FeatureReport::ran(source.source.is_empty());
+        (Some(source), report)
+    }
+
     /// Check if a symbol represents a type that supports type hierarchies
     fn supports_type_hierarchy(symbol_kind: lsp_types::SymbolKind) -> bool {
         matches!(
@@ -309,12 +777,21 @@ impl AnalyzeSymbolContextTool {
         &self,
         component_session: &ComponentSession,
     ) -> Result<(Symbol, SymbolContext), CallToolError> {
-        let workspace_symbol = get_matching_symbol(&self.symbol, component_session)
-            .await
-            .map_err(|err| {
+        let workspace_symbol = match get_matching_symbol(&self.symbol, component_session).await {
+            Ok(symbol) => symbol,
+            Err(err @ AnalyzerError::NoSymbols { .. }) => {
+                // Symbols clangd has not indexed may still be declared in an open file
+                if let Some(found) = self.resolve_symbol_in_open_files(component_session).await {
+                    return Ok(found);
+                }
                 error!("Failed to get matching workspace symbol: {}", err);
-                CallToolError::from(err)
-            })?;
+                return Err(CallToolError::from(err));
+            }
+            Err(err) => {
+                error!("Failed to get matching workspace symbol: {}", err);
+                return Err(CallToolError::from(err));
+            }
+        };
 
         let symbol = workspace_symbol.clone();
 
@@ -353,17 +830,93 @@ impl AnalyzeSymbolContextTool {
             .await
             .map_err(CallToolError::from)?;
 
+        if document_symbols.is_empty() {
+            return Err(CallToolError::new(std::io::Error::other(format!(
+                "clangd reported no symbols in '{}' - check that the file is covered by the compilation database",
+                location.file_path.display()
+            ))));
+        }
+
         let position: lsp_types::Position = location.range.start.into();
 
-        let (doc_symbol, container_path) =
-            find_symbol_at_position_with_path(&document_symbols, &position).ok_or_else(|| {
+        let Some((doc_symbol, container_path)) =
+            find_symbol_at_position_with_path(&document_symbols, &position)
+        else {
+            // Locals, parameters and macros are not document symbols
+            return self
+                .resolve_symbol_via_definition(location, component_session)
+                .await?
+                .ok_or_else(|| {
+                    CallToolError::new(std::io::Error::other(format!(
+                        "No symbol found at location {}",
+                        location.to_compact_range()
+                    )))
+                });
+        };
+
+        // A position inside a function body hits the function; prefer the symbol
+        // the position refers to when the enclosing one is named differently
+        if !symbol_name_matches(&doc_symbol.name, &self.symbol)
+            && let Ok(Some(resolved)) = self
+                .resolve_symbol_via_definition(location, component_session)
+                .await
+        {
+            return Ok(resolved);
+        }
+
+        let mut symbol = Symbol::from((doc_symbol, location.file_path.as_path()));
+        symbol.container_name = container_path.last().cloned();
+
+        let context = SymbolContext {
+            document_symbol: doc_symbol.clone(),
+            container_path,
+        };
+
+        Ok((symbol, context))
+    }
+
+    /// Resolve the symbol declared or defined on a line, using `self.symbol` as name hint
+    async fn resolve_symbol_context_on_line(
+        &self,
+        file_line: &FileLine,
+        component_session: &ComponentSession,
+    ) -> Result<(Symbol, SymbolContext), CallToolError> {
+        let file_uri = crate::symbol::uri_from_pathbuf(&file_line.file_path);
+
+        let document_symbols = get_document_symbols(component_session, file_uri)
+            .await
+            .map_err(CallToolError::from)?;
+
+        let Some((doc_symbol, container_path)) =
+            find_symbol_on_line(&document_symbols, file_line.line_number, Some(&self.symbol))
+        else {
+            // The line may use the symbol or declare one clangd does not list
+            let mut resolved = None;
+            if let Some(location) = self
+                .name_location_on_line(file_line, component_session)
+                .await
+            {
+                resolved = self
+                    .resolve_symbol_via_definition(&location, component_session)
+                    .await?;
+            }
+            return resolved.ok_or_else(|| {
                 CallToolError::new(std::io::Error::other(format!(
-                    "No symbol found at location {}",
-                    location.to_compact_range()
+                    "No symbol is declared on line {} of '{}'",
+                    file_line.line_number + 1,
+                    file_line.file_path.display()
                 )))
-            })?;
+            });
+        };
 
-        let mut symbol = Symbol::from((doc_symbol, location.file_path.as_path()));
+        info!(
+            "Resolved '{}' on line {} to '{}'",
+            self.symbol,
+            file_line.line_number + 1,
+            doc_symbol.name
+        );
+
+        let mut symbol = Symbol::from((doc_symbol, file_line.file_path.as_path()));
         symbol.container_name = container_path.last().cloned();
 
         let context = SymbolContext {
@@ -374,20 +927,169 @@ impl AnalyzeSymbolContextTool {
         Ok((symbol, context))
     }
 
+    /// Find the symbol among the document symbols of the files open in clangd
+    async fn resolve_symbol_in_open_files(
+        &self,
+        component_session: &ComponentSession,
+    ) -> Option<(Symbol, SymbolContext)> {
+        let mut open_files = component_session.open_files().await;
+        open_files.sort();
+        for file_path in open_files {
+            let file_uri = crate::symbol::uri_from_pathbuf(&file_path);
+            let Ok(document_symbols) = get_document_symbols(component_session, file_uri).await
+            else {
+                continue;
+            };
+            if let Some((doc_symbol, container_path)) =
+                find_symbol_named(&document_symbols, &self.symbol)
+            {
+                info!(
+                    "Found '{}' in open file '{}'",
+                    self.symbol,
+                    file_path.display()
+                );
+                let mut symbol = Symbol::from((doc_symbol, file_path.as_path()));
+                symbol.container_name = container_path.last().cloned();
+                let context = SymbolContext {
+                    document_symbol: doc_symbol.clone(),
+                    container_path,
+                };
+                return Some((symbol, context));
+            }
+        }
+        None
+    }
+
+    /// Location of the queried name on a line, matched as a whole identifier
+    async fn name_location_on_line(
+        &self,
+        file_line: &FileLine,
+        component_session: &ComponentSession,
+    ) -> Option<FileLocation> {
+        let name = self.symbol.rsplit("::").next()?.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let content = read_source_file_async(&file_line.file_path).await.ok()?;
+        let line = content.lines().nth(file_line.line_number as usize)?;
+
+        let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+        let offset = line
+            .match_indices(name)
+            .map(|(offset, _)| offset)
+            .find(|&offset| {
+                !line[..offset].ends_with(is_identifier)
+                    && !line[offset + name.len()..].starts_with(is_identifier)
+            })?;
+
+        let encoding = component_session.position_encoding().await;
+        let start = encoding.column(line, offset);
+        let end = encoding.column(line, offset + name.len());
+        Some(FileLocation {
+            file_path: file_line.file_path.clone(),
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(file_line.line_number, start),
+                lsp_types::Position::new(file_line.line_number, end),
+            )
+            .into(),
+        })
+    }
+
+    /// Locate the symbol by following `textDocument/definition` from a position
+    ///
+    /// Used when no document symbol is declared at the position, as for local
+    /// variables, parameters, macros and plain usages. The document symbol named
+    /// like the query on the definition line is preferred; symbols clangd does
+    /// not list are described by their definition location and hover heading.
+    async fn resolve_symbol_via_definition(
+        &self,
+        position: &FileLocation,
+        component_session: &ComponentSession,
+    ) -> Result<Option<(Symbol, SymbolContext)>, CallToolError> {
+        let mut targets = get_definitions(position, component_session)
+            .await
+            .map_err(CallToolError::from)?;
+        if targets.is_empty() {
+            targets = get_declarations(position, component_session)
+                .await
+                .map_err(CallToolError::from)?;
+        }
+        let Some(target) = targets.into_iter().next() else {
+            return Ok(None);
+        };
+        info!(
+            "Resolved '{}' at {} via its definition at {}",
+            self.symbol,
+            position.to_compact_range(),
+            target.to_compact_range()
+        );
+
+        let document_symbols = get_document_symbols(component_session, target.get_uri())
+            .await
+            .unwrap_or_default();
+        let (document_symbol, container_path) = match find_symbol_on_line(
+            &document_symbols,
+            target.range.start.line,
+            Some(&self.symbol),
+        )
+        .filter(|(doc_symbol, _)| symbol_name_matches(&doc_symbol.name, &self.symbol))
+        {
+            Some((doc_symbol, container_path)) => (doc_symbol.clone(), container_path),
+            None => {
+                let kind = get_hover_info(&target, component_session)
+                    .await
+                    .ok()
+                    .and_then(|hover| hover_symbol_kind(&hover))
+                    .unwrap_or(lsp_types::SymbolKind::VARIABLE);
+                let range: lsp_types::Range = target.range.into();
+                #[allow(deprecated)]
+                let document_symbol = lsp_types::DocumentSymbol {
+                    name: self
+                        .symbol
+                        .rsplit("::")
+                        .next()
+                        .unwrap_or(&self.symbol)
+                        .to_string(),
+                    detail: None,
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    range,
+                    selection_range: range,
+                    children: None,
+                };
+                (document_symbol, Vec::new())
+            }
+        };
+
+        let mut symbol = Symbol::from((&document_symbol, target.file_path.as_path()));
+        symbol.container_name = container_path.last().cloned();
+        Ok(Some((
+            symbol,
+            SymbolContext {
+                document_symbol,
+                container_path,
+            },
+        )))
+    }
+
     /// Retrieves definitions and declarations for the symbol
     async fn get_definitions_and_declarations(
         &self,
         symbol_location: &crate::symbol::FileLocation,
         component_session: &ComponentSession,
+        exclusions: &ResultExclusions,
     ) -> Result<(Vec<FileLocation>, Vec<FileLocation>), CallToolError> {
-        let definitions = get_definitions(symbol_location, component_session).await?;
+        let mut definitions = get_definitions(symbol_location, component_session).await?;
+        definitions.retain(|location| !exclusions.is_excluded(&location.file_path));
         info!(
             "Found {} definitions for '{}'",
             definitions.len(),
             self.symbol
         );
 
-        let declarations = get_declarations(symbol_location, component_session).await?;
+        let mut declarations = get_declarations(symbol_location, component_session).await?;
+        declarations.retain(|location| !exclusions.is_excluded(&location.file_path));
         info!(
             "Found {} declarations for '{}'",
             declarations.len(),
@@ -402,30 +1104,43 @@ impl AnalyzeSymbolContextTool {
         &self,
         symbol_location: &crate::symbol::FileLocation,
         component_session: &ComponentSession,
-    ) -> Option<String> {
+    ) -> (Option<String>, FeatureReport) {
         match get_hover_info(symbol_location, component_session).await {
-            Ok(info) => Some(info),
+            Ok(info) => {
+                let report = FeatureReport::ran(info.trim().is_empty());
+                (Some(info), report)
+            }
             Err(err) => {
                 warn!("Failed to get hover information: {}", err);
-                None
+                (None, FeatureReport::failed(err))
             }
         }
     }
 
-    /// Retrieves usage examples for the symbol
+    /// Retrieves usage examples for the symbol together with all references grouped by file
     async fn get_usage_examples(
         &self,
         symbol_location: &crate::symbol::FileLocation,
         component_session: &ComponentSession,
-    ) -> Vec<FileLocation> {
-        match get_examples(component_session, symbol_location, self.max_examples).await {
-            Ok(examples) => {
-                info!("Found {} examples for '{}'", examples.len(), self.symbol);
-                examples
+        exclusions: &ResultExclusions,
+    ) -> (Vec<FileLocation>, Vec<FileReferences>, FeatureReport) {
+        match get_references(component_session, symbol_location).await {
+            Ok(mut references) => {
+                references.retain(|location| !exclusions.is_excluded(&location.file_path));
+                let references_by_file = group_references_by_file(&references);
+                let examples = limit_examples(references, self.max_examples);
+                info!(
+                    "Found {} examples for '{}' across {} files",
+                    examples.len(),
+                    self.symbol,
+                    references_by_file.len()
+                );
+                let report = FeatureReport::ran(references_by_file.is_empty());
+                (examples, references_by_file, report)
             }
             Err(err) => {
                 warn!("Failed to get usage examples: {}", err);
-                Vec::new()
+                (Vec::new(), Vec::new(), FeatureReport::failed(err))
             }
         }
     }
@@ -436,7 +1151,10 @@ impl AnalyzeSymbolContextTool {
         symbol: &Symbol,
         symbol_location: &crate::symbol::FileLocation,
         component_session: &ComponentSession,
-    ) -> (Option<TypeHierarchy>, Option<CallHierarchy>) {
+    ) -> (
+        (Option<TypeHierarchy>, FeatureReport),
+        (Option<CallHierarchy>, FeatureReport),
+    ) {
         let type_hierarchy = if Self::supports_type_hierarchy(symbol.kind) {
             match get_type_hierarchy(symbol_location, component_session).await {
                 Ok(hierarchy) => {
@@ -446,15 +1164,16 @@ impl AnalyzeSymbolContextTool {
                         hierarchy.supertypes.len(),
                         hierarchy.subtypes.len()
                     );
-                    Some(hierarchy)
+                    let empty = hierarchy.supertypes.is_empty() && hierarchy.subtypes.is_empty();
+                    (Some(hierarchy), FeatureReport::ran(empty))
                 }
                 Err(err) => {
                     warn!("Failed to get type hierarchy: {}", err);
-                    None
+                    (None, FeatureReport::failed(err))
                 }
             }
         } else {
-            None
+            (None, FeatureReport::not_requested())
         };
 
         let call_hierarchy = match symbol.kind {
@@ -469,15 +1188,16 @@ impl AnalyzeSymbolContextTool {
                             hierarchy.callers.len(),
                             hierarchy.callees.len()
                         );
-                        Some(hierarchy)
+                        let empty = hierarchy.callers.is_empty() && hierarchy.callees.is_empty();
+                        (Some(hierarchy), FeatureReport::ran(empty))
                     }
                     Err(err) => {
                         warn!("Failed to get call hierarchy: {}", err);
-                        None
+                        (None, FeatureReport::failed(err))
                     }
                 }
             }
-            _ => None,
+            _ => (None, FeatureReport::not_requested()),
         };
 
         (type_hierarchy, call_hierarchy)
@@ -486,22 +1206,28 @@ impl AnalyzeSymbolContextTool {
     /// V2 entry point - uses shared ClangdSession from server
     #[instrument(
         name = "analyze_symbol_context",
-        skip(self, component_session, _workspace)
+        skip(self, component_session, workspace)
     )]
     pub async fn call_tool(
         &self,
         component_session: Arc<ComponentSession>,
-        _workspace: &ProjectWorkspace,
+        workspace: &ProjectWorkspace,
     ) -> Result<CallToolResult, CallToolError> {
         info!(
             "Starting symbol analysis for '{}', location_hint={:?}, wait_timeout={:?}",
             self.symbol, self.location_hint, self.wait_timeout
         );
 
-        // Selective indexing wait logic based on location_hint
+        // Selective indexing wait logic based on location_hint: document-specific
+        // analysis only waits for the hinted file to be parsed
+        let document_files: Option<Vec<_>> = self.location_hint.as_ref().map(|hint| {
+            utils::location_hint_file(workspace, hint)
+                .into_iter()
+                .collect()
+        });
         let index_status = utils::handle_selective_indexing_wait(
             &component_session,
-            self.location_hint.is_some(), // Skip indexing for document-specific analysis (location hint provided)
+            document_files.as_deref(),
             self.wait_timeout,
             if self.location_hint.is_some() {
                 "Document-specific analysis"
@@ -511,30 +1237,174 @@ impl AnalyzeSymbolContextTool {
         )
         .await;
 
-        // Note: LSP session access is now handled by individual helper functions
+        let exclusions = if self.allow_outside_root.unwrap_or(false) {
+            self.check_outside_root_hint()?;
+            workspace.result_exclusions.clone().inside_project_only()
+        } else {
+            workspace.result_exclusions.clone()
+        };
+
+        let result = self
+            .analyze(&component_session, workspace, &exclusions, index_status)
+            .await?;
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+
+    /// Analyze the symbol once indexing has been waited for
+    ///
+    /// `index_status` is reported as-is in the result. Definitions, declarations
+    /// and references in files matching `exclusions` are left out. A symbol with
+    /// template arguments ("std::vector<int>") is analyzed as its template, with
+    /// the instantiation resolved at a use site.
+    pub(crate) async fn analyze(
+        &self,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+        exclusions: &ResultExclusions,
+        index_status: Option<IndexStatusView>,
+    ) -> Result<AnalyzerResult, CallToolError> {
+        let Some((template_name, arguments)) = split_template_arguments(&self.symbol) else {
+            return self
+                .analyze_symbol(component_session, workspace, exclusions, index_status)
+                .await;
+        };
+
+        info!(
+            "Analyzing template '{}' for instantiation '{}'",
+            template_name, self.symbol
+        );
+        let template_tool = Self {
+            symbol: template_name.to_string(),
+            ..self.clone()
+        };
+        let mut result = template_tool
+            .analyze_symbol(component_session, workspace, exclusions, index_status)
+            .await?;
+
+        let references = get_references(component_session, &result.symbol.location)
+            .await
+            .unwrap_or_default();
+        let template_locations: Vec<FileLocation> = std::iter::once(result.symbol.location.clone())
+            .chain(result.definitions.iter().cloned())
+            .chain(result.declarations.iter().cloned())
+            .collect();
+        result.instantiation = Some(
+            find_instantiation(
+                component_session,
+                &template_locations,
+                &references,
+                &self.symbol,
+                arguments,
+                workspace.max_context_file_size,
+            )
+            .await,
+        );
+        result.query = self.symbol.clone();
+        Ok(result)
+    }
 
-        let (symbol, symbol_context) = match &self.location_hint {
+    /// Resolve `self.symbol` through the location hint, or workspace symbols without one
+    async fn resolve_symbol(
+        &self,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<(Symbol, SymbolContext), CallToolError> {
+        match &self.location_hint {
             None => {
-                self.resolve_symbol_via_workspace_with_context(&component_session)
-                    .await?
+                self.resolve_symbol_via_workspace_with_context(component_session)
+                    .await
             }
             Some(location_str) => {
-                let location: FileLocation = location_str.parse().map_err(|e| {
-                    CallToolError::new(std::io::Error::other(format!(
-                        "Invalid location format '{}': {}",
-                        location_str, e
-                    )))
-                })?;
-                self.resolve_symbol_context_at_location(&location, &component_session)
-                    .await?
+                // Fall back to the line-only form of grep results
+                let hinted = match utils::parse_location_hint(workspace, location_str) {
+                    Ok(location) => Ok(location),
+                    Err(err) => {
+                        let mut file_line: FileLine = location_str.parse().map_err(|_| err)?;
+                        file_line.file_path =
+                            utils::resolve_project_path(workspace, &file_line.file_path);
+                        Err(file_line)
+                    }
+                };
+                match hinted {
+                    Ok(location) => {
+                        self.resolve_symbol_context_at_location(&location, component_session)
+                            .await
+                    }
+                    Err(file_line) => {
+                        self.resolve_symbol_context_on_line(&file_line, component_session)
+                            .await
+                    }
+                }
             }
+        }
+    }
+
+    /// Error for a symbol not resolved while clangd is still indexing, telling to retry
+    fn unresolved_during_indexing(err: CallToolError, status: &IndexStatusView) -> CallToolError {
+        CallToolError::new(std::io::Error::other(format!(
+            "{} (clangd is still indexing, {}/{} files indexed: retry later, or give a \
+             location_hint to analyze the symbol in its file)",
+            err, status.indexed_files, status.total_files
+        )))
+    }
+
+    /// Analyze the symbol named by `self.symbol`
+    async fn analyze_symbol(
+        &self,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+        exclusions: &ResultExclusions,
+        index_status: Option<IndexStatusView>,
+    ) -> Result<AnalyzerResult, CallToolError> {
+        let member_filter = self.member_filter()?;
+
+        // The hinted file has to be parsed in the context before resolving the symbol in it
+        if let Some(hint_file) = self
+            .location_hint
+            .as_deref()
+            .and_then(|hint| utils::location_hint_file(workspace, hint))
+        {
+            self.apply_context_file(&hint_file, component_session, workspace)
+                .await?;
+        }
+
+        let indexing_incomplete = index_status
+            .as_ref()
+            .is_some_and(|status| !status.is_finished());
+        let (symbol, symbol_context) = match self.resolve_symbol(component_session, workspace).await
+        {
+            Ok(resolved) => resolved,
+            Err(err) => match index_status.as_ref().filter(|_| indexing_incomplete) {
+                Some(status) => return Err(Self::unresolved_during_indexing(err, status)),
+                None => return Err(err),
+            },
         };
 
-        // Get definitions and declarations
-        let (definitions, mut declarations) = self
-            .get_definitions_and_declarations(&symbol.location, &component_session)
+        let context_file = self
+            .apply_context_file(&symbol.location.file_path, component_session, workspace)
             .await?;
 
+        // Get definitions and declarations; while clangd is still indexing they
+        // are best effort like the rest of the analysis
+        let (definitions, mut declarations) = match self
+            .get_definitions_and_declarations(&symbol.location, component_session, exclusions)
+            .await
+        {
+            Ok(found) => found,
+            Err(err) if indexing_incomplete => {
+                warn!(
+                    "Failed to get definitions of '{}' during indexing: {}",
+                    self.symbol, err
+                );
+                (Vec::new(), Vec::new())
+            }
+            Err(err) => return Err(err),
+        };
+
         // Deduplicate: if definitions == declarations, clear declarations
         if definitions == declarations {
             info!("Definitions and declarations are identical, clearing declarations");
@@ -542,19 +1412,20 @@ impl AnalyzeSymbolContextTool {
         }
 
         // Get hover information
-        let hover = self
-            .get_hover_documentation(&symbol.location, &component_session)
+        let (hover, hover_report) = self
+            .get_hover_documentation(&symbol.location, component_session)
             .await;
+        let documentation = hover.as_deref().and_then(parse_hover_documentation);
 
-        // Get usage examples
-        let examples = self
-            .get_usage_examples(&symbol.location, &component_session)
+        // Get usage examples and per-file reference counts
+        let (examples, references_by_file, examples_report) = self
+            .get_usage_examples(&symbol.location, component_session, exclusions)
             .await;
 
         // Get hierarchies based on symbol type
-        let (type_hierarchy, call_hierarchy) = self
-            .get_hierarchies(&symbol, &symbol.location, &component_session)
-            .await;
+        let ((type_hierarchy, type_hierarchy_report), (call_hierarchy, call_hierarchy_report)) =
+            self.get_hierarchies(&symbol, &symbol.location, component_session)
+                .await;
 
         let detail = symbol_context.document_symbol.detail.clone();
 
@@ -562,30 +1433,65 @@ impl AnalyzeSymbolContextTool {
             info!("Found detail for '{}': {}", self.symbol, d);
         }
 
-        let members = Self::extract_members_if_structural(
+        let position_encoding = component_session.position_encoding().await;
+        let (members, members_report) = Self::extract_members_if_structural(
             &symbol,
             &Some(symbol_context.document_symbol.clone()),
             &self.symbol,
+            position_encoding,
+            &member_filter,
+            workspace.max_context_file_size,
+        );
+        let (enumerators, enumerators_report) = Self::extract_enumerators_if_enum(
+            &symbol,
+            &symbol_context.document_symbol,
+            position_encoding,
+            workspace.max_context_file_size,
         );
 
+        let (definition_source, definition_source_report) =
+            if self.include_definition_source.unwrap_or(false) {
+                Self::get_definition_source(
+                    definitions.first().unwrap_or(&symbol.location),
+                    component_session,
+                    workspace.max_context_file_size,
+                )
+                .await
+            } else {
+                (None, FeatureReport::not_requested())
+            };
+
         let result = AnalyzerResult {
             symbol,
             query: self.symbol.clone(),
             hover_documentation: hover,
+            documentation,
             detail,
             definitions,
             declarations,
             examples,
+            references_by_file,
             type_hierarchy,
             call_hierarchy,
             members,
+            enumerators,
+            definition_source,
+            features_used: FeaturesUsed {
+                hover_documentation: hover_report,
+                examples: examples_report,
+                type_hierarchy: type_hierarchy_report,
+                call_hierarchy: call_hierarchy_report,
+                members: members_report,
+                enumerators: enumerators_report,
+                definition_source: definition_source_report,
+            },
+            instantiation: None,
+            context_file: context_file.map(|path| path.display().to_string()),
             index_status,
+            indexing_incomplete,
         };
 
-        let output = serde_json::to_string_pretty(&result).map_err(AnalyzerError::from)?;
-        Ok(CallToolResult::text_content(vec![TextContent::from(
-            output,
-        )]))
+        Ok(result)
     }
 }
 
@@ -617,9 +1523,16 @@ mod tests {
         let tool = AnalyzeSymbolContextTool {
             symbol: "Math".to_string(),
             build_directory: None,
+            session_id: None,
             max_examples: None,
             location_hint: None,
             wait_timeout: None,
+            member_access: None,
+            member_kinds: None,
+            exclude_special_members: None,
+            context_file: None,
+            allow_outside_root: None,
+            include_definition_source: None,
         };
 
         let component_session = workspace_session
@@ -725,9 +1638,16 @@ mod tests {
         let tool = AnalyzeSymbolContextTool {
             symbol: "Math".to_string(),
             build_directory: None,
+            session_id: None,
             max_examples: Some(2),
             location_hint: None,
             wait_timeout: None,
+            member_access: None,
+            member_kinds: None,
+            exclude_special_members: None,
+            context_file: None,
+            allow_outside_root: None,
+            include_definition_source: None,
         };
 
         let component_session = workspace_session
@@ -761,4 +1681,630 @@ mod tests {
             analyzer_result.examples.len()
         );
     }
+
+    #[test]
+    fn test_member_filter_validation() {
+        use super::*;
+
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "Math",
+            "member_access": ["public"],
+            "member_kinds": ["method", "field"],
+            "exclude_special_members": true
+        }))
+        .unwrap();
+        let filter = tool.member_filter().unwrap();
+        assert_eq!(filter.access, Some(vec!["public".to_string()]));
+        assert_eq!(
+            filter.kinds,
+            Some(vec!["method".to_string(), "field".to_string()])
+        );
+        assert!(filter.exclude_special);
+
+        let tool = AnalyzeSymbolContextTool {
+            member_kinds: Some(vec!["methods".to_string()]),
+            ..tool
+        };
+        assert!(tool.member_filter().is_err());
+
+        let tool = AnalyzeSymbolContextTool {
+            member_kinds: None,
+            member_access: Some(vec!["internal".to_string()]),
+            ..tool
+        };
+        assert!(tool.member_filter().is_err());
+    }
+
+    #[test]
+    fn test_outside_root_hint_validation() {
+        use super::*;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let header = temp_dir.path().join("dep.h");
+        std::fs::write(&header, "int dep();\n").unwrap();
+        let tool = |location_hint: Option<String>| AnalyzeSymbolContextTool {
+            location_hint,
+            allow_outside_root: Some(true),
+            ..serde_json::from_value(serde_json::json!({"symbol": "dep"})).unwrap()
+        };
+
+        assert!(
+            tool(Some(format!("{}:1:5", header.display())))
+                .check_outside_root_hint()
+                .is_ok()
+        );
+        assert!(
+            tool(Some(format!("{}:1", header.display())))
+                .check_outside_root_hint()
+                .is_ok()
+        );
+        assert!(tool(None).check_outside_root_hint().is_err());
+        assert!(
+            tool(Some("include/dep.h:1:5".to_string()))
+                .check_outside_root_hint()
+                .is_err()
+        );
+        assert!(
+            tool(Some(format!(
+                "{}:1:5",
+                temp_dir.path().join("gone.h").display()
+            )))
+            .check_outside_root_hint()
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_symbol_from_line_hint() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/bar.cpp",
+            "#include \"bar.h\"\n\nvoid Bar::baz() {\n}\n",
+        )]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Bar::baz",
+                "detail": "void ()",
+                "kind": 6,
+                "range": {"start": {"line": 2, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 2, "character": 10}, "end": {"line": 2, "character": 13}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "baz",
+            "location_hint": "src/bar.cpp:3",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool
+            .call_tool(component_session.clone(), &workspace)
+            .await
+            .unwrap();
+        let rust_mcp_sdk::schema::ContentBlock::TextContent(TextContent { text, .. }) =
+            &result.content[0]
+        else {
+            panic!("Expected TextContent in call_result");
+        };
+        let analyzer_result: AnalyzerResult = serde_json::from_str(text).unwrap();
+        assert_eq!(analyzer_result.symbol.name, "Bar::baz");
+        assert_eq!(analyzer_result.symbol.location.range.start.line, 2);
+        assert_eq!(analyzer_result.detail.as_deref(), Some("void ()"));
+
+        // Project-relative "file:line:column" hints are looked up in the project
+        let position_tool = AnalyzeSymbolContextTool {
+            location_hint: Some("src/bar.cpp:3:11".to_string()),
+            ..tool.clone()
+        };
+        let result = position_tool
+            .call_tool(component_session.clone(), &workspace)
+            .await
+            .unwrap();
+        let analyzer_result: AnalyzerResult = tool_result(&result);
+        assert_eq!(analyzer_result.symbol.name, "Bar::baz");
+        assert_eq!(
+            analyzer_result.symbol.location.file_path,
+            workspace.project_root_path.join("src/bar.cpp")
+        );
+
+        // Lines without a declaration are reported as errors
+        let tool = AnalyzeSymbolContextTool {
+            location_hint: Some("src/bar.cpp:2".to_string()),
+            ..tool
+        };
+        assert!(tool.call_tool(component_session, &workspace).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_includes_definition_source() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let body = "    step();\n".repeat(250);
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/bar.cpp",
+            &format!(
+                "#include \"bar.h\"\n\nvoid Bar::baz() {{\n}}\n\nvoid Bar::run() {{\n{body}}}\n"
+            ),
+        )]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Bar::baz",
+                "kind": 6,
+                "range": {"start": {"line": 2, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 2, "character": 10}, "end": {"line": 2, "character": 13}}
+            }, {
+                "name": "Bar::run",
+                "kind": 6,
+                "range": {"start": {"line": 5, "character": 0}, "end": {"line": 256, "character": 1}},
+                "selectionRange": {"start": {"line": 5, "character": 10}, "end": {"line": 5, "character": 13}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let analyze = |symbol: &str, location_hint: &str, include: Option<bool>| {
+            let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "location_hint": location_hint,
+                "wait_timeout": 0,
+                "include_definition_source": include
+            }))
+            .unwrap();
+            let component_session = component_session.clone();
+            let workspace = workspace.clone();
+            async move {
+                tool.analyze(
+                    &component_session,
+                    &workspace,
+                    &workspace.result_exclusions,
+                    None,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let result = analyze("baz", "src/bar.cpp:3", Some(true)).await;
+        let source = result.definition_source.unwrap();
+        assert_eq!(source.source, "void Bar::baz() {\n}");
+        assert_eq!(source.location.range.end.line, 3);
+        assert!(!source.truncated);
+        assert_eq!(
+            result.features_used.definition_source.status,
+            FeatureStatus::RequestedAndFound
+        );
+
+        // Long definitions are cut off at the line cap
+        let result = analyze("run", "src/bar.cpp:6", Some(true)).await;
+        let source = result.definition_source.unwrap();
+        assert!(source.truncated);
+        assert_eq!(source.source.lines().count(), MAX_DEFINITION_SOURCE_LINES);
+        assert!(source.source.starts_with("void Bar::run() {\n    step();"));
+
+        // Files over the context size limit are not read
+        let mut limited = workspace.clone();
+        limited.max_context_file_size = 64;
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "baz",
+            "location_hint": "src/bar.cpp:3",
+            "wait_timeout": 0,
+            "include_definition_source": true
+        }))
+        .unwrap();
+        let result = tool
+            .analyze(
+                &component_session,
+                &limited,
+                &limited.result_exclusions,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(result.definition_source.is_none());
+        assert_eq!(
+            result.features_used.definition_source.status,
+            FeatureStatus::RequestedButFailed
+        );
+
+        // The source is only read when asked for
+        let result = analyze("baz", "src/bar.cpp:3", None).await;
+        assert!(result.definition_source.is_none());
+        assert_eq!(
+            result.features_used.definition_source.status,
+            FeatureStatus::NotRequested
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_reports_feature_status() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/bar.cpp",
+            "#include \"bar.h\"\n\nvoid Bar::baz() {\n}\n",
+        )]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Bar::baz",
+                "kind": 6,
+                "range": {"start": {"line": 2, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 2, "character": 10}, "end": {"line": 2, "character": 13}}
+            }]),
+        );
+        server.respond(
+            "textDocument/hover",
+            serde_json::json!({"contents": {"kind": "markdown", "value": "### method `baz`"}}),
+        );
+        server.fail(
+            "textDocument/prepareCallHierarchy",
+            -32603,
+            "index not ready",
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "baz",
+            "location_hint": "src/bar.cpp:3",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool
+            .analyze(
+                &component_session,
+                &workspace,
+                &workspace.result_exclusions,
+                None,
+            )
+            .await
+            .unwrap();
+        let features = result.features_used;
+
+        assert_eq!(
+            features.hover_documentation.status,
+            FeatureStatus::RequestedAndFound
+        );
+        assert_eq!(features.examples.status, FeatureStatus::RequestedButEmpty);
+        assert_eq!(
+            features.call_hierarchy.status,
+            FeatureStatus::RequestedButFailed
+        );
+        assert!(
+            features
+                .call_hierarchy
+                .error
+                .unwrap()
+                .contains("index not ready")
+        );
+        assert_eq!(features.type_hierarchy, FeatureReport::not_requested());
+        assert_eq!(features.members.status, FeatureStatus::NotRequested);
+        assert_eq!(features.enumerators.status, FeatureStatus::NotRequested);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_local_symbol_via_definition() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/main.cpp",
+            "int main() {\n  int count = 0;\n  return count;\n}\n",
+        )]);
+        let main_cpp = workspace.project_root_path.join("src/main.cpp");
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "main",
+                "detail": "int ()",
+                "kind": 12,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 8}}
+            }]),
+        );
+        server.respond(
+            "textDocument/definition",
+            serde_json::json!([{
+                "uri": crate::symbol::uri_from_pathbuf(&main_cpp).as_str(),
+                "range": {"start": {"line": 1, "character": 6}, "end": {"line": 1, "character": 11}}
+            }]),
+        );
+        server.respond(
+            "textDocument/hover",
+            serde_json::json!({
+                "contents": {"kind": "markdown", "value": "### variable `count`\n\n---\nType: `int`"}
+            }),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // The usage on line 3 is neither a document symbol nor in the workspace index
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "count",
+            "location_hint": "src/main.cpp:3",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let rust_mcp_sdk::schema::ContentBlock::TextContent(TextContent { text, .. }) =
+            &result.content[0]
+        else {
+            panic!("Expected TextContent in call_result");
+        };
+        let analyzer_result: AnalyzerResult = serde_json::from_str(text).unwrap();
+        assert_eq!(analyzer_result.symbol.name, "count");
+        assert_eq!(analyzer_result.symbol.kind, lsp_types::SymbolKind::VARIABLE);
+        assert_eq!(analyzer_result.symbol.location.range.start.line, 1);
+
+        // The definition was requested at the name on the hinted line
+        let params = server.received_params("textDocument/definition")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["position"]["line"], 2);
+        assert_eq!(params["position"]["character"], 9);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_symbol_found_in_open_file() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/util.cpp", "static int helper() { return 1; }\n")]);
+        let server = MockClangd::new();
+        server.respond("workspace/symbol", serde_json::json!([]));
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "helper",
+                "detail": "int ()",
+                "kind": 12,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 33}},
+                "selectionRange": {"start": {"line": 0, "character": 11}, "end": {"line": 0, "character": 17}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        component_session
+            .ensure_file_ready(&workspace.project_root_path.join("src/util.cpp"))
+            .await
+            .unwrap();
+
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "helper",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await
+            .unwrap();
+        let rust_mcp_sdk::schema::ContentBlock::TextContent(TextContent { text, .. }) =
+            &result.content[0]
+        else {
+            panic!("Expected TextContent in call_result");
+        };
+        let analyzer_result: AnalyzerResult = serde_json::from_str(text).unwrap();
+        assert_eq!(analyzer_result.symbol.name, "helper");
+        assert_eq!(analyzer_result.detail.as_deref(), Some("int ()"));
+
+        // Names in no open file are still reported as not found
+        let tool = AnalyzeSymbolContextTool {
+            symbol: "missing".to_string(),
+            ..tool
+        };
+        assert!(tool.call_tool(component_session, &workspace).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_template_instantiation() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[
+            (
+                "src/box.h",
+                "template <typename T>\nclass Box {\n  T value;\n};\n",
+            ),
+            (
+                "src/main.cpp",
+                "#include \"box.h\"\nBox<float> a;\nBox< int > b;\n",
+            ),
+        ]);
+        let uri = |file: &str| {
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join(file)).to_string()
+        };
+        let location = |file: &str, line: u32, start: u32, end: u32| {
+            serde_json::json!({
+                "uri": uri(file),
+                "range": {"start": {"line": line, "character": start}, "end": {"line": line, "character": end}}
+            })
+        };
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([{"name": "Box", "kind": 5, "location": location("src/box.h", 1, 6, 9)}]),
+        );
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Box",
+                "kind": 5,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 1, "character": 6}, "end": {"line": 1, "character": 9}}
+            }]),
+        );
+        server.respond(
+            "textDocument/references",
+            serde_json::json!([
+                location("src/main.cpp", 1, 0, 3),
+                location("src/main.cpp", 2, 0, 3)
+            ]),
+        );
+        server.respond(
+            "textDocument/definition",
+            serde_json::json!([location("src/box.h", 1, 6, 9)]),
+        );
+        server.respond(
+            "textDocument/hover",
+            serde_json::json!({"contents": {"kind": "markdown", "value": "### class `Box<int>`"}}),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let analyze = |symbol: &str| {
+            let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "wait_timeout": 0
+            }))
+            .unwrap();
+            let component_session = Arc::clone(&component_session);
+            let workspace = &workspace;
+            async move {
+                tool.analyze(
+                    &component_session,
+                    workspace,
+                    &workspace.result_exclusions,
+                    None,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let result = analyze("Box<int>").await;
+        assert_eq!(result.query, "Box<int>");
+        assert_eq!(result.symbol.name, "Box");
+        let instantiation = result.instantiation.unwrap();
+        let use_site = instantiation.use_site.unwrap();
+        assert!(use_site.file_path.ends_with("src/main.cpp"));
+        assert_eq!(use_site.range.start.line, 2);
+        assert_eq!(
+            instantiation.use_site_code.as_deref(),
+            Some("Box< int > b;")
+        );
+        assert_eq!(instantiation.hover.as_deref(), Some("### class `Box<int>`"));
+        // The use resolves to the primary template
+        assert!(instantiation.specialization.is_none());
+        assert!(instantiation.note.is_none());
+
+        let instantiation = analyze("Box<double>").await.instantiation.unwrap();
+        assert!(instantiation.use_site.is_none());
+        assert!(instantiation.note.unwrap().contains("2 references"));
+
+        assert!(analyze("Box").await.instantiation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_returns_partial_result_while_indexing() {
+        use super::*;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/bar.cpp",
+            "#include \"bar.h\"\n\nvoid Bar::baz() {\n}\n",
+        )]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Bar::baz",
+                "kind": 6,
+                "range": {"start": {"line": 2, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 2, "character": 10}, "end": {"line": 2, "character": 13}}
+            }]),
+        );
+        server.fail("textDocument/definition", -32603, "index not ready");
+        server.respond("workspace/symbol", serde_json::json!([]));
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let indexing = || {
+            Some(IndexStatusView::new(
+                true,
+                Some(40.0),
+                4,
+                10,
+                None,
+                "InProgress(40.0%)".to_string(),
+            ))
+        };
+
+        let tool: AnalyzeSymbolContextTool = serde_json::from_value(serde_json::json!({
+            "symbol": "baz",
+            "location_hint": "src/bar.cpp:3",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let analyze = |tool: AnalyzeSymbolContextTool, index_status: Option<IndexStatusView>| {
+            let component_session = &component_session;
+            let workspace = &workspace;
+            async move {
+                tool.analyze(
+                    component_session,
+                    workspace,
+                    &workspace.result_exclusions,
+                    index_status,
+                )
+                .await
+            }
+        };
+
+        // Failing steps are left empty while clangd is indexing
+        let result = analyze(tool.clone(), indexing()).await.unwrap();
+        assert!(result.indexing_incomplete);
+        assert!(result.definitions.is_empty());
+        assert_eq!(result.symbol.name, "Bar::baz");
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["indexing_incomplete"], true);
+
+        // Once indexed, the failure is reported
+        let completed = IndexStatusView::new(false, None, 10, 10, None, "Completed".to_string());
+        assert!(analyze(tool.clone(), Some(completed)).await.is_err());
+        assert!(analyze(tool.clone(), None).await.is_err());
+
+        // A symbol clangd has not indexed yet fails with a hint to retry
+        let tool = AnalyzeSymbolContextTool {
+            symbol: "Missing".to_string(),
+            location_hint: None,
+            ..tool
+        };
+        let error = analyze(tool, indexing()).await.unwrap_err().to_string();
+        assert!(
+            error.contains("still indexing, 4/10 files indexed"),
+            "{error}"
+        );
+    }
 }
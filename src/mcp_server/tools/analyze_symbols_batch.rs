@@ -0,0 +1,309 @@
+//! Batch symbol analysis tool
+//!
+//! Runs `analyze_symbol_context` for several symbols in one call. Indexing is
+//! waited for once for the whole batch, and the analyses share the same clangd
+//! session, running concurrently up to a small limit.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::propagate_source_overlay;
+use crate::mcp_server::tools::analyze_symbols::{
+    AnalyzeSymbolContextTool, AnalyzerError, AnalyzerResult,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Maximum number of symbols in one batch
+const MAX_BATCH_SYMBOLS: usize = 50;
+
+/// Number of analyses running at the same time
+const MAX_CONCURRENT_ANALYSES: usize = 4;
+
+#[mcp_tool(
+    name = "analyze_symbols_batch",
+    description = "Run analyze_symbol_context for several C++ symbols in one call. Indexing is waited \
+                   for once for the whole batch and the analyses run concurrently on the same clangd \
+                   session, which is much faster than the same number of single calls.
+
+                   🎯 PRIMARY USE CASES:
+                   Analyzing all parameters or members of a function or class • Comparing related \
+                   symbols • Following up on a list of search_symbols results
+
+                   📋 OUTPUT:
+                   • results: One entry per requested symbol, in request order, each with the query,
+                     its location_hint and either the full analyze_symbol_context result or an error
+                   • succeeded / failed: Number of analyses that succeeded or failed
+                   • index_status: Index status when indexing was not complete (shared by all results)
+
+                   INPUT REQUIREMENTS:
+                   • symbols: Required array of {symbol, location_hint?} entries (at most 50), same
+                     formats as analyze_symbol_context
+                   • max_examples: Optional number - usage examples per symbol
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted files to be parsed when every symbol has a location_hint (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct AnalyzeSymbolsBatchTool {
+    /// Symbols to analyze, each with an optional location hint
+    pub symbols: Vec<BatchSymbol>,
+
+    /// Maximum number of usage examples per symbol. OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_examples: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the hinted files
+    /// to be parsed when every symbol has a location hint (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A symbol of a batch
+#[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct BatchSymbol {
+    /// Symbol name, as for analyze_symbol_context
+    pub symbol: String,
+
+    /// Location hint, as for analyze_symbol_context. OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+}
+
+/// Analysis of one symbol of a batch
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchEntryResult {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<AnalyzerResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchAnalyzerResult {
+    pub results: Vec<BatchEntryResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl AnalyzeSymbolsBatchTool {
+    /// Single-symbol analysis of a batch entry
+    fn entry_tool(&self, entry: &BatchSymbol) -> AnalyzeSymbolContextTool {
+        AnalyzeSymbolContextTool {
+            symbol: entry.symbol.clone(),
+            build_directory: None,
+            session_id: None,
+            max_examples: self.max_examples,
+            location_hint: entry.location_hint.clone(),
+            member_access: None,
+            member_kinds: None,
+            exclude_special_members: None,
+            context_file: None,
+            wait_timeout: Some(0),
+            allow_outside_root: None,
+            include_definition_source: None,
+        }
+    }
+
+    #[instrument(
+        name = "analyze_symbols_batch",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        if self.symbols.is_empty() || self.symbols.len() > MAX_BATCH_SYMBOLS {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "'symbols' must contain between 1 and {} entries, got {}",
+                    MAX_BATCH_SYMBOLS,
+                    self.symbols.len()
+                ),
+            )));
+        }
+
+        info!(
+            "Starting batch analysis of {} symbols, wait_timeout={:?}",
+            self.symbols.len(),
+            self.wait_timeout
+        );
+
+        // Document-specific wait only when every symbol is located by a hint
+        let document_files: Option<Vec<_>> = self
+            .symbols
+            .iter()
+            .map(|entry| {
+                entry
+                    .location_hint
+                    .as_ref()
+                    .and_then(|hint| utils::location_hint_file(workspace, hint))
+            })
+            .collect();
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if document_files.is_some() {
+                "Document-specific batch analysis"
+            } else {
+                "Workspace batch analysis"
+            },
+        )
+        .await;
+
+        let limiter = Arc::new(Semaphore::new(MAX_CONCURRENT_ANALYSES));
+        let shared_workspace = Arc::new(workspace.clone());
+        let mut analyses = JoinSet::new();
+        for (index, entry) in self.symbols.iter().enumerate() {
+            let tool = self.entry_tool(entry);
+            let component_session = Arc::clone(&component_session);
+            let workspace = Arc::clone(&shared_workspace);
+            let limiter = Arc::clone(&limiter);
+            analyses.spawn(propagate_source_overlay(async move {
+                let _permit = limiter.acquire_owned().await;
+                let analysis = tool
+                    .analyze(
+                        &component_session,
+                        &workspace,
+                        &workspace.result_exclusions,
+                        None,
+                    )
+                    .await
+                    .map_err(|e| e.to_string());
+                (index, analysis)
+            }));
+        }
+
+        let mut outcomes: Vec<Option<Result<AnalyzerResult, String>>> =
+            self.symbols.iter().map(|_| None).collect();
+        while let Some(joined) = analyses.join_next().await {
+            let (index, analysis) = joined.map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!(
+                    "Symbol analysis task failed: {}",
+                    e
+                )))
+            })?;
+            outcomes[index] = Some(analysis);
+        }
+
+        let results: Vec<BatchEntryResult> = self
+            .symbols
+            .iter()
+            .zip(outcomes)
+            .map(|(entry, outcome)| {
+                let (analysis, error) = match outcome {
+                    Some(Ok(analysis)) => (Some(analysis), None),
+                    Some(Err(error)) => (None, Some(error)),
+                    None => (None, Some("Analysis did not complete".to_string())),
+                };
+                BatchEntryResult {
+                    query: entry.symbol.clone(),
+                    location_hint: entry.location_hint.clone(),
+                    analysis,
+                    error,
+                }
+            })
+            .collect();
+        let succeeded = results.iter().filter(|r| r.analysis.is_some()).count();
+
+        let result = BatchAnalyzerResult {
+            failed: results.len() - succeeded,
+            succeeded,
+            results,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_batch_reports_each_symbol() {
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/bar.cpp",
+            "#include \"bar.h\"\n\nvoid Bar::baz() {\n}\n",
+        )]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Bar::baz",
+                "detail": "void ()",
+                "kind": 6,
+                "range": {"start": {"line": 2, "character": 0}, "end": {"line": 3, "character": 1}},
+                "selectionRange": {"start": {"line": 2, "character": 10}, "end": {"line": 2, "character": 13}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: AnalyzeSymbolsBatchTool = serde_json::from_value(serde_json::json!({
+            "symbols": [
+                {"symbol": "baz", "location_hint": "src/bar.cpp:3"},
+                {"symbol": "missing", "location_hint": "src/bar.cpp:2"}
+            ],
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await
+            .unwrap();
+        let result: BatchAnalyzerResult = tool_result(&result);
+
+        assert_eq!((result.succeeded, result.failed), (1, 1));
+        assert_eq!(result.results[0].query, "baz");
+        let analysis = result.results[0].analysis.as_ref().unwrap();
+        assert_eq!(analysis.symbol.name, "Bar::baz");
+        assert_eq!(result.results[1].query, "missing");
+        assert!(result.results[1].error.is_some());
+
+        let empty: AnalyzeSymbolsBatchTool =
+            serde_json::from_value(serde_json::json!({"symbols": []})).unwrap();
+        assert!(
+            empty
+                .call_tool(component_session, &workspace)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! AST dump tool
+//!
+//! Returns the clang AST of a file range as reported by clangd's non-standard
+//! `textDocument/ast` request: the node enclosing the range and its children,
+//! each with its role, kind, detail and source range.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::ast::{AstLookup, ClangdAstNode, get_ast};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Default depth of the returned tree below the enclosing node
+const DEFAULT_MAX_DEPTH: u32 = 8;
+
+#[mcp_tool(
+    name = "get_ast",
+    description = "Dump the clang AST of a C++ source range using clangd's AST extension. Returns the \
+                   smallest AST node entirely containing the range with its children, or the whole \
+                   translation unit when no range is given.
+
+                   🎯 PRIMARY USE CASES:
+                   Understanding how clang parses an expression or declaration • Inspecting implicit \
+                   conversions and template instantiations • Debugging macro-heavy code
+
+                   📋 OUTPUT:
+                   • status: \"ok\", \"no_node\" when nothing encloses the range, or \"feature_unavailable\"
+                     when the clangd version of the session lacks the AST extension (clangd < 12)
+                   • reason: Why the AST is unavailable
+                   • clangd_version: Version of the clangd serving the session
+                   • document_version: LSP version of the file content the AST was built from
+                   • ast: Node tree - role (expression, declaration, statement, type...), kind
+                     (e.g. BinaryOperator), detail (e.g. operator or declared name), range
+                     (\"line:column-line:column\", 1-based) and children
+                   • truncated_children on nodes cut at max_depth: number of omitted children
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • start_line / start_column: Optional 1-based start of the range (both or neither)
+                   • end_line / end_column: Optional 1-based end of the range (default: the start)
+                   • max_depth: Optional depth of the returned tree (default: 8)
+                   • include_arcana: Optional boolean - include clang's raw node dump (default: false)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct AstTool {
+    /// File to dump. Absolute paths are used as-is, relative paths are resolved
+    /// against the project root.
+    pub file: String,
+
+    /// Line of the start of the range (1-based). OPTIONAL: the whole translation
+    /// unit is returned when no range is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+
+    /// Column of the start of the range (1-based). Required with start_line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<u32>,
+
+    /// Line of the end of the range (1-based). OPTIONAL, defaults to start_line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+
+    /// Column of the end of the range (1-based). OPTIONAL, defaults to start_column.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+
+    /// Depth of the returned tree below the enclosing node (default: 8). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+
+    /// Include clang's raw dump of each node (default: false). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_arcana: Option<bool>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Availability of the AST for the requested range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AstStatus {
+    Ok,
+    NoNode,
+    FeatureUnavailable,
+}
+
+/// AST node in tool output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AstNode {
+    pub role: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Source range as "line:column-line:column", 1-based
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arcana: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<AstNode>,
+    /// Number of children omitted below max_depth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated_children: Option<usize>,
+}
+
+impl AstNode {
+    /// Convert a clangd node, keeping `depth` levels of children
+    fn from_clangd(node: ClangdAstNode, depth: u32, include_arcana: bool) -> Self {
+        let (children, truncated_children) = if depth == 0 {
+            let omitted = node.children.len();
+            (Vec::new(), (omitted > 0).then_some(omitted))
+        } else {
+            let children = node
+                .children
+                .into_iter()
+                .map(|child| Self::from_clangd(child, depth - 1, include_arcana))
+                .collect();
+            (children, None)
+        };
+
+        Self {
+            role: node.role,
+            kind: node.kind,
+            detail: node.detail,
+            range: node.range.map(|range| compact_range(&range)),
+            arcana: node.arcana.filter(|_| include_arcana),
+            children,
+            truncated_children,
+        }
+    }
+}
+
+/// Format a range as "line:column-line:column" with 1-based numbers
+fn compact_range(range: &lsp_types::Range) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line + 1,
+        range.start.character + 1,
+        range.end.line + 1,
+        range.end.character + 1
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AstResult {
+    pub file: PathBuf,
+    pub status: AstStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub clangd_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_version: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ast: Option<AstNode>,
+}
+
+impl AstTool {
+    /// LSP range requested by the tool input, if any
+    fn requested_range(&self) -> Result<Option<lsp_types::Range>, CallToolError> {
+        let invalid = |message: String| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        };
+
+        let (start_line, start_column) = match (self.start_line, self.start_column) {
+            (None, None) if self.end_line.is_none() && self.end_column.is_none() => {
+                return Ok(None);
+            }
+            (Some(line), Some(column)) => (line, column),
+            _ => {
+                return Err(invalid(
+                    "Specify both 'start_line' and 'start_column' to select a range".to_string(),
+                ));
+            }
+        };
+        let end_line = self.end_line.unwrap_or(start_line);
+        let end_column = self.end_column.unwrap_or(start_column);
+
+        if [start_line, start_column, end_line, end_column].contains(&0) {
+            return Err(invalid("Lines and columns are 1-based".to_string()));
+        }
+        let start = lsp_types::Position::new(start_line - 1, start_column - 1);
+        let end = lsp_types::Position::new(end_line - 1, end_column - 1);
+        if end < start {
+            return Err(invalid(format!(
+                "Range ends at {}:{} before it starts at {}:{}",
+                end_line, end_column, start_line, start_column
+            )));
+        }
+
+        Ok(Some(lsp_types::Range::new(start, end)))
+    }
+
+    #[instrument(name = "get_ast", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let range = self.requested_range()?;
+
+        info!(
+            "Dumping AST of {} (range: {:?})",
+            file_path.display(),
+            range
+        );
+
+        let lookup = get_ast(&file_path, range, &component_session).await?;
+        let (status, reason, ast) = match lookup {
+            AstLookup::Node(node) => (
+                AstStatus::Ok,
+                None,
+                Some(AstNode::from_clangd(
+                    node,
+                    self.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+                    self.include_arcana.unwrap_or(false),
+                )),
+            ),
+            AstLookup::NoNode => (AstStatus::NoNode, None, None),
+            AstLookup::Unsupported(reason) => {
+                warn!("AST unavailable: {}", reason);
+                (AstStatus::FeatureUnavailable, Some(reason), None)
+            }
+        };
+
+        let result = AstResult {
+            document_version: component_session.document_version(&file_path).await,
+            file: file_path,
+            status,
+            reason,
+            clangd_version: component_session.clangd_version().to_string(),
+            ast,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    fn tool(start: Option<(u32, u32)>, max_depth: Option<u32>) -> AstTool {
+        AstTool {
+            file: "src/main.cpp".to_string(),
+            start_line: start.map(|s| s.0),
+            start_column: start.map(|s| s.1),
+            end_line: None,
+            end_column: None,
+            max_depth,
+            include_arcana: None,
+            build_directory: None,
+            session_id: None,
+        }
+    }
+
+    async fn call(server: &MockClangd, tool: &AstTool) -> (AstResult, Vec<String>) {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int f(int a) {\n  return a + 1;\n}\n")]);
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        (tool_result(&result), server.received_methods())
+    }
+
+    #[tokio::test]
+    async fn test_ast_of_range() {
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/ast",
+            serde_json::json!({
+                "role": "expression",
+                "kind": "BinaryOperator",
+                "detail": "+",
+                "arcana": "BinaryOperator 0x1 'int' '+'",
+                "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 14}},
+                "children": [{
+                    "role": "expression",
+                    "kind": "ImplicitCast",
+                    "detail": "LValueToRValue",
+                    "children": [{"role": "expression", "kind": "DeclRef", "detail": "a"}]
+                }, {
+                    "role": "expression",
+                    "kind": "IntegerLiteral",
+                    "detail": "1"
+                }]
+            }),
+        );
+
+        let (result, methods) = call(&server, &tool(Some((2, 12)), Some(1))).await;
+        assert!(methods.contains(&"textDocument/ast".to_string()));
+        let params = server.received_params("textDocument/ast")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["range"]["start"]["line"], 1);
+        assert_eq!(params["range"]["start"]["character"], 11);
+
+        assert_eq!(result.status, AstStatus::Ok);
+        assert_eq!(result.clangd_version, "18.1.8");
+        assert_eq!(result.document_version, Some(1));
+        let ast = result.ast.unwrap();
+        assert_eq!(ast.kind, "BinaryOperator");
+        assert_eq!(ast.range.as_deref(), Some("2:10-2:15"));
+        assert!(ast.arcana.is_none());
+        assert_eq!(ast.children.len(), 2);
+        assert_eq!(ast.children[0].truncated_children, Some(1));
+        assert_eq!(ast.children[1].truncated_children, None);
+    }
+
+    #[tokio::test]
+    async fn test_ast_feature_unavailable() {
+        let server = MockClangd::new();
+        server.fail("textDocument/ast", -32601, "method not found");
+
+        let (result, _) = call(&server, &tool(None, None)).await;
+        assert_eq!(result.status, AstStatus::FeatureUnavailable);
+        assert!(result.reason.unwrap().contains("method not found"));
+        assert!(result.ast.is_none());
+    }
+
+    #[test]
+    fn test_requested_range_validation() {
+        assert!(tool(None, None).requested_range().unwrap().is_none());
+        assert!(tool(Some((0, 1)), None).requested_range().is_err());
+
+        let mut partial = tool(None, None);
+        partial.start_line = Some(3);
+        assert!(partial.requested_range().is_err());
+
+        let mut reversed = tool(Some((3, 1)), None);
+        reversed.end_line = Some(2);
+        assert!(reversed.requested_range().is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! Batch hover tool
+//!
+//! Collects hover information for many positions of one file in a single call:
+//! either explicit positions or every symbol of the file outline. The file is
+//! opened once and the hover requests are issued concurrently.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::propagate_source_overlay;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::get_document_symbols;
+use crate::mcp_server::tools::lsp_helpers::hover::get_hover_info;
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, uri_from_pathbuf};
+
+/// Maximum number of positions hovered in one call
+const MAX_BATCH_POSITIONS: usize = 200;
+
+/// Number of hover requests in flight at the same time
+const MAX_CONCURRENT_HOVERS: usize = 8;
+
+#[mcp_tool(
+    name = "batch_hover",
+    description = "Get hover information (type, signature, documentation) for many positions of one \
+                   C++ file in a single call. The file is opened once and the hovers run concurrently, \
+                   which is much faster than one request per identifier. Without explicit positions, \
+                   every symbol of the file outline is hovered, producing an annotated outline.
+
+                   🎯 PRIMARY USE CASES:
+                   Summarizing a file • Annotating an outline with types and documentation • \
+                   Inspecting several identifiers found with grep at once
+
+                   📋 OUTPUT:
+                   • file: The hovered file
+                   • hovers: One entry per position, in request order (outline order without positions), each with
+                     - position: \"line:column\" (1-based)
+                     - symbol: Outline symbol name (outline mode only)
+                     - hover: Hover text as returned by clangd (markdown)
+                     - error: Why no hover is available for the position
+                   • succeeded / failed: Number of positions with and without hover information
+                   • truncated: true when more than 200 positions were requested or found
+
+                   INPUT REQUIREMENTS:
+                   • file: Required source or header file (absolute or project-relative)
+                   • positions: Optional array of \"line:column\" strings (1-based); omit to hover every outline symbol
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct BatchHoverTool {
+    /// File to hover in. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Positions to hover, as "line:column" with 1-based line/column numbers. OPTIONAL.
+    ///
+    /// When omitted, the name of every symbol in the file outline (document symbols,
+    /// including nested members) is hovered. At most 200 positions are hovered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positions: Option<Vec<String>>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Hover information for one position
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PositionHover {
+    /// Hovered position, "line:column" (1-based)
+    pub position: String,
+    /// Outline symbol at the position, when hovering the outline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hover: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchHoverResult {
+    pub file: PathBuf,
+    pub hovers: Vec<PositionHover>,
+    pub succeeded: usize,
+    pub failed: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub truncated: bool,
+}
+
+/// A position to hover, with the outline symbol it belongs to
+struct HoverTarget {
+    position: lsp_types::Position,
+    symbol: Option<String>,
+}
+
+impl BatchHoverTool {
+    /// Parse a 1-based "line:column" position
+    fn parse_position(position: &str) -> Result<lsp_types::Position, CallToolError> {
+        let parsed = position.split_once(':').and_then(|(line, column)| {
+            let line: u32 = line.trim().parse().ok()?;
+            let column: u32 = column.trim().parse().ok()?;
+            (line > 0 && column > 0).then(|| lsp_types::Position::new(line - 1, column - 1))
+        });
+        parsed.ok_or_else(|| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Invalid position '{}': expected \"line:column\" with 1-based numbers",
+                    position
+                ),
+            ))
+        })
+    }
+
+    /// Name positions of every outline symbol, in outline order
+    fn outline_targets(symbols: &[lsp_types::DocumentSymbol], targets: &mut Vec<HoverTarget>) {
+        for symbol in symbols {
+            targets.push(HoverTarget {
+                position: symbol.selection_range.start,
+                symbol: Some(symbol.name.clone()),
+            });
+            if let Some(children) = &symbol.children {
+                Self::outline_targets(children, targets);
+            }
+        }
+    }
+
+    #[instrument(name = "batch_hover", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        let mut targets = match &self.positions {
+            Some(positions) => positions
+                .iter()
+                .map(|position| {
+                    Ok(HoverTarget {
+                        position: Self::parse_position(position)?,
+                        symbol: None,
+                    })
+                })
+                .collect::<Result<Vec<_>, CallToolError>>()?,
+            None => {
+                let symbols =
+                    get_document_symbols(&component_session, uri_from_pathbuf(&file_path)).await?;
+                let mut targets = Vec::new();
+                Self::outline_targets(&symbols, &mut targets);
+                targets
+            }
+        };
+        let truncated = targets.len() > MAX_BATCH_POSITIONS;
+        targets.truncate(MAX_BATCH_POSITIONS);
+
+        info!(
+            "Hovering {} positions in {}",
+            targets.len(),
+            file_path.display()
+        );
+
+        // Open the file once up front instead of racing to open it from every hover
+        component_session
+            .ensure_file_ready(&file_path)
+            .await
+            .map_err(AnalyzerError::from)?;
+
+        let limiter = Arc::new(Semaphore::new(MAX_CONCURRENT_HOVERS));
+        let mut hovers = JoinSet::new();
+        for (index, target) in targets.iter().enumerate() {
+            let location = FileLocation {
+                file_path: file_path.clone(),
+                range: lsp_types::Range::new(target.position, target.position).into(),
+            };
+            let component_session = Arc::clone(&component_session);
+            let limiter = Arc::clone(&limiter);
+            hovers.spawn(propagate_source_overlay(async move {
+                let _permit = limiter.acquire_owned().await;
+                let hover = get_hover_info(&location, &component_session)
+                    .await
+                    .map_err(|e| e.to_string());
+                (index, hover)
+            }));
+        }
+
+        let mut outcomes: Vec<Option<Result<String, String>>> =
+            targets.iter().map(|_| None).collect();
+        while let Some(joined) = hovers.join_next().await {
+            let (index, hover) = joined.map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!("Hover task failed: {}", e)))
+            })?;
+            outcomes[index] = Some(hover);
+        }
+
+        let hovers: Vec<PositionHover> = targets
+            .into_iter()
+            .zip(outcomes)
+            .map(|(target, outcome)| {
+                let (hover, error) = match outcome {
+                    Some(Ok(hover)) => (Some(hover), None),
+                    Some(Err(error)) => (None, Some(error)),
+                    None => (None, Some("Hover did not complete".to_string())),
+                };
+                PositionHover {
+                    position: format!(
+                        "{}:{}",
+                        target.position.line + 1,
+                        target.position.character + 1
+                    ),
+                    symbol: target.symbol,
+                    hover,
+                    error,
+                }
+            })
+            .collect();
+        let succeeded = hovers.iter().filter(|h| h.hover.is_some()).count();
+
+        let result = BatchHoverResult {
+            file: file_path,
+            failed: hovers.len() - succeeded,
+            succeeded,
+            hovers,
+            truncated,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, range, tool_result};
+
+    /// Answer hovers on line 0 with a type and fail on every other line
+    fn hover_by_line(server: &MockClangd) {
+        server.respond_with("textDocument/hover", |params| {
+            let position = &params.unwrap()["position"];
+            if position["line"] == 0 {
+                serde_json::json!({
+                    "contents": {
+                        "kind": "markdown",
+                        "value": format!("hover at {}", position["character"])
+                    }
+                })
+            } else {
+                serde_json::Value::Null
+            }
+        });
+    }
+
+    async fn hover(
+        server: &MockClangd,
+        workspace: &ProjectWorkspace,
+        positions: Option<Vec<&str>>,
+    ) -> Result<BatchHoverResult, CallToolError> {
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool = BatchHoverTool {
+            file: "src/widget.h".to_string(),
+            positions: positions.map(|p| p.into_iter().map(str::to_string).collect()),
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool.call_tool(component_session, workspace).await?;
+        Ok(tool_result(&result))
+    }
+
+    #[tokio::test]
+    async fn test_hover_explicit_positions_in_order() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/widget.h", "struct Widget { int size; };\n")]);
+        let server = MockClangd::new();
+        hover_by_line(&server);
+
+        let result = hover(&server, &workspace, Some(vec!["1:21", "1:8", "2:1"]))
+            .await
+            .unwrap();
+
+        let summary: Vec<_> = result
+            .hovers
+            .iter()
+            .map(|h| (h.position.as_str(), h.hover.as_deref(), h.error.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("1:21", Some("hover at 20"), false),
+                ("1:8", Some("hover at 7"), false),
+                ("2:1", None, true),
+            ]
+        );
+        assert_eq!((result.succeeded, result.failed), (2, 1));
+        assert!(!result.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_hover_outline_symbols() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/widget.h", "struct Widget { int size; };\n")]);
+        let server = MockClangd::new();
+        hover_by_line(&server);
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "Widget", "kind": 23,
+                "range": range(0, 0, 27), "selectionRange": range(0, 7, 13),
+                "children": [{
+                    "name": "size", "kind": 8,
+                    "range": range(0, 16, 24), "selectionRange": range(0, 20, 24)
+                }]
+            }]),
+        );
+
+        let result = hover(&server, &workspace, None).await.unwrap();
+
+        let summary: Vec<_> = result
+            .hovers
+            .iter()
+            .map(|h| (h.symbol.as_deref(), h.position.as_str(), h.hover.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("Widget"), "1:8", Some("hover at 7")),
+                (Some("size"), "1:21", Some("hover at 20")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_position_rejected() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/widget.h", "int x;\n")]);
+        let server = MockClangd::new();
+
+        for position in ["0:1", "1", "a:b"] {
+            assert!(
+                hover(&server, &workspace, Some(vec![position]))
+                    .await
+                    .is_err(),
+                "{position}"
+            );
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! Blast radius tool
+//!
+//! Follows incoming calls transitively to list every function that may be
+//! affected by changing a function, answering "what might break if I change
+//! this" in one request instead of one call hierarchy level at a time.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::call_hierarchy::{
+    AffectedFunction, get_transitive_callers,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+/// Default number of call levels followed
+const DEFAULT_MAX_DEPTH: u32 = 5;
+
+/// Default number of callers reported
+const DEFAULT_MAX_FUNCTIONS: u32 = 200;
+
+/// Upper bound of the call levels followed, whatever was requested
+const MAX_DEPTH_LIMIT: u32 = 20;
+
+#[mcp_tool(
+    name = "get_blast_radius",
+    description = "Compute the blast radius of a C++ function or method: every function that calls \
+                   it directly or indirectly, found by following incoming calls transitively.
+
+                   🎯 PRIMARY USE CASES:
+                   Assessing the risk of a refactoring • Finding the code paths to retest after a \
+                   change • Spotting entry points (main, handlers, tests) that reach a function
+
+                   📋 OUTPUT:
+                   • function: The analyzed function as clangd names it
+                   • count: Number of affected functions
+                   • callers: Affected functions, closest first, each reported once with its \
+                     location, depth (1 = direct caller) and the function it calls on the way
+                   • files: Files containing affected functions, deduplicated
+                   • truncated: true when the depth or size limit left callers out
+
+                   Calls through function pointers and virtual dispatch through base classes are \
+                   not followed; combine with find_overrides for virtual methods.
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required function name, preferably qualified (e.g. \"Parser::parse\")
+                   • location_hint: Optional string - exact function location (format: \"/path/file.cpp:line:column\")
+                   • max_depth: Optional number - call levels to follow (default: 5, max: 20)
+                   • max_functions: Optional number - affected functions to report (default: 200)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetBlastRadiusTool {
+    /// Name of the function or method. Qualify methods with their class to avoid
+    /// picking a same-named function.
+    ///
+    /// EXAMPLES: "Parser::parse", "utils::split", "main"
+    pub symbol: String,
+
+    /// Location of the function name for precise selection. OPTIONAL.
+    ///
+    /// FORMAT: Compact LSP-style location string with 1-based line/column numbers:
+    /// • "/absolute/path/to/file.cpp:line:column"
+    ///
+    /// BEHAVIOR: When specified, the function at this location is used and workspace
+    /// symbol search is skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Number of call levels to follow (default: 5, at most 20). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+
+    /// Number of affected functions to report (default: 200). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_functions: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlastRadiusResult {
+    /// Symbol as requested
+    pub symbol: String,
+    /// The analyzed function as clangd names it
+    pub function: String,
+    /// Location the call hierarchy was computed from
+    pub location: FileLocation,
+    /// Number of affected functions
+    pub count: usize,
+    /// Affected functions in breadth-first order
+    pub callers: Vec<AffectedFunction>,
+    /// Files containing affected functions
+    pub files: Vec<PathBuf>,
+    /// Whether the depth or size limit left callers out
+    pub truncated: bool,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl GetBlastRadiusTool {
+    #[instrument(name = "get_blast_radius", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let max_depth = self
+            .max_depth
+            .unwrap_or(DEFAULT_MAX_DEPTH)
+            .min(MAX_DEPTH_LIMIT);
+        let max_functions = self.max_functions.unwrap_or(DEFAULT_MAX_FUNCTIONS) as usize;
+        info!(
+            "Computing blast radius of '{}', location_hint={:?}, max_depth={}, max_functions={}",
+            self.symbol, self.location_hint, max_depth, max_functions
+        );
+
+        // Callers are found through the index, so wait for it even with a hint
+        let index_status = utils::handle_selective_indexing_wait(
+            component_session,
+            None,
+            self.wait_timeout,
+            "Blast radius",
+        )
+        .await;
+
+        let location = utils::locate_symbol(
+            workspace,
+            component_session,
+            &self.symbol,
+            self.location_hint.as_deref(),
+        )
+        .await?;
+        let transitive = get_transitive_callers(
+            &location,
+            component_session,
+            &workspace.result_exclusions,
+            max_depth,
+            max_functions,
+        )
+        .await
+        .map_err(CallToolError::from)?;
+        let Some(function) = transitive.function else {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "No function or method found at {}; the blast radius follows calls, so \
+                     '{}' must be callable",
+                    location.to_compact_range(),
+                    self.symbol
+                ),
+            )));
+        };
+
+        let files: BTreeSet<PathBuf> = transitive
+            .callers
+            .iter()
+            .map(|caller| caller.location.file_path.clone())
+            .collect();
+        let result = BlastRadiusResult {
+            symbol: self.symbol.clone(),
+            function,
+            location,
+            count: transitive.callers.len(),
+            callers: transitive.callers,
+            files: files.into_iter().collect(),
+            truncated: transitive.truncated,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::project::ResultExclusions;
+
+    const SOURCE: &str = "int parse() { return 0; }\n\
+                          int load() { return parse(); }\n\
+                          int retry() { return load() + retry(); }\n\
+                          int main() { return load() + retry(); }\n";
+
+    fn function(uri: &str, name: &str, line: u32) -> serde_json::Value {
+        let range = serde_json::json!({
+            "start": {"line": line, "character": 4},
+            "end": {"line": line, "character": 4 + name.len()}
+        });
+        serde_json::json!({
+            "name": name, "kind": 12, "detail": "int ()", "uri": uri,
+            "range": range, "selectionRange": range
+        })
+    }
+
+    async fn blast_radius(
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+        location_hint: String,
+        max_depth: Option<u32>,
+    ) -> BlastRadiusResult {
+        let tool = GetBlastRadiusTool {
+            symbol: "parse".to_string(),
+            location_hint: Some(location_hint),
+            max_depth,
+            max_functions: None,
+            build_directory: None,
+            session_id: None,
+            wait_timeout: Some(0),
+        };
+        let result = tool.call_tool(component_session, workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_blast_radius_follows_callers_transitively() {
+        let (_temp_dir, mut workspace) = create_mock_project(&[("src/main.cpp", SOURCE)]);
+        let source = workspace.project_root_path.join("src/main.cpp");
+        let uri = crate::symbol::uri_from_pathbuf(&source).to_string();
+
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/prepareCallHierarchy",
+            serde_json::json!([function(&uri, "parse", 0)]),
+        );
+        let caller_uri = uri.clone();
+        server.respond_with("callHierarchy/incomingCalls", move |params| {
+            let callers: &[(&str, u32)] =
+                match params.as_ref().unwrap()["item"]["name"].as_str().unwrap() {
+                    "parse" => &[("load", 1)],
+                    "load" => &[("retry", 2), ("main", 3)],
+                    "retry" => &[("retry", 2), ("main", 3)],
+                    _ => &[],
+                };
+            serde_json::Value::Array(
+                callers
+                    .iter()
+                    .map(|(name, line)| {
+                        serde_json::json!({
+                            "from": function(&caller_uri, name, *line),
+                            "fromRanges": []
+                        })
+                    })
+                    .collect(),
+            )
+        });
+
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        // Relative hints are resolved against the project root
+        let hint = "src/main.cpp:1:5".to_string();
+
+        // Recursion and callers reached twice are reported once, at their
+        // smallest depth
+        let result = blast_radius(&component_session, &workspace, hint.clone(), None).await;
+        assert_eq!(result.function, "parse");
+        assert_eq!(result.count, 3);
+        let callers: Vec<_> = result
+            .callers
+            .iter()
+            .map(|caller| (caller.name.as_str(), caller.depth, caller.calls.as_str()))
+            .collect();
+        assert_eq!(
+            callers,
+            vec![
+                ("load", 1, "parse"),
+                ("retry", 2, "load"),
+                ("main", 2, "load")
+            ]
+        );
+        assert_eq!(result.files, vec![source.clone()]);
+        assert!(!result.truncated);
+
+        // Depth limit with callers left beyond it
+        let result = blast_radius(&component_session, &workspace, hint.clone(), Some(1)).await;
+        assert_eq!(result.count, 1);
+        assert!(result.truncated);
+
+        // Callers in excluded files are neither reported nor followed
+        workspace.result_exclusions =
+            ResultExclusions::new(vec!["src/**".to_string()], &workspace.project_root_path)
+                .unwrap();
+        let result = blast_radius(&component_session, &workspace, hint, None).await;
+        assert_eq!(result.function, "parse");
+        assert_eq!(result.count, 0);
+        assert!(!result.truncated);
+    }
+}
@@ -0,0 +1,134 @@
+//! Build directory freshness tool
+//!
+//! Reports whether build directories are older than the sources they compile,
+//! so agents can tell when analysis results may be stale and a reconfigure or
+//! rebuild should come first.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::freshness::{BuildFreshness, check_build_freshness};
+use crate::project::{ProjectComponent, ProjectWorkspace};
+
+#[mcp_tool(
+    name = "check_build_freshness",
+    description = "Check whether build directories are up to date with the sources: compares the \
+                   modification times of compile_commands.json and the build system's configure \
+                   output (CMakeCache.txt, meson-info) with the compiled sources and the build \
+                   system files (CMakeLists.txt, *.cmake, meson.build) under the source root.
+
+                   🎯 USE CASES:
+                   • Decide whether to trust analysis results or ask for a reconfigure/rebuild first
+                   • Explain why new files or flags are unknown to clangd
+                   • Check a build directory before a long analysis session
+
+                   📋 OUTPUT (per build directory):
+                   • status:
+                     - fresh: nothing changed since the build directory was configured
+                     - sources_changed: sources were edited since; clangd re-parses them, but
+                       generated headers are only updated by a build
+                     - reconfigure_needed: build system files changed or compiled sources were
+                       removed since; the compilation database is outdated
+                     - unknown: compile_commands.json cannot be read
+                   • reason: Explanation of the status with the recommended action
+                   • compile_commands_modified, configure_modified: When the build directory was configured
+                   • newest_source, newest_build_file: Most recently modified files, with their times
+                   • sources_changed, sources_missing: Compiled sources edited after configuration or deleted
+
+                   INPUT REQUIREMENTS:
+                   • build_directory: Optional - check only this build directory (default: all)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct CheckBuildFreshnessTool {
+    /// Build directory to check. OPTIONAL: all build directories of the
+    /// project are checked when omitted. Relative paths are resolved against
+    /// the project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+}
+
+/// Freshness of one build directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildDirectoryFreshness {
+    pub build_directory: PathBuf,
+    pub provider: String,
+    #[serde(flatten)]
+    pub freshness: BuildFreshness,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildFreshnessResult {
+    pub build_directories: Vec<BuildDirectoryFreshness>,
+}
+
+/// Check a component against the compilation database clangd uses for it
+pub fn component_freshness(
+    workspace: &ProjectWorkspace,
+    component: &ProjectComponent,
+) -> BuildFreshness {
+    check_build_freshness(
+        component,
+        utils::component_compilation_database(workspace, component),
+    )
+}
+
+impl CheckBuildFreshnessTool {
+    #[instrument(name = "check_build_freshness", skip(self, workspace))]
+    pub fn call_tool(&self, workspace: &ProjectWorkspace) -> Result<CallToolResult, CallToolError> {
+        let build_directories: Vec<BuildDirectoryFreshness> =
+            utils::select_components(workspace, self.build_directory.as_deref())?
+                .into_iter()
+                .map(|component| {
+                    let freshness = component_freshness(workspace, component);
+                    info!(
+                        "{}: {:?}",
+                        component.build_dir_path.display(),
+                        freshness.status
+                    );
+                    BuildDirectoryFreshness {
+                        build_directory: component.build_dir_path.clone(),
+                        provider: component.provider_type.clone(),
+                        freshness,
+                    }
+                })
+                .collect();
+
+        let result = BuildFreshnessResult { build_directories };
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::project::freshness::FreshnessStatus;
+
+    #[test]
+    fn test_checks_requested_build_directory() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "")]);
+        std::fs::remove_file(workspace.project_root_path.join("src/main.cpp")).unwrap();
+
+        let tool: CheckBuildFreshnessTool =
+            serde_json::from_value(serde_json::json!({ "build_directory": "build" })).unwrap();
+        let result = tool.call_tool(&workspace).unwrap();
+        let result: BuildFreshnessResult = tool_result(&result);
+        assert_eq!(result.build_directories.len(), 1);
+        let checked = &result.build_directories[0];
+        assert_eq!(checked.provider, "cmake");
+        assert_eq!(checked.freshness.status, FreshnessStatus::ReconfigureNeeded);
+        assert_eq!(checked.freshness.sources_missing, 1);
+
+        let tool: CheckBuildFreshnessTool =
+            serde_json::from_value(serde_json::json!({ "build_directory": "other" })).unwrap();
+        assert!(tool.call_tool(&workspace).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Build target listing tool
+//!
+//! Lists the libraries and executables of each build directory with the
+//! sources compiled into them, giving agents a view of the module boundaries
+//! of the build.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::ProjectWorkspace;
+use crate::project::build_targets::{TargetOrigin, discover_build_targets};
+
+#[mcp_tool(
+    name = "list_build_targets",
+    description = "List the build targets (libraries, executables) of each build directory and the \
+                   source files compiled into them.
+
+                   🎯 USE CASES:
+                   • Understand module boundaries: which sources form which library
+                   • Find the target a file belongs to before changing its dependencies
+                   • Pick the target to rebuild after editing a file
+
+                   🔎 TARGET SOURCES (origin):
+                   • meson_introspection: meson-info/intro-targets.json, with target types
+                   • cmake_file_api: CMake File API codemodel, with target types. CMake writes it
+                     only when the file <build>/.cmake/api/v1/query/codemodel-v2 existed when
+                     configuring
+                   • compilation_database: compile_commands.json entries grouped by the target
+                     directory of their object files; target types are unknown
+
+                   📋 OUTPUT (per build directory):
+                   • origin: Where the targets were taken from (see above)
+                   • targets: Targets sorted by name with target_type, source_count and sources
+                   • ungrouped_sources: Compiled sources whose target could not be determined
+
+                   INPUT REQUIREMENTS:
+                   • build_directory: Optional - list only this build directory (default: all)
+                   • target: Optional - report only the target with this name
+                   • include_sources: Optional boolean - list source files (default: true)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ListBuildTargetsTool {
+    /// Build directory to list. OPTIONAL: all build directories of the project
+    /// are listed when omitted. Relative paths are resolved against the
+    /// project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Name of the target to report. OPTIONAL: all targets are reported when
+    /// omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Whether to list the source files of each target. OPTIONAL, default
+    /// true. Set to false for an overview of large projects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_sources: Option<bool>,
+}
+
+/// A target as reported by the tool
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TargetView {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub target_type: Option<String>,
+    pub source_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sources: Option<Vec<PathBuf>>,
+}
+
+/// Targets of one build directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildDirectoryTargets {
+    pub build_directory: PathBuf,
+    pub provider: String,
+    pub origin: TargetOrigin,
+    pub targets: Vec<TargetView>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ungrouped_sources: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildTargetsResult {
+    pub build_directories: Vec<BuildDirectoryTargets>,
+}
+
+impl ListBuildTargetsTool {
+    #[instrument(name = "list_build_targets", skip(self, workspace))]
+    pub fn call_tool(&self, workspace: &ProjectWorkspace) -> Result<CallToolResult, CallToolError> {
+        let include_sources = self.include_sources.unwrap_or(true);
+        let mut build_directories = Vec::new();
+        for component in utils::select_components(workspace, self.build_directory.as_deref())? {
+            let compile_commands = utils::component_compilation_database(workspace, component);
+            let discovered = discover_build_targets(component, compile_commands)
+                .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+            info!(
+                "{}: {} targets from {:?}",
+                component.build_dir_path.display(),
+                discovered.targets.len(),
+                discovered.origin
+            );
+
+            let targets = discovered
+                .targets
+                .into_iter()
+                .filter(|target| self.target.as_ref().is_none_or(|name| target.name == *name))
+                .map(|target| TargetView {
+                    name: target.name,
+                    target_type: target.target_type,
+                    source_count: target.sources.len(),
+                    sources: include_sources.then_some(target.sources),
+                })
+                .collect();
+            build_directories.push(BuildDirectoryTargets {
+                build_directory: component.build_dir_path.clone(),
+                provider: component.provider_type.clone(),
+                origin: discovered.origin,
+                targets,
+                ungrouped_sources: if include_sources && self.target.is_none() {
+                    discovered.ungrouped_sources
+                } else {
+                    Vec::new()
+                },
+            });
+        }
+
+        let result = BuildTargetsResult { build_directories };
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
@@ -0,0 +1,326 @@
+//! clang-tidy diagnostics tool
+//!
+//! Collects the clang-tidy findings clangd reports for a file and groups them
+//! by check name, separately from regular compiler diagnostics.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::diagnostics::{
+    clang_tidy_check_name, get_file_diagnostics, severity_name,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+/// Default time to wait for clangd to publish diagnostics for a file
+const DEFAULT_DIAGNOSTICS_WAIT_TIMEOUT_SECS: u64 = 10;
+
+#[mcp_tool(
+    name = "clang_tidy",
+    description = "Focused clang-tidy lint report for a C++ file. Opens the file in clangd, collects only \
+                   the diagnostics produced by clang-tidy (compiler errors and warnings are excluded) and \
+                   groups them by check name with counts and locations.
+
+                   🎯 PRIMARY USE CASES:
+                   Code-quality passes • Modernization sweeps • Reviewing lint findings per check
+
+                   ⚙️ CHECK CONFIGURATION:
+                   clangd runs the checks configured in the project's .clang-tidy files or in the
+                   Diagnostics.ClangTidy section of .clangd config. This tool cannot enable other
+                   checks: clangd ignores the retired --clang-tidy-checks flag and reads checks only
+                   from those files, so add checks there (e.g. 'Diagnostics: {ClangTidy: {Add:
+                   [modernize-*]}}' in .clangd) before linting. The optional 'check_filter'
+                   parameter narrows the report to the findings of matching check names (glob
+                   patterns such as 'modernize-*').
+
+                   📋 OUTPUT:
+                   • total_findings: Number of clang-tidy diagnostics reported
+                   • checks: Findings grouped per check name, sorted by count (highest first),
+                     each with severity, count and locations with messages
+                   • diagnostics_received: false when clangd did not publish diagnostics in time
+                   • note: Reminder of where checks are configured when nothing was found
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file to lint (absolute or project-relative)
+                   • check_filter: Optional list of check name patterns to report
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional seconds to wait for diagnostics (default: 10s)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ClangTidyTool {
+    /// File to collect clang-tidy findings for. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Check name patterns whose findings are reported. OPTIONAL.
+    ///
+    /// FORMAT: Exact check names or glob patterns with '*' wildcards:
+    /// • "modernize-use-nullptr"
+    /// • "readability-*", "*-magic-numbers"
+    ///
+    /// BEHAVIOR: Filters the findings of the checks clangd already runs, as configured
+    /// in .clang-tidy or .clangd files; checks that are not configured are not enabled.
+    /// When omitted, all clang-tidy findings are reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_filter: Option<Vec<String>>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for clangd to publish diagnostics (default: 10s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A single clang-tidy finding
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TidyFinding {
+    pub location: FileLocation,
+    pub message: String,
+}
+
+/// Findings of one clang-tidy check
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TidyCheckGroup {
+    /// Check name, e.g. "modernize-use-nullptr"
+    pub check: String,
+    /// Severity reported by clangd ("error", "warning", "information", "hint")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    pub count: usize,
+    pub findings: Vec<TidyFinding>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClangTidyResult {
+    pub file: PathBuf,
+    pub total_findings: usize,
+    pub checks: Vec<TidyCheckGroup>,
+    pub diagnostics_received: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+}
+
+impl ClangTidyTool {
+    /// Compile the requested check name patterns, `None` when all checks are reported
+    fn check_filter(&self) -> Result<Option<GlobSet>, CallToolError> {
+        let Some(patterns) = &self.check_filter else {
+            return Ok(None);
+        };
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern.trim()).map_err(|e| {
+                CallToolError::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid check_filter pattern '{}': {}", pattern, e),
+                ))
+            })?;
+            builder.add(glob);
+        }
+        builder
+            .build()
+            .map(Some)
+            .map_err(|e| CallToolError::new(std::io::Error::other(e)))
+    }
+
+    /// Group clang-tidy diagnostics by check name, keeping the checks matching `filter`
+    fn group_findings(
+        file_path: &std::path::Path,
+        diagnostics: &[lsp_types::Diagnostic],
+        filter: Option<&GlobSet>,
+    ) -> Vec<TidyCheckGroup> {
+        let mut groups: BTreeMap<String, TidyCheckGroup> = BTreeMap::new();
+
+        for diagnostic in diagnostics {
+            let Some(check) = clang_tidy_check_name(diagnostic) else {
+                continue;
+            };
+            if filter.is_some_and(|filter| !filter.is_match(&check)) {
+                continue;
+            }
+
+            let group = groups
+                .entry(check.clone())
+                .or_insert_with(|| TidyCheckGroup {
+                    check,
+                    severity: diagnostic.severity.map(severity_name),
+                    count: 0,
+                    findings: Vec::new(),
+                });
+            group.count += 1;
+            group.findings.push(TidyFinding {
+                location: FileLocation {
+                    file_path: file_path.to_path_buf(),
+                    range: diagnostic.range.into(),
+                },
+                message: diagnostic.message.clone(),
+            });
+        }
+
+        let mut groups: Vec<TidyCheckGroup> = groups.into_values().collect();
+        groups.sort_by_key(|group| std::cmp::Reverse(group.count));
+        groups
+    }
+
+    #[instrument(name = "clang_tidy", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let filter = self.check_filter()?;
+
+        info!(
+            "Collecting clang-tidy findings for {} (check filter: {:?})",
+            file_path.display(),
+            self.check_filter
+        );
+
+        let timeout = Duration::from_secs(
+            self.wait_timeout
+                .unwrap_or(DEFAULT_DIAGNOSTICS_WAIT_TIMEOUT_SECS),
+        );
+        let diagnostics = get_file_diagnostics(&file_path, &component_session, timeout).await?;
+
+        let diagnostics_received = diagnostics.is_some();
+        let checks = Self::group_findings(
+            &file_path,
+            &diagnostics.unwrap_or_default(),
+            filter.as_ref(),
+        );
+
+        let total_findings = checks.iter().map(|group| group.count).sum();
+        let note = (diagnostics_received && total_findings == 0).then(|| {
+            "No clang-tidy findings. clangd only runs the checks enabled in .clang-tidy files \
+             or the Diagnostics.ClangTidy section of .clangd config, and this tool cannot enable \
+             others."
+                .to_string()
+        });
+
+        let result = ClangTidyResult {
+            file: file_path,
+            total_findings,
+            checks,
+            diagnostics_received,
+            note,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tidy_diagnostic(check: &str, line: u32) -> lsp_types::Diagnostic {
+        lsp_types::Diagnostic {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(line, 0),
+                lsp_types::Position::new(line, 4),
+            ),
+            severity: Some(lsp_types::DiagnosticSeverity::WARNING),
+            code: Some(lsp_types::NumberOrString::String(check.to_string())),
+            source: Some("clang-tidy".to_string()),
+            message: format!("{} triggered", check),
+            ..Default::default()
+        }
+    }
+
+    fn filter(check_filter: Option<Vec<&str>>) -> Option<GlobSet> {
+        ClangTidyTool {
+            file: "/src/main.cpp".to_string(),
+            check_filter: check_filter.map(|c| c.into_iter().map(String::from).collect()),
+            build_directory: None,
+            session_id: None,
+            wait_timeout: None,
+        }
+        .check_filter()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_filter() {
+        assert!(filter(None).is_none());
+        let checks = filter(Some(vec![
+            "modernize-use-nullptr",
+            "*-magic-numbers",
+            "cert-*-c",
+        ]))
+        .unwrap();
+        assert!(checks.is_match("modernize-use-nullptr"));
+        assert!(!checks.is_match("modernize-use"));
+        assert!(checks.is_match("readability-magic-numbers"));
+        assert!(checks.is_match("cert-err33-c"));
+        assert!(!checks.is_match("readability-identifier-naming"));
+        assert!(filter(Some(vec!["*"])).unwrap().is_match("anything"));
+    }
+
+    #[test]
+    fn test_group_findings_excludes_compiler_diagnostics() {
+        let compiler_error = lsp_types::Diagnostic {
+            severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+            source: Some("clang".to_string()),
+            message: "use of undeclared identifier".to_string(),
+            ..Default::default()
+        };
+        let diagnostics = vec![
+            tidy_diagnostic("modernize-use-nullptr", 1),
+            compiler_error,
+            tidy_diagnostic("readability-magic-numbers", 2),
+            tidy_diagnostic("modernize-use-nullptr", 3),
+        ];
+
+        let groups = ClangTidyTool::group_findings(
+            std::path::Path::new("/src/main.cpp"),
+            &diagnostics,
+            None,
+        );
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].check, "modernize-use-nullptr");
+        assert_eq!(groups[0].count, 2);
+        assert_eq!(groups[0].severity.as_deref(), Some("warning"));
+        assert_eq!(groups[1].check, "readability-magic-numbers");
+        assert_eq!(groups[1].count, 1);
+    }
+
+    #[test]
+    fn test_group_findings_filters_checks() {
+        let diagnostics = vec![
+            tidy_diagnostic("modernize-use-nullptr", 1),
+            tidy_diagnostic("readability-magic-numbers", 2),
+        ];
+
+        let groups = ClangTidyTool::group_findings(
+            std::path::Path::new("/src/main.cpp"),
+            &diagnostics,
+            filter(Some(vec!["readability-*"])).as_ref(),
+        );
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].check, "readability-magic-numbers");
+    }
+}
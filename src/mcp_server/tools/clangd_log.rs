@@ -0,0 +1,181 @@
+//! Clangd log tail tool
+//!
+//! Returns the most recent lines clangd wrote to stderr. When a query comes back
+//! empty, clangd usually logged why (a missing compile command, a failed
+//! preamble build, a crash in a worker); this tool makes those lines available
+//! to clients that cannot read the server's log files.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::clangd::log_monitor::{LOG_TAIL_CAPACITY, LogSeverity};
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::ComponentSession;
+
+/// Default number of lines returned
+const DEFAULT_LOG_LINES: u32 = 100;
+
+#[mcp_tool(
+    name = "get_clangd_log",
+    description = "Return the most recent lines of the clangd server log (its stderr output) for a \
+                   build directory, optionally keeping only errors or other severities.
+
+                   🎯 PRIMARY USE CASES:
+                   Diagnosing empty or surprising results • Finding files clangd could not \
+                   build (missing compile commands, failed preambles) • Checking what clangd \
+                   was doing before a timeout
+
+                   📋 OUTPUT:
+                   • lines: Log lines, oldest first. clangd prefixes each message with its \
+                     severity and time, e.g. \"E[12:01:02.345] ...\"
+                   • returned / buffered: Lines returned and lines currently kept by the server \
+                     (the server keeps the last 2000)
+
+                   INPUT REQUIREMENTS:
+                   • lines: Optional number - lines to return (default: 100, max: 2000)
+                   • severity: Optional - minimum severity: error, warning, info, verbose or debug \
+                     (default: all lines)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetClangdLogTool {
+    /// Number of most recent lines to return (default: 100, max: 2000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<u32>,
+
+    /// Minimum severity of the returned lines: "error", "warning", "info",
+    /// "verbose" or "debug". OPTIONAL - all lines are returned by default.
+    ///
+    /// Lines without a severity prefix continue the preceding message and are
+    /// returned with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClangdLogResult {
+    /// Minimum severity applied, when filtered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<LogSeverity>,
+    /// Number of lines returned
+    pub returned: u32,
+    /// Number of lines currently kept by the server
+    pub buffered: u32,
+    /// Log lines, oldest first
+    pub lines: Vec<String>,
+}
+
+impl GetClangdLogTool {
+    #[instrument(name = "get_clangd_log", skip(self, component_session))]
+    pub async fn call_tool(
+        &self,
+        component_session: &ComponentSession,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Fetching clangd log tail: lines={:?}, severity={:?}",
+            self.lines, self.severity
+        );
+
+        let severity = self
+            .severity
+            .as_deref()
+            .map(str::parse::<LogSeverity>)
+            .transpose()
+            .map_err(|e| {
+                CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+            })?;
+        let limit = (self.lines.unwrap_or(DEFAULT_LOG_LINES) as usize).min(LOG_TAIL_CAPACITY);
+
+        let (lines, buffered) = {
+            let session = component_session.lsp_session().await;
+            let monitor = session.log_monitor();
+            (
+                monitor.recent_lines(limit, severity),
+                monitor.buffered_lines(),
+            )
+        };
+
+        let result = ClangdLogResult {
+            severity,
+            returned: lines.len() as u32,
+            buffered: buffered as u32,
+            lines,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_get_clangd_log_tail_with_severity() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        {
+            let session = component_session.lsp_session().await;
+            let monitor = session.log_monitor();
+            monitor.process_line("I[10:00:00.000] clangd version 18.1.3");
+            monitor.process_line(
+                "E[10:00:01.000] Could not build CompilerInvocation for file /src/b.cpp",
+            );
+            monitor.process_line("V[10:00:02.000] ASTWorker building file /src/main.cpp");
+        }
+
+        let call = |lines: Option<u32>, severity: Option<&str>| {
+            let tool = GetClangdLogTool {
+                lines,
+                severity: severity.map(str::to_string),
+                build_directory: None,
+                session_id: None,
+            };
+            let component_session = &component_session;
+            async move { tool.call_tool(component_session).await }
+        };
+
+        let result = call(Some(2), None).await.unwrap();
+        let log: ClangdLogResult = tool_result(&result);
+        assert_eq!(log.buffered, 3);
+        assert_eq!(log.returned, 2);
+        assert!(log.lines[0].starts_with("E["));
+        assert!(log.lines[1].starts_with("V["));
+
+        let result = call(None, Some("error")).await.unwrap();
+        let log: ClangdLogResult = tool_result(&result);
+        assert_eq!(log.severity, Some(LogSeverity::Error));
+        assert_eq!(
+            log.lines,
+            vec!["E[10:00:01.000] Could not build CompilerInvocation for file /src/b.cpp"]
+        );
+
+        assert!(call(None, Some("fatal")).await.is_err());
+    }
+}
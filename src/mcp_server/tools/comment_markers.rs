@@ -0,0 +1,490 @@
+//! Comment marker listing tool
+//!
+//! Lists TODO/FIXME-style markers of a file for codebase triage. Markers are only
+//! searched inside comments, so occurrences in string literals or identifiers are
+//! not reported. Comment ranges come from clangd's `comment` semantic tokens,
+//! complemented by a literal-aware scan of the comment syntax because clangd does
+//! not report every comment as a semantic token.
+
+use regex::Regex;
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::read_source_file;
+use crate::lsp::PositionEncoding;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::semantic_tokens::{
+    DecodedSemanticToken, get_semantic_tokens,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Markers searched when none are requested
+pub const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX"];
+
+#[mcp_tool(
+    name = "find_comment_markers",
+    description = "List TODO, FIXME and other comment markers of a file. Only comments are searched: \
+                   comment ranges come from clangd semantic tokens and a literal-aware comment scan, \
+                   so markers inside string literals, identifiers or code are not reported.
+
+                   🎯 PRIMARY USE CASES:
+                   Codebase triage • Finding known issues and workarounds near code being changed \
+                   • Collecting follow-up work left in a file
+
+                   📋 OUTPUT:
+                   • file: File that was searched
+                   • searched_markers: Markers that were looked for
+                   • total_count / counts: Number of markers found, overall and per marker
+                   • entries: Each marker with 1-based line and column, the marker and the comment
+                     text starting at the marker (e.g. \"TODO(bob): handle overflow\")
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • markers: Optional - markers to search, default TODO, FIXME, HACK, XXX
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindCommentMarkersTool {
+    /// File to search. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Markers to search for, matched case-sensitively as whole words. OPTIONAL.
+    ///
+    /// DEFAULT: ["TODO", "FIXME", "HACK", "XXX"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markers: Option<Vec<String>>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// A marker found in a comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentMarker {
+    /// Marker that was matched
+    pub marker: String,
+    /// 1-based line of the marker
+    pub line: u32,
+    /// 1-based column of the marker, in characters
+    pub column: u32,
+    /// Comment text from the marker to the end of the comment line
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentMarkersResult {
+    /// File that was searched
+    pub file: PathBuf,
+    /// Markers that were looked for
+    pub searched_markers: Vec<String>,
+    /// Number of markers found
+    pub total_count: usize,
+    /// Number of markers found per marker
+    pub counts: BTreeMap<String, usize>,
+    /// Markers in source order
+    pub entries: Vec<CommentMarker>,
+}
+
+impl FindCommentMarkersTool {
+    #[instrument(
+        name = "find_comment_markers",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let markers: Vec<String> = match &self.markers {
+            Some(markers) => markers
+                .iter()
+                .map(|marker| marker.trim().to_string())
+                .filter(|marker| !marker.is_empty())
+                .collect(),
+            None => DEFAULT_MARKERS.iter().map(|m| m.to_string()).collect(),
+        };
+        if markers.is_empty() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "At least one non-empty marker is required",
+            )));
+        }
+
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        info!(
+            "Searching {} for comment markers {:?}",
+            file_path.display(),
+            markers
+        );
+
+        let content = read_source_file(&file_path).map_err(|e| {
+            CallToolError::new(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read '{}': {}", file_path.display(), e),
+            ))
+        })?;
+
+        let tokens = get_semantic_tokens(&file_path, &component_session)
+            .await
+            .map_err(CallToolError::from)?;
+        let position_encoding = component_session.position_encoding().await;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut segments = scan_comments(&content);
+        segments.extend(comment_token_segments(&tokens, &lines, position_encoding));
+        let entries = find_markers(&lines, merge_segments(segments), &markers);
+
+        let mut counts = BTreeMap::new();
+        for entry in &entries {
+            *counts.entry(entry.marker.clone()).or_insert(0) += 1;
+        }
+
+        let result = CommentMarkersResult {
+            file: file_path,
+            searched_markers: markers,
+            total_count: entries.len(),
+            counts,
+            entries,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+// ============================================================================
+// Comment Ranges
+// ============================================================================
+
+/// Part of a comment on a single line, as byte offsets into the line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct CommentSegment {
+    /// 0-based line
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Lexer state of `scan_comments`
+enum ScanState {
+    Code,
+    LineComment,
+    BlockComment,
+    /// String or character literal, closed by the given quote
+    Literal(u8),
+    /// Raw string literal, closed by the given `)delimiter"` terminator
+    RawString(String),
+}
+
+/// Identifier-like run of characters ending right before `end`
+fn preceding_word(bytes: &[u8], end: usize) -> &[u8] {
+    let start = bytes[..end]
+        .iter()
+        .rposition(|b| !(b.is_ascii_alphanumeric() || *b == b'_' || *b == b'\''))
+        .map_or(0, |index| index + 1);
+    &bytes[start..end]
+}
+
+/// Find the comments of C/C++ source text
+///
+/// String, character and raw string literals are skipped, so comment syntax
+/// inside them is not mistaken for a comment. Line comments continued with a
+/// trailing backslash extend to the next line.
+fn scan_comments(source: &str) -> Vec<CommentSegment> {
+    let bytes = source.as_bytes();
+    let mut segments = Vec::new();
+    let mut state = ScanState::Code;
+    let mut line = 0;
+    let mut line_start = 0;
+    let mut segment_start = 0;
+    let mut i = 0;
+
+    // Close the comment segment of the current line at byte `end`
+    let mut push_segment = |line: usize, line_start: usize, start: usize, mut end: usize| {
+        if end > start && bytes[end - 1] == b'\r' {
+            end -= 1;
+        }
+        if end > start {
+            segments.push(CommentSegment {
+                line,
+                start: start - line_start,
+                end: end - line_start,
+            });
+        }
+    };
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let next = bytes.get(i + 1).copied();
+
+        match &state {
+            ScanState::Code => match (byte, next) {
+                (b'/', Some(b'/')) => {
+                    state = ScanState::LineComment;
+                    segment_start = i;
+                    i += 1;
+                }
+                (b'/', Some(b'*')) => {
+                    state = ScanState::BlockComment;
+                    segment_start = i;
+                    i += 1;
+                }
+                (b'"', _) => {
+                    let prefix = preceding_word(bytes, i);
+                    let is_raw = matches!(prefix, b"R" | b"u8R" | b"uR" | b"UR" | b"LR");
+                    let open = bytes[i + 1..].iter().position(|b| *b == b'(');
+                    match open.filter(|_| is_raw) {
+                        Some(open) => {
+                            let delimiter = &source[i + 1..i + 1 + open];
+                            state = ScanState::RawString(format!("){delimiter}\""));
+                            i += open + 1;
+                        }
+                        None => state = ScanState::Literal(b'"'),
+                    }
+                }
+                (b'\'', _) => {
+                    // C++14 digit separators (1'000'000) do not start a literal
+                    let word = preceding_word(bytes, i);
+                    if !word.first().is_some_and(u8::is_ascii_digit) {
+                        state = ScanState::Literal(b'\'');
+                    }
+                }
+                _ => {}
+            },
+            ScanState::LineComment => {
+                if byte == b'\n' {
+                    push_segment(line, line_start, segment_start, i);
+                    let continued = bytes[segment_start..i]
+                        .iter()
+                        .rev()
+                        .find(|b| **b != b'\r')
+                        .is_some_and(|b| *b == b'\\');
+                    segment_start = i + 1;
+                    if !continued {
+                        state = ScanState::Code;
+                    }
+                }
+            }
+            ScanState::BlockComment => {
+                if byte == b'*' && next == Some(b'/') {
+                    push_segment(line, line_start, segment_start, i + 2);
+                    state = ScanState::Code;
+                    i += 1;
+                } else if byte == b'\n' {
+                    push_segment(line, line_start, segment_start, i);
+                    segment_start = i + 1;
+                }
+            }
+            ScanState::Literal(quote) => {
+                if byte == b'\\' && next.is_some_and(|b| b != b'\n') {
+                    i += 1;
+                } else if byte == *quote || byte == b'\n' {
+                    state = ScanState::Code;
+                }
+            }
+            ScanState::RawString(terminator) => {
+                if bytes[i..].starts_with(terminator.as_bytes()) {
+                    i += terminator.len() - 1;
+                    state = ScanState::Code;
+                }
+            }
+        }
+
+        if bytes.get(i) == Some(&b'\n') {
+            line += 1;
+            line_start = i + 1;
+        }
+        i += 1;
+    }
+
+    if matches!(state, ScanState::LineComment | ScanState::BlockComment) {
+        push_segment(line, line_start, segment_start, bytes.len());
+    }
+
+    segments
+}
+
+/// Comment ranges reported by clangd as `comment` semantic tokens
+fn comment_token_segments(
+    tokens: &[DecodedSemanticToken],
+    lines: &[&str],
+    position_encoding: PositionEncoding,
+) -> Vec<CommentSegment> {
+    tokens
+        .iter()
+        .filter(|token| token.token_type == lsp_types::SemanticTokenType::COMMENT.as_str())
+        .filter_map(|token| {
+            let text = lines.get(token.line as usize)?;
+            let start = position_encoding.byte_offset(text, token.start)?;
+            let end = position_encoding
+                .byte_offset(text, token.start + token.length)
+                .unwrap_or(text.len());
+            Some(CommentSegment {
+                line: token.line as usize,
+                start,
+                end,
+            })
+        })
+        .collect()
+}
+
+/// Sort segments and merge overlapping ones on the same line
+fn merge_segments(mut segments: Vec<CommentSegment>) -> Vec<CommentSegment> {
+    segments.sort();
+    let mut merged: Vec<CommentSegment> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if last.line == segment.line && segment.start <= last.end => {
+                last.end = last.end.max(segment.end);
+            }
+            _ => merged.push(segment),
+        }
+    }
+    merged
+}
+
+/// Find the first marker of every comment segment
+fn find_markers(
+    lines: &[&str],
+    segments: Vec<CommentSegment>,
+    markers: &[String],
+) -> Vec<CommentMarker> {
+    let alternatives: Vec<String> = markers.iter().map(|m| regex::escape(m)).collect();
+    let pattern = Regex::new(&format!(
+        r"(?:^|[^A-Za-z0-9_])({})(?:$|[^A-Za-z0-9_])",
+        alternatives.join("|")
+    ))
+    .expect("escaped markers form a valid pattern");
+
+    segments
+        .into_iter()
+        .filter_map(|segment| {
+            let line_text = lines.get(segment.line)?;
+            let comment = line_text.get(segment.start..segment.end)?;
+            let marker = pattern.captures(comment)?.get(1)?;
+
+            let text = comment[marker.start()..].trim_end();
+            let text = text.strip_suffix("*/").unwrap_or(text).trim_end();
+            let column_offset = segment.start + marker.start();
+
+            Some(CommentMarker {
+                marker: marker.as_str().to_string(),
+                line: segment.line as u32 + 1,
+                column: PositionEncoding::Utf32.column(line_text, column_offset) + 1,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    fn markers_of(source: &str) -> Vec<(u32, String)> {
+        let lines: Vec<&str> = source.lines().collect();
+        let markers: Vec<String> = DEFAULT_MARKERS.iter().map(|m| m.to_string()).collect();
+        find_markers(&lines, merge_segments(scan_comments(source)), &markers)
+            .into_iter()
+            .map(|entry| (entry.line, entry.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_skips_literals() {
+        let source = "const char* s = \"// TODO: not a comment\";\n\
+                      char c = '\"'; int n = 1'000; // FIXME: real one\n\
+                      auto r = R\"x(/* HACK: raw */)x\"; /* XXX: block\n\
+                      \x20* TODO(bob): second line */\n\
+                      int TODO_count = 0; // TODOS are not markers\n";
+        assert_eq!(
+            markers_of(source),
+            vec![
+                (2, "FIXME: real one".to_string()),
+                (3, "XXX: block".to_string()),
+                (4, "TODO(bob): second line".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_continued_line_comment() {
+        let source = "// note \\\n   TODO: continued\nint x; // ok\n";
+        assert_eq!(markers_of(source), vec![(2, "TODO: continued".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_find_comment_markers_with_semantic_tokens() {
+        let source = "#if 0\n\
+                      TODO: inactive note\n\
+                      #endif\n\
+                      const char* s = \"FIXME in string\";\n\
+                      int größe = 0; // HACK: umlaut before\n";
+        let (_temp_dir, workspace) = create_mock_project(&[("src/notes.cpp", source)]);
+        let server = MockClangd::new();
+        server.respond(
+            "initialize",
+            serde_json::json!({
+                "capabilities": {
+                    "semanticTokensProvider": {
+                        "legend": {"tokenTypes": ["variable", "comment"], "tokenModifiers": []},
+                        "full": true
+                    }
+                }
+            }),
+        );
+        // Line 1 is an inactive preprocessor region reported as a comment token
+        server.respond(
+            "textDocument/semanticTokens/full",
+            serde_json::json!({"data": [1, 0, 19, 1, 0, 2, 12, 1, 0, 0]}),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = FindCommentMarkersTool {
+            file: "src/notes.cpp".to_string(),
+            markers: None,
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let markers: CommentMarkersResult = tool_result(&result);
+        assert_eq!(markers.total_count, 2);
+        assert_eq!(
+            markers.entries[0],
+            CommentMarker {
+                marker: "TODO".to_string(),
+                line: 2,
+                column: 1,
+                text: "TODO: inactive note".to_string(),
+            }
+        );
+        assert_eq!(markers.entries[1].marker, "HACK");
+        assert_eq!(markers.entries[1].column, 19);
+        assert_eq!(markers.counts.get("FIXME"), None);
+    }
+}
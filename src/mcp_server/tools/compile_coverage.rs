@@ -0,0 +1,260 @@
+//! Compilation database coverage tool
+//!
+//! Reports how clangd obtains the compile command of a file: from its own
+//! compilation database entry, from a translation unit that includes it, or
+//! from guessed fallback flags. The answer explains why analysis quality
+//! differs between files.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::find_includers;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Include depth followed from each translation unit
+const MAX_DEPTH: u32 = 50;
+
+/// Maximum number of files in the include graph of one translation unit
+const MAX_NODES: usize = 2000;
+
+/// Maximum number of including translation units listed in the result
+const MAX_LISTED_TRANSLATION_UNITS: usize = 10;
+
+#[mcp_tool(
+    name = "check_compile_coverage",
+    description = "Check how a C++ file is covered by the compilation database, which decides how \
+                   well clangd can analyze it. Files with their own entry are parsed with their real \
+                   compile command. Headers are parsed with the command of a translation unit that \
+                   includes them. Any other file is parsed with guessed fallback flags, so includes, \
+                   macros and symbols may be missing from its results.
+
+                   🎯 USE CASES:
+                   • Explain missing or wrong results for a file before querying it further
+                   • Check that a new source file has been picked up by the build system
+                   • Find the exact compile command (defines, include paths, standard) of a file
+
+                   📋 OUTPUT:
+                   • coverage: compilation_database, included_by_translation_unit or fallback
+                   • compile_command: Directory and arguments of the file's own entry
+                   • translation_units: Translation units including the file (first 10)
+                   • translation_units_total: Number of translation units including the file
+                   • incomplete: An include graph hit its depth or size limit, includers may be missing
+                   • note: What the coverage means for analysis quality
+
+                   Includes are followed textually: #if/#ifdef conditions are not evaluated.
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct CheckCompileCoverageTool {
+    /// File to check. Absolute paths are used as-is, relative paths are
+    /// resolved against the project root.
+    pub file: String,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Where clangd takes the compile command of a file from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompileCoverage {
+    /// The file has its own compilation database entry
+    CompilationDatabase,
+    /// The file is a header reached from at least one translation unit
+    IncludedByTranslationUnit,
+    /// Neither: clangd falls back to guessed flags
+    Fallback,
+}
+
+impl CompileCoverage {
+    /// What the coverage means for analysis quality
+    fn note(self) -> &'static str {
+        match self {
+            CompileCoverage::CompilationDatabase => {
+                "Parsed with its own compile command; analysis is complete."
+            }
+            CompileCoverage::IncludedByTranslationUnit => {
+                "Parsed with the compile command of an including translation unit; analysis is \
+                 complete for code that unit enables, other #if branches may be missing."
+            }
+            CompileCoverage::Fallback => {
+                "Not in the compilation database and not included by any translation unit; \
+                 clangd guesses its flags, so includes, macros and symbols may be missing."
+            }
+        }
+    }
+}
+
+/// Compile command of a compilation database entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompileCommand {
+    /// Working directory of the compiler
+    pub directory: PathBuf,
+    /// Compiler invocation, response files expanded
+    pub arguments: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompileCoverageResult {
+    /// File that was checked
+    pub file: PathBuf,
+    pub coverage: CompileCoverage,
+    /// Compile command of the file's own entry
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub compile_command: Option<CompileCommand>,
+    /// Translation units including the file directly or transitively
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub translation_units: Vec<PathBuf>,
+    /// Number of translation units including the file
+    pub translation_units_total: usize,
+    /// Whether an include graph hit its depth or node limit
+    pub incomplete: bool,
+    pub note: String,
+}
+
+impl CheckCompileCoverageTool {
+    #[instrument(
+        name = "check_compile_coverage",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        let compilation_database = component_session.compilation_database();
+        let compile_command = compilation_database
+            .find_entry_for_file(&file_path)
+            .map(|entry| CompileCommand {
+                directory: entry.directory.clone(),
+                arguments: entry.arguments.clone(),
+            });
+
+        let entries = compilation_database.entries().to_vec();
+        let project_root = workspace
+            .project_root_path
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.project_root_path.clone());
+        let file = file_path.clone();
+        // Reads every header of every translation unit; keep it off the async workers
+        let search = tokio::task::spawn_blocking(move || {
+            find_includers(&entries, &file, &project_root, MAX_DEPTH, MAX_NODES)
+        })
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+        let coverage = if compile_command.is_some() {
+            CompileCoverage::CompilationDatabase
+        } else if !search.translation_units.is_empty() {
+            CompileCoverage::IncludedByTranslationUnit
+        } else {
+            CompileCoverage::Fallback
+        };
+        info!(
+            "Compile coverage of {}: {:?}",
+            file_path.display(),
+            coverage
+        );
+
+        let translation_units_total = search.translation_units.len();
+        let result = CompileCoverageResult {
+            file: file_path,
+            coverage,
+            compile_command,
+            translation_units: search
+                .translation_units
+                .into_iter()
+                .take(MAX_LISTED_TRANSLATION_UNITS)
+                .collect(),
+            translation_units_total,
+            incomplete: search.incomplete,
+            note: coverage.note().to_string(),
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    async fn check(
+        server: &MockClangd,
+        workspace: &ProjectWorkspace,
+        file: &str,
+    ) -> CompileCoverageResult {
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool: CheckCompileCoverageTool =
+            serde_json::from_value(serde_json::json!({ "file": file })).unwrap();
+        let result = tool.call_tool(component_session, workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_reports_coverage_of_sources_and_headers() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "#include \"shapes.h\"\n")]);
+        // Headers without compilation database entries
+        let src = workspace.project_root_path.join("src");
+        std::fs::write(src.join("shapes.h"), "").unwrap();
+        std::fs::write(src.join("orphan.h"), "").unwrap();
+        let server = MockClangd::new();
+
+        let source = check(&server, &workspace, "src/main.cpp").await;
+        assert_eq!(source.coverage, CompileCoverage::CompilationDatabase);
+        let command = source.compile_command.unwrap();
+        assert!(
+            command
+                .arguments
+                .iter()
+                .any(|arg| arg.ends_with("main.cpp"))
+        );
+
+        let header = check(&server, &workspace, "src/shapes.h").await;
+        assert_eq!(header.coverage, CompileCoverage::IncludedByTranslationUnit);
+        assert_eq!(header.translation_units_total, 1);
+        assert!(header.translation_units[0].ends_with("src/main.cpp"));
+        assert!(header.compile_command.is_none());
+
+        let orphan = check(&server, &workspace, "src/orphan.h").await;
+        assert_eq!(orphan.coverage, CompileCoverage::Fallback);
+        assert!(orphan.translation_units.is_empty());
+    }
+}
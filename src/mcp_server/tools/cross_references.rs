@@ -0,0 +1,452 @@
+//! Cross-references tool
+//!
+//! Answers "does A use B" for two symbols: the references of each symbol are
+//! filtered down to those inside the other one's definition, whose range is
+//! taken from the document symbol at its definition site. Unlike listing all
+//! references, only the sites that couple the two symbols are returned.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::propagate_source_overlay_blocking;
+use crate::io::file_manager::RealFileBufferManager;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    DocumentSymbolIterator, get_document_symbols,
+};
+use crate::mcp_server::tools::lsp_helpers::examples::get_references;
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, FileLocationWithContents};
+
+#[mcp_tool(
+    name = "find_cross_references",
+    description = "Determine whether one C++ symbol references another and return the exact \
+                   reference sites. Checks both directions: the uses of other_symbol inside the \
+                   definition of symbol, and the uses of symbol inside the definition of \
+                   other_symbol.
+
+                   🎯 PRIMARY USE CASES:
+                   \"Does function A call or use B?\" • Coupling and dependency analysis between \
+                   two functions, classes or namespaces • Checking a refactoring removed every \
+                   use of a symbol from a function
+
+                   📋 OUTPUT:
+                   • symbol / other_symbol: Each symbol with its definition location and the \
+                     range of its definition (body), when it has one
+                   • symbol_references_other: true when other_symbol is used inside the \
+                     definition of symbol
+                   • sites: Those uses, each with location (1-based) and the trimmed source line
+                   • other_references_symbol / reverse_sites: The same for the opposite direction
+                   • note: Present when a symbol has no definition with a body to search in
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required name of the symbol whose definition is searched, preferably qualified
+                   • other_symbol: Required name of the symbol looked for
+                   • location_hint / other_location_hint: Optional strings - exact symbol locations (format: \"/path/file.cpp:line:column\")
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindCrossReferencesTool {
+    /// Symbol whose definition is searched for uses of other_symbol, e.g. a
+    /// function, method, class or namespace.
+    ///
+    /// EXAMPLES: "Parser::parse", "net::Socket", "main"
+    pub symbol: String,
+
+    /// Location of the symbol name for precise selection, e.g. of an
+    /// overload. OPTIONAL.
+    ///
+    /// FORMAT: "/absolute/path/to/file.cpp:line:column" with 1-based line/column numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Symbol whose uses are looked for inside the definition of symbol.
+    ///
+    /// EXAMPLES: "Lexer::next_token", "Config", "kMaxRetries"
+    pub other_symbol: String,
+
+    /// Location of the other symbol's name. OPTIONAL.
+    ///
+    /// FORMAT: "/absolute/path/to/file.cpp:line:column" with 1-based line/column numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_location_hint: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A symbol and the extent of its definition
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolScope {
+    /// Symbol as requested
+    pub symbol: String,
+    /// Location of the symbol name at its definition, or where it was found
+    /// when clangd knows no definition
+    pub location: FileLocation,
+    /// Full range of the definition, e.g. a function including its body
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub definition_range: Option<FileLocation>,
+}
+
+/// A use of one symbol inside the definition of the other
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferenceSite {
+    pub location: FileLocation,
+    /// Source line of the reference, trimmed
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub line: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrossReferencesResult {
+    pub symbol: SymbolScope,
+    pub other_symbol: SymbolScope,
+    /// Whether other_symbol is used inside the definition of symbol
+    pub symbol_references_other: bool,
+    /// Uses of other_symbol inside the definition of symbol
+    pub sites: Vec<ReferenceSite>,
+    /// Whether symbol is used inside the definition of other_symbol
+    pub other_references_symbol: bool,
+    /// Uses of symbol inside the definition of other_symbol
+    pub reverse_sites: Vec<ReferenceSite>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+/// Whether `position` lies within `range`, ends included
+fn range_contains(range: &lsp_types::Range, position: lsp_types::Position) -> bool {
+    range.start <= position && position <= range.end
+}
+
+/// References of a symbol that lie within a definition
+fn references_within(references: &[FileLocation], scope: &FileLocation) -> Vec<FileLocation> {
+    references
+        .iter()
+        .filter(|reference| {
+            reference.file_path == scope.file_path
+                && range_contains(&scope.range.into(), reference.range.start.into())
+        })
+        .cloned()
+        .collect()
+}
+
+/// References of `target` inside the definition of `scope`
+async fn uses_within(
+    component_session: &ComponentSession,
+    target: &SymbolScope,
+    scope: &SymbolScope,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    let Some(scope_range) = &scope.definition_range else {
+        return Ok(Vec::new());
+    };
+    let references = get_references(component_session, &target.location).await?;
+    Ok(references_within(&references, scope_range))
+}
+
+impl FindCrossReferencesTool {
+    /// Find a symbol's definition and the range it spans
+    ///
+    /// The range is that of the document symbol whose name is at the definition.
+    async fn resolve_scope(
+        symbol: &str,
+        location_hint: Option<&str>,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<SymbolScope, CallToolError> {
+        let found =
+            utils::locate_symbol(workspace, component_session, symbol, location_hint).await?;
+        let location = get_definitions(&found, component_session)
+            .await
+            .map_err(CallToolError::from)?
+            .into_iter()
+            .next()
+            .unwrap_or(found);
+
+        let document_symbols = get_document_symbols(component_session, location.get_uri())
+            .await
+            .map_err(CallToolError::from)?;
+        let definition_range = DocumentSymbolIterator::new(&document_symbols)
+            .filter(|(document_symbol, _)| {
+                range_contains(
+                    &document_symbol.selection_range,
+                    location.range.start.into(),
+                )
+            })
+            .last()
+            .map(|(document_symbol, _)| FileLocation {
+                file_path: location.file_path.clone(),
+                range: document_symbol.range.into(),
+            });
+
+        Ok(SymbolScope {
+            symbol: symbol.to_string(),
+            location,
+            definition_range,
+        })
+    }
+
+    #[instrument(
+        name = "find_cross_references",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Finding cross-references between '{}' and '{}'",
+            self.symbol, self.other_symbol
+        );
+
+        // References come from the whole index, so wait for all of it
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            None,
+            self.wait_timeout,
+            "Cross-reference search",
+        )
+        .await;
+
+        let symbol = Self::resolve_scope(
+            &self.symbol,
+            self.location_hint.as_deref(),
+            &component_session,
+            workspace,
+        )
+        .await?;
+        let other_symbol = Self::resolve_scope(
+            &self.other_symbol,
+            self.other_location_hint.as_deref(),
+            &component_session,
+            workspace,
+        )
+        .await?;
+
+        let mut forward = uses_within(&component_session, &other_symbol, &symbol)
+            .await
+            .map_err(CallToolError::from)?;
+        let mut reverse = uses_within(&component_session, &symbol, &other_symbol)
+            .await
+            .map_err(CallToolError::from)?;
+        for sites in [&mut forward, &mut reverse] {
+            sites.retain(|site| !workspace.result_exclusions.is_excluded(&site.file_path));
+        }
+
+        let max_file_size = workspace.max_context_file_size;
+        // Source lines are read from disk, so keep it off the async executor
+        let (sites, reverse_sites) =
+            tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+                let mut file_buffer_manager =
+                    RealFileBufferManager::new_real().with_max_file_size(Some(max_file_size));
+                let mut with_lines = |locations: Vec<FileLocation>| -> Vec<ReferenceSite> {
+                    locations
+                        .into_iter()
+                        .map(|location| {
+                            let line = FileLocationWithContents::new_from_location_full_line(
+                                &location,
+                                &mut file_buffer_manager,
+                            )
+                            .ok()
+                            .map(|with_contents| with_contents.contents.trim().to_string());
+                            ReferenceSite { location, line }
+                        })
+                        .collect()
+                };
+                (with_lines(forward), with_lines(reverse))
+            }))
+            .await
+            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+        let without_body: Vec<&str> = [&symbol, &other_symbol]
+            .into_iter()
+            .filter(|scope| scope.definition_range.is_none())
+            .map(|scope| scope.symbol.as_str())
+            .collect();
+        let note = (!without_body.is_empty()).then(|| {
+            format!(
+                "No definition found for {}: it is only declared in the project, or defined in \
+                 a library or a file clangd has not indexed, so uses inside it were not searched.",
+                without_body.join(" and ")
+            )
+        });
+
+        info!(
+            "'{}' references '{}' {} times, '{}' references '{}' {} times",
+            self.symbol,
+            self.other_symbol,
+            sites.len(),
+            self.other_symbol,
+            self.symbol,
+            reverse_sites.len()
+        );
+
+        let result = CrossReferencesResult {
+            symbol,
+            other_symbol,
+            symbol_references_other: !sites.is_empty(),
+            sites,
+            other_references_symbol: !reverse_sites.is_empty(),
+            reverse_sites,
+            note,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, location, span, tool_result};
+    use crate::project::ResultExclusions;
+
+    const SOURCE: &str = "int helper(int x) {\n\
+                          \x20   return x * 2;\n\
+                          }\n\
+                          int compute(int a) {\n\
+                          \x20   int b = helper(a);\n\
+                          \x20   return helper(b);\n\
+                          }\n\
+                          int other() { return helper(1); }\n";
+
+    #[tokio::test]
+    async fn test_cross_references_within_definition() {
+        let (_temp_dir, mut workspace) = create_mock_project(&[("src/calc.cpp", SOURCE)]);
+        let uri =
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join("src/calc.cpp"))
+                .to_string();
+
+        let server = MockClangd::new();
+        server.respond_with("textDocument/definition", {
+            let (helper, compute) = (location(&uri, 0, 4, 10), location(&uri, 3, 4, 11));
+            move |params| {
+                if params.as_ref().unwrap()["position"]["line"] == 0 {
+                    serde_json::json!([helper])
+                } else {
+                    serde_json::json!([compute])
+                }
+            }
+        });
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([
+                {"name": "helper", "kind": 12, "range": span((0, 0), (2, 1)),
+                 "selectionRange": span((0, 4), (0, 10))},
+                {"name": "compute", "kind": 12, "range": span((3, 0), (6, 1)),
+                 "selectionRange": span((3, 4), (3, 11))},
+                {"name": "other", "kind": 12, "range": span((7, 0), (7, 33)),
+                 "selectionRange": span((7, 4), (7, 9))}
+            ]),
+        );
+        server.respond_with("textDocument/references", {
+            let helper_uses = vec![
+                location(&uri, 4, 12, 18),
+                location(&uri, 5, 11, 17),
+                location(&uri, 7, 21, 27),
+            ];
+            move |params| {
+                if params.as_ref().unwrap()["position"]["line"] == 0 {
+                    serde_json::json!(helper_uses)
+                } else {
+                    serde_json::json!([])
+                }
+            }
+        });
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // Absolute and project-relative hints are both accepted
+        let file = workspace.project_root_path.join("src/calc.cpp");
+        let tool: FindCrossReferencesTool = serde_json::from_value(serde_json::json!({
+            "symbol": "compute",
+            "location_hint": format!("{}:4:5", file.display()),
+            "other_symbol": "helper",
+            "other_location_hint": "src/calc.cpp:1:5",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let cross_references = |workspace: ProjectWorkspace| {
+            let tool = &tool;
+            let component_session = Arc::clone(&component_session);
+            async move {
+                let result = tool.call_tool(component_session, &workspace).await.unwrap();
+                tool_result::<CrossReferencesResult>(&result)
+            }
+        };
+        let result = cross_references(workspace.clone()).await;
+
+        assert!(result.symbol_references_other);
+        let lines: Vec<_> = result
+            .sites
+            .iter()
+            .map(|site| site.line.as_deref().unwrap())
+            .collect();
+        assert_eq!(lines, vec!["int b = helper(a);", "return helper(b);"]);
+        assert!(!result.other_references_symbol);
+        assert!(result.reverse_sites.is_empty());
+        assert_eq!(
+            result.symbol.definition_range.unwrap().range.end.line,
+            6,
+            "compute spans its body"
+        );
+        assert!(result.note.is_none());
+
+        // Site lines come from an unsaved buffer the references were computed on
+        let buffers = [(
+            workspace.project_root_path.join("src/calc.cpp"),
+            SOURCE.replace("helper(a);", "helper(a + 1);"),
+        )];
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            cross_references(workspace.clone()),
+        )
+        .await;
+        assert_eq!(
+            result.sites[0].line.as_deref(),
+            Some("int b = helper(a + 1);")
+        );
+
+        // Sites in excluded files are left out
+        workspace.result_exclusions = ResultExclusions::new(
+            vec!["src/calc.cpp".to_string()],
+            &workspace.project_root_path,
+        )
+        .unwrap();
+        let result = cross_references(workspace).await;
+        assert!(!result.symbol_references_other);
+        assert!(result.sites.is_empty());
+    }
+}
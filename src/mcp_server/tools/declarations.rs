@@ -0,0 +1,383 @@
+//! Declaration sites tool
+//!
+//! Collects every declaration of a symbol together with its definition, so the
+//! places a type or function is announced (forward declarations, prototypes,
+//! `extern` variables) can be told apart from the place it is fully defined.
+//!
+//! clangd's references with `includeDeclaration` add the declaration and
+//! definition sites to the plain references; the sites present only in the
+//! former are the declarations. Those matching `textDocument/definition` are
+//! the defining declaration, the rest forward declarations.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::propagate_source_overlay_blocking;
+use crate::io::file_manager::RealFileBufferManager;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::lsp_helpers::examples::{
+    get_references, get_references_with_declarations,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, FileLocationWithContents};
+
+#[mcp_tool(
+    name = "find_declarations",
+    description = "List every declaration site of a C++ symbol and mark which one is the definition. \
+                   Types and functions are often forward-declared in several headers and defined in \
+                   one place; this shows where a symbol is only announced and where it is fully defined.
+
+                   🎯 USE CASES:
+                   • Finding the header that really defines a forward-declared class
+                   • Listing the headers that forward-declare a type (candidates for include cleanup)
+                   • Checking that all declarations of a function agree with its definition
+
+                   📋 OUTPUT:
+                   • symbol: Symbol as requested
+                   • definition: Location of the defining declaration, if the symbol is defined
+                   • declarations: All declaration sites, definition first, each with
+                     - kind: \"definition\" or \"forward_declaration\" (any declaration that is not the
+                       definition: class forward declarations, function prototypes, extern variables)
+                     - location: \"/path/file.h:line:column-line:column\" (1-based)
+                     - line: Source line of the declaration, trimmed
+                   • forward_declarations: Number of declarations that are not the definition
+                   • note: Present when no definition was found
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required symbol name, preferably qualified (e.g. \"net::Socket\")
+                   • location_hint: Optional string - exact symbol location (format: \"/path/file.cpp:line:column\")
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted file to be parsed when location_hint is given (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindDeclarationsTool {
+    /// Name of the symbol. Qualify it to avoid picking a same-named symbol of
+    /// another scope.
+    ///
+    /// EXAMPLES: "Socket", "net::Socket", "Parser::parse"
+    pub symbol: String,
+
+    /// Location of the symbol name for precise selection. OPTIONAL.
+    ///
+    /// FORMAT: Compact LSP-style location string with 1-based line/column numbers:
+    /// • "/absolute/path/to/file.cpp:line:column"
+    ///
+    /// BEHAVIOR: When specified, the symbol at this location is used and workspace
+    /// symbol search is skipped. Any declaration or use of the symbol works.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// Role of a declaration site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclarationKind {
+    /// The defining declaration
+    Definition,
+    /// A declaration that does not define the symbol
+    ForwardDeclaration,
+}
+
+/// A place the symbol is declared
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeclarationSite {
+    pub kind: DeclarationKind,
+    pub location: FileLocation,
+    /// Source line of the declaration, trimmed
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub line: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeclarationsResult {
+    /// Symbol as requested
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub definition: Option<FileLocation>,
+    pub declarations: Vec<DeclarationSite>,
+    pub forward_declarations: usize,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+/// File and start position identifying a site
+fn site_key(location: &FileLocation) -> (PathBuf, u32, u32) {
+    let start = location.range.start;
+    (location.file_path.clone(), start.line, start.column)
+}
+
+/// Classify the declaration sites of a symbol
+///
+/// `with_declarations` are the references including declarations, `references`
+/// the plain references. Definitions missing from the references are added,
+/// since clangd's index may hold them under a different file version.
+fn classify_sites(
+    definitions: &[FileLocation],
+    with_declarations: Vec<FileLocation>,
+    references: &[FileLocation],
+) -> Vec<(DeclarationKind, FileLocation)> {
+    let plain: HashSet<_> = references.iter().map(site_key).collect();
+    let defining: HashSet<_> = definitions.iter().map(site_key).collect();
+
+    let mut seen = HashSet::new();
+    let mut sites: Vec<_> = definitions
+        .iter()
+        .cloned()
+        .chain(with_declarations)
+        .filter(|location| {
+            !plain.contains(&site_key(location)) || defining.contains(&site_key(location))
+        })
+        .filter(|location| seen.insert(site_key(location)))
+        .map(|location| {
+            let kind = if defining.contains(&site_key(&location)) {
+                DeclarationKind::Definition
+            } else {
+                DeclarationKind::ForwardDeclaration
+            };
+            (kind, location)
+        })
+        .collect();
+    sites
+        .sort_by_key(|(kind, location)| (*kind != DeclarationKind::Definition, site_key(location)));
+    sites
+}
+
+impl FindDeclarationsTool {
+    #[instrument(name = "find_declarations", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Finding declarations of '{}', location_hint={:?}",
+            self.symbol, self.location_hint
+        );
+
+        let document_files: Option<Vec<_>> = self.location_hint.as_ref().map(|hint| {
+            utils::location_hint_file(workspace, hint)
+                .into_iter()
+                .collect()
+        });
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if self.location_hint.is_some() {
+                "Document-specific declaration search"
+            } else {
+                "Workspace symbol resolution"
+            },
+        )
+        .await;
+
+        let location = utils::locate_symbol(
+            workspace,
+            &component_session,
+            &self.symbol,
+            self.location_hint.as_deref(),
+        )
+        .await?;
+        let definitions = get_definitions(&location, &component_session)
+            .await
+            .map_err(CallToolError::from)?;
+        let with_declarations = get_references_with_declarations(&component_session, &location)
+            .await
+            .map_err(CallToolError::from)?;
+        let references = get_references(&component_session, &location)
+            .await
+            .map_err(CallToolError::from)?;
+
+        let sites = classify_sites(&definitions, with_declarations, &references);
+        let max_file_size = workspace.max_context_file_size;
+        // Source lines are read from disk, so keep it off the async executor
+        let declarations: Vec<DeclarationSite> =
+            tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+                let mut file_buffer_manager =
+                    RealFileBufferManager::new_real().with_max_file_size(Some(max_file_size));
+                sites
+                    .into_iter()
+                    .map(|(kind, location)| {
+                        let line = FileLocationWithContents::new_from_location_full_line(
+                            &location,
+                            &mut file_buffer_manager,
+                        )
+                        .ok()
+                        .map(|with_contents| with_contents.contents.trim().to_string());
+                        DeclarationSite {
+                            kind,
+                            location,
+                            line,
+                        }
+                    })
+                    .collect()
+            }))
+            .await
+            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+        let definition = declarations
+            .iter()
+            .find(|site| site.kind == DeclarationKind::Definition)
+            .map(|site| site.location.clone());
+        let forward_declarations = declarations
+            .iter()
+            .filter(|site| site.kind == DeclarationKind::ForwardDeclaration)
+            .count();
+        info!(
+            "'{}': {} declarations, definition {}",
+            self.symbol,
+            declarations.len(),
+            if definition.is_some() {
+                "found"
+            } else {
+                "not found"
+            }
+        );
+
+        let note = definition.is_none().then(|| {
+            "No definition found: the symbol is only declared in the project, or defined in a \
+             library or a file clangd has not indexed."
+                .to_string()
+        });
+        let result = DeclarationsResult {
+            symbol: self.symbol.clone(),
+            definition,
+            declarations,
+            forward_declarations,
+            note,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, location, tool_result};
+
+    const FWD: &str = "namespace net { class Socket; }\n";
+    const SOCKET: &str = "namespace net {\nclass Socket {\n  int fd;\n};\n}\n";
+    const MAIN: &str = "#include \"fwd.h\"\n#include \"socket.h\"\nnet::Socket* s;\n";
+
+    #[tokio::test]
+    async fn test_classifies_definition_and_forward_declarations() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/fwd.h", FWD),
+            ("src/socket.h", SOCKET),
+            ("src/main.cpp", MAIN),
+        ]);
+        let uri = |file: &str| {
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join(file)).to_string()
+        };
+        let (fwd, socket, main) = (uri("src/fwd.h"), uri("src/socket.h"), uri("src/main.cpp"));
+
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/definition",
+            serde_json::json!([location(&socket, 1, 6, 12)]),
+        );
+        let use_site = location(&main, 2, 5, 11);
+        let declarations = vec![
+            location(&fwd, 0, 22, 28),
+            location(&socket, 1, 6, 12),
+            use_site.clone(),
+        ];
+        server.respond_with("textDocument/references", move |params| {
+            if params.as_ref().unwrap()["context"]["includeDeclaration"] == true {
+                serde_json::json!(declarations)
+            } else {
+                serde_json::json!([use_site])
+            }
+        });
+
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool: FindDeclarationsTool = serde_json::from_value(serde_json::json!({
+            "symbol": "net::Socket",
+            "location_hint": "src/main.cpp:3:6",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool
+            .call_tool(component_session.clone(), &workspace)
+            .await
+            .unwrap();
+        let result: DeclarationsResult = tool_result(&result);
+
+        assert_eq!(result.declarations.len(), 2);
+        assert_eq!(result.forward_declarations, 1);
+        let definition = &result.declarations[0];
+        assert_eq!(definition.kind, DeclarationKind::Definition);
+        assert!(definition.location.file_path.ends_with("src/socket.h"));
+        assert_eq!(definition.line.as_deref(), Some("class Socket {"));
+        assert_eq!(result.definition.as_ref(), Some(&definition.location));
+        let forward = &result.declarations[1];
+        assert_eq!(forward.kind, DeclarationKind::ForwardDeclaration);
+        assert_eq!(
+            forward.line.as_deref(),
+            Some("namespace net { class Socket; }")
+        );
+        assert!(result.note.is_none());
+
+        // The project-relative hint is looked up in the project
+        let params = server.received_params("textDocument/definition")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["textDocument"]["uri"], main);
+
+        // Lines come from an unsaved buffer the locations were computed on
+        let buffers = [(
+            workspace.project_root_path.join("src/socket.h"),
+            SOCKET.replace("class Socket {", "class Socket final {"),
+        )];
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            tool.call_tool(component_session, &workspace),
+        )
+        .await
+        .unwrap();
+        let result: DeclarationsResult = tool_result(&result);
+        assert_eq!(
+            result.declarations[0].line.as_deref(),
+            Some("class Socket final {")
+        );
+    }
+}
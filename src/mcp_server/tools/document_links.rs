@@ -0,0 +1,200 @@
+//! Document links tool
+//!
+//! Maps every `#include` directive of a file to the header clangd resolved it
+//! to, using clangd's document links instead of re-implementing include path
+//! lookup.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::read_source_file;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_links::get_document_links;
+use crate::mcp_server::tools::utils;
+use crate::project::include_paths::IncludeDirective;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, pathbuf_from_uri};
+
+#[mcp_tool(
+    name = "get_document_links",
+    description = "List the #include directives of a C++ file together with the header each one resolves \
+                   to, as reported by clangd (LSP documentLink). Resolution uses the file's real compile \
+                   command, so the targets match what the compiler includes.
+
+                   🎯 USE CASES:
+                   • Jumping from an #include to the included header without resolving paths yourself
+                   • Checking which copy of a header a file actually picks up
+                   • Spotting includes clangd cannot resolve
+
+                   📋 OUTPUT:
+                   • links: One entry per resolved directive, in file order, with
+                     - location: Range of the header name (\"/path/file.cpp:line:column-line:column\", 1-based)
+                     - target: Absolute path of the included file
+                   • unresolved: Directives without a link (header not found), with 1-based line and header name
+                   • document_version: LSP version of the file content the links refer to
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct DocumentLinksTool {
+    /// File to list the include links of. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// An include directive and the file it resolves to
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DocumentLink {
+    /// Range of the linked text, the header name of the directive
+    pub location: FileLocation,
+    /// File the directive includes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<PathBuf>,
+}
+
+/// An include directive clangd did not link to a file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnlinkedInclude {
+    /// 1-based line of the directive
+    pub line: u32,
+    /// Header name as written in the directive
+    pub header: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DocumentLinksResult {
+    pub file: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_version: Option<i32>,
+    pub links: Vec<DocumentLink>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unresolved: Vec<UnlinkedInclude>,
+}
+
+impl DocumentLinksTool {
+    #[instrument(name = "get_document_links", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        info!("Getting document links of {}", file_path.display());
+
+        let lsp_links = get_document_links(&file_path, &component_session).await?;
+        let linked_lines: HashSet<u32> =
+            lsp_links.iter().map(|link| link.range.start.line).collect();
+        let links = lsp_links
+            .into_iter()
+            .map(|link| DocumentLink {
+                location: FileLocation {
+                    file_path: file_path.clone(),
+                    range: link.range.into(),
+                },
+                target: link.target.as_ref().map(pathbuf_from_uri),
+            })
+            .collect();
+
+        // clangd only links directives it resolved; report the others from the text
+        let content = read_source_file(&file_path).unwrap_or_default();
+        let unresolved = content
+            .lines()
+            .enumerate()
+            .filter(|(index, _)| !linked_lines.contains(&(*index as u32)))
+            .filter_map(|(index, line)| {
+                IncludeDirective::parse(line).map(|directive| UnlinkedInclude {
+                    line: index as u32 + 1,
+                    header: directive.header,
+                })
+            })
+            .collect();
+
+        let result = DocumentLinksResult {
+            document_version: component_session.document_version(&file_path).await,
+            file: file_path,
+            links,
+            unresolved,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::symbol::uri_from_pathbuf;
+
+    #[tokio::test]
+    async fn test_document_links_and_unresolved_includes() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            (
+                "src/main.cpp",
+                "#include \"util.h\"\n#include <missing.h>\nint main() { return 0; }\n",
+            ),
+            ("src/util.h", "#pragma once\n"),
+        ]);
+        let util = workspace.project_root_path.join("src/util.h");
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentLink",
+            serde_json::json!([{
+                "range": {"start": {"line": 0, "character": 9}, "end": {"line": 0, "character": 17}},
+                "target": uri_from_pathbuf(&util).as_str()
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = DocumentLinksTool {
+            file: "src/main.cpp".to_string(),
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: DocumentLinksResult = tool_result(&result);
+
+        assert_eq!(result.links.len(), 1);
+        assert_eq!(result.links[0].target.as_deref(), Some(util.as_path()));
+        assert_eq!(result.links[0].location.range.start.line, 0);
+        assert_eq!(result.unresolved.len(), 1);
+        assert_eq!(result.unresolved[0].line, 2);
+        assert_eq!(result.unresolved[0].header, "missing.h");
+        assert_eq!(result.document_version, Some(1));
+    }
+}
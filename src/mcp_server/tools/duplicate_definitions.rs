@@ -0,0 +1,573 @@
+//! Duplicate definitions tool
+//!
+//! Detects symbols defined more than once across translation units, the
+//! one-definition-rule violations linkers report as "multiple definition" or
+//! silently resolve to an arbitrary copy.
+//!
+//! clangd's index merges all definitions of a symbol into one, so
+//! `textDocument/definition` reports a single site. The other definitions are
+//! found among the symbol's references: a reference is a definition when the
+//! document symbol declared there has a body (functions, classes) or is a
+//! namespace-scope variable that is not `extern`.
+
+use lsp_types::{DocumentSymbol, SymbolKind};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::{FileBufferError, propagate_source_overlay_blocking};
+use crate::io::file_manager::RealFileBufferManager;
+use crate::lsp::position_encoding::PositionEncoding;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    get_document_symbols, symbol_name_matches,
+};
+use crate::mcp_server::tools::lsp_helpers::examples::get_references_with_declarations;
+use crate::mcp_server::tools::lsp_helpers::symbol_resolution::get_matching_symbols;
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, FileLocationWithContents, Symbol, uri_from_pathbuf};
+
+/// Maximum number of matching symbols checked for duplicates
+const MAX_SYMBOLS: usize = 10;
+
+/// Symbol kinds that have definitions the one-definition rule applies to
+const DEFINABLE_KINDS: &[SymbolKind] = &[
+    SymbolKind::FUNCTION,
+    SymbolKind::METHOD,
+    SymbolKind::CONSTRUCTOR,
+    SymbolKind::OPERATOR,
+    SymbolKind::CLASS,
+    SymbolKind::STRUCT,
+    SymbolKind::ENUM,
+    SymbolKind::INTERFACE,
+    SymbolKind::VARIABLE,
+    SymbolKind::CONSTANT,
+    SymbolKind::FIELD,
+];
+
+#[mcp_tool(
+    name = "find_duplicate_definitions",
+    description = "Detect C++ symbols defined more than once across translation units (ODR violations, \
+                   \"multiple definition\" link errors). For every symbol matching the name, reports all \
+                   definition sites found in the index, not just the one go-to-definition returns.
+
+                   🎯 USE CASES:
+                   • Diagnosing \"multiple definition of\" / duplicate symbol linker errors
+                   • Finding functions or globals accidentally defined in two source files
+                   • Checking a header does not define non-inline functions or variables
+
+                   📋 OUTPUT:
+                   • symbols: Each matching symbol with
+                     - definitions: Every definition site, with internal_linkage set for static,
+                       anonymous-namespace and namespace-scope const definitions
+                     - duplicate: More than one definition with external linkage
+                   • duplicates_found: Any symbol has duplicate definitions
+                   • index_status: Indexing progress; results are only as complete as the index
+
+                   Definitions with internal linkage are private to their translation unit and do not \
+                   conflict. Identical inline definitions reached through one header are reported once.
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Symbol name, optionally qualified (e.g. \"counter\", \"app::init\")
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindDuplicateDefinitionsTool {
+    /// Name of the symbol to check. Qualified names (namespace::name) narrow the
+    /// match to symbols in that scope.
+    pub symbol: String,
+
+    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// A place where a symbol is defined
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DefinitionSite {
+    pub location: FileLocation,
+    /// The definition is private to its translation unit and cannot conflict
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub internal_linkage: bool,
+}
+
+/// All definitions of one symbol
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolDefinitions {
+    pub symbol: Symbol,
+    pub definitions: Vec<DefinitionSite>,
+    /// More than one definition with external linkage
+    pub duplicate: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateDefinitionsResult {
+    pub query: String,
+    pub symbols: Vec<SymbolDefinitions>,
+    pub duplicates_found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+/// Classification of the declaration at a reference site
+struct Declaration {
+    is_definition: bool,
+    internal_linkage: bool,
+}
+
+impl FindDuplicateDefinitionsTool {
+    #[instrument(
+        name = "find_duplicate_definitions",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!("Searching duplicate definitions of '{}'", self.symbol);
+
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            None,
+            self.wait_timeout,
+            "Duplicate definition search",
+        )
+        .await;
+
+        let candidates: Vec<Symbol> = get_matching_symbols(&self.symbol, &component_session)
+            .await?
+            .into_iter()
+            .filter(|symbol| self.matches(symbol))
+            .take(MAX_SYMBOLS)
+            .collect();
+        if candidates.is_empty() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No function, type or variable named '{}'", self.symbol),
+            )));
+        }
+
+        let position_encoding = component_session.position_encoding().await;
+        let mut file_symbols = HashMap::new();
+        let file_buffer_manager = Arc::new(Mutex::new(
+            RealFileBufferManager::new_real()
+                .with_max_file_size(Some(workspace.max_context_file_size)),
+        ));
+        let mut symbols = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let definitions = find_definition_sites(
+                &component_session,
+                &candidate,
+                position_encoding,
+                &mut file_symbols,
+                &file_buffer_manager,
+            )
+            .await?;
+            let external = definitions
+                .iter()
+                .filter(|site| !site.internal_linkage)
+                .count();
+            symbols.push(SymbolDefinitions {
+                symbol: candidate,
+                definitions,
+                duplicate: external > 1,
+            });
+        }
+
+        let result = DuplicateDefinitionsResult {
+            query: self.symbol.clone(),
+            duplicates_found: symbols.iter().any(|symbol| symbol.duplicate),
+            symbols,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+
+    /// Whether a workspace symbol is a definable symbol named like the query
+    fn matches(&self, symbol: &Symbol) -> bool {
+        if !DEFINABLE_KINDS.contains(&symbol.kind)
+            || !symbol_name_matches(&symbol.name, &self.symbol)
+        {
+            return false;
+        }
+        // A qualified query must match the end of the qualified symbol name
+        match self.symbol.rsplit_once("::") {
+            Some((scope, _)) if !scope.trim().is_empty() => {
+                let qualified = match &symbol.container_name {
+                    Some(container) => format!("{}::{}", container, symbol.name),
+                    None => symbol.name.clone(),
+                };
+                qualified.ends_with(self.symbol.trim().trim_start_matches("::"))
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Collect the definition sites of a symbol from its definition and references
+async fn find_definition_sites(
+    component_session: &ComponentSession,
+    symbol: &Symbol,
+    position_encoding: PositionEncoding,
+    file_symbols: &mut HashMap<PathBuf, Arc<Vec<DocumentSymbol>>>,
+    file_buffer_manager: &Arc<Mutex<RealFileBufferManager>>,
+) -> Result<Vec<DefinitionSite>, AnalyzerError> {
+    let definitions = get_definitions(&symbol.location, component_session).await?;
+    let references = get_references_with_declarations(component_session, &symbol.location).await?;
+
+    let known_definitions: HashSet<_> = definitions
+        .iter()
+        .map(|location| {
+            let start = location.range.start;
+            (location.file_path.clone(), start.line, start.column)
+        })
+        .collect();
+    let mut by_file: BTreeMap<PathBuf, Vec<FileLocation>> = BTreeMap::new();
+    for location in definitions.into_iter().chain(references) {
+        by_file
+            .entry(location.file_path.clone())
+            .or_default()
+            .push(location);
+    }
+
+    let mut sites = Vec::new();
+    for (file_path, mut locations) in by_file {
+        locations.sort_by_key(|location| (location.range.start.line, location.range.start.column));
+        locations.dedup_by_key(|location| location.range.start);
+
+        if !file_symbols.contains_key(&file_path) {
+            let symbols = get_document_symbols(component_session, uri_from_pathbuf(&file_path))
+                .await
+                .map_err(|e| warn!("No document symbols for {}: {}", file_path.display(), e))
+                .unwrap_or_default();
+            file_symbols.insert(file_path.clone(), Arc::new(symbols));
+        }
+        let document_symbols = Arc::clone(&file_symbols[&file_path]);
+        let locations: Vec<_> = locations
+            .into_iter()
+            .map(|location| {
+                let start = location.range.start;
+                let known =
+                    known_definitions.contains(&(file_path.clone(), start.line, start.column));
+                (location, known)
+            })
+            .collect();
+
+        // Declarations are classified from their text on disk, so keep it off the async executor
+        let file_buffer_manager = Arc::clone(file_buffer_manager);
+        let file_sites =
+            tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+                let mut file_buffer_manager = file_buffer_manager.lock().unwrap();
+                locations
+                    .into_iter()
+                    .filter_map(|(location, known)| {
+                        let declaration =
+                            find_declaring_symbol(&document_symbols, location.range.start.into())
+                                .and_then(|(declared, ancestors)| {
+                                    classify_declaration(
+                                        declared,
+                                        &ancestors,
+                                        &file_path,
+                                        position_encoding,
+                                        &mut file_buffer_manager,
+                                    )
+                                });
+                        let (is_definition, internal_linkage) = match declaration {
+                            Some(declaration) => (
+                                known || declaration.is_definition,
+                                declaration.internal_linkage,
+                            ),
+                            None => (known, false),
+                        };
+                        is_definition.then_some(DefinitionSite {
+                            location,
+                            internal_linkage,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            }))
+            .await
+            .map_err(|e| FileBufferError::from(std::io::Error::other(e.to_string())))?;
+        sites.extend(file_sites);
+    }
+    Ok(sites)
+}
+
+/// Find the symbol whose name starts at a position, with its enclosing symbols
+fn find_declaring_symbol(
+    symbols: &[DocumentSymbol],
+    position: lsp_types::Position,
+) -> Option<(&DocumentSymbol, Vec<&DocumentSymbol>)> {
+    for symbol in symbols {
+        if position < symbol.range.start || position > symbol.range.end {
+            continue;
+        }
+        if symbol.selection_range.start == position {
+            return Some((symbol, Vec::new()));
+        }
+        if let Some(children) = &symbol.children
+            && let Some((found, mut ancestors)) = find_declaring_symbol(children, position)
+        {
+            ancestors.insert(0, symbol);
+            return Some((found, ancestors));
+        }
+    }
+    None
+}
+
+/// Decide whether a declaration is a definition and whether it has internal linkage
+///
+/// Returns `None` when the declaration text cannot be read, as for files over
+/// the context size limit.
+fn classify_declaration(
+    symbol: &DocumentSymbol,
+    ancestors: &[&DocumentSymbol],
+    file_path: &std::path::Path,
+    position_encoding: PositionEncoding,
+    file_buffer_manager: &mut RealFileBufferManager,
+) -> Option<Declaration> {
+    let mut text_between = |start: lsp_types::Position, end: lsp_types::Position| {
+        let location = FileLocation {
+            file_path: file_path.to_path_buf(),
+            range: lsp_types::Range { start, end }.into(),
+        };
+        FileLocationWithContents::new_from_location(
+            &location,
+            position_encoding,
+            file_buffer_manager,
+        )
+        .map(|with_contents| with_contents.contents)
+        .ok()
+    };
+    // Specifiers before the name, and everything after it up to the end of the declaration
+    let prefix = text_between(symbol.range.start, symbol.selection_range.start)?;
+    let tail = text_between(symbol.selection_range.end, symbol.range.end)?;
+
+    let in_record = ancestors.iter().any(|ancestor| is_record(ancestor.kind));
+    let in_function = ancestors.iter().any(|ancestor| is_function(ancestor.kind));
+    let is_variable = matches!(
+        symbol.kind,
+        SymbolKind::VARIABLE | SymbolKind::CONSTANT | SymbolKind::FIELD
+    );
+
+    let is_definition = if is_variable {
+        !in_record && !in_function && !has_word(&prefix, "extern")
+    } else {
+        (is_function(symbol.kind) || is_record(symbol.kind)) && tail.contains('{')
+    };
+
+    let in_anonymous_namespace = ancestors
+        .iter()
+        .any(|ancestor| ancestor.name.starts_with("(anonymous"));
+    let const_variable = is_variable
+        && (has_word(&prefix, "const") || has_word(&prefix, "constexpr"))
+        && !has_word(&prefix, "extern")
+        && !has_word(&prefix, "inline");
+    let internal_linkage =
+        in_anonymous_namespace || (!in_record && (has_word(&prefix, "static") || const_variable));
+
+    Some(Declaration {
+        is_definition,
+        internal_linkage,
+    })
+}
+
+fn is_function(kind: SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::FUNCTION | SymbolKind::METHOD | SymbolKind::CONSTRUCTOR | SymbolKind::OPERATOR
+    )
+}
+
+fn is_record(kind: SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::CLASS | SymbolKind::STRUCT | SymbolKind::ENUM | SymbolKind::INTERFACE
+    )
+}
+
+/// Whether `text` contains `word` as a whole identifier
+fn has_word(text: &str, word: &str) -> bool {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|token| token == word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, range, tool_result};
+
+    /// Find the definitions of `counter` with `other_definition` in src/b.cpp
+    ///
+    /// With `unsaved`, clangd answers for that unsaved content of src/b.cpp instead.
+    async fn find_counter_definitions(
+        other_definition: &str,
+        unsaved: Option<&str>,
+    ) -> DuplicateDefinitionsResult {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/a.cpp", "int counter = 1;\n"),
+            ("src/b.cpp", other_definition),
+            (
+                "src/main.cpp",
+                "extern int counter;\nint main() { return counter; }\n",
+            ),
+        ]);
+        let uri = |file: &str| {
+            uri_from_pathbuf(&workspace.project_root_path.join(file))
+                .as_str()
+                .to_string()
+        };
+        let buffer = unsaved.unwrap_or(other_definition);
+        let name_start = buffer.find("counter").unwrap() as u32;
+        let other_end = buffer.trim_end().len() as u32;
+
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([{
+                "name": "counter",
+                "kind": 13,
+                "location": {"uri": uri("src/a.cpp"), "range": range(0, 4, 11)}
+            }]),
+        );
+        server.respond(
+            "textDocument/definition",
+            serde_json::json!([{"uri": uri("src/a.cpp"), "range": range(0, 4, 11)}]),
+        );
+        server.respond(
+            "textDocument/references",
+            serde_json::json!([
+                {"uri": uri("src/a.cpp"), "range": range(0, 4, 11)},
+                {"uri": uri("src/b.cpp"), "range": range(0, name_start, name_start + 7)},
+                {"uri": uri("src/main.cpp"), "range": range(0, 11, 18)},
+                {"uri": uri("src/main.cpp"), "range": range(1, 20, 27)}
+            ]),
+        );
+        let (a_uri, b_uri) = (uri("src/a.cpp"), uri("src/b.cpp"));
+        server.respond_with("textDocument/documentSymbol", move |params| {
+            let file = params.unwrap()["textDocument"]["uri"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            if file == a_uri {
+                serde_json::json!([{
+                    "name": "counter", "kind": 13,
+                    "range": range(0, 0, 15), "selectionRange": range(0, 4, 11)
+                }])
+            } else if file == b_uri {
+                serde_json::json!([{
+                    "name": "counter", "kind": 13,
+                    "range": range(0, 0, other_end - 1),
+                    "selectionRange": range(0, name_start, name_start + 7)
+                }])
+            } else {
+                serde_json::json!([
+                    {
+                        "name": "counter", "kind": 13,
+                        "range": range(0, 0, 18), "selectionRange": range(0, 11, 18)
+                    },
+                    {
+                        "name": "main", "kind": 12,
+                        "range": {"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 30}},
+                        "selectionRange": range(1, 4, 8)
+                    }
+                ])
+            }
+        });
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = FindDuplicateDefinitionsTool {
+            symbol: "counter".to_string(),
+            wait_timeout: Some(0),
+            build_directory: None,
+            session_id: None,
+        };
+        let buffers = unsaved.map(|content| {
+            (
+                workspace.project_root_path.join("src/b.cpp"),
+                content.to_string(),
+            )
+        });
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            tool.call_tool(component_session, &workspace),
+        )
+        .await
+        .unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_variable_defined_in_two_files_is_duplicate() {
+        let result = find_counter_definitions("int counter = 2;\n", None).await;
+
+        assert!(result.duplicates_found);
+        assert_eq!(result.symbols.len(), 1);
+        let definitions = &result.symbols[0].definitions;
+        let files: Vec<_> = definitions
+            .iter()
+            .map(|site| site.location.file_path.file_name().unwrap().to_owned())
+            .collect();
+        assert_eq!(files, vec!["a.cpp", "b.cpp"]);
+        assert!(definitions.iter().all(|site| !site.internal_linkage));
+    }
+
+    #[tokio::test]
+    async fn test_internal_linkage_definition_does_not_conflict() {
+        let result = find_counter_definitions("static int counter = 2;\n", None).await;
+
+        assert!(!result.duplicates_found);
+        let definitions = &result.symbols[0].definitions;
+        assert_eq!(definitions.len(), 2);
+        assert!(!definitions[0].internal_linkage);
+        assert!(definitions[1].internal_linkage);
+    }
+
+    #[tokio::test]
+    async fn test_declarations_are_classified_from_unsaved_buffers() {
+        let result =
+            find_counter_definitions("int counter = 2;\n", Some("static int counter = 2;\n")).await;
+
+        assert!(!result.duplicates_found);
+        let definitions = &result.symbols[0].definitions;
+        assert_eq!(definitions.len(), 2);
+        assert!(definitions[1].internal_linkage);
+    }
+
+    #[test]
+    fn test_has_word() {
+        assert!(has_word("static const ", "static"));
+        assert!(!has_word("static_assert", "static"));
+        assert!(!has_word("", "extern"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Enclosing function lookup tool
+//!
+//! Turns a `file:line` location, typically a stack trace frame, into the full
+//! source of the function containing it together with its qualified name and
+//! signature.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::read_context_source_async;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    find_enclosing_function, get_document_symbols,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, uri_from_pathbuf};
+
+#[mcp_tool(
+    name = "get_enclosing_function",
+    description = "Get the source of the function that contains a given line. Designed for stack traces \
+                   and crash reports: pass the file and line of a frame and receive the complete \
+                   enclosing function or method with its fully qualified name and signature.
+
+                   🎯 PRIMARY USE CASES:
+                   Turning crash locations into readable code • Reviewing the function around a \
+                   compiler diagnostic or log line • Jumping from a line number to its context
+
+                   📋 OUTPUT:
+                   • name / qualified_name: Function name and namespace/class-qualified name
+                   • signature: Signature reported by clangd (e.g. \"int (int, int) const\")
+                   • location: Full range of the function (1-based start_line/end_line also given)
+                   • source: Complete source text of the function
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • line: 1-based line number inside the function
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetEnclosingFunctionTool {
+    /// File containing the location. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// 1-based line number inside the function of interest.
+    pub line: u32,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnclosingFunctionResult {
+    /// Function name as reported by clangd
+    pub name: String,
+    /// Name qualified with enclosing namespaces and classes
+    pub qualified_name: String,
+    /// Signature reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Full range of the function
+    pub location: FileLocation,
+    /// 1-based first line of the function
+    pub start_line: u32,
+    /// 1-based last line of the function
+    pub end_line: u32,
+    /// Complete source text of the function
+    pub source: String,
+}
+
+impl GetEnclosingFunctionTool {
+    #[instrument(
+        name = "get_enclosing_function",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        if self.line == 0 {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Line numbers are 1-based (> 0)",
+            )));
+        }
+
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        info!(
+            "Looking up enclosing function for {}:{}",
+            file_path.display(),
+            self.line
+        );
+
+        let content = read_context_source_async(&file_path, workspace.max_context_file_size)
+            .await
+            .map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!(
+                    "Failed to read '{}': {}",
+                    file_path.display(),
+                    e
+                )))
+            })?;
+
+        let symbols = get_document_symbols(&component_session, uri_from_pathbuf(&file_path))
+            .await
+            .map_err(CallToolError::from)?;
+
+        let (function, container_path) = find_enclosing_function(&symbols, self.line - 1)
+            .ok_or_else(|| {
+                CallToolError::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "Line {} of '{}' is not inside a function",
+                        self.line,
+                        file_path.display()
+                    ),
+                ))
+            })?;
+
+        let mut qualified_parts = container_path;
+        qualified_parts.push(function.name.clone());
+
+        let result = EnclosingFunctionResult {
+            name: function.name.clone(),
+            qualified_name: qualified_parts.join("::"),
+            signature: function.detail.clone(),
+            location: FileLocation {
+                file_path: file_path.clone(),
+                range: function.range.into(),
+            },
+            start_line: function.range.start.line + 1,
+            end_line: function.range.end.line + 1,
+            source: extract_lines(&content, function.range.start.line, function.range.end.line),
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+/// Text of the 0-based inclusive line range
+fn extract_lines(content: &str, start_line: u32, end_line: u32) -> String {
+    content
+        .lines()
+        .skip(start_line as usize)
+        .take((end_line.saturating_sub(start_line) + 1) as usize)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    const SOURCE: &str = "namespace app {\n\
+                          int Calculator::add(int a, int b) {\n\
+                          \x20   int sum = a + b;\n\
+                          \x20   return sum;\n\
+                          }\n\
+                          }\n";
+
+    #[tokio::test]
+    async fn test_enclosing_function_with_mock_clangd() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/calc.cpp", SOURCE)]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "app",
+                "kind": 3,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 5, "character": 1}},
+                "selectionRange": {"start": {"line": 0, "character": 10}, "end": {"line": 0, "character": 13}},
+                "children": [{
+                    "name": "Calculator::add",
+                    "detail": "int (int, int)",
+                    "kind": 6,
+                    "range": {"start": {"line": 1, "character": 0}, "end": {"line": 4, "character": 1}},
+                    "selectionRange": {"start": {"line": 1, "character": 16}, "end": {"line": 1, "character": 19}}
+                }]
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = GetEnclosingFunctionTool {
+            file: "src/calc.cpp".to_string(),
+            line: 3,
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool
+            .call_tool(component_session.clone(), &workspace)
+            .await
+            .unwrap();
+        let function: EnclosingFunctionResult = tool_result(&result);
+        assert_eq!(function.qualified_name, "app::Calculator::add");
+        assert_eq!(function.signature.as_deref(), Some("int (int, int)"));
+        assert_eq!((function.start_line, function.end_line), (2, 5));
+        assert_eq!(
+            function.source,
+            "int Calculator::add(int a, int b) {\n    int sum = a + b;\n    return sum;\n}"
+        );
+
+        // Source comes from an unsaved buffer the ranges were computed on
+        let buffer = SOURCE.replace("int sum = a + b;", "long sum = a + b;");
+        let buffers = [(workspace.project_root_path.join("src/calc.cpp"), buffer)];
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            tool.call_tool(component_session.clone(), &workspace),
+        )
+        .await
+        .unwrap();
+        let function: EnclosingFunctionResult = tool_result(&result);
+        assert!(function.source.contains("long sum = a + b;"));
+
+        // Files over the context size limit are not read
+        let mut limited = workspace.clone();
+        limited.max_context_file_size = 16;
+        let err = tool
+            .call_tool(component_session.clone(), &limited)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds the size limit"));
+
+        // Namespace scope outside of any function
+        let tool = GetEnclosingFunctionTool { line: 1, ..tool };
+        assert!(tool.call_tool(component_session, &workspace).await.is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! Expression type tool
+//!
+//! Reports the type clang computed for an arbitrary expression span, such as
+//! `a + b.foo()`, with its value category and const qualification. Hover only
+//! describes named entities; the type of a composite expression is read from
+//! the AST node clangd selects for the span.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::{FilePosition, propagate_source_overlay_blocking};
+use crate::io::file_manager::RealFileBufferManager;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::ast::{
+    AstLookup, ClangdAstNode, ExpressionType, get_ast, parse_expression_type,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Node kinds wrapping an expression without changing its type
+const TRANSPARENT_KINDS: &[&str] = &["ExprWithCleanups", "Constant"];
+
+#[mcp_tool(
+    name = "get_expression_type",
+    description = "Get the type clang deduces for a C++ expression spanning a source range, e.g. the \
+                   result of `a + b.foo()` or `*it`, using clangd's AST extension. More precise than \
+                   hover, which only describes named entities.
+
+                   🎯 PRIMARY USE CASES:
+                   Reasoning about expression types during refactoring • Checking what `auto` would \
+                   deduce • Finding out whether an expression yields a reference to const or a temporary
+
+                   📋 OUTPUT:
+                   • status: \"ok\", \"not_an_expression\" when the range selects a statement or
+                     declaration, \"no_node\", or \"feature_unavailable\" (clangd < 12)
+                   • code: Source text of the selected expression
+                   • expression_kind: Clang node kind (e.g. BinaryOperator, CXXMemberCall)
+                   • exact_match: false when the smallest enclosing expression is larger than the range
+                   • expression_type: type_name as written, desugared_type when it differs,
+                     value_category (lvalue, xvalue, prvalue) and const_qualified
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • start_line / start_column: 1-based start of the expression
+                   • end_line / end_column: 1-based end of the expression (exclusive column)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetExpressionTypeTool {
+    /// File containing the expression. Absolute paths are used as-is, relative
+    /// paths are resolved against the project root.
+    pub file: String,
+
+    /// Line of the start of the expression (1-based).
+    pub start_line: u32,
+
+    /// Column of the start of the expression (1-based).
+    pub start_column: u32,
+
+    /// Line of the end of the expression (1-based).
+    pub end_line: u32,
+
+    /// Column just past the end of the expression (1-based).
+    pub end_column: u32,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Outcome of the expression type lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpressionTypeStatus {
+    Ok,
+    NotAnExpression,
+    NoNode,
+    FeatureUnavailable,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExpressionTypeResult {
+    pub file: PathBuf,
+    pub status: ExpressionTypeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Source text of the selected node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Kind of the selected node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression_kind: Option<String>,
+    /// Whether the selected node spans exactly the requested range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact_match: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression_type: Option<ExpressionType>,
+}
+
+impl GetExpressionTypeTool {
+    /// LSP range requested by the tool input
+    fn requested_range(&self) -> Result<lsp_types::Range, CallToolError> {
+        let invalid = |message: String| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        };
+        if [
+            self.start_line,
+            self.start_column,
+            self.end_line,
+            self.end_column,
+        ]
+        .contains(&0)
+        {
+            return Err(invalid("Lines and columns are 1-based".to_string()));
+        }
+        let start = lsp_types::Position::new(self.start_line - 1, self.start_column - 1);
+        let end = lsp_types::Position::new(self.end_line - 1, self.end_column - 1);
+        if end <= start {
+            return Err(invalid(format!(
+                "Range ends at {}:{} before it starts at {}:{}",
+                self.end_line, self.end_column, self.start_line, self.start_column
+            )));
+        }
+        Ok(lsp_types::Range::new(start, end))
+    }
+
+    #[instrument(name = "get_expression_type", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let range = self.requested_range()?;
+        info!(
+            "Getting expression type in {} at {:?}",
+            file_path.display(),
+            range
+        );
+
+        let mut result = ExpressionTypeResult {
+            file: file_path.clone(),
+            status: ExpressionTypeStatus::Ok,
+            reason: None,
+            code: None,
+            expression_kind: None,
+            exact_match: None,
+            expression_type: None,
+        };
+        match get_ast(&file_path, Some(range), &component_session).await? {
+            AstLookup::Node(node) => {
+                let node = unwrap_transparent(node);
+                if let Some(node_range) = node.range {
+                    let encoding = component_session.position_encoding().await;
+                    let source_path = file_path.clone();
+                    let max_file_size = workspace.max_context_file_size;
+                    // The expression source is read from disk, so keep it off the async executor
+                    result.code =
+                        tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+                            RealFileBufferManager::new_real()
+                                .with_max_file_size(Some(max_file_size))
+                                .get_buffer(&source_path)
+                                .and_then(|buffer| {
+                                    buffer.text_between_encoded(
+                                        FilePosition::new(
+                                            node_range.start.line,
+                                            node_range.start.character,
+                                        ),
+                                        FilePosition::new(
+                                            node_range.end.line,
+                                            node_range.end.character,
+                                        ),
+                                        encoding,
+                                    )
+                                })
+                        }))
+                        .await
+                        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?
+                        .map_err(|e| warn!("Failed to read the expression source: {}", e))
+                        .ok();
+                    result.exact_match = Some(node_range == range);
+                }
+                result.expression_kind = Some(node.kind.clone());
+                if node.role != "expression" {
+                    result.status = ExpressionTypeStatus::NotAnExpression;
+                    result.reason = Some(format!(
+                        "The range selects a {} ({}), not an expression",
+                        node.role, node.kind
+                    ));
+                } else {
+                    result.expression_type = node.arcana.as_deref().and_then(parse_expression_type);
+                    if result.expression_type.is_none() {
+                        result.reason =
+                            Some("clangd did not report the type of the expression".to_string());
+                    }
+                }
+            }
+            AstLookup::NoNode => result.status = ExpressionTypeStatus::NoNode,
+            AstLookup::Unsupported(reason) => {
+                warn!("AST unavailable: {}", reason);
+                result.status = ExpressionTypeStatus::FeatureUnavailable;
+                result.reason = Some(reason);
+            }
+        }
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+/// Skip nodes that only wrap an expression, such as `ExprWithCleanups`
+fn unwrap_transparent(mut node: ClangdAstNode) -> ClangdAstNode {
+    while TRANSPARENT_KINDS.contains(&node.kind.as_str()) && node.children.len() == 1 {
+        node = node.children.remove(0);
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, range, tool_result};
+    use crate::mcp_server::tools::lsp_helpers::ast::ValueCategory;
+
+    const SOURCE: &str = "struct S { const int &get() const; };\n\
+                          int f(S s, int a) {\n  return a + s.get();\n}\n";
+
+    /// Expression type at a span of line 3, with `unsaved` replacing the file on disk
+    async fn type_at(
+        server: &MockClangd,
+        start: u32,
+        end: u32,
+        unsaved: Option<&str>,
+    ) -> ExpressionTypeResult {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", SOURCE)]);
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool = GetExpressionTypeTool {
+            file: "src/main.cpp".to_string(),
+            start_line: 3,
+            start_column: start,
+            end_line: 3,
+            end_column: end,
+            build_directory: None,
+            session_id: None,
+        };
+        let buffers = unsaved.map(|content| {
+            (
+                workspace.project_root_path.join("src/main.cpp"),
+                content.to_string(),
+            )
+        });
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            tool.call_tool(component_session, &workspace),
+        )
+        .await
+        .unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_type_of_composite_and_member_call_expressions() {
+        let server = MockClangd::new();
+        server.respond_with("textDocument/ast", |params| {
+            match params.as_ref().unwrap()["range"]["start"]["character"].as_u64() {
+                Some(9) => serde_json::json!({
+                    "role": "expression",
+                    "kind": "BinaryOperator",
+                    "detail": "+",
+                    "arcana": "BinaryOperator 0x1 <line:3:10, col:20> 'int' '+'\n|-ImplicitCastExpr",
+                    "range": range(2, 9, 20),
+                    "children": []
+                }),
+                _ => serde_json::json!({
+                    "role": "expression",
+                    "kind": "ExprWithCleanups",
+                    "children": [{
+                        "role": "expression",
+                        "kind": "CXXMemberCall",
+                        "arcana": "CXXMemberCallExpr 0x2 <col:14, col:20> 'const int' lvalue",
+                        "range": range(2, 13, 20),
+                        "children": []
+                    }]
+                }),
+            }
+        });
+
+        let result = type_at(&server, 10, 20, None).await;
+        assert_eq!(result.status, ExpressionTypeStatus::Ok);
+        assert_eq!(result.code.as_deref(), Some("a + s.get()"));
+        assert_eq!(result.expression_kind.as_deref(), Some("BinaryOperator"));
+        assert_eq!(result.exact_match, Some(false));
+        let expression_type = result.expression_type.unwrap();
+        assert_eq!(expression_type.type_name, "int");
+        assert_eq!(expression_type.value_category, ValueCategory::Prvalue);
+        assert!(!expression_type.const_qualified);
+
+        let result = type_at(&server, 14, 21, None).await;
+        assert_eq!(result.code.as_deref(), Some("s.get()"));
+        assert_eq!(result.expression_kind.as_deref(), Some("CXXMemberCall"));
+        assert_eq!(result.exact_match, Some(true));
+        let expression_type = result.expression_type.unwrap();
+        assert_eq!(expression_type.value_category, ValueCategory::Lvalue);
+        assert!(expression_type.const_qualified);
+
+        // The source comes from an unsaved buffer the AST was built from
+        let unsaved = SOURCE.replace("a + s.get()", "a - s.get()");
+        let result = type_at(&server, 10, 20, Some(&unsaved)).await;
+        assert_eq!(result.code.as_deref(), Some("a - s.get()"));
+    }
+
+    #[tokio::test]
+    async fn test_statement_is_not_an_expression() {
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/ast",
+            serde_json::json!({
+                "role": "statement",
+                "kind": "Return",
+                "arcana": "ReturnStmt 0x1 <col:3, col:20>",
+                "range": range(2, 2, 20),
+                "children": []
+            }),
+        );
+
+        let result = type_at(&server, 3, 21, None).await;
+        assert_eq!(result.status, ExpressionTypeStatus::NotAnExpression);
+        assert_eq!(result.expression_kind.as_deref(), Some("Return"));
+        assert!(result.expression_type.is_none());
+    }
+}
@@ -0,0 +1,327 @@
+//! Virtual method override tool
+//!
+//! Lists every method overriding a virtual method, and optionally the base
+//! class methods it overrides itself, each with its class and location.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_declarations;
+use crate::mcp_server::tools::lsp_helpers::overrides::{
+    MethodInfo, describe_method, find_base_methods, find_overrides,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+#[mcp_tool(
+    name = "find_overrides",
+    description = "List every override of a C++ virtual method across the project, with the class \
+                   and location of each. Optionally also lists the base class methods the method \
+                   itself overrides.
+
+                   🎯 PRIMARY USE CASES:
+                   Understanding virtual dispatch • Finding every implementation of an interface method \
+                   • Checking which base method an override belongs to
+
+                   📋 OUTPUT:
+                   • method: The resolved method (name, class, signature, declaration location)
+                   • overrides: Methods overriding it, directly or indirectly (class_name, detail, location)
+                   • base_methods (with include_base_methods): Same-named methods of all base classes,
+                     overloads included so signatures can be compared
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required method name, preferably qualified (e.g. \"Shape::area\")
+                   • location_hint: Optional string - exact method location (format: \"/path/file.cpp:line:column\")
+                   • include_base_methods: Optional boolean - also list overridden base methods (default: false)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted file to be parsed when location_hint is given (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindOverridesTool {
+    /// Name of the virtual method. Qualify it with its class to avoid picking a
+    /// same-named method of another class.
+    ///
+    /// EXAMPLES: "Shape::area", "app::Plugin::load", "draw"
+    pub symbol: String,
+
+    /// Location of the method name for precise selection. OPTIONAL.
+    ///
+    /// FORMAT: Compact LSP-style location string with 1-based line/column numbers:
+    /// • "/absolute/path/to/file.cpp:line:column"
+    ///
+    /// BEHAVIOR: When specified, the method at this location is used and workspace
+    /// symbol search is skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Also list the base class methods this method overrides (default: false). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_base_methods: Option<bool>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OverridesResult {
+    /// Symbol as requested
+    pub symbol: String,
+    /// The resolved method, at its declaration
+    pub method: MethodInfo,
+    /// Methods overriding the method
+    pub overrides: Vec<MethodInfo>,
+    /// Base class methods the method overrides (only with include_base_methods)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_methods: Option<Vec<MethodInfo>>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl FindOverridesTool {
+    #[instrument(name = "find_overrides", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Finding overrides of '{}', location_hint={:?}, include_base_methods={:?}",
+            self.symbol, self.location_hint, self.include_base_methods
+        );
+
+        let document_files: Option<Vec<_>> = self.location_hint.as_ref().map(|hint| {
+            utils::location_hint_file(workspace, hint)
+                .into_iter()
+                .collect()
+        });
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if self.location_hint.is_some() {
+                "Document-specific override search"
+            } else {
+                "Workspace symbol resolution"
+            },
+        )
+        .await;
+
+        // Work from the in-class declaration: out-of-line definitions have no
+        // enclosing class in their file's document symbols
+        let location = utils::locate_symbol(
+            workspace,
+            &component_session,
+            &self.symbol,
+            self.location_hint.as_deref(),
+        )
+        .await?;
+        let declaration = get_declarations(&location, &component_session)
+            .await
+            .map_err(CallToolError::from)?
+            .into_iter()
+            .next()
+            .unwrap_or(location);
+
+        let (method, kind) = describe_method(&declaration, &component_session)
+            .await
+            .map_err(CallToolError::from)?
+            .ok_or_else(|| {
+                CallToolError::new(std::io::Error::other(format!(
+                    "No symbol found at {}",
+                    declaration.to_compact_range()
+                )))
+            })?;
+        if !matches!(
+            kind,
+            lsp_types::SymbolKind::METHOD | lsp_types::SymbolKind::FUNCTION
+        ) {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "'{}' is a {:?}, not a method; overrides exist only for virtual methods",
+                    method.name, kind
+                ),
+            )));
+        }
+
+        let overrides = find_overrides(&declaration, &component_session)
+            .await
+            .map_err(CallToolError::from)?;
+
+        let base_methods = if self.include_base_methods.unwrap_or(false) {
+            Some(
+                find_base_methods(&declaration, &method.name, &component_session)
+                    .await
+                    .map_err(CallToolError::from)?,
+            )
+        } else {
+            None
+        };
+
+        let result = OverridesResult {
+            symbol: self.symbol.clone(),
+            method,
+            overrides,
+            base_methods,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, line_of, range, tool_result};
+
+    const SHAPES: &str = "struct Shape {\n  virtual double area() const = 0;\n};\n\
+                          struct Circle : Shape {\n  double area() const override;\n};\n";
+    const CIRCLE: &str = "#include \"shapes.h\"\ndouble Circle::area() const { return 3.14; }\n";
+
+    #[tokio::test]
+    async fn test_find_overrides_and_base_methods() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/shapes.h", SHAPES), ("src/circle.cpp", CIRCLE)]);
+        let header = workspace.project_root_path.join("src/shapes.h");
+        let source = workspace.project_root_path.join("src/circle.cpp");
+        let header_uri = crate::symbol::uri_from_pathbuf(&header).to_string();
+        let source_uri = crate::symbol::uri_from_pathbuf(&source).to_string();
+
+        let server = MockClangd::new();
+        let symbols_header = header_uri.clone();
+        server.respond_with("textDocument/documentSymbol", move |params| {
+            let uri = params.as_ref().unwrap()["textDocument"]["uri"].as_str().unwrap();
+            if uri == symbols_header {
+                serde_json::json!([
+                    {"name": "Shape", "kind": 23, "range": {"start": {"line": 0, "character": 0}, "end": {"line": 2, "character": 1}},
+                     "selectionRange": range(0, 7, 12), "children": [
+                        {"name": "area", "kind": 6, "detail": "double () const",
+                         "range": range(1, 2, 33), "selectionRange": range(1, 17, 21)}
+                    ]},
+                    {"name": "Circle", "kind": 23, "range": {"start": {"line": 3, "character": 0}, "end": {"line": 5, "character": 1}},
+                     "selectionRange": range(3, 7, 13), "children": [
+                        {"name": "area", "kind": 6, "detail": "double () const override",
+                         "range": range(4, 2, 30), "selectionRange": range(4, 9, 13)}
+                    ]}
+                ])
+            } else {
+                serde_json::json!([
+                    {"name": "Circle::area", "kind": 6, "detail": "double () const",
+                     "range": range(1, 0, 46), "selectionRange": range(1, 15, 19)}
+                ])
+            }
+        });
+        server.respond(
+            "textDocument/implementation",
+            serde_json::json!([{"uri": source_uri, "range": range(1, 15, 19)}]),
+        );
+        let hierarchy_uri = header_uri.clone();
+        server.respond_with(
+            "textDocument/prepareTypeHierarchy",
+            move |params| match line_of(&params) {
+                Some(3) => serde_json::json!([{
+                    "name": "Circle", "kind": 23, "uri": hierarchy_uri,
+                    "range": range(3, 0, 1), "selectionRange": range(3, 7, 13)
+                }]),
+                _ => serde_json::json!([{
+                    "name": "Shape", "kind": 23, "uri": hierarchy_uri,
+                    "range": range(0, 0, 1), "selectionRange": range(0, 7, 12)
+                }]),
+            },
+        );
+        let supertype_uri = header_uri.clone();
+        server.respond_with("typeHierarchy/supertypes", move |params| {
+            if params.as_ref().unwrap()["item"]["name"] == "Circle" {
+                serde_json::json!([{
+                    "name": "Shape", "kind": 23, "uri": supertype_uri,
+                    "range": range(0, 0, 1), "selectionRange": range(0, 7, 12)
+                }])
+            } else {
+                serde_json::json!([])
+            }
+        });
+        let declaration_uri = header_uri.clone();
+        server.respond_with("textDocument/declaration", move |params| {
+            let line = line_of(&params).unwrap();
+            serde_json::json!([{"uri": declaration_uri, "range": range(line as u32, 9, 13)}])
+        });
+
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // Overrides of the base method
+        let tool = FindOverridesTool {
+            symbol: "Shape::area".to_string(),
+            location_hint: Some("src/shapes.h:2:18".to_string()),
+            include_base_methods: None,
+            build_directory: None,
+            session_id: None,
+            wait_timeout: Some(0),
+        };
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await
+            .unwrap();
+        let overrides: OverridesResult = tool_result(&result);
+        assert_eq!(overrides.method.class_name.as_deref(), Some("Shape"));
+        assert_eq!(overrides.overrides.len(), 1);
+        assert_eq!(overrides.overrides[0].name, "area");
+        assert_eq!(overrides.overrides[0].class_name.as_deref(), Some("Circle"));
+        assert_eq!(overrides.overrides[0].location.file_path, source);
+        assert!(overrides.base_methods.is_none());
+
+        // The project-relative hint is looked up in the project
+        let params = server.received_params("textDocument/declaration")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["textDocument"]["uri"], header_uri);
+
+        // Base methods of the override
+        let tool = FindOverridesTool {
+            symbol: "Circle::area".to_string(),
+            location_hint: Some(format!("{}:5:10", header.display())),
+            include_base_methods: Some(true),
+            ..tool
+        };
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await
+            .unwrap();
+        let overrides: OverridesResult = tool_result(&result);
+        assert_eq!(overrides.method.class_name.as_deref(), Some("Circle"));
+        let base_methods = overrides.base_methods.unwrap();
+        assert_eq!(base_methods.len(), 1);
+        assert_eq!(base_methods[0].class_name.as_deref(), Some("Shape"));
+        assert_eq!(base_methods[0].detail.as_deref(), Some("double () const"));
+        assert_eq!(base_methods[0].location.range.start.line, 1);
+    }
+}
@@ -0,0 +1,504 @@
+//! clang-format style tool
+//!
+//! Resolves the clang-format style that applies to a file the way clang-format
+//! does: the nearest `.clang-format` or `_clang-format` up the directory tree,
+//! merged with its parents when it sets `InheritParentConfig`, on top of the
+//! predefined style named by `BasedOnStyle`. Only the YAML subset used by
+//! clang-format configurations is understood: top-level options, one level of
+//! nested options (`BraceWrapping`) and per-language documents.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::ProjectWorkspace;
+
+/// Configuration file names, in the order clang-format looks for them
+const CONFIG_FILE_NAMES: &[&str] = &[".clang-format", "_clang-format"];
+
+/// Style clang-format uses when no configuration file is found
+const FALLBACK_STYLE: &str = "LLVM";
+
+#[mcp_tool(
+    name = "get_format_style",
+    description = "Report the effective clang-format style of a C++ file: locates the applicable \
+                   .clang-format files up the directory tree, resolves BasedOnStyle and \
+                   InheritParentConfig, and returns the key settings as structured fields.
+
+                   🎯 USE CASES:
+                   • Generate code that matches the project's formatting without a separate format pass
+                   • Check indentation, brace placement and column limit before editing a file
+                   • Find out which .clang-format governs a directory
+
+                   📋 OUTPUT:
+                   • config_files: .clang-format files that apply, outermost first (empty when none)
+                   • based_on_style: Predefined style the configuration starts from (LLVM when unset)
+                   • language: Configuration section used (Cpp, or ObjC for .m/.mm files)
+                   • style: indent_width, column_limit (0 = no limit), use_tab, tab_width,
+                     break_before_braces, pointer_alignment, derive_pointer_alignment,
+                     access_modifier_offset, namespace_indentation
+                   • options: All options set explicitly by the configuration files, nested options
+                     as \"BraceWrapping.AfterClass\"
+                   • disable_format: true when formatting is disabled for the file
+                   • note: Present when no configuration file applies
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative); it does not need to exist yet"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetFormatStyleTool {
+    /// File to report the style for. Absolute paths are used as-is, relative
+    /// paths are resolved against the project root. The file does not need to
+    /// exist: the style of a new file is that of its directory.
+    pub file: String,
+}
+
+/// Key settings of a clang-format style
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatStyle {
+    pub indent_width: u32,
+    /// Maximum line length; 0 means no limit
+    pub column_limit: u32,
+    pub use_tab: String,
+    pub tab_width: u32,
+    pub break_before_braces: String,
+    pub pointer_alignment: String,
+    /// Whether the pointer alignment is taken from the file's existing code
+    pub derive_pointer_alignment: bool,
+    pub access_modifier_offset: i32,
+    pub namespace_indentation: String,
+}
+
+impl FormatStyle {
+    /// Settings of a predefined style, if the name is known
+    fn predefined(name: &str) -> Option<Self> {
+        let llvm = Self {
+            indent_width: 2,
+            column_limit: 80,
+            use_tab: "Never".to_string(),
+            tab_width: 8,
+            break_before_braces: "Attach".to_string(),
+            pointer_alignment: "Right".to_string(),
+            derive_pointer_alignment: false,
+            access_modifier_offset: -2,
+            namespace_indentation: "None".to_string(),
+        };
+        let style = match name.to_ascii_lowercase().as_str() {
+            "llvm" => llvm,
+            "google" => Self {
+                pointer_alignment: "Left".to_string(),
+                derive_pointer_alignment: true,
+                access_modifier_offset: -1,
+                ..llvm
+            },
+            "chromium" => Self {
+                pointer_alignment: "Left".to_string(),
+                access_modifier_offset: -1,
+                ..llvm
+            },
+            "mozilla" => Self {
+                break_before_braces: "Mozilla".to_string(),
+                pointer_alignment: "Left".to_string(),
+                ..llvm
+            },
+            "webkit" => Self {
+                indent_width: 4,
+                column_limit: 0,
+                break_before_braces: "WebKit".to_string(),
+                pointer_alignment: "Left".to_string(),
+                access_modifier_offset: -4,
+                namespace_indentation: "Inner".to_string(),
+                ..llvm
+            },
+            "microsoft" => Self {
+                indent_width: 4,
+                column_limit: 120,
+                break_before_braces: "Custom".to_string(),
+                access_modifier_offset: -4,
+                ..llvm
+            },
+            "gnu" => Self {
+                column_limit: 79,
+                break_before_braces: "GNU".to_string(),
+                ..llvm
+            },
+            _ => return None,
+        };
+        Some(style)
+    }
+
+    /// Override the settings with explicitly configured options
+    fn apply(&mut self, options: &BTreeMap<String, String>) {
+        for (key, value) in options {
+            match key.as_str() {
+                "IndentWidth" => set_parsed(&mut self.indent_width, value),
+                "ColumnLimit" => set_parsed(&mut self.column_limit, value),
+                "UseTab" => self.use_tab = value.clone(),
+                "TabWidth" => set_parsed(&mut self.tab_width, value),
+                "BreakBeforeBraces" => self.break_before_braces = value.clone(),
+                "PointerAlignment" => self.pointer_alignment = value.clone(),
+                "DerivePointerAlignment" => set_parsed(&mut self.derive_pointer_alignment, value),
+                "AccessModifierOffset" => set_parsed(&mut self.access_modifier_offset, value),
+                "NamespaceIndentation" => self.namespace_indentation = value.clone(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Replace a setting with a parsed option value, keeping it when the value is malformed
+fn set_parsed<T: std::str::FromStr>(setting: &mut T, value: &str) {
+    if let Ok(parsed) = value.parse() {
+        *setting = parsed;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormatStyleResult {
+    pub file: PathBuf,
+    /// Configuration files that apply, outermost first
+    pub config_files: Vec<PathBuf>,
+    pub based_on_style: String,
+    pub language: String,
+    pub style: FormatStyle,
+    /// Options set explicitly by the configuration files
+    pub options: BTreeMap<String, String>,
+    pub disable_format: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+}
+
+/// Remove a trailing `# comment` outside of quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('#', None) if index == 0 || line[..index].ends_with(char::is_whitespace) => {
+                return &line[..index];
+            }
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Remove the quotes around a scalar value
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['\'', '"'] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Parse the documents of a clang-format configuration into flat option maps
+fn parse_documents(text: &str) -> Vec<BTreeMap<String, String>> {
+    let mut documents = vec![BTreeMap::new()];
+    // Top-level key whose value is a nested block, the block's indentation and its list items
+    let mut parent: Option<(String, Option<usize>, Vec<String>)> = None;
+
+    fn flush(
+        parent: &mut Option<(String, Option<usize>, Vec<String>)>,
+        document: &mut BTreeMap<String, String>,
+    ) {
+        if let Some((key, _, items)) = parent.take()
+            && !items.is_empty()
+        {
+            document.insert(key, format!("[{}]", items.join(", ")));
+        }
+    }
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed == "---" || trimmed == "..." {
+            flush(&mut parent, documents.last_mut().unwrap());
+            if trimmed == "---" && !documents.last().unwrap().is_empty() {
+                documents.push(BTreeMap::new());
+            }
+            continue;
+        }
+        let content = strip_comment(line).trim_end();
+        if content.trim().is_empty() {
+            continue;
+        }
+        let document = documents.last_mut().unwrap();
+        let indent = content.len() - content.trim_start().len();
+        let content = content.trim_start();
+
+        if indent == 0 {
+            flush(&mut parent, document);
+            let Some((key, value)) = content.split_once(':') else {
+                continue;
+            };
+            let value = unquote(value);
+            if value.is_empty() {
+                parent = Some((key.trim().to_string(), None, Vec::new()));
+            } else {
+                document.insert(key.trim().to_string(), value.to_string());
+            }
+            continue;
+        }
+
+        let Some((key, block_indent, items)) = parent.as_mut() else {
+            continue;
+        };
+        let block_indent = *block_indent.get_or_insert(indent);
+        if indent != block_indent {
+            // Deeper levels (e.g. the maps of IncludeCategories) are not reported
+            continue;
+        }
+        if let Some(item) = content.strip_prefix('-') {
+            if !item.contains(':') {
+                items.push(unquote(item).to_string());
+            }
+        } else if let Some((sub_key, value)) = content.split_once(':') {
+            let value = unquote(value);
+            if !value.is_empty() {
+                document.insert(format!("{}.{}", key, sub_key.trim()), value.to_string());
+            }
+        }
+    }
+    flush(&mut parent, documents.last_mut().unwrap());
+    documents.retain(|document| !document.is_empty());
+    documents
+}
+
+/// Options of a configuration that apply to a language: the default document
+/// without `Language`, overridden by the document of the language
+fn select_language(
+    documents: Vec<BTreeMap<String, String>>,
+    language: &str,
+) -> BTreeMap<String, String> {
+    let mut options = BTreeMap::new();
+    let (default, specific): (Vec<_>, Vec<_>) = documents
+        .into_iter()
+        .partition(|document| !document.contains_key("Language"));
+    for document in default.into_iter().take(1).chain(
+        specific
+            .into_iter()
+            .filter(|document| document["Language"] == language)
+            .take(1),
+    ) {
+        options.extend(document);
+    }
+    options.remove("Language");
+    options
+}
+
+/// Whether a configuration continues with the one of its parent directory
+fn inherits_parent(options: &BTreeMap<String, String>) -> bool {
+    options
+        .get("InheritParentConfig")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+        || options
+            .get("BasedOnStyle")
+            .is_some_and(|value| value.eq_ignore_ascii_case("InheritParentConfig"))
+}
+
+/// clang-format language of a file
+fn file_language(file: &Path) -> &'static str {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("m" | "mm") => "ObjC",
+        _ => "Cpp",
+    }
+}
+
+/// Resolve the clang-format style of a file
+///
+/// Returns an error message when a configuration file cannot be read.
+pub fn resolve_format_style(file: &Path) -> Result<FormatStyleResult, String> {
+    let language = file_language(file);
+    let mut chain = Vec::new();
+    for dir in file.ancestors().skip(1) {
+        let Some(config_file) = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        else {
+            continue;
+        };
+        let text = std::fs::read_to_string(&config_file)
+            .map_err(|e| format!("Failed to read {}: {}", config_file.display(), e))?;
+        let options = select_language(parse_documents(&text), language);
+        let inherits = inherits_parent(&options);
+        chain.push((config_file, options));
+        if !inherits {
+            break;
+        }
+    }
+    chain.reverse();
+
+    let mut options = BTreeMap::new();
+    for (_, config_options) in &chain {
+        options.extend(config_options.clone());
+    }
+    options.remove("InheritParentConfig");
+    if options
+        .get("BasedOnStyle")
+        .is_some_and(|value| value.eq_ignore_ascii_case("InheritParentConfig"))
+    {
+        options.remove("BasedOnStyle");
+    }
+
+    let requested_style = options.get("BasedOnStyle").map(String::as_str);
+    let (based_on_style, mut style) = match requested_style.and_then(FormatStyle::predefined) {
+        Some(style) => (requested_style.unwrap().to_string(), style),
+        None => (
+            FALLBACK_STYLE.to_string(),
+            FormatStyle::predefined(FALLBACK_STYLE).unwrap(),
+        ),
+    };
+    style.apply(&options);
+
+    let disable_format = options
+        .get("DisableFormat")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    let note = if chain.is_empty() {
+        Some(format!(
+            "No .clang-format file applies to this file; clang-format falls back to the \
+             {FALLBACK_STYLE} style."
+        ))
+    } else {
+        requested_style
+            .filter(|name| FormatStyle::predefined(name).is_none())
+            .map(|unknown| {
+                format!(
+                    "BasedOnStyle '{unknown}' is not a predefined style; settings not set \
+                     explicitly are those of {FALLBACK_STYLE}."
+                )
+            })
+    };
+
+    Ok(FormatStyleResult {
+        file: file.to_path_buf(),
+        config_files: chain.into_iter().map(|(path, _)| path).collect(),
+        based_on_style,
+        language: language.to_string(),
+        style,
+        options,
+        disable_format,
+        note,
+    })
+}
+
+impl GetFormatStyleTool {
+    #[instrument(name = "get_format_style", skip(self, workspace))]
+    pub fn call_tool(&self, workspace: &ProjectWorkspace) -> Result<CallToolResult, CallToolError> {
+        let file = utils::resolve_project_path(workspace, &self.file);
+
+        let result = resolve_format_style(&file).map_err(|e| {
+            CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })?;
+        info!(
+            "Format style of {}: {} from {:?}",
+            file.display(),
+            result.based_on_style,
+            result.config_files
+        );
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[test]
+    fn test_parse_documents() {
+        let documents = parse_documents(
+            "# Project style\n\
+             BasedOnStyle: Google  # with tweaks\n\
+             IndentWidth: 4\n\
+             CommentPragmas: '^ IWYU pragma:'\n\
+             BraceWrapping:\n\
+             \x20 AfterClass: true\n\
+             \x20 AfterFunction: false\n\
+             StatementMacros:\n\
+             \x20 - Q_UNUSED\n\
+             \x20 - QT_REQUIRE_VERSION\n\
+             IncludeCategories:\n\
+             \x20 - Regex: '^<.*'\n\
+             \x20   Priority: 1\n\
+             ---\n\
+             Language: Cpp\n\
+             ColumnLimit: 100\n\
+             ---\n\
+             Language: JavaScript\n\
+             ColumnLimit: 120\n\
+             ...\n",
+        );
+        assert_eq!(documents.len(), 3);
+        assert_eq!(documents[0]["BasedOnStyle"], "Google");
+        assert_eq!(documents[0]["CommentPragmas"], "^ IWYU pragma:");
+        assert_eq!(documents[0]["BraceWrapping.AfterClass"], "true");
+        assert_eq!(
+            documents[0]["StatementMacros"],
+            "[Q_UNUSED, QT_REQUIRE_VERSION]"
+        );
+        assert!(!documents[0].contains_key("IncludeCategories"));
+
+        let options = select_language(documents, "Cpp");
+        assert_eq!(options["ColumnLimit"], "100");
+        assert_eq!(options["IndentWidth"], "4");
+        assert!(!options.contains_key("Language"));
+    }
+
+    #[test]
+    fn test_resolves_inherited_configuration() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            (
+                ".clang-format",
+                "BasedOnStyle: Google\nIndentWidth: 4\n---\nLanguage: Cpp\nColumnLimit: 100\n",
+            ),
+            (
+                "src/legacy/.clang-format",
+                "InheritParentConfig: true\nPointerAlignment: Right\nDerivePointerAlignment: false\n",
+            ),
+            ("src/legacy/old.cpp", ""),
+        ]);
+
+        let tool = GetFormatStyleTool {
+            file: "src/legacy/old.cpp".to_string(),
+        };
+        let result = tool.call_tool(&workspace).unwrap();
+        let result: FormatStyleResult = tool_result(&result);
+        assert_eq!(result.config_files.len(), 2);
+        assert!(result.config_files[1].ends_with("src/legacy/.clang-format"));
+        assert_eq!(result.based_on_style, "Google");
+        assert_eq!(
+            result.style,
+            FormatStyle {
+                indent_width: 4,
+                column_limit: 100,
+                pointer_alignment: "Right".to_string(),
+                derive_pointer_alignment: false,
+                ..FormatStyle::predefined("Google").unwrap()
+            }
+        );
+        assert!(!result.options.contains_key("InheritParentConfig"));
+        assert!(result.note.is_none());
+
+        // A new file in a directory without configuration gets the fallback style
+        let result = resolve_format_style(Path::new("/nonexistent/dir/new.cpp")).unwrap();
+        assert!(result.config_files.is_empty());
+        assert_eq!(result.style, FormatStyle::predefined("LLVM").unwrap());
+        assert!(result.note.is_some());
+    }
+}
@@ -0,0 +1,392 @@
+//! Function metrics tool
+//!
+//! Reports simple size and complexity metrics for the functions of a file:
+//! line counts from the document symbol ranges, parameter counts from the
+//! signatures clangd reports, and the brace nesting depth of each body.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::io::file_buffer::read_source_file;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    collect_functions, get_document_symbols,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, uri_from_pathbuf};
+
+#[mcp_tool(
+    name = "get_function_metrics",
+    description = "Compute size and complexity metrics for the functions defined in a C++ file: \
+                   lines of code, number of parameters and nesting depth, one entry per function.
+
+                   🎯 PRIMARY USE CASES:
+                   Code health assessments • Finding overly long or deeply nested functions • \
+                   Spotting functions with too many parameters before refactoring
+
+                   📋 OUTPUT:
+                   • file: Analyzed file
+                   • functions: Functions with a body, in document order, each with
+                     - name / qualified_name / signature
+                     - location: Full range of the function
+                     - lines: Lines spanned by the function, signature included
+                     - code_lines: Lines containing code (blank and comment-only lines excluded)
+                     - parameter_count: Number of parameters from the signature (omitted when clangd
+                       reports no signature)
+                     - max_nesting_depth: Deepest nesting of braced blocks inside the body (0 for a
+                       flat body; unbraced if/for bodies are not counted)
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • function: Optional name - only report functions with this name or qualified name
+                     suffix (e.g. \"add\" or \"Calculator::add\")
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FunctionMetricsTool {
+    /// File to analyze. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Only report functions with this name or qualified name suffix
+    /// (e.g. "add" or "Calculator::add"). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Metrics of one function
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionMetrics {
+    pub name: String,
+    pub qualified_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    pub location: FileLocation,
+    pub lines: u32,
+    pub code_lines: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter_count: Option<u32>,
+    pub max_nesting_depth: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionMetricsResult {
+    pub file: PathBuf,
+    pub functions: Vec<FunctionMetrics>,
+}
+
+impl FunctionMetricsTool {
+    /// Check a function against the optional name filter
+    fn matches_filter(&self, qualified_name: &str) -> bool {
+        self.function.as_deref().is_none_or(|filter| {
+            let filter = filter.trim_start_matches("::");
+            qualified_name == filter || qualified_name.ends_with(&format!("::{filter}"))
+        })
+    }
+
+    #[instrument(
+        name = "get_function_metrics",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        info!("Computing function metrics for {}", file_path.display());
+
+        let content = read_source_file(&file_path).map_err(|e| {
+            CallToolError::new(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read '{}': {}", file_path.display(), e),
+            ))
+        })?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        let symbols = get_document_symbols(&component_session, uri_from_pathbuf(&file_path))
+            .await
+            .map_err(CallToolError::from)?;
+
+        let functions = collect_functions(&symbols)
+            .into_iter()
+            .filter_map(|(function, container_path)| {
+                let mut qualified_parts = container_path;
+                qualified_parts.push(function.name.clone());
+                let qualified_name = qualified_parts.join("::");
+                if !self.matches_filter(&qualified_name) {
+                    return None;
+                }
+
+                let start = function.range.start.line as usize;
+                let end = (function.range.end.line as usize).min(lines.len().saturating_sub(1));
+                let body = BodyMetrics::scan(lines.get(start..=end)?)?;
+                Some(FunctionMetrics {
+                    name: function.name.clone(),
+                    qualified_name,
+                    signature: function.detail.clone(),
+                    location: FileLocation {
+                        file_path: file_path.clone(),
+                        range: function.range.into(),
+                    },
+                    lines: function.range.end.line - function.range.start.line + 1,
+                    code_lines: body.code_lines,
+                    parameter_count: function.detail.as_deref().and_then(count_parameters),
+                    max_nesting_depth: body.max_nesting_depth,
+                })
+            })
+            .collect();
+
+        let result = FunctionMetricsResult {
+            file: file_path,
+            functions,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+/// Metrics read from the source text of a function
+#[derive(Debug, PartialEq)]
+struct BodyMetrics {
+    code_lines: u32,
+    max_nesting_depth: u32,
+}
+
+impl BodyMetrics {
+    /// Scan the lines of a function, skipping comments and string literals
+    ///
+    /// Returns `None` for declarations without a body.
+    fn scan(lines: &[&str]) -> Option<Self> {
+        #[derive(PartialEq)]
+        enum State {
+            Code,
+            BlockComment,
+            Literal(char),
+        }
+
+        let mut state = State::Code;
+        let mut code_lines = 0;
+        let mut depth: u32 = 0;
+        let mut max_depth: u32 = 0;
+        for line in lines {
+            let mut has_code = false;
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                match state {
+                    State::BlockComment => {
+                        if c == '*' && chars.peek() == Some(&'/') {
+                            chars.next();
+                            state = State::Code;
+                        }
+                    }
+                    State::Literal(quote) => {
+                        if c == '\\' {
+                            chars.next();
+                        } else if c == quote {
+                            state = State::Code;
+                        }
+                    }
+                    State::Code => match c {
+                        '/' if chars.peek() == Some(&'/') => break,
+                        '/' if chars.peek() == Some(&'*') => {
+                            chars.next();
+                            state = State::BlockComment;
+                        }
+                        c if c.is_whitespace() => {}
+                        c => {
+                            has_code = true;
+                            match c {
+                                '"' | '\'' => state = State::Literal(c),
+                                '{' => {
+                                    depth += 1;
+                                    max_depth = max_depth.max(depth);
+                                }
+                                '}' => depth = depth.saturating_sub(1),
+                                _ => {}
+                            }
+                        }
+                    },
+                }
+            }
+            // Unterminated literals do not span lines
+            if matches!(state, State::Literal(_)) {
+                state = State::Code;
+            }
+            if has_code {
+                code_lines += 1;
+            }
+        }
+
+        // The braces of the body itself do not count as nesting
+        (max_depth > 0).then(|| Self {
+            code_lines,
+            max_nesting_depth: max_depth - 1,
+        })
+    }
+}
+
+/// Count the parameters of a signature reported by clangd, e.g. "int (int, int) const"
+///
+/// The parameter list is the first parenthesized group outside template
+/// arguments; commas inside nested brackets do not separate parameters.
+fn count_parameters(signature: &str) -> Option<u32> {
+    let mut angle_depth = 0;
+    let mut list_start = None;
+    for (index, c) in signature.char_indices() {
+        match c {
+            '<' => angle_depth += 1,
+            '>' => angle_depth -= 1,
+            '(' if angle_depth == 0 => {
+                list_start = Some(index + 1);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let list = &signature[list_start?..];
+
+    let mut depth = 0;
+    let mut count = 0;
+    let mut current_empty = true;
+    let mut current = String::new();
+    for c in list.chars() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                if !current_empty && current.trim() != "void" {
+                    count += 1;
+                }
+                return Some(count);
+            }
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                count += 1;
+                current_empty = true;
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            current_empty = false;
+        }
+        current.push(c);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    const SOURCE: &str = "namespace app {\n\
+                          int Calculator::add(int a, int b) {\n\
+                          \x20   // Sum \"{\" of both\n\
+                          \x20   for (int i = 0; i < 1; ++i) {\n\
+                          \x20       if (a > b) { return a; }\n\
+                          \x20   }\n\
+                          \n\
+                          \x20   return a + b;\n\
+                          }\n\
+                          void reset();\n\
+                          }\n";
+
+    #[test]
+    fn test_count_parameters() {
+        assert_eq!(count_parameters("int (int, int) const"), Some(2));
+        assert_eq!(count_parameters("void ()"), Some(0));
+        assert_eq!(count_parameters("void (void)"), Some(0));
+        assert_eq!(
+            count_parameters("std::function<void (int)> (std::map<int, int>, void (*)(int, int))"),
+            Some(2)
+        );
+        assert_eq!(count_parameters("int (const char *, ...)"), Some(2));
+        assert_eq!(count_parameters("int"), None);
+    }
+
+    #[tokio::test]
+    async fn test_function_metrics_with_mock_clangd() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/calc.cpp", SOURCE)]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "app",
+                "kind": 3,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 10, "character": 1}},
+                "selectionRange": {"start": {"line": 0, "character": 10}, "end": {"line": 0, "character": 13}},
+                "children": [{
+                    "name": "Calculator::add",
+                    "detail": "int (int, int)",
+                    "kind": 6,
+                    "range": {"start": {"line": 1, "character": 0}, "end": {"line": 8, "character": 1}},
+                    "selectionRange": {"start": {"line": 1, "character": 16}, "end": {"line": 1, "character": 19}}
+                }, {
+                    "name": "reset",
+                    "detail": "void ()",
+                    "kind": 12,
+                    "range": {"start": {"line": 9, "character": 0}, "end": {"line": 9, "character": 12}},
+                    "selectionRange": {"start": {"line": 9, "character": 5}, "end": {"line": 9, "character": 10}}
+                }]
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = FunctionMetricsTool {
+            file: "src/calc.cpp".to_string(),
+            function: None,
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool
+            .call_tool(Arc::clone(&component_session), &workspace)
+            .await
+            .unwrap();
+        let result: FunctionMetricsResult = tool_result(&result);
+
+        // The declaration without a body is left out
+        assert_eq!(result.functions.len(), 1);
+        let add = &result.functions[0];
+        assert_eq!(add.qualified_name, "app::Calculator::add");
+        assert_eq!(add.lines, 8);
+        assert_eq!(add.code_lines, 6);
+        assert_eq!(add.parameter_count, Some(2));
+        assert_eq!(add.max_nesting_depth, 2);
+
+        let tool = FunctionMetricsTool {
+            function: Some("Calculator::sub".to_string()),
+            ..tool
+        };
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: FunctionMetricsResult = tool_result(&result);
+        assert!(result.functions.is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! Header includers tool
+//!
+//! Reports which files `#include` a header: every directive in the project's
+//! translation units and their headers that resolves to the requested file.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::{IncluderSearch, find_includers};
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Include depth followed from each translation unit
+const MAX_DEPTH: u32 = 50;
+
+/// Maximum number of files in the include graph of one translation unit
+const MAX_NODES: usize = 2000;
+
+#[mcp_tool(
+    name = "find_header_includers",
+    description = "Find all files that #include a C++ header. Follows the #include directives of every \
+                   translation unit in the compilation database, resolving them with that unit's include \
+                   directories, and reports each directive that resolves to the header.
+
+                   🎯 USE CASES:
+                   • Refactoring impact: which files are affected when a header changes or moves
+                   • Removing or splitting a header: every place that must be updated
+                   • Build impact: which translation units recompile when the header changes
+
+                   📋 OUTPUT:
+                   • includers: Files including the header directly, with the 1-based line of the directive
+                   • translation_units: Source files that include the header directly or transitively
+                   • translation_units_scanned: Number of compilation database entries searched
+                   • incomplete: An include graph hit its depth or size limit, includers may be missing
+
+                   Directives are read textually: #if/#ifdef conditions are not evaluated. Headers that
+                   are not reachable from any translation unit are not searched.
+
+                   INPUT REQUIREMENTS:
+                   • header: Header file (absolute or project-relative)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindHeaderIncludersTool {
+    /// Header to find the includers of. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub header: String,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeaderIncludersResult {
+    /// Header the includers were searched for
+    pub header: PathBuf,
+    #[serde(flatten)]
+    pub search: IncluderSearch,
+}
+
+impl FindHeaderIncludersTool {
+    #[instrument(
+        name = "find_header_includers",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let header_path = utils::resolve_project_path(workspace, &self.header);
+        if !header_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", header_path.display()),
+            )));
+        }
+
+        let entries = component_session.compilation_database().entries().to_vec();
+        info!(
+            "Searching {} compilation database entries for includers of {}",
+            entries.len(),
+            header_path.display()
+        );
+
+        let project_root = workspace
+            .project_root_path
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.project_root_path.clone());
+        let header = header_path.clone();
+        // Reads every header of every translation unit; keep it off the async workers
+        let search = tokio::task::spawn_blocking(move || {
+            find_includers(&entries, &header, &project_root, MAX_DEPTH, MAX_NODES)
+        })
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+        let result = HeaderIncludersResult {
+            header: header_path,
+            search,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
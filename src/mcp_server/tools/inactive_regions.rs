@@ -0,0 +1,296 @@
+//! Inactive preprocessor regions tool
+//!
+//! Lists the `#if`/`#ifdef` branches the preprocessor skips when clangd parses
+//! a file, as reported by clangd's `textDocument/inactiveRegions` extension,
+//! so dead branches can be left out of any further analysis.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::read_source_file;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::uri_from_pathbuf;
+
+/// Default time to wait for clangd to parse the file
+const DEFAULT_PARSE_WAIT_TIMEOUT_SECS: u64 = 10;
+
+/// Time allowed for the inactive regions of a parsed file to arrive; clangd
+/// publishes them right after diagnostics
+const INACTIVE_REGIONS_GRACE: Duration = Duration::from_secs(2);
+
+/// Maximum length of the first line shown for a region
+const MAX_PREVIEW_CHARS: usize = 120;
+
+#[mcp_tool(
+    name = "get_inactive_regions",
+    description = "List the preprocessor regions of a C++ file that are inactive (skipped) under its \
+                   compile command: #if/#ifdef/#else branches whose condition is false. Code in these \
+                   regions is not compiled, has no symbols and no cross-references; skip it when \
+                   reading or analyzing the file.
+
+                   🎯 USE CASES:
+                   • Tell live from dead branches in heavily conditional code (platform, feature flags)
+                   • Explain why symbols in part of a file cannot be found
+                   • Check which configuration a file is compiled in
+
+                   📋 OUTPUT:
+                   • available: Whether clangd reported inactive regions (requires clangd 17 or later)
+                   • regions: Inactive line ranges (1-based, inclusive) with their first line of code
+                   • inactive_lines: Total number of inactive lines
+                   • note: Why regions are unavailable, when they are
+
+                   Headers are evaluated with the compile command of a translation unit including them.
+                   Use get_active_macros to see the macros deciding the branches.
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • wait_timeout: Optional seconds to wait for clangd to parse the file (default: 10s)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetInactiveRegionsTool {
+    /// File to inspect. Absolute paths are used as-is, relative paths are
+    /// resolved against the project root.
+    pub file: String,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for clangd to parse the file (default: 10s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A run of lines skipped by the preprocessor
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InactiveRegion {
+    /// First inactive line (1-based)
+    pub start_line: u32,
+    /// Last inactive line (1-based, inclusive)
+    pub end_line: u32,
+    /// First non-blank line of the region, trimmed
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub preview: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InactiveRegionsResult {
+    /// File that was inspected
+    pub file: PathBuf,
+    pub available: bool,
+    pub regions: Vec<InactiveRegion>,
+    pub inactive_lines: u32,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+}
+
+/// Convert an LSP range into inclusive 1-based lines
+///
+/// clangd ends a region either at the end of its last line or at the start of
+/// the following one.
+fn region_lines(range: &lsp_types::Range) -> (u32, u32) {
+    let end = if range.end.character == 0 && range.end.line > range.start.line {
+        range.end.line
+    } else {
+        range.end.line + 1
+    };
+    (range.start.line + 1, end)
+}
+
+/// First non-blank line of a 1-based inclusive line range
+fn region_preview(lines: &[&str], start_line: u32, end_line: u32) -> Option<String> {
+    lines
+        .iter()
+        .skip(start_line as usize - 1)
+        .take((end_line + 1 - start_line) as usize)
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(MAX_PREVIEW_CHARS).collect())
+}
+
+impl GetInactiveRegionsTool {
+    #[instrument(
+        name = "get_inactive_regions",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let file_path = file_path.canonicalize().map_err(|_| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            ))
+        })?;
+
+        let timeout =
+            Duration::from_secs(self.wait_timeout.unwrap_or(DEFAULT_PARSE_WAIT_TIMEOUT_SECS));
+        let parsed = component_session
+            .wait_until_file_parsed(&file_path, timeout)
+            .await
+            .map_err(AnalyzerError::from)?;
+
+        let uri = uri_from_pathbuf(&file_path);
+        let monitor = component_session
+            .lsp_session()
+            .await
+            .file_status_monitor()
+            .clone();
+        let regions = if parsed {
+            monitor
+                .wait_for_inactive_regions(&uri, INACTIVE_REGIONS_GRACE)
+                .await
+        } else {
+            warn!(
+                "{} not parsed within {:?}, using the last reported regions",
+                file_path.display(),
+                timeout
+            );
+            monitor.inactive_regions(&uri)
+        };
+
+        let result = match regions {
+            Some(ranges) => {
+                let content = read_source_file(&file_path).unwrap_or_default();
+                let lines: Vec<&str> = content.lines().collect();
+                let regions: Vec<InactiveRegion> = ranges
+                    .iter()
+                    .map(|range| {
+                        let (start_line, end_line) = region_lines(range);
+                        InactiveRegion {
+                            start_line,
+                            end_line,
+                            preview: region_preview(&lines, start_line, end_line),
+                        }
+                    })
+                    .collect();
+                let inactive_lines = regions
+                    .iter()
+                    .map(|region| region.end_line + 1 - region.start_line)
+                    .sum();
+                info!(
+                    "{} inactive regions in {}",
+                    regions.len(),
+                    file_path.display()
+                );
+                InactiveRegionsResult {
+                    file: file_path,
+                    available: true,
+                    regions,
+                    inactive_lines,
+                    note: None,
+                }
+            }
+            None => InactiveRegionsResult {
+                file: file_path,
+                available: false,
+                regions: Vec::new(),
+                inactive_lines: 0,
+                note: Some(if parsed {
+                    "clangd did not report inactive regions; this requires clangd 17 or later."
+                        .to_string()
+                } else {
+                    "clangd did not finish parsing the file within the timeout; retry with a \
+                     longer wait_timeout."
+                        .to_string()
+                }),
+            },
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::file_status::INACTIVE_REGIONS_METHOD;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_reports_inactive_regions() {
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/main.cpp",
+            "#ifdef _WIN32\n\n  int windows_only();\n#else\nint posix();\n#endif\n",
+        )]);
+        let main_cpp = workspace
+            .project_root_path
+            .join("src/main.cpp")
+            .canonicalize()
+            .unwrap();
+        let uri = uri_from_pathbuf(&main_cpp);
+
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        // Open the file, then report its parse
+        let opened = component_session
+            .wait_until_file_parsed(&main_cpp, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(!opened);
+        server.notify(
+            "textDocument/publishDiagnostics",
+            serde_json::json!({"uri": uri.as_str(), "diagnostics": []}),
+        );
+        server.notify(
+            INACTIVE_REGIONS_METHOD,
+            serde_json::json!({
+                "textDocument": {"uri": uri.as_str()},
+                "regions": [{
+                    "start": {"line": 1, "character": 0},
+                    "end": {"line": 2, "character": 21}
+                }]
+            }),
+        );
+
+        let tool: GetInactiveRegionsTool =
+            serde_json::from_value(serde_json::json!({ "file": "src/main.cpp" })).unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: InactiveRegionsResult = tool_result(&result);
+
+        assert!(result.available);
+        assert_eq!(result.regions.len(), 1);
+        assert_eq!(result.regions[0].start_line, 2);
+        assert_eq!(result.regions[0].end_line, 3);
+        assert_eq!(
+            result.regions[0].preview.as_deref(),
+            Some("int windows_only();")
+        );
+        assert_eq!(result.inactive_lines, 2);
+
+        let initialize = &server.received_params("initialize")[0];
+        assert_eq!(
+            initialize.as_ref().unwrap()["capabilities"]["textDocument"]["inactiveRegionsCapabilities"]
+                ["inactiveRegions"],
+            true
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Include graph tool
+//!
+//! Reports the transitive include graph of a file: every header it pulls in,
+//! the include relationships between them, include cycles, and which headers
+//! are system headers.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::{IncludeGraph, IncludeGraphBuilder};
+use crate::project::include_paths::IncludeSearchPaths;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Include distance followed when no depth is requested
+const DEFAULT_MAX_DEPTH: u32 = 5;
+
+/// Largest accepted include depth
+const MAX_DEPTH_LIMIT: u32 = 50;
+
+/// Maximum number of files reported in one graph
+const MAX_NODES: usize = 2000;
+
+#[mcp_tool(
+    name = "get_include_graph",
+    description = "Compute the transitive include graph of a C++ source or header file. Follows #include \
+                   directives recursively, resolving them with the include directories of the file's \
+                   compile command, up to a depth limit.
+
+                   🎯 USE CASES:
+                   • Build-time analysis: which headers a translation unit pulls in
+                   • Dependency analysis: how a file reaches a given header
+                   • Finding include cycles
+
+                   📋 OUTPUT:
+                   • nodes: Files with kind (root / project / system), shortest include depth and
+                     whether their includes were followed
+                   • edges: Include relationships (from, to, 1-based line), cycle edges flagged
+                   • cycles: Include cycles as file paths, first file repeated at the end
+                   • unresolved: Directives whose header was not found (compiler builtin directories
+                     such as the standard library are not known from the compile command)
+                   • depth_limit_reached / truncated: Whether the graph is incomplete
+
+                   Directives are read textually: #if/#ifdef conditions are not evaluated.
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • max_depth: Optional include depth to follow (default: 5, max: 50)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct GetIncludeGraphTool {
+    /// File to compute the include graph of. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Include depth to follow. Files at this distance from `file` are listed
+    /// but their includes are not followed. Default: 5, maximum: 50.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IncludeGraphResult {
+    /// File the graph was computed for
+    pub file: PathBuf,
+    /// Include depth followed
+    pub max_depth: u32,
+    /// Whether the file has its own compilation database entry; headers usually
+    /// do not, in which case only directories relative to the includers are searched
+    pub compile_command_found: bool,
+    #[serde(flatten)]
+    pub graph: IncludeGraph,
+}
+
+impl GetIncludeGraphTool {
+    /// Validate the requested depth
+    fn max_depth(&self) -> Result<u32, CallToolError> {
+        match self.max_depth {
+            None => Ok(DEFAULT_MAX_DEPTH),
+            Some(depth) if depth <= MAX_DEPTH_LIMIT => Ok(depth),
+            Some(depth) => Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("max_depth must not exceed {MAX_DEPTH_LIMIT}, got {depth}"),
+            ))),
+        }
+    }
+
+    #[instrument(name = "get_include_graph", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let max_depth = self.max_depth()?;
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        info!(
+            "Computing include graph of {} (max_depth: {})",
+            file_path.display(),
+            max_depth
+        );
+
+        let entry = component_session
+            .compilation_database()
+            .find_entry_for_file(&file_path);
+        let search_paths = entry
+            .map(IncludeSearchPaths::from_entry)
+            .unwrap_or_default();
+        let project_root = workspace
+            .project_root_path
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.project_root_path.clone());
+
+        let graph = IncludeGraphBuilder::new(&search_paths, &project_root, max_depth, MAX_NODES)
+            .build(&file_path);
+
+        let result = IncludeGraphResult {
+            file: file_path,
+            max_depth,
+            compile_command_found: entry.is_some(),
+            graph,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_depth_validation() {
+        let tool: GetIncludeGraphTool =
+            serde_json::from_str(r#"{"file": "src/main.cpp"}"#).unwrap();
+        assert_eq!(tool.max_depth().unwrap(), DEFAULT_MAX_DEPTH);
+
+        let tool = GetIncludeGraphTool {
+            max_depth: Some(MAX_DEPTH_LIMIT + 1),
+            ..tool
+        };
+        assert!(tool.max_depth().is_err());
+    }
+}
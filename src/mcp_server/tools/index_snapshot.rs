@@ -0,0 +1,211 @@
+//! Index snapshot tool
+//!
+//! Snapshots the indexing state of a build directory and reports what changed
+//! since an earlier snapshot, so the effect of a rebuild or of ongoing
+//! background indexing can be seen without re-querying every file.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::ComponentSession;
+use crate::project::index::snapshot::SnapshotFileState;
+use crate::project::index::{IndexSnapshot, IndexSnapshotDiff};
+
+#[mcp_tool(
+    name = "index_snapshot",
+    description = "Snapshot the clangd indexing state of a build directory and report what changed \
+                   since an earlier snapshot. Every call takes a new snapshot and returns its id; pass \
+                   that id as `since` in a later call to get the difference.
+
+                   🎯 USE CASES:
+                   • Seeing the effect of a rebuild or of editing headers on the index
+                   • Following background indexing without re-querying every file
+                   • Finding files that started failing to index
+
+                   📋 OUTPUT:
+                   • snapshot_id: Id of the snapshot taken by this call
+                   • files: Per-state file counts (indexed / pending / in_progress / failed)
+                   • changes (with `since`): newly_indexed, reindexed (index shard rewritten),
+                     no_longer_indexed, newly_failed, added_files and removed_files
+
+                   NOTE: Changes are tracked per compilation database file from index shard state and
+                   modification times; symbol-level differences are not reported. The most recent 16
+                   snapshots of each session are kept.
+
+                   INPUT REQUIREMENTS:
+                   • since: Optional id of an earlier snapshot of the same session
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct IndexSnapshotTool {
+    /// Id of an earlier snapshot to report changes against. Snapshot ids are
+    /// returned by previous calls of this tool for the same session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Number of files per indexing state in a snapshot
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SnapshotFileCounts {
+    pub total: usize,
+    pub indexed: usize,
+    pub pending: usize,
+    pub in_progress: usize,
+    pub failed: usize,
+}
+
+impl From<&IndexSnapshot> for SnapshotFileCounts {
+    fn from(snapshot: &IndexSnapshot) -> Self {
+        let mut counts = Self {
+            total: snapshot.files.len(),
+            ..Self::default()
+        };
+        for file in snapshot.files.values() {
+            match file.state {
+                SnapshotFileState::Indexed => counts.indexed += 1,
+                SnapshotFileState::Pending => counts.pending += 1,
+                SnapshotFileState::InProgress => counts.in_progress += 1,
+                SnapshotFileState::Failed => counts.failed += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// Changes since an earlier snapshot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotChanges {
+    /// Id of the earlier snapshot
+    pub since: String,
+    /// Seconds between the two snapshots
+    pub elapsed_seconds: f64,
+    /// Whether any change was found
+    pub changed: bool,
+    #[serde(flatten)]
+    pub diff: IndexSnapshotDiff,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexSnapshotResult {
+    pub snapshot_id: String,
+    pub files: SnapshotFileCounts,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<SnapshotChanges>,
+}
+
+impl IndexSnapshotTool {
+    #[instrument(name = "index_snapshot", skip(self, component_session))]
+    pub async fn call_tool(
+        &self,
+        component_session: &ComponentSession,
+    ) -> Result<CallToolResult, CallToolError> {
+        // Look up the earlier snapshot first so an unknown id takes no snapshot
+        let earlier = match &self.since {
+            Some(id) => Some(component_session.index_snapshot(id).ok_or_else(|| {
+                CallToolError::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "Unknown snapshot id '{}'. Snapshot ids are only valid for the session \
+                         that took them, and only the most recent snapshots are kept.",
+                        id
+                    ),
+                ))
+            })?),
+            None => None,
+        };
+
+        let snapshot = component_session.take_index_snapshot().await;
+        info!(
+            "Took index snapshot {} of {} files (since: {:?})",
+            snapshot.id,
+            snapshot.files.len(),
+            self.since
+        );
+
+        let changes = earlier.map(|earlier| {
+            let diff = earlier.diff(&snapshot);
+            SnapshotChanges {
+                since: earlier.id.clone(),
+                elapsed_seconds: snapshot
+                    .taken_at
+                    .duration_since(earlier.taken_at)
+                    .unwrap_or_default()
+                    .as_secs_f64(),
+                changed: !diff.is_empty(),
+                diff,
+            }
+        });
+
+        let result = IndexSnapshotResult {
+            snapshot_id: snapshot.id.clone(),
+            files: SnapshotFileCounts::from(&snapshot),
+            changes,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    async fn call(tool: &IndexSnapshotTool, session: &ComponentSession) -> IndexSnapshotResult {
+        let result = tool.call_tool(session).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_diff() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: IndexSnapshotTool = serde_json::from_str("{}").unwrap();
+        let first = call(&tool, &component_session).await;
+        assert_eq!(first.files.total, 1);
+        assert!(first.changes.is_none());
+
+        let tool = IndexSnapshotTool {
+            since: Some(first.snapshot_id.clone()),
+            ..tool
+        };
+        let second = call(&tool, &component_session).await;
+        assert_ne!(second.snapshot_id, first.snapshot_id);
+        let changes = second.changes.unwrap();
+        assert_eq!(changes.since, first.snapshot_id);
+        assert!(!changes.changed);
+
+        let tool = IndexSnapshotTool {
+            since: Some("unknown".to_string()),
+            ..tool
+        };
+        assert!(tool.call_tool(&component_session).await.is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Workspace-wide indexing status tool
+//!
+//! Reports clangd background indexing progress for every build directory of the
+//! workspace in one response, together with a workspace-wide aggregate.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::index::location::IndexLocation;
+use crate::project::index::{IndexStatusView, WorkspaceIndexStatusView};
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+#[mcp_tool(
+    name = "workspace_index_status",
+    description = "Report clangd indexing progress across ALL build directories of the workspace in a \
+                   single call. Each build directory is indexed by its own clangd session; this tool \
+                   lists the progress of every one of them and a workspace-wide total.
+
+                   🎯 PRIMARY USE CASES:
+                   Multi-component projects • Deciding whether workspace-wide searches are complete \
+                   • Estimating how long until indexing finishes
+
+                   📋 OUTPUT:
+                   • workspace: Aggregate over active sessions - indexed_files, total_files,
+                     coverage_percentage, remaining_files, files_per_second, in_progress and
+                     estimated_time_remaining (longest component ETA)
+                   • components: Per build directory - session_active, session_id, the component's
+                     index status (state, coverage, ETA, queue depth and throughput) and
+                     index_location (directory, whether it is the default or configured one,
+                     read_only, static_index_file and a warning when the configured directory
+                     could not be used)
+
+                   NOTE: Build directories without an active session have not been used by any tool
+                   yet, so clangd has not started indexing them.
+
+                   INPUT REQUIREMENTS:
+                   • No parameters required"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct WorkspaceIndexStatusTool {}
+
+/// Indexing status of a single build directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComponentIndexStatus {
+    pub build_directory: PathBuf,
+    /// Whether a clangd session is running for this build directory
+    pub session_active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+    /// Where clangd keeps the index of this build directory
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub index_location: Option<IndexLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkspaceIndexStatusResult {
+    pub workspace: WorkspaceIndexStatusView,
+    pub components: Vec<ComponentIndexStatus>,
+}
+
+impl WorkspaceIndexStatusTool {
+    #[instrument(name = "workspace_index_status", skip(self, sessions, workspace))]
+    pub async fn call_tool(
+        &self,
+        sessions: &[Arc<ComponentSession>],
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Collecting index status for {} active sessions across {} components",
+            sessions.len(),
+            workspace.component_count()
+        );
+
+        let mut components = Vec::new();
+        for session in sessions {
+            components.push(ComponentIndexStatus {
+                build_directory: session.build_dir().clone(),
+                session_active: true,
+                session_id: Some(session.session_id().to_string()),
+                index_status: Some(session.get_index_status().await),
+                index_location: Some(session.index_location().clone()),
+            });
+        }
+
+        // Components discovered in the workspace that no tool has used yet
+        for build_dir in workspace.get_build_dirs() {
+            if !components.iter().any(|c| c.build_directory == build_dir) {
+                components.push(ComponentIndexStatus {
+                    build_directory: build_dir,
+                    session_active: false,
+                    session_id: None,
+                    index_status: None,
+                    index_location: None,
+                });
+            }
+        }
+
+        let result = WorkspaceIndexStatusResult {
+            workspace: WorkspaceIndexStatusView::aggregate(
+                components.iter().filter_map(|c| c.index_status.as_ref()),
+            ),
+            components,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_workspace_index_status_lists_all_components() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: WorkspaceIndexStatusTool = serde_json::from_str("{}").unwrap();
+
+        // Without sessions the component is listed as inactive
+        let result = tool.call_tool(&[], &workspace).await.unwrap();
+        let status: WorkspaceIndexStatusResult = tool_result(&result);
+        assert_eq!(status.components.len(), 1);
+        assert!(!status.components[0].session_active);
+        assert_eq!(status.workspace.total_files, 0);
+
+        let result = tool
+            .call_tool(std::slice::from_ref(&component_session), &workspace)
+            .await
+            .unwrap();
+        let status: WorkspaceIndexStatusResult = tool_result(&result);
+        assert_eq!(status.components.len(), 1);
+        assert!(status.components[0].session_active);
+        assert_eq!(
+            status.components[0].session_id.as_deref(),
+            Some(component_session.session_id())
+        );
+        assert_eq!(status.workspace.total_files, 1);
+        let index_location = status.components[0].index_location.as_ref().unwrap();
+        assert_eq!(
+            index_location.directory,
+            workspace.components[0]
+                .build_dir_path
+                .join(".cache/clangd/index")
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Symbols-by-kind listing tool
+//!
+//! Lists every symbol of the requested kinds declared in the project, whatever
+//! its name. `workspace/symbol` needs a query and ranks a limited number of
+//! fuzzy matches, so the document symbols of each project file are walked
+//! instead, a page of files at a time.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::propagate_source_overlay_blocking;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    DocumentSymbolIterator, get_document_symbols,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::project_files;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{Symbol, uri_from_pathbuf};
+
+/// Include depth followed to find the headers of the project
+const MAX_INCLUDE_DEPTH: u32 = 50;
+
+/// Maximum number of files in the include graph of one translation unit
+const MAX_INCLUDE_NODES: usize = 2000;
+
+/// Symbols returned per page unless page_size is given
+const DEFAULT_PAGE_SIZE: u32 = 200;
+
+/// Upper bound for page_size
+const MAX_PAGE_SIZE: u32 = 1000;
+
+#[mcp_tool(
+    name = "list_symbols_by_kind",
+    description = "List ALL symbols of given kinds across the project, regardless of their names - e.g. \
+                   every class, every free function or every enum. Walks the document symbols of each \
+                   project file instead of relying on name search, so nothing is left out by clangd's \
+                   ranking or result limit.
+
+                   🎯 USE CASES:
+                   • Architecture overview: all classes and structs of the project
+                   • API inventory: all free functions, all enums
+                   • Finding candidates when the name is unknown
+
+                   📂 FILES WALKED:
+                   • Translation units of the compilation database inside the project root
+                   • Project headers included by them (headers nobody includes are not listed)
+                   • Files excluded from results by the project configuration are skipped
+
+                   📄 PAGINATION:
+                   • Files are walked in sorted order; a page ends at the first file boundary after
+                     page_size symbols
+                   • next_cursor is set while files remain: pass it as cursor to get the next page
+                   • total_files and files_scanned show how far the listing got
+
+                   📋 OUTPUT:
+                   • symbols: Name, kind, container_name (enclosing scopes) and location
+                   • failed_files: Files clangd returned no symbols for
+                   • A function declared in a header and defined in a source file is listed for both
+
+                   INPUT REQUIREMENTS:
+                   • kinds: PascalCase symbol kinds, e.g. [\"Class\", \"Struct\"] or [\"Function\"]
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ListSymbolsByKindTool {
+    /// Symbol kinds to list. Supported PascalCase names: "Class", "Function", "Method",
+    /// "Variable", "Enum", "Namespace", "Constructor", "Field", "Interface", "Struct",
+    /// "EnumMember", "TypeParameter" and the other LSP symbol kinds.
+    pub kinds: Vec<String>,
+
+    /// Cursor from the next_cursor of the previous page. OPTIONAL (default: first page).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<u32>,
+
+    /// Minimum number of symbols per page; a page always ends at a file boundary.
+    /// OPTIONAL (default: 200, max: 1000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolsByKindResult {
+    /// Kinds that were listed
+    pub kinds: Vec<String>,
+    /// Symbols of this page in file order
+    pub symbols: Vec<Symbol>,
+    /// Number of project files
+    pub total_files: usize,
+    /// Number of files walked up to the end of this page
+    pub files_scanned: usize,
+    /// Cursor of the next page, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub next_cursor: Option<usize>,
+    /// Files of this page clangd returned no document symbols for
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub failed_files: Vec<PathBuf>,
+}
+
+impl ListSymbolsByKindTool {
+    /// Parse the requested kind names
+    fn symbol_kinds(&self) -> Result<Vec<lsp_types::SymbolKind>, CallToolError> {
+        if self.kinds.is_empty() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "kinds must name at least one symbol kind",
+            )));
+        }
+        self.kinds
+            .iter()
+            .map(|kind_name| {
+                lsp_types::SymbolKind::try_from(kind_name.as_str()).map_err(|_| {
+                    CallToolError::new(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("Invalid symbol kind: '{}'", kind_name),
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        name = "list_symbols_by_kind",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let kinds = self.symbol_kinds()?;
+        let page_size = self
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE) as usize;
+
+        let entries = component_session.compilation_database().entries().to_vec();
+        let project_root = workspace
+            .project_root_path
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.project_root_path.clone());
+        // Reads every header of every translation unit; keep it off the async workers
+        let mut files = tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+            project_files(
+                &entries,
+                &project_root,
+                MAX_INCLUDE_DEPTH,
+                MAX_INCLUDE_NODES,
+            )
+        }))
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+        files.retain(|file| !workspace.result_exclusions.is_excluded(file));
+
+        let start = self.cursor.unwrap_or(0) as usize;
+        if start > files.len() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("cursor {} is past the last of {} files", start, files.len()),
+            )));
+        }
+        info!(
+            "Listing {:?} symbols from file {} of {}",
+            kinds,
+            start,
+            files.len()
+        );
+
+        let mut symbols = Vec::new();
+        let mut failed_files = Vec::new();
+        let mut next = start;
+        while next < files.len() && symbols.len() < page_size {
+            let file = &files[next];
+            next += 1;
+
+            match get_document_symbols(&component_session, uri_from_pathbuf(file)).await {
+                Ok(document_symbols) => {
+                    symbols.extend(
+                        DocumentSymbolIterator::new(&document_symbols)
+                            .filter(|(symbol, _)| kinds.contains(&symbol.kind))
+                            .map(|(symbol, path)| {
+                                let mut symbol = Symbol::from((symbol, file.as_path()));
+                                symbol.container_name = (!path.is_empty()).then(|| path.join("::"));
+                                symbol
+                            }),
+                    );
+                }
+                Err(e) => {
+                    warn!("No document symbols for {}: {}", file.display(), e);
+                    failed_files.push(file.clone());
+                }
+            }
+        }
+
+        let result = SymbolsByKindResult {
+            kinds: self.kinds.clone(),
+            symbols,
+            total_files: files.len(),
+            files_scanned: next,
+            next_cursor: (next < files.len()).then_some(next),
+            failed_files,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, symbol, tool_result};
+
+    async fn list(
+        server: &MockClangd,
+        workspace: &ProjectWorkspace,
+        arguments: serde_json::Value,
+    ) -> SymbolsByKindResult {
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool: ListSymbolsByKindTool = serde_json::from_value(arguments).unwrap();
+        let result = tool.call_tool(component_session, workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_lists_symbols_of_kind_with_pagination() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/a.cpp", "#include \"shapes.h\"\n"),
+            ("src/b.cpp", ""),
+            ("src/shapes.h", ""),
+        ]);
+        let server = MockClangd::new();
+        server.respond_with("textDocument/documentSymbol", |params| {
+            let uri = params.unwrap()["textDocument"]["uri"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            if uri.ends_with("shapes.h") {
+                serde_json::json!([symbol(
+                    "geo",
+                    3,
+                    0,
+                    serde_json::json!([
+                        symbol(
+                            "Circle",
+                            5,
+                            1,
+                            serde_json::json!([symbol("area", 6, 2, serde_json::json!([]))])
+                        ),
+                        symbol("Square", 23, 4, serde_json::json!([])),
+                    ])
+                )])
+            } else if uri.ends_with("a.cpp") {
+                serde_json::json!([symbol("helper", 12, 1, serde_json::json!([]))])
+            } else {
+                serde_json::json!([symbol("Widget", 5, 0, serde_json::json!([]))])
+            }
+        });
+
+        let first = list(
+            &server,
+            &workspace,
+            serde_json::json!({"kinds": ["Class", "Struct"], "page_size": 1}),
+        )
+        .await;
+        // a.cpp has no classes, b.cpp ends the page
+        let names: Vec<_> = first.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Widget"]);
+        assert_eq!(first.total_files, 3);
+        assert_eq!(first.next_cursor, Some(2));
+
+        let second = list(
+            &server,
+            &workspace,
+            serde_json::json!({"kinds": ["Class", "Struct"], "cursor": 2}),
+        )
+        .await;
+        let names: Vec<_> = second
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.container_name.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Circle", Some("geo")), ("Square", Some("geo"))]
+        );
+        assert_eq!(second.next_cursor, None);
+        assert_eq!(second.files_scanned, 3);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_kind() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/a.cpp", "")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool = ListSymbolsByKindTool {
+            kinds: vec!["Gadget".to_string()],
+            cursor: None,
+            page_size: None,
+            build_directory: None,
+            session_id: None,
+        };
+
+        assert!(tool.call_tool(component_session, &workspace).await.is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! Clang AST dump through clangd's `textDocument/ast` extension
+//!
+//! clangd 12 added a non-standard request returning the AST node that entirely
+//! contains a range, together with its children. lsp-types does not model it,
+//! so it is sent through the raw request path and decoded here.
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::clangd::version::ClangdVersion;
+use crate::lsp::client::LspError;
+use crate::lsp::jsonrpc_utils::error_codes;
+use crate::lsp::protocol::JsonRpcError;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::uri_from_pathbuf;
+
+/// Method name of the clangd AST extension
+pub const AST_METHOD: &str = "textDocument/ast";
+
+/// First clangd major version answering `textDocument/ast`
+pub const MIN_AST_CLANGD_MAJOR: u32 = 12;
+
+/// AST node as returned by clangd
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClangdAstNode {
+    /// General category of the node, e.g. "expression", "declaration"
+    pub role: String,
+    /// Clang node class without its category suffix, e.g. "BinaryOperator"
+    pub kind: String,
+    /// Brief extra information, such as a declared name or an operator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Clang's own dump of the node, only meant for debugging
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arcana: Option<String>,
+    /// Source range of the node, missing for implicit nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<lsp_types::Range>,
+    #[serde(default)]
+    pub children: Vec<ClangdAstNode>,
+}
+
+/// Outcome of an AST request
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstLookup {
+    /// Node enclosing the requested range, or the translation unit
+    Node(ClangdAstNode),
+    /// clangd found no node for the range
+    NoNode,
+    /// The clangd serving the session does not implement the extension
+    Unsupported(String),
+}
+
+/// Reason the AST extension is unavailable for a clangd version, if it is
+pub fn unsupported_reason(version: &ClangdVersion) -> Option<String> {
+    (version.major < MIN_AST_CLANGD_MAJOR).then(|| {
+        format!(
+            "{} requires clangd {} or newer, the session runs clangd {}",
+            AST_METHOD, MIN_AST_CLANGD_MAJOR, version
+        )
+    })
+}
+
+/// Request the AST of a file, or of the node enclosing `range`
+///
+/// Without a range clangd returns the translation unit node.
+pub async fn get_ast(
+    file_path: &std::path::Path,
+    range: Option<lsp_types::Range>,
+    component_session: &ComponentSession,
+) -> Result<AstLookup, AnalyzerError> {
+    if let Some(reason) = unsupported_reason(component_session.clangd_version()) {
+        return Ok(AstLookup::Unsupported(reason));
+    }
+
+    let file_path = file_path
+        .canonicalize()
+        .unwrap_or_else(|_| file_path.to_path_buf());
+    component_session.ensure_file_ready(&file_path).await?;
+
+    let mut params = serde_json::json!({
+        "textDocument": { "uri": uri_from_pathbuf(&file_path) },
+    });
+    if let Some(range) = range {
+        params["range"] = serde_json::to_value(range)?;
+    }
+    trace!("Requesting AST: {}", params);
+
+    let response = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .raw_request(AST_METHOD.to_string(), params)
+            .await
+    };
+
+    match response {
+        Ok(serde_json::Value::Null) => Ok(AstLookup::NoNode),
+        Ok(value) => Ok(AstLookup::Node(serde_json::from_value(value)?)),
+        Err(LspError::JsonRpc(JsonRpcError::Server { code, message, .. }))
+            if code == error_codes::METHOD_NOT_FOUND =>
+        {
+            debug!("clangd does not implement {}: {}", AST_METHOD, message);
+            Ok(AstLookup::Unsupported(format!(
+                "clangd does not implement {}: {}",
+                AST_METHOD, message
+            )))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Value category of an expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueCategory {
+    Lvalue,
+    Xvalue,
+    Prvalue,
+}
+
+/// Type of an expression node, read from clang's dump of it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpressionType {
+    /// Type as written, e.g. "size_type"
+    pub type_name: String,
+    /// Canonical type when it differs, e.g. "unsigned long"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desugared_type: Option<String>,
+    pub value_category: ValueCategory,
+    /// Whether the type itself is const, as in `const int` or `int *const`
+    pub const_qualified: bool,
+}
+
+/// Read the type of an expression from the `arcana` of its node
+///
+/// The first line of clang's dump reads like
+/// `DeclRefExpr 0x1 <col:10> 'const size_type':'const unsigned long' lvalue Var 0x2 'n'`:
+/// the quoted type (and desugared type) follows the source range, followed by
+/// `lvalue` or `xvalue` unless the expression is a prvalue.
+pub fn parse_expression_type(arcana: &str) -> Option<ExpressionType> {
+    let line = arcana.lines().next()?;
+    let range_start = line.find('<')?;
+    let mut depth = 0;
+    let mut rest = None;
+    for (index, c) in line[range_start..].char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    rest = Some(&line[range_start + index + 1..]);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let rest = rest?.trim_start().strip_prefix('\'')?;
+    let (type_name, rest) = rest.split_once('\'')?;
+    let (desugared_type, rest) = match rest.strip_prefix(":'") {
+        Some(rest) => {
+            let (desugared, rest) = rest.split_once('\'')?;
+            (Some(desugared.to_string()), rest)
+        }
+        None => (None, rest),
+    };
+    let value_category = match rest.split_whitespace().next() {
+        Some("lvalue") => ValueCategory::Lvalue,
+        Some("xvalue") => ValueCategory::Xvalue,
+        _ => ValueCategory::Prvalue,
+    };
+
+    Some(ExpressionType {
+        const_qualified: is_const_qualified(desugared_type.as_deref().unwrap_or(type_name)),
+        type_name: type_name.to_string(),
+        desugared_type,
+        value_category,
+    })
+}
+
+/// Whether a type spelling is const at the top level
+///
+/// `const int` and `int *const` are, `const int *` is not: qualifiers before
+/// the last pointer declarator belong to the pointee.
+fn is_const_qualified(type_name: &str) -> bool {
+    let type_name = type_name.trim();
+    let mut depth = 0;
+    let mut declarator_end = 0;
+    for (index, c) in type_name.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            '*' | '&' if depth == 0 => declarator_end = index + 1,
+            _ => {}
+        }
+    }
+    let mut tokens = String::new();
+    let mut depth = 0;
+    for c in type_name[declarator_end..].chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            _ if depth == 0 => tokens.push(c),
+            _ => {}
+        }
+    }
+    tokens.split_whitespace().any(|token| token == "const")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major: u32) -> ClangdVersion {
+        ClangdVersion {
+            major,
+            minor: 0,
+            patch: 1,
+            variant: None,
+            date: None,
+        }
+    }
+
+    #[test]
+    fn test_unsupported_reason() {
+        assert_eq!(
+            unsupported_reason(&version(11)).unwrap(),
+            "textDocument/ast requires clangd 12 or newer, the session runs clangd 11.0.1"
+        );
+        assert!(unsupported_reason(&version(12)).is_none());
+    }
+
+    #[test]
+    fn test_decode_clangd_node() {
+        let node: ClangdAstNode = serde_json::from_value(serde_json::json!({
+            "role": "expression",
+            "kind": "BinaryOperator",
+            "detail": "+",
+            "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 14}},
+            "children": [
+                {"role": "expression", "kind": "ImplicitCast", "detail": "LValueToRValue"}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(node.detail.as_deref(), Some("+"));
+        assert!(node.children[0].range.is_none());
+        assert!(node.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn test_parse_expression_type() {
+        let parsed = parse_expression_type(
+            "DeclRefExpr 0x1 <col:10> 'const size_type':'const unsigned long' lvalue Var 0x2 'n'\n\
+             `-Child",
+        )
+        .unwrap();
+        assert_eq!(parsed.type_name, "const size_type");
+        assert_eq!(
+            parsed.desugared_type.as_deref(),
+            Some("const unsigned long")
+        );
+        assert_eq!(parsed.value_category, ValueCategory::Lvalue);
+        assert!(parsed.const_qualified);
+
+        let parsed =
+            parse_expression_type("BinaryOperator 0x1 <line:2:10, col:14> 'int' '+'").unwrap();
+        assert_eq!(parsed.type_name, "int");
+        assert_eq!(parsed.value_category, ValueCategory::Prvalue);
+        assert!(!parsed.const_qualified);
+
+        let parsed = parse_expression_type(
+            "CallExpr 0x1 <col:3, col:20> 'std::vector<int>':'std::vector<int, std::allocator<int>>' xvalue",
+        )
+        .unwrap();
+        assert_eq!(parsed.type_name, "std::vector<int>");
+        assert_eq!(parsed.value_category, ValueCategory::Xvalue);
+
+        assert!(parse_expression_type("CompoundStmt 0x1 <col:3, col:20>").is_none());
+    }
+
+    #[test]
+    fn test_const_qualification() {
+        assert!(is_const_qualified("const int"));
+        assert!(is_const_qualified("int const"));
+        assert!(is_const_qualified("int *const"));
+        assert!(!is_const_qualified("const int *"));
+        assert!(!is_const_qualified("std::map<const int, int>"));
+        assert!(is_const_qualified("const std::map<int, int *>"));
+    }
+}
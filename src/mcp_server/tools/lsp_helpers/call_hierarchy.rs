@@ -6,17 +6,19 @@
 
 use crate::clangd::session::ClangdSessionTrait;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
 
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::ResultExclusions;
 use crate::project::component_session::ComponentSession;
-use crate::symbol::FileLocation;
+use crate::symbol::{FileLocation, pathbuf_from_uri};
 
 // ============================================================================
 // Call Hierarchy Types
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallHierarchy {
     /// Functions that call this function (incoming calls)
     pub callers: Vec<String>,
@@ -24,6 +26,30 @@ pub struct CallHierarchy {
     pub callees: Vec<String>,
 }
 
+/// A function reached by following incoming calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedFunction {
+    pub name: String,
+    /// Signature or enclosing scope as reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub detail: Option<String>,
+    pub location: FileLocation,
+    /// Number of calls between this function and the analyzed symbol
+    pub depth: u32,
+    /// Function called by this one on the way to the analyzed symbol
+    pub calls: String,
+}
+
+/// Transitive callers of a function, in breadth-first order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitiveCallers {
+    /// The function as clangd names it; `None` when it is not callable
+    pub function: Option<String>,
+    pub callers: Vec<AffectedFunction>,
+    /// Whether callers were left out because of the depth or size limit
+    pub truncated: bool,
+}
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -36,6 +62,26 @@ pub async fn get_call_hierarchy(
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
 
+    // The prepare, incoming and outgoing requests are shared as a whole
+    component_session
+        .coalesced("callHierarchy", &(&uri, lsp_position), || {
+            request_call_hierarchy(
+                symbol_location,
+                component_session,
+                uri.clone(),
+                lsp_position,
+            )
+        })
+        .await
+}
+
+/// Send the call hierarchy requests for a symbol
+async fn request_call_hierarchy(
+    symbol_location: &FileLocation,
+    component_session: &ComponentSession,
+    uri: lsp_types::Uri,
+    lsp_position: lsp_types::Position,
+) -> Result<CallHierarchy, AnalyzerError> {
     // Ensure file is ready first
     component_session
         .ensure_file_ready(&symbol_location.file_path)
@@ -51,14 +97,12 @@ pub async fn get_call_hierarchy(
         .await
         .map_err(AnalyzerError::from)?;
 
-    // If we don't get any call hierarchy items, return empty hierarchy
-    let call_hierarchy_item = if call_hierarchy_items.is_empty() {
+    // If we don't get any call hierarchy items (null or empty), return empty hierarchy
+    let Some(call_hierarchy_item) = call_hierarchy_items.into_iter().flatten().next() else {
         return Ok(CallHierarchy {
             callers: Vec::new(),
             callees: Vec::new(),
         });
-    } else {
-        call_hierarchy_items.into_iter().next().unwrap()
     };
 
     // Get incoming calls (callers)
@@ -66,6 +110,7 @@ pub async fn get_call_hierarchy(
         .call_hierarchy_incoming_calls(call_hierarchy_item.clone())
         .await
         .map_err(AnalyzerError::from)?
+        .unwrap_or_default()
         .into_iter()
         .map(|call| call.from.name)
         .collect();
@@ -75,9 +120,127 @@ pub async fn get_call_hierarchy(
         .call_hierarchy_outgoing_calls(call_hierarchy_item)
         .await
         .map_err(AnalyzerError::from)?
+        .unwrap_or_default()
         .into_iter()
         .map(|call| call.to.name)
         .collect();
 
     Ok(CallHierarchy { callers, callees })
 }
+
+/// Identity of a call hierarchy item, for deduplication
+fn item_key(item: &lsp_types::CallHierarchyItem) -> (String, u32, u32) {
+    (
+        item.uri.to_string(),
+        item.selection_range.start.line,
+        item.selection_range.start.character,
+    )
+}
+
+/// Incoming calls of a call hierarchy item
+async fn incoming_callers(
+    item: lsp_types::CallHierarchyItem,
+    component_session: &ComponentSession,
+) -> Result<Vec<lsp_types::CallHierarchyItem>, AnalyzerError> {
+    let mut session = component_session.lsp_session().await;
+    Ok(session
+        .client_mut()
+        .call_hierarchy_incoming_calls(item)
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|call| call.from)
+        .collect())
+}
+
+/// Walk incoming calls breadth-first to collect every function that reaches a symbol
+///
+/// Callers are reported once, at their smallest depth. Callers in files matching
+/// `exclusions` are neither reported nor followed. The walk stops at
+/// `max_depth` levels of calls or `max_functions` callers; `truncated` tells
+/// whether further callers exist.
+pub async fn get_transitive_callers(
+    symbol_location: &FileLocation,
+    component_session: &ComponentSession,
+    exclusions: &ResultExclusions,
+    max_depth: u32,
+    max_functions: usize,
+) -> Result<TransitiveCallers, AnalyzerError> {
+    component_session
+        .ensure_file_ready(&symbol_location.file_path)
+        .await?;
+
+    let root = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .text_document_prepare_call_hierarchy(
+                symbol_location.get_uri(),
+                symbol_location.range.start.into(),
+            )
+            .await?
+            .into_iter()
+            .flatten()
+            .next()
+    };
+    let Some(root) = root else {
+        return Ok(TransitiveCallers {
+            function: None,
+            callers: Vec::new(),
+            truncated: false,
+        });
+    };
+
+    let function = root.name.clone();
+    let mut visited = HashSet::from([item_key(&root)]);
+    let mut pending = VecDeque::from([(root, 0)]);
+    let mut callers = Vec::new();
+    let mut truncated = false;
+
+    while let Some((item, depth)) = pending.pop_front() {
+        let callee = item.name.clone();
+        let incoming = incoming_callers(item, component_session).await?;
+        let unseen: Vec<_> = incoming
+            .into_iter()
+            .filter(|caller| !visited.contains(&item_key(caller)))
+            .filter(|caller| !exclusions.is_excluded(&pathbuf_from_uri(&caller.uri)))
+            .collect();
+        if depth >= max_depth || callers.len() >= max_functions {
+            // Only checked for new callers, which tells whether the walk is complete
+            if !unseen.is_empty() {
+                truncated = true;
+                break;
+            }
+            continue;
+        }
+        for caller in unseen {
+            if callers.len() >= max_functions {
+                truncated = true;
+                break;
+            }
+            if !visited.insert(item_key(&caller)) {
+                continue;
+            }
+            callers.push(AffectedFunction {
+                name: caller.name.clone(),
+                detail: caller.detail.clone(),
+                location: FileLocation {
+                    file_path: pathbuf_from_uri(&caller.uri),
+                    range: caller.selection_range.into(),
+                },
+                depth: depth + 1,
+                calls: callee.clone(),
+            });
+            pending.push_back((caller, depth + 1));
+        }
+        if truncated {
+            break;
+        }
+    }
+
+    Ok(TransitiveCallers {
+        function: Some(function),
+        callers,
+        truncated,
+    })
+}
@@ -23,20 +23,37 @@ pub async fn get_declarations(
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
 
-    // Ensure file is ready first
-    component_session
-        .ensure_file_ready(&symbol_location.file_path)
-        .await?;
+    let declaration = component_session
+        .coalesced(
+            "textDocument/declaration",
+            &(&uri, lsp_position),
+            || async {
+                // Ensure file is ready first
+                component_session
+                    .ensure_file_ready(&symbol_location.file_path)
+                    .await?;
 
-    // Get LSP session and make the request
-    let mut session = component_session.lsp_session().await;
-    let declaration = session
-        .client_mut()
-        .text_document_declaration(uri, lsp_position)
-        .await
-        .map_err(AnalyzerError::from)?;
+                // Get LSP session and make the request
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .text_document_declaration(uri.clone(), lsp_position)
+                    .await
+                    .map_err(AnalyzerError::from)
+            },
+        )
+        .await?;
 
-    goto_defdecl_response_to_file_locations(declaration)
+    match declaration {
+        Some(declaration) => goto_defdecl_response_to_file_locations(declaration),
+        None => {
+            trace!(
+                "No declaration result at {}",
+                symbol_location.to_compact_range()
+            );
+            Ok(Vec::new())
+        }
+    }
 }
 
 /// Get the definition locations of a symbol
@@ -47,20 +64,119 @@ pub async fn get_definitions(
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
 
-    // Ensure file is ready first
-    component_session
-        .ensure_file_ready(&symbol_location.file_path)
+    let definition = component_session
+        .coalesced("textDocument/definition", &(&uri, lsp_position), || async {
+            // Ensure file is ready first
+            component_session
+                .ensure_file_ready(&symbol_location.file_path)
+                .await?;
+
+            // Get LSP session and make the request
+            let mut session = component_session.lsp_session().await;
+            session
+                .client_mut()
+                .text_document_definition(uri.clone(), lsp_position)
+                .await
+                .map_err(AnalyzerError::from)
+        })
+        .await?;
+
+    match definition {
+        Some(definition) => goto_defdecl_response_to_file_locations(definition),
+        None => {
+            trace!(
+                "No definition result at {}",
+                symbol_location.to_compact_range()
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Get the locations of the type declarations for a symbol
+///
+/// For a type alias this is the declaration of the aliased type.
+pub async fn get_type_definitions(
+    symbol_location: &FileLocation,
+    component_session: &ComponentSession,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    let uri = symbol_location.get_uri();
+    let lsp_position: lsp_types::Position = symbol_location.range.start.into();
+
+    let type_definition = component_session
+        .coalesced(
+            "textDocument/typeDefinition",
+            &(&uri, lsp_position),
+            || async {
+                // Ensure file is ready first
+                component_session
+                    .ensure_file_ready(&symbol_location.file_path)
+                    .await?;
+
+                // Get LSP session and make the request
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .text_document_type_definition(uri.clone(), lsp_position)
+                    .await
+                    .map_err(AnalyzerError::from)
+            },
+        )
         .await?;
 
-    // Get LSP session and make the request
-    let mut session = component_session.lsp_session().await;
-    let definition = session
-        .client_mut()
-        .text_document_definition(uri, lsp_position)
-        .await
-        .map_err(AnalyzerError::from)?;
+    match type_definition {
+        Some(type_definition) => goto_defdecl_response_to_file_locations(type_definition),
+        None => {
+            trace!(
+                "No type definition result at {}",
+                symbol_location.to_compact_range()
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Get the implementation locations of a symbol
+///
+/// For a virtual method these are the methods overriding it.
+pub async fn get_implementations(
+    symbol_location: &FileLocation,
+    component_session: &ComponentSession,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    let uri = symbol_location.get_uri();
+    let lsp_position: lsp_types::Position = symbol_location.range.start.into();
+
+    let implementation = component_session
+        .coalesced(
+            "textDocument/implementation",
+            &(&uri, lsp_position),
+            || async {
+                // Ensure file is ready first
+                component_session
+                    .ensure_file_ready(&symbol_location.file_path)
+                    .await?;
 
-    goto_defdecl_response_to_file_locations(definition)
+                // Get LSP session and make the request
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .text_document_implementation(uri.clone(), lsp_position)
+                    .await
+                    .map_err(AnalyzerError::from)
+            },
+        )
+        .await?;
+
+    match implementation {
+        Some(implementation) => goto_defdecl_response_to_file_locations(implementation),
+        None => {
+            trace!(
+                "No implementation result at {}",
+                symbol_location.to_compact_range()
+            );
+            Ok(Vec::new())
+        }
+    }
 }
 
 // ============================================================================
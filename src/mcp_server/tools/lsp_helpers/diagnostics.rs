@@ -0,0 +1,92 @@
+//! Diagnostics retrieval functionality for C++ files
+//!
+//! This module provides access to the diagnostics clangd publishes for open files,
+//! including compiler errors/warnings and clang-tidy findings.
+
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::uri_from_pathbuf;
+
+/// Diagnostic source clangd reports for clang-tidy findings
+pub const CLANG_TIDY_SOURCE: &str = "clang-tidy";
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Get the diagnostics published for a file
+///
+/// Opens the file in clangd if needed and waits up to `timeout` for clangd to
+/// publish diagnostics. The session lock is released while waiting so other
+/// requests can proceed.
+///
+/// # Returns
+/// * `Ok(Some(diagnostics))` - Diagnostics published for the file
+/// * `Ok(None)` - clangd did not publish diagnostics within the timeout
+pub async fn get_file_diagnostics(
+    file_path: &Path,
+    component_session: &ComponentSession,
+    timeout: Duration,
+) -> Result<Option<Vec<lsp_types::Diagnostic>>, AnalyzerError> {
+    component_session.ensure_file_ready(file_path).await?;
+
+    let canonical_path = file_path
+        .canonicalize()
+        .unwrap_or_else(|_| file_path.to_path_buf());
+    let uri = uri_from_pathbuf(&canonical_path);
+
+    let diagnostics_monitor = component_session
+        .lsp_session()
+        .await
+        .diagnostics_monitor()
+        .clone();
+
+    let diagnostics = diagnostics_monitor
+        .wait_for_diagnostics(&uri, timeout)
+        .await;
+    match &diagnostics {
+        Some(diagnostics) => debug!(
+            "Received {} diagnostics for {}",
+            diagnostics.len(),
+            file_path.display()
+        ),
+        None => warn!(
+            "No diagnostics published for {} within {:?}",
+            file_path.display(),
+            timeout
+        ),
+    }
+
+    Ok(diagnostics)
+}
+
+/// Human-readable name of an LSP diagnostic severity
+pub fn severity_name(severity: lsp_types::DiagnosticSeverity) -> String {
+    match severity {
+        lsp_types::DiagnosticSeverity::ERROR => "error",
+        lsp_types::DiagnosticSeverity::WARNING => "warning",
+        lsp_types::DiagnosticSeverity::INFORMATION => "information",
+        lsp_types::DiagnosticSeverity::HINT => "hint",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+/// Get the check name of a clang-tidy diagnostic
+///
+/// Returns `None` for diagnostics that do not originate from clang-tidy.
+pub fn clang_tidy_check_name(diagnostic: &lsp_types::Diagnostic) -> Option<String> {
+    if diagnostic.source.as_deref() != Some(CLANG_TIDY_SOURCE) {
+        return None;
+    }
+
+    match &diagnostic.code {
+        Some(lsp_types::NumberOrString::String(name)) => Some(name.clone()),
+        Some(lsp_types::NumberOrString::Number(number)) => Some(number.to_string()),
+        None => Some("unknown".to_string()),
+    }
+}
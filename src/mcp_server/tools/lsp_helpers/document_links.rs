@@ -0,0 +1,32 @@
+//! Document link functionality
+//!
+//! clangd answers `textDocument/documentLink` with one link per `#include`
+//! directive of a file: the range of the header name and the URI of the file
+//! the directive resolved to. Directives clangd cannot resolve have no link.
+
+use std::path::Path;
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::uri_from_pathbuf;
+
+/// Get the links of a file, in document order
+pub async fn get_document_links(
+    file_path: &Path,
+    component_session: &ComponentSession,
+) -> Result<Vec<lsp_types::DocumentLink>, AnalyzerError> {
+    component_session.ensure_file_ready(file_path).await?;
+    let links = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .text_document_document_link(uri_from_pathbuf(file_path))
+            .await?
+    };
+
+    let mut links = links.unwrap_or_default();
+    links.sort_by_key(|link| (link.range.start.line, link.range.start.character));
+    Ok(links)
+}
@@ -13,8 +13,10 @@ use tracing::{trace, warn};
 
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::objc_selectors::SelectorQuery;
+use crate::mcp_server::tools::lsp_helpers::workspace_symbols::NameMatchMode;
 use crate::project::component_session::ComponentSession;
-use crate::symbol::uri_from_pathbuf;
+use crate::symbol::{pathbuf_from_uri, uri_from_pathbuf};
 
 // Fuzzy matching threshold - accept all positive scores
 const FUZZY_MATCH_THRESHOLD: isize = 0;
@@ -219,6 +221,7 @@ pub struct SymbolSearchBuilder {
     kind: Option<lsp_types::SymbolKind>,
     kinds: Option<Vec<lsp_types::SymbolKind>>,
     path_contains: Option<String>,
+    match_mode: NameMatchMode,
 }
 
 impl SymbolSearchBuilder {
@@ -230,6 +233,7 @@ impl SymbolSearchBuilder {
             kind: None,
             kinds: None,
             path_contains: None,
+            match_mode: NameMatchMode::default(),
         }
     }
 
@@ -245,6 +249,12 @@ impl SymbolSearchBuilder {
         self
     }
 
+    /// Set how the name filter is matched (fuzzy by default)
+    pub fn with_match_mode(mut self, mode: NameMatchMode) -> Self {
+        self.match_mode = mode;
+        self
+    }
+
     /// Search for symbol by kind
     pub fn with_kind(mut self, kind: lsp_types::SymbolKind) -> Self {
         self.kind = Some(kind);
@@ -287,7 +297,7 @@ impl SymbolSearchBuilder {
             .collect();
 
         // Sort by score (highest first)
-        scored_matches.sort_by(|a, b| b.score.cmp(&a.score));
+        scored_matches.sort_by_key(|scored| std::cmp::Reverse(scored.score));
 
         // Return just the symbols
         scored_matches
@@ -399,7 +409,7 @@ impl SymbolSearchBuilder {
             file_path, self
         );
 
-        let file_uri = if file_path.starts_with("file://") {
+        let file_uri = if file_path.starts_with("file:") {
             file_path
                 .parse()
                 .map_err(|e| AnalyzerError::NoData(format!("Invalid URI: {}", e)))?
@@ -427,6 +437,10 @@ impl SymbolSearchBuilder {
 
     /// Get fuzzy match score for a symbol, returns None if no match
     fn fuzzy_match_score(&self, symbol: &DocumentSymbol) -> Option<isize> {
+        if self.match_mode != NameMatchMode::Fuzzy {
+            // Non-fuzzy matches keep document order
+            return Some(0);
+        }
         if let Some(ref name) = self.name {
             if let Some(fuzzy_match) = best_match(name, &symbol.name)
                 && fuzzy_match.score() >= FUZZY_MATCH_THRESHOLD
@@ -455,8 +469,23 @@ impl SymbolSearchBuilder {
             return false;
         }
 
-        // Name matching - use fuzzy matching
-        if let Some(ref name) = self.name {
+        // Name matching - exact or case-sensitive modes
+        if let Some(ref name) = self.name
+            && self.match_mode != NameMatchMode::Fuzzy
+        {
+            let container = path.join("::");
+            if !self
+                .match_mode
+                .matches(name, &symbol.name, Some(container.as_str()))
+            {
+                trace!(
+                    "Symbol '{}' rejected: no {:?} match for query '{}'",
+                    symbol.name, self.match_mode, name
+                );
+                return false;
+            }
+        } else if let Some(ref name) = self.name {
+            // Name matching - use fuzzy matching
             if let Some(fuzzy_match) = best_match(name, &symbol.name) {
                 if fuzzy_match.score() < FUZZY_MATCH_THRESHOLD {
                     trace!(
@@ -567,7 +596,7 @@ impl Default for SymbolSearchBuilder {
 ///
 /// # Returns
 /// * `Ok(Vec<DocumentSymbol>)` - Hierarchical document symbols
-/// * `Err(AnalyzerError)` - LSP error or unexpected flat response
+/// * `Err(AnalyzerError)` - LSP error, no result from clangd, or unexpected flat response
 pub async fn get_document_symbols(
     component_session: &ComponentSession,
     file_uri: lsp_types::Uri,
@@ -575,21 +604,24 @@ pub async fn get_document_symbols(
     trace!("Requesting document symbols for URI: {:?}", file_uri);
 
     // Ensure file is ready
-    let uri_str = file_uri.to_string();
-    let file_path_str = uri_str.strip_prefix("file://").unwrap_or(&uri_str);
-
-    component_session
-        .ensure_file_ready(Path::new(file_path_str))
-        .await?;
-
-    let mut session = component_session.lsp_session().await;
-    let client = session.client_mut();
+    let file_path = pathbuf_from_uri(&file_uri);
 
     // Get document symbols from LSP
-    let document_symbols = client
-        .text_document_document_symbol(file_uri.clone())
-        .await
-        .map_err(AnalyzerError::from)?;
+    let document_symbols = component_session
+        .coalesced("textDocument/documentSymbol", &file_uri, || async {
+            component_session.ensure_file_ready(&file_path).await?;
+
+            let mut session = component_session.lsp_session().await;
+            session
+                .client_mut()
+                .text_document_document_symbol(file_uri.clone())
+                .await
+                .map_err(AnalyzerError::from)
+        })
+        .await?
+        .ok_or_else(|| {
+            AnalyzerError::NoResult(format!("document symbols of '{}'", file_uri.as_str()))
+        })?;
 
     trace!(
         "Document symbols response type: {:?}",
@@ -685,6 +717,214 @@ fn find_symbol_recursive_with_path<'a>(
     None
 }
 
+/// Find the innermost function-like symbol whose range covers a line
+///
+/// Functions, methods, constructors and operators are considered; class and
+/// namespace scopes are only descended into. Nested function-like symbols
+/// (e.g. methods of a local class) win over the function that contains them.
+///
+/// # Arguments
+/// * `symbols` - Hierarchical document symbols to search
+/// * `line` - Target line number (0-based)
+///
+/// # Returns
+/// * `Some((&DocumentSymbol, Vec<String>))` - Enclosing function and its container path
+/// * `None` - Line is not inside any function
+pub fn find_enclosing_function(
+    symbols: &[DocumentSymbol],
+    line: u32,
+) -> Option<(&DocumentSymbol, Vec<String>)> {
+    find_enclosing_function_recursive(symbols, line, Vec::new())
+}
+
+/// Recursive helper for enclosing function lookup
+fn find_enclosing_function_recursive(
+    symbols: &[DocumentSymbol],
+    line: u32,
+    current_path: Vec<String>,
+) -> Option<(&DocumentSymbol, Vec<String>)> {
+    for symbol in symbols {
+        if line < symbol.range.start.line || line > symbol.range.end.line {
+            continue;
+        }
+
+        if let Some(children) = &symbol.children {
+            let mut child_path = current_path.clone();
+            child_path.push(symbol.name.clone());
+            if let Some(result) = find_enclosing_function_recursive(children, line, child_path) {
+                return Some(result);
+            }
+        }
+
+        if is_function_like(symbol.kind) {
+            return Some((symbol, current_path));
+        }
+    }
+    None
+}
+
+/// Check whether a symbol kind denotes a function, method, constructor or operator
+fn is_function_like(kind: lsp_types::SymbolKind) -> bool {
+    matches!(
+        kind,
+        lsp_types::SymbolKind::FUNCTION
+            | lsp_types::SymbolKind::METHOD
+            | lsp_types::SymbolKind::CONSTRUCTOR
+            | lsp_types::SymbolKind::OPERATOR
+    )
+}
+
+/// Collect all function-like symbols in document order
+///
+/// Includes nested ones such as methods of classes and of local classes.
+///
+/// # Arguments
+/// * `symbols` - Hierarchical document symbols to search
+///
+/// # Returns
+/// * `Vec<(&DocumentSymbol, Vec<String>)>` - Functions with their container paths
+pub fn collect_functions(symbols: &[DocumentSymbol]) -> Vec<(&DocumentSymbol, Vec<String>)> {
+    let mut functions = Vec::new();
+    collect_functions_recursive(symbols, Vec::new(), &mut functions);
+    functions
+}
+
+/// Recursive helper for function collection
+fn collect_functions_recursive<'a>(
+    symbols: &'a [DocumentSymbol],
+    current_path: Vec<String>,
+    functions: &mut Vec<(&'a DocumentSymbol, Vec<String>)>,
+) {
+    for symbol in symbols {
+        if is_function_like(symbol.kind) {
+            functions.push((symbol, current_path.clone()));
+        }
+        if let Some(children) = &symbol.children {
+            let mut child_path = current_path.clone();
+            child_path.push(symbol.name.clone());
+            collect_functions_recursive(children, child_path, functions);
+        }
+    }
+}
+
+/// Find the symbol declared or defined on a line
+///
+/// Candidates are symbols whose name (selection range) starts on `line`, in
+/// document order, outermost first. When `name_hint` is given, the first
+/// candidate whose name or last `::` segment matches the hint's last segment
+/// wins; otherwise, or when nothing matches, the first candidate is returned.
+///
+/// # Arguments
+/// * `symbols` - Hierarchical document symbols to search
+/// * `line` - Target line number (0-based)
+/// * `name_hint` - Optional symbol name expected on the line
+///
+/// # Returns
+/// * `Some((&DocumentSymbol, Vec<String>))` - Symbol on the line and its container path
+/// * `None` - No symbol is declared on the line
+pub fn find_symbol_on_line<'a>(
+    symbols: &'a [DocumentSymbol],
+    line: u32,
+    name_hint: Option<&str>,
+) -> Option<(&'a DocumentSymbol, Vec<String>)> {
+    let mut candidates = Vec::new();
+    collect_symbols_on_line(symbols, line, Vec::new(), &mut candidates);
+
+    let hint = name_hint
+        .map(last_name_segment)
+        .filter(|hint| !hint.is_empty());
+    if let Some(hint) = hint
+        && let Some(index) = candidates
+            .iter()
+            .position(|(symbol, _)| last_name_segment(&symbol.name) == hint)
+    {
+        return Some(candidates.swap_remove(index));
+    }
+
+    candidates.into_iter().next()
+}
+
+/// Find the first symbol, in document order, named like `name`
+///
+/// Names are compared by their last `::` segment, so `Bar::baz` matches both
+/// `baz` and an out-of-line `Bar::baz` definition.
+///
+/// # Arguments
+/// * `symbols` - Hierarchical document symbols to search
+/// * `name` - Plain or qualified symbol name
+///
+/// # Returns
+/// * `Some((&DocumentSymbol, Vec<String>))` - Matching symbol and its container path
+/// * `None` - No symbol has the name
+pub fn find_symbol_named<'a>(
+    symbols: &'a [DocumentSymbol],
+    name: &str,
+) -> Option<(&'a DocumentSymbol, Vec<String>)> {
+    let wanted = last_name_segment(name);
+    if wanted.is_empty() {
+        return None;
+    }
+    find_symbol_named_recursive(symbols, wanted, Vec::new())
+}
+
+/// Recursive helper for name lookup
+fn find_symbol_named_recursive<'a>(
+    symbols: &'a [DocumentSymbol],
+    wanted: &str,
+    current_path: Vec<String>,
+) -> Option<(&'a DocumentSymbol, Vec<String>)> {
+    for symbol in symbols {
+        if last_name_segment(&symbol.name) == wanted {
+            return Some((symbol, current_path));
+        }
+        if let Some(children) = &symbol.children {
+            let mut child_path = current_path.clone();
+            child_path.push(symbol.name.clone());
+            if let Some(found) = find_symbol_named_recursive(children, wanted, child_path) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Check whether a symbol name matches a plain or qualified query by last `::` segment
+pub fn symbol_name_matches(name: &str, query: &str) -> bool {
+    let wanted = last_name_segment(query);
+    !wanted.is_empty() && last_name_segment(name) == wanted
+}
+
+/// Last `::` segment of a possibly qualified name, the selector of an Objective-C method
+fn last_name_segment(name: &str) -> &str {
+    // Objective-C methods compare by selector: `-[MyClass doThing:]`, `-doThing:`
+    if let Some(query) = SelectorQuery::parse(name) {
+        return query.selector;
+    }
+    name.rsplit("::").next().unwrap_or(name).trim()
+}
+
+/// Recursive helper collecting symbols whose name starts on a line
+fn collect_symbols_on_line<'a>(
+    symbols: &'a [DocumentSymbol],
+    line: u32,
+    current_path: Vec<String>,
+    candidates: &mut Vec<(&'a DocumentSymbol, Vec<String>)>,
+) {
+    for symbol in symbols {
+        if line < symbol.range.start.line || line > symbol.range.end.line {
+            continue;
+        }
+        if symbol.selection_range.start.line == line {
+            candidates.push((symbol, current_path.clone()));
+        }
+        if let Some(children) = &symbol.children {
+            let mut child_path = current_path.clone();
+            child_path.push(symbol.name.clone());
+            collect_symbols_on_line(children, line, child_path, candidates);
+        }
+    }
+}
+
 /// Find symbols by name using idiomatic iterator approach
 ///
 /// # Arguments
@@ -1199,4 +1439,85 @@ mod tests {
         // First result should be exact match "Math"
         assert_eq!(found[0].name, "Math");
     }
+
+    #[test]
+    fn test_find_enclosing_function() {
+        let mut method = create_test_symbol_with_kind("compute", SymbolKind::METHOD, 3, 4, 9, 5);
+        method.children = Some(vec![create_test_symbol_with_kind(
+            "local",
+            SymbolKind::VARIABLE,
+            4,
+            8,
+            4,
+            20,
+        )]);
+        let mut class_symbol = create_test_symbol("Calculator", 1, 0, 10, 1);
+        class_symbol.children = Some(vec![method]);
+        let mut namespace = create_test_symbol_with_kind("app", SymbolKind::NAMESPACE, 0, 0, 20, 1);
+        namespace.children = Some(vec![
+            class_symbol,
+            create_test_symbol_with_kind("helper", SymbolKind::FUNCTION, 12, 0, 15, 1),
+        ]);
+        let symbols = vec![namespace];
+
+        let (found, path) = find_enclosing_function(&symbols, 4).unwrap();
+        assert_eq!(found.name, "compute");
+        assert_eq!(path, vec!["app", "Calculator"]);
+
+        let (found, path) = find_enclosing_function(&symbols, 15).unwrap();
+        assert_eq!(found.name, "helper");
+        assert_eq!(path, vec!["app"]);
+
+        // Inside the class but outside any method
+        assert!(find_enclosing_function(&symbols, 2).is_none());
+        assert!(find_enclosing_function(&symbols, 30).is_none());
+
+        let functions: Vec<(&str, Vec<String>)> = collect_functions(&symbols)
+            .into_iter()
+            .map(|(symbol, path)| (symbol.name.as_str(), path))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![
+                ("compute", vec!["app".to_string(), "Calculator".to_string()]),
+                ("helper", vec!["app".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_symbol_on_line() {
+        // struct Point { int x; int y; };
+        let mut point = create_test_symbol("Point", 2, 0, 2, 32);
+        point.children = Some(vec![
+            create_test_symbol_with_kind("x", SymbolKind::FIELD, 2, 15, 2, 20),
+            create_test_symbol_with_kind("y", SymbolKind::FIELD, 2, 22, 2, 27),
+        ]);
+        let mut namespace = create_test_symbol_with_kind("app", SymbolKind::NAMESPACE, 0, 0, 10, 1);
+        namespace.children = Some(vec![
+            point,
+            create_test_symbol_with_kind("Bar::baz", SymbolKind::METHOD, 4, 0, 8, 1),
+        ]);
+        let symbols = vec![namespace];
+
+        // Outermost symbol without a hint
+        let (found, path) = find_symbol_on_line(&symbols, 2, None).unwrap();
+        assert_eq!(found.name, "Point");
+        assert_eq!(path, vec!["app"]);
+
+        let (found, path) = find_symbol_on_line(&symbols, 2, Some("Point::y")).unwrap();
+        assert_eq!(found.name, "y");
+        assert_eq!(path, vec!["app", "Point"]);
+
+        // Qualified out-of-line definitions match their unqualified name
+        let (found, _) = find_symbol_on_line(&symbols, 4, Some("baz")).unwrap();
+        assert_eq!(found.name, "Bar::baz");
+
+        // Unmatched hints fall back to the first symbol on the line
+        let (found, _) = find_symbol_on_line(&symbols, 2, Some("missing")).unwrap();
+        assert_eq!(found.name, "Point");
+
+        // Inside a function body nothing is declared at symbol level
+        assert!(find_symbol_on_line(&symbols, 6, None).is_none());
+    }
 }
@@ -0,0 +1,388 @@
+//! Structured documentation from clangd hover content
+//!
+//! clangd's hover shows the documentation comment of a symbol verbatim, between
+//! the type information it renders itself and the definition code block. This
+//! module isolates that comment and splits Doxygen/Javadoc commands (`@brief`,
+//! `@param`, `\return`, `@see`, ...) into structured fields.
+
+use serde::{Deserialize, Serialize};
+
+/// Documentation of a symbol in raw and structured form
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Documentation {
+    /// Documentation comment as shown by clangd
+    pub raw: String,
+    /// Brief description: `@brief`, or the first paragraph without one
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub brief: Option<String>,
+    /// Remaining description paragraphs
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub details: Option<String>,
+    /// `@param` entries in comment order
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub params: Vec<ParamDoc>,
+    /// `@tparam` entries in comment order
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub template_params: Vec<ParamDoc>,
+    /// `@return` / `@returns` description, `@retval` entries appended
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub returns: Option<String>,
+    /// `@throws` / `@exception` entries
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub throws: Vec<String>,
+    /// `@see` / `@sa` references
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub see_also: Vec<String>,
+    /// `@note` / `@warning` / `@attention` remarks
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub notes: Vec<String>,
+    /// `@deprecated` explanation
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub deprecated: Option<String>,
+}
+
+/// Documentation of a single function or template parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamDoc {
+    pub name: String,
+    /// Direction from `@param[in]`, `[out]` or `[in,out]`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub direction: Option<String>,
+    pub description: String,
+}
+
+/// Field a documentation line is appended to
+enum Target {
+    Text,
+    Brief,
+    Param(usize),
+    TemplateParam(usize),
+    Returns,
+    Throws(usize),
+    SeeAlso(usize),
+    Note(usize),
+    Deprecated,
+}
+
+/// Extract the documentation comment from clangd hover content and structure it
+///
+/// # Returns
+/// * `Some(Documentation)` - The hover contains a documentation comment
+/// * `None` - The hover holds only clangd's type and definition information
+pub fn parse_hover_documentation(hover: &str) -> Option<Documentation> {
+    let raw = documentation_text(hover);
+    if raw.is_empty() {
+        return None;
+    }
+
+    let mut doc = Documentation {
+        raw: raw.clone(),
+        ..Documentation::default()
+    };
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut target = Target::Text;
+    let mut new_paragraph = true;
+
+    for line in raw.lines().map(|line| unescape_markdown(line.trim())) {
+        if line.is_empty() {
+            target = Target::Text;
+            new_paragraph = true;
+            continue;
+        }
+
+        if let Some((command, rest)) = split_command(&line)
+            && let Some(next) = start_command(&mut doc, command, rest)
+        {
+            target = next;
+            new_paragraph = true;
+            continue;
+        }
+
+        let field = match target {
+            Target::Text => {
+                if new_paragraph {
+                    paragraphs.push(String::new());
+                    new_paragraph = false;
+                }
+                paragraphs.last_mut()
+            }
+            Target::Brief => doc.brief.as_mut(),
+            Target::Param(index) => doc.params.get_mut(index).map(|p| &mut p.description),
+            Target::TemplateParam(index) => doc
+                .template_params
+                .get_mut(index)
+                .map(|p| &mut p.description),
+            Target::Returns => doc.returns.as_mut(),
+            Target::Throws(index) => doc.throws.get_mut(index),
+            Target::SeeAlso(index) => doc.see_also.get_mut(index),
+            Target::Note(index) => doc.notes.get_mut(index),
+            Target::Deprecated => doc.deprecated.as_mut(),
+        };
+        if let Some(field) = field {
+            append_line(field, &line);
+        }
+    }
+
+    let mut paragraphs = paragraphs.into_iter().filter(|p| !p.is_empty());
+    if doc.brief.is_none() {
+        doc.brief = paragraphs.next();
+    }
+    let details: Vec<String> = paragraphs.collect();
+    doc.details = (!details.is_empty()).then(|| details.join("\n\n"));
+
+    Some(doc)
+}
+
+/// Record a documentation command and return the field its text continues in
+///
+/// Returns None for commands that carry no structured information; their line
+/// is kept as description text.
+fn start_command(doc: &mut Documentation, command: &str, rest: &str) -> Option<Target> {
+    let (name, direction) = match command.split_once('[') {
+        Some((name, direction)) => (name, Some(direction.trim_end_matches(']'))),
+        None => (command, None),
+    };
+
+    let target = match name {
+        "brief" | "short" => {
+            doc.brief = Some(rest.to_string());
+            Target::Brief
+        }
+        "param" | "tparam" => {
+            let (param, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let param = ParamDoc {
+                name: param.to_string(),
+                direction: direction.map(|d| d.replace(' ', "")),
+                description: description.trim().to_string(),
+            };
+            if name == "param" {
+                doc.params.push(param);
+                Target::Param(doc.params.len() - 1)
+            } else {
+                doc.template_params.push(param);
+                Target::TemplateParam(doc.template_params.len() - 1)
+            }
+        }
+        "return" | "returns" | "result" => {
+            match &mut doc.returns {
+                Some(returns) => append_line(returns, rest),
+                None => doc.returns = Some(rest.to_string()),
+            }
+            Target::Returns
+        }
+        "retval" => {
+            match &mut doc.returns {
+                Some(returns) => returns.push_str(&format!("; {rest}")),
+                None => doc.returns = Some(rest.to_string()),
+            }
+            Target::Returns
+        }
+        "throws" | "throw" | "exception" => {
+            doc.throws.push(rest.to_string());
+            Target::Throws(doc.throws.len() - 1)
+        }
+        "see" | "sa" => {
+            doc.see_also.push(rest.to_string());
+            Target::SeeAlso(doc.see_also.len() - 1)
+        }
+        "note" | "warning" | "attention" | "remark" => {
+            let note = match name {
+                "note" | "remark" => rest.to_string(),
+                _ => format!("Warning: {rest}"),
+            };
+            doc.notes.push(note);
+            Target::Note(doc.notes.len() - 1)
+        }
+        "deprecated" => {
+            doc.deprecated = Some(rest.to_string());
+            Target::Deprecated
+        }
+        _ => return None,
+    };
+    Some(target)
+}
+
+/// Split a `@command rest` or `\command rest` line
+fn split_command(line: &str) -> Option<(&str, &str)> {
+    let body = line.strip_prefix('@').or_else(|| line.strip_prefix('\\'))?;
+    let end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+    let (command, rest) = body.split_at(end);
+    let command_name_end = command.find('[').unwrap_or(command.len());
+    if command_name_end == 0
+        || !command[..command_name_end]
+            .chars()
+            .all(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    Some((command, rest.trim()))
+}
+
+/// Append a continuation line to a field, separated by a space
+fn append_line(field: &mut String, line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    if !field.is_empty() {
+        field.push(' ');
+    }
+    field.push_str(line);
+}
+
+/// Documentation comment part of a clangd hover
+///
+/// Drops the heading, rulers, code blocks and the lines clangd renders itself
+/// (return type, parameter list, type, value, size and offset).
+fn documentation_text(hover: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    let mut in_parameter_list = false;
+
+    for line in hover.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        if in_parameter_list {
+            if trimmed.starts_with("- ") {
+                continue;
+            }
+            in_parameter_list = false;
+        }
+        if trimmed == "Parameters:" || trimmed == "Template parameters:" {
+            in_parameter_list = true;
+            continue;
+        }
+
+        let generated = trimmed.starts_with('#')
+            || trimmed == "---"
+            || trimmed.starts_with("provided by")
+            || trimmed.starts_with("→ ")
+            || ["Type: ", "Value = ", "Offset: ", "Size: ", "Passed "]
+                .iter()
+                .any(|prefix| trimmed.starts_with(prefix));
+        if !generated {
+            lines.push(line.trim_end());
+        }
+    }
+
+    // Collapse the blank lines left around removed parts
+    let mut text = String::new();
+    for line in lines {
+        if line.trim().is_empty() && (text.is_empty() || text.ends_with("\n\n")) {
+            continue;
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.trim().to_string()
+}
+
+/// Remove the backslashes clangd adds before markdown punctuation
+fn unescape_markdown(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && next.is_ascii_punctuation()
+        {
+            result.push(next);
+            chars.next();
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOVER: &str = "\
+### function `clamp`
+
+---
+→ `int`
+Parameters:
+- `int value`
+- `int low`
+- `int high`
+
+Clamp a value into a range.
+
+Values below \\`low\\` map to \\`low\\`, values above
+\\`high\\` map to \\`high\\`.
+
+@param[in] value Value to clamp
+@param low Lower bound,
+  inclusive
+\\\\param high Upper bound
+@return The clamped value
+@throws std::invalid\\_argument if low > high
+@see saturate
+
+---
+```cpp
+// In namespace math
+int clamp(int value, int low, int high)
+```";
+
+    #[test]
+    fn test_parse_doxygen_hover() {
+        let doc = parse_hover_documentation(HOVER).unwrap();
+
+        assert_eq!(doc.brief.as_deref(), Some("Clamp a value into a range."));
+        assert_eq!(
+            doc.details.as_deref(),
+            Some("Values below `low` map to `low`, values above `high` map to `high`.")
+        );
+        let params: Vec<_> = doc
+            .params
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.direction.as_deref(),
+                    p.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("value", Some("in"), "Value to clamp"),
+                ("low", None, "Lower bound, inclusive"),
+                ("high", None, "Upper bound"),
+            ]
+        );
+        assert_eq!(doc.returns.as_deref(), Some("The clamped value"));
+        assert_eq!(doc.throws, vec!["std::invalid_argument if low > high"]);
+        assert_eq!(doc.see_also, vec!["saturate"]);
+        assert!(doc.raw.starts_with("Clamp a value into a range."));
+        assert!(doc.raw.ends_with("@see saturate"));
+    }
+
+    #[test]
+    fn test_explicit_brief_and_notes() {
+        let hover = "### class `Cache`\n\n---\n\\\\brief LRU cache.\nSpans lines.\n\nKeeps recent entries.\n\n@tparam T Value type\n@warning Not thread safe\n@deprecated Use Store";
+        let doc = parse_hover_documentation(hover).unwrap();
+
+        assert_eq!(doc.brief.as_deref(), Some("LRU cache. Spans lines."));
+        assert_eq!(doc.details.as_deref(), Some("Keeps recent entries."));
+        assert_eq!(doc.template_params[0].name, "T");
+        assert_eq!(doc.notes, vec!["Warning: Not thread safe"]);
+        assert_eq!(doc.deprecated.as_deref(), Some("Use Store"));
+    }
+
+    #[test]
+    fn test_hover_without_documentation() {
+        let hover = "### variable `count`\n\n---\nType: `int`\nValue = 3\n\n---\n```cpp\nint count = 3\n```";
+        assert_eq!(parse_hover_documentation(hover), None);
+    }
+}
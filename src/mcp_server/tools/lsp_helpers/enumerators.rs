@@ -0,0 +1,278 @@
+//! Enumerator extraction for C++ enums
+//!
+//! Generic member extraction reports enumerators as untyped members without
+//! their values. This module lists the enumerators of an enum document symbol in
+//! declaration order, together with their integer values where they can be
+//! determined from clangd's symbol detail or the enumerator initializers.
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::lsp::PositionEncoding;
+
+/// A single enumerator of an enum
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Enumerator {
+    /// Enumerator name
+    pub name: String,
+    /// Integer value, None if it cannot be determined without evaluating an expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<i64>,
+    /// Initializer expression as written in the source, None if the value is implicit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initializer: Option<String>,
+}
+
+/// List the enumerators of an enum document symbol in declaration order
+///
+/// Values are taken from the `= N` clangd appends to the detail of an
+/// enumerator when present. Otherwise integer literal initializers from the
+/// source text are used, and enumerators without an initializer count up from
+/// the previous value, starting at 0.
+///
+/// # Arguments
+/// * `enum_symbol` - Document symbol of the enum
+/// * `source` - Content of the file declaring the enum, if available
+/// * `position_encoding` - Encoding of the columns in `enum_symbol`
+pub fn get_enumerators(
+    enum_symbol: &lsp_types::DocumentSymbol,
+    source: Option<&str>,
+    position_encoding: PositionEncoding,
+) -> Vec<Enumerator> {
+    let mut members: Vec<&lsp_types::DocumentSymbol> = enum_symbol
+        .children
+        .iter()
+        .flatten()
+        .filter(|child| child.kind == lsp_types::SymbolKind::ENUM_MEMBER)
+        .collect();
+    members.sort_by_key(|member| (member.range.start.line, member.range.start.character));
+
+    let lines: Option<Vec<&str>> = source.map(|source| source.lines().collect());
+    let mut next_value = Some(0i64);
+
+    let enumerators: Vec<Enumerator> = members
+        .into_iter()
+        .map(|member| {
+            let initializer = lines
+                .as_deref()
+                .and_then(|lines| initializer_text(lines, member, position_encoding));
+            let value = member
+                .detail
+                .as_deref()
+                .and_then(value_from_detail)
+                .or_else(|| match &initializer {
+                    Some(initializer) => parse_integer_literal(initializer),
+                    None => next_value,
+                });
+            next_value = value.and_then(|value| value.checked_add(1));
+
+            Enumerator {
+                name: member.name.clone(),
+                value,
+                initializer,
+            }
+        })
+        .collect();
+
+    debug!(
+        "Found {} enumerators for enum '{}'",
+        enumerators.len(),
+        enum_symbol.name
+    );
+    enumerators
+}
+
+/// Initializer of an enumerator: the source text after `=` following its name
+fn initializer_text(
+    lines: &[&str],
+    member: &lsp_types::DocumentSymbol,
+    position_encoding: PositionEncoding,
+) -> Option<String> {
+    let start = member.selection_range.end;
+    let end = member.range.end;
+    if (end.line, end.character) <= (start.line, start.character) {
+        return None;
+    }
+
+    let mut text = String::new();
+    for line_number in start.line..=end.line {
+        let line = lines.get(line_number as usize)?;
+        let from = if line_number == start.line {
+            position_encoding.byte_offset(line, start.character)?
+        } else {
+            0
+        };
+        let to = if line_number == end.line {
+            position_encoding.byte_offset(line, end.character)?
+        } else {
+            line.len()
+        };
+        text.push_str(line.get(from..to)?);
+        text.push(' ');
+    }
+
+    let expression = text.trim_start().strip_prefix('=')?;
+    let expression = expression.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!expression.is_empty()).then_some(expression)
+}
+
+/// Value clangd reports in an enumerator detail such as `Color = 2` or `= 2`
+fn value_from_detail(detail: &str) -> Option<i64> {
+    let (_, value) = detail.rsplit_once('=')?;
+    parse_integer_literal(value)
+}
+
+/// Parse a C++ integer literal, optionally negated
+///
+/// Handles decimal, hexadecimal, octal and binary literals with digit
+/// separators and integer suffixes. Anything else yields None.
+fn parse_integer_literal(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, literal) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+
+    let literal = literal
+        .trim_end_matches(['u', 'U', 'l', 'L', 'z', 'Z'])
+        .replace('\'', "");
+    let (digits, radix) = if let Some(hex) = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if let Some(binary) = literal
+        .strip_prefix("0b")
+        .or_else(|| literal.strip_prefix("0B"))
+    {
+        (binary, 2)
+    } else if literal.len() > 1 && literal.starts_with('0') {
+        (&literal[1..], 8)
+    } else {
+        (literal.as_str(), 10)
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let magnitude = i128::from_str_radix(digits, radix).ok()?;
+    let value = if negative { -magnitude } else { magnitude };
+    i64::try_from(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{DocumentSymbol, Position, Range, SymbolKind};
+
+    #[allow(deprecated)]
+    fn symbol(
+        name: &str,
+        kind: SymbolKind,
+        range: Range,
+        selection_range: Range,
+        detail: Option<&str>,
+        children: Option<Vec<DocumentSymbol>>,
+    ) -> DocumentSymbol {
+        DocumentSymbol {
+            name: name.to_string(),
+            detail: detail.map(str::to_string),
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range,
+            children,
+        }
+    }
+
+    fn enumerator(name: &str, line: u32, column: u32, end: u32) -> DocumentSymbol {
+        let name_end = column + name.len() as u32;
+        symbol(
+            name,
+            SymbolKind::ENUM_MEMBER,
+            Range::new(Position::new(line, column), Position::new(line, end)),
+            Range::new(Position::new(line, column), Position::new(line, name_end)),
+            None,
+            None,
+        )
+    }
+
+    const SOURCE: &str = "\
+enum class Mode {
+  Off,
+  On = 0x10,
+  Auto,
+  Mask = On | 1,
+  Last,
+};
+";
+
+    fn mode() -> DocumentSymbol {
+        let whole = Range::new(Position::new(0, 0), Position::new(6, 1));
+        let name = Range::new(Position::new(0, 11), Position::new(0, 15));
+        // Out of order on purpose: enumerators are reported in declaration order
+        symbol(
+            "Mode",
+            SymbolKind::ENUM,
+            whole,
+            name,
+            None,
+            Some(vec![
+                enumerator("Auto", 3, 2, 6),
+                enumerator("Off", 1, 2, 5),
+                enumerator("On", 2, 2, 11),
+                enumerator("Mask", 4, 2, 15),
+                enumerator("Last", 5, 2, 6),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_enumerators_from_source() {
+        let enumerators = get_enumerators(&mode(), Some(SOURCE), PositionEncoding::Utf16);
+        let summary: Vec<_> = enumerators
+            .iter()
+            .map(|e| (e.name.as_str(), e.value, e.initializer.as_deref()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Off", Some(0), None),
+                ("On", Some(16), Some("0x10")),
+                ("Auto", Some(17), None),
+                ("Mask", None, Some("On | 1")),
+                ("Last", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enumerator_values_from_detail() {
+        let mut mode = mode();
+        for child in mode.children.iter_mut().flatten() {
+            if child.name == "Mask" {
+                child.detail = Some("Mode = 17".to_string());
+            }
+        }
+
+        let enumerators = get_enumerators(&mode, None, PositionEncoding::Utf16);
+        let values: Vec<_> = enumerators.iter().map(|e| e.value).collect();
+
+        assert_eq!(values, vec![Some(0), Some(1), Some(2), Some(17), Some(18)]);
+    }
+
+    #[test]
+    fn test_parse_integer_literal() {
+        assert_eq!(parse_integer_literal("42"), Some(42));
+        assert_eq!(parse_integer_literal(" -7 "), Some(-7));
+        assert_eq!(parse_integer_literal("0x1F"), Some(31));
+        assert_eq!(parse_integer_literal("0b101"), Some(5));
+        assert_eq!(parse_integer_literal("017"), Some(15));
+        assert_eq!(parse_integer_literal("1'000ull"), Some(1000));
+        assert_eq!(parse_integer_literal("0"), Some(0));
+        assert_eq!(parse_integer_literal("1 << 3"), None);
+        assert_eq!(parse_integer_literal("Other"), None);
+    }
+}
@@ -4,47 +4,229 @@
 //! that work with clangd to find real usage patterns of symbols throughout the
 //! codebase, with configurable limits.
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::reference_cache::ReferenceKey;
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
 use crate::project::component_session::ComponentSession;
 use crate::symbol::FileLocation;
 
+/// References to a symbol within a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileReferences {
+    pub file: PathBuf,
+    pub reference_count: usize,
+    /// 1-based line numbers of the references, in ascending order
+    pub lines: Vec<u32>,
+}
+
 // ============================================================================
 // Public API
 // ============================================================================
 
+/// Get all references to a symbol, excluding its declaration
+pub async fn get_references(
+    component_session: &ComponentSession,
+    symbol_location: &FileLocation,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    request_references(component_session, symbol_location, false).await
+}
+
+/// Get all references to a symbol, including its declarations and definitions
+pub async fn get_references_with_declarations(
+    component_session: &ComponentSession,
+    symbol_location: &FileLocation,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    request_references(component_session, symbol_location, true).await
+}
+
 /// Get usage examples for a symbol (returns locations only)
+#[allow(dead_code)]
 pub async fn get_examples(
     component_session: &ComponentSession,
     symbol_location: &FileLocation,
     max_examples: Option<u32>,
+) -> Result<Vec<FileLocation>, AnalyzerError> {
+    let reference_locations = get_references(component_session, symbol_location).await?;
+    Ok(limit_examples(reference_locations, max_examples))
+}
+
+/// Apply the max_examples limit to a list of reference locations
+pub fn limit_examples(
+    reference_locations: Vec<FileLocation>,
+    max_examples: Option<u32>,
+) -> Vec<FileLocation> {
+    match max_examples {
+        Some(max) => reference_locations.into_iter().take(max as usize).collect(),
+        None => reference_locations,
+    }
+}
+
+/// Group reference locations by file
+///
+/// Files are sorted by reference count (highest first), ties by path.
+pub fn group_references_by_file(references: &[FileLocation]) -> Vec<FileReferences> {
+    let mut by_file: HashMap<&PathBuf, Vec<u32>> = HashMap::new();
+    for reference in references {
+        by_file
+            .entry(&reference.file_path)
+            .or_default()
+            .push(reference.range.start.line + 1);
+    }
+
+    let mut groups: Vec<FileReferences> = by_file
+        .into_iter()
+        .map(|(file, mut lines)| {
+            lines.sort_unstable();
+            FileReferences {
+                file: file.clone(),
+                reference_count: lines.len(),
+                lines,
+            }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.reference_count
+            .cmp(&a.reference_count)
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    groups
+}
+
+/// Send a references request, with or without declarations
+///
+/// Results are cached until the index generation of the session advances, so
+/// repeated analyses of the same symbol on unchanged code skip the round-trip.
+async fn request_references(
+    component_session: &ComponentSession,
+    symbol_location: &FileLocation,
+    include_declaration: bool,
 ) -> Result<Vec<FileLocation>, AnalyzerError> {
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
 
-    // Ensure file is ready first
+    // Sync the file first: a change on disk advances the generation
     component_session
         .ensure_file_ready(&symbol_location.file_path)
         .await?;
+    let generation = component_session.index_generation();
+    let key = ReferenceKey::new(&uri, lsp_position, include_declaration);
+    if let Some(references) = component_session.cached_references(generation, &key) {
+        return Ok(references.iter().map(FileLocation::from).collect());
+    }
 
-    // Get LSP session and make the request
-    let mut session = component_session.lsp_session().await;
-    let references = session
-        .client_mut()
-        .text_document_references(uri, lsp_position, false)
-        .await
-        .map_err(AnalyzerError::from)?;
+    let references = component_session
+        .coalesced(
+            "textDocument/references",
+            &(&uri, lsp_position, include_declaration),
+            || async {
+                // Get LSP session and make the request
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .text_document_references(uri.clone(), lsp_position, include_declaration)
+                    .await
+                    .map_err(AnalyzerError::from)
+            },
+        )
+        .await?
+        .unwrap_or_default();
+    component_session.cache_references(generation, key, references.clone());
 
     // Convert references to FileLocation
-    let reference_locations: Vec<FileLocation> =
-        references.iter().map(FileLocation::from).collect();
+    Ok(references.iter().map(FileLocation::from).collect())
+}
 
-    // Apply max_examples limit if specified
-    let example_locations = match max_examples {
-        Some(max) => reference_locations.into_iter().take(max as usize).collect(),
-        None => reference_locations,
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(file: &str, line: u32) -> FileLocation {
+        format!("{}:{}:1", file, line).parse().unwrap()
+    }
+
+    #[test]
+    fn test_group_references_by_file() {
+        let references = vec![
+            reference("/src/b.cpp", 10),
+            reference("/src/a.cpp", 7),
+            reference("/src/b.cpp", 3),
+            reference("/src/c.cpp", 1),
+            reference("/src/b.cpp", 3),
+            reference("/src/a.cpp", 2),
+        ];
+
+        let groups = group_references_by_file(&references);
+        assert_eq!(
+            groups,
+            vec![
+                FileReferences {
+                    file: PathBuf::from("/src/b.cpp"),
+                    reference_count: 3,
+                    lines: vec![3, 3, 10],
+                },
+                FileReferences {
+                    file: PathBuf::from("/src/a.cpp"),
+                    reference_count: 2,
+                    lines: vec![2, 7],
+                },
+                FileReferences {
+                    file: PathBuf::from("/src/c.cpp"),
+                    reference_count: 1,
+                    lines: vec![1],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_references_are_cached_until_file_changes() {
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int answer();\nint x = answer();\n")]);
+        let main_cpp = workspace.project_root_path.join("src/main.cpp");
+        let uri = crate::symbol::uri_from_pathbuf(&main_cpp);
+
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/references",
+            serde_json::json!([{
+                "uri": uri.as_str(),
+                "range": {
+                    "start": {"line": 1, "character": 8},
+                    "end": {"line": 1, "character": 14}
+                }
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let location: FileLocation = format!("{}:1:5", main_cpp.display()).parse().unwrap();
+
+        let first = get_references(&component_session, &location).await.unwrap();
+        let second = get_references(&component_session, &location).await.unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(second[0].range.start.line, 1);
+        assert_eq!(server.received_params("textDocument/references").len(), 1);
+
+        // Editing the file advances the index generation
+        std::fs::write(&main_cpp, "int answer();\nint y = answer();\n").unwrap();
+        get_references(&component_session, &location).await.unwrap();
+        assert_eq!(server.received_params("textDocument/references").len(), 2);
+    }
 
-    Ok(example_locations)
+    #[test]
+    fn test_limit_examples() {
+        let references = vec![reference("/a.cpp", 1), reference("/a.cpp", 2)];
+        assert_eq!(limit_examples(references.clone(), Some(1)).len(), 1);
+        assert_eq!(limit_examples(references, None).len(), 2);
+    }
 }
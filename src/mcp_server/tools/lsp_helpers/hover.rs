@@ -22,18 +22,22 @@ pub async fn get_hover_info(
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
 
-    // Ensure file is ready first
-    component_session
-        .ensure_file_ready(&symbol_location.file_path)
-        .await?;
+    let hover_info = component_session
+        .coalesced("textDocument/hover", &(&uri, lsp_position), || async {
+            // Ensure file is ready first
+            component_session
+                .ensure_file_ready(&symbol_location.file_path)
+                .await?;
 
-    // Get LSP session and make the request
-    let mut session = component_session.lsp_session().await;
-    let client = session.client_mut();
-    let hover_info = client
-        .text_document_hover(uri, lsp_position)
-        .await
-        .map_err(AnalyzerError::from)?;
+            // Get LSP session and make the request
+            let mut session = component_session.lsp_session().await;
+            session
+                .client_mut()
+                .text_document_hover(uri.clone(), lsp_position)
+                .await
+                .map_err(AnalyzerError::from)
+        })
+        .await?;
 
     let markup = match hover_info {
         Some(lsp_types::Hover {
@@ -57,3 +61,46 @@ pub async fn get_hover_info(
         )),
     }
 }
+
+/// Symbol kind named in the heading of clangd's hover, e.g. "### variable `x`"
+///
+/// Local variables, parameters and macros have no document symbol; the hover
+/// heading is the only place clangd reports what they are. Macros map to
+/// `CONSTANT` as LSP has no dedicated kind for them.
+pub fn hover_symbol_kind(hover: &str) -> Option<lsp_types::SymbolKind> {
+    let heading = hover.lines().next()?.strip_prefix("### ")?;
+    let kind = heading.split_whitespace().next()?;
+    Some(match kind {
+        "variable" | "param" => lsp_types::SymbolKind::VARIABLE,
+        "field" | "static-property" => lsp_types::SymbolKind::FIELD,
+        "macro" => lsp_types::SymbolKind::CONSTANT,
+        "enum-member" => lsp_types::SymbolKind::ENUM_MEMBER,
+        "function" => lsp_types::SymbolKind::FUNCTION,
+        "instance-method" | "static-method" | "method" => lsp_types::SymbolKind::METHOD,
+        "constructor" => lsp_types::SymbolKind::CONSTRUCTOR,
+        "class" | "type-alias" => lsp_types::SymbolKind::CLASS,
+        "struct" | "union" => lsp_types::SymbolKind::STRUCT,
+        "enum" => lsp_types::SymbolKind::ENUM,
+        "namespace" => lsp_types::SymbolKind::NAMESPACE,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hover_symbol_kind() {
+        assert_eq!(
+            hover_symbol_kind("### variable `count`\n\n---\nType: `int`"),
+            Some(lsp_types::SymbolKind::VARIABLE)
+        );
+        assert_eq!(
+            hover_symbol_kind("### macro `MAX_SIZE`\n\n---\n```cpp\n#define MAX_SIZE 16\n```"),
+            Some(lsp_types::SymbolKind::CONSTANT)
+        );
+        assert_eq!(hover_symbol_kind("### unknown-kind `x`"), None);
+        assert_eq!(hover_symbol_kind("int x"), None);
+    }
+}
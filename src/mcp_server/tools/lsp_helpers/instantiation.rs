@@ -0,0 +1,250 @@
+//! Template instantiations at their use sites
+//!
+//! clangd has no request describing `std::vector<int>` as such: instantiations
+//! only exist where code uses them. An instantiation is found by looking
+//! through the references of the template for a use spelled with the requested
+//! arguments, then asking clangd about that use site. Its hover describes the
+//! instantiated type, and its definition is an explicit or partial
+//! specialization when one is selected instead of the primary template.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+use crate::io::file_buffer::read_context_source_async;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::lsp_helpers::hover::get_hover_info;
+use crate::project::ComponentSession;
+use crate::symbol::FileLocation;
+
+/// Number of lines after a reference searched for its template argument list
+const MAX_ARGUMENT_LIST_LINES: usize = 5;
+
+/// A template instantiation resolved at a use site
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateInstantiation {
+    /// Instantiation as requested, e.g. "std::vector<int>"
+    pub requested: String,
+    /// Use of the template with the requested arguments
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub use_site: Option<FileLocation>,
+    /// Source line of the use site
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub use_site_code: Option<String>,
+    /// Hover at the use site, describing the instantiated type
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub hover: Option<String>,
+    /// Explicit or partial specialization used for the instantiation
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub specialization: Option<FileLocation>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+}
+
+/// Split a symbol into the template name and its argument list
+///
+/// `"std::map<int, Foo<bool>>"` gives `("std::map", "int, Foo<bool>")`.
+/// Returns `None` when the symbol has no trailing argument list, as for
+/// comparison operators.
+pub fn split_template_arguments(symbol: &str) -> Option<(&str, &str)> {
+    let symbol = symbol.trim();
+    let inner = symbol.strip_suffix('>')?;
+    let mut depth = 0;
+    for (index, c) in inner.char_indices().rev() {
+        match c {
+            '>' => depth += 1,
+            '<' if depth > 0 => depth -= 1,
+            '<' => {
+                let name = inner[..index].trim_end();
+                let is_operator = name
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or(name)
+                    .starts_with("operator");
+                return (!name.is_empty() && !is_operator)
+                    .then(|| (name, inner[index + 1..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Normalize the spelling of a template argument list for comparison
+///
+/// Whitespace is dropped except between two identifier characters, so
+/// `Foo< int,bool >` and `Foo<int, bool>` compare equal while `unsigned int`
+/// keeps its space.
+pub fn normalize_template_arguments(arguments: &str) -> String {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let mut normalized = String::with_capacity(arguments.len());
+    let mut pending_space = false;
+    for c in arguments.trim().chars() {
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && normalized.ends_with(is_identifier) && is_identifier(c) {
+            normalized.push(' ');
+        }
+        pending_space = false;
+        normalized.push(c);
+    }
+    normalized
+}
+
+/// Template argument list following a template name, without its brackets
+fn argument_list_at(text: &str) -> Option<&str> {
+    let rest = text.trim_start();
+    let start = text.len() - rest.len();
+    let rest = rest.strip_prefix('<')?;
+    let mut depth = 0;
+    for (index, c) in rest.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            ')' => depth -= 1,
+            '>' if depth > 0 => depth -= 1,
+            '>' => return Some(&text[start + 1..start + 1 + index]),
+            ';' | '{' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find the use of a template with the requested arguments and resolve it
+///
+/// `references` are the references of the template; `template_locations` its
+/// declarations and definitions, which tell the primary template apart from
+/// specializations. Reference files over `max_file_size` bytes are skipped.
+pub async fn find_instantiation(
+    component_session: &ComponentSession,
+    template_locations: &[FileLocation],
+    references: &[FileLocation],
+    requested: &str,
+    arguments: &str,
+    max_file_size: u64,
+) -> TemplateInstantiation {
+    let wanted = normalize_template_arguments(arguments);
+    let encoding = component_session.position_encoding().await;
+    let mut contents: HashMap<PathBuf, Option<String>> = HashMap::new();
+
+    let mut found = None;
+    for reference in references {
+        if !contents.contains_key(&reference.file_path) {
+            let content = read_context_source_async(&reference.file_path, max_file_size)
+                .await
+                .ok();
+            contents.insert(reference.file_path.clone(), content);
+        }
+        let Some(content) = &contents[&reference.file_path] else {
+            continue;
+        };
+        let lines: Vec<&str> = content
+            .lines()
+            .skip(reference.range.end.line as usize)
+            .take(MAX_ARGUMENT_LIST_LINES)
+            .collect();
+        let Some(offset) = lines
+            .first()
+            .and_then(|line| encoding.byte_offset(line, reference.range.end.column))
+        else {
+            continue;
+        };
+        let text = lines.join("\n");
+        if argument_list_at(&text[offset..])
+            .is_some_and(|list| normalize_template_arguments(list) == wanted)
+        {
+            let code = content
+                .lines()
+                .nth(reference.range.start.line as usize)
+                .map(|line| line.trim().to_string());
+            found = Some((reference.clone(), code));
+            break;
+        }
+    }
+
+    let Some((use_site, use_site_code)) = found else {
+        info!(
+            "No use of {} among {} references",
+            requested,
+            references.len()
+        );
+        return TemplateInstantiation {
+            requested: requested.to_string(),
+            use_site: None,
+            use_site_code: None,
+            hover: None,
+            specialization: None,
+            note: Some(format!(
+                "No use of {requested} was found among the {} references of the template. \
+                 clangd only knows the instantiations that appear in indexed code; the analysis \
+                 describes the primary template.",
+                references.len()
+            )),
+        };
+    };
+
+    let hover = get_hover_info(&use_site, component_session)
+        .await
+        .map_err(|e| warn!("Failed to get hover at the instantiation: {}", e))
+        .ok();
+    let specialization = get_definitions(&use_site, component_session)
+        .await
+        .map_err(|e| warn!("Failed to get the definition of the instantiation: {}", e))
+        .unwrap_or_default()
+        .into_iter()
+        .find(|definition| {
+            !template_locations.iter().any(|location| {
+                location.file_path == definition.file_path
+                    && location.range.start.line == definition.range.start.line
+            })
+        });
+
+    TemplateInstantiation {
+        requested: requested.to_string(),
+        use_site: Some(use_site),
+        use_site_code,
+        hover,
+        specialization,
+        note: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_template_arguments() {
+        assert_eq!(
+            split_template_arguments("std::map<int, Foo<bool>>"),
+            Some(("std::map", "int, Foo<bool>"))
+        );
+        assert_eq!(split_template_arguments("Buffer<>"), Some(("Buffer", "")));
+        assert_eq!(split_template_arguments("std::vector"), None);
+        assert_eq!(split_template_arguments("Point::operator<"), None);
+        assert_eq!(split_template_arguments("operator<=>"), None);
+        assert_eq!(split_template_arguments("<int>"), None);
+    }
+
+    #[test]
+    fn test_argument_list_matching() {
+        assert_eq!(
+            normalize_template_arguments(" const  unsigned int *, Foo< bool > "),
+            "const unsigned int*,Foo<bool>"
+        );
+        assert_eq!(
+            argument_list_at("<int, std::vector<int>> values;"),
+            Some("int, std::vector<int>")
+        );
+        assert_eq!(
+            argument_list_at(" <\n  int,\n  bool> x;"),
+            Some("\n  int,\n  bool")
+        );
+        assert_eq!(argument_list_at("<(2 > 1)> x;"), Some("(2 > 1)"));
+        assert_eq!(argument_list_at(" values;"), None);
+        assert_eq!(argument_list_at(" < b; }"), None);
+    }
+}
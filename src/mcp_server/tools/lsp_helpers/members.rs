@@ -8,6 +8,7 @@
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+use crate::lsp::PositionEncoding;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
 use crate::mcp_server::tools::lsp_helpers::document_symbols::{
     extract_class_members, get_document_symbols,
@@ -23,7 +24,7 @@ use crate::symbol::FileLocation;
 pub struct Member {
     /// Member name
     pub name: String,
-    /// Member type: "method", "constructor", "operator"
+    /// Member type: "method", "field", "constructor", "destructor", "operator", "type"
     pub member_type: String,
     /// Full function signature
     pub signature: String,
@@ -32,16 +33,71 @@ pub struct Member {
     pub access: Option<String>,
 }
 
+/// Member type names accepted by `MemberFilter::kinds`
+pub const MEMBER_KINDS: &[&str] = &[
+    "method",
+    "field",
+    "constructor",
+    "destructor",
+    "operator",
+    "type",
+];
+
+/// Access level names accepted by `MemberFilter::access`
+pub const ACCESS_LEVELS: &[&str] = &["public", "protected", "private"];
+
+/// Criteria selecting which class members are reported
+///
+/// The default filter keeps every member.
+#[derive(Debug, Clone, Default)]
+pub struct MemberFilter {
+    /// Access levels to keep; members with unknown access are always kept
+    pub access: Option<Vec<String>>,
+    /// Member types to keep (see `MEMBER_KINDS`)
+    pub kinds: Option<Vec<String>>,
+    /// Drop constructors, destructors and operator overloads
+    pub exclude_special: bool,
+}
+
+impl MemberFilter {
+    /// Check whether a member passes the filter
+    pub fn matches(&self, member: &Member) -> bool {
+        if self.exclude_special
+            && matches!(
+                member.member_type.as_str(),
+                "constructor" | "destructor" | "operator"
+            )
+        {
+            return false;
+        }
+
+        if let Some(kinds) = &self.kinds
+            && !kinds.iter().any(|kind| kind == &member.member_type)
+        {
+            return false;
+        }
+
+        match (&self.access, &member.access) {
+            (Some(levels), Some(access)) => levels.iter().any(|level| level == access),
+            _ => true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Members {
     /// Methods (both instance and static methods)
     pub methods: Vec<Member>,
+    /// Data members (both instance and static fields)
+    pub fields: Vec<Member>,
     /// Constructors
     pub constructors: Vec<Member>,
     /// Destructors
     pub destructors: Vec<Member>,
     /// Operator overloads
     pub operators: Vec<Member>,
+    /// Nested types (classes, structs, enums)
+    pub types: Vec<Member>,
 }
 
 // ============================================================================
@@ -92,7 +148,11 @@ pub async fn get_members(
     );
 
     // Convert document symbols to structured member information
-    let members = categorize_members_from_symbols(member_symbols, target_name);
+    let members = member_symbols
+        .into_iter()
+        .map(|symbol| create_member_from_document_symbol(symbol, None))
+        .collect();
+    let members = categorize_members(members, target_name, &MemberFilter::default());
 
     Ok(members)
 }
@@ -130,25 +190,39 @@ pub fn get_members_from_symbols(
     );
 
     // Convert document symbols to structured member information
-    categorize_members_from_symbols(member_symbols, target_name)
+    let members = member_symbols
+        .into_iter()
+        .map(|symbol| create_member_from_document_symbol(symbol, None))
+        .collect();
+    categorize_members(members, target_name, &MemberFilter::default())
 }
 
 /// Extract members directly from a single matched document symbol
 ///
-/// This function extracts callable members directly from a matched document symbol,
+/// This function extracts members directly from a matched document symbol,
 /// avoiding the need to traverse all document symbols again when we already have
 /// the target symbol. This is more efficient for analyzer workflows where the
 /// matched document symbol is already available.
 ///
+/// clangd does not report access levels in document symbols; when the source text
+/// of the file declaring the class is given, they are inferred from its access
+/// specifiers.
+///
 /// # Arguments
 /// * `document_symbol` - The matched document symbol for the class/struct
 /// * `target_name` - Name of the class or struct (used for categorization logic)
+/// * `source` - Content of the file declaring the class, if available
+/// * `position_encoding` - Encoding of the columns in `document_symbol`
+/// * `filter` - Criteria selecting which members are reported
 ///
 /// # Returns
-/// * `Members` - Categorized member information including methods, constructors, and operators
+/// * `Members` - Categorized member information including methods, fields, constructors, and operators
 pub fn get_members_from_document_symbol(
     document_symbol: &lsp_types::DocumentSymbol,
     target_name: &str,
+    source: Option<&str>,
+    position_encoding: PositionEncoding,
+    filter: &MemberFilter,
 ) -> Members {
     debug!(
         "Extracting members directly from document symbol for class '{}'",
@@ -156,11 +230,8 @@ pub fn get_members_from_document_symbol(
     );
 
     // Get the children of the document symbol (these are the members)
-    let member_symbols: Vec<&lsp_types::DocumentSymbol> = document_symbol
-        .children
-        .as_ref()
-        .map(|children| children.iter().collect())
-        .unwrap_or_default();
+    let member_symbols: &[lsp_types::DocumentSymbol] =
+        document_symbol.children.as_deref().unwrap_or_default();
 
     debug!(
         "Found {} direct members for class '{}'",
@@ -168,67 +239,70 @@ pub fn get_members_from_document_symbol(
         target_name
     );
 
+    let accesses = match source {
+        Some(source) => infer_member_access(document_symbol, source, position_encoding),
+        None => vec![None; member_symbols.len()],
+    };
+
     // Convert document symbols to structured member information
-    categorize_members_from_symbols(member_symbols, target_name)
+    let members = member_symbols
+        .iter()
+        .zip(accesses)
+        .map(|(symbol, access)| create_member_from_document_symbol(symbol, access))
+        .collect();
+    categorize_members(members, target_name, filter)
 }
 
 // ============================================================================
 // Member Categorization Logic
 // ============================================================================
 
-/// Convert document symbols to categorized member information using modern symbol analysis
+/// Distribute members into categories, keeping only those accepted by the filter
 ///
-/// This function processes the hierarchical document symbols extracted by the advanced
-/// document symbols infrastructure and categorizes them into structured member types
-/// (methods, constructors, destructors, operators) for comprehensive class analysis.
-fn categorize_members_from_symbols(
-    member_symbols: Vec<&lsp_types::DocumentSymbol>,
-    target_name: &str,
-) -> Members {
-    let mut methods = Vec::new();
-    let mut constructors = Vec::new();
-    let mut destructors = Vec::new();
-    let mut operators = Vec::new();
+/// This function organizes the members of a class into structured member types
+/// (methods, fields, constructors, destructors, operators) for comprehensive class analysis.
+fn categorize_members(members: Vec<Member>, target_name: &str, filter: &MemberFilter) -> Members {
+    let mut categorized = Members {
+        methods: Vec::new(),
+        fields: Vec::new(),
+        constructors: Vec::new(),
+        destructors: Vec::new(),
+        operators: Vec::new(),
+        types: Vec::new(),
+    };
 
     debug!(
         "Categorizing {} member symbols for class '{}'",
-        member_symbols.len(),
+        members.len(),
         target_name
     );
 
-    for symbol in member_symbols {
+    for member in members {
         debug!(
-            "Processing member: '{}' (kind: {:?}, detail: {:?})",
-            symbol.name, symbol.kind, symbol.detail
+            "Processing member: '{}' (type: {}, access: {:?})",
+            member.name, member.member_type, member.access
         );
 
-        let member = create_member_from_document_symbol(symbol);
+        if !filter.matches(&member) {
+            continue;
+        }
 
         // Categorize member into appropriate type
-        categorize_member(
-            member,
-            &mut methods,
-            &mut constructors,
-            &mut destructors,
-            &mut operators,
-        );
+        categorize_member(member, &mut categorized);
     }
 
     debug!(
-        "Member categorization complete for '{}': {} methods, {} constructors, {} destructors, {} operators",
+        "Member categorization complete for '{}': {} methods, {} fields, {} constructors, {} destructors, {} operators, {} types",
         target_name,
-        methods.len(),
-        constructors.len(),
-        destructors.len(),
-        operators.len()
+        categorized.methods.len(),
+        categorized.fields.len(),
+        categorized.constructors.len(),
+        categorized.destructors.len(),
+        categorized.operators.len(),
+        categorized.types.len()
     );
 
-    Members {
-        methods,
-        constructors,
-        destructors,
-        operators,
-    }
+    categorized
 }
 
 // ============================================================================
@@ -240,21 +314,24 @@ fn categorize_members_from_symbols(
 /// This function converts a hierarchical document symbol into a structured Member
 /// representation, extracting the symbol name, type classification, signature details,
 /// and other relevant member characteristics for C++ class analysis.
-fn create_member_from_document_symbol(symbol: &lsp_types::DocumentSymbol) -> Member {
+fn create_member_from_document_symbol(
+    symbol: &lsp_types::DocumentSymbol,
+    access: Option<String>,
+) -> Member {
     let member_type = classify_member_kind(&symbol.name, symbol.kind);
 
     Member {
         name: symbol.name.clone(),
         member_type,
         signature: symbol.detail.clone().unwrap_or_else(|| symbol.name.clone()),
-        access: None, // Access level information is not typically available in DocumentSymbol
+        access,
     }
 }
 
 /// Classify member kind based on symbol name and LSP SymbolKind
 ///
 /// This function analyzes the symbol name and LSP type information to determine
-/// the appropriate member classification (method, constructor, operator) for
+/// the appropriate member classification (method, field, constructor, operator) for
 /// structured member categorization in C++ class analysis.
 fn classify_member_kind(name: &str, symbol_kind: lsp_types::SymbolKind) -> String {
     use lsp_types::SymbolKind;
@@ -274,6 +351,10 @@ fn classify_member_kind(name: &str, symbol_kind: lsp_types::SymbolKind) -> Strin
                 "method".to_string()
             }
         }
+        SymbolKind::FIELD | SymbolKind::VARIABLE | SymbolKind::CONSTANT => "field".to_string(),
+        SymbolKind::CLASS | SymbolKind::STRUCT | SymbolKind::ENUM | SymbolKind::INTERFACE => {
+            "type".to_string()
+        }
         SymbolKind::FUNCTION => "method".to_string(), // Treat function symbols as regular methods
         _ => "method".to_string(),                    // Default fallback to method classification
     }
@@ -282,20 +363,293 @@ fn classify_member_kind(name: &str, symbol_kind: lsp_types::SymbolKind) -> Strin
 /// Categorize a Member into the appropriate collection based on member type
 ///
 /// This function distributes Member objects into their respective categories
-/// (methods, constructors, destructors, operators) based on the classified member type,
+/// (methods, fields, constructors, destructors, operators, types) based on the classified member type,
 /// providing structured organization for comprehensive class member analysis.
-fn categorize_member(
-    member: Member,
-    methods: &mut Vec<Member>,
-    constructors: &mut Vec<Member>,
-    destructors: &mut Vec<Member>,
-    operators: &mut Vec<Member>,
-) {
+fn categorize_member(member: Member, members: &mut Members) {
     match member.member_type.as_str() {
-        "method" => methods.push(member),
-        "constructor" => constructors.push(member),
-        "destructor" => destructors.push(member),
-        "operator" => operators.push(member),
-        _ => methods.push(member), // Default to methods for unknown member types
+        "method" => members.methods.push(member),
+        "field" => members.fields.push(member),
+        "constructor" => members.constructors.push(member),
+        "destructor" => members.destructors.push(member),
+        "operator" => members.operators.push(member),
+        "type" => members.types.push(member),
+        _ => members.methods.push(member), // Default to methods for unknown member types
+    }
+}
+
+// ============================================================================
+// Access Level Inference
+// ============================================================================
+
+/// Infer the access level of each direct child of a class from its source text
+///
+/// Members declared before the first access specifier get the default access of
+/// the class key: private for classes, public for structs. Specifiers inside
+/// nested types are ignored.
+///
+/// # Returns
+/// * One entry per child of `class_symbol`, `None` when access cannot be determined
+fn infer_member_access(
+    class_symbol: &lsp_types::DocumentSymbol,
+    source: &str,
+    position_encoding: PositionEncoding,
+) -> Vec<Option<String>> {
+    let children: &[lsp_types::DocumentSymbol] =
+        class_symbol.children.as_deref().unwrap_or_default();
+
+    let default_access = match class_symbol.kind {
+        lsp_types::SymbolKind::CLASS => "private",
+        lsp_types::SymbolKind::STRUCT => "public",
+        _ => return vec![None; children.len()],
+    };
+
+    let inside = |position: &lsp_types::Position, range: &lsp_types::Range| {
+        (position.line, position.character) >= (range.start.line, range.start.character)
+            && (position.line, position.character) < (range.end.line, range.end.character)
+    };
+
+    // Access specifiers of the class body itself, in source order
+    let specifiers: Vec<(lsp_types::Position, &str)> = source
+        .lines()
+        .enumerate()
+        .skip(class_symbol.range.start.line as usize)
+        .take((class_symbol.range.end.line - class_symbol.range.start.line + 1) as usize)
+        .flat_map(|(line, text)| {
+            find_access_specifiers(text)
+                .into_iter()
+                .map(move |(byte_offset, access)| {
+                    let column = position_encoding.column(text, byte_offset);
+                    (lsp_types::Position::new(line as u32, column), access)
+                })
+        })
+        .filter(|(position, _)| inside(position, &class_symbol.range))
+        .filter(|(position, _)| !children.iter().any(|child| inside(position, &child.range)))
+        .collect();
+
+    children
+        .iter()
+        .map(|child| {
+            let start = (child.range.start.line, child.range.start.character);
+            let access = specifiers
+                .iter()
+                .rev()
+                .find(|(position, _)| (position.line, position.character) < start)
+                .map_or(default_access, |(_, access)| access);
+            Some(access.to_string())
+        })
+        .collect()
+}
+
+/// Find access specifier labels (`public:`, `protected:`, `private:`) in a line
+///
+/// # Returns
+/// * Byte offset and access level of each label, in line order
+fn find_access_specifiers(line: &str) -> Vec<(usize, &'static str)> {
+    let code = line.split("//").next().unwrap_or_default();
+    let mut specifiers = Vec::new();
+
+    for access in ACCESS_LEVELS {
+        for (column, _) in code.match_indices(access) {
+            let preceded_by_identifier = code[..column]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            let rest = code[column + access.len()..].trim_start();
+            if !preceded_by_identifier && rest.starts_with(':') && !rest.starts_with("::") {
+                specifiers.push((column, *access));
+            }
+        }
+    }
+
+    specifiers.sort_by_key(|(column, _)| *column);
+    specifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{DocumentSymbol, Position, Range, SymbolKind};
+
+    fn symbol(
+        name: &str,
+        kind: SymbolKind,
+        lines: (u32, u32),
+        children: Option<Vec<DocumentSymbol>>,
+    ) -> DocumentSymbol {
+        let range = Range::new(Position::new(lines.0, 2), Position::new(lines.1, 20));
+        #[allow(deprecated)]
+        DocumentSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range: range,
+            children,
+        }
+    }
+
+    const SOURCE: &str = "\
+class Widget {
+  int id_;
+public:
+  Widget();
+  ~Widget();
+  void draw();
+  bool operator==(const Widget&) const;
+  struct Config {
+  private:
+    int depth;
+  };
+protected: int cache_;
+};
+";
+
+    fn widget() -> DocumentSymbol {
+        let config = symbol(
+            "Config",
+            SymbolKind::STRUCT,
+            (7, 10),
+            Some(vec![symbol("depth", SymbolKind::FIELD, (9, 9), None)]),
+        );
+        let mut class = symbol(
+            "Widget",
+            SymbolKind::CLASS,
+            (0, 12),
+            Some(vec![
+                symbol("id_", SymbolKind::FIELD, (1, 1), None),
+                symbol("Widget", SymbolKind::CONSTRUCTOR, (3, 3), None),
+                symbol("~Widget", SymbolKind::CONSTRUCTOR, (4, 4), None),
+                symbol("draw", SymbolKind::METHOD, (5, 5), None),
+                symbol("operator==", SymbolKind::METHOD, (6, 6), None),
+                config,
+                symbol("cache_", SymbolKind::FIELD, (11, 11), None),
+            ]),
+        );
+        class.range.start.character = 0;
+        // "protected:" precedes the field on the same line
+        class.children.as_mut().unwrap()[6].range.start.character = 13;
+        class
+    }
+
+    #[test]
+    fn test_find_access_specifiers() {
+        assert_eq!(find_access_specifiers("public:"), vec![(0, "public")]);
+        assert_eq!(
+            find_access_specifiers("  protected: int x; private :"),
+            vec![(2, "protected"), (20, "private")]
+        );
+        assert!(find_access_specifiers("class A : public B {").is_empty());
+        assert!(find_access_specifiers("x = public::value;").is_empty());
+        assert!(find_access_specifiers("int is_public: 1;").is_empty());
+        assert!(find_access_specifiers("// public:").is_empty());
+    }
+
+    #[test]
+    fn test_infer_member_access() {
+        let access = infer_member_access(&widget(), SOURCE, PositionEncoding::Utf16);
+        let access: Vec<&str> = access.iter().map(|a| a.as_deref().unwrap()).collect();
+        assert_eq!(
+            access,
+            vec![
+                "private",   // id_ (class default)
+                "public",    // Widget
+                "public",    // ~Widget
+                "public",    // draw
+                "public",    // operator==
+                "public",    // Config ("private:" inside it is ignored)
+                "protected", // cache_
+            ]
+        );
+
+        let mut record = widget();
+        record.kind = SymbolKind::STRUCT;
+        let access = infer_member_access(
+            &record,
+            "struct Widget {\n  int id_;\n",
+            PositionEncoding::Utf16,
+        );
+        assert_eq!(access[0].as_deref(), Some("public"));
+    }
+
+    #[test]
+    fn test_infer_member_access_after_multibyte_text() {
+        // Each emoji is 4 UTF-8 bytes but 2 UTF-16 units, so byte offsets would
+        // place "public:" after the field that follows it
+        let source = "class Emoji {\n  /* 😀😀😀😀😀 */ public: int wert;\n};\n";
+        let mut class = symbol(
+            "Emoji",
+            SymbolKind::CLASS,
+            (0, 2),
+            Some(vec![symbol("wert", SymbolKind::FIELD, (1, 1), None)]),
+        );
+        class.range.start.character = 0;
+
+        class.children.as_mut().unwrap()[0].range =
+            Range::new(Position::new(1, 27), Position::new(1, 35));
+        let access = infer_member_access(&class, source, PositionEncoding::Utf16);
+        assert_eq!(access[0].as_deref(), Some("public"));
+
+        // The same field with columns in UTF-8 bytes
+        class.children.as_mut().unwrap()[0].range =
+            Range::new(Position::new(1, 37), Position::new(1, 45));
+        let access = infer_member_access(&class, source, PositionEncoding::Utf8);
+        assert_eq!(access[0].as_deref(), Some("public"));
+    }
+
+    #[test]
+    fn test_members_with_filter() {
+        let all = get_members_from_document_symbol(
+            &widget(),
+            "Widget",
+            Some(SOURCE),
+            PositionEncoding::Utf16,
+            &MemberFilter::default(),
+        );
+        assert_eq!(all.methods.len(), 1);
+        assert_eq!(all.fields.len(), 2);
+        assert_eq!(all.constructors.len(), 1);
+        assert_eq!(all.destructors.len(), 1);
+        assert_eq!(all.operators.len(), 1);
+        assert_eq!(all.types.len(), 1);
+
+        let public_methods = get_members_from_document_symbol(
+            &widget(),
+            "Widget",
+            Some(SOURCE),
+            PositionEncoding::Utf16,
+            &MemberFilter {
+                access: Some(vec!["public".to_string()]),
+                kinds: Some(vec!["method".to_string()]),
+                exclude_special: false,
+            },
+        );
+        let names: Vec<&str> = public_methods
+            .methods
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["draw"]);
+        assert!(public_methods.fields.is_empty());
+        assert!(public_methods.types.is_empty());
+        assert!(public_methods.constructors.is_empty());
+
+        let no_special = get_members_from_document_symbol(
+            &widget(),
+            "Widget",
+            None,
+            PositionEncoding::Utf16,
+            &MemberFilter {
+                access: Some(vec!["public".to_string()]),
+                kinds: None,
+                exclude_special: true,
+            },
+        );
+        // Without source text access is unknown, so the access filter keeps everything
+        assert_eq!(no_special.fields.len(), 2);
+        assert!(no_special.constructors.is_empty());
+        assert!(no_special.destructors.is_empty());
+        assert!(no_special.operators.is_empty());
     }
 }
@@ -3,12 +3,26 @@
 //! This module contains helper functions that wrap LSP operations with
 //! domain-specific logic for C++ code analysis.
 
+pub mod ast;
 pub mod call_hierarchy;
 pub mod definitions;
+pub mod diagnostics;
+pub mod document_links;
 pub mod document_symbols;
+pub mod documentation;
+pub mod enumerators;
 pub mod examples;
 pub mod hover;
+pub mod instantiation;
 pub mod members;
+pub mod objc_selectors;
+pub mod overrides;
+pub mod selection_range;
+pub mod semantic_tokens;
+pub mod signatures;
+pub mod suggestions;
+pub mod switch_source_header;
 pub mod symbol_resolution;
+pub mod type_aliases;
 pub mod type_hierarchy;
 pub mod workspace_symbols;
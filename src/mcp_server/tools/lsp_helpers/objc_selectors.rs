@@ -0,0 +1,192 @@
+//! Objective-C method selector queries
+//!
+//! Objective-C++ methods are named by selectors such as `doThing:with:` and
+//! written `-[MyClass doThing:with:]` (instance methods) or `+[MyClass shared]`
+//! (class methods). clangd reports them as workspace symbols named by the
+//! selector, with the class or `Class(Category)` as container, and as document
+//! symbols named by the selector prefixed with `-` or `+`. This module
+//! recognizes selector-style queries and matches them against those symbols.
+
+/// Whether a method is sent to instances (`-`) or to the class (`+`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    Instance,
+    Class,
+}
+
+/// A parsed Objective-C method query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectorQuery<'a> {
+    /// `-` or `+` prefix, when given
+    pub method_kind: Option<MethodKind>,
+    /// Receiver class of a bracketed query
+    pub class_name: Option<&'a str>,
+    /// Category of a `Class(Category)` receiver, empty for class extensions
+    pub category: Option<&'a str>,
+    /// Method selector, e.g. `doThing:with:` or `count`
+    pub selector: &'a str,
+}
+
+impl<'a> SelectorQuery<'a> {
+    /// Parse a selector-style query
+    ///
+    /// Recognized forms are bracketed method names (`-[MyClass doThing:with:]`,
+    /// `+[MyClass(Cat) shared]`, `[MyClass count]`), signed selectors as used by
+    /// clangd document symbols (`-doThing:with:`, `+shared`) and bare keyword
+    /// selectors (`doThing:with:`). Plain identifiers and C++ names return `None`.
+    pub fn parse(query: &'a str) -> Option<Self> {
+        let (method_kind, rest) = split_method_kind(query.trim());
+
+        if let Some(body) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            let (receiver, selector) = body.trim().split_once(char::is_whitespace)?;
+            let selector = selector.trim();
+            let (class_name, category) = match receiver.split_once('(') {
+                Some((class_name, category)) => {
+                    (class_name.trim(), Some(category.strip_suffix(')')?.trim()))
+                }
+                None => (receiver, None),
+            };
+            let valid = is_identifier(class_name)
+                && category.is_none_or(|c| c.is_empty() || is_identifier(c))
+                && (is_identifier(selector) || is_keyword_selector(selector));
+            return valid.then_some(Self {
+                method_kind,
+                class_name: Some(class_name),
+                category,
+                selector,
+            });
+        }
+
+        let valid = is_keyword_selector(rest) || (method_kind.is_some() && is_identifier(rest));
+        valid.then_some(Self {
+            method_kind,
+            class_name: None,
+            category: None,
+            selector: rest,
+        })
+    }
+
+    /// Whether a symbol named `name` in `container` is the queried method
+    ///
+    /// `name` is a selector, optionally signed like clangd document symbols.
+    /// `container` is compared with the receiver class, ignoring the category
+    /// unless both name one.
+    pub fn matches(&self, name: &str, container: Option<&str>) -> bool {
+        let (method_kind, selector) = split_method_kind(name);
+        if selector != self.selector {
+            return false;
+        }
+        if let (Some(wanted), Some(kind)) = (self.method_kind, method_kind)
+            && wanted != kind
+        {
+            return false;
+        }
+        let Some(class_name) = self.class_name else {
+            return true;
+        };
+        let Some(container) = container else {
+            return false;
+        };
+        let (container_class, container_category) = match container.split_once('(') {
+            Some((class, category)) => (class.trim(), category.strip_suffix(')')),
+            None => (container.trim(), None),
+        };
+        container_class == class_name
+            && match (self.category, container_category) {
+                (Some(wanted), Some(category)) => wanted == category.trim(),
+                _ => true,
+            }
+    }
+}
+
+/// Split a leading `-` / `+` method kind off a name
+fn split_method_kind(name: &str) -> (Option<MethodKind>, &str) {
+    if let Some(rest) = name.strip_prefix('-') {
+        (Some(MethodKind::Instance), rest)
+    } else if let Some(rest) = name.strip_prefix('+') {
+        (Some(MethodKind::Class), rest)
+    } else {
+        (None, name)
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether `selector` is a selector taking arguments, such as `doThing:with:`
+fn is_keyword_selector(selector: &str) -> bool {
+    let Some(pieces) = selector.strip_suffix(':') else {
+        return false;
+    };
+    let mut pieces = pieces.split(':');
+    // Only the first piece is required, `foo::` is a valid selector
+    pieces.next().is_some_and(is_identifier) && pieces.all(|p| p.is_empty() || is_identifier(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selector_queries() {
+        assert_eq!(
+            SelectorQuery::parse("-[MyClass doThing:with:]"),
+            Some(SelectorQuery {
+                method_kind: Some(MethodKind::Instance),
+                class_name: Some("MyClass"),
+                category: None,
+                selector: "doThing:with:",
+            })
+        );
+        assert_eq!(
+            SelectorQuery::parse("+[MyClass(Factory) shared]"),
+            Some(SelectorQuery {
+                method_kind: Some(MethodKind::Class),
+                class_name: Some("MyClass"),
+                category: Some("Factory"),
+                selector: "shared",
+            })
+        );
+        let bare = SelectorQuery::parse("doThing:with:").unwrap();
+        assert_eq!((bare.method_kind, bare.class_name), (None, None));
+        assert_eq!(SelectorQuery::parse("-count").unwrap().selector, "count");
+        assert_eq!(SelectorQuery::parse("[Foo bar:]").unwrap().selector, "bar:");
+
+        for query in [
+            "count",
+            "MyClass::method",
+            "::main",
+            "operator-",
+            "std::vector<int>",
+            "-[MyClass]",
+            "-[My-Class count]",
+        ] {
+            assert_eq!(SelectorQuery::parse(query), None, "{query}");
+        }
+    }
+
+    #[test]
+    fn test_selector_matches_clangd_symbols() {
+        let query = SelectorQuery::parse("-[MyClass doThing:with:]").unwrap();
+        assert!(query.matches("doThing:with:", Some("MyClass")));
+        assert!(query.matches("-doThing:with:", Some("MyClass(Private)")));
+        assert!(!query.matches("+doThing:with:", Some("MyClass")));
+        assert!(!query.matches("doThing:with:", Some("OtherClass")));
+        assert!(!query.matches("doThing:", Some("MyClass")));
+        assert!(!query.matches("doThing:with:", None));
+
+        let category = SelectorQuery::parse("+[MyClass(Factory) shared]").unwrap();
+        assert!(category.matches("shared", Some("MyClass(Factory)")));
+        assert!(category.matches("shared", Some("MyClass")));
+        assert!(!category.matches("shared", Some("MyClass(Other)")));
+
+        let bare = SelectorQuery::parse("doThing:with:").unwrap();
+        assert!(bare.matches("doThing:with:", Some("AnyClass")));
+        assert!(bare.matches("-doThing:with:", None));
+    }
+}
@@ -0,0 +1,264 @@
+//! Virtual method override analysis
+//!
+//! Overrides of a virtual method come from `textDocument/implementation`, which
+//! clangd answers with every method overriding the one at the given position.
+//! clangd has no request for the opposite direction, so the methods a method
+//! overrides are found by walking the supertypes of its class with the type
+//! hierarchy and looking for same-named methods in each base class.
+
+use lsp_types::{DocumentSymbol, SymbolKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_implementations;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::{
+    PositionContains, find_symbol_at_position_with_path, get_document_symbols,
+};
+use crate::project::component_session::ComponentSession;
+use crate::symbol::{FileLocation, pathbuf_from_uri, uri_from_pathbuf};
+
+/// Maximum number of base classes visited when looking for overridden methods
+const MAX_BASE_CLASSES: usize = 64;
+
+/// A method taking part in virtual dispatch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodInfo {
+    /// Method name without qualification
+    pub name: String,
+    /// Name of the class declaring the method, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    /// Signature reported by clangd (e.g. "double () const override")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Location of the method name
+    pub location: FileLocation,
+}
+
+/// Describe the method at a location using the document symbols of its file
+///
+/// Returns None when no symbol encloses the location.
+pub async fn describe_method(
+    location: &FileLocation,
+    component_session: &ComponentSession,
+) -> Result<Option<(MethodInfo, SymbolKind)>, AnalyzerError> {
+    let symbols =
+        get_document_symbols(component_session, uri_from_pathbuf(&location.file_path)).await?;
+    let position: lsp_types::Position = location.range.start.into();
+
+    let Some((symbol, container_path)) = find_symbol_at_position_with_path(&symbols, &position)
+    else {
+        return Ok(None);
+    };
+
+    // Out-of-line definitions are top-level symbols named "Class::method"
+    let (qualifier, name) = match symbol.name.rsplit_once("::") {
+        Some((qualifier, name)) => (Some(qualifier), name),
+        None => (None, symbol.name.as_str()),
+    };
+    let class_name = container_path
+        .last()
+        .map(String::as_str)
+        .or(qualifier)
+        .map(|class| class.rsplit("::").next().unwrap_or(class).to_string());
+
+    Ok(Some((
+        MethodInfo {
+            name: name.to_string(),
+            class_name,
+            detail: symbol.detail.clone(),
+            location: location.clone(),
+        },
+        symbol.kind,
+    )))
+}
+
+/// Find the methods overriding the virtual method at a location
+pub async fn find_overrides(
+    method_location: &FileLocation,
+    component_session: &ComponentSession,
+) -> Result<Vec<MethodInfo>, AnalyzerError> {
+    let locations = get_implementations(method_location, component_session).await?;
+    debug!(
+        "clangd reported {} implementations of {}",
+        locations.len(),
+        method_location.to_compact_range()
+    );
+
+    let mut overrides = Vec::new();
+    for location in locations {
+        match describe_method(&location, component_session).await? {
+            Some((method, _)) => overrides.push(method),
+            None => trace!("No symbol at override {}", location.to_compact_range()),
+        }
+    }
+    Ok(overrides)
+}
+
+/// Find the base class methods that the method declared at a location overrides
+///
+/// `method_name` is the unqualified method name. All supertypes are searched,
+/// so methods of indirect bases are included; overloads with the same name are
+/// reported too, with their signatures to tell them apart.
+pub async fn find_base_methods(
+    declaration: &FileLocation,
+    method_name: &str,
+    component_session: &ComponentSession,
+) -> Result<Vec<MethodInfo>, AnalyzerError> {
+    let symbols =
+        get_document_symbols(component_session, uri_from_pathbuf(&declaration.file_path)).await?;
+    let position: lsp_types::Position = declaration.range.start.into();
+    let Some(class) = find_enclosing_class(&symbols, &position) else {
+        debug!(
+            "No class encloses {}, not looking for base methods",
+            declaration.to_compact_range()
+        );
+        return Ok(Vec::new());
+    };
+
+    let class_uri = declaration.get_uri();
+    let mut pending = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .text_document_prepare_type_hierarchy(class_uri, class.selection_range.start)
+            .await?
+            .unwrap_or_default()
+    };
+
+    let mut visited = HashSet::new();
+    let mut base_methods = Vec::new();
+    let mut is_start_class = true;
+    while let Some(item) = pending.pop() {
+        if !visited.insert((item.uri.to_string(), item.selection_range.start)) {
+            continue;
+        }
+        if visited.len() > MAX_BASE_CLASSES {
+            debug!("Stopping base class search after {MAX_BASE_CLASSES} classes");
+            break;
+        }
+
+        if !is_start_class {
+            base_methods.extend(methods_of_class(&item, method_name, component_session).await?);
+        }
+        is_start_class = false;
+
+        let supertypes = {
+            let mut session = component_session.lsp_session().await;
+            session
+                .client_mut()
+                .type_hierarchy_supertypes(item)
+                .await?
+                .unwrap_or_default()
+        };
+        pending.extend(supertypes);
+    }
+
+    Ok(base_methods)
+}
+
+/// Methods with a given name declared directly in a type hierarchy class
+async fn methods_of_class(
+    item: &lsp_types::TypeHierarchyItem,
+    method_name: &str,
+    component_session: &ComponentSession,
+) -> Result<Vec<MethodInfo>, AnalyzerError> {
+    let file_path = pathbuf_from_uri(&item.uri);
+    let symbols = get_document_symbols(component_session, item.uri.clone()).await?;
+    let Some((class, _)) = find_symbol_at_position_with_path(&symbols, &item.selection_range.start)
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(class
+        .children
+        .iter()
+        .flatten()
+        .filter(|child| child.kind == SymbolKind::METHOD && child.name == method_name)
+        .map(|child| MethodInfo {
+            name: child.name.clone(),
+            class_name: Some(item.name.clone()),
+            detail: child.detail.clone(),
+            location: FileLocation {
+                file_path: file_path.clone(),
+                range: child.selection_range.into(),
+            },
+        })
+        .collect())
+}
+
+/// Find the innermost class or struct whose body contains a position
+fn find_enclosing_class<'a>(
+    symbols: &'a [DocumentSymbol],
+    position: &lsp_types::Position,
+) -> Option<&'a DocumentSymbol> {
+    let symbol = symbols
+        .iter()
+        .find(|symbol| symbol.range.contains(position))?;
+    let inner = symbol
+        .children
+        .as_deref()
+        .and_then(|children| find_enclosing_class(children, position));
+    inner
+        .or_else(|| matches!(symbol.kind, SymbolKind::CLASS | SymbolKind::STRUCT).then_some(symbol))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Position, Range};
+
+    #[allow(deprecated)]
+    fn symbol(
+        name: &str,
+        kind: SymbolKind,
+        lines: (u32, u32),
+        children: Vec<DocumentSymbol>,
+    ) -> DocumentSymbol {
+        let range = Range::new(Position::new(lines.0, 0), Position::new(lines.1, 1));
+        DocumentSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range: range,
+            children: Some(children),
+        }
+    }
+
+    #[test]
+    fn test_find_enclosing_class() {
+        let symbols = vec![symbol(
+            "app",
+            SymbolKind::NAMESPACE,
+            (0, 20),
+            vec![symbol(
+                "Outer",
+                SymbolKind::CLASS,
+                (1, 10),
+                vec![
+                    symbol("run", SymbolKind::METHOD, (2, 2), vec![]),
+                    symbol(
+                        "Inner",
+                        SymbolKind::STRUCT,
+                        (4, 8),
+                        vec![symbol("step", SymbolKind::METHOD, (5, 5), vec![])],
+                    ),
+                ],
+            )],
+        )];
+
+        let class_at = |line| {
+            find_enclosing_class(&symbols, &Position::new(line, 0)).map(|class| class.name.clone())
+        };
+        assert_eq!(class_at(2).as_deref(), Some("Outer"));
+        assert_eq!(class_at(5).as_deref(), Some("Inner"));
+        assert_eq!(class_at(15), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! Selection range ("expand selection") functionality
+//!
+//! clangd answers `textDocument/selectionRange` with the syntactic ranges
+//! enclosing a position, each linked to the next larger one: identifier,
+//! expression, statement, block, function and so on up to the whole file.
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::FileLocation;
+
+/// Get the nested ranges enclosing a position, innermost first
+pub async fn get_selection_ranges(
+    location: &FileLocation,
+    component_session: &ComponentSession,
+) -> Result<Vec<lsp_types::Range>, AnalyzerError> {
+    let uri = location.get_uri();
+    let lsp_position: lsp_types::Position = location.range.start.into();
+
+    component_session
+        .ensure_file_ready(&location.file_path)
+        .await?;
+    let selection_ranges = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .text_document_selection_range(uri, vec![lsp_position])
+            .await?
+    };
+
+    let Some(selection_range) = selection_ranges.and_then(|ranges| ranges.into_iter().next())
+    else {
+        return Err(AnalyzerError::NoResult(format!(
+            "selection ranges at {}",
+            location.to_compact_range()
+        )));
+    };
+
+    Ok(flatten_selection_range(selection_range))
+}
+
+/// Unlink a selection range chain, innermost range first
+///
+/// Ranges identical to their child are dropped.
+pub fn flatten_selection_range(
+    selection_range: lsp_types::SelectionRange,
+) -> Vec<lsp_types::Range> {
+    let mut ranges: Vec<lsp_types::Range> = Vec::new();
+    let mut current = Some(selection_range);
+    while let Some(selection_range) = current {
+        if ranges.last() != Some(&selection_range.range) {
+            ranges.push(selection_range.range);
+        }
+        current = selection_range.parent.map(|parent| *parent);
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> lsp_types::Range {
+        lsp_types::Range::new(
+            lsp_types::Position::new(start.0, start.1),
+            lsp_types::Position::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn test_flatten_selection_range() {
+        let chain: lsp_types::SelectionRange = serde_json::from_value(serde_json::json!({
+            "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 10}},
+            "parent": {
+                "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 10}},
+                "parent": {
+                    "range": {"start": {"line": 1, "character": 2}, "end": {"line": 1, "character": 15}}
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            flatten_selection_range(chain),
+            vec![range((1, 9), (1, 10)), range((1, 2), (1, 15))]
+        );
+    }
+}
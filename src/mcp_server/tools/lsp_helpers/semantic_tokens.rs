@@ -0,0 +1,147 @@
+//! Semantic token retrieval and decoding for C++ files
+//!
+//! LSP transfers semantic tokens as a flat integer array where each token is
+//! encoded relative to the previous one. This module requests the tokens of a
+//! whole document from clangd and decodes them into absolute positions with
+//! their token type and modifiers resolved through the server's legend.
+
+use std::path::Path;
+use tracing::debug;
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::uri_from_pathbuf;
+
+/// A semantic token with absolute position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSemanticToken {
+    /// 0-based line
+    pub line: u32,
+    /// 0-based start column, in the negotiated position encoding
+    pub start: u32,
+    /// Token length, in the negotiated position encoding
+    pub length: u32,
+    /// Token type name from the legend (e.g. "comment", "function")
+    pub token_type: String,
+    /// Token modifier names from the legend (e.g. "declaration", "readonly")
+    pub modifiers: Vec<String>,
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Get the decoded semantic tokens of a file
+///
+/// Returns an empty list when clangd does not provide semantic tokens.
+pub async fn get_semantic_tokens(
+    file_path: &Path,
+    component_session: &ComponentSession,
+) -> Result<Vec<DecodedSemanticToken>, AnalyzerError> {
+    component_session.ensure_file_ready(file_path).await?;
+
+    let mut session = component_session.lsp_session().await;
+    let client = session.client_mut();
+    let Some(legend) = client.semantic_tokens_legend() else {
+        debug!("clangd does not provide semantic tokens");
+        return Ok(Vec::new());
+    };
+
+    let tokens = match client
+        .text_document_semantic_tokens_full(uri_from_pathbuf(file_path))
+        .await
+        .map_err(AnalyzerError::from)?
+    {
+        Some(lsp_types::SemanticTokensResult::Tokens(tokens)) => tokens.data,
+        Some(lsp_types::SemanticTokensResult::Partial(partial)) => partial.data,
+        None => Vec::new(),
+    };
+
+    Ok(decode_semantic_tokens(&tokens, &legend))
+}
+
+/// Decode relative semantic tokens into absolute positions
+///
+/// Token types or modifiers missing from the legend are reported as "unknown"
+/// and skipped respectively.
+pub fn decode_semantic_tokens(
+    tokens: &[lsp_types::SemanticToken],
+    legend: &lsp_types::SemanticTokensLegend,
+) -> Vec<DecodedSemanticToken> {
+    let mut line = 0;
+    let mut start = 0;
+
+    tokens
+        .iter()
+        .map(|token| {
+            if token.delta_line > 0 {
+                line += token.delta_line;
+                start = token.delta_start;
+            } else {
+                start += token.delta_start;
+            }
+
+            let token_type = legend
+                .token_types
+                .get(token.token_type as usize)
+                .map_or("unknown", |token_type| token_type.as_str())
+                .to_string();
+            let modifiers = legend
+                .token_modifiers
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| *bit < 32 && token.token_modifiers_bitset & (1 << bit) != 0)
+                .map(|(_, modifier)| modifier.as_str().to_string())
+                .collect();
+
+            DecodedSemanticToken {
+                line,
+                start,
+                length: token.length,
+                token_type,
+                modifiers,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{
+        SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend,
+    };
+
+    #[test]
+    fn test_decode_relative_positions() {
+        let legend = SemanticTokensLegend {
+            token_types: vec![SemanticTokenType::VARIABLE, SemanticTokenType::COMMENT],
+            token_modifiers: vec![
+                SemanticTokenModifier::DECLARATION,
+                SemanticTokenModifier::READONLY,
+            ],
+        };
+        let token =
+            |delta_line, delta_start, length, token_type, token_modifiers_bitset| SemanticToken {
+                delta_line,
+                delta_start,
+                length,
+                token_type,
+                token_modifiers_bitset,
+            };
+        let tokens = [
+            token(2, 4, 3, 0, 0b11),
+            token(0, 6, 5, 1, 0),
+            token(1, 2, 1, 7, 0),
+        ];
+
+        let decoded = decode_semantic_tokens(&tokens, &legend);
+        let positions: Vec<_> = decoded.iter().map(|t| (t.line, t.start)).collect();
+        assert_eq!(positions, vec![(2, 4), (2, 10), (3, 2)]);
+        assert_eq!(decoded[0].modifiers, vec!["declaration", "readonly"]);
+        assert_eq!(decoded[1].token_type, "comment");
+        assert_eq!(decoded[2].token_type, "unknown");
+    }
+}
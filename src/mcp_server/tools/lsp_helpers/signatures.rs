@@ -0,0 +1,835 @@
+//! Function signature parsing and comparison
+//!
+//! clangd shows the declaration of a function in the code block of its hover,
+//! e.g. `public: virtual int area(const Shape &shape) const noexcept`. The
+//! declaration is split into return type, parameters and qualifiers so that two
+//! signatures can be compared part by part. When the hover has no code block,
+//! the `→ `type`` and `Parameters:` lines are used instead, without qualifiers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::mcp_server::tools::lsp_helpers::type_aliases::{backtick_values, code_block_lines};
+
+/// Hover heading kinds of functions
+const FUNCTION_KINDS: &[&str] = &[
+    "function",
+    "method",
+    "instance-method",
+    "static-method",
+    "constructor",
+    "destructor",
+    "conversion-function",
+];
+
+/// Declaration specifiers reported separately from the return type
+const SPECIFIERS: &[&str] = &[
+    "virtual",
+    "static",
+    "inline",
+    "constexpr",
+    "consteval",
+    "explicit",
+    "extern",
+    "friend",
+];
+
+/// Words that end a parameter type rather than name the parameter
+const TYPE_WORDS: &[&str] = &[
+    "int", "char", "short", "long", "signed", "unsigned", "float", "double", "bool", "void",
+    "const", "volatile", "auto", "wchar_t", "char8_t", "char16_t", "char32_t",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureParameter {
+    /// Parameter type as written
+    #[serde(rename = "type")]
+    pub type_name: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSignature {
+    /// Function name as declared, e.g. "area", "operator==" or "Shape::area"
+    pub name: String,
+    /// Template header, e.g. "template <typename T>"
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub template: Option<String>,
+    /// Return type; absent for constructors, destructors and conversion functions
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub return_type: Option<String>,
+    pub parameters: Vec<SignatureParameter>,
+    /// Whether the parameter list ends with `...`
+    pub variadic: bool,
+    pub is_const: bool,
+    pub is_volatile: bool,
+    /// `&` or `&&`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ref_qualifier: Option<String>,
+    /// `noexcept`, `noexcept(expression)` or `throw()`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub noexcept: Option<String>,
+    /// Specifiers such as virtual, static, explicit, override, `= 0`, `= delete`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub specifiers: Vec<String>,
+}
+
+/// Part of a signature that differs between two signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureAspect {
+    Name,
+    Template,
+    ReturnType,
+    ParameterAdded,
+    ParameterRemoved,
+    ParameterType,
+    ParameterName,
+    DefaultArgument,
+    Variadic,
+    Const,
+    Volatile,
+    RefQualifier,
+    Noexcept,
+    Specifier,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureChange {
+    pub aspect: SignatureAspect,
+    /// 1-based position of the parameter, for parameter changes
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parameter: Option<u32>,
+    /// Value in the first signature
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub before: Option<String>,
+    /// Value in the second signature
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub after: Option<String>,
+    /// Whether calls written against the first signature may stop compiling
+    pub breaking: bool,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Function signature shown in clangd hover markdown
+///
+/// # Returns
+/// * `Some(FunctionSignature)` - Hover describes a function, method or constructor
+/// * `None` - Hover describes something else, or the declaration cannot be parsed
+pub fn parse_hover_signature(hover: &str) -> Option<FunctionSignature> {
+    let heading = hover
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("### "));
+    if let Some(heading) = heading {
+        let kind = heading.split_whitespace().next()?;
+        if !FUNCTION_KINDS.contains(&kind) {
+            return None;
+        }
+    }
+
+    let declaration: Vec<&str> = code_block_lines(hover)
+        .into_iter()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .collect();
+    if let Some(signature) = parse_signature(&declaration.join(" ")) {
+        return Some(signature);
+    }
+
+    // No usable code block: name from the heading, types from the detail lines
+    let name = backtick_values(heading?).into_iter().next()?;
+    let return_type = hover
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("→ "))
+        .and_then(|line| backtick_values(line).into_iter().next());
+    let mut parameters = Vec::new();
+    let mut variadic = false;
+    for line in hover
+        .lines()
+        .skip_while(|line| line.trim() != "Parameters:")
+        .skip(1)
+        .map_while(|line| line.trim().strip_prefix("- "))
+    {
+        let Some(parameter) = backtick_values(line).into_iter().next() else {
+            continue;
+        };
+        if parameter == "..." {
+            variadic = true;
+        } else {
+            parameters.push(parse_parameter(&parameter));
+        }
+    }
+    Some(FunctionSignature {
+        name,
+        return_type,
+        parameters,
+        variadic,
+        ..Default::default()
+    })
+}
+
+/// Parse a function declaration such as `static int add(int a, int b = 0) noexcept`
+///
+/// Access specifiers (`public:`), a template header, a trailing `;` and
+/// trailing return types are accepted. Returns `None` when no parameter list
+/// is found.
+pub fn parse_signature(declaration: &str) -> Option<FunctionSignature> {
+    let text = declaration.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut rest = text.trim().trim_end_matches(';').trim_end();
+    for access in ["public:", "protected:", "private:"] {
+        if let Some(stripped) = rest.strip_prefix(access) {
+            rest = stripped.trim_start();
+        }
+    }
+
+    let (template, rest) = split_template_header(rest)?;
+    let open = parameter_list_start(rest)?;
+    let close = matching_close(rest, open)?;
+    let head = rest[..open].trim_end();
+    let start = name_start(head);
+    let name = head[start..].trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut signature = FunctionSignature {
+        name: name.to_string(),
+        template,
+        ..Default::default()
+    };
+    for word in head[..start].split_whitespace() {
+        if SPECIFIERS.contains(&word) || word.starts_with("[[") {
+            signature.specifiers.push(word.to_string());
+        } else {
+            let return_type = signature.return_type.get_or_insert_with(String::new);
+            if !return_type.is_empty() {
+                return_type.push(' ');
+            }
+            return_type.push_str(word);
+        }
+    }
+
+    for parameter in split_top_level(&rest[open + 1..close], ',') {
+        match parameter {
+            "" | "void" => {}
+            "..." => signature.variadic = true,
+            parameter => signature.parameters.push(parse_parameter(parameter)),
+        }
+    }
+
+    parse_qualifiers(&rest[close + 1..], &mut signature);
+    Some(signature)
+}
+
+/// Split a leading `template <...>` header from a declaration
+fn split_template_header(text: &str) -> Option<(Option<String>, &str)> {
+    let Some(after) = strip_word(text, "template") else {
+        return Some((None, text));
+    };
+    let open = text.len() - after.trim_start().len();
+    if !text[open..].starts_with('<') {
+        return Some((None, text));
+    }
+    let mut depth = 0;
+    for (index, c) in text[open..].char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    let end = open + index + 1;
+                    return Some((Some(text[..end].to_string()), text[end..].trim_start()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Position of the `(` opening the parameter list
+fn parameter_list_start(text: &str) -> Option<usize> {
+    if let Some(operator) = find_word(text, "operator") {
+        // The call operator's own parentheses are part of its name
+        let after = operator + "operator".len();
+        let symbol_end = match text[after..].trim_start().strip_prefix("()") {
+            Some(rest) => text.len() - rest.len(),
+            None => after,
+        };
+        return text[symbol_end..].find('(').map(|index| symbol_end + index);
+    }
+
+    let mut angle_depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' => angle_depth += 1,
+            '>' => angle_depth -= 1,
+            '(' if angle_depth <= 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Position of the `)` closing the parenthesis opened at `open`
+fn matching_close(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Start of the declarator name at the end of `head`, with its qualification
+fn name_start(head: &str) -> usize {
+    let end = find_word(head, "operator").unwrap_or(head.len());
+    let mut angle_depth = 0;
+    for (index, c) in head[..end].char_indices().rev() {
+        match c {
+            '>' => angle_depth += 1,
+            '<' => angle_depth -= 1,
+            ' ' | '*' | '&' if angle_depth == 0 => return index + 1,
+            _ => {}
+        }
+    }
+    0
+}
+
+/// Split at `separator` outside of brackets, trimming the parts
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Parse one parameter declaration, e.g. `const std::string &name = "x"`
+fn parse_parameter(text: &str) -> SignatureParameter {
+    let mut parts = split_top_level(text, '=').into_iter();
+    let declaration = parts.next().unwrap_or_default();
+    let default_value: Vec<&str> = parts.collect();
+    let default_value = (!default_value.is_empty()).then(|| default_value.join(" = "));
+
+    // Function pointers keep their declarator in the type; arrays their bounds
+    let (declarator, array_bounds) = match declaration.find('[') {
+        Some(index) if !declaration.contains('(') => {
+            (declaration[..index].trim_end(), &declaration[index..])
+        }
+        _ => (declaration, ""),
+    };
+    let start = name_start(declarator);
+    let candidate = &declarator[start..];
+    let type_part = declarator[..start].trim_end();
+    let is_name = start > 0
+        && !declaration.contains('(')
+        && candidate
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && candidate.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !TYPE_WORDS.contains(&candidate)
+        && !matches!(
+            type_part,
+            "const" | "volatile" | "struct" | "class" | "enum" | "union" | "typename"
+        );
+
+    let (type_name, name) = if is_name {
+        (
+            format!("{type_part}{array_bounds}"),
+            Some(candidate.to_string()),
+        )
+    } else {
+        (declaration.to_string(), None)
+    };
+    SignatureParameter {
+        type_name,
+        name,
+        default_value,
+    }
+}
+
+/// Parse what follows the parameter list: cv and ref qualifiers, noexcept,
+/// trailing return type, override/final and `= 0`/`= default`/`= delete`
+fn parse_qualifiers(text: &str, signature: &mut FunctionSignature) {
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        if let Some(after) = strip_word(rest, "const") {
+            signature.is_const = true;
+            rest = after;
+        } else if let Some(after) = strip_word(rest, "volatile") {
+            signature.is_volatile = true;
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("&&") {
+            signature.ref_qualifier = Some("&&".to_string());
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('&') {
+            signature.ref_qualifier = Some("&".to_string());
+            rest = after;
+        } else if let Some(after) = strip_word(rest, "noexcept") {
+            let after = after.trim_start();
+            match after
+                .starts_with('(')
+                .then(|| matching_close(after, 0))
+                .flatten()
+            {
+                Some(close) => {
+                    signature.noexcept = Some(format!("noexcept{}", &after[..=close]));
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    signature.noexcept = Some("noexcept".to_string());
+                    rest = after;
+                }
+            }
+        } else if let Some(after) = rest.strip_prefix("throw()") {
+            signature.noexcept = Some("throw()".to_string());
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("->") {
+            let after = after.trim_start();
+            let end = [" override", " final", " =", " requires "]
+                .iter()
+                .filter_map(|marker| after.find(marker))
+                .min()
+                .unwrap_or(after.len());
+            signature.return_type = Some(after[..end].trim().to_string());
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('=') {
+            signature.specifiers.push(format!("= {}", after.trim()));
+            break;
+        } else if strip_word(rest, "requires").is_some() {
+            break;
+        } else {
+            // override, final and anything not understood
+            let (word, after) = rest.split_once(' ').unwrap_or((rest, ""));
+            if word == "override" || word == "final" {
+                signature.specifiers.push(word.to_string());
+            }
+            rest = after;
+        }
+        rest = rest.trim_start();
+    }
+}
+
+/// Strip a leading word, not matching a longer identifier
+fn strip_word<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    text.strip_prefix(word)
+        .filter(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+/// Position of the first occurrence of `word` as a whole identifier
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    text.match_indices(word)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let before = text[..index].chars().next_back();
+            !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                && strip_word(&text[index..], word).is_some()
+        })
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+/// Type with whitespace only between identifiers, so that `const T &` equals `const T&`
+pub fn normalize_type(type_name: &str) -> String {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let mut normalized = String::with_capacity(type_name.len());
+    let mut pending_space = false;
+    for c in type_name.chars() {
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && normalized.ends_with(is_identifier) && is_identifier(c) {
+            normalized.push(' ');
+        }
+        pending_space = false;
+        normalized.push(c);
+    }
+    normalized
+}
+
+/// Unqualified function name, e.g. "area" for "Shape::area"
+fn unqualified_name(name: &str) -> &str {
+    match find_word(name, "operator") {
+        Some(index) => &name[index..],
+        None => name.rsplit("::").next().unwrap_or(name),
+    }
+}
+
+/// Differences from the `before` signature to the `after` signature
+///
+/// Names are compared unqualified and types after normalizing whitespace.
+/// A change is breaking when calls or overrides written against `before` may
+/// stop compiling against `after`: parameters added without defaults or
+/// removed, changed types, a const or volatile qualifier removed, a ref
+/// qualifier added or changed, noexcept removed, static changed, and explicit
+/// or `= delete` added. Template header changes are reported as breaking.
+pub fn diff_signatures(
+    before: &FunctionSignature,
+    after: &FunctionSignature,
+) -> Vec<SignatureChange> {
+    let mut changes = Vec::new();
+    let mut change =
+        |aspect, parameter: Option<usize>, old: Option<&str>, new: Option<&str>, breaking| {
+            changes.push(SignatureChange {
+                aspect,
+                parameter: parameter.map(|index| index as u32 + 1),
+                before: old.map(str::to_string),
+                after: new.map(str::to_string),
+                breaking,
+            });
+        };
+    let flag = |value: bool| value.to_string();
+
+    if unqualified_name(&before.name) != unqualified_name(&after.name) {
+        change(
+            SignatureAspect::Name,
+            None,
+            Some(&before.name),
+            Some(&after.name),
+            true,
+        );
+    }
+    if before.template.as_deref().map(normalize_type)
+        != after.template.as_deref().map(normalize_type)
+    {
+        change(
+            SignatureAspect::Template,
+            None,
+            before.template.as_deref(),
+            after.template.as_deref(),
+            true,
+        );
+    }
+    if before.return_type.as_deref().map(normalize_type)
+        != after.return_type.as_deref().map(normalize_type)
+    {
+        change(
+            SignatureAspect::ReturnType,
+            None,
+            before.return_type.as_deref(),
+            after.return_type.as_deref(),
+            true,
+        );
+    }
+
+    for (index, (old, new)) in before.parameters.iter().zip(&after.parameters).enumerate() {
+        if normalize_type(&old.type_name) != normalize_type(&new.type_name) {
+            change(
+                SignatureAspect::ParameterType,
+                Some(index),
+                Some(&old.type_name),
+                Some(&new.type_name),
+                true,
+            );
+        }
+        if let (Some(old_name), Some(new_name)) = (&old.name, &new.name)
+            && old_name != new_name
+        {
+            change(
+                SignatureAspect::ParameterName,
+                Some(index),
+                Some(old_name),
+                Some(new_name),
+                false,
+            );
+        }
+        if old.default_value != new.default_value {
+            change(
+                SignatureAspect::DefaultArgument,
+                Some(index),
+                old.default_value.as_deref(),
+                new.default_value.as_deref(),
+                new.default_value.is_none(),
+            );
+        }
+    }
+    for (index, removed) in before
+        .parameters
+        .iter()
+        .enumerate()
+        .skip(after.parameters.len())
+    {
+        change(
+            SignatureAspect::ParameterRemoved,
+            Some(index),
+            Some(&removed.type_name),
+            None,
+            true,
+        );
+    }
+    for (index, added) in after
+        .parameters
+        .iter()
+        .enumerate()
+        .skip(before.parameters.len())
+    {
+        change(
+            SignatureAspect::ParameterAdded,
+            Some(index),
+            None,
+            Some(&added.type_name),
+            added.default_value.is_none(),
+        );
+    }
+
+    if before.variadic != after.variadic {
+        change(
+            SignatureAspect::Variadic,
+            None,
+            Some(&flag(before.variadic)),
+            Some(&flag(after.variadic)),
+            before.variadic,
+        );
+    }
+    if before.is_const != after.is_const {
+        change(
+            SignatureAspect::Const,
+            None,
+            Some(&flag(before.is_const)),
+            Some(&flag(after.is_const)),
+            before.is_const,
+        );
+    }
+    if before.is_volatile != after.is_volatile {
+        change(
+            SignatureAspect::Volatile,
+            None,
+            Some(&flag(before.is_volatile)),
+            Some(&flag(after.is_volatile)),
+            before.is_volatile,
+        );
+    }
+    if before.ref_qualifier != after.ref_qualifier {
+        change(
+            SignatureAspect::RefQualifier,
+            None,
+            before.ref_qualifier.as_deref(),
+            after.ref_qualifier.as_deref(),
+            after.ref_qualifier.is_some(),
+        );
+    }
+    if before.noexcept.as_deref().map(normalize_type)
+        != after.noexcept.as_deref().map(normalize_type)
+    {
+        change(
+            SignatureAspect::Noexcept,
+            None,
+            before.noexcept.as_deref(),
+            after.noexcept.as_deref(),
+            before.noexcept.is_some(),
+        );
+    }
+
+    for removed in before
+        .specifiers
+        .iter()
+        .filter(|specifier| !after.specifiers.contains(specifier))
+    {
+        change(
+            SignatureAspect::Specifier,
+            None,
+            Some(removed),
+            None,
+            removed == "static",
+        );
+    }
+    for added in after
+        .specifiers
+        .iter()
+        .filter(|specifier| !before.specifiers.contains(specifier))
+    {
+        change(
+            SignatureAspect::Specifier,
+            None,
+            None,
+            Some(added),
+            matches!(added.as_str(), "static" | "explicit" | "= delete"),
+        );
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(type_name: &str, name: Option<&str>) -> SignatureParameter {
+        SignatureParameter {
+            type_name: type_name.to_string(),
+            name: name.map(str::to_string),
+            default_value: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        let signature = parse_signature(
+            "public: virtual std::map<int, std::string> lookup(const std::string &key, \
+             int (*hash)(int), char buffer[16], unsigned long = 0) const & noexcept override",
+        )
+        .unwrap();
+        assert_eq!(signature.name, "lookup");
+        assert_eq!(
+            signature.return_type.as_deref(),
+            Some("std::map<int, std::string>")
+        );
+        assert_eq!(signature.specifiers, vec!["virtual", "override"]);
+        assert_eq!(
+            signature.parameters,
+            vec![
+                parameter("const std::string &", Some("key")),
+                parameter("int (*hash)(int)", None),
+                parameter("char[16]", Some("buffer")),
+                SignatureParameter {
+                    default_value: Some("0".to_string()),
+                    ..parameter("unsigned long", None)
+                },
+            ]
+        );
+        assert!(signature.is_const);
+        assert_eq!(signature.ref_qualifier.as_deref(), Some("&"));
+        assert_eq!(signature.noexcept.as_deref(), Some("noexcept"));
+
+        let signature = parse_signature(
+            "template <typename T, typename = std::enable_if_t<true>> \
+             static auto max(T a, T b, ...) noexcept(noexcept(a < b)) -> T;",
+        )
+        .unwrap();
+        assert_eq!(
+            signature.template.as_deref(),
+            Some("template <typename T, typename = std::enable_if_t<true>>")
+        );
+        assert_eq!(signature.name, "max");
+        assert_eq!(signature.return_type.as_deref(), Some("T"));
+        assert_eq!(signature.specifiers, vec!["static"]);
+        assert!(signature.variadic);
+        assert_eq!(signature.parameters.len(), 2);
+        assert_eq!(
+            signature.noexcept.as_deref(),
+            Some("noexcept(noexcept(a < b))")
+        );
+
+        let signature = parse_signature("bool Point::operator<(const Point &other) const").unwrap();
+        assert_eq!(signature.name, "Point::operator<");
+        assert_eq!(signature.return_type.as_deref(), Some("bool"));
+        let signature = parse_signature("int operator()(int x) = delete").unwrap();
+        assert_eq!(signature.name, "operator()");
+        assert_eq!(signature.specifiers, vec!["= delete"]);
+
+        let signature = parse_signature("explicit Widget(void)").unwrap();
+        assert_eq!(signature.name, "Widget");
+        assert_eq!(signature.return_type, None);
+        assert_eq!(signature.specifiers, vec!["explicit"]);
+        assert!(signature.parameters.is_empty());
+
+        assert!(parse_signature("int count").is_none());
+    }
+
+    #[test]
+    fn test_parse_hover_signature() {
+        let hover = "### instance-method `area`\n\n---\n→ `double`\n\n---\n```cpp\n\
+                     // In Shape\npublic: virtual double area(\n    int precision) const = 0\n```";
+        let signature = parse_hover_signature(hover).unwrap();
+        assert_eq!(signature.name, "area");
+        assert_eq!(
+            signature.parameters,
+            vec![parameter("int", Some("precision"))]
+        );
+        assert!(signature.is_const);
+        assert_eq!(signature.specifiers, vec!["virtual", "= 0"]);
+
+        // Without a code block the detail lines are used
+        let hover =
+            "### function `clamp`\n\n---\n→ `int`\nParameters:\n- `int value`\n- `int high = 10`";
+        let signature = parse_hover_signature(hover).unwrap();
+        assert_eq!(signature.name, "clamp");
+        assert_eq!(signature.return_type.as_deref(), Some("int"));
+        assert_eq!(signature.parameters[1].name.as_deref(), Some("high"));
+        assert_eq!(signature.parameters[1].default_value.as_deref(), Some("10"));
+
+        let hover = "### variable `x`\n\n---\nType: `int`\n\n---\n```cpp\nint x = compute()\n```";
+        assert!(parse_hover_signature(hover).is_none());
+    }
+
+    #[test]
+    fn test_diff_signatures() {
+        let before =
+            parse_signature("int clamp(int value, int low, int high) const noexcept").unwrap();
+        let same =
+            parse_signature("int  math::clamp(int v, int low, int high)const noexcept;").unwrap();
+        let changes = diff_signatures(&before, &same);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].aspect, SignatureAspect::ParameterName);
+        assert!(!changes[0].breaking);
+
+        let after =
+            parse_signature("long clamp(int value, const long &low, int high, int step = 1)")
+                .unwrap();
+        let changes: Vec<_> = diff_signatures(&before, &after)
+            .into_iter()
+            .map(|change| (change.aspect, change.parameter, change.breaking))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (SignatureAspect::ReturnType, None, true),
+                (SignatureAspect::ParameterType, Some(2), true),
+                (SignatureAspect::ParameterAdded, Some(4), false),
+                (SignatureAspect::Const, None, true),
+                (SignatureAspect::Noexcept, None, true),
+            ]
+        );
+
+        // Relaxing qualifiers does not break callers
+        let changes = diff_signatures(&after, &before);
+        let breaking: Vec<_> = changes
+            .iter()
+            .filter(|change| change.breaking)
+            .map(|change| change.aspect)
+            .collect();
+        assert_eq!(
+            breaking,
+            vec![
+                SignatureAspect::ReturnType,
+                SignatureAspect::ParameterType,
+                SignatureAspect::ParameterRemoved
+            ]
+        );
+
+        assert_eq!(
+            normalize_type("const std::map<int , int> &"),
+            "const std::map<int,int>&"
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! "Did you mean" suggestions for symbols that could not be found
+//!
+//! clangd matches workspace symbol queries by fuzzy subsequence, so a typo in
+//! the requested name usually yields no result at all. Candidates are therefore
+//! gathered with shorter queries taken from the start and the end of the name,
+//! then ranked by case-insensitive edit distance to the requested name.
+//!
+//! Ranking uses the unqualified name first, so that a symbol in another
+//! namespace or class than the one requested is still suggested; the distance
+//! of the qualified names only breaks ties.
+
+use lsp_types::SymbolKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::{FileLocation, Symbol};
+
+/// Number of suggestions reported for a symbol that was not found
+pub const DEFAULT_SUGGESTION_LIMIT: usize = 5;
+
+/// Length of the name fragments used as candidate queries
+const FRAGMENT_LENGTH: usize = 3;
+
+/// A symbol similar to a requested name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolSuggestion {
+    /// Fully qualified symbol name
+    pub name: String,
+    pub kind: SymbolKind,
+    pub location: FileLocation,
+    /// Edit distance between the unqualified names
+    pub distance: usize,
+}
+
+impl std::fmt::Display for SymbolSuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({:?} at {})",
+            self.name,
+            self.kind,
+            self.location.to_compact_range()
+        )
+    }
+}
+
+/// Render suggestions as a "did you mean" hint appended to error messages
+///
+/// Returns an empty string when there are no suggestions.
+pub fn suggestions_hint(suggestions: &[SymbolSuggestion]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let list: Vec<String> = suggestions.iter().map(ToString::to_string).collect();
+    format!(". Did you mean: {}?", list.join(", "))
+}
+
+/// Find workspace symbols similar to a name that was not found
+///
+/// Returns at most `limit` suggestions, closest first. Symbols whose
+/// unqualified name is too far from the requested one are not suggested.
+pub async fn find_similar_symbols(
+    symbol_query: &str,
+    component_session: &ComponentSession,
+    limit: usize,
+) -> Result<Vec<SymbolSuggestion>, AnalyzerError> {
+    let mut candidates = Vec::new();
+    for query in candidate_queries(symbol_query) {
+        let symbols = component_session
+            .coalesced("workspace/symbol", &query, || async {
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .workspace_symbols(query.clone())
+                    .await
+                    .map_err(AnalyzerError::from)
+            })
+            .await?
+            .unwrap_or_default();
+        trace!("Query '{}' found {} candidates", query, symbols.len());
+        candidates.extend(symbols.into_iter().map(Symbol::from));
+    }
+
+    let suggestions = rank_suggestions(symbol_query, candidates, limit);
+    debug!(
+        "{} suggestions for unknown symbol '{}'",
+        suggestions.len(),
+        symbol_query
+    );
+    Ok(suggestions)
+}
+
+/// Workspace symbol queries likely to find names similar to `symbol_query`
+///
+/// The unqualified name is queried as-is and by its leading and trailing
+/// fragments, so that a typo in one half of the name still finds candidates.
+fn candidate_queries(symbol_query: &str) -> Vec<String> {
+    let name = unqualified(symbol_query);
+    let chars: Vec<char> = name.chars().collect();
+
+    let mut queries = vec![name.to_string()];
+    if chars.len() > FRAGMENT_LENGTH {
+        queries.push(chars[..FRAGMENT_LENGTH].iter().collect());
+        queries.push(chars[chars.len() - FRAGMENT_LENGTH..].iter().collect());
+    }
+    queries.dedup();
+    queries
+}
+
+/// Rank candidate symbols by edit distance to the requested name
+pub fn rank_suggestions(
+    symbol_query: &str,
+    candidates: Vec<Symbol>,
+    limit: usize,
+) -> Vec<SymbolSuggestion> {
+    let name = unqualified(symbol_query).to_lowercase();
+    let query = symbol_query.trim_start_matches("::").to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+
+    let mut seen = HashSet::new();
+    let mut ranked: Vec<(usize, usize, SymbolSuggestion)> = candidates
+        .into_iter()
+        .filter_map(|symbol| {
+            let qualified_name = match &symbol.container_name {
+                Some(container) if !container.is_empty() => {
+                    format!("{}::{}", container, symbol.name)
+                }
+                _ => symbol.name.clone(),
+            };
+            if !seen.insert((qualified_name.clone(), symbol.location.to_compact_range())) {
+                return None;
+            }
+
+            let distance = edit_distance(&name, &symbol.name.to_lowercase());
+            if distance > max_distance {
+                return None;
+            }
+            let qualified_distance = edit_distance(&query, &qualified_name.to_lowercase());
+
+            Some((
+                distance,
+                qualified_distance,
+                SymbolSuggestion {
+                    name: qualified_name,
+                    kind: symbol.kind,
+                    location: symbol.location,
+                    distance,
+                },
+            ))
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        (a.0, a.1)
+            .cmp(&(b.0, b.1))
+            .then_with(|| a.2.name.cmp(&b.2.name))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, suggestion)| suggestion)
+        .collect()
+}
+
+/// Last component of a possibly qualified name
+fn unqualified(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn symbol(name: &str, container: Option<&str>, line: u32) -> Symbol {
+        Symbol::new(
+            name.to_string(),
+            SymbolKind::CLASS,
+            container.map(String::from),
+            FileLocation {
+                file_path: PathBuf::from("/project/src/shapes.h"),
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(line, 6),
+                    lsp_types::Position::new(line, 12),
+                )
+                .into(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("widget", "widget"), 0);
+        assert_eq!(edit_distance("widgte", "widget"), 2);
+        assert_eq!(edit_distance("widge", "widget"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("größe", "grösse"), 2);
+    }
+
+    #[test]
+    fn test_candidate_queries() {
+        assert_eq!(
+            candidate_queries("app::Wdiget"),
+            vec!["Wdiget", "Wdi", "get"]
+        );
+        assert_eq!(candidate_queries("Foo"), vec!["Foo"]);
+    }
+
+    #[test]
+    fn test_rank_suggestions() {
+        let candidates = vec![
+            symbol("Gadget", Some("app"), 1),
+            symbol("Widget", Some("ui"), 2),
+            symbol("Widget", Some("app"), 3),
+            symbol("Widgets", None, 4),
+            symbol("Window", Some("ui"), 5),
+            symbol("Widget", Some("app"), 3),
+        ];
+
+        let suggestions = rank_suggestions("app::Widgt", candidates, 3);
+        let names: Vec<&str> = suggestions.iter().map(|s| s.name.as_str()).collect();
+        // Same unqualified distance: the requested namespace wins
+        assert_eq!(names, vec!["app::Widget", "ui::Widget", "Widgets"]);
+        assert_eq!(suggestions[0].distance, 1);
+
+        assert!(rank_suggestions("Renderer", vec![symbol("Widget", None, 1)], 5).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_hint() {
+        assert_eq!(suggestions_hint(&[]), "");
+        let suggestions = rank_suggestions("Widgte", vec![symbol("Widget", Some("ui"), 2)], 5);
+        assert_eq!(
+            suggestions_hint(&suggestions),
+            ". Did you mean: ui::Widget (Class at /project/src/shapes.h:3:7-13)?"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Source/header switching through clangd's `textDocument/switchSourceHeader` extension
+//!
+//! clangd pairs a header with its source file (and back) using the index and
+//! file name heuristics. The request is not part of LSP and lsp-types does not
+//! model it, so it is sent through the raw request path: the parameters are a
+//! bare text document identifier and the result is a URI or null.
+
+use std::path::{Path, PathBuf};
+use tracing::trace;
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::lsp::traits::LspClientTrait;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::{path_from_file_uri, uri_from_pathbuf};
+
+/// Method name of the clangd source/header switch extension
+pub const SWITCH_SOURCE_HEADER_METHOD: &str = "textDocument/switchSourceHeader";
+
+/// Get the header of a source file or the source of a header
+///
+/// Returns `None` when clangd knows no counterpart.
+pub async fn get_source_header_counterpart(
+    file_path: &Path,
+    component_session: &ComponentSession,
+) -> Result<Option<PathBuf>, AnalyzerError> {
+    component_session.ensure_file_ready(file_path).await?;
+
+    let params = serde_json::json!({ "uri": uri_from_pathbuf(file_path) });
+    trace!("Requesting source/header counterpart: {}", params);
+
+    let response = {
+        let mut session = component_session.lsp_session().await;
+        session
+            .client_mut()
+            .raw_request(SWITCH_SOURCE_HEADER_METHOD.to_string(), params)
+            .await?
+    };
+
+    match response {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(uri) => path_from_file_uri(&uri).map(Some).ok_or_else(|| {
+            AnalyzerError::NoData(format!(
+                "{} returned a non-file URI: {}",
+                SWITCH_SOURCE_HEADER_METHOD, uri
+            ))
+        }),
+        other => Err(AnalyzerError::NoData(format!(
+            "Unexpected {} result: {}",
+            SWITCH_SOURCE_HEADER_METHOD, other
+        ))),
+    }
+}
@@ -3,12 +3,19 @@
 //! This module provides LSP-based symbol resolution capabilities that work with
 //! clangd to find and identify symbols based on user queries, handling ambiguous
 //! matches and providing the best candidate symbol for analysis.
+//!
+//! Objective-C method queries such as `-[MyClass doThing:with:]` are looked up
+//! by their selector and narrowed to the receiver class.
 
 use crate::clangd::session::ClangdSessionTrait;
 use tracing::debug;
 
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::objc_selectors::SelectorQuery;
+use crate::mcp_server::tools::lsp_helpers::suggestions::{
+    DEFAULT_SUGGESTION_LIMIT, find_similar_symbols,
+};
 use crate::project::component_session::ComponentSession;
 use crate::symbol::Symbol;
 
@@ -21,16 +28,59 @@ pub async fn get_matching_symbol(
     symbol_query: &str,
     component_session: &ComponentSession,
 ) -> Result<Symbol, AnalyzerError> {
-    // Use the LSP client to find symbols matching the provided name
-    let mut session = component_session.lsp_session().await;
-    let symbols = session
-        .client_mut()
-        .workspace_symbols(symbol_query.to_string())
-        .await
-        .map_err(AnalyzerError::from)?;
+    let symbols = get_matching_symbols(symbol_query, component_session).await?;
+    Ok(symbols[0].clone())
+}
+
+/// Get all symbols matching the user query, best match first
+///
+/// Never returns an empty list: no match is reported as `AnalyzerError::NoSymbols`.
+pub async fn get_matching_symbols(
+    symbol_query: &str,
+    component_session: &ComponentSession,
+) -> Result<Vec<Symbol>, AnalyzerError> {
+    // clangd indexes Objective-C methods by selector, the class is their container
+    let selector_query = SelectorQuery::parse(symbol_query);
+    let lsp_query = selector_query.map_or(symbol_query, |query| query.selector);
+
+    // Use the LSP client to find symbols matching the provided name; an empty
+    // answer right after indexing completed is retried while the index loads
+    let mut symbols = component_session
+        .retry_while_empty(
+            |symbols: &Option<Vec<_>>| symbols.as_ref().is_some_and(Vec::is_empty),
+            || {
+                component_session.coalesced("workspace/symbol", lsp_query, || async {
+                    let mut session = component_session.lsp_session().await;
+                    session
+                        .client_mut()
+                        .workspace_symbols(lsp_query.to_string())
+                        .await
+                        .map_err(AnalyzerError::from)
+                })
+            },
+        )
+        .await?
+        .ok_or_else(|| {
+            AnalyzerError::NoResult(format!("workspace symbol query '{}'", symbol_query))
+        })?;
+
+    if let Some(query) = selector_query {
+        symbols.retain(|symbol| query.matches(&symbol.name, symbol.container_name.as_deref()));
+    }
 
     if symbols.is_empty() {
-        return Err(AnalyzerError::NoSymbols(symbol_query.to_string()));
+        // Suggestions are best effort, a failure still reports the symbol as not found
+        let suggestions =
+            find_similar_symbols(symbol_query, component_session, DEFAULT_SUGGESTION_LIMIT)
+                .await
+                .unwrap_or_else(|e| {
+                    debug!("No suggestions for '{}': {}", symbol_query, e);
+                    Vec::new()
+                });
+        return Err(AnalyzerError::NoSymbols {
+            query: symbol_query.to_string(),
+            suggestions,
+        });
     }
 
     debug!(
@@ -39,6 +89,164 @@ pub async fn get_matching_symbol(
         symbol_query
     );
 
-    // Convert to our Symbol type and return the first as the best match
-    Ok(symbols[0].clone().into())
+    Ok(symbols.into_iter().map(Symbol::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+    use crate::lsp::client::LspError;
+    use crate::lsp::protocol::JsonRpcError;
+
+    #[tokio::test]
+    async fn test_null_empty_and_error_results_are_distinct() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // Unscripted: clangd answers null
+        let result = get_matching_symbol("Widget", &component_session).await;
+        assert!(matches!(result, Err(AnalyzerError::NoResult(_))));
+
+        server.respond("workspace/symbol", serde_json::json!([]));
+        let result = get_matching_symbol("Widget", &component_session).await;
+        assert!(matches!(
+            result,
+            Err(AnalyzerError::NoSymbols { query, suggestions })
+                if query == "Widget" && suggestions.is_empty()
+        ));
+
+        server.fail("workspace/symbol", -32603, "index corrupted");
+        let result = get_matching_symbol("Widget", &component_session).await;
+        assert!(matches!(
+            result,
+            Err(AnalyzerError::Lsp(LspError::JsonRpc(
+                JsonRpcError::Server { code: -32603, .. }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_queries_share_request() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([{
+                "name": "main",
+                "kind": 12,
+                "location": {
+                    "uri": "file:///src/main.cpp",
+                    "range": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 8}}
+                }
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let symbol_requests = || {
+            server
+                .received_methods()
+                .iter()
+                .filter(|m| *m == "workspace/symbol")
+                .count()
+        };
+
+        let (a, b) = tokio::join!(
+            get_matching_symbol("main", &component_session),
+            get_matching_symbol("main", &component_session),
+        );
+        assert_eq!(a.unwrap().name, "main");
+        assert_eq!(b.unwrap().name, "main");
+        assert_eq!(symbol_requests(), 1);
+
+        // Sequential calls are sent again
+        get_matching_symbol("main", &component_session)
+            .await
+            .unwrap();
+        assert_eq!(symbol_requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_suggests_similar_symbols() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        // Only the fragment queries find the misspelled class
+        server.respond_with("workspace/symbol", |params| {
+            let query = params.unwrap()["query"].as_str().unwrap().to_string();
+            if query == "Wdiget" {
+                return serde_json::json!([]);
+            }
+            serde_json::json!([{
+                "name": "Widget",
+                "kind": 5,
+                "containerName": "ui",
+                "location": {
+                    "uri": "file:///src/widget.h",
+                    "range": {"start": {"line": 2, "character": 6}, "end": {"line": 2, "character": 12}}
+                }
+            }])
+        });
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let error = get_matching_symbol("Wdiget", &component_session)
+            .await
+            .unwrap_err();
+        let AnalyzerError::NoSymbols { suggestions, .. } = &error else {
+            panic!("Expected NoSymbols error, got {error:?}");
+        };
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].name, "ui::Widget");
+        assert!(
+            error
+                .to_string()
+                .contains("Did you mean: ui::Widget (Class at")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_objc_selector_query_narrows_to_receiver_class() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.mm", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let method = |container: &str, line: u32| {
+            serde_json::json!({
+                "name": "doThing:with:",
+                "kind": 6,
+                "containerName": container,
+                "location": {
+                    "uri": "file:///src/widgets.mm",
+                    "range": {"start": {"line": line, "character": 0}, "end": {"line": line, "character": 7}}
+                }
+            })
+        };
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([method("Gadget", 3), method("Widget(Private)", 9)]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let symbol = get_matching_symbol("-[Widget doThing:with:]", &component_session)
+            .await
+            .unwrap();
+        assert_eq!(symbol.container_name.as_deref(), Some("Widget(Private)"));
+        assert_eq!(symbol.location.range.start.line, 9);
+        // clangd is asked for the selector alone
+        let params = server.received_params("workspace/symbol");
+        assert_eq!(params[0].as_ref().unwrap()["query"], "doThing:with:");
+
+        let result = get_matching_symbol("-[Sprocket doThing:with:]", &component_session).await;
+        assert!(matches!(result, Err(AnalyzerError::NoSymbols { .. })));
+    }
 }
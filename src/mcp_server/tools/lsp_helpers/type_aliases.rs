@@ -0,0 +1,303 @@
+//! Type alias resolution functionality for C++ typedef and using declarations
+//!
+//! This module follows chains of type aliases (`using Vec = std::vector<int>;`,
+//! `typedef Vec Buffer;`) down to the underlying type. Alias declarations are
+//! recognized from clangd hover content and each level is followed with
+//! `textDocument/typeDefinition`.
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_type_definitions;
+use crate::mcp_server::tools::lsp_helpers::hover::get_hover_info;
+use crate::project::component_session::ComponentSession;
+use crate::symbol::FileLocation;
+
+/// Default number of alias levels followed
+pub const DEFAULT_ALIAS_DEPTH: u32 = 8;
+
+/// Upper bound for the number of alias levels followed
+pub const MAX_ALIAS_DEPTH: u32 = 32;
+
+// ============================================================================
+// Type Alias Types
+// ============================================================================
+
+/// Alias declaration recognized from clangd hover content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasHover {
+    /// Alias name
+    pub name: String,
+    /// Type the alias refers to, as written in the declaration
+    pub aliased_type: String,
+    /// Fully desugared type reported by clangd ("aka" type), if any
+    pub canonical_type: Option<String>,
+}
+
+/// One level of an alias chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AliasStep {
+    /// Alias name
+    pub name: String,
+    /// Type the alias refers to, as written in the declaration
+    pub aliased_type: String,
+    /// Location of the alias declaration
+    pub location: FileLocation,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasResolution {
+    /// Alias declarations followed, outermost first
+    pub chain: Vec<AliasStep>,
+    /// Type the innermost alias refers to, as written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_type: Option<String>,
+    /// Fully desugared type as reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_type: Option<String>,
+    /// Declaration of the underlying type (absent for builtin types)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_location: Option<FileLocation>,
+    /// Whether the depth cap stopped resolution while still inside an alias
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub depth_limit_reached: bool,
+    /// Whether the chain led back to an alias already visited
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle_detected: bool,
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Follow the alias declared at `location` down to its underlying type
+///
+/// Each level is identified from hover content and followed with a type definition
+/// request. Resolution stops at the first non-alias type, at a builtin type (no type
+/// definition), on a cycle, or after `max_depth` levels.
+///
+/// # Returns
+/// * `Ok(AliasResolution)` - Chain of aliases (empty when `location` is not an alias)
+/// * `Err(AnalyzerError)` - LSP error on the first hover request
+pub async fn resolve_alias_chain(
+    location: &FileLocation,
+    component_session: &ComponentSession,
+    max_depth: u32,
+) -> Result<AliasResolution, AnalyzerError> {
+    let mut resolution = AliasResolution::default();
+    let mut current = location.clone();
+
+    for depth in 0..max_depth {
+        let hover = match get_hover_info(&current, component_session).await {
+            Ok(hover) => hover,
+            Err(err) if depth == 0 => return Err(err),
+            Err(err) => {
+                // The type definition is known, it just cannot be classified
+                warn!(
+                    "Hover failed at {}: {} - stopping alias resolution",
+                    current.to_compact_range(),
+                    err
+                );
+                resolution.underlying_location = Some(current);
+                return Ok(resolution);
+            }
+        };
+
+        let Some(alias) = parse_alias_hover(&hover) else {
+            // Reached a type that is not an alias
+            debug!("{} is not an alias", current.to_compact_range());
+            resolution.underlying_location = Some(current);
+            return Ok(resolution);
+        };
+
+        debug!(
+            "Alias '{}' = '{}' at {}",
+            alias.name,
+            alias.aliased_type,
+            current.to_compact_range()
+        );
+        if resolution.canonical_type.is_none() {
+            resolution.canonical_type = alias.canonical_type.clone();
+        }
+        resolution.underlying_type = Some(alias.aliased_type.clone());
+        resolution.chain.push(AliasStep {
+            name: alias.name,
+            aliased_type: alias.aliased_type,
+            location: current.clone(),
+        });
+
+        let targets = get_type_definitions(&current, component_session).await?;
+        let Some(next) = targets.into_iter().next() else {
+            // Builtin or otherwise declaration-less type
+            return Ok(resolution);
+        };
+
+        if resolution.chain.iter().any(|step| {
+            step.location.file_path == next.file_path && step.location.range == next.range
+        }) {
+            resolution.cycle_detected = true;
+            return Ok(resolution);
+        }
+        current = next;
+    }
+
+    resolution.depth_limit_reached = !resolution.chain.is_empty();
+    Ok(resolution)
+}
+
+/// Recognize an alias declaration in clangd hover markdown
+///
+/// clangd renders aliases as `### type-alias `Name`` followed by a
+/// `Type: `T` (aka `U`)` line and the declaration in a code block.
+///
+/// # Returns
+/// * `Some(AliasHover)` - Hover describes a `using` or `typedef` alias
+/// * `None` - Hover describes something else
+pub fn parse_alias_hover(hover: &str) -> Option<AliasHover> {
+    let declaration = code_block_lines(hover)
+        .into_iter()
+        .find_map(|line| parse_alias_declaration(line.trim()));
+
+    let is_alias_header = hover.lines().next().is_some_and(|line| {
+        line.trim_start_matches('#')
+            .trim()
+            .starts_with("type-alias")
+    });
+
+    let (name, aliased_type) = match declaration {
+        Some(declaration) => declaration,
+        None if is_alias_header => {
+            // Declaration not shown: fall back to header name and Type line
+            let name = backtick_values(hover.lines().next()?).into_iter().next()?;
+            let aliased_type = type_line_values(hover)?.0;
+            (name, aliased_type)
+        }
+        None => return None,
+    };
+
+    let canonical_type = type_line_values(hover).and_then(|(_, aka)| aka);
+
+    Some(AliasHover {
+        name,
+        aliased_type,
+        canonical_type,
+    })
+}
+
+// ============================================================================
+// Hover Parsing Utilities
+// ============================================================================
+
+/// Lines inside fenced code blocks
+pub(crate) fn code_block_lines(hover: &str) -> Vec<&str> {
+    let mut in_block = false;
+    let mut lines = Vec::new();
+    for line in hover.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+        } else if in_block {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Parse `using Name = Type` or `typedef Type Name` into (name, type)
+fn parse_alias_declaration(line: &str) -> Option<(String, String)> {
+    let line = line.trim_end_matches(';').trim();
+    // Alias templates are rendered with the template header on the same line
+    let line = match line.strip_prefix("template") {
+        Some(rest) => rest.find("using ").map(|index| &rest[index..])?,
+        None => line,
+    };
+
+    if let Some(rest) = line.strip_prefix("using ") {
+        let (name, aliased) = rest.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return None;
+        }
+        return Some((name.to_string(), aliased.trim().to_string()));
+    }
+
+    let rest = line.strip_prefix("typedef ")?.trim();
+    let name_start = rest
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |index| index + 1);
+    let name = &rest[name_start..];
+    let aliased = rest[..name_start].trim();
+    if name.is_empty() || aliased.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), aliased.to_string()))
+}
+
+/// Values of the `Type: `T` (aka `U`)` line
+fn type_line_values(hover: &str) -> Option<(String, Option<String>)> {
+    let line = hover
+        .lines()
+        .find(|line| line.trim_start().starts_with("Type:"))?;
+    let mut values = backtick_values(line).into_iter();
+    let written = values.next()?;
+    let aka = line.contains("aka").then(|| values.next()).flatten();
+    Some((written, aka))
+}
+
+/// Texts enclosed in single backticks
+pub(crate) fn backtick_values(line: &str) -> Vec<String> {
+    line.split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_using_alias_hover() {
+        let hover = "### type-alias `Vec`  \n\n---\nType: `std::vector<int>` (aka `std::vector<int, std::allocator<int>>`)  \n\n---\n```cpp\n// In namespace app\nusing Vec = std::vector<int>\n```";
+        let alias = parse_alias_hover(hover).unwrap();
+        assert_eq!(alias.name, "Vec");
+        assert_eq!(alias.aliased_type, "std::vector<int>");
+        assert_eq!(
+            alias.canonical_type.as_deref(),
+            Some("std::vector<int, std::allocator<int>>")
+        );
+    }
+
+    #[test]
+    fn test_parse_typedef_hover() {
+        let hover = "### type-alias `Buffer`  \n\n---\nType: `Vec`  \n\n---\n```cpp\ntypedef Vec Buffer\n```";
+        let alias = parse_alias_hover(hover).unwrap();
+        assert_eq!(alias.name, "Buffer");
+        assert_eq!(alias.aliased_type, "Vec");
+        assert_eq!(alias.canonical_type, None);
+
+        let (name, aliased) = parse_alias_declaration("typedef unsigned long *ulong_ptr;").unwrap();
+        assert_eq!(name, "ulong_ptr");
+        assert_eq!(aliased, "unsigned long *");
+    }
+
+    #[test]
+    fn test_parse_alias_template_hover() {
+        let hover = "### type-alias `Ptr`  \n\n---\n```cpp\ntemplate <typename T> using Ptr = std::unique_ptr<T>\n```";
+        let alias = parse_alias_hover(hover).unwrap();
+        assert_eq!(alias.name, "Ptr");
+        assert_eq!(alias.aliased_type, "std::unique_ptr<T>");
+    }
+
+    #[test]
+    fn test_non_alias_hover() {
+        let hover =
+            "### class `Widget`  \n\n---\nSize: 8 bytes  \n\n---\n```cpp\nclass Widget {}\n```";
+        assert!(parse_alias_hover(hover).is_none());
+
+        // using-directives and using-declarations are not aliases
+        let hover = "### namespace `std`\n\n---\n```cpp\nusing namespace std\n```";
+        assert!(parse_alias_hover(hover).is_none());
+    }
+}
@@ -6,22 +6,53 @@
 
 use crate::clangd::session::ClangdSessionTrait;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::warn;
 
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
 use crate::project::component_session::ComponentSession;
 use crate::symbol::FileLocation;
 
+/// Maximum time allowed for each type hierarchy request (prepare, supertypes, subtypes)
+pub const TYPE_HIERARCHY_STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
 // ============================================================================
 // Type Hierarchy Types
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Individual LSP request made while building a type hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeHierarchyStep {
+    /// textDocument/prepareTypeHierarchy
+    Prepare,
+    /// typeHierarchy/supertypes
+    Supertypes,
+    /// typeHierarchy/subtypes
+    Subtypes,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TypeHierarchy {
     /// Parent classes/interfaces that this type inherits from
     pub supertypes: Vec<String>,
     /// Derived classes that inherit from this type
     pub subtypes: Vec<String>,
+    /// Whether any step timed out, leaving the hierarchy partial
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub timed_out: bool,
+    /// Steps that timed out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out_steps: Vec<TypeHierarchyStep>,
+}
+
+impl TypeHierarchy {
+    fn mark_timed_out(&mut self, step: TypeHierarchyStep) {
+        warn!("Type hierarchy step {:?} timed out", step);
+        self.timed_out = true;
+        self.timed_out_steps.push(step);
+    }
 }
 
 // ============================================================================
@@ -29,9 +60,26 @@ pub struct TypeHierarchy {
 // ============================================================================
 
 /// Get type hierarchy information for a symbol (classes, structs, interfaces)
+///
+/// Each request is bounded by [`TYPE_HIERARCHY_STEP_TIMEOUT`]; steps that time
+/// out are reported in the result instead of failing the analysis.
 pub async fn get_type_hierarchy(
     symbol_location: &FileLocation,
     component_session: &ComponentSession,
+) -> Result<TypeHierarchy, AnalyzerError> {
+    get_type_hierarchy_with_timeout(
+        symbol_location,
+        component_session,
+        TYPE_HIERARCHY_STEP_TIMEOUT,
+    )
+    .await
+}
+
+/// Get type hierarchy information with a custom per-step timeout
+pub async fn get_type_hierarchy_with_timeout(
+    symbol_location: &FileLocation,
+    component_session: &ComponentSession,
+    step_timeout: Duration,
 ) -> Result<TypeHierarchy, AnalyzerError> {
     let uri = symbol_location.get_uri();
     let lsp_position: lsp_types::Position = symbol_location.range.start.into();
@@ -42,46 +90,104 @@ pub async fn get_type_hierarchy(
 
     let mut session = component_session.lsp_session().await;
     let client = session.client_mut();
+    let mut hierarchy = TypeHierarchy::default();
 
     // Prepare type hierarchy at the symbol location
-    let hierarchy_items = client
-        .text_document_prepare_type_hierarchy(uri, lsp_position)
-        .await
-        .map_err(AnalyzerError::from)?;
+    let Some(hierarchy_items) = with_step_timeout(
+        step_timeout,
+        client.text_document_prepare_type_hierarchy(uri, lsp_position),
+    )
+    .await?
+    else {
+        hierarchy.mark_timed_out(TypeHierarchyStep::Prepare);
+        return Ok(hierarchy);
+    };
 
     // If we don't get any hierarchy items, return empty hierarchy
     let hierarchy_item = match hierarchy_items {
         Some(items) if !items.is_empty() => items.into_iter().next().unwrap(),
-        _ => {
-            return Ok(TypeHierarchy {
-                supertypes: Vec::new(),
-                subtypes: Vec::new(),
-            });
-        }
+        _ => return Ok(hierarchy),
     };
 
     // Get supertypes (parent classes/interfaces)
-    let supertypes = client
-        .type_hierarchy_supertypes(hierarchy_item.clone())
-        .await
-        .map_err(AnalyzerError::from)?
-        .unwrap_or_default()
-        .into_iter()
-        .map(|item| item.name)
-        .collect();
+    match with_step_timeout(
+        step_timeout,
+        client.type_hierarchy_supertypes(hierarchy_item.clone()),
+    )
+    .await?
+    {
+        Some(items) => {
+            hierarchy.supertypes = items
+                .unwrap_or_default()
+                .into_iter()
+                .map(|item| item.name)
+                .collect()
+        }
+        None => hierarchy.mark_timed_out(TypeHierarchyStep::Supertypes),
+    }
 
     // Get subtypes (derived classes)
-    let subtypes = client
-        .type_hierarchy_subtypes(hierarchy_item)
+    match with_step_timeout(step_timeout, client.type_hierarchy_subtypes(hierarchy_item)).await? {
+        Some(items) => {
+            hierarchy.subtypes = items
+                .unwrap_or_default()
+                .into_iter()
+                .map(|item| item.name)
+                .collect()
+        }
+        None => hierarchy.mark_timed_out(TypeHierarchyStep::Subtypes),
+    }
+
+    Ok(hierarchy)
+}
+
+/// Run one hierarchy request with a timeout
+///
+/// Returns `Ok(None)` when the request did not complete in time.
+async fn with_step_timeout<T, E>(
+    step_timeout: Duration,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<Option<T>, AnalyzerError>
+where
+    AnalyzerError: From<E>,
+{
+    match tokio::time::timeout(step_timeout, request).await {
+        Ok(result) => result.map(Some).map_err(AnalyzerError::from),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_step_timeout() {
+        let completed =
+            with_step_timeout(Duration::from_secs(1), async { Ok::<_, AnalyzerError>(42) })
+                .await
+                .unwrap();
+        assert_eq!(completed, Some(42));
+
+        let timed_out = with_step_timeout(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, AnalyzerError>(42)
+        })
         .await
-        .map_err(AnalyzerError::from)?
-        .unwrap_or_default()
-        .into_iter()
-        .map(|item| item.name)
-        .collect();
-
-    Ok(TypeHierarchy {
-        supertypes,
-        subtypes,
-    })
+        .unwrap();
+        assert_eq!(timed_out, None);
+    }
+
+    #[test]
+    fn test_timed_out_markers_serialization() {
+        let complete = serde_json::to_value(TypeHierarchy::default()).unwrap();
+        assert!(complete.get("timed_out").is_none());
+        assert!(complete.get("timed_out_steps").is_none());
+
+        let mut partial = TypeHierarchy::default();
+        partial.mark_timed_out(TypeHierarchyStep::Subtypes);
+        let partial = serde_json::to_value(partial).unwrap();
+        assert_eq!(partial["timed_out"], true);
+        assert_eq!(partial["timed_out_steps"], serde_json::json!(["subtypes"]));
+    }
 }
@@ -37,15 +37,22 @@
 //! The workspace symbol limit is configured via `DEFAULT_WORKSPACE_SYMBOL_LIMIT` (1000)
 //! and passed to clangd via the `--limit-results` argument. However, clangd may return
 //! fewer results based on its internal filtering.
+//!
+//! When clangd returns exactly as many symbols as its limit allows, the list was
+//! most likely truncated before any of the filters above ran.
+//! `WorkspaceSymbolSearchBuilder::search_with_limit` reports this so that callers
+//! can tell an incomplete result apart from a complete one.
 
 use lsp_types::{SymbolKind, WorkspaceSymbol};
 use tracing::{debug, trace};
 
+use crate::clangd::config::DEFAULT_WORKSPACE_SYMBOL_LIMIT;
 use crate::clangd::session::ClangdSessionTrait;
 use crate::lsp::traits::LspClientTrait;
 use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
-use crate::project::ProjectComponent;
 use crate::project::component_session::ComponentSession;
+use crate::project::{ProjectComponent, ResultExclusions};
+use crate::symbol::path_from_file_uri;
 
 // ============================================================================
 // Traits for Workspace Symbol Filtering
@@ -60,6 +67,8 @@ pub trait WorkspaceSymbolFilter {
 /// Filter for project boundary detection
 pub struct ProjectBoundaryFilter {
     include_external: bool,
+    /// Scopes external symbols must belong to; empty means no restriction
+    external_scopes: Vec<String>,
     canonical_source_root: std::path::PathBuf,
 }
 
@@ -72,14 +81,42 @@ impl ProjectBoundaryFilter {
 
         Self {
             include_external,
+            external_scopes: Vec::new(),
             canonical_source_root,
         }
     }
 
-    /// Check if a file path belongs to the project
-    fn is_project_file(&self, path: &str) -> bool {
-        let file_path = std::path::PathBuf::from(path);
+    /// Keep only external symbols within the given namespaces or qualified-name prefixes
+    ///
+    /// Scopes are written like `std` or `std::` and match the scope itself and
+    /// everything nested in it, so `std` matches `std::vector` but neither
+    /// `stdx::span` nor `__gnu_cxx::__normal_iterator`. Non-empty scopes include
+    /// matching external symbols even when `include_external` is false.
+    pub fn with_external_scopes(mut self, scopes: &[String]) -> Self {
+        self.external_scopes = scopes
+            .iter()
+            .map(|scope| normalize_scope(scope).to_string())
+            .collect();
+        self
+    }
 
+    /// Check if a symbol's qualified name lies within one of the external scopes
+    fn in_external_scope(&self, symbol: &WorkspaceSymbol) -> bool {
+        let qualified = match symbol.container_name.as_deref() {
+            Some(container) if !container.is_empty() => {
+                format!("{}::{}", normalize_scope(container), symbol.name)
+            }
+            _ => symbol.name.clone(),
+        };
+        self.external_scopes.iter().any(|scope| {
+            qualified
+                .strip_prefix(scope.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+
+    /// Check if a file path belongs to the project
+    fn is_project_file(&self, file_path: &std::path::Path) -> bool {
         if let Ok(canonical_file) = file_path.canonicalize() {
             canonical_file.starts_with(&self.canonical_source_root)
         } else {
@@ -90,7 +127,7 @@ impl ProjectBoundaryFilter {
 
 impl WorkspaceSymbolFilter for ProjectBoundaryFilter {
     fn matches(&self, symbol: &WorkspaceSymbol) -> bool {
-        if self.include_external {
+        if self.include_external && self.external_scopes.is_empty() {
             return true;
         }
 
@@ -99,14 +136,19 @@ impl WorkspaceSymbolFilter for ProjectBoundaryFilter {
             lsp_types::OneOf::Right(workspace_location) => workspace_location.uri.as_str(),
         };
 
-        if let Some(path) = uri_str.strip_prefix("file://") {
-            self.is_project_file(path)
-        } else {
-            true // Default to inclusion when URI parsing fails
-        }
+        let is_project_symbol = match path_from_file_uri(uri_str) {
+            Some(path) => self.is_project_file(&path),
+            None => true, // Default to inclusion when URI parsing fails
+        };
+        is_project_symbol || (!self.external_scopes.is_empty() && self.in_external_scope(symbol))
     }
 }
 
+/// Strip leading and trailing `::` from a scope such as `::std::`
+fn normalize_scope(scope: &str) -> &str {
+    scope.trim_start_matches("::").trim_end_matches("::")
+}
+
 /// Filter for symbol kinds
 pub struct SymbolKindFilter {
     allowed_kinds: Vec<lsp_types::SymbolKind>,
@@ -127,31 +169,107 @@ impl WorkspaceSymbolFilter for SymbolKindFilter {
     }
 }
 
-/// Filter for symbol names using substring matching
+/// Filter leaving out symbols of excluded generated or vendored files
+pub struct ExcludedPathFilter<'a> {
+    exclusions: &'a ResultExclusions,
+}
+
+impl<'a> ExcludedPathFilter<'a> {
+    pub fn new(exclusions: &'a ResultExclusions) -> Self {
+        Self { exclusions }
+    }
+}
+
+impl WorkspaceSymbolFilter for ExcludedPathFilter<'_> {
+    fn matches(&self, symbol: &WorkspaceSymbol) -> bool {
+        let uri_str = match &symbol.location {
+            lsp_types::OneOf::Left(location) => location.uri.as_str(),
+            lsp_types::OneOf::Right(workspace_location) => workspace_location.uri.as_str(),
+        };
+        path_from_file_uri(uri_str).is_none_or(|path| !self.exclusions.is_excluded(&path))
+    }
+}
+
+/// How symbol names are matched against a search query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameMatchMode {
+    /// Case-insensitive matching; workspace results keep clangd's fuzzy ranking
+    #[default]
+    Fuzzy,
+    /// Name or qualified name equal to the query, case-sensitive
+    Exact,
+    /// Name containing the query, case-sensitive
+    CaseSensitiveSubstring,
+}
+
+impl NameMatchMode {
+    /// Accepted parameter values
+    pub const VALUES: [&'static str; 3] = ["fuzzy", "exact", "case_sensitive_substring"];
+
+    /// Check a symbol name against the query
+    ///
+    /// `container` is the enclosing scope (e.g. "ns::Class"), used by exact matching
+    /// of qualified queries. An empty query matches every symbol.
+    pub fn matches(&self, query: &str, name: &str, container: Option<&str>) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+        match self {
+            NameMatchMode::Fuzzy => name.to_lowercase().contains(&query.to_lowercase()),
+            NameMatchMode::Exact => {
+                if !query.contains("::") {
+                    // Unqualified query: compare with the unqualified name
+                    return name.rsplit("::").next() == Some(query);
+                }
+                let qualified = match container {
+                    Some(container) if !container.is_empty() => format!("{container}::{name}"),
+                    _ => name.to_string(),
+                };
+                match query.strip_prefix("::") {
+                    // Fully qualified query
+                    Some(query) => qualified == query,
+                    // Partially qualified query matches the innermost scopes
+                    None => qualified == query || qualified.ends_with(&format!("::{query}")),
+                }
+            }
+            NameMatchMode::CaseSensitiveSubstring => name.contains(query),
+        }
+    }
+}
+
+impl TryFrom<&str> for NameMatchMode {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "fuzzy" => Ok(NameMatchMode::Fuzzy),
+            "exact" => Ok(NameMatchMode::Exact),
+            "case_sensitive_substring" => Ok(NameMatchMode::CaseSensitiveSubstring),
+            _ => Err(format!(
+                "Invalid match mode '{}'. Valid values: {}",
+                value,
+                NameMatchMode::VALUES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Filter for symbol names according to a match mode
 pub struct NameFilter {
     query: String,
-    case_sensitive: bool,
+    mode: NameMatchMode,
 }
 
 impl NameFilter {
-    pub fn new(query: String, case_sensitive: bool) -> Self {
-        Self {
-            query,
-            case_sensitive,
-        }
+    pub fn new(query: String, mode: NameMatchMode) -> Self {
+        Self { query, mode }
     }
 }
 
 impl WorkspaceSymbolFilter for NameFilter {
     fn matches(&self, symbol: &WorkspaceSymbol) -> bool {
-        if self.case_sensitive {
-            symbol.name.contains(&self.query)
-        } else {
-            symbol
-                .name
-                .to_lowercase()
-                .contains(&self.query.to_lowercase())
-        }
+        self.mode
+            .matches(&self.query, &symbol.name, symbol.container_name.as_deref())
     }
 }
 
@@ -203,6 +321,15 @@ impl<'a> Iterator for WorkspaceSymbolIterator<'a> {
 // Workspace Symbol Search Builder
 // ============================================================================
 
+/// Filtered workspace symbols together with clangd's result limit
+#[derive(Debug, Clone)]
+pub struct WorkspaceSymbolSearchResult {
+    pub symbols: Vec<WorkspaceSymbol>,
+    /// clangd's result limit when clangd returned that many symbols, meaning
+    /// more symbols matching the query may exist
+    pub clangd_limit_reached: Option<u32>,
+}
+
 /// Builder pattern for flexible workspace symbol searching
 #[derive(Debug, Clone)]
 pub struct WorkspaceSymbolSearchBuilder {
@@ -210,7 +337,9 @@ pub struct WorkspaceSymbolSearchBuilder {
     kinds: Option<Vec<lsp_types::SymbolKind>>,
     max_results: Option<u32>,
     include_external: bool,
-    case_sensitive: bool,
+    external_scopes: Vec<String>,
+    match_mode: NameMatchMode,
+    exclusions: ResultExclusions,
 }
 
 impl WorkspaceSymbolSearchBuilder {
@@ -221,7 +350,9 @@ impl WorkspaceSymbolSearchBuilder {
             kinds: None,
             max_results: None,
             include_external: false,
-            case_sensitive: false,
+            external_scopes: Vec::new(),
+            match_mode: NameMatchMode::default(),
+            exclusions: ResultExclusions::none(),
         }
     }
 
@@ -243,10 +374,26 @@ impl WorkspaceSymbolSearchBuilder {
         self
     }
 
-    /// Enable case-sensitive search
-    #[allow(dead_code)]
-    pub fn case_sensitive(mut self, sensitive: bool) -> Self {
-        self.case_sensitive = sensitive;
+    /// Include only external symbols within the given namespaces or prefixes
+    ///
+    /// See `ProjectBoundaryFilter::with_external_scopes`.
+    pub fn with_external_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.external_scopes = scopes;
+        self
+    }
+
+    /// Set how symbol names are matched against the query
+    ///
+    /// clangd ranks and pre-filters workspace symbols with its own fuzzy matcher in
+    /// every mode; exact and case-sensitive matching are applied on top of that.
+    pub fn with_match_mode(mut self, mode: NameMatchMode) -> Self {
+        self.match_mode = mode;
+        self
+    }
+
+    /// Leave out symbols of files matching the exclusion globs
+    pub fn with_exclusions(mut self, exclusions: ResultExclusions) -> Self {
+        self.exclusions = exclusions;
         self
     }
 
@@ -256,27 +403,62 @@ impl WorkspaceSymbolSearchBuilder {
         component_session: &ComponentSession,
         component: &ProjectComponent,
     ) -> Result<Vec<WorkspaceSymbol>, AnalyzerError> {
+        Ok(self
+            .search_with_limit(component_session, component)
+            .await?
+            .symbols)
+    }
+
+    /// Execute the search and also report whether clangd's result limit was hit
+    pub async fn search_with_limit(
+        &self,
+        component_session: &ComponentSession,
+        component: &ProjectComponent,
+    ) -> Result<WorkspaceSymbolSearchResult, AnalyzerError> {
         trace!(
             "Executing workspace symbol search with query: {}",
             self.query
         );
 
         // Get symbols from clangd with a large limit (2000) to preserve ranking
-        let mut session = component_session.lsp_session().await;
-        let symbols = session
-            .client_mut()
-            .workspace_symbols(self.query.clone())
-            .await
-            .map_err(AnalyzerError::from)?;
+        let symbols = component_session
+            .coalesced("workspace/symbol", &self.query, || async {
+                let mut session = component_session.lsp_session().await;
+                session
+                    .client_mut()
+                    .workspace_symbols(self.query.clone())
+                    .await
+                    .map_err(AnalyzerError::from)
+            })
+            .await?
+            .unwrap_or_else(|| {
+                debug!("clangd returned no result for query '{}'", self.query);
+                Vec::new()
+            });
 
         debug!("Retrieved {} symbols from clangd", symbols.len());
 
+        let clangd_limit_reached = component_session
+            .workspace_symbol_limit()
+            .filter(|&limit| symbols.len() >= limit as usize);
+        if let Some(limit) = clangd_limit_reached {
+            debug!(
+                "clangd returned {} symbols for query '{}', results are capped",
+                limit, self.query
+            );
+        }
+
         // Apply filters using iterator pattern
         let mut filtered_iter = WorkspaceSymbolIterator::new(&symbols);
 
         // Add project boundary filter
-        filtered_iter =
-            filtered_iter.with_filter(ProjectBoundaryFilter::new(component, self.include_external));
+        filtered_iter = filtered_iter.with_filter(
+            ProjectBoundaryFilter::new(component, self.include_external)
+                .with_external_scopes(&self.external_scopes),
+        );
+
+        // Leave out generated and vendored files
+        filtered_iter = filtered_iter.with_filter(ExcludedPathFilter::new(&self.exclusions));
 
         // Add symbol kind filter if specified
         if let Some(ref kinds) = self.kinds {
@@ -285,12 +467,12 @@ impl WorkspaceSymbolSearchBuilder {
 
         // Add name filter for additional refinement (beyond clangd's initial filtering)
         filtered_iter =
-            filtered_iter.with_filter(NameFilter::new(self.query.clone(), self.case_sensitive));
+            filtered_iter.with_filter(NameFilter::new(self.query.clone(), self.match_mode));
 
         // Collect results with optional limit
         let results: Vec<WorkspaceSymbol> = if let Some(max) = self.max_results {
             filtered_iter
-                .take(max.min(1000) as usize)
+                .take(max.min(DEFAULT_WORKSPACE_SYMBOL_LIMIT) as usize)
                 .cloned()
                 .collect()
         } else {
@@ -301,7 +483,10 @@ impl WorkspaceSymbolSearchBuilder {
             "Filtered to {} symbols after applying filters",
             results.len()
         );
-        Ok(results)
+        Ok(WorkspaceSymbolSearchResult {
+            symbols: results,
+            clangd_limit_reached,
+        })
     }
 
     /// Execute search and return only the first result
@@ -480,6 +665,7 @@ mod tests {
             generator: "Ninja".to_string(),
             build_type: "Debug".to_string(),
             build_options: std::collections::HashMap::new(),
+            preset: None,
         }
     }
 
@@ -519,7 +705,7 @@ mod tests {
 
         let results: Vec<_> = WorkspaceSymbolIterator::new(&symbols)
             .with_filter(SymbolKindFilter::new(vec![SymbolKind::CLASS]))
-            .with_filter(NameFilter::new("Test".to_string(), false))
+            .with_filter(NameFilter::new("Test".to_string(), NameMatchMode::Fuzzy))
             .collect();
 
         assert_eq!(results.len(), 1);
@@ -545,7 +731,7 @@ mod tests {
 
     #[test]
     fn test_name_filter_case_insensitive() {
-        let filter = NameFilter::new("test".to_string(), false);
+        let filter = NameFilter::new("test".to_string(), NameMatchMode::Fuzzy);
 
         let symbol1 =
             create_test_workspace_symbol("TestClass", SymbolKind::CLASS, "file:///test.cpp", None);
@@ -561,7 +747,7 @@ mod tests {
 
     #[test]
     fn test_name_filter_case_sensitive() {
-        let filter = NameFilter::new("Test".to_string(), true);
+        let filter = NameFilter::new("Test".to_string(), NameMatchMode::CaseSensitiveSubstring);
 
         let symbol1 =
             create_test_workspace_symbol("TestClass", SymbolKind::CLASS, "file:///test.cpp", None);
@@ -576,19 +762,57 @@ mod tests {
         assert!(!filter.matches(&symbol2));
     }
 
+    #[test]
+    fn test_name_match_mode_exact() {
+        let mode = NameMatchMode::Exact;
+        assert!(mode.matches("Color", "Color", Some("gfx")));
+        assert!(!mode.matches("Color", "color", Some("gfx")));
+        assert!(!mode.matches("Color", "ColorSpace", Some("gfx")));
+
+        // Qualified queries
+        assert!(mode.matches("gfx::Color", "Color", Some("gfx")));
+        assert!(mode.matches("Palette::Color", "Color", Some("gfx::Palette")));
+        assert!(!mode.matches("ui::Color", "Color", Some("gfx")));
+        assert!(mode.matches("::gfx::Color", "Color", Some("gfx")));
+        assert!(!mode.matches("::Palette::Color", "Color", Some("gfx::Palette")));
+        assert!(mode.matches("::main", "main", None));
+
+        // Out-of-line definitions carry their class in the name
+        assert!(mode.matches("add", "Calculator::add", Some("app")));
+        assert!(mode.matches("app::Calculator::add", "Calculator::add", Some("app")));
+
+        // Empty query lists everything
+        assert!(mode.matches("", "anything", None));
+    }
+
+    #[test]
+    fn test_name_match_mode_parse() {
+        assert_eq!(NameMatchMode::try_from("fuzzy"), Ok(NameMatchMode::Fuzzy));
+        assert_eq!(NameMatchMode::try_from("exact"), Ok(NameMatchMode::Exact));
+        assert_eq!(
+            NameMatchMode::try_from("case_sensitive_substring"),
+            Ok(NameMatchMode::CaseSensitiveSubstring)
+        );
+        assert!(NameMatchMode::try_from("regex").is_err());
+
+        let mode = NameMatchMode::CaseSensitiveSubstring;
+        assert!(mode.matches("Color", "ColorSpace", None));
+        assert!(!mode.matches("Color", "color_space", None));
+    }
+
     #[test]
     fn test_workspace_symbol_search_builder() {
         let builder = WorkspaceSymbolSearchBuilder::new("test".to_string())
             .with_kinds(vec![lsp_types::SymbolKind::CLASS])
             .with_max_results(10)
             .include_external(true)
-            .case_sensitive(false);
+            .with_match_mode(NameMatchMode::Exact);
 
         assert_eq!(builder.query, "test");
         assert_eq!(builder.kinds, Some(vec![lsp_types::SymbolKind::CLASS]));
         assert_eq!(builder.max_results, Some(10));
         assert!(builder.include_external);
-        assert!(!builder.case_sensitive);
+        assert_eq!(builder.match_mode, NameMatchMode::Exact);
     }
 
     #[test]
@@ -622,4 +846,44 @@ mod tests {
                 || filter.canonical_source_root.ends_with("test/project")
         );
     }
+
+    #[test]
+    fn test_external_scopes_filter() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_file = temp_dir.path().join("main.cpp");
+        std::fs::write(&project_file, "").unwrap();
+        let mut component = create_test_component();
+        component.source_root_path = temp_dir.path().to_path_buf();
+        let project_uri = format!("file://{}", project_file.canonicalize().unwrap().display());
+
+        let filter = ProjectBoundaryFilter::new(&component, true)
+            .with_external_scopes(&["std::".to_string(), "::boost::asio".to_string()]);
+        let symbol = |name, container| {
+            create_test_workspace_symbol(
+                name,
+                SymbolKind::CLASS,
+                "file:///usr/include/c++/13/bits/stl_vector.h",
+                container,
+            )
+        };
+
+        assert!(filter.matches(&symbol("vector", Some("std"))));
+        assert!(filter.matches(&symbol("_Vector_base", Some("std::__detail"))));
+        assert!(filter.matches(&symbol("std", None)));
+        assert!(filter.matches(&symbol("io_context", Some("boost::asio"))));
+        assert!(!filter.matches(&symbol("__normal_iterator", Some("__gnu_cxx"))));
+        assert!(!filter.matches(&symbol("span", Some("stdx"))));
+        assert!(!filter.matches(&symbol("any", Some("boost"))));
+
+        // Project symbols are unaffected by the scopes
+        let project_symbol =
+            create_test_workspace_symbol("Widget", SymbolKind::CLASS, &project_uri, Some("app"));
+        assert!(filter.matches(&project_symbol));
+
+        // Scopes include matching external symbols without include_external
+        let filter = ProjectBoundaryFilter::new(&component, false)
+            .with_external_scopes(&["std".to_string()]);
+        assert!(filter.matches(&symbol("vector", Some("std"))));
+        assert!(!filter.matches(&symbol("__normal_iterator", Some("__gnu_cxx"))));
+    }
 }
@@ -3,11 +3,50 @@
 //! This module contains all the tools available through the MCP server,
 //! including symbol analysis, project analysis, and LSP helper functions.
 
+pub mod active_macros;
+pub mod analyze_edit;
 pub mod analyze_symbols;
+pub mod analyze_symbols_batch;
+pub mod ast;
+pub mod batch_hover;
+pub mod blast_radius;
+pub mod build_freshness;
+pub mod build_targets;
+pub mod clang_tidy;
+pub mod clangd_log;
+pub mod comment_markers;
+pub mod compile_coverage;
+pub mod cross_references;
+pub mod declarations;
+pub mod document_links;
+pub mod duplicate_definitions;
+pub mod enclosing_function;
+pub mod expression_type;
+pub mod find_overrides;
+pub mod format_style;
+pub mod function_metrics;
+pub mod header_includers;
+pub mod inactive_regions;
+pub mod include_graph;
+pub mod index_snapshot;
+pub mod index_status;
+pub mod list_symbols_by_kind;
 pub mod lsp_helpers;
+pub mod namespaces;
+pub mod operators;
 pub mod project_tools;
+pub mod reset_clangd;
+pub mod resolve_include;
+pub mod resolve_type_alias;
 pub mod search_symbols;
+pub mod selection_range;
+pub mod signature_diff;
+pub mod switch_source_header;
+pub mod symbol_exists;
+pub mod system_headers;
+pub mod test_locations;
 pub mod utils;
+pub mod warm_up;
 
 #[cfg(feature = "clangd-integration-tests")]
 pub mod tests;
@@ -0,0 +1,416 @@
+//! Namespace tree tool
+//!
+//! Reconstructs the namespace hierarchy of the project from the document
+//! symbols of its files, counting the symbols declared directly in each
+//! namespace. Gives an overview of the code structure before searching for
+//! individual symbols.
+
+use lsp_types::{DocumentSymbol, SymbolKind};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::propagate_source_overlay_blocking;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::document_symbols::get_document_symbols;
+use crate::mcp_server::tools::utils;
+use crate::project::include_graph::project_files;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::uri_from_pathbuf;
+
+/// Include depth followed to find the headers of the project
+const MAX_INCLUDE_DEPTH: u32 = 50;
+
+/// Maximum number of files in the include graph of one translation unit
+const MAX_INCLUDE_NODES: usize = 2000;
+
+/// Files walked unless max_files is given
+const DEFAULT_MAX_FILES: u32 = 500;
+
+#[mcp_tool(
+    name = "list_namespaces",
+    description = "Show the namespace structure of the project as a tree: which namespaces exist, how \
+                   they nest, and how many symbols each declares. Use it to orient in a large codebase \
+                   before searching for specific symbols.
+
+                   📂 FILES WALKED:
+                   • Translation units of the compilation database inside the project root and the
+                     project headers they include, as for list_symbols_by_kind
+                   • Files excluded from results by the project configuration are skipped
+
+                   📋 OUTPUT:
+                   • namespaces: Tree of namespaces with name, qualified_name, symbol_count (distinct
+                     names declared directly in the namespace, class members not counted),
+                     total_symbol_count (including nested namespaces), file_count and children
+                   • global_symbol_count: Symbols declared outside any namespace
+                   • Anonymous namespaces appear as \"(anonymous namespace)\"
+                   • files_scanned / total_files and files_truncated when max_files was reached
+
+                   INPUT REQUIREMENTS:
+                   • namespace: Optional qualified namespace (e.g. \"engine::render\") to show only its subtree
+                   • max_depth: Optional number - nesting levels returned (default: all); deeper
+                     namespaces still count in total_symbol_count
+                   • max_files: Optional number - files walked (default: 500)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ListNamespacesTool {
+    /// Qualified namespace whose subtree is returned, e.g. "engine::render". OPTIONAL
+    /// (default: all namespaces).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Number of nesting levels returned. OPTIONAL (default: unlimited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+
+    /// Maximum number of files walked. OPTIONAL (default: 500).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// A namespace and the namespaces nested in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceNode {
+    /// Innermost name, e.g. "render"
+    pub name: String,
+    /// Fully qualified name, e.g. "engine::render"
+    pub qualified_name: String,
+    /// Distinct symbol names declared directly in the namespace
+    pub symbol_count: u32,
+    /// Symbols of the namespace and all namespaces nested in it
+    pub total_symbol_count: u32,
+    /// Files opening the namespace
+    pub file_count: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<NamespaceNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NamespacesResult {
+    /// Namespace tree, or the subtree of the requested namespace
+    pub namespaces: Vec<NamespaceNode>,
+    /// Number of namespaces found, including those below max_depth
+    pub namespace_count: u32,
+    /// Distinct symbol names declared outside any namespace
+    pub global_symbol_count: u32,
+    /// Number of project files
+    pub total_files: usize,
+    /// Number of files walked
+    pub files_scanned: usize,
+    /// Whether max_files stopped the walk before the last file
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub files_truncated: bool,
+    /// Files clangd returned no document symbols for
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub failed_files: Vec<PathBuf>,
+}
+
+/// Symbols and files of one namespace, keyed by its qualified name segments
+#[derive(Debug, Default)]
+struct NamespaceStats {
+    symbol_names: HashSet<String>,
+    files: BTreeSet<PathBuf>,
+}
+
+type NamespaceMap = BTreeMap<Vec<String>, NamespaceStats>;
+
+/// Record the namespaces and directly declared symbols of a document
+///
+/// Symbols with qualified names are out-of-line definitions of symbols declared
+/// in their own scope, they are not counted again. A symbol declared in a header
+/// and defined in a source file, or overloaded, counts once.
+fn collect_namespaces(
+    symbols: &[DocumentSymbol],
+    scope: &[String],
+    file: &Path,
+    namespaces: &mut NamespaceMap,
+) {
+    for symbol in symbols {
+        if symbol.kind == SymbolKind::NAMESPACE {
+            // `namespace a::b {}` is reported as a single "a::b" symbol
+            let mut nested = scope.to_vec();
+            nested.extend(symbol.name.split("::").map(|s| s.trim().to_string()));
+            for depth in scope.len() + 1..=nested.len() {
+                namespaces.entry(nested[..depth].to_vec()).or_default();
+            }
+            if let Some(stats) = namespaces.get_mut(&nested) {
+                stats.files.insert(file.to_path_buf());
+            }
+            if let Some(children) = &symbol.children {
+                collect_namespaces(children, &nested, file, namespaces);
+            }
+        } else if !symbol.name.contains("::") {
+            namespaces
+                .entry(scope.to_vec())
+                .or_default()
+                .symbol_names
+                .insert(symbol.name.clone());
+        }
+    }
+}
+
+/// Build the nodes of the namespaces nested directly in `parent`
+///
+/// Nodes deeper than `max_depth` levels below the first call are counted but
+/// not returned.
+fn build_tree(
+    namespaces: &NamespaceMap,
+    parent: &[String],
+    max_depth: Option<u32>,
+) -> Vec<NamespaceNode> {
+    namespaces
+        .iter()
+        .filter(|(key, _)| key.len() == parent.len() + 1 && key.starts_with(parent))
+        .map(|(key, stats)| {
+            // Nested namespaces are counted whether or not they are returned
+            let total_symbol_count = namespaces
+                .iter()
+                .filter(|(nested, _)| nested.starts_with(key))
+                .map(|(_, nested)| nested.symbol_names.len() as u32)
+                .sum();
+            let children = match max_depth {
+                Some(depth) if depth <= 1 => Vec::new(),
+                depth => build_tree(namespaces, key, depth.map(|depth| depth - 1)),
+            };
+            NamespaceNode {
+                name: key[key.len() - 1].clone(),
+                qualified_name: key.join("::"),
+                symbol_count: stats.symbol_names.len() as u32,
+                total_symbol_count,
+                file_count: stats.files.len() as u32,
+                children,
+            }
+        })
+        .collect()
+}
+
+impl ListNamespacesTool {
+    #[instrument(name = "list_namespaces", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let max_files = self.max_files.unwrap_or(DEFAULT_MAX_FILES).max(1) as usize;
+
+        let entries = component_session.compilation_database().entries().to_vec();
+        let project_root = workspace
+            .project_root_path
+            .canonicalize()
+            .unwrap_or_else(|_| workspace.project_root_path.clone());
+        // Reads every header of every translation unit; keep it off the async workers
+        let mut files = tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+            project_files(
+                &entries,
+                &project_root,
+                MAX_INCLUDE_DEPTH,
+                MAX_INCLUDE_NODES,
+            )
+        }))
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+        files.retain(|file| !workspace.result_exclusions.is_excluded(file));
+        info!(
+            "Collecting namespaces from {} of {} files",
+            files.len().min(max_files),
+            files.len()
+        );
+
+        let mut namespaces = NamespaceMap::new();
+        let mut failed_files = Vec::new();
+        for file in files.iter().take(max_files) {
+            match get_document_symbols(&component_session, uri_from_pathbuf(file)).await {
+                Ok(document_symbols) => {
+                    collect_namespaces(&document_symbols, &[], file, &mut namespaces)
+                }
+                Err(e) => {
+                    warn!("No document symbols for {}: {}", file.display(), e);
+                    failed_files.push(file.clone());
+                }
+            }
+        }
+
+        let global_symbol_count = namespaces
+            .get(&Vec::new())
+            .map_or(0, |stats| stats.symbol_names.len() as u32);
+        let namespace_count = namespaces.keys().filter(|key| !key.is_empty()).count() as u32;
+
+        let tree = match self.namespace.as_deref() {
+            None => build_tree(&namespaces, &[], self.max_depth),
+            Some(namespace) => {
+                let key: Vec<String> = namespace
+                    .trim_start_matches("::")
+                    .split("::")
+                    .map(|s| s.trim().to_string())
+                    .collect();
+                let parent = &key[..key.len() - 1];
+                let node = build_tree(&namespaces, parent, self.max_depth)
+                    .into_iter()
+                    .find(|node| node.name == key[key.len() - 1])
+                    .ok_or_else(|| {
+                        CallToolError::new(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("Namespace '{}' not found in the project", namespace),
+                        ))
+                    })?;
+                vec![node]
+            }
+        };
+
+        let result = NamespacesResult {
+            namespaces: tree,
+            namespace_count,
+            global_symbol_count,
+            total_files: files.len(),
+            files_scanned: files.len().min(max_files),
+            files_truncated: files.len() > max_files,
+            failed_files,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, symbol, tool_result};
+
+    #[tokio::test]
+    async fn test_namespace_tree_with_symbol_counts() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/render.cpp", "#include \"render.h\"\n"),
+            ("src/render.h", ""),
+        ]);
+        let server = MockClangd::new();
+        server.respond_with("textDocument/documentSymbol", |params| {
+            let uri = params.unwrap()["textDocument"]["uri"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            if uri.ends_with("render.h") {
+                serde_json::json!([
+                    symbol(
+                        "engine",
+                        3,
+                        0,
+                        serde_json::json!([
+                            symbol("init", 12, 1, serde_json::json!([])),
+                            symbol(
+                                "render",
+                                3,
+                                2,
+                                serde_json::json!([
+                                    symbol(
+                                        "Renderer",
+                                        5,
+                                        3,
+                                        serde_json::json!([symbol(
+                                            "draw",
+                                            6,
+                                            4,
+                                            serde_json::json!([])
+                                        )])
+                                    ),
+                                    symbol("draw_all", 12, 5, serde_json::json!([])),
+                                ])
+                            ),
+                        ])
+                    ),
+                    symbol("main_loop", 12, 8, serde_json::json!([])),
+                ])
+            } else {
+                // Definitions of the header's declarations
+                serde_json::json!([
+                    symbol(
+                        "engine::render",
+                        3,
+                        1,
+                        serde_json::json!([
+                            symbol("draw_all", 12, 2, serde_json::json!([])),
+                            symbol("Renderer::draw", 6, 3, serde_json::json!([])),
+                        ])
+                    ),
+                    symbol(
+                        "(anonymous namespace)",
+                        3,
+                        5,
+                        serde_json::json!([symbol("scratch", 13, 6, serde_json::json!([]))])
+                    ),
+                ])
+            }
+        });
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let list = |arguments: serde_json::Value| {
+            let tool: ListNamespacesTool = serde_json::from_value(arguments).unwrap();
+            let component_session = Arc::clone(&component_session);
+            let workspace = &workspace;
+            async move {
+                let result = tool.call_tool(component_session, workspace).await?;
+                Ok::<_, CallToolError>(tool_result::<NamespacesResult>(&result))
+            }
+        };
+
+        let result = list(serde_json::json!({})).await.unwrap();
+        assert_eq!(result.namespace_count, 3);
+        assert_eq!(result.global_symbol_count, 1);
+        assert_eq!(result.files_scanned, 2);
+        let names: Vec<_> = result.namespaces.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["(anonymous namespace)", "engine"]);
+
+        let engine = &result.namespaces[1];
+        assert_eq!(engine.symbol_count, 1);
+        assert_eq!(engine.total_symbol_count, 3);
+        let render = &engine.children[0];
+        assert_eq!(render.qualified_name, "engine::render");
+        // Renderer and draw_all, declared in the header and defined in the source
+        assert_eq!(render.symbol_count, 2);
+        assert_eq!(render.file_count, 2);
+
+        let result = list(serde_json::json!({"namespace": "engine", "max_depth": 1}))
+            .await
+            .unwrap();
+        assert_eq!(result.namespaces.len(), 1);
+        assert_eq!(result.namespaces[0].qualified_name, "engine");
+        assert!(result.namespaces[0].children.is_empty());
+        assert_eq!(result.namespaces[0].total_symbol_count, 3);
+
+        let result = list(serde_json::json!({"namespace": "engine::render"}))
+            .await
+            .unwrap();
+        assert_eq!(result.namespaces[0].name, "render");
+
+        assert!(
+            list(serde_json::json!({"namespace": "physics"}))
+                .await
+                .is_err()
+        );
+    }
+}
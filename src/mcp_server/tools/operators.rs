@@ -0,0 +1,565 @@
+//! Operator overload lookup tool
+//!
+//! `workspace/symbol` matches names fuzzily and operators such as `operator+`
+//! or `operator[]` are barely searchable that way. This tool finds them
+//! structurally instead: at a use site, clangd resolves the operator the
+//! compiler picked; for a type, the document symbols of the type's definition
+//! file are searched for its member operators and the free operators taking it.
+
+use lsp_types::{DocumentSymbol, SymbolKind};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::{get_declarations, get_definitions};
+use crate::mcp_server::tools::lsp_helpers::document_symbols::get_document_symbols;
+use crate::mcp_server::tools::lsp_helpers::symbol_resolution::get_matching_symbol;
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+#[mcp_tool(
+    name = "find_operator",
+    description = "Find the definition of an overloaded C++ operator (operator+, operator[], \
+                   operator<<, operator==, conversion operators, ...), which name-based symbol \
+                   search cannot find reliably. Either give the type and the operator token, or \
+                   the location of the operator at a use site.
+
+                   🎯 PRIMARY USE CASES:
+                   Inspecting what `a + b` or `out << value` does for a class • Listing the \
+                   comparison or stream operators of a type • Jumping from an operator use to \
+                   the overload the compiler picked
+
+                   📋 OUTPUT:
+                   • operators: Matching overloads, each with
+                     - name / qualified_name: e.g. \"operator+\", \"math::Vec3::operator+\"
+                     - signature: Type signature reported by clangd (e.g. \"Vec3 (const Vec3 &) const\")
+                     - scope: \"member\" or \"free\" (non-member, including friends defined outside the class)
+                     - declaration / definition: Locations (1-based); definition is absent when \
+                       clangd knows none (e.g. defaulted or library operators)
+
+                   INPUT REQUIREMENTS (one of):
+                   • type_name + operator: Type, preferably qualified (e.g. \"math::Vec3\"), and \
+                     operator token (e.g. \"+\", \"[]\", \"<<\", \"==\", \"()\", \"bool\")
+                   • use_site: Location of the operator token where it is used \
+                     (format: \"/path/file.cpp:line:column\"); type_name and operator then \
+                     optional
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the use site's file to be parsed when use_site is given (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindOperatorTool {
+    /// Type whose operator is looked for. Member operators of the type and
+    /// free operators taking it as a parameter are returned.
+    ///
+    /// EXAMPLES: "Vec3", "math::Vec3", "BigInt"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+
+    /// Operator token, with or without the `operator` keyword. Type names
+    /// select conversion operators.
+    ///
+    /// EXAMPLES: "+", "[]", "<<", "==", "()", "operator<=>", "bool"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+
+    /// Location of the operator token at a use, e.g. the `+` of `a + b`. OPTIONAL.
+    ///
+    /// FORMAT: "/absolute/path/to/file.cpp:line:column" with 1-based line/column numbers
+    ///
+    /// BEHAVIOR: When specified, the overload clangd resolves at this location is
+    /// returned and type_name/operator are not needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_site: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the use
+    /// site's file to be parsed (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// Whether an operator is a class member or a free function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorScope {
+    Member,
+    Free,
+}
+
+/// An operator overload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorDefinition {
+    /// Operator name as reported by clangd, e.g. "operator+"
+    pub name: String,
+    /// Name qualified with enclosing namespaces and classes
+    pub qualified_name: String,
+    /// Type signature reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    pub scope: OperatorScope,
+    pub declaration: FileLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<FileLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperatorResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    /// Requested operator, normalized (e.g. "operator[]")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_site: Option<String>,
+    pub operators: Vec<OperatorDefinition>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+/// An operator document symbol with its context
+struct OperatorSymbol<'a> {
+    symbol: &'a DocumentSymbol,
+    /// Names of the enclosing namespaces and classes
+    path: Vec<String>,
+    /// Class the operator is a member of, from the enclosing class or an
+    /// out-of-line qualified name (`Vec3::operator+`)
+    owner: Option<String>,
+}
+
+impl OperatorSymbol<'_> {
+    fn qualified_name(&self) -> String {
+        let mut parts = self.path.clone();
+        parts.push(self.symbol.name.clone());
+        parts.join("::")
+    }
+}
+
+/// Operator token of an operator name without the `operator` keyword and
+/// whitespace, e.g. "[]" for "operator []" or "bool" for "operator bool"
+///
+/// `None` for names that are not operators.
+fn operator_token(name: &str) -> Option<String> {
+    let token = name.trim().strip_prefix("operator")?;
+    // `operatorX` is an identifier, not an operator
+    if token.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let token: String = token.chars().filter(|c| !c.is_whitespace()).collect();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Normalize a requested operator: "+", "operator +" and "operator+" all give "+"
+fn requested_token(operator: &str) -> Option<String> {
+    let trimmed = operator.trim();
+    if trimmed.starts_with("operator") {
+        return operator_token(trimmed);
+    }
+    let token: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Split an out-of-line name such as `Vec3::operator+` into owner and operator
+fn split_owner(name: &str) -> (Option<&str>, &str) {
+    match name.find("operator") {
+        Some(start) if start > 0 => {
+            let owner = name[..start].trim_end_matches("::");
+            let owner = owner.rsplit("::").next().unwrap_or(owner);
+            (Some(owner), &name[start..])
+        }
+        _ => (None, name),
+    }
+}
+
+/// Collect every operator in a document symbol tree
+fn collect_operators<'a>(
+    symbols: &'a [DocumentSymbol],
+    path: Vec<String>,
+    class: Option<&'a str>,
+    operators: &mut Vec<OperatorSymbol<'a>>,
+) {
+    for symbol in symbols {
+        let (qualifier, name) = split_owner(&symbol.name);
+        let is_callable = matches!(
+            symbol.kind,
+            SymbolKind::OPERATOR | SymbolKind::METHOD | SymbolKind::FUNCTION
+        );
+        if is_callable && operator_token(name).is_some() {
+            operators.push(OperatorSymbol {
+                symbol,
+                path: path.clone(),
+                owner: qualifier.or(class).map(str::to_string),
+            });
+        }
+
+        if let Some(children) = &symbol.children {
+            let mut child_path = path.clone();
+            child_path.push(symbol.name.clone());
+            let child_class = matches!(symbol.kind, SymbolKind::CLASS | SymbolKind::STRUCT)
+                .then_some(symbol.name.as_str());
+            collect_operators(children, child_path, child_class, operators);
+        }
+    }
+}
+
+/// Whether `text` mentions `name` as a whole identifier
+fn mentions_identifier(text: &str, name: &str) -> bool {
+    let is_identifier_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+    })
+}
+
+/// Whether a symbol's selection range covers a position
+fn names_position(symbol: &DocumentSymbol, location: &FileLocation) -> bool {
+    let position: lsp_types::Position = location.range.start.into();
+    symbol.selection_range.start <= position && position <= symbol.selection_range.end
+}
+
+impl FindOperatorTool {
+    /// Build the result entry of an operator declared at `declaration`
+    async fn describe(
+        operator: &OperatorSymbol<'_>,
+        declaration: FileLocation,
+        component_session: &ComponentSession,
+    ) -> Result<OperatorDefinition, CallToolError> {
+        let definition = get_definitions(&declaration, component_session)
+            .await
+            .map_err(CallToolError::from)?
+            .into_iter()
+            .next();
+        Ok(OperatorDefinition {
+            name: split_owner(&operator.symbol.name).1.to_string(),
+            qualified_name: operator.qualified_name(),
+            signature: operator.symbol.detail.clone(),
+            scope: if operator.owner.is_some() {
+                OperatorScope::Member
+            } else {
+                OperatorScope::Free
+            },
+            declaration,
+            definition,
+        })
+    }
+
+    /// Resolve the overload clangd picks at a use site
+    async fn resolve_use_site(
+        use_site: &str,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<Vec<OperatorDefinition>, CallToolError> {
+        let location = utils::parse_location_hint(workspace, use_site)?;
+
+        // The declaration carries the enclosing class, unlike an out-of-line definition
+        let declarations = get_declarations(&location, component_session)
+            .await
+            .map_err(CallToolError::from)?;
+        let mut operators = Vec::new();
+        for declaration in declarations {
+            let document_symbols = get_document_symbols(component_session, declaration.get_uri())
+                .await
+                .map_err(CallToolError::from)?;
+            let mut candidates = Vec::new();
+            collect_operators(&document_symbols, Vec::new(), None, &mut candidates);
+            if let Some(operator) = candidates
+                .iter()
+                .find(|candidate| names_position(candidate.symbol, &declaration))
+            {
+                operators.push(Self::describe(operator, declaration, component_session).await?);
+            }
+        }
+
+        if operators.is_empty() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "No overloaded operator is used at {}: point use_site at the operator token \
+                     of an expression calling an overload; built-in operators have no definition",
+                    location.to_compact_range()
+                ),
+            )));
+        }
+        Ok(operators)
+    }
+
+    /// Find a type's member operators and the free operators taking it
+    async fn resolve_for_type(
+        type_name: &str,
+        token: &str,
+        component_session: &ComponentSession,
+    ) -> Result<Vec<OperatorDefinition>, CallToolError> {
+        let symbol = get_matching_symbol(type_name, component_session)
+            .await
+            .map_err(|err| {
+                error!("Failed to get matching workspace symbol: {}", err);
+                CallToolError::from(err)
+            })?;
+        let type_location = get_definitions(&symbol.location, component_session)
+            .await
+            .map_err(CallToolError::from)?
+            .into_iter()
+            .next()
+            .unwrap_or(symbol.location);
+
+        let document_symbols = get_document_symbols(component_session, type_location.get_uri())
+            .await
+            .map_err(CallToolError::from)?;
+        let mut candidates = Vec::new();
+        collect_operators(&document_symbols, Vec::new(), None, &mut candidates);
+
+        let simple_name = type_name.rsplit("::").next().unwrap_or(type_name).trim();
+        let mut operators = Vec::new();
+        for operator in candidates.iter().filter(|operator| {
+            let name = split_owner(&operator.symbol.name).1;
+            operator_token(name).as_deref() == Some(token)
+                && match &operator.owner {
+                    Some(owner) => owner == simple_name,
+                    None => operator
+                        .symbol
+                        .detail
+                        .as_deref()
+                        .is_some_and(|detail| mentions_identifier(detail, simple_name)),
+                }
+        }) {
+            let declaration = FileLocation {
+                file_path: type_location.file_path.clone(),
+                range: operator.symbol.selection_range.into(),
+            };
+            operators.push(Self::describe(operator, declaration, component_session).await?);
+        }
+        Ok(operators)
+    }
+
+    #[instrument(name = "find_operator", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Finding operator {:?} of type {:?}, use_site={:?}",
+            self.operator, self.type_name, self.use_site
+        );
+
+        let token = self.operator.as_deref().map(requested_token);
+        let invalid_input = |message: String| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        };
+        if let Some(None) = token {
+            return Err(invalid_input(format!(
+                "'{}' is not an operator, e.g. \"+\", \"[]\" or \"operator<<\"",
+                self.operator.as_deref().unwrap_or_default()
+            )));
+        }
+        let token = token.flatten();
+        if self.use_site.is_none() && (self.type_name.is_none() || token.is_none()) {
+            return Err(invalid_input(
+                "Either use_site, or type_name together with operator, is required".to_string(),
+            ));
+        }
+
+        let document_files: Option<Vec<_>> = self.use_site.as_ref().map(|site| {
+            utils::location_hint_file(workspace, site)
+                .into_iter()
+                .collect()
+        });
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if self.use_site.is_some() {
+                "Document-specific operator resolution"
+            } else {
+                "Workspace symbol resolution"
+            },
+        )
+        .await;
+
+        let operators = match (&self.use_site, &self.type_name, &token) {
+            (Some(use_site), _, _) => {
+                Self::resolve_use_site(use_site, &component_session, workspace).await?
+            }
+            (None, Some(type_name), Some(token)) => {
+                Self::resolve_for_type(type_name, token, &component_session).await?
+            }
+            _ => unreachable!("inputs validated above"),
+        };
+        info!("Found {} operator overloads", operators.len());
+
+        let result = OperatorResult {
+            type_name: self.type_name.clone(),
+            operator: token.map(|token| format!("operator{token}")),
+            use_site: self.use_site.clone(),
+            operators,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, range, tool_result};
+
+    const VEC3: &str = "namespace math {\n\
+                        struct Vec3 {\n\
+                        \x20 Vec3 operator+(const Vec3 &o) const;\n\
+                        \x20 float operator[](int i) const;\n\
+                        };\n\
+                        std::ostream &operator<<(std::ostream &out, const Vec3 &v);\n\
+                        }\n";
+
+    #[test]
+    fn test_operator_tokens() {
+        assert_eq!(requested_token("+").as_deref(), Some("+"));
+        assert_eq!(requested_token(" operator [] ").as_deref(), Some("[]"));
+        assert_eq!(requested_token("operator bool").as_deref(), Some("bool"));
+        assert_eq!(requested_token("operatorFoo"), None);
+        assert_eq!(requested_token("  "), None);
+        assert_eq!(operator_token("operator<<").as_deref(), Some("<<"));
+        assert_eq!(operator_token("operator_name"), None);
+        assert_eq!(
+            split_owner("math::Vec3::operator+"),
+            (Some("Vec3"), "operator+")
+        );
+        assert!(mentions_identifier(
+            "std::ostream &(std::ostream &, const Vec3 &)",
+            "Vec3"
+        ));
+        assert!(!mentions_identifier("Vec3f (const Vec3f &)", "Vec3"));
+    }
+
+    #[tokio::test]
+    async fn test_find_operator_for_type() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/vec3.h", VEC3)]);
+        let uri = crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join("src/vec3.h"))
+            .to_string();
+
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([{
+                "name": "Vec3", "kind": 23, "containerName": "math",
+                "location": {"uri": uri, "range": range(1, 7, 11)}
+            }]),
+        );
+        server.respond_with("textDocument/definition", {
+            let uri = uri.clone();
+            move |params| {
+                let position = &params.as_ref().unwrap()["position"];
+                serde_json::json!([{"uri": uri, "range": {"start": position, "end": position}}])
+            }
+        });
+        server.respond(
+            "textDocument/documentSymbol",
+            serde_json::json!([{
+                "name": "math", "kind": 3,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 6, "character": 1}},
+                "selectionRange": range(0, 10, 14),
+                "children": [
+                    {"name": "Vec3", "kind": 23,
+                     "range": {"start": {"line": 1, "character": 0}, "end": {"line": 4, "character": 2}},
+                     "selectionRange": range(1, 7, 11),
+                     "children": [
+                        {"name": "operator+", "kind": 25, "detail": "Vec3 (const Vec3 &) const",
+                         "range": range(2, 2, 38), "selectionRange": range(2, 7, 16)},
+                        {"name": "operator[]", "kind": 25, "detail": "float (int) const",
+                         "range": range(3, 2, 33), "selectionRange": range(3, 8, 18)}
+                     ]},
+                    {"name": "operator<<", "kind": 12,
+                     "detail": "std::ostream &(std::ostream &, const Vec3 &)",
+                     "range": range(5, 0, 58), "selectionRange": range(5, 14, 24)}
+                ]
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let call = |type_name: &str, operator: &str| {
+            let tool: FindOperatorTool = serde_json::from_value(serde_json::json!({
+                "type_name": type_name,
+                "operator": operator,
+                "wait_timeout": 0
+            }))
+            .unwrap();
+            let component_session = Arc::clone(&component_session);
+            let workspace = &workspace;
+            async move {
+                let result = tool.call_tool(component_session, workspace).await?;
+                Ok::<_, CallToolError>(tool_result::<OperatorResult>(&result))
+            }
+        };
+
+        let result = call("math::Vec3", "operator []").await.unwrap();
+        assert_eq!(result.operator.as_deref(), Some("operator[]"));
+        assert_eq!(result.operators.len(), 1);
+        let operator = &result.operators[0];
+        assert_eq!(operator.qualified_name, "math::Vec3::operator[]");
+        assert_eq!(operator.signature.as_deref(), Some("float (int) const"));
+        assert_eq!(operator.scope, OperatorScope::Member);
+        assert_eq!(operator.declaration.range.start.line, 3);
+        assert!(operator.definition.is_some());
+
+        let result = call("Vec3", "<<").await.unwrap();
+        assert_eq!(result.operators.len(), 1);
+        assert_eq!(result.operators[0].scope, OperatorScope::Free);
+        assert_eq!(result.operators[0].qualified_name, "math::operator<<");
+
+        assert!(call("Vec3", "-").await.unwrap().operators.is_empty());
+        assert!(call("Vec3", "operatorx").await.is_err());
+
+        // Project-relative use sites are looked up in the project
+        server.respond_with("textDocument/declaration", move |params| {
+            let params = params.as_ref().unwrap();
+            let position = &params["position"];
+            serde_json::json!([{
+                "uri": params["textDocument"]["uri"],
+                "range": {"start": position, "end": position}
+            }])
+        });
+        let tool: FindOperatorTool = serde_json::from_value(serde_json::json!({
+            "use_site": "src/vec3.h:4:9",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: OperatorResult = tool_result(&result);
+        assert_eq!(result.operators.len(), 1);
+        assert_eq!(result.operators[0].qualified_name, "math::Vec3::operator[]");
+        let params = server.received_params("textDocument/declaration")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["textDocument"]["uri"], uri);
+    }
+}
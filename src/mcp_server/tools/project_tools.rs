@@ -4,6 +4,7 @@ use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
 use tracing::{info, instrument};
 
+use super::build_freshness::component_freshness;
 use super::utils::serialize_result;
 use crate::project::ProjectWorkspace;
 
@@ -32,6 +33,9 @@ use crate::project::ProjectWorkspace;
                    🔧 MULTI-PROVIDER DISCOVERY:
                    • Automatic detection of CMake projects (CMakeLists.txt + build directories)
                    • Meson project support (meson.build + build configurations)
+                   • CMake presets (CMakePresets.json/CMakeUserPresets.json): configured build directories
+                     report their preset name, unconfigured preset build directories are listed in
+                     unconfigured_presets
                    • Extensible architecture ready for Bazel, Buck, xmake, and other build systems
                    • Unified component representation across all providers
 
@@ -53,6 +57,8 @@ use crate::project::ProjectWorkspace;
                    • Generator and build type information in standardized format
                    • Complete build options and configuration details
                    • Compilation database status for LSP integration
+                   • freshness: fresh, sources_changed, reconfigure_needed or unknown - whether the
+                     build directory is up to date with the sources (details: check_build_freshness)
 
                    🎯 PRIMARY USE CASES:
                    Project assessment • Build system inventory • LSP setup validation
@@ -142,7 +148,10 @@ impl GetProjectDetailsTool {
                 requested_depth
             );
 
-            Some(self.perform_fresh_scan(scan_root, requested_depth)?)
+            let mut fresh_workspace = self.perform_fresh_scan(scan_root, requested_depth)?;
+            fresh_workspace.global_compilation_database =
+                meta_project.global_compilation_database.clone();
+            Some(fresh_workspace)
         } else {
             // Use cached ProjectWorkspace
             info!("Using cached ProjectWorkspace scan results");
@@ -154,11 +163,19 @@ impl GetProjectDetailsTool {
         let include_details = self.include_details.unwrap_or(false);
 
         // Create appropriate view based on include_details flag
-        let view = if include_details {
+        let mut view = if include_details {
             effective_meta_project.get_full_view()
         } else {
             effective_meta_project.get_short_view()
         };
+        for (component_view, component) in view
+            .components
+            .iter_mut()
+            .zip(&effective_meta_project.components)
+        {
+            component_view.freshness =
+                Some(component_freshness(effective_meta_project, component).status);
+        }
 
         // Serialize the view
         let mut content = serde_json::to_value(&view).map_err(|e| {
@@ -183,7 +200,7 @@ impl GetProjectDetailsTool {
         );
 
         Ok(CallToolResult::text_content(vec![TextContent::from(
-            serialize_result(&content),
+            serialize_result(&content).unwrap_or_else(|e| format!("Error serializing result: {e}")),
         )]))
     }
 
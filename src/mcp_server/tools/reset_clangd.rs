@@ -0,0 +1,154 @@
+//! Clangd session reset tool
+//!
+//! Gives clients an explicit recovery action when clangd results look stale or
+//! wrong: the session of a build directory is shut down and started again from
+//! scratch without restarting the MCP server.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::clangd::version::ClangdVersion;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::ComponentSession;
+use crate::project::index::IndexStatusView;
+
+#[mcp_tool(
+    name = "reset_clangd",
+    description = "Restart the clangd session of a build directory. The running clangd process is shut \
+                   down gracefully and a fresh one is started, dropping open documents, cached \
+                   diagnostics and in-memory index state. The on-disk index is kept and reloaded.
+
+                   🎯 PRIMARY USE CASES:
+                   Recovering when results look stale or wrong • Picking up regenerated \
+                   compile_commands.json • Recovering from an unresponsive clangd
+
+                   📋 OUTPUT:
+                   • build_directory: Build directory whose session was restarted
+                   • previous_session_id: Id of the replaced session (absent if none was running)
+                   • session_id: Id of the new session - use it for subsequent calls
+                   • clangd_version: Version of the clangd running the new session
+                   • index_status: Indexing state of the new session
+
+                   INPUT REQUIREMENTS:
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • session_id: Optional - restart the build directory of this session instead"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ResetClangdTool {
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of the clangd session to restart. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetClangdResult {
+    /// Build directory whose session was restarted
+    pub build_directory: PathBuf,
+    /// Id of the replaced session, if one was running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_session_id: Option<String>,
+    /// Id of the new session
+    pub session_id: String,
+    /// Version of the clangd running the new session
+    pub clangd_version: String,
+    /// Indexing state of the new session
+    pub index_status: IndexStatusView,
+}
+
+impl ResetClangdTool {
+    #[instrument(name = "reset_clangd", skip(self, component_session, clangd_version))]
+    pub async fn call_tool(
+        &self,
+        previous_session_id: Option<String>,
+        component_session: Arc<ComponentSession>,
+        clangd_version: &ClangdVersion,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Clangd session for {} restarted: {:?} -> {}",
+            component_session.build_dir().display(),
+            previous_session_id,
+            component_session.session_id()
+        );
+
+        let result = ResetClangdResult {
+            build_directory: component_session.build_dir().clone(),
+            previous_session_id,
+            session_id: component_session.session_id().to_string(),
+            clangd_version: format!(
+                "{}.{}.{}",
+                clangd_version.major, clangd_version.minor, clangd_version.patch
+            ),
+            index_status: component_session.get_index_status().await,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_reset_reports_new_session() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let server = MockClangd::new();
+        let previous = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        previous.shutdown().await;
+        assert!(server.received_methods().iter().any(|m| m == "shutdown"));
+
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let clangd_version = ClangdVersion {
+            major: 18,
+            minor: 1,
+            patch: 8,
+            variant: None,
+            date: None,
+        };
+
+        let tool: ResetClangdTool = serde_json::from_str("{}").unwrap();
+        let result = tool
+            .call_tool(
+                Some(previous.session_id().to_string()),
+                component_session.clone(),
+                &clangd_version,
+            )
+            .await
+            .unwrap();
+        let reset: ResetClangdResult = tool_result(&result);
+        assert_eq!(
+            reset.previous_session_id.as_deref(),
+            Some(previous.session_id())
+        );
+        assert_eq!(reset.session_id, component_session.session_id());
+        assert_ne!(reset.session_id, previous.session_id());
+        assert_eq!(reset.clangd_version, "18.1.8");
+        assert_eq!(reset.index_status.total_files, 1);
+    }
+}
@@ -0,0 +1,253 @@
+//! Include directive resolution tool
+//!
+//! Explains how an `#include` directive resolves: the absolute header path when
+//! clangd can follow the include, otherwise the include directories that were
+//! searched according to the file's compile command.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::read_source_file;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::utils;
+use crate::project::include_paths::{IncludeDirective, IncludeDirectiveKind, IncludeSearchPaths};
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+#[mcp_tool(
+    name = "resolve_include",
+    description = "Diagnose how a C++ #include directive resolves. Given a source file and the line of an \
+                   #include, reports the absolute path of the header clangd resolved it to, or - when the \
+                   header cannot be found - the include directories that were searched, in lookup order, \
+                   as derived from the file's compile command.
+
+                   🎯 PRIMARY USE CASE:
+                   Diagnosing 'file not found' errors for #include \"foo.h\" / #include <foo.h>.
+
+                   📋 OUTPUT:
+                   • resolved: Whether clangd could follow the include
+                   • resolved_path: Absolute header path (when resolved)
+                   • search_paths: Directories searched with their origin flag (-I, -iquote, -isystem,
+                     -idirafter, or 'includer') and whether the header exists there (when unresolved)
+                   • compile_command_found: Whether the file has its own compile_commands.json entry
+                     (headers usually do not - clangd then infers flags from a related source file)
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file containing the #include (absolute or project-relative)
+                   • line: 1-based line number of the #include directive
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ResolveIncludeTool {
+    /// File containing the #include directive. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// 1-based line number of the #include directive within the file.
+    pub line: u32,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Include directory searched while resolving a directive
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchedPath {
+    /// Absolute include directory
+    pub path: PathBuf,
+    /// Flag that introduced the directory, or "includer"
+    pub origin: String,
+    /// Whether `path/<header>` exists on disk
+    pub candidate_exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IncludeResolutionResult {
+    /// File containing the directive
+    pub file: PathBuf,
+    /// 1-based line of the directive
+    pub line: u32,
+    /// Header name as written in the directive
+    pub header: String,
+    /// Delimiter style of the directive
+    pub kind: IncludeDirectiveKind,
+    /// Whether clangd resolved the include to a file
+    pub resolved: bool,
+    /// Absolute path of the resolved header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<PathBuf>,
+    /// Whether the file has its own compilation database entry
+    pub compile_command_found: bool,
+    /// Include directories searched, in lookup order (only when unresolved)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub search_paths: Vec<SearchedPath>,
+}
+
+impl ResolveIncludeTool {
+    /// Read the directive at the requested line
+    fn read_directive(&self, file_path: &Path) -> Result<IncludeDirective, CallToolError> {
+        if self.line == 0 {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Line numbers are 1-based (> 0)",
+            )));
+        }
+
+        let content = read_source_file(file_path).map_err(|e| {
+            CallToolError::new(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read '{}': {}", file_path.display(), e),
+            ))
+        })?;
+
+        let line_text = content.lines().nth(self.line as usize - 1).ok_or_else(|| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Line {} is past the end of '{}'",
+                    self.line,
+                    file_path.display()
+                ),
+            ))
+        })?;
+
+        IncludeDirective::parse(line_text).ok_or_else(|| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Line {} of '{}' is not an #include directive: {}",
+                    self.line,
+                    file_path.display(),
+                    line_text.trim()
+                ),
+            ))
+        })
+    }
+
+    #[instrument(name = "resolve_include", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        let directive = self.read_directive(&file_path)?;
+
+        info!(
+            "Resolving include '{}' at {}:{}",
+            directive.header,
+            file_path.display(),
+            self.line
+        );
+
+        // clangd answers go-to-definition on an include with the included file
+        let location: FileLocation = format!(
+            "{}:{}:{}",
+            file_path.display(),
+            self.line,
+            directive.header_column + 1
+        )
+        .parse()
+        .map_err(|e: String| CallToolError::new(std::io::Error::other(e)))?;
+
+        let resolved_path = match get_definitions(&location, &component_session).await {
+            Ok(definitions) => definitions
+                .into_iter()
+                .map(|definition| definition.file_path)
+                .find(|path| path != &file_path),
+            Err(err) => {
+                warn!("Definition lookup on include failed: {}", err);
+                None
+            }
+        };
+
+        let entry = component_session
+            .compilation_database()
+            .find_entry_for_file(&file_path);
+
+        let search_paths = if resolved_path.is_some() {
+            Vec::new()
+        } else {
+            let include_paths = entry
+                .map(IncludeSearchPaths::from_entry)
+                .unwrap_or_default();
+            include_paths
+                .search_order(directive.kind, file_path.parent())
+                .into_iter()
+                .map(|search_path| SearchedPath {
+                    candidate_exists: search_path.path.join(&directive.header).is_file(),
+                    path: search_path.path,
+                    origin: search_path.origin,
+                })
+                .collect()
+        };
+
+        let result = IncludeResolutionResult {
+            file: file_path,
+            line: self.line,
+            header: directive.header,
+            kind: directive.kind,
+            resolved: resolved_path.is_some(),
+            resolved_path,
+            compile_command_found: entry.is_some(),
+            search_paths,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_include_tool_deserialize() {
+        let json = r#"{"file": "src/main.cpp", "line": 3}"#;
+        let tool: ResolveIncludeTool = serde_json::from_str(json).unwrap();
+        assert_eq!(tool.file, "src/main.cpp");
+        assert_eq!(tool.line, 3);
+        assert!(tool.build_directory.is_none());
+    }
+
+    #[test]
+    fn test_read_directive_rejects_non_include_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("main.cpp");
+        std::fs::write(&file, "#include \"a.h\"\nint main() {}\n").unwrap();
+
+        let tool = ResolveIncludeTool {
+            file: file.to_string_lossy().to_string(),
+            line: 1,
+            build_directory: None,
+            session_id: None,
+        };
+        let directive = tool.read_directive(&file).unwrap();
+        assert_eq!(directive.header, "a.h");
+
+        let tool = ResolveIncludeTool { line: 2, ..tool };
+        assert!(tool.read_directive(&file).is_err());
+
+        let tool = ResolveIncludeTool { line: 0, ..tool };
+        assert!(tool.read_directive(&file).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Type alias resolution tool
+//!
+//! Follows `typedef` and `using` aliases through any number of levels down to
+//! the underlying type and reports each alias on the way together with the
+//! canonical (fully desugared) type.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::type_aliases::{
+    AliasStep, DEFAULT_ALIAS_DEPTH, MAX_ALIAS_DEPTH, resolve_alias_chain,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+#[mcp_tool(
+    name = "resolve_type_alias",
+    description = "Resolve a C++ typedef or using alias to the type it stands for. Follows alias chains \
+                   (an alias of an alias of ...) level by level and reports every alias on the way, the \
+                   underlying type with its declaration, and the canonical fully desugared type.
+
+                   🎯 PRIMARY USE CASES:
+                   Understanding what 'Buffer' or 'Handle' really is • Untangling template alias layers \
+                   • Finding the declaration behind an alias
+
+                   📋 OUTPUT:
+                   • is_alias: Whether the symbol is a typedef/using alias
+                   • chain: Aliases followed, outermost first (name, aliased type, location)
+                   • underlying_type: Type the innermost alias refers to, as written
+                   • canonical_type: Fully desugared type as reported by clangd (when it differs)
+                   • underlying_location: Declaration of the underlying type (absent for builtin types)
+                   • depth_limit_reached / cycle_detected: Why resolution stopped early, if it did
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required alias name (e.g. \"Buffer\", \"app::Handle\")
+                   • location_hint: Optional string - exact alias location (format: \"/path/file.cpp:line:column\")
+                   • max_depth: Optional number - maximum alias levels to follow (default: 8, max: 32)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted file to be parsed when location_hint is given (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct ResolveTypeAliasTool {
+    /// Name of the typedef or using alias to resolve.
+    ///
+    /// EXAMPLES: "Buffer", "app::Handle", "StringMap"
+    pub symbol: String,
+
+    /// Location of the alias name for precise selection. OPTIONAL.
+    ///
+    /// FORMAT: Compact LSP-style location string with 1-based line/column numbers:
+    /// • "/absolute/path/to/file.cpp:line:column"
+    ///
+    /// BEHAVIOR: When specified, the alias at this location is resolved and workspace
+    /// symbol search is skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Maximum number of alias levels to follow (default: 8, max: 32). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeAliasResult {
+    /// Symbol as requested
+    pub symbol: String,
+    /// Location the resolution started from
+    pub location: FileLocation,
+    /// Whether the symbol is a typedef/using alias
+    pub is_alias: bool,
+    /// Aliases followed, outermost first
+    pub chain: Vec<AliasStep>,
+    /// Type the innermost alias refers to, as written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_type: Option<String>,
+    /// Fully desugared type as reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_type: Option<String>,
+    /// Declaration of the underlying type (absent for builtin types)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying_location: Option<FileLocation>,
+    /// Whether max_depth stopped resolution while still inside an alias
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub depth_limit_reached: bool,
+    /// Whether the chain led back to an alias already visited
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle_detected: bool,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl ResolveTypeAliasTool {
+    /// Validate and clamp the requested alias depth
+    fn max_depth(&self) -> Result<u32, CallToolError> {
+        match self.max_depth {
+            None => Ok(DEFAULT_ALIAS_DEPTH),
+            Some(0) => Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "max_depth must be at least 1",
+            ))),
+            Some(depth) => Ok(depth.min(MAX_ALIAS_DEPTH)),
+        }
+    }
+
+    #[instrument(name = "resolve_type_alias", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Resolving type alias '{}', location_hint={:?}, max_depth={:?}",
+            self.symbol, self.location_hint, self.max_depth
+        );
+
+        let max_depth = self.max_depth()?;
+
+        let document_files: Option<Vec<_>> = self.location_hint.as_ref().map(|hint| {
+            utils::location_hint_file(workspace, hint)
+                .into_iter()
+                .collect()
+        });
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if self.location_hint.is_some() {
+                "Document-specific alias resolution"
+            } else {
+                "Workspace symbol resolution"
+            },
+        )
+        .await;
+
+        let location = utils::locate_symbol(
+            workspace,
+            &component_session,
+            &self.symbol,
+            self.location_hint.as_deref(),
+        )
+        .await?;
+        let resolution = resolve_alias_chain(&location, &component_session, max_depth)
+            .await
+            .map_err(CallToolError::from)?;
+
+        let result = TypeAliasResult {
+            symbol: self.symbol.clone(),
+            location,
+            is_alias: !resolution.chain.is_empty(),
+            chain: resolution.chain,
+            underlying_type: resolution.underlying_type,
+            canonical_type: resolution.canonical_type,
+            underlying_location: resolution.underlying_location,
+            depth_limit_reached: resolution.depth_limit_reached,
+            cycle_detected: resolution.cycle_detected,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, line_of, tool_result};
+
+    const SOURCE: &str = "#include \"vector.h\"\n\
+                          using Vec = std::vector<int>;\n\
+                          typedef Vec Buffer;\n";
+
+    fn alias_hover(header: &str, declaration: &str) -> serde_json::Value {
+        serde_json::json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("### type-alias `{}`\n\n---\n```cpp\n{}\n```", header, declaration)
+            }
+        })
+    }
+
+    #[test]
+    fn test_max_depth_validation() {
+        let mut tool: ResolveTypeAliasTool =
+            serde_json::from_str(r#"{"symbol": "Buffer"}"#).unwrap();
+        assert_eq!(tool.max_depth().unwrap(), DEFAULT_ALIAS_DEPTH);
+
+        tool.max_depth = Some(1000);
+        assert_eq!(tool.max_depth().unwrap(), MAX_ALIAS_DEPTH);
+
+        tool.max_depth = Some(0);
+        assert!(tool.max_depth().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias_chain_with_mock_clangd() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/main.cpp", SOURCE),
+            (
+                "src/vector.h",
+                "namespace std { template <class T> class vector {}; }\n",
+            ),
+        ]);
+        let file = workspace.project_root_path.join("src/main.cpp");
+        let header = workspace.project_root_path.join("src/vector.h");
+        let uri = crate::symbol::uri_from_pathbuf(&file).to_string();
+        let header_uri = crate::symbol::uri_from_pathbuf(&header).to_string();
+
+        let server = MockClangd::new();
+        // Hover content depends on which line is asked about
+        server.respond_with("textDocument/hover", |params| match line_of(&params) {
+            Some(2) => alias_hover("Buffer", "typedef Vec Buffer"),
+            Some(1) => alias_hover("Vec", "using Vec = std::vector<int>"),
+            _ => serde_json::json!({
+                "contents": {"kind": "markdown", "value": "### class `vector`"}
+            }),
+        });
+        let typedef_uri = uri.clone();
+        server.respond_with("textDocument/typeDefinition", move |params| {
+            match line_of(&params) {
+                Some(2) => serde_json::json!([{
+                    "uri": typedef_uri,
+                    "range": {"start": {"line": 1, "character": 6}, "end": {"line": 1, "character": 9}}
+                }]),
+                _ => serde_json::json!([{
+                    "uri": header_uri,
+                    "range": {"start": {"line": 0, "character": 40}, "end": {"line": 0, "character": 46}}
+                }]),
+            }
+        });
+
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let start: FileLocation = format!("{}:3:13", file.display()).parse().unwrap();
+        let resolution = resolve_alias_chain(&start, &component_session, 8)
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = resolution.chain.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Buffer", "Vec"]);
+        assert_eq!(
+            resolution.underlying_type.as_deref(),
+            Some("std::vector<int>")
+        );
+        assert_eq!(resolution.underlying_location.unwrap().file_path, header);
+        assert!(!resolution.depth_limit_reached);
+
+        // A depth of one stops inside the chain
+        let resolution = resolve_alias_chain(&start, &component_session, 1)
+            .await
+            .unwrap();
+        assert_eq!(resolution.chain.len(), 1);
+        assert!(resolution.depth_limit_reached);
+
+        // Project-relative hints are looked up in the project
+        let tool: ResolveTypeAliasTool = serde_json::from_value(serde_json::json!({
+            "symbol": "Buffer",
+            "location_hint": "src/main.cpp:3:13",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: TypeAliasResult = tool_result(&result);
+        assert_eq!(result.location.file_path, file);
+        assert_eq!(result.chain.len(), 2);
+    }
+}
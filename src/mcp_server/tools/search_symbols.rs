@@ -29,10 +29,12 @@ use std::sync::Arc;
 use tracing::{info, instrument};
 
 use crate::mcp_server::tools::lsp_helpers::document_symbols::SymbolSearchBuilder;
-use crate::mcp_server::tools::lsp_helpers::workspace_symbols::WorkspaceSymbolSearchBuilder;
-use crate::mcp_server::tools::utils;
+use crate::mcp_server::tools::lsp_helpers::workspace_symbols::{
+    NameMatchMode, WorkspaceSymbolSearchBuilder, WorkspaceSymbolSearchResult,
+};
+use crate::mcp_server::tools::utils::{self, DEFAULT_MAX_PATTERN_FILES, PatternMatch};
 use crate::project::index::IndexStatusView;
-use crate::project::{ComponentSession, ProjectComponent, ProjectWorkspace};
+use crate::project::{ComponentSession, ProjectComponent, ProjectWorkspace, ResultExclusions};
 use crate::symbol::Symbol;
 
 /// Search result structure for search_symbols tool
@@ -55,6 +57,19 @@ pub struct SearchMetadata {
     pub build_directory: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files_processed: Option<Vec<FileProcessingResult>>,
+    /// clangd returned as many symbols as its result limit allows, so more
+    /// matches may exist than reported
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub capped_by_clangd: bool,
+    /// clangd's workspace symbol result limit, reported when it was reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clangd_result_limit: Option<u32>,
+    /// Files matched by each glob pattern in `files`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_patterns: Vec<PatternMatch>,
+    /// Pattern matches beyond `max_files` were not searched
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub files_truncated: bool,
 }
 
 /// Result of processing a specific file during search
@@ -103,6 +118,9 @@ pub struct FileProcessingResult {
                    • Symbol kinds: Class, Function, Method, Variable, Enum, Namespace, Constructor, Field, Interface, Struct
                    • Project boundary detection (exclude external/system symbols by default)
                    • Fuzzy matching with clangd's relevance ranking preserved
+                   • match_mode for precise name matching: \"exact\" (e.g. Color but not color or
+                     ColorSpace) or \"case_sensitive_substring\". Workspace search still retrieves and
+                     ranks candidates with clangd's fuzzy matcher; the mode is applied on top of it
                    • Configurable result limits with smart client-side application
 
                    ⚡ PERFORMANCE & RELIABILITY:
@@ -110,6 +128,8 @@ pub struct FileProcessingResult {
                    • Indexing progress tracking with configurable timeout control
                    • Automatic build directory detection and validation
                    • Graceful handling of large codebases with intelligent result capping
+                   • metadata.capped_by_clangd (with metadata.clangd_result_limit) flags workspace
+                     searches truncated by clangd's own result limit - refine the query to see more
 
                    🏗️ BUILD SYSTEM INTEGRATION:
                    • Multi-provider support (CMake, Meson, extensible architecture)
@@ -121,19 +141,26 @@ pub struct FileProcessingResult {
                    • Discovery: search_symbols {\"query\": \"vector\", \"max_results\": 10}
                    • Type filtering: search_symbols {\"query\": \"Process\", \"kinds\": [\"Class\", \"Struct\"]}
                    • File overview: search_symbols {\"query\": \"\", \"files\": [\"include/api.h\"]}
+                   • Directory overview: search_symbols {\"query\": \"\", \"files\": [\"src/net/**/*.h\"]}
+                     → metadata.file_patterns lists the files each pattern matched
                    • PROJECT EXPLORATION: search_symbols {\"query\": \"\", \"max_results\": 100, \"build_directory\": \"/abs/path\"}
                      → Returns top symbols to understand what the project does (classes, main functions, key APIs)
                    • Workspace overview: search_symbols {\"query\": \"\", \"max_results\": 500} (limited by clangd)
                    • External symbols: search_symbols {\"query\": \"std::\", \"include_external\": true}
+                   • Scoped external symbols: search_symbols {\"query\": \"vector\", \"external_scopes\": [\"std\"]}
+                     → std:: symbols without __gnu_cxx:: or other implementation namespaces
 
                    INPUT PARAMETERS:
                    • query: C++ symbol name to search (NOT file paths!) - use \"\" when unsure to explore first
-                   • files: Optional file paths for document-specific search
+                   • files: Optional file paths or glob patterns (e.g. \"src/**/*.cpp\") for document-specific search
+                   • max_files: Maximum number of files glob patterns expand to (default: 100)
                    • kinds: Optional symbol type filtering (PascalCase names)
                    • max_results: Result limit (default: 100, max: 1000)
                    • include_external: Include system/library symbols (default: false)
+                   • external_scopes: Only include external symbols within these namespaces (e.g. [\"std\", \"boost::asio\"])
+                   • match_mode: Name matching - fuzzy (default), exact, case_sensitive_substring
                    • build_directory: Custom build directory path (STRONGLY PREFER ABSOLUTE PATHS from get_project_details)
-                   • wait_timeout: Indexing completion timeout in seconds (default: 20s)"
+                   • wait_timeout: Indexing completion timeout in seconds, or parse timeout of the given files for document search (default: 20s)"
 )]
 #[derive(Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
 pub struct SearchSymbolsTool {
@@ -158,10 +185,16 @@ pub struct SearchSymbolsTool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub kinds: Option<Vec<String>>,
 
-    /// Optional file paths to limit search scope
+    /// Optional file paths to limit search scope. Entries containing `*`, `?`,
+    /// `[` or `{` are glob patterns matched against paths relative to the project
+    /// root, e.g. "src/**/*.cpp"; `*` does not cross directories.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<String>>,
 
+    /// Maximum number of files glob patterns in `files` expand to (default: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<u32>,
+
     /// Maximum number of results (default: 100, max: 1000)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_results: Option<u32>,
@@ -170,6 +203,28 @@ pub struct SearchSymbolsTool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub include_external: Option<bool>,
 
+    /// Namespaces or qualified-name prefixes of external symbols to include. OPTIONAL.
+    ///
+    /// External symbols are kept only when their qualified name lies within one of
+    /// these scopes: "std" (or "std::") matches std::vector and std::__detail::X but
+    /// not __gnu_cxx::__normal_iterator. Implies include_external. Project symbols
+    /// are not affected. Applies to workspace search only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_scopes: Option<Vec<String>>,
+
+    /// How symbol names are matched against the query. OPTIONAL (default: "fuzzy").
+    ///
+    /// VALUES:
+    /// • "fuzzy": Case-insensitive matching ranked by relevance
+    /// • "exact": Name equals the query, case-sensitive. Qualified queries such as
+    ///   "ns::Color" or "Palette::Color" match against the enclosing scopes
+    /// • "case_sensitive_substring": Name contains the query, case-sensitive
+    ///
+    /// NOTE: Workspace search always retrieves and ranks candidates with clangd's own
+    /// fuzzy matcher; exact and case-sensitive filtering is applied to those results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<String>,
+
     /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
     ///
     /// WORKFLOW:
@@ -183,7 +238,15 @@ pub struct SearchSymbolsTool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub build_directory: Option<String>,
 
-    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wait_timeout: Option<u64>,
 }
@@ -215,15 +278,80 @@ impl SearchSymbolsTool {
                 None
             };
 
+        if let Some(scopes) = &self.external_scopes
+            && scopes
+                .iter()
+                .any(|scope| scope.trim_matches(':').is_empty())
+        {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "external_scopes entries must name a namespace or prefix",
+            )));
+        }
+
+        let match_mode = match self.match_mode.as_deref() {
+            None => NameMatchMode::default(),
+            Some(mode) => NameMatchMode::try_from(mode).map_err(|e| {
+                CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+            })?,
+        };
+
         info!(
-            "Searching symbols (v2): query='{}', kinds={:?}, max_results={:?}, wait_timeout={:?}",
-            self.query, symbol_kinds, self.max_results, self.wait_timeout
+            "Searching symbols (v2): query='{}', kinds={:?}, match_mode={:?}, max_results={:?}, wait_timeout={:?}",
+            self.query, symbol_kinds, match_mode, self.max_results, self.wait_timeout
         );
 
-        // Selective indexing wait logic based on search type
+        // Get the component for this session's build directory
+        let build_dir = component_session.build_dir();
+        let component = workspace
+            .get_component_by_build_dir(build_dir)
+            .ok_or_else(|| {
+                CallToolError::new(std::io::Error::other(
+                    "Build directory not found in workspace",
+                ))
+            })?;
+
+        // Replace glob patterns in `files` by the files they match
+        let expansion = match &self.files {
+            Some(files) if files.iter().any(|file| utils::is_file_pattern(file)) => {
+                let files = files.clone();
+                let root = component.source_root_path.clone();
+                let max_files = self
+                    .max_files
+                    .map_or(DEFAULT_MAX_PATTERN_FILES, |max| max as usize);
+                let expansion = tokio::task::spawn_blocking(move || {
+                    utils::expand_file_patterns(&files, &root, max_files)
+                })
+                .await
+                .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?
+                .map_err(|e| {
+                    CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+                })?;
+                info!(
+                    "Expanded file patterns to {} files (truncated: {})",
+                    expansion.files.len(),
+                    expansion.truncated
+                );
+                Some(expansion)
+            }
+            _ => None,
+        };
+        let files = match &expansion {
+            Some(expansion) => Some(&expansion.files),
+            None => self.files.as_ref(),
+        };
+
+        // Selective indexing wait logic based on search type: document search
+        // (files specified) only waits for the files to be parsed
+        let document_files: Option<Vec<_>> = files.map(|files| {
+            files
+                .iter()
+                .map(|file| component.source_root_path.join(file))
+                .collect()
+        });
         let index_status = utils::handle_selective_indexing_wait(
             &component_session,
-            self.files.is_some(), // Skip indexing for document search (files specified)
+            document_files.as_deref(),
             self.wait_timeout,
             if self.files.is_some() {
                 "Document search"
@@ -233,33 +361,39 @@ impl SearchSymbolsTool {
         )
         .await;
 
-        // Get the component for this session's build directory
-        let build_dir = component_session.build_dir();
-        let component = workspace
-            .get_component_by_build_dir(build_dir)
-            .ok_or_else(|| {
-                CallToolError::new(std::io::Error::other(
-                    "Build directory not found in workspace",
-                ))
-            })?;
-
         // Determine search scope and delegate to appropriate LSP method.
         // File-specific searches use textDocument/documentSymbol for precise results,
         // while workspace searches use workspace/symbol for broad discovery.
-        let mut result = if let Some(ref files) = self.files {
+        let mut result = if let Some(files) = files {
             // File-specific search using document symbols for targeted analysis
-            self.search_in_files(&component_session, files, component, symbol_kinds.as_ref())
-                .await?
+            self.search_in_files(
+                &component_session,
+                files,
+                component,
+                symbol_kinds.as_ref(),
+                match_mode,
+            )
+            .await?
         } else {
             // Workspace-wide search using workspace symbols for comprehensive discovery
-            self.search_workspace_symbols(&component_session, component, symbol_kinds.as_ref())
-                .await?
+            self.search_workspace_symbols(
+                &component_session,
+                component,
+                symbol_kinds.as_ref(),
+                match_mode,
+                &workspace.result_exclusions,
+            )
+            .await?
         };
 
         // Include index status if available
         result.index_status = index_status;
+        if let Some(expansion) = expansion {
+            result.metadata.file_patterns = expansion.patterns;
+            result.metadata.files_truncated = expansion.truncated;
+        }
 
-        let output = serde_json::to_string_pretty(&result).map_err(|e| {
+        let output = utils::serialize_result(&result).map_err(|e| {
             CallToolError::new(std::io::Error::other(format!(
                 "Failed to serialize result: {}",
                 e
@@ -277,10 +411,15 @@ impl SearchSymbolsTool {
         component_session: &ComponentSession,
         component: &ProjectComponent,
         symbol_kinds: Option<&Vec<lsp_types::SymbolKind>>,
+        match_mode: NameMatchMode,
+        exclusions: &ResultExclusions,
     ) -> Result<SearchResult, CallToolError> {
         // Build the search using the new helper's builder pattern
         let mut search_builder = WorkspaceSymbolSearchBuilder::new(self.query.clone())
-            .include_external(self.include_external.unwrap_or(false));
+            .include_external(self.include_external.unwrap_or(false))
+            .with_external_scopes(self.external_scopes.clone().unwrap_or_default())
+            .with_match_mode(match_mode)
+            .with_exclusions(exclusions.clone());
 
         // Add kind filtering if specified
         if let Some(kinds) = symbol_kinds {
@@ -292,9 +431,12 @@ impl SearchSymbolsTool {
             search_builder = search_builder.with_max_results(max);
         }
 
-        // Execute the search
-        let workspace_symbols = search_builder
-            .search(component_session, component)
+        // Execute the search, retrying an empty result right after indexing completed
+        let search = component_session
+            .retry_while_empty(
+                |search: &WorkspaceSymbolSearchResult| search.symbols.is_empty(),
+                || search_builder.search_with_limit(component_session, component),
+            )
             .await
             .map_err(|e| {
                 CallToolError::new(std::io::Error::other(format!(
@@ -304,7 +446,7 @@ impl SearchSymbolsTool {
             })?;
 
         // Convert WorkspaceSymbol to Symbol using the From trait
-        let symbols: Vec<Symbol> = workspace_symbols.into_iter().map(Symbol::from).collect();
+        let symbols: Vec<Symbol> = search.symbols.into_iter().map(Symbol::from).collect();
 
         Ok(SearchResult {
             success: true,
@@ -315,6 +457,10 @@ impl SearchSymbolsTool {
                 search_type: "workspace".to_string(),
                 build_directory: component.build_dir_path.display().to_string(),
                 files_processed: None,
+                capped_by_clangd: search.clangd_limit_reached.is_some(),
+                clangd_result_limit: search.clangd_limit_reached,
+                file_patterns: Vec::new(),
+                files_truncated: false,
             },
             index_status: None, // Will be set by caller
         })
@@ -327,6 +473,7 @@ impl SearchSymbolsTool {
         files: &[String],
         component: &ProjectComponent,
         symbol_kinds: Option<&Vec<lsp_types::SymbolKind>>,
+        match_mode: NameMatchMode,
     ) -> Result<SearchResult, CallToolError> {
         info!(
             "Document search: query='{}', files={:?}, kinds={:?}",
@@ -360,7 +507,7 @@ impl SearchSymbolsTool {
         info!("Resolved files: {:?}", absolute_files);
 
         // Build the search using the document symbols helper's builder pattern
-        let mut search_builder = SymbolSearchBuilder::new();
+        let mut search_builder = SymbolSearchBuilder::new().with_match_mode(match_mode);
 
         // Only add name filter if query is not empty - this allows listing all symbols in files
         if !self.query.is_empty() {
@@ -418,6 +565,10 @@ impl SearchSymbolsTool {
                 search_type: "file_specific".to_string(),
                 build_directory: component.build_dir_path.display().to_string(),
                 files_processed: Some(processed_files),
+                capped_by_clangd: false,
+                clangd_result_limit: None,
+                file_patterns: Vec::new(),
+                files_truncated: false,
             },
             index_status: None, // Will be set by caller
         })
@@ -457,4 +608,244 @@ mod tests {
         assert_eq!(tool.max_results, None);
         assert_eq!(tool.wait_timeout, None);
     }
+
+    #[tokio::test]
+    async fn test_workspace_search_with_mock_clangd() {
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/math.cpp", "int add(int a, int b) { return a + b; }\n")]);
+        let math_cpp = workspace.project_root_path.join("src/math.cpp");
+
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            json!([
+                {
+                    "name": "add",
+                    "kind": 12,
+                    "containerName": "",
+                    "location": {
+                        "uri": crate::symbol::uri_from_pathbuf(&math_cpp).as_str(),
+                        "range": {
+                            "start": {"line": 0, "character": 4},
+                            "end": {"line": 0, "character": 7}
+                        }
+                    }
+                },
+                {
+                    "name": "addressof",
+                    "kind": 12,
+                    "containerName": "std",
+                    "location": {
+                        "uri": "file:///usr/include/c++/13/bits/move.h",
+                        "range": {
+                            "start": {"line": 10, "character": 0},
+                            "end": {"line": 10, "character": 9}
+                        }
+                    }
+                }
+            ]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: SearchSymbolsTool = serde_json::from_value(json!({
+            "query": "add",
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+
+        let text = match &result.content[0] {
+            rust_mcp_sdk::schema::ContentBlock::TextContent(text) => text.text.clone(),
+            other => panic!("Unexpected content: {:?}", other),
+        };
+        let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(output["metadata"]["search_type"], "workspace");
+        assert_eq!(output["total_matches"], 1);
+        assert_eq!(output["symbols"][0]["name"], "add");
+        assert!(output["metadata"].get("capped_by_clangd").is_none());
+
+        let params = server.received_params("workspace/symbol");
+        assert_eq!(params.len(), 1);
+        assert_eq!(params[0].as_ref().unwrap()["query"], "add");
+    }
+
+    #[tokio::test]
+    async fn test_workspace_search_capped_by_clangd() {
+        use crate::clangd::config::CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT;
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[("src/gen.cpp", "int value0 = 0;\n")]);
+        let gen_cpp = workspace.project_root_path.join("src/gen.cpp");
+        let uri = crate::symbol::uri_from_pathbuf(&gen_cpp);
+
+        // The mock session runs without --limit-results, so clangd's own limit applies
+        let symbols: Vec<serde_json::Value> = (0..CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT)
+            .map(|i| {
+                json!({
+                    "name": format!("value{i}"),
+                    "kind": 13,
+                    "location": {
+                        "uri": uri.as_str(),
+                        "range": {
+                            "start": {"line": i, "character": 4},
+                            "end": {"line": i, "character": 9}
+                        }
+                    }
+                })
+            })
+            .collect();
+        let server = MockClangd::new();
+        server.respond("workspace/symbol", json!(symbols));
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: SearchSymbolsTool = serde_json::from_value(json!({
+            "query": "value",
+            "max_results": 10,
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+
+        let text = match &result.content[0] {
+            rust_mcp_sdk::schema::ContentBlock::TextContent(text) => text.text.clone(),
+            other => panic!("Unexpected content: {:?}", other),
+        };
+        let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(output["total_matches"], 10);
+        assert_eq!(output["metadata"]["capped_by_clangd"], true);
+        assert_eq!(
+            output["metadata"]["clangd_result_limit"],
+            CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_search_match_modes_with_mock_clangd() {
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/color.h",
+            "struct Color {};\nint color;\nstruct ColorSpace {};\n",
+        )]);
+
+        let symbol = |name: &str, kind: u32, line: u32| {
+            json!({
+                "name": name,
+                "kind": kind,
+                "range": {"start": {"line": line, "character": 0}, "end": {"line": line, "character": 20}},
+                "selectionRange": {"start": {"line": line, "character": 7}, "end": {"line": line, "character": 12}}
+            })
+        };
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            json!([
+                symbol("Color", 23, 0),
+                symbol("color", 13, 1),
+                symbol("ColorSpace", 23, 2)
+            ]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let search = |match_mode: &str| {
+            let tool: SearchSymbolsTool = serde_json::from_value(json!({
+                "query": "Color",
+                "files": ["src/color.h"],
+                "match_mode": match_mode
+            }))
+            .unwrap();
+            let component_session = component_session.clone();
+            let workspace = &workspace;
+            async move {
+                let result = tool.call_tool(component_session, workspace).await?;
+                let text = match &result.content[0] {
+                    rust_mcp_sdk::schema::ContentBlock::TextContent(text) => text.text.clone(),
+                    other => panic!("Unexpected content: {:?}", other),
+                };
+                let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let names: Vec<String> = output["symbols"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|s| s["name"].as_str().unwrap().to_string())
+                    .collect();
+                Ok::<_, CallToolError>(names)
+            }
+        };
+
+        assert_eq!(search("exact").await.unwrap(), vec!["Color"]);
+        assert_eq!(
+            search("case_sensitive_substring").await.unwrap(),
+            vec!["Color", "ColorSpace"]
+        );
+        assert_eq!(search("fuzzy").await.unwrap().len(), 3);
+        assert!(search("regex").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_search_expands_glob_patterns() {
+        use crate::clangd::testing::MockClangd;
+        use crate::clangd::testing::test_helpers::create_mock_project;
+
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/net/socket.cpp", "int open_socket();\n"),
+            ("src/net/dns.cpp", "int resolve();\n"),
+            ("src/main.cpp", "int main();\n"),
+        ]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/documentSymbol",
+            json!([{
+                "name": "function",
+                "kind": 12,
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 10}},
+                "selectionRange": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 8}}
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool: SearchSymbolsTool = serde_json::from_value(json!({
+            "query": "",
+            "files": ["src/net/*.cpp"],
+            "max_files": 1,
+            "wait_timeout": 0
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+
+        let text = match &result.content[0] {
+            rust_mcp_sdk::schema::ContentBlock::TextContent(text) => text.text.clone(),
+            other => panic!("Unexpected content: {:?}", other),
+        };
+        let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            output["metadata"]["file_patterns"],
+            json!([{"pattern": "src/net/*.cpp", "files": ["src/net/dns.cpp"]}])
+        );
+        assert_eq!(output["metadata"]["files_truncated"], true);
+        assert_eq!(
+            output["metadata"]["files_processed"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
 }
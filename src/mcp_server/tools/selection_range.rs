@@ -0,0 +1,247 @@
+//! Selection range tool
+//!
+//! Lists the syntactic ranges enclosing a position, from the innermost token
+//! outwards, so that agents can pick well-formed code spans (an expression, a
+//! statement, a block) instead of guessing where they begin and end.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::io::file_buffer::propagate_source_overlay_blocking;
+use crate::io::file_manager::RealFileBufferManager;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::selection_range::get_selection_ranges;
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, FileLocationWithContents};
+
+/// Spans longer than this many lines are reported without their text
+const MAX_TEXT_LINES: u32 = 50;
+
+#[mcp_tool(
+    name = "get_selection_range",
+    description = "List the nested syntactic ranges around a position in a C++ file, like an editor's \
+                   \"expand selection\": identifier, expression, statement, block, function and so \
+                   on, innermost first.
+
+                   🎯 PRIMARY USE CASES:
+                   Extracting a complete expression or statement • Finding where the enclosing block \
+                   or function begins and ends • Picking well-formed spans for refactoring edits
+
+                   📋 OUTPUT:
+                   • position: The requested position
+                   • document_version: LSP version of the file content the ranges refer to
+                   • ranges: Enclosing spans, innermost first, each with
+                     - location: \"/path/file.cpp:start_line:start_column-end_line:end_column\" (1-based, end exclusive)
+                     - line_count: Number of lines spanned
+                     - text: Source text of the span (omitted for spans over 50 lines, files over the context size limit, or with include_text=false)
+
+                   INPUT REQUIREMENTS:
+                   • location: Required position (format: \"/path/file.cpp:line:column\", 1-based;
+                     relative paths are resolved against the project root)
+                   • include_text: Optional boolean - include the source text of each span (default: true)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct SelectionRangeTool {
+    /// Position to expand from.
+    ///
+    /// FORMAT: "/path/to/file.cpp:line:column" with 1-based line/column numbers.
+    /// Relative paths are resolved against the project root.
+    pub location: String,
+
+    /// Include the source text of each span (default: true). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_text: Option<bool>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// A syntactic span enclosing the requested position
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SelectionSpan {
+    pub location: FileLocation,
+    pub line_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SelectionRangeResult {
+    pub position: FileLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_version: Option<i32>,
+    pub ranges: Vec<SelectionSpan>,
+}
+
+impl SelectionRangeTool {
+    #[instrument(name = "get_selection_range", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let mut position: FileLocation = self.location.parse().map_err(|e| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid location format '{}': {}", self.location, e),
+            ))
+        })?;
+        position.file_path = utils::resolve_project_path(workspace, &position.file_path);
+
+        info!(
+            "Getting selection ranges at {}",
+            position.to_compact_range()
+        );
+
+        let ranges = get_selection_ranges(&position, &component_session).await?;
+
+        let include_text = self.include_text.unwrap_or(true);
+        let position_encoding = component_session.position_encoding().await;
+        let max_file_size = workspace.max_context_file_size;
+        let file_path = position.file_path.clone();
+        // Span text is read from disk, so keep it off the async executor
+        let spans = tokio::task::spawn_blocking(propagate_source_overlay_blocking(move || {
+            let mut file_buffer_manager =
+                RealFileBufferManager::new_real().with_max_file_size(Some(max_file_size));
+            ranges
+                .into_iter()
+                .map(|range| {
+                    let location = FileLocation {
+                        file_path: file_path.clone(),
+                        range: range.into(),
+                    };
+                    let line_count = range.end.line - range.start.line + 1;
+                    let text = (include_text && line_count <= MAX_TEXT_LINES)
+                        .then(|| {
+                            FileLocationWithContents::new_from_location(
+                                &location,
+                                position_encoding,
+                                &mut file_buffer_manager,
+                            )
+                            .map_err(|e| {
+                                warn!(
+                                    "Failed to read text of {}: {}",
+                                    location.to_compact_range(),
+                                    e
+                                )
+                            })
+                            .ok()
+                        })
+                        .flatten()
+                        .map(|with_contents| with_contents.contents);
+                    SelectionSpan {
+                        location,
+                        line_count,
+                        text,
+                    }
+                })
+                .collect::<Vec<_>>()
+        }))
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+        let result = SelectionRangeResult {
+            document_version: component_session
+                .document_version(&position.file_path)
+                .await,
+            position,
+            ranges: spans,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_selection_ranges_with_text() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", "int f(int a) {\n  return a + 1;\n}\n")]);
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/selectionRange",
+            serde_json::json!([{
+                "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 10}},
+                "parent": {
+                    "range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 14}},
+                    "parent": {
+                        "range": {"start": {"line": 0, "character": 13}, "end": {"line": 2, "character": 1}}
+                    }
+                }
+            }]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let tool = SelectionRangeTool {
+            location: "src/main.cpp:2:10".to_string(),
+            include_text: None,
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool
+            .call_tool(component_session.clone(), &workspace)
+            .await
+            .unwrap();
+        let result: SelectionRangeResult = tool_result(&result);
+
+        let texts: Vec<Option<&str>> = result
+            .ranges
+            .iter()
+            .map(|span| span.text.as_deref())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![Some("a"), Some("a + 1"), Some("{\n  return a + 1;\n}")]
+        );
+        assert_eq!(result.ranges[2].line_count, 3);
+        assert_eq!(result.document_version, Some(1));
+
+        let params = server.received_params("textDocument/selectionRange")[0]
+            .clone()
+            .unwrap();
+        assert_eq!(params["positions"][0]["line"], 1);
+        assert_eq!(params["positions"][0]["character"], 9);
+
+        // Span text comes from an unsaved buffer the ranges were computed on
+        let buffers = [(
+            workspace.project_root_path.join("src/main.cpp"),
+            "int f(int b) {\n  return b * 2;\n}\n".to_string(),
+        )];
+        let result = crate::io::file_buffer::with_source_overlay(
+            buffers,
+            tool.call_tool(component_session, &workspace),
+        )
+        .await
+        .unwrap();
+        let result: SelectionRangeResult = tool_result(&result);
+        assert_eq!(result.ranges[0].text.as_deref(), Some("b"));
+        assert_eq!(result.ranges[1].text.as_deref(), Some("b * 2"));
+    }
+}
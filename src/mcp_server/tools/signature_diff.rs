@@ -0,0 +1,334 @@
+//! Signature diff tool
+//!
+//! Compares the signatures of two functions, or of a function and an expected
+//! declaration, part by part: return type, parameters, qualifiers and
+//! specifiers. Signatures are read from the declaration clangd shows on hover.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::hover::get_hover_info;
+use crate::mcp_server::tools::lsp_helpers::signatures::{
+    FunctionSignature, SignatureChange, diff_signatures, parse_hover_signature, parse_signature,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+#[mcp_tool(
+    name = "diff_signatures",
+    description = "Compare the signatures of two C++ functions or methods, or of a function and an \
+                   expected declaration, and report what differs: name, return type, parameter \
+                   count and types, default arguments, const/volatile/ref qualifiers, noexcept \
+                   and specifiers (virtual, static, explicit, override, = delete).
+
+                   🎯 PRIMARY USE CASES:
+                   API compatibility checks • Comparing an overload or override with its \
+                   counterpart • Checking a function still matches the declaration a caller or \
+                   another branch expects
+
+                   📋 OUTPUT:
+                   • first / second: Each parsed signature with where it came from
+                   • identical: true when nothing differs
+                   • breaking: true when calls written against the first signature may stop \
+                     compiling against the second
+                   • changes: One entry per difference with aspect, parameter (1-based), \
+                     before, after and breaking
+
+                   Types are compared as written, ignoring whitespace; typedefs are not expanded.
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required function name, preferably qualified (e.g. \"Parser::parse\")
+                   • location_hint: Optional string - exact function location (format: \"/path/file.cpp:line:column\")
+                   • Second signature, one of:
+                     - other_symbol and/or other_location_hint: Another function of the same build directory
+                     - expected_signature: A declaration, e.g. \"int parse(const std::string &text) const noexcept\"
+                       (use it with a signature obtained from another build directory or branch)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct DiffSignaturesTool {
+    /// Name of the first function or method.
+    ///
+    /// EXAMPLES: "Parser::parse", "utils::split", "Shape::area"
+    pub symbol: String,
+
+    /// Location of the first function's name for precise selection, e.g. of an
+    /// overload. OPTIONAL.
+    ///
+    /// FORMAT: "/absolute/path/to/file.cpp:line:column" with 1-based line/column numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Name of the second function or method. OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_symbol: Option<String>,
+
+    /// Location of the second function's name. OPTIONAL.
+    ///
+    /// FORMAT: "/absolute/path/to/file.cpp:line:column" with 1-based line/column numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_location_hint: Option<String>,
+
+    /// Declaration the first function is compared with, instead of a second
+    /// function. OPTIONAL.
+    ///
+    /// EXAMPLES: "int parse(const std::string &text) const noexcept",
+    /// "virtual double area() const = 0"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_signature: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A signature with its origin
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignatureSource {
+    /// Symbol as requested, or "expected" for expected_signature
+    pub source: String,
+    /// Location of the function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<FileLocation>,
+    pub signature: FunctionSignature,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignatureDiffResult {
+    pub first: SignatureSource,
+    pub second: SignatureSource,
+    /// Whether the signatures do not differ
+    pub identical: bool,
+    /// Whether any change may break calls written against the first signature
+    pub breaking: bool,
+    pub changes: Vec<SignatureChange>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl DiffSignaturesTool {
+    /// Signature of the function named by a symbol or location hint
+    async fn resolve_signature(
+        symbol: Option<&str>,
+        location_hint: Option<&str>,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<SignatureSource, CallToolError> {
+        if symbol.is_none() && location_hint.is_none() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Either other_symbol, other_location_hint or expected_signature is required",
+            )));
+        }
+        let location = utils::locate_symbol(
+            workspace,
+            component_session,
+            symbol.unwrap_or_default(),
+            location_hint,
+        )
+        .await?;
+        let hover = get_hover_info(&location, component_session)
+            .await
+            .map_err(CallToolError::from)?;
+        let source = symbol
+            .map(str::to_string)
+            .unwrap_or_else(|| location.to_compact_range());
+        let signature = parse_hover_signature(&hover).ok_or_else(|| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "No function signature found for '{}' at {}",
+                    source,
+                    location.to_compact_range()
+                ),
+            ))
+        })?;
+        Ok(SignatureSource {
+            source,
+            location: Some(location),
+            signature,
+        })
+    }
+
+    #[instrument(name = "diff_signatures", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: &ComponentSession,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Comparing signature of '{}' with other_symbol={:?}, other_location_hint={:?}, expected_signature={:?}",
+            self.symbol, self.other_symbol, self.other_location_hint, self.expected_signature
+        );
+
+        // Parse the expected declaration first so that typos fail before waiting
+        let expected = match &self.expected_signature {
+            Some(_) if self.other_symbol.is_some() || self.other_location_hint.is_some() => {
+                return Err(CallToolError::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "expected_signature cannot be combined with other_symbol or other_location_hint",
+                )));
+            }
+            Some(declaration) => Some(parse_signature(declaration).ok_or_else(|| {
+                CallToolError::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "expected_signature '{}' is not a function declaration, e.g. \
+                         \"int parse(const std::string &text) const\"",
+                        declaration
+                    ),
+                ))
+            })?),
+            None => None,
+        };
+
+        let index_status = utils::handle_selective_indexing_wait(
+            component_session,
+            None,
+            self.wait_timeout,
+            "Signature diff",
+        )
+        .await;
+
+        let first = Self::resolve_signature(
+            Some(&self.symbol),
+            self.location_hint.as_deref(),
+            component_session,
+            workspace,
+        )
+        .await?;
+        let second = match expected {
+            Some(signature) => SignatureSource {
+                source: "expected".to_string(),
+                location: None,
+                signature,
+            },
+            None => {
+                Self::resolve_signature(
+                    self.other_symbol.as_deref(),
+                    self.other_location_hint.as_deref(),
+                    component_session,
+                    workspace,
+                )
+                .await?
+            }
+        };
+
+        let changes = diff_signatures(&first.signature, &second.signature);
+        let result = SignatureDiffResult {
+            identical: changes.is_empty(),
+            breaking: changes.iter().any(|change| change.breaking),
+            changes,
+            first,
+            second,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::mcp_server::tools::lsp_helpers::signatures::SignatureAspect;
+
+    const SOURCE: &str = "int parse(const char *text, int flags) noexcept;\n\
+                          long parse(const char *text);\n";
+
+    #[tokio::test]
+    async fn test_diff_signatures_of_overloads_and_expected_declaration() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/parser.h", SOURCE)]);
+        let header = workspace.project_root_path.join("src/parser.h");
+
+        let server = MockClangd::new();
+        server.respond_with("textDocument/hover", |params| {
+            let value = match params.as_ref().unwrap()["position"]["line"].as_u64() {
+                Some(0) => {
+                    "### function `parse`\n\n---\n```cpp\nint parse(const char *text, int flags) noexcept\n```"
+                }
+                _ => "### function `parse`\n\n---\n```cpp\nlong parse(const char *text)\n```",
+            };
+            serde_json::json!({"contents": {"kind": "markdown", "value": value}})
+        });
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let diff = |other_location_hint: Option<String>, expected_signature: Option<&str>| {
+            let tool = DiffSignaturesTool {
+                symbol: "parse".to_string(),
+                location_hint: Some("src/parser.h:1:5".to_string()),
+                other_symbol: None,
+                other_location_hint,
+                expected_signature: expected_signature.map(str::to_string),
+                build_directory: None,
+                session_id: None,
+                wait_timeout: Some(0),
+            };
+            let (component_session, workspace) = (&component_session, &workspace);
+            async move {
+                let result = tool.call_tool(component_session, workspace).await?;
+                Ok::<SignatureDiffResult, CallToolError>(tool_result(&result))
+            }
+        };
+
+        let result = diff(Some(format!("{}:2:6", header.display())), None)
+            .await
+            .unwrap();
+        assert!(!result.identical);
+        assert!(result.breaking);
+        let aspects: Vec<_> = result.changes.iter().map(|change| change.aspect).collect();
+        assert_eq!(
+            aspects,
+            vec![
+                SignatureAspect::ReturnType,
+                SignatureAspect::ParameterRemoved,
+                SignatureAspect::Noexcept
+            ]
+        );
+        assert_eq!(result.changes[1].parameter, Some(2));
+        // The project-relative hint is looked up in the project
+        assert_eq!(result.first.location.unwrap().file_path, header);
+
+        let result = diff(
+            None,
+            Some("int parse(const char* input, int flags = 0) noexcept;"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.second.source, "expected");
+        assert!(!result.breaking);
+        assert_eq!(result.changes.len(), 2);
+
+        assert!(diff(None, Some("not a declaration")).await.is_err());
+        assert!(diff(None, None).await.is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Source/header switch tool
+//!
+//! Finds the counterpart of a file, the header declaring what a source file
+//! implements or the source implementing a header, with clangd's
+//! `textDocument/switchSourceHeader` extension.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::switch_source_header::get_source_header_counterpart;
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+#[mcp_tool(
+    name = "switch_source_header",
+    description = "Find the counterpart of a C++ file: the source file (.cpp, .cc...) implementing a \
+                   header, or the header (.h, .hpp...) declaring what a source file implements. Uses \
+                   clangd's switchSourceHeader extension, which consults the index as well as file names, \
+                   so pairs in different directories are found too.
+
+                   🎯 USE CASES:
+                   • Jumping from a declaration file to its implementation and back
+                   • Locating the implementation of a class whose header you are reading
+
+                   📋 OUTPUT:
+                   • file: The requested file
+                   • counterpart: Absolute path of the matching source or header, absent when none exists
+                   • message: Explanation when no counterpart was found
+
+                   INPUT REQUIREMENTS:
+                   • file: Source or header file (absolute or project-relative)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct SwitchSourceHeaderTool {
+    /// File to find the counterpart of. Absolute paths are used as-is,
+    /// relative paths are resolved against the project root.
+    pub file: String,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwitchSourceHeaderResult {
+    pub file: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterpart: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SwitchSourceHeaderTool {
+    #[instrument(
+        name = "switch_source_header",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = utils::resolve_project_path(workspace, &self.file);
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+
+        info!("Switching source/header of {}", file_path.display());
+
+        let counterpart = get_source_header_counterpart(&file_path, &component_session).await?;
+        let message = counterpart.is_none().then(|| {
+            format!(
+                "No counterpart found for {}: clangd knows no matching source or header",
+                file_path.display()
+            )
+        });
+
+        let result = SwitchSourceHeaderResult {
+            file: file_path,
+            counterpart,
+            message,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::symbol::uri_from_pathbuf;
+
+    async fn switch(server: &MockClangd, workspace: &ProjectWorkspace) -> SwitchSourceHeaderResult {
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool = SwitchSourceHeaderTool {
+            file: "src/widget.h".to_string(),
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool.call_tool(component_session, workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_counterpart_found() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/widget.h", "struct Widget {};\n"),
+            ("src/widget.cpp", "#include \"widget.h\"\n"),
+        ]);
+        let source = workspace.project_root_path.join("src/widget.cpp");
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/switchSourceHeader",
+            serde_json::json!(uri_from_pathbuf(&source).as_str()),
+        );
+
+        let result = switch(&server, &workspace).await;
+        assert_eq!(result.counterpart.as_deref(), Some(source.as_path()));
+        assert!(result.message.is_none());
+
+        let params = server.received_params("textDocument/switchSourceHeader");
+        let header = workspace.project_root_path.join("src/widget.h");
+        assert_eq!(
+            params[0].as_ref().unwrap()["uri"],
+            uri_from_pathbuf(&header).as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_counterpart() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/widget.h", "struct Widget {};\n")]);
+        // Unscripted: clangd answers null
+        let server = MockClangd::new();
+
+        let result = switch(&server, &workspace).await;
+        assert!(result.counterpart.is_none());
+        assert!(result.message.unwrap().starts_with("No counterpart found"));
+    }
+}
@@ -0,0 +1,277 @@
+//! Symbol existence check tool
+//!
+//! Runs only the `workspace/symbol` query of a symbol analysis, without hover,
+//! references or hierarchies, so that a guessed symbol name can be validated
+//! cheaply before asking for the full analysis.
+
+use lsp_types::SymbolKind;
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::objc_selectors::SelectorQuery;
+use crate::mcp_server::tools::lsp_helpers::suggestions::SymbolSuggestion;
+use crate::mcp_server::tools::lsp_helpers::symbol_resolution::get_matching_symbols;
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::{FileLocation, Symbol};
+
+/// Candidates reported unless max_candidates is given
+const DEFAULT_MAX_CANDIDATES: u32 = 20;
+
+#[mcp_tool(
+    name = "symbol_exists",
+    description = "Check whether a C++ symbol exists before analyzing it. Runs only clangd's workspace \
+                   symbol query - no hover, references or hierarchies - and reports the matching \
+                   candidates, so a guessed or misspelled name costs one cheap call instead of a \
+                   full analyze_symbol_context.
+
+                   📋 OUTPUT:
+                   • exists: Whether a candidate's name is the queried name (\"parse\" matches \
+                     \"json::parse\", qualified queries match the trailing part of the qualified name)
+                   • match_count: Number of symbols clangd matched, including fuzzy matches
+                   • exact_match_count: Number of those whose name is the queried name
+                   • candidates: Exact matches first, each with qualified_name, kind (LSP symbol kind), \
+                     location (1-based) and exact
+                   • suggestions: Similar symbol names when nothing matched at all
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required C++ symbol name, plain or qualified (e.g. \"Widget\", \"ui::Widget::draw\")
+                   • max_candidates: Optional number - candidates reported (default: 20)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct SymbolExistsTool {
+    /// The C++ symbol name to look for, plain or qualified.
+    ///
+    /// EXAMPLES: "Widget", "ui::Widget", "ui::Widget::draw", "-[MyView layout]"
+    pub symbol: String,
+
+    /// Maximum number of candidates reported. OPTIONAL (default: 20).
+    ///
+    /// match_count still counts all matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_candidates: Option<u32>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A symbol matching the query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolCandidate {
+    /// Name qualified with its container, e.g. "ui::Widget::draw"
+    pub qualified_name: String,
+    pub kind: SymbolKind,
+    pub location: FileLocation,
+    /// Whether the name is the queried name rather than a fuzzy match
+    pub exact: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SymbolExistsResult {
+    pub query: String,
+    pub exists: bool,
+    pub match_count: usize,
+    pub exact_match_count: usize,
+    pub candidates: Vec<SymbolCandidate>,
+
+    /// Similar symbols, when nothing matched the query
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub suggestions: Vec<SymbolSuggestion>,
+
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+/// Name of a symbol qualified with its container
+fn qualified_name(symbol: &Symbol) -> String {
+    match symbol.container_name.as_deref() {
+        Some(container)
+            if !container.is_empty() && !symbol.name.starts_with(&format!("{container}::")) =>
+        {
+            format!("{container}::{}", symbol.name)
+        }
+        _ => symbol.name.clone(),
+    }
+}
+
+/// Whether a qualified name is the queried name, ignoring the qualifiers the query leaves out
+fn is_exact_match(query: &str, qualified_name: &str) -> bool {
+    // Objective-C method queries were already narrowed by selector and class
+    if SelectorQuery::parse(query).is_some() {
+        return true;
+    }
+    let query = query.trim().trim_start_matches("::");
+    !query.is_empty()
+        && (qualified_name == query
+            || qualified_name
+                .strip_suffix(query)
+                .is_some_and(|prefix| prefix.ends_with("::")))
+}
+
+impl SymbolExistsTool {
+    #[instrument(name = "symbol_exists", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!("Checking whether symbol '{}' exists", self.symbol);
+
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            None,
+            self.wait_timeout,
+            "Symbol existence check",
+        )
+        .await;
+
+        let (symbols, suggestions) =
+            match get_matching_symbols(&self.symbol, &component_session).await {
+                Ok(symbols) => (symbols, Vec::new()),
+                Err(AnalyzerError::NoSymbols { suggestions, .. }) => (Vec::new(), suggestions),
+                Err(err) => return Err(CallToolError::from(err)),
+            };
+
+        let mut candidates: Vec<SymbolCandidate> = symbols
+            .iter()
+            .filter(|symbol| {
+                !workspace
+                    .result_exclusions
+                    .is_excluded(&symbol.location.file_path)
+            })
+            .map(|symbol| {
+                let qualified_name = qualified_name(symbol);
+                SymbolCandidate {
+                    exact: is_exact_match(&self.symbol, &qualified_name),
+                    qualified_name,
+                    kind: symbol.kind,
+                    location: symbol.location.clone(),
+                }
+            })
+            .collect();
+        // Stable sort keeps clangd's ranking within exact and fuzzy matches
+        candidates.sort_by_key(|candidate| !candidate.exact);
+
+        let match_count = candidates.len();
+        let exact_match_count = candidates.iter().filter(|c| c.exact).count();
+        info!(
+            "Found {} matches for '{}', {} exact",
+            match_count, self.symbol, exact_match_count
+        );
+        candidates.truncate(self.max_candidates.unwrap_or(DEFAULT_MAX_CANDIDATES) as usize);
+
+        let result = SymbolExistsResult {
+            query: self.symbol.clone(),
+            exists: exact_match_count > 0,
+            match_count,
+            exact_match_count,
+            candidates,
+            suggestions,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, location, tool_result};
+
+    #[test]
+    fn test_exact_match() {
+        assert!(is_exact_match("draw", "ui::Widget::draw"));
+        assert!(is_exact_match("Widget::draw", "ui::Widget::draw"));
+        assert!(is_exact_match("::ui::Widget::draw", "ui::Widget::draw"));
+        assert!(!is_exact_match("get::draw", "ui::Widget::draw"));
+        assert!(!is_exact_match("raw", "ui::Widget::draw"));
+        assert!(!is_exact_match("drawAll", "ui::Widget::draw"));
+    }
+
+    #[tokio::test]
+    async fn test_symbol_exists() {
+        let (_temp_dir, workspace) = create_mock_project(&[(
+            "src/widget.h",
+            "namespace ui {\nclass Widget {\n  void draw();\n  void drawAll();\n};\n}\n",
+        )]);
+        let uri =
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join("src/widget.h"))
+                .to_string();
+
+        let server = MockClangd::new();
+        server.respond(
+            "workspace/symbol",
+            serde_json::json!([
+                {"name": "drawAll", "kind": 6, "containerName": "ui::Widget", "location": location(&uri, 3, 7, 11)},
+                {"name": "draw", "kind": 6, "containerName": "ui::Widget", "location": location(&uri, 2, 7, 11)}
+            ]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let check = |symbol: &str| {
+            let tool: SymbolExistsTool = serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "wait_timeout": 0
+            }))
+            .unwrap();
+            let component_session = component_session.clone();
+            let workspace = workspace.clone();
+            async move {
+                let result = tool.call_tool(component_session, &workspace).await.unwrap();
+                tool_result::<SymbolExistsResult>(&result)
+            }
+        };
+
+        let result = check("Widget::draw").await;
+        assert!(result.exists);
+        assert_eq!(result.match_count, 2);
+        assert_eq!(result.exact_match_count, 1);
+        assert_eq!(result.candidates[0].qualified_name, "ui::Widget::draw");
+        assert_eq!(result.candidates[0].kind, SymbolKind::METHOD);
+        assert!(result.candidates[0].exact);
+        assert!(!result.candidates[1].exact);
+
+        // Fuzzy matches alone do not make the symbol exist
+        let result = check("drw").await;
+        assert!(!result.exists);
+        assert_eq!(result.match_count, 2);
+
+        // Nothing matched at all
+        server.respond("workspace/symbol", serde_json::json!([]));
+        let result = check("Gadget").await;
+        assert!(!result.exists);
+        assert_eq!(result.match_count, 0);
+        assert!(result.candidates.is_empty());
+    }
+}
@@ -0,0 +1,461 @@
+//! System header check tool
+//!
+//! Confirms that clangd finds the standard library and other system headers
+//! for a file. Cross compilers and embedded toolchains keep their system
+//! directories out of the compile command; clangd only learns them by running
+//! drivers allowed by `--query-driver`, and a driver that is not allowed or
+//! fails to run shows up as every system header missing.
+//!
+//! The check includes a canonical header in the file's in-memory content and
+//! asks clangd where it resolves, and lists the system directories the
+//! compiler driver reports.
+
+use globset::Glob;
+use lsp_types::TextDocumentContentChangeEvent;
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+use crate::clangd::session::ClangdSessionTrait;
+use crate::io::file_buffer::read_source_file_async;
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::definitions::get_definitions;
+use crate::mcp_server::tools::utils;
+use crate::project::include_paths::query_driver_include_paths;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::FileLocation;
+
+/// Header probed in C++ files
+const DEFAULT_CXX_PROBE_HEADER: &str = "vector";
+
+/// Header probed in C files
+const DEFAULT_C_PROBE_HEADER: &str = "stdio.h";
+
+#[mcp_tool(
+    name = "check_system_headers",
+    description = "Check that clangd resolves system headers (standard library, toolchain headers) for a \
+                   C++ file, and show where they come from. Intended for cross-compilation and embedded \
+                   toolchains, where clangd only finds system headers by querying the compiler driver \
+                   (--query-driver) and a misconfiguration makes every system header 'file not found'.
+
+                   🎯 USE CASES:
+                   • Diagnosing \"'vector' file not found\" and similar errors on every file
+                   • Confirming a --query-driver setting matches the project's cross compiler
+                   • Listing the system include directories the compiler uses
+
+                   📋 OUTPUT:
+                   • status: \"ok\" when the probe header resolves, otherwise \"system_headers_missing\"
+                   • probe: Header included for the check (default <vector>, <stdio.h> for C files),
+                     whether clangd resolved it and to which file
+                   • compiler: Compiler of the file's compile command
+                   • query_driver_globs: --query-driver globs clangd runs with
+                   • compiler_allowed: Whether the compiler matches one of the globs
+                   • system_include_paths: #include <...> search list reported by the compiler
+                   • driver_error: Why the compiler could not be queried
+                   • hint: Likely cause when system headers are missing
+
+                   The probe is added to clangd's in-memory copy of the file only; nothing is written to disk.
+
+                   INPUT REQUIREMENTS:
+                   • file: Optional source file (absolute or project-relative); defaults to the first
+                     compilation database entry
+                   • probe_header: Optional system header to check instead of the default
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct CheckSystemHeadersTool {
+    /// Source file to check. Absolute paths are used as-is, relative paths are
+    /// resolved against the project root. OPTIONAL: defaults to the first entry
+    /// of the compilation database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// System header to resolve, without angle brackets (e.g. "cstdint",
+    /// "stm32f4xx.h"). OPTIONAL: defaults to "vector", or "stdio.h" for C files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_header: Option<String>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Outcome of the system header check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemHeadersStatus {
+    Ok,
+    SystemHeadersMissing,
+}
+
+/// Resolution of the probe header
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeaderProbe {
+    /// Header name as included
+    pub header: String,
+    /// Whether clangd resolved the include to a file
+    pub resolved: bool,
+    /// File the include resolved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SystemHeadersResult {
+    pub file: PathBuf,
+    pub status: SystemHeadersStatus,
+    pub probe: HeaderProbe,
+    /// Whether the file has its own compilation database entry
+    pub compile_command_found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiler: Option<String>,
+    pub query_driver_globs: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiler_allowed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub system_include_paths: Vec<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CheckSystemHeadersTool {
+    /// Pick the file to check: the requested one or the first compiled file
+    fn resolve_file_path(
+        &self,
+        workspace: &ProjectWorkspace,
+        component_session: &ComponentSession,
+    ) -> Result<PathBuf, CallToolError> {
+        let file_path = match &self.file {
+            Some(file) => utils::resolve_project_path(workspace, file),
+            None => component_session
+                .compilation_database()
+                .entries()
+                .iter()
+                .map(|entry| entry.directory.join(&entry.file))
+                .find(|path| path.is_file())
+                .ok_or_else(|| {
+                    CallToolError::new(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        "The compilation database lists no existing source file",
+                    ))
+                })?,
+        };
+        if !file_path.is_file() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file_path.display()),
+            )));
+        }
+        Ok(file_path)
+    }
+
+    #[instrument(
+        name = "check_system_headers",
+        skip(self, component_session, workspace)
+    )]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        let file_path = self.resolve_file_path(workspace, &component_session)?;
+        let is_c = file_path.extension().is_some_and(|ext| ext == "c");
+        let header = self
+            .probe_header
+            .as_deref()
+            .map(|header| header.trim().trim_start_matches('<').trim_end_matches('>'))
+            .filter(|header| !header.is_empty())
+            .unwrap_or(if is_c {
+                DEFAULT_C_PROBE_HEADER
+            } else {
+                DEFAULT_CXX_PROBE_HEADER
+            })
+            .to_string();
+
+        info!(
+            "Checking system header <{}> for {}",
+            header,
+            file_path.display()
+        );
+
+        let resolved_path = probe_include(&component_session, &file_path, &header).await?;
+
+        let entry = component_session
+            .compilation_database()
+            .find_entry_for_file(&file_path)
+            .cloned();
+        let query_driver_globs = component_session
+            .lsp_session()
+            .await
+            .config()
+            .query_driver_globs();
+
+        let compiler = entry
+            .as_ref()
+            .and_then(|entry| entry.arguments.first().cloned());
+        let compiler_allowed = entry.as_ref().and_then(|entry| {
+            let compiler = resolve_compiler_path(entry.arguments.first()?, &entry.directory);
+            Some(query_driver_globs.iter().any(|glob| {
+                Glob::new(glob)
+                    .map(|glob| glob.compile_matcher().is_match(&compiler))
+                    .unwrap_or(false)
+            }))
+        });
+        let (system_include_paths, driver_error) = match &entry {
+            Some(entry) => match query_driver_include_paths(entry).await {
+                Ok(paths) => (paths, None),
+                Err(e) => {
+                    warn!("Compiler driver query failed: {}", e);
+                    (Vec::new(), Some(e))
+                }
+            },
+            None => (Vec::new(), None),
+        };
+
+        let status = if resolved_path.is_some() {
+            SystemHeadersStatus::Ok
+        } else {
+            SystemHeadersStatus::SystemHeadersMissing
+        };
+        let hint = (status == SystemHeadersStatus::SystemHeadersMissing).then(|| {
+            missing_headers_hint(
+                &header,
+                compiler.as_deref(),
+                compiler_allowed,
+                driver_error.as_deref(),
+                &system_include_paths,
+            )
+        });
+
+        let result = SystemHeadersResult {
+            file: file_path,
+            status,
+            probe: HeaderProbe {
+                header,
+                resolved: resolved_path.is_some(),
+                resolved_path,
+            },
+            compile_command_found: entry.is_some(),
+            compiler,
+            query_driver_globs,
+            compiler_allowed,
+            system_include_paths,
+            driver_error,
+            hint,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+/// Include `header` at the top of clangd's copy of the file and resolve it
+///
+/// The on-disk content is sent to clangd again afterwards.
+async fn probe_include(
+    component_session: &ComponentSession,
+    file_path: &Path,
+    header: &str,
+) -> Result<Option<PathBuf>, CallToolError> {
+    let content = read_source_file_async(file_path).await.map_err(|e| {
+        CallToolError::new(std::io::Error::new(
+            e.kind(),
+            format!("Failed to read '{}': {}", file_path.display(), e),
+        ))
+    })?;
+    let directive = format!("#include <{header}>");
+    let change = TextDocumentContentChangeEvent {
+        range: None,
+        range_length: None,
+        text: format!("{directive}\n{content}"),
+    };
+    component_session
+        .apply_in_memory_changes(file_path, vec![change])
+        .await
+        .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?;
+
+    // clangd answers go-to-definition on an include with the included file
+    let header_column = "#include <".len() as u32;
+    let location = FileLocation {
+        file_path: file_path.to_path_buf(),
+        range: lsp_types::Range::new(
+            lsp_types::Position::new(0, header_column),
+            lsp_types::Position::new(0, directive.len() as u32 - 1),
+        )
+        .into(),
+    };
+    let resolved = get_definitions(&location, component_session)
+        .await
+        .map(|definitions| {
+            definitions
+                .into_iter()
+                .map(|definition| definition.file_path)
+                .find(|path| path != file_path)
+        })
+        .map_err(|e| e.to_string());
+
+    if let Err(e) = component_session.restore_file(file_path).await {
+        warn!(
+            "Failed to restore on-disk content of {}: {}",
+            file_path.display(),
+            e
+        );
+    }
+    resolved.map_err(|e| CallToolError::new(std::io::Error::other(e)))
+}
+
+/// Path clangd matches `--query-driver` globs against
+///
+/// Bare program names are looked up in `PATH`, relative paths are resolved
+/// against the compile command's directory.
+fn resolve_compiler_path(compiler: &str, directory: &Path) -> PathBuf {
+    let path = Path::new(compiler);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    if path.components().count() > 1 {
+        return directory.join(path);
+    }
+    std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(compiler))
+                .find(|candidate| candidate.is_file())
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Most likely explanation for a system header clangd does not resolve
+fn missing_headers_hint(
+    header: &str,
+    compiler: Option<&str>,
+    compiler_allowed: Option<bool>,
+    driver_error: Option<&str>,
+    system_include_paths: &[PathBuf],
+) -> String {
+    let Some(compiler) = compiler else {
+        return format!(
+            "The file has no compile command; clangd guesses its flags and may not find <{header}>. \
+             Check the file is part of the compilation database."
+        );
+    };
+    if compiler_allowed == Some(false) {
+        return format!(
+            "clangd is not allowed to query {compiler}: add --query-driver=<glob matching its path> \
+             to the clangd arguments (clangd_args in .mcp-cpp.toml)."
+        );
+    }
+    if let Some(error) = driver_error {
+        return format!("Querying {compiler} for its system include directories failed: {error}");
+    }
+    if system_include_paths.is_empty() {
+        return format!(
+            "{compiler} reports no system include directories; check its --sysroot or toolchain installation."
+        );
+    }
+    format!(
+        "{compiler} reports system include directories but clangd does not resolve <{header}>. \
+         The header may not exist in them, or clangd failed to run the driver: check the clangd log \
+         for query-driver errors."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+    use crate::symbol::uri_from_pathbuf;
+
+    async fn check(server: &MockClangd, workspace: &ProjectWorkspace) -> SystemHeadersResult {
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let tool = CheckSystemHeadersTool {
+            file: None,
+            probe_header: None,
+            build_directory: None,
+            session_id: None,
+        };
+        let result = tool.call_tool(component_session, workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_probe_header_resolves() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let vector = PathBuf::from("/usr/include/c++/13/vector");
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/definition",
+            serde_json::json!([{
+                "uri": uri_from_pathbuf(&vector).as_str(),
+                "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 0}}
+            }]),
+        );
+
+        let result = check(&server, &workspace).await;
+        assert_eq!(result.status, SystemHeadersStatus::Ok);
+        assert_eq!(result.probe.header, "vector");
+        assert_eq!(result.probe.resolved_path, Some(vector));
+        assert!(result.compile_command_found);
+        assert_eq!(result.compiler.as_deref(), Some("c++"));
+        assert!(result.hint.is_none());
+
+        // The probe goes to clangd's copy of the file, which is restored afterwards
+        let mut changes = server.received_params("textDocument/didChange");
+        for _ in 0..100 {
+            if changes.len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            changes = server.received_params("textDocument/didChange");
+        }
+        let texts: Vec<_> = changes
+            .iter()
+            .map(|params| params.as_ref().unwrap()["contentChanges"][0]["text"].clone())
+            .collect();
+        assert_eq!(
+            texts,
+            vec!["#include <vector>\nint main() {}\n", "int main() {}\n"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_probe_reports_hint() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.c", "int main() {}\n")]);
+        // Unscripted: clangd answers null
+        let server = MockClangd::new();
+
+        let result = check(&server, &workspace).await;
+        assert_eq!(result.status, SystemHeadersStatus::SystemHeadersMissing);
+        assert_eq!(result.probe.header, "stdio.h");
+        assert!(!result.probe.resolved);
+        assert!(result.hint.is_some());
+    }
+
+    #[test]
+    fn test_hint_for_disallowed_driver() {
+        let hint =
+            missing_headers_hint("vector", Some("arm-none-eabi-g++"), Some(false), None, &[]);
+        assert!(hint.contains("--query-driver"));
+    }
+}
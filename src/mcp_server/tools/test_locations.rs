@@ -0,0 +1,269 @@
+//! Test location tool
+//!
+//! Finds the tests exercising a symbol: its references, filtered to files
+//! recognized as tests by path (see `TestFilePatterns`) and grouped by file.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::lsp_helpers::examples::{
+    FileReferences, get_references, group_references_by_file,
+};
+use crate::mcp_server::tools::utils;
+use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectWorkspace, TestFilePatterns};
+use crate::symbol::FileLocation;
+
+#[mcp_tool(
+    name = "find_tests",
+    description = "Find the tests of a C++ symbol: references to it from files that look like tests \
+                   by path (file or directory names containing \"test\": foo_test.cpp, FooTest.cc, \
+                   gtest_main.cc, tests/, unittests/), grouped by file.
+
+                   🎯 USE CASES:
+                   • TDD workflows: find the test to extend before changing a function
+                   • Check whether a function or class is covered by tests at all
+                   • Pick the test target to rebuild and run after a change
+
+                   📋 OUTPUT:
+                   • symbol_location: Location the references were requested for
+                   • test_files: Test files referencing the symbol, most references first, each with
+                     reference_count and the 1-based lines of the references
+                   • test_references: Number of references from test files
+                   • total_references: Number of references from all files
+                   • patterns: Test file patterns that were applied
+                   • note: Present when no test references were found
+
+                   Only indexed files are searched: test sources missing from the compilation
+                   database (e.g. test targets that were not configured) are not found.
+
+                   INPUT REQUIREMENTS:
+                   • symbol: Required symbol name, preferably qualified (e.g. \"Parser::parse\")
+                   • location_hint: Optional string - exact symbol location (format: \"/path/file.cpp:line:column\")
+                   • test_patterns: Optional glob patterns replacing the configured test file patterns
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details
+                   • wait_timeout: Optional number - timeout for indexing completion in seconds, or for the hinted file to be parsed when location_hint is given (default: 20s, 0 = no wait)"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct FindTestsTool {
+    /// Name of the symbol. Qualify it to avoid picking a same-named symbol of
+    /// another scope.
+    ///
+    /// EXAMPLES: "parse", "Parser::parse", "net::Socket"
+    pub symbol: String,
+
+    /// Location of the symbol name for precise selection. OPTIONAL.
+    ///
+    /// FORMAT: Compact LSP-style location string with 1-based line/column numbers:
+    /// • "/absolute/path/to/file.cpp:line:column"
+    ///
+    /// BEHAVIOR: When specified, the symbol at this location is used and workspace
+    /// symbol search is skipped. Any declaration or use of the symbol works.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_hint: Option<String>,
+
+    /// Glob patterns of test files, matched case-insensitively against paths
+    /// relative to the project root. OPTIONAL: replaces the `test_file_patterns`
+    /// of .mcp-cpp.toml and the defaults (["**/*test*", "**/*test*/**"]).
+    ///
+    /// EXAMPLES: ["spec/**"], ["**/*_unittest.cc", "**/*_test.cpp"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_patterns: Option<Vec<String>>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Timeout in seconds to wait for indexing completion, or for the target files
+    /// to be parsed in document-specific mode (default: 20s, 0 = no wait)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestLocationsResult {
+    /// Symbol as requested
+    pub symbol: String,
+    pub symbol_location: FileLocation,
+    pub test_files: Vec<FileReferences>,
+    pub test_references: usize,
+    pub total_references: usize,
+    pub patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub note: Option<String>,
+    /// Index status information when timeout occurred or no indexing wait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<IndexStatusView>,
+}
+
+impl FindTestsTool {
+    /// Test file patterns of the request, or the configured ones
+    fn test_file_patterns(
+        &self,
+        workspace: &ProjectWorkspace,
+    ) -> Result<TestFilePatterns, CallToolError> {
+        match &self.test_patterns {
+            Some(patterns) => TestFilePatterns::new(patterns.clone(), &workspace.project_root_path)
+                .map_err(|e| {
+                    CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+                }),
+            None => Ok(workspace.test_file_patterns.clone()),
+        }
+    }
+
+    #[instrument(name = "find_tests", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        info!(
+            "Finding tests of '{}', location_hint={:?}",
+            self.symbol, self.location_hint
+        );
+        let test_file_patterns = self.test_file_patterns(workspace)?;
+
+        let document_files: Option<Vec<_>> = self.location_hint.as_ref().map(|hint| {
+            utils::location_hint_file(workspace, hint)
+                .into_iter()
+                .collect()
+        });
+        let index_status = utils::handle_selective_indexing_wait(
+            &component_session,
+            document_files.as_deref(),
+            self.wait_timeout,
+            if self.location_hint.is_some() {
+                "Document-specific test search"
+            } else {
+                "Workspace symbol resolution"
+            },
+        )
+        .await;
+
+        let symbol_location = utils::locate_symbol(
+            workspace,
+            &component_session,
+            &self.symbol,
+            self.location_hint.as_deref(),
+        )
+        .await?;
+        let mut references = get_references(&component_session, &symbol_location)
+            .await
+            .map_err(CallToolError::from)?;
+        references.retain(|location| !workspace.result_exclusions.is_excluded(&location.file_path));
+        let total_references = references.len();
+        references.retain(|location| test_file_patterns.is_test_file(&location.file_path));
+        let test_files = group_references_by_file(&references);
+        info!(
+            "'{}': {} of {} references in {} test files",
+            self.symbol,
+            references.len(),
+            total_references,
+            test_files.len()
+        );
+
+        let note = test_files.is_empty().then(|| {
+            if total_references == 0 {
+                "The symbol has no references in indexed files.".to_string()
+            } else {
+                format!(
+                    "None of the {total_references} references is in a file matching the test \
+                     patterns; adjust test_patterns if the project names its tests differently."
+                )
+            }
+        });
+        let result = TestLocationsResult {
+            symbol: self.symbol.clone(),
+            symbol_location,
+            test_files,
+            test_references: references.len(),
+            total_references,
+            patterns: test_file_patterns.patterns().to_vec(),
+            note,
+            index_status,
+        };
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, location, tool_result};
+
+    async fn find_tests(arguments: serde_json::Value) -> TestLocationsResult {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/parser.cpp", "int parse() { return 0; }\n"),
+            ("src/main.cpp", "int parse();\nint x = parse();\n"),
+            (
+                "tests/parser_test.cpp",
+                "int parse();\nint a = parse();\nint b = parse();\n",
+            ),
+            ("src/ParserSpec.cpp", "int parse();\nint c = parse();\n"),
+        ]);
+        let uri = |file: &str| {
+            crate::symbol::uri_from_pathbuf(&workspace.project_root_path.join(file)).to_string()
+        };
+        let server = MockClangd::new();
+        server.respond(
+            "textDocument/references",
+            serde_json::json!([
+                location(&uri("src/main.cpp"), 1, 0, 5),
+                location(&uri("tests/parser_test.cpp"), 1, 0, 5),
+                location(&uri("tests/parser_test.cpp"), 2, 0, 5),
+                location(&uri("src/ParserSpec.cpp"), 1, 0, 5),
+            ]),
+        );
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let mut arguments = arguments;
+        arguments["symbol"] = "parse".into();
+        arguments["wait_timeout"] = 0.into();
+        arguments["location_hint"] = "src/parser.cpp:1:5".into();
+        let tool: FindTestsTool = serde_json::from_value(arguments).unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        tool_result(&result)
+    }
+
+    #[tokio::test]
+    async fn test_filters_references_to_test_files() {
+        let result = find_tests(serde_json::json!({})).await;
+        assert_eq!(result.total_references, 4);
+        assert_eq!(result.test_references, 2);
+        assert_eq!(result.test_files.len(), 1);
+        assert!(result.test_files[0].file.ends_with("tests/parser_test.cpp"));
+        assert_eq!(result.test_files[0].lines, vec![2, 3]);
+        assert!(result.note.is_none());
+
+        // The project-relative hint is looked up in the project
+        assert!(result.symbol_location.file_path.is_absolute());
+        assert!(result.symbol_location.file_path.ends_with("src/parser.cpp"));
+
+        let result = find_tests(serde_json::json!({"test_patterns": ["**/*Spec.cpp"]})).await;
+        assert_eq!(result.test_references, 1);
+        assert!(result.test_files[0].file.ends_with("src/ParserSpec.cpp"));
+        assert_eq!(result.patterns, vec!["**/*Spec.cpp"]);
+    }
+}
@@ -31,9 +31,16 @@ async fn test_analyzer_call_hierarchy_function() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "factorial".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -98,9 +105,16 @@ async fn test_analyzer_call_hierarchy_method() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "Math::Complex::add".to_string(), // Fully qualified name
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -164,9 +178,16 @@ async fn test_analyzer_call_hierarchy_non_function() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "Math".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -252,9 +273,16 @@ async fn test_analyzer_call_hierarchy_coherence() {
     let variance_tool = AnalyzeSymbolContextTool {
         symbol: "variance".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: Some(variance_location),
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -297,9 +325,16 @@ async fn test_analyzer_call_hierarchy_coherence() {
     let mean_tool = AnalyzeSymbolContextTool {
         symbol: "Math::mean".to_string(), // Use qualified name
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -342,9 +377,16 @@ async fn test_analyzer_call_hierarchy_coherence() {
     let std_dev_tool = AnalyzeSymbolContextTool {
         symbol: "Math::standardDeviation".to_string(), // Use qualified name
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -31,9 +31,16 @@ async fn test_analyzer_members_math() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "Math".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -145,9 +152,16 @@ async fn test_analyzer_members_interface() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "IStorageBackend".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -261,9 +275,16 @@ async fn test_analyzer_members_non_class() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "factorial".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
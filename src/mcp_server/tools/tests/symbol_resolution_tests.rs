@@ -120,9 +120,11 @@ async fn test_symbol_resolution_no_match() {
 
     assert!(result.is_err());
     match result {
-        Err(crate::mcp_server::tools::analyze_symbols::AnalyzerError::NoSymbols(symbol)) => {
-            assert_eq!(symbol, "NonExistentSymbol");
-            info!("Correctly detected no symbols found for '{}'", symbol);
+        Err(crate::mcp_server::tools::analyze_symbols::AnalyzerError::NoSymbols {
+            query, ..
+        }) => {
+            assert_eq!(query, "NonExistentSymbol");
+            info!("Correctly detected no symbols found for '{}'", query);
         }
         _ => panic!("Expected NoSymbols error"),
     }
@@ -31,9 +31,16 @@ async fn test_analyzer_type_hierarchy_interface() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "IStorageBackend".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -96,9 +103,16 @@ async fn test_analyzer_type_hierarchy_derived_class() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "MemoryStorage".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -159,9 +173,16 @@ async fn test_analyzer_type_hierarchy_non_class() {
     let tool = AnalyzeSymbolContextTool {
         symbol: "factorial".to_string(),
         build_directory: None,
+        session_id: None,
         max_examples: Some(2),
         location_hint: None,
         wait_timeout: None,
+        member_access: None,
+        member_kinds: None,
+        exclude_special_members: None,
+        context_file: None,
+        allow_outside_root: None,
+        include_definition_source: None,
     };
 
     let component_session = workspace_session
@@ -1,26 +1,54 @@
 //! Common utilities for MCP tools
 
 use crate::clangd::config::DEFAULT_INDEX_WAIT_TIMEOUT_SECS;
-use crate::project::ComponentSession;
+use crate::mcp_server::tools::lsp_helpers::symbol_resolution::get_matching_symbol;
 use crate::project::index::IndexStatusView;
+use crate::project::{ComponentSession, ProjectComponent, ProjectWorkspace};
+use crate::symbol::{FileLine, FileLocation};
+use globset::GlobBuilder;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tracing::info;
+use tracing::{error, info, warn};
+use walkdir::WalkDir;
 
-/// Helper function to serialize JSON content and handle errors gracefully
-pub fn serialize_result(content: &serde_json::Value) -> String {
-    serde_json::to_string_pretty(content)
-        .unwrap_or_else(|e| format!("Error serializing result: {e}"))
+/// Default maximum number of files a set of file patterns expands to
+pub const DEFAULT_MAX_PATTERN_FILES: usize = 100;
+
+tokio::task_local! {
+    /// Whether the tool call running in this task renders compact JSON
+    static COMPACT_JSON: bool;
+}
+
+/// Run a tool call rendering its results as compact JSON or not
+///
+/// Results serialized outside such a scope are pretty-printed.
+pub async fn with_compact_json<F: Future>(compact: bool, call: F) -> F::Output {
+    COMPACT_JSON.scope(compact, call).await
+}
+
+/// Serialize a tool result, compact when the running tool call asks for it
+pub fn serialize_result<T: Serialize + ?Sized>(content: &T) -> serde_json::Result<String> {
+    if COMPACT_JSON.try_with(|compact| *compact).unwrap_or(false) {
+        serde_json::to_string(content)
+    } else {
+        serde_json::to_string_pretty(content)
+    }
 }
 
 /// Selective indexing wait logic for MCP tools
 ///
 /// This function implements the common pattern where:
-/// - Document-specific operations (with hints) skip indexing wait and return current status
+/// - Document-specific operations (with hints) skip indexing wait, wait only until
+///   their files are parsed by clangd and return current status
 /// - Workspace operations wait for indexing completion based on timeout
 ///
 /// # Arguments
 /// * `component_session` - The component session to use for indexing operations
-/// * `skip_indexing_condition` - Whether to skip indexing wait (e.g., has location_hint or files)
+/// * `document_files` - Files of a document-specific operation (e.g., from location_hint
+///   or files), `None` for workspace operations
 /// * `wait_timeout` - Optional timeout in seconds (uses default if None)
 /// * `operation_type` - Human-readable operation type for logging (e.g., "document search", "workspace analysis")
 ///
@@ -29,19 +57,30 @@ pub fn serialize_result(content: &serde_json::Value) -> String {
 /// * `None` - Indexing completed successfully
 pub async fn handle_selective_indexing_wait(
     component_session: &ComponentSession,
-    skip_indexing_condition: bool,
+    document_files: Option<&[PathBuf]>,
     wait_timeout: Option<u64>,
     operation_type: &str,
 ) -> Option<IndexStatusView> {
-    if skip_indexing_condition {
-        // Document-specific operation: Skip indexing wait and return current status
+    let wait_timeout_secs = wait_timeout.unwrap_or(DEFAULT_INDEX_WAIT_TIMEOUT_SECS);
+
+    if let Some(files) = document_files {
+        // Document-specific operation: Skip indexing wait, only wait for the files to be parsed
         info!("{} detected - skipping indexing wait", operation_type);
+        if wait_timeout_secs > 0 {
+            wait_for_files_parsed(
+                component_session,
+                files,
+                Duration::from_secs(wait_timeout_secs),
+            )
+            .await;
+        }
         Some(component_session.get_index_status().await)
     } else {
         // Workspace operation: Wait for indexing based on timeout parameter
-        let wait_timeout_secs = wait_timeout.unwrap_or(DEFAULT_INDEX_WAIT_TIMEOUT_SECS);
-
-        if wait_timeout_secs == 0 {
+        if component_session.quiet_indexing() {
+            info!("Quiet indexing - no background index to wait for");
+            Some(component_session.get_index_status().await)
+        } else if wait_timeout_secs == 0 {
             info!("Zero timeout specified - skipping indexing wait");
             Some(component_session.get_index_status().await)
         } else {
@@ -63,3 +102,288 @@ pub async fn handle_selective_indexing_wait(
         }
     }
 }
+
+/// Wait until clangd has parsed each file, sharing one timeout
+///
+/// Files that cannot be opened are skipped; the operation itself reports them.
+async fn wait_for_files_parsed(
+    component_session: &ComponentSession,
+    files: &[PathBuf],
+    timeout: Duration,
+) {
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    for file in files {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        match component_session
+            .wait_until_file_parsed(file, remaining)
+            .await
+        {
+            Ok(true) => info!("File {} is parsed", file.display()),
+            Ok(false) => info!("Timeout waiting for {} to be parsed", file.display()),
+            Err(e) => warn!("Cannot wait for {} to be parsed: {}", file.display(), e),
+        }
+    }
+}
+
+/// Resolve a path given in tool arguments against the project root
+pub fn resolve_project_path(workspace: &ProjectWorkspace, path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        workspace.project_root_path.join(path)
+    }
+}
+
+/// Parse a "file:line:column" location hint, resolving relative paths against the project root
+pub fn parse_location_hint(
+    workspace: &ProjectWorkspace,
+    location_hint: &str,
+) -> Result<FileLocation, CallToolError> {
+    let mut location: FileLocation = location_hint.parse().map_err(|e| {
+        CallToolError::new(std::io::Error::other(format!(
+            "Invalid location format '{}': {}",
+            location_hint, e
+        )))
+    })?;
+    location.file_path = resolve_project_path(workspace, &location.file_path);
+    Ok(location)
+}
+
+/// File path as written in a location hint ("file:line:column" or "file:line")
+pub fn location_hint_path(location_hint: &str) -> Option<PathBuf> {
+    match location_hint.parse::<FileLocation>() {
+        Ok(location) => Some(location.file_path),
+        Err(_) => Some(location_hint.parse::<FileLine>().ok()?.file_path),
+    }
+}
+
+/// File referenced by a location hint, resolving relative paths against the project root
+pub fn location_hint_file(workspace: &ProjectWorkspace, location_hint: &str) -> Option<PathBuf> {
+    location_hint_path(location_hint).map(|path| resolve_project_path(workspace, path))
+}
+
+/// Locate a symbol from its location hint, or workspace symbols without one
+///
+/// The hint is parsed with `parse_location_hint`, so relative paths are
+/// resolved against the project root.
+pub async fn locate_symbol(
+    workspace: &ProjectWorkspace,
+    component_session: &ComponentSession,
+    symbol: &str,
+    location_hint: Option<&str>,
+) -> Result<FileLocation, CallToolError> {
+    match location_hint {
+        Some(location_hint) => parse_location_hint(workspace, location_hint),
+        None => {
+            let symbol = get_matching_symbol(symbol, component_session)
+                .await
+                .map_err(|err| {
+                    error!("Failed to get matching workspace symbol: {}", err);
+                    CallToolError::from(err)
+                })?;
+            Ok(symbol.location)
+        }
+    }
+}
+
+/// Components of the requested build directory, or all components when none is requested
+///
+/// Relative build directories are resolved against the project root.
+pub fn select_components<'a>(
+    workspace: &'a ProjectWorkspace,
+    build_directory: Option<&str>,
+) -> Result<Vec<&'a ProjectComponent>, CallToolError> {
+    let Some(build_directory) = build_directory else {
+        return Ok(workspace.components.iter().collect());
+    };
+    let requested = workspace.project_root_path.join(build_directory);
+    let requested = requested.canonicalize().unwrap_or(requested);
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    workspace
+        .components
+        .iter()
+        .find(|component| canonical(&component.build_dir_path) == requested)
+        .map(|component| vec![component])
+        .ok_or_else(|| {
+            CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "Build directory '{}' is not a build directory of the project. \
+                     Available: {:?}",
+                    build_directory,
+                    workspace.get_build_dirs()
+                ),
+            ))
+        })
+}
+
+/// Compilation database clangd uses for a component: the global one when
+/// configured, otherwise the component's own
+pub fn component_compilation_database<'a>(
+    workspace: &'a ProjectWorkspace,
+    component: &'a ProjectComponent,
+) -> &'a Path {
+    workspace
+        .global_compilation_database
+        .as_ref()
+        .map(|database| database.path().as_path())
+        .unwrap_or(&component.compilation_database_path)
+}
+
+/// Files matched by one glob pattern of a file list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternMatch {
+    pub pattern: String,
+    /// Matching files, relative to the directory the pattern was expanded against
+    pub files: Vec<String>,
+}
+
+/// File list with glob patterns replaced by the files they match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilePatternExpansion {
+    /// Plain entries unchanged, followed by the files matched by patterns
+    pub files: Vec<String>,
+    /// Matches of each glob pattern, in request order
+    pub patterns: Vec<PatternMatch>,
+    /// Whether matches were dropped to stay within the file limit
+    pub truncated: bool,
+}
+
+/// Whether a file list entry is a glob pattern rather than a path
+pub fn is_file_pattern(entry: &str) -> bool {
+    entry.contains(['*', '?', '[', '{'])
+}
+
+/// Expand glob patterns such as `src/**/*.cpp` in a file list
+///
+/// Patterns are matched against the paths of files below `root`, relative to
+/// it, with `*` not crossing directory separators; hidden directories are not
+/// searched. Matches are sorted and become absolute paths. Plain entries are
+/// kept as given. At most `max_files` files are returned in total.
+pub fn expand_file_patterns(
+    entries: &[String],
+    root: &Path,
+    max_files: usize,
+) -> Result<FilePatternExpansion, String> {
+    let mut expansion = FilePatternExpansion::default();
+    let (patterns, plain): (Vec<&String>, Vec<&String>) =
+        entries.iter().partition(|entry| is_file_pattern(entry));
+    expansion.files = plain.into_iter().cloned().collect();
+    if patterns.is_empty() {
+        return Ok(expansion);
+    }
+
+    let matchers = patterns
+        .iter()
+        .map(|pattern| {
+            GlobBuilder::new(pattern.trim_start_matches("./"))
+                .literal_separator(true)
+                .build()
+                .map(|glob| glob.compile_matcher())
+                .map_err(|e| format!("Invalid file pattern '{pattern}': {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut candidates = BTreeSet::new();
+    let walker = WalkDir::new(root).into_iter().filter_entry(|entry| {
+        entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+    });
+    for entry in walker.filter_map(Result::ok) {
+        if entry.file_type().is_file()
+            && let Ok(relative) = entry.path().strip_prefix(root)
+        {
+            candidates.insert(relative.to_path_buf());
+        }
+    }
+
+    let mut seen: BTreeSet<PathBuf> = BTreeSet::new();
+    for (pattern, matcher) in patterns.into_iter().zip(matchers) {
+        let mut files = Vec::new();
+        for relative in candidates.iter().filter(|path| matcher.is_match(path)) {
+            if seen.contains(relative) {
+                files.push(relative.to_string_lossy().to_string());
+                continue;
+            }
+            if expansion.files.len() >= max_files {
+                expansion.truncated = true;
+                break;
+            }
+            seen.insert(relative.clone());
+            files.push(relative.to_string_lossy().to_string());
+            expansion
+                .files
+                .push(root.join(relative).to_string_lossy().to_string());
+        }
+        expansion.patterns.push(PatternMatch {
+            pattern: pattern.clone(),
+            files,
+        });
+    }
+
+    Ok(expansion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_serialize_result_compact_json() {
+        let content = serde_json::json!({"symbol": "Math::add", "lines": [1, 2]});
+        let pretty = serde_json::to_string_pretty(&content).unwrap();
+        assert_eq!(serialize_result(&content).unwrap(), pretty);
+
+        let compact = with_compact_json(true, async { serialize_result(&content).unwrap() }).await;
+        assert_eq!(compact, serde_json::to_string(&content).unwrap());
+        assert!(!compact.contains(char::is_whitespace));
+        let result = with_compact_json(false, async { serialize_result(&content).unwrap() }).await;
+        assert_eq!(result, pretty);
+    }
+
+    #[test]
+    fn test_expand_file_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for file in [
+            "src/main.cpp",
+            "src/util/math.cpp",
+            "src/util/math.h",
+            ".cache/clangd.cpp",
+        ] {
+            std::fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let entries = vec!["include/api.h".to_string(), "src/**/*.cpp".to_string()];
+        let expansion = expand_file_patterns(&entries, root, 10).unwrap();
+        assert_eq!(
+            expansion.files,
+            vec![
+                "include/api.h".to_string(),
+                root.join("src/main.cpp").to_string_lossy().to_string(),
+                root.join("src/util/math.cpp").to_string_lossy().to_string(),
+            ]
+        );
+        assert_eq!(
+            expansion.patterns,
+            vec![PatternMatch {
+                pattern: "src/**/*.cpp".to_string(),
+                files: vec!["src/main.cpp".to_string(), "src/util/math.cpp".to_string()],
+            }]
+        );
+        assert!(!expansion.truncated);
+
+        // `*` stays within one directory
+        let expansion = expand_file_patterns(&["src/*".to_string()], root, 10).unwrap();
+        assert_eq!(expansion.patterns[0].files, vec!["src/main.cpp"]);
+
+        let expansion = expand_file_patterns(&["**/*.{cpp,h}".to_string()], root, 2).unwrap();
+        assert_eq!(expansion.files.len(), 2);
+        assert!(expansion.truncated);
+
+        assert!(expand_file_patterns(&["src/[".to_string()], root, 10).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! File warm-up tool
+//!
+//! The first query on a file waits for clangd to build its preamble (the
+//! precompiled `#include`s) and AST, which takes seconds to minutes for files
+//! including large headers and looks like a hang. This tool opens files ahead
+//! of the analysis and waits for clangd to finish with them, reporting the
+//! preamble state of each, so later queries on them answer quickly.
+
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, TextContent, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{info, instrument};
+
+use crate::mcp_server::tools::analyze_symbols::AnalyzerError;
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+use crate::symbol::uri_from_pathbuf;
+
+/// Default time allowed for all files to be ready
+const DEFAULT_WARM_UP_TIMEOUT_SECS: u64 = 120;
+
+/// Default number of files glob patterns expand to
+const DEFAULT_MAX_FILES: usize = 50;
+
+/// Files opened at the same time; clangd builds their preambles in parallel
+const MAX_CONCURRENT_WARM_UPS: usize = 4;
+
+#[mcp_tool(
+    name = "warm_up_files",
+    description = "Prepare C++ files for fast analysis: open them in clangd and wait until their \
+                   preamble (precompiled #includes) and AST are built.
+
+                   The first query on a file including large headers can take seconds to minutes \
+                   while clangd builds its preamble. Warm up the files you are about to analyze \
+                   so that this cost is paid once, up front, with visible progress instead of an \
+                   apparent hang. Files stay open in the session afterwards.
+
+                   📋 OUTPUT per file:
+                   • status: ready, timed_out or error
+                   • preamble_ready: Whether clangd has built the preamble
+                   • preamble_ms: Duration of the preamble build, when clangd reported it
+                   • state: Latest clangd file status (e.g. \"parsing includes\", \"idle\")
+                   • elapsed_ms: Time until the file was ready
+                   Plus ready / timed_out / failed counts.
+
+                   INPUT REQUIREMENTS:
+                   • files: Required file paths or glob patterns (e.g. \"src/net/**/*.cpp\"), absolute or project-relative
+                   • max_files: Optional number - files glob patterns expand to (default: 50)
+                   • timeout: Optional number - seconds allowed for all files (default: 120)
+                   • build_directory: Optional - STRONGLY PREFER absolute paths from get_project_details"
+)]
+#[derive(Debug, ::serde::Serialize, ::serde::Deserialize, JsonSchema)]
+pub struct WarmUpFilesTool {
+    /// Files to prepare: paths or glob patterns. Relative paths and patterns are
+    /// resolved against the project root.
+    ///
+    /// EXAMPLES: ["src/parser.cpp"], ["src/net/**/*.cpp", "include/net/*.h"]
+    pub files: Vec<String>,
+
+    /// Maximum number of files glob patterns expand to (default: 50)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<u32>,
+
+    /// Seconds allowed for all files to be ready (default: 120)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+
+    /// Build directory path containing compile_commands.json. STRONGLY RECOMMENDED: Use absolute paths from get_project_details output.
+    ///
+    /// AUTO-DETECTION (when not specified): Attempts to find single build directory
+    /// in current workspace. Fails if multiple or zero build directories found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_directory: Option<String>,
+
+    /// Session id of an existing clangd session to use. OPTIONAL.
+    ///
+    /// Session ids are reported in the `_meta.session_id` field of every tool response.
+    /// When specified, takes precedence over build_directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Outcome of warming up one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmUpStatus {
+    /// clangd finished parsing the file
+    Ready,
+    /// clangd was still working on the file when the timeout expired
+    TimedOut,
+    /// The file could not be opened
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileWarmUp {
+    pub file: PathBuf,
+    pub status: WarmUpStatus,
+    pub preamble_ready: bool,
+    /// Duration of the latest preamble build clangd reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preamble_ms: Option<u64>,
+    /// Latest file status reported by clangd
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Time from the start of the warm-up until the file was ready
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WarmUpResult {
+    pub files: Vec<FileWarmUp>,
+    pub ready: usize,
+    pub timed_out: usize,
+    pub failed: usize,
+    /// Whether pattern matches beyond max_files were left out
+    pub files_truncated: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Open a file and wait until clangd is done with it or the deadline passes
+async fn warm_up_file(
+    component_session: &ComponentSession,
+    file: PathBuf,
+    started: Instant,
+    deadline: Instant,
+) -> FileWarmUp {
+    let canonical = match file.canonicalize() {
+        Ok(canonical) => canonical,
+        Err(e) => {
+            return FileWarmUp {
+                file,
+                status: WarmUpStatus::Error,
+                preamble_ready: false,
+                preamble_ms: None,
+                state: None,
+                elapsed_ms: 0,
+                error: Some(format!("File not found: {}", e)),
+            };
+        }
+    };
+
+    let timeout = deadline.saturating_duration_since(Instant::now());
+    let outcome = component_session
+        .wait_until_file_parsed(&canonical, timeout)
+        .await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let uri = uri_from_pathbuf(&canonical);
+    let monitor = component_session
+        .lsp_session()
+        .await
+        .file_status_monitor()
+        .clone();
+    let preamble = monitor.preamble_status(&uri);
+    let (status, error) = match outcome {
+        Ok(true) => (WarmUpStatus::Ready, None),
+        Ok(false) => (WarmUpStatus::TimedOut, None),
+        Err(e) => (WarmUpStatus::Error, Some(e.to_string())),
+    };
+    FileWarmUp {
+        file: canonical,
+        status,
+        preamble_ready: preamble.ready,
+        preamble_ms: preamble
+            .last_build
+            .map(|duration| duration.as_millis() as u64),
+        state: monitor.state(&uri),
+        elapsed_ms,
+        error,
+    }
+}
+
+impl WarmUpFilesTool {
+    #[instrument(name = "warm_up_files", skip(self, component_session, workspace))]
+    pub async fn call_tool(
+        &self,
+        component_session: Arc<ComponentSession>,
+        workspace: &ProjectWorkspace,
+    ) -> Result<CallToolResult, CallToolError> {
+        if self.files.is_empty() {
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "'files' must name at least one file or pattern",
+            )));
+        }
+
+        let root = workspace.project_root_path.clone();
+        let entries = self.files.clone();
+        let max_files = self.max_files.map_or(DEFAULT_MAX_FILES, |max| max as usize);
+        let expansion = {
+            let root = root.clone();
+            tokio::task::spawn_blocking(move || {
+                utils::expand_file_patterns(&entries, &root, max_files)
+            })
+            .await
+            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))?
+            .map_err(|e| {
+                CallToolError::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+            })?
+        };
+        let timeout = Duration::from_secs(self.timeout.unwrap_or(DEFAULT_WARM_UP_TIMEOUT_SECS));
+        info!(
+            "Warming up {} files (truncated: {}), timeout {:?}",
+            expansion.files.len(),
+            expansion.truncated,
+            timeout
+        );
+
+        let started = Instant::now();
+        let deadline = started + timeout;
+        let limiter = Arc::new(Semaphore::new(MAX_CONCURRENT_WARM_UPS));
+        let mut warm_ups = JoinSet::new();
+        for (index, file) in expansion.files.iter().enumerate() {
+            let file = root.join(file);
+            let component_session = Arc::clone(&component_session);
+            let limiter = Arc::clone(&limiter);
+            warm_ups.spawn(async move {
+                let _permit = limiter.acquire_owned().await;
+                (
+                    index,
+                    warm_up_file(&component_session, file, started, deadline).await,
+                )
+            });
+        }
+
+        let mut files: Vec<Option<FileWarmUp>> = expansion.files.iter().map(|_| None).collect();
+        while let Some(joined) = warm_ups.join_next().await {
+            let (index, warm_up) = joined.map_err(|e| {
+                CallToolError::new(std::io::Error::other(format!("Warm-up task failed: {}", e)))
+            })?;
+            files[index] = Some(warm_up);
+        }
+        let files: Vec<FileWarmUp> = files.into_iter().flatten().collect();
+
+        let count = |status| files.iter().filter(|file| file.status == status).count();
+        let result = WarmUpResult {
+            ready: count(WarmUpStatus::Ready),
+            timed_out: count(WarmUpStatus::TimedOut),
+            failed: count(WarmUpStatus::Error),
+            files_truncated: expansion.truncated,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            files,
+        };
+        info!(
+            "Warm-up done: {} ready, {} timed out, {} failed in {} ms",
+            result.ready, result.timed_out, result.failed, result.elapsed_ms
+        );
+
+        let output = utils::serialize_result(&result).map_err(AnalyzerError::from)?;
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            output,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::file_status::FILE_STATUS_METHOD;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::{create_mock_project, tool_result};
+
+    #[tokio::test]
+    async fn test_warm_up_reports_preamble_status() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/fast.cpp", "int fast();\n"),
+            ("src/slow.cpp", "#include <vector>\nint slow();\n"),
+        ]);
+        let uri = |file: &str| {
+            uri_from_pathbuf(
+                &workspace
+                    .project_root_path
+                    .join(file)
+                    .canonicalize()
+                    .unwrap(),
+            )
+            .to_string()
+        };
+        let (fast, slow) = (uri("src/fast.cpp"), uri("src/slow.cpp"));
+
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        // clangd finishes fast.cpp and is still building the preamble of slow.cpp
+        let notifier = server.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            for (uri, state) in [
+                (&fast, "parsing includes, running Update"),
+                (&slow, "parsing includes, running Update"),
+                (&fast, "parsing main file"),
+                (&fast, "idle"),
+            ] {
+                notifier.notify(
+                    FILE_STATUS_METHOD,
+                    serde_json::json!({"uri": uri, "state": state}),
+                );
+            }
+        });
+
+        let tool: WarmUpFilesTool = serde_json::from_value(serde_json::json!({
+            "files": ["src/*.cpp", "src/missing.cpp"],
+            "timeout": 1
+        }))
+        .unwrap();
+        let result = tool.call_tool(component_session, &workspace).await.unwrap();
+        let result: WarmUpResult = tool_result(&result);
+
+        let files: Vec<_> = result
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.file.file_name().unwrap().to_string_lossy().to_string(),
+                    file.status,
+                    file.preamble_ready,
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("missing.cpp".to_string(), WarmUpStatus::Error, false),
+                ("fast.cpp".to_string(), WarmUpStatus::Ready, true),
+                ("slow.cpp".to_string(), WarmUpStatus::TimedOut, false),
+            ]
+        );
+        assert!(result.files[1].preamble_ms.is_some());
+        assert_eq!(
+            result.files[2].state.as_deref(),
+            Some("parsing includes, running Update")
+        );
+        assert_eq!((result.ready, result.timed_out, result.failed), (1, 1, 1));
+    }
+}
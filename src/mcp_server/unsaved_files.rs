@@ -0,0 +1,302 @@
+//! Unsaved editor buffers applied for the duration of a tool call
+//!
+//! Every clangd-backed tool accepts an `unsaved_files` argument with the
+//! current content of files an editor has not saved yet. The server sends that
+//! content to clangd before running the tool, so the results describe what the
+//! user sees, and sends the on-disk content again afterwards. Nothing is
+//! written to disk.
+//!
+//! The argument is handled here rather than by each tool: it is taken out of
+//! the arguments before they are deserialized, and added to the input schema of
+//! the tools whose results come from clangd. Source text those tools read
+//! themselves is taken from the same buffers (see `with_source_overlay`).
+//!
+//! The buffers stay open in clangd until the call ends, so calls carrying
+//! unsaved files run exclusively: other clangd-backed calls wait for them and
+//! never see another call's buffers.
+
+use lsp_types::TextDocumentContentChangeEvent;
+use rust_mcp_sdk::schema::{Tool, schema_utils::CallToolError};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+use crate::mcp_server::tools::utils;
+use crate::project::{ComponentSession, ProjectWorkspace};
+
+/// Name of the tool argument carrying unsaved buffers
+pub const UNSAVED_FILES_ARGUMENT: &str = "unsaved_files";
+
+/// Tools whose results come from clangd and so reflect unsaved buffers
+///
+/// Tools that only read the compilation database, build files or logs are left
+/// out, as are tools that send file content to clangd themselves.
+const UNSAVED_FILES_TOOLS: &[&str] = &[
+    "analyze_symbol_context",
+    "analyze_symbols_batch",
+    "batch_hover",
+    "clang_tidy",
+    "diff_signatures",
+    "find_comment_markers",
+    "find_cross_references",
+    "find_declarations",
+    "find_duplicate_definitions",
+    "find_operator",
+    "find_overrides",
+    "find_tests",
+    "get_ast",
+    "get_blast_radius",
+    "get_document_links",
+    "get_enclosing_function",
+    "get_expression_type",
+    "get_function_metrics",
+    "get_inactive_regions",
+    "get_selection_range",
+    "list_namespaces",
+    "list_symbols_by_kind",
+    "resolve_include",
+    "resolve_type_alias",
+    "search_symbols",
+    "switch_source_header",
+    "symbol_exists",
+];
+
+/// Tools that send file content to clangd themselves
+///
+/// They do not accept unsaved files, but must not run while another call's
+/// buffers are open in clangd either.
+const DOCUMENT_TOOLS: &[&str] = &["analyze_edit", "warm_up_files"];
+
+/// Content of a file as held by the client, replacing the on-disk content
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UnsavedFile {
+    /// File path, absolute or relative to the project root; the file must exist on disk
+    pub file: String,
+    /// Full current content of the file
+    pub content: String,
+}
+
+/// Whether a tool's results come from clangd and so it accepts unsaved buffers
+pub fn accepts_unsaved_files(tool_name: &str) -> bool {
+    UNSAVED_FILES_TOOLS.contains(&tool_name)
+}
+
+/// Whether a tool must wait for calls carrying unsaved files to finish
+pub fn waits_for_unsaved_files(tool_name: &str) -> bool {
+    accepts_unsaved_files(tool_name) || DOCUMENT_TOOLS.contains(&tool_name)
+}
+
+/// Add the `unsaved_files` argument to the input schema of the tools accepting it
+pub fn with_unsaved_files_schema(mut tools: Vec<Tool>) -> Vec<Tool> {
+    let schema = serde_json::json!({
+        "type": "array",
+        "description": "Unsaved editor buffers to analyze instead of the on-disk content. OPTIONAL.\n\n\
+                        Each entry has `file` (absolute or project-relative path of an existing file) \
+                        and `content` (its full current text). The content is sent to clangd for the \
+                        duration of this call only; nothing is written to disk. Other clangd-backed \
+                        calls wait until this one has finished.",
+        "items": {
+            "type": "object",
+            "properties": {
+                "file": {"type": "string"},
+                "content": {"type": "string"}
+            },
+            "required": ["file", "content"]
+        }
+    });
+    let serde_json::Value::Object(schema) = schema else {
+        unreachable!("schema literal is an object");
+    };
+
+    for tool in tools
+        .iter_mut()
+        .filter(|tool| accepts_unsaved_files(&tool.name))
+    {
+        if let Some(properties) = tool.input_schema.properties.as_mut() {
+            properties.insert(UNSAVED_FILES_ARGUMENT.to_string(), schema.clone());
+        }
+    }
+    tools
+}
+
+/// Remove the `unsaved_files` argument from tool arguments and parse it
+pub fn take_unsaved_files(
+    arguments: &mut Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<Vec<UnsavedFile>, CallToolError> {
+    let Some(value) = arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(UNSAVED_FILES_ARGUMENT))
+    else {
+        return Ok(Vec::new());
+    };
+    if value.is_null() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_value(value).map_err(|e| {
+        CallToolError::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid {UNSAVED_FILES_ARGUMENT}: {e}"),
+        ))
+    })
+}
+
+/// Send unsaved buffers to clangd in place of the on-disk content
+///
+/// Returns the paths of the applied files, to be passed to `restore_unsaved_files()`
+/// once the tool has run. Files applied before a failure are restored right away.
+pub async fn apply_unsaved_files(
+    component_session: &ComponentSession,
+    workspace: &ProjectWorkspace,
+    unsaved_files: &[UnsavedFile],
+) -> Result<Vec<PathBuf>, CallToolError> {
+    let mut applied = Vec::with_capacity(unsaved_files.len());
+    for unsaved_file in unsaved_files {
+        let path = utils::resolve_project_path(workspace, &unsaved_file.file);
+
+        info!(
+            "Applying unsaved buffer of {} ({} bytes)",
+            path.display(),
+            unsaved_file.content.len()
+        );
+        let change = TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: unsaved_file.content.clone(),
+        };
+        if let Err(e) = component_session
+            .apply_in_memory_changes(&path, vec![change])
+            .await
+        {
+            restore_unsaved_files(component_session, &applied).await;
+            return Err(CallToolError::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Failed to apply unsaved buffer of {}: {}",
+                    path.display(),
+                    e
+                ),
+            )));
+        }
+        applied.push(path);
+    }
+    Ok(applied)
+}
+
+/// Send the on-disk content of files with applied unsaved buffers to clangd again
+pub async fn restore_unsaved_files(component_session: &ComponentSession, paths: &[PathBuf]) {
+    for path in paths {
+        if let Err(e) = component_session.restore_file(path).await {
+            warn!(
+                "Failed to restore on-disk content of {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+    use crate::mcp_server::tools::ast::AstTool;
+    use crate::mcp_server::tools::project_tools::GetProjectDetailsTool;
+
+    #[test]
+    fn test_take_unsaved_files() {
+        let mut arguments = serde_json::json!({
+            "file": "src/main.cpp",
+            "unsaved_files": [{"file": "src/main.cpp", "content": "int x;"}]
+        })
+        .as_object()
+        .cloned();
+        let unsaved_files = take_unsaved_files(&mut arguments).unwrap();
+        assert_eq!(
+            unsaved_files,
+            vec![UnsavedFile {
+                file: "src/main.cpp".to_string(),
+                content: "int x;".to_string(),
+            }]
+        );
+        assert!(!arguments.unwrap().contains_key(UNSAVED_FILES_ARGUMENT));
+
+        assert!(take_unsaved_files(&mut None).unwrap().is_empty());
+        let mut invalid = serde_json::json!({"unsaved_files": [{"file": "a.cpp"}]})
+            .as_object()
+            .cloned();
+        assert!(take_unsaved_files(&mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_schema_added_to_session_tools_only() {
+        let tools = with_unsaved_files_schema(vec![AstTool::tool(), GetProjectDetailsTool::tool()]);
+        let has_argument = |tool: &Tool| {
+            tool.input_schema
+                .properties
+                .as_ref()
+                .is_some_and(|properties| properties.contains_key(UNSAVED_FILES_ARGUMENT))
+        };
+        assert!(has_argument(&tools[0]));
+        assert!(!has_argument(&tools[1]));
+
+        let registered = crate::mcp_server::server::CppServerHandler::registered_tools();
+        for name in UNSAVED_FILES_TOOLS.iter().chain(DOCUMENT_TOOLS) {
+            assert!(
+                registered.iter().any(|tool| tool.name == *name),
+                "{name} is not a registered tool"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_and_restore_unsaved_files() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int on_disk;\n")]);
+        let server = MockClangd::new();
+        let component_session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let unsaved_files = vec![UnsavedFile {
+            file: "src/main.cpp".to_string(),
+            content: "int in_editor;\n".to_string(),
+        }];
+        let applied = apply_unsaved_files(&component_session, &workspace, &unsaved_files)
+            .await
+            .unwrap();
+        restore_unsaved_files(&component_session, &applied).await;
+
+        // Notifications are not answered, wait for both changes to arrive
+        let mut changes = server.received_params("textDocument/didChange");
+        for _ in 0..100 {
+            if changes.len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            changes = server.received_params("textDocument/didChange");
+        }
+        let texts: Vec<String> = changes
+            .into_iter()
+            .map(|params| {
+                params.unwrap()["contentChanges"][0]["text"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(texts, vec!["int in_editor;\n", "int on_disk;\n"]);
+
+        let missing = vec![UnsavedFile {
+            file: "src/missing.cpp".to_string(),
+            content: String::new(),
+        }];
+        assert!(
+            apply_unsaved_files(&component_session, &workspace, &missing)
+                .await
+                .is_err()
+        );
+    }
+}
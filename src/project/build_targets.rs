@@ -0,0 +1,417 @@
+//! Build targets of a build directory and the sources they compile
+//!
+//! Targets come from build system metadata when it is available: Meson's
+//! introspection files (meson-info/intro-targets.json) or the CMake File API
+//! codemodel (.cmake/api/v1/reply), which CMake only writes when a codemodel
+//! query was placed in the build directory before configuring. Otherwise the
+//! compilation database entries are grouped by the target directory of their
+//! object files (`CMakeFiles/<target>.dir/`, `<target>.p/`), which gives the
+//! target names but not their types.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::project::{CompilationDatabase, ProjectComponent, ProjectError};
+
+/// Where the targets of a build directory were taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetOrigin {
+    MesonIntrospection,
+    CmakeFileApi,
+    CompilationDatabase,
+}
+
+/// A library, executable or other target of the build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildTarget {
+    pub name: String,
+    /// Target type (executable, static_library, shared_library, ...); unknown
+    /// when the targets were derived from the compilation database
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub target_type: Option<String>,
+    pub sources: Vec<PathBuf>,
+}
+
+/// Targets of a build directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildTargets {
+    pub origin: TargetOrigin,
+    pub targets: Vec<BuildTarget>,
+    /// Compiled sources whose target could not be determined
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ungrouped_sources: Vec<PathBuf>,
+}
+
+/// Normalize a build system target type: "static library", "STATIC_LIBRARY" -> "static_library"
+fn normalize_target_type(target_type: &str) -> String {
+    target_type.to_ascii_lowercase().replace(' ', "_")
+}
+
+/// Read and parse a JSON file
+fn read_json(path: &Path) -> Result<serde_json::Value, ProjectError> {
+    let content = fs::read_to_string(path).map_err(ProjectError::Io)?;
+    serde_json::from_str(&content).map_err(|e| ProjectError::ParseError {
+        reason: format!("Failed to parse {}: {e}", path.display()),
+    })
+}
+
+/// Targets from meson-info/intro-targets.json
+fn meson_targets(build_dir: &Path) -> Result<Option<Vec<BuildTarget>>, ProjectError> {
+    let targets_file = build_dir.join("meson-info").join("intro-targets.json");
+    if !targets_file.exists() {
+        return Ok(None);
+    }
+
+    let targets = read_json(&targets_file)?
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|target| {
+            let name = target.get("name")?.as_str()?.to_string();
+            let target_type = target.get("type").and_then(|t| t.as_str());
+            let sources = target
+                .get("target_sources")
+                .and_then(|s| s.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|group| group.get("sources")?.as_array())
+                .flatten()
+                .filter_map(|source| source.as_str())
+                .map(|source| build_dir.join(source))
+                .collect();
+            Some(BuildTarget {
+                name,
+                target_type: target_type.map(normalize_target_type),
+                sources,
+            })
+        })
+        .collect();
+    Ok(Some(targets))
+}
+
+/// Targets from the newest CMake File API codemodel reply
+///
+/// The configuration matching `build_type` is used, or the first one.
+fn cmake_file_api_targets(
+    build_dir: &Path,
+    build_type: &str,
+) -> Result<Option<Vec<BuildTarget>>, ProjectError> {
+    let reply_dir = build_dir.join(".cmake/api/v1/reply");
+    let Ok(entries) = fs::read_dir(&reply_dir) else {
+        return Ok(None);
+    };
+    // Index files are named index-<timestamp>.json; the newest sorts last
+    let Some(index_file) = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("index-") && name.ends_with(".json"))
+        })
+        .max()
+    else {
+        return Ok(None);
+    };
+
+    let index = read_json(&index_file)?;
+    let Some(codemodel_file) = index
+        .get("objects")
+        .and_then(|objects| objects.as_array())
+        .into_iter()
+        .flatten()
+        .find(|object| object.get("kind").and_then(|k| k.as_str()) == Some("codemodel"))
+        .and_then(|object| object.get("jsonFile")?.as_str())
+    else {
+        return Ok(None);
+    };
+
+    let codemodel = read_json(&reply_dir.join(codemodel_file))?;
+    let source_dir = codemodel
+        .pointer("/paths/source")
+        .and_then(|path| path.as_str())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| build_dir.to_path_buf());
+    let configurations = codemodel
+        .get("configurations")
+        .and_then(|c| c.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let Some(configuration) = configurations
+        .iter()
+        .find(|c| c.get("name").and_then(|n| n.as_str()) == Some(build_type))
+        .or(configurations.first())
+    else {
+        return Ok(Some(Vec::new()));
+    };
+
+    let mut targets = Vec::new();
+    for target_ref in configuration
+        .get("targets")
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(target_file) = target_ref.get("jsonFile").and_then(|f| f.as_str()) else {
+            continue;
+        };
+        let target = read_json(&reply_dir.join(target_file))?;
+        let Some(name) = target.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let sources = target
+            .get("sources")
+            .and_then(|s| s.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|source| source.get("path")?.as_str())
+            .map(|source| source_dir.join(source))
+            .collect();
+        targets.push(BuildTarget {
+            name: name.to_string(),
+            target_type: target
+                .get("type")
+                .and_then(|t| t.as_str())
+                .map(normalize_target_type),
+            sources,
+        });
+    }
+    Ok(Some(targets))
+}
+
+/// Object file a compile command writes
+fn object_file(entry: &json_compilation_db::Entry) -> Option<PathBuf> {
+    if let Some(output) = &entry.output {
+        return Some(output.clone());
+    }
+    let mut arguments = entry.arguments.iter();
+    while let Some(argument) = arguments.next() {
+        if argument == "-o" {
+            return arguments.next().map(PathBuf::from);
+        }
+        for prefix in ["-o", "/Fo", "-Fo"] {
+            if let Some(path) = argument.strip_prefix(prefix)
+                && !path.is_empty()
+            {
+                return Some(PathBuf::from(path));
+            }
+        }
+    }
+    None
+}
+
+/// Target an object file belongs to, from the directory the build system puts it in
+fn object_target(object: &Path) -> Option<String> {
+    let components: Vec<&str> = object
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    // CMake: CMakeFiles/<target>.dir/...
+    let cmake_target = components.windows(2).find_map(|pair| {
+        (pair[0] == "CMakeFiles")
+            .then(|| pair[1].strip_suffix(".dir"))
+            .flatten()
+    });
+    // Meson: <target>.p/...
+    let meson_target = || {
+        components[..components.len().saturating_sub(1)]
+            .iter()
+            .find_map(|name| name.strip_suffix(".p"))
+    };
+    cmake_target
+        .or_else(meson_target)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// Targets from the object file paths of the compilation database
+fn compilation_database_targets(database: &CompilationDatabase) -> BuildTargets {
+    let mut targets: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut ungrouped_sources = Vec::new();
+    for entry in database.entries() {
+        let source = if entry.file.is_relative() {
+            entry.directory.join(&entry.file)
+        } else {
+            entry.file.clone()
+        };
+        match object_file(entry).as_deref().and_then(object_target) {
+            Some(target) => targets.entry(target).or_default().push(source),
+            None => ungrouped_sources.push(source),
+        }
+    }
+
+    BuildTargets {
+        origin: TargetOrigin::CompilationDatabase,
+        targets: targets
+            .into_iter()
+            .map(|(name, sources)| BuildTarget {
+                name,
+                target_type: None,
+                sources,
+            })
+            .collect(),
+        ungrouped_sources,
+    }
+}
+
+/// Discover the targets of a build directory
+///
+/// Build system metadata is preferred; the compilation database at
+/// `compile_commands` is the fallback.
+pub fn discover_build_targets(
+    component: &ProjectComponent,
+    compile_commands: &Path,
+) -> Result<BuildTargets, ProjectError> {
+    let metadata = match component.provider_type.as_str() {
+        "meson" => meson_targets(&component.build_dir_path)
+            .map(|targets| targets.map(|t| (TargetOrigin::MesonIntrospection, t))),
+        "cmake" => cmake_file_api_targets(&component.build_dir_path, &component.build_type)
+            .map(|targets| targets.map(|t| (TargetOrigin::CmakeFileApi, t))),
+        _ => Ok(None),
+    };
+
+    match metadata {
+        Ok(Some((origin, mut targets))) => {
+            // Utility targets (custom commands, install scripts) compile nothing
+            targets.retain(|target| !target.sources.is_empty());
+            targets.sort_by(|a, b| a.name.cmp(&b.name));
+            debug!(
+                "{} targets of {} from {:?}",
+                targets.len(),
+                component.build_dir_path.display(),
+                origin
+            );
+            return Ok(BuildTargets {
+                origin,
+                targets,
+                ungrouped_sources: Vec::new(),
+            });
+        }
+        Ok(None) => {}
+        Err(e) => warn!(
+            "Failed to read build system targets of {}, using the compilation database: {}",
+            component.build_dir_path.display(),
+            e
+        ),
+    }
+
+    let database = CompilationDatabase::new(compile_commands.to_path_buf()).map_err(|e| {
+        ProjectError::ParseError {
+            reason: format!("Failed to read {}: {e}", compile_commands.display()),
+        }
+    })?;
+    Ok(compilation_database_targets(&database))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+
+    #[test]
+    fn test_targets_from_compilation_database() {
+        let (_temp_dir, workspace) = create_mock_project(&[]);
+        let component = &workspace.components[0];
+        let root = &workspace.project_root_path;
+        let entries = serde_json::json!([
+            {
+                "directory": component.build_dir_path,
+                "file": root.join("src/core/parser.cpp"),
+                "arguments": ["c++", "-o", "src/core/CMakeFiles/core.dir/parser.cpp.o", "-c", "parser.cpp"]
+            },
+            {
+                "directory": component.build_dir_path,
+                "file": root.join("src/main.cpp"),
+                "command": "c++ -oCMakeFiles/app.dir/src/main.cpp.o -c main.cpp"
+            },
+            {
+                "directory": component.build_dir_path,
+                "file": root.join("src/core/lexer.cpp"),
+                "arguments": ["c++", "-c", "lexer.cpp"],
+                "output": "libcore.a.p/src_core_lexer.cpp.o"
+            },
+            {
+                "directory": component.build_dir_path,
+                "file": root.join("gen.cpp"),
+                "arguments": ["c++", "-c", "gen.cpp"]
+            }
+        ]);
+        std::fs::write(&component.compilation_database_path, entries.to_string()).unwrap();
+
+        let targets =
+            discover_build_targets(component, &component.compilation_database_path).unwrap();
+        assert_eq!(targets.origin, TargetOrigin::CompilationDatabase);
+        let names: Vec<_> = targets.targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "core", "libcore.a"]);
+        assert_eq!(
+            targets.targets[1].sources,
+            vec![root.join("src/core/parser.cpp")]
+        );
+        assert_eq!(targets.targets[1].target_type, None);
+        assert_eq!(targets.ungrouped_sources, vec![root.join("gen.cpp")]);
+    }
+
+    #[test]
+    fn test_targets_from_cmake_file_api() {
+        let (_temp_dir, workspace) = create_mock_project(&[]);
+        let component = &workspace.components[0];
+        let reply_dir = component.build_dir_path.join(".cmake/api/v1/reply");
+        std::fs::create_dir_all(&reply_dir).unwrap();
+        let write = |name: &str, value: serde_json::Value| {
+            std::fs::write(reply_dir.join(name), value.to_string()).unwrap();
+        };
+        write(
+            "index-2024-01-01T00-00-00-0000.json",
+            serde_json::json!({
+                "objects": [{"kind": "codemodel", "jsonFile": "codemodel-v2-1.json"}]
+            }),
+        );
+        write(
+            "codemodel-v2-1.json",
+            serde_json::json!({
+                "paths": {"source": workspace.project_root_path},
+                "configurations": [{
+                    "name": "Debug",
+                    "targets": [
+                        {"name": "core", "jsonFile": "target-core.json"},
+                        {"name": "format", "jsonFile": "target-format.json"}
+                    ]
+                }]
+            }),
+        );
+        write(
+            "target-core.json",
+            serde_json::json!({
+                "name": "core",
+                "type": "STATIC_LIBRARY",
+                "sources": [{"path": "src/parser.cpp", "compileGroupIndex": 0}, {"path": "src/parser.h"}]
+            }),
+        );
+        write(
+            "target-format.json",
+            serde_json::json!({"name": "format", "type": "UTILITY", "sources": []}),
+        );
+
+        let targets =
+            discover_build_targets(component, &component.compilation_database_path).unwrap();
+        assert_eq!(targets.origin, TargetOrigin::CmakeFileApi);
+        assert_eq!(
+            targets.targets,
+            vec![BuildTarget {
+                name: "core".to_string(),
+                target_type: Some("static_library".to_string()),
+                sources: vec![
+                    workspace.project_root_path.join("src/parser.cpp"),
+                    workspace.project_root_path.join("src/parser.h")
+                ],
+            }]
+        );
+    }
+}
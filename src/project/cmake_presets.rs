@@ -0,0 +1,535 @@
+//! CMake presets support
+//!
+//! Parses `CMakePresets.json` and `CMakeUserPresets.json` to find the binary
+//! directories of configure presets, so build directories can be discovered
+//! (and named) even before they are configured.
+
+use crate::project::ProjectError;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Project-wide presets file name
+pub const CMAKE_PRESETS_FILE: &str = "CMakePresets.json";
+
+/// User-specific presets file name (implicitly includes `CMakePresets.json`)
+pub const CMAKE_USER_PRESETS_FILE: &str = "CMakeUserPresets.json";
+
+/// Maximum inheritance/include nesting before a preset graph is considered cyclic
+const MAX_NESTING: usize = 32;
+
+// ============================================================================
+// Raw presets file format
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetsFile {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    configure_presets: Vec<RawConfigurePreset>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Inherits {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfigurePreset {
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+    inherits: Option<Inherits>,
+    generator: Option<String>,
+    binary_dir: Option<String>,
+    #[serde(default)]
+    cache_variables: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    environment: HashMap<String, Option<String>>,
+    /// Directory of the file declaring the preset (for `${fileDir}`)
+    #[serde(skip)]
+    file_dir: PathBuf,
+}
+
+impl RawConfigurePreset {
+    fn parents(&self) -> Vec<&str> {
+        match &self.inherits {
+            None => Vec::new(),
+            Some(Inherits::One(name)) => vec![name.as_str()],
+            Some(Inherits::Many(names)) => names.iter().map(String::as_str).collect(),
+        }
+    }
+
+    fn build_type(&self) -> Option<String> {
+        match self.cache_variables.get("CMAKE_BUILD_TYPE")? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            serde_json::Value::Object(object) => object
+                .get("value")
+                .and_then(|value| value.as_str())
+                .map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Resolved presets
+// ============================================================================
+
+/// A configure preset with inheritance applied and macros expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurePreset {
+    /// Preset name
+    pub name: String,
+    /// Generator, if set by the preset or one of its parents
+    pub generator: Option<String>,
+    /// `CMAKE_BUILD_TYPE` cache variable, if set
+    pub build_type: Option<String>,
+    /// Absolute binary directory, if the preset declares one
+    pub binary_dir: Option<PathBuf>,
+}
+
+/// Configure presets declared by a CMake source directory
+#[derive(Debug, Clone)]
+pub struct CmakePresets {
+    source_dir: PathBuf,
+    presets: Vec<ConfigurePreset>,
+}
+
+impl CmakePresets {
+    /// Load the presets declared in a source directory
+    ///
+    /// Returns `Ok(None)` if the directory has neither `CMakePresets.json`
+    /// nor `CMakeUserPresets.json`. Hidden presets are resolved for
+    /// inheritance but not returned.
+    pub fn load(source_dir: &Path) -> Result<Option<Self>, ProjectError> {
+        let source_dir = normalize_path(source_dir);
+        let presets_file = source_dir.join(CMAKE_PRESETS_FILE);
+        let user_presets_file = source_dir.join(CMAKE_USER_PRESETS_FILE);
+
+        let mut files = Vec::new();
+        if presets_file.is_file() {
+            files.push(presets_file.clone());
+        }
+        if user_presets_file.is_file() {
+            files.push(user_presets_file);
+        }
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let mut raw_presets = Vec::new();
+        let mut loaded = HashSet::new();
+        for file in files {
+            load_presets_file(&file, &mut raw_presets, &mut loaded, 0)?;
+        }
+
+        Ok(Some(Self::resolve(&source_dir, raw_presets)?))
+    }
+
+    /// Source directory declaring the presets
+    pub fn source_dir(&self) -> &Path {
+        &self.source_dir
+    }
+
+    /// Visible (non-hidden) configure presets in declaration order
+    pub fn configure_presets(&self) -> &[ConfigurePreset] {
+        &self.presets
+    }
+
+    /// Find the preset whose binary directory is `build_dir`
+    pub fn find_by_binary_dir(&self, build_dir: &Path) -> Option<&ConfigurePreset> {
+        let build_dir = normalize_path(build_dir);
+        self.presets.iter().find(|preset| {
+            preset
+                .binary_dir
+                .as_deref()
+                .is_some_and(|binary_dir| normalize_path(binary_dir) == build_dir)
+        })
+    }
+
+    fn resolve(
+        source_dir: &Path,
+        raw_presets: Vec<RawConfigurePreset>,
+    ) -> Result<Self, ProjectError> {
+        let by_name: HashMap<&str, &RawConfigurePreset> = raw_presets
+            .iter()
+            .map(|preset| (preset.name.as_str(), preset))
+            .collect();
+
+        let mut presets = Vec::new();
+        for raw in raw_presets.iter().filter(|preset| !preset.hidden) {
+            let generator = inherited(raw, &by_name, 0, &|preset| preset.generator.clone())?;
+            let build_type = inherited(raw, &by_name, 0, &RawConfigurePreset::build_type)?;
+            let binary_dir_template =
+                inherited(raw, &by_name, 0, &|preset| preset.binary_dir.clone())?;
+            let environment = inherited_environment(raw, &by_name, 0)?;
+
+            let expander = MacroExpander {
+                source_dir,
+                preset_name: &raw.name,
+                generator: generator.as_deref(),
+                file_dir: &raw.file_dir,
+                environment: &environment,
+            };
+
+            let binary_dir = binary_dir_template.map(|template| {
+                let expanded = PathBuf::from(expander.expand(&template));
+                let absolute = if expanded.is_relative() {
+                    source_dir.join(expanded)
+                } else {
+                    expanded
+                };
+                normalize_path(&absolute)
+            });
+
+            presets.push(ConfigurePreset {
+                name: raw.name.clone(),
+                generator,
+                build_type,
+                binary_dir,
+            });
+        }
+
+        Ok(Self {
+            source_dir: source_dir.to_path_buf(),
+            presets,
+        })
+    }
+}
+
+/// Read a presets file and the files it includes
+fn load_presets_file(
+    file: &Path,
+    presets: &mut Vec<RawConfigurePreset>,
+    loaded: &mut HashSet<PathBuf>,
+    depth: usize,
+) -> Result<(), ProjectError> {
+    if depth > MAX_NESTING {
+        return Err(ProjectError::ParseError {
+            reason: format!("Presets include nesting too deep at {}", file.display()),
+        });
+    }
+
+    let key = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    if !loaded.insert(key) {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(file).map_err(ProjectError::Io)?;
+    let parsed: PresetsFile =
+        serde_json::from_str(&content).map_err(|e| ProjectError::ParseError {
+            reason: format!("Failed to parse {}: {e}", file.display()),
+        })?;
+
+    let file_dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    // CMakeUserPresets.json implicitly includes CMakePresets.json
+    let mut includes: Vec<PathBuf> = Vec::new();
+    if file
+        .file_name()
+        .is_some_and(|name| name == CMAKE_USER_PRESETS_FILE)
+    {
+        let project_presets = file_dir.join(CMAKE_PRESETS_FILE);
+        if project_presets.is_file() {
+            includes.push(project_presets);
+        }
+    }
+    includes.extend(parsed.include.iter().map(|include| file_dir.join(include)));
+
+    for include in includes {
+        load_presets_file(&include, presets, loaded, depth + 1)?;
+    }
+
+    presets.extend(parsed.configure_presets.into_iter().map(|mut preset| {
+        preset.file_dir = file_dir.clone();
+        preset
+    }));
+
+    Ok(())
+}
+
+/// Look up a field on a preset, falling back to its parents in `inherits` order
+fn inherited<T>(
+    preset: &RawConfigurePreset,
+    by_name: &HashMap<&str, &RawConfigurePreset>,
+    depth: usize,
+    field: &dyn Fn(&RawConfigurePreset) -> Option<T>,
+) -> Result<Option<T>, ProjectError> {
+    if depth > MAX_NESTING {
+        return Err(ProjectError::ParseError {
+            reason: format!("Cyclic preset inheritance involving '{}'", preset.name),
+        });
+    }
+
+    if let Some(value) = field(preset) {
+        return Ok(Some(value));
+    }
+
+    for parent in preset.parents() {
+        let Some(parent) = by_name.get(parent) else {
+            return Err(ProjectError::ParseError {
+                reason: format!(
+                    "Preset '{}' inherits from unknown preset '{}'",
+                    preset.name, parent
+                ),
+            });
+        };
+        if let Some(value) = inherited(parent, by_name, depth + 1, field)? {
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Merge the `environment` maps of a preset and its parents
+///
+/// Entries of the preset win over inherited ones; earlier parents win over
+/// later parents. A `null` value removes the variable.
+fn inherited_environment(
+    preset: &RawConfigurePreset,
+    by_name: &HashMap<&str, &RawConfigurePreset>,
+    depth: usize,
+) -> Result<HashMap<String, Option<String>>, ProjectError> {
+    if depth > MAX_NESTING {
+        return Err(ProjectError::ParseError {
+            reason: format!("Cyclic preset inheritance involving '{}'", preset.name),
+        });
+    }
+
+    let mut environment = HashMap::new();
+    for parent in preset.parents().into_iter().rev() {
+        if let Some(parent) = by_name.get(parent) {
+            environment.extend(inherited_environment(parent, by_name, depth + 1)?);
+        }
+    }
+    environment.extend(preset.environment.clone());
+    Ok(environment)
+}
+
+/// Expands the preset macros supported in `binaryDir`
+struct MacroExpander<'a> {
+    source_dir: &'a Path,
+    preset_name: &'a str,
+    generator: Option<&'a str>,
+    file_dir: &'a Path,
+    environment: &'a HashMap<String, Option<String>>,
+}
+
+impl MacroExpander<'_> {
+    fn expand(&self, template: &str) -> String {
+        let mut output = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('$') {
+            output.push_str(&rest[..start]);
+            let candidate = &rest[start..];
+
+            match self.expand_macro(candidate) {
+                Some((value, consumed)) => {
+                    output.push_str(&value);
+                    rest = &candidate[consumed..];
+                }
+                None => {
+                    output.push('$');
+                    rest = &candidate[1..];
+                }
+            }
+        }
+
+        output.push_str(rest);
+        output
+    }
+
+    /// Expand the macro at the start of `text`, returning the value and the
+    /// number of bytes consumed
+    fn expand_macro(&self, text: &str) -> Option<(String, usize)> {
+        let open = text.find('{')?;
+        let namespace = &text[1..open];
+        let close = open + text[open..].find('}')?;
+        let name = &text[open + 1..close];
+        let consumed = close + 1;
+
+        let value = match (namespace, name) {
+            ("", "sourceDir") => self.source_dir.to_string_lossy().to_string(),
+            ("", "sourceParentDir") => self
+                .source_dir
+                .parent()
+                .unwrap_or(self.source_dir)
+                .to_string_lossy()
+                .to_string(),
+            ("", "sourceDirName") => self
+                .source_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            ("", "presetName") => self.preset_name.to_string(),
+            ("", "generator") => self.generator.unwrap_or_default().to_string(),
+            ("", "hostSystemName") => host_system_name().to_string(),
+            ("", "fileDir") => self.file_dir.to_string_lossy().to_string(),
+            ("", "dollar") => "$".to_string(),
+            ("", "pathListSep") => if cfg!(windows) { ";" } else { ":" }.to_string(),
+            ("env", variable) => match self.environment.get(variable) {
+                Some(value) => value.clone().unwrap_or_default(),
+                None => std::env::var(variable).unwrap_or_default(),
+            },
+            ("penv", variable) => std::env::var(variable).unwrap_or_default(),
+            _ => return None,
+        };
+
+        Some((value, consumed))
+    }
+}
+
+/// Value of CMake's `${hostSystemName}` for the running host
+fn host_system_name() -> &'static str {
+    match std::env::consts::OS {
+        "linux" => "Linux",
+        "macos" => "Darwin",
+        "windows" => "Windows",
+        "freebsd" => "FreeBSD",
+        other => other,
+    }
+}
+
+/// Canonicalize a path if it exists, otherwise normalize `.` and `..` lexically
+fn normalize_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_load_without_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(CmakePresets::load(dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_inheritance_and_macro_expansion() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            CMAKE_PRESETS_FILE,
+            r#"{
+                "version": 6,
+                "configurePresets": [
+                    {
+                        "name": "base",
+                        "hidden": true,
+                        "generator": "Ninja",
+                        "binaryDir": "${sourceDir}/out/build/${presetName}"
+                    },
+                    {
+                        "name": "debug",
+                        "inherits": "base",
+                        "cacheVariables": {"CMAKE_BUILD_TYPE": "Debug"}
+                    },
+                    {
+                        "name": "release",
+                        "inherits": ["base"],
+                        "binaryDir": "build-$env{FLAVOR}",
+                        "environment": {"FLAVOR": "rel"},
+                        "cacheVariables": {"CMAKE_BUILD_TYPE": {"type": "STRING", "value": "Release"}}
+                    }
+                ]
+            }"#,
+        );
+
+        let presets = CmakePresets::load(dir.path()).unwrap().unwrap();
+        let names: Vec<&str> = presets
+            .configure_presets()
+            .iter()
+            .map(|preset| preset.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["debug", "release"]);
+
+        let debug = &presets.configure_presets()[0];
+        assert_eq!(debug.generator.as_deref(), Some("Ninja"));
+        assert_eq!(debug.build_type.as_deref(), Some("Debug"));
+        assert_eq!(
+            debug.binary_dir,
+            Some(normalize_path(dir.path()).join("out/build/debug"))
+        );
+
+        let release = &presets.configure_presets()[1];
+        assert_eq!(release.build_type.as_deref(), Some("Release"));
+        assert_eq!(
+            release.binary_dir,
+            Some(normalize_path(dir.path()).join("build-rel"))
+        );
+    }
+
+    #[test]
+    fn test_user_presets_include_project_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            CMAKE_PRESETS_FILE,
+            r#"{"version": 3, "configurePresets": [
+                {"name": "ci", "binaryDir": "${sourceDir}/../ci-build"}
+            ]}"#,
+        );
+        write(
+            dir.path(),
+            CMAKE_USER_PRESETS_FILE,
+            r#"{"version": 3, "configurePresets": [
+                {"name": "mine", "inherits": "ci", "binaryDir": "${sourceDir}/build/${presetName}"}
+            ]}"#,
+        );
+
+        let presets = CmakePresets::load(dir.path()).unwrap().unwrap();
+        assert_eq!(presets.configure_presets().len(), 2);
+
+        let ci_dir = normalize_path(dir.path())
+            .parent()
+            .unwrap()
+            .join("ci-build");
+        assert_eq!(presets.find_by_binary_dir(&ci_dir).unwrap().name, "ci");
+
+        let mine_dir = dir.path().join("build").join("mine");
+        fs::create_dir_all(&mine_dir).unwrap();
+        assert_eq!(presets.find_by_binary_dir(&mine_dir).unwrap().name, "mine");
+    }
+
+    #[test]
+    fn test_unknown_parent_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            CMAKE_PRESETS_FILE,
+            r#"{"version": 3, "configurePresets": [
+                {"name": "orphan", "inherits": "missing"}
+            ]}"#,
+        );
+
+        assert!(CmakePresets::load(dir.path()).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::project::{ProjectComponent, ProjectComponentProvider, ProjectError};
+use crate::project::cmake_presets::CmakePresets;
+use crate::project::{BuildPreset, ProjectComponent, ProjectComponentProvider, ProjectError};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -7,6 +8,8 @@ use std::path::{Path, PathBuf};
 ///
 /// This provider detects and parses CMake build directories by looking for
 /// CMakeCache.txt files and extracting build configuration information.
+/// Build directories declared by CMakePresets.json/CMakeUserPresets.json are
+/// reported as presets and configured ones are tagged with their preset name.
 pub struct CmakeProvider;
 
 impl CmakeProvider {
@@ -102,6 +105,19 @@ impl CmakeProvider {
         })
     }
 
+    /// Find the name of the configure preset whose binary directory is `build_dir`
+    fn find_preset_name(&self, source_root: &Path, build_dir: &Path) -> Option<String> {
+        match CmakePresets::load(source_root) {
+            Ok(presets) => presets?
+                .find_by_binary_dir(build_dir)
+                .map(|preset| preset.name.clone()),
+            Err(e) => {
+                tracing::warn!("Ignoring CMake presets in {}: {}", source_root.display(), e);
+                None
+            }
+        }
+    }
+
     /// Find compilation database path in build directory
     fn find_compilation_database(&self, build_dir: &Path) -> Option<PathBuf> {
         let compile_commands = build_dir.join("compile_commands.json");
@@ -139,17 +155,14 @@ impl ProjectComponentProvider for CmakeProvider {
         };
 
         // Find compilation database
-        let compilation_database_path = self.find_compilation_database(path).ok_or_else(|| {
-            ProjectError::CompilationDatabaseNotFound {
-                path: path
-                    .join("compile_commands.json")
-                    .to_string_lossy()
-                    .to_string(),
-            }
-        })?;
+        let compilation_database_path = self
+            .find_compilation_database(path)
+            .ok_or_else(|| ProjectError::missing_compile_commands("cmake", path, &source_root))?;
+
+        let preset = self.find_preset_name(&source_root, path);
 
         // Create project component with validation
-        let component = ProjectComponent::new(
+        let mut component = ProjectComponent::new(
             path.to_path_buf(),
             source_root,
             compilation_database_path,
@@ -162,9 +175,29 @@ impl ProjectComponentProvider for CmakeProvider {
                 .unwrap_or_else(|| "Unknown".to_string()),
             cmake_info.build_options,
         )?;
+        component.preset = preset;
 
         Ok(Some(component))
     }
+
+    fn scan_presets(&self, path: &Path) -> Result<Vec<BuildPreset>, ProjectError> {
+        let Some(presets) = CmakePresets::load(path)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(presets
+            .configure_presets()
+            .iter()
+            .filter_map(|preset| {
+                Some(BuildPreset {
+                    name: preset.name.clone(),
+                    provider_type: "cmake".to_string(),
+                    source_root_path: presets.source_dir().to_path_buf(),
+                    build_dir_path: preset.binary_dir.clone()?,
+                })
+            })
+            .collect())
+    }
 }
 
 impl Default for CmakeProvider {
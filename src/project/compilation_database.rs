@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Maximum nesting of response files referencing other response files
+const MAX_RESPONSE_FILE_DEPTH: usize = 16;
 
 /// Type alias for bidirectional path mappings
 /// (original_path -> canonical_path, canonical_path -> original_path)
@@ -63,6 +67,8 @@ impl CompilationDatabase {
             return Err(CompilationDatabaseError::EmptyDatabase);
         }
 
+        let entries = entries.into_iter().map(expand_response_files).collect();
+
         Ok(Self { path, entries })
     }
 
@@ -128,6 +134,25 @@ impl CompilationDatabase {
         Ok((original_to_canonical, canonical_to_original))
     }
 
+    /// Find the compilation entry for a source file
+    ///
+    /// The lookup canonicalizes both the requested path and the entry paths, so
+    /// relative entries (Meson) and absolute entries (CMake) are matched alike.
+    pub fn find_entry_for_file(&self, file_path: &Path) -> Option<&Entry> {
+        let target = file_path
+            .canonicalize()
+            .unwrap_or_else(|_| file_path.to_path_buf());
+
+        self.entries.iter().find(|entry| {
+            let entry_path = if entry.file.is_relative() {
+                entry.directory.join(&entry.file)
+            } else {
+                entry.file.clone()
+            };
+            entry_path.canonicalize().unwrap_or(entry_path) == target
+        })
+    }
+
     /// Canonicalize a single entry path using the same logic for all paths
     ///
     /// This is the single source of truth for path canonicalization in the system.
@@ -157,6 +182,138 @@ impl CompilationDatabase {
     }
 }
 
+/// Replace `@file` response file arguments of an entry with the arguments they contain
+///
+/// clangd expands response files itself, but include paths and other flags are
+/// also read from the entry here. Response files that cannot be read are kept as
+/// `@file` arguments so the entry itself is never dropped.
+fn expand_response_files(mut entry: Entry) -> Entry {
+    if entry.arguments.iter().any(|arg| arg.starts_with('@')) {
+        entry.arguments = expand_arguments(&entry.arguments, &entry.directory, &entry.file, 0);
+    }
+    entry
+}
+
+/// Expand response files in `arguments`, resolving relative ones against `directory`
+fn expand_arguments(
+    arguments: &[String],
+    directory: &Path,
+    source_file: &Path,
+    depth: usize,
+) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(arguments.len());
+
+    for arg in arguments {
+        let Some(response_file) = arg.strip_prefix('@').filter(|name| !name.is_empty()) else {
+            expanded.push(arg.clone());
+            continue;
+        };
+
+        if depth >= MAX_RESPONSE_FILE_DEPTH {
+            warn!(
+                "Response file {} for {} nested too deeply, not expanded",
+                response_file,
+                source_file.display()
+            );
+            expanded.push(arg.clone());
+            continue;
+        }
+
+        let response_path = directory.join(response_file);
+        match std::fs::read_to_string(&response_path) {
+            Ok(content) => {
+                let nested = tokenize_response_file(&content);
+                debug!(
+                    "Expanded response file {} with {} arguments for {}",
+                    response_path.display(),
+                    nested.len(),
+                    source_file.display()
+                );
+                // Nested response files are relative to the including one
+                let nested_directory = response_path.parent().unwrap_or(directory);
+                expanded.extend(expand_arguments(
+                    &nested,
+                    nested_directory,
+                    source_file,
+                    depth + 1,
+                ));
+            }
+            Err(e) => {
+                warn!(
+                    "Cannot read response file {} for {}: {}",
+                    response_path.display(),
+                    source_file.display(),
+                    e
+                );
+                expanded.push(arg.clone());
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Split response file content into arguments using GNU command line rules
+///
+/// Arguments are separated by whitespace; single quotes keep their content
+/// literally, double quotes allow backslash escapes, and a backslash outside
+/// quotes escapes the next character.
+fn tokenize_response_file(content: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            '\\' => {
+                in_argument = true;
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            '\'' => {
+                in_argument = true;
+                for quoted in chars.by_ref() {
+                    if quoted == '\'' {
+                        break;
+                    }
+                    current.push(quoted);
+                }
+            }
+            '"' => {
+                in_argument = true;
+                while let Some(quoted) = chars.next() {
+                    match quoted {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                current.push(escaped);
+                            }
+                        }
+                        _ => current.push(quoted),
+                    }
+                }
+            }
+            _ => {
+                in_argument = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_argument {
+        arguments.push(current);
+    }
+    arguments
+}
+
 /// Custom serialization that only outputs the path field
 ///
 /// This ensures that when the CompilationDatabase is serialized (e.g., in JSON responses),
@@ -169,3 +326,62 @@ impl Serialize for CompilationDatabase {
         self.path.serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_response_file() {
+        let content = "-I include  -DNAME=\"a b\"\n'-Ipath with spaces' -DX=a\\ b\n";
+        assert_eq!(
+            tokenize_response_file(content),
+            vec![
+                "-I",
+                "include",
+                "-DNAME=a b",
+                "-Ipath with spaces",
+                "-DX=a b"
+            ]
+        );
+        assert!(tokenize_response_file(" \n\t").is_empty());
+        assert_eq!(tokenize_response_file("''"), vec![""]);
+    }
+
+    #[test]
+    fn test_response_files_expanded_on_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let build_dir = temp_dir.path();
+        std::fs::create_dir_all(build_dir.join("rsp")).unwrap();
+        std::fs::write(build_dir.join("rsp/main.rsp"), "-Iinclude @nested.rsp\n").unwrap();
+        std::fs::write(build_dir.join("rsp/nested.rsp"), "-DNESTED=1").unwrap();
+
+        let database_path = build_dir.join("compile_commands.json");
+        let entries = serde_json::json!([
+            {
+                "directory": build_dir,
+                "file": "main.cpp",
+                "arguments": ["c++", "@rsp/main.rsp", "-c", "main.cpp"]
+            },
+            {
+                "directory": build_dir,
+                "file": "other.cpp",
+                "arguments": ["c++", "@missing.rsp", "-c", "other.cpp"]
+            }
+        ]);
+        std::fs::write(&database_path, entries.to_string()).unwrap();
+
+        let database = CompilationDatabase::new(database_path).unwrap();
+        assert_eq!(
+            database.entries[0].arguments,
+            vec!["c++", "-Iinclude", "-DNESTED=1", "-c", "main.cpp"]
+        );
+
+        // Unreadable response files are kept and the entry is not dropped
+        assert_eq!(
+            database.entries[1].arguments,
+            vec!["c++", "@missing.rsp", "-c", "other.cpp"]
+        );
+        assert_eq!(database.canonical_source_files().unwrap().len(), 2);
+    }
+}
@@ -30,6 +30,29 @@ pub struct ProjectComponent {
 
     /// Raw build options and configuration (provider-specific key-value pairs)
     pub build_options: HashMap<String, String>,
+
+    /// Name of the build system preset that configures this build directory, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+}
+
+/// Build directory declared by a build system preset
+///
+/// Presets describe where a build will be configured, so the directory may not
+/// exist yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildPreset {
+    /// Preset name
+    pub name: String,
+
+    /// Build system provider type (e.g., "cmake")
+    pub provider_type: String,
+
+    /// Source directory declaring the preset
+    pub source_root_path: PathBuf,
+
+    /// Build directory the preset configures into
+    pub build_dir_path: PathBuf,
 }
 
 impl ProjectComponent {
@@ -76,6 +99,7 @@ impl ProjectComponent {
             generator,
             build_type,
             build_options,
+            preset: None,
         })
     }
 }
@@ -4,37 +4,81 @@
 //! instances for a single project component. This module encapsulates the lifecycle
 //! and operations for a specific build directory and its associated resources.
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, info, instrument, warn};
 
-use crate::clangd::config::DEFAULT_WORKSPACE_SYMBOL_LIMIT;
+#[cfg(test)]
+use crate::clangd::config::{CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT, default_indexing_threads};
+use crate::clangd::config::{DEFAULT_WORKSPACE_SYMBOL_LIMIT, PchStorage};
 use crate::clangd::file_manager::ClangdFileManager;
+use crate::clangd::index::ProgressEvent;
 use crate::clangd::session::ClangdSessionTrait;
 use crate::clangd::version::ClangdVersion;
 use crate::clangd::{ClangdConfigBuilder, ClangdSession, ClangdSessionBuilder};
 use crate::io::file_system::RealFileSystem;
+use crate::io::file_watcher::{FileChange, FileChangeKind, FileWatcher, file_watcher};
+use crate::lsp::reference_cache::{ReferenceCache, ReferenceKey};
+use crate::lsp::traits::LspClientTrait;
+use crate::lsp::{PositionEncoding, RequestCoalescer};
 #[cfg(all(test, feature = "clangd-integration-tests"))]
 use crate::project::index::ComponentIndexState;
+#[cfg(test)]
+use crate::project::index::location::{IndexDirectorySource, default_index_directory};
+use crate::project::index::location::{IndexLocation, IndexStorageOptions, prepare_index_location};
 use crate::project::index::reader::{IndexReader, IndexReaderTrait};
 use crate::project::index::storage::IndexStorage;
 use crate::project::index::storage::filesystem::FilesystemIndexStorage;
+use crate::project::index::version_check::{
+    IndexVersionCheck, check_index_version, record_index_version,
+};
 use crate::project::index::{
-    ClangdIndexTrigger, ComponentIndexMonitor, ComponentIndexingState, IndexStatusView,
+    ClangdIndexTrigger, ComponentIndexMonitor, ComponentIndexingState, EmptyResultRetry,
+    IndexQueueView, IndexSnapshot, IndexStatusView,
 };
 use crate::project::{CompilationDatabase, ProjectComponent, ProjectError};
+use crate::symbol::uri_from_pathbuf;
 
 /// Channel buffer size for progress event processing
 const PROGRESS_CHANNEL_BUFFER_SIZE: usize = 10_000;
 
+/// Number of index snapshots kept per session; older ones are discarded
+const MAX_INDEX_SNAPSHOTS: usize = 16;
+
+/// Process-level settings applied to every clangd a session launches
+#[derive(Debug, Clone, Default)]
+pub struct ClangdProcessOptions {
+    /// Additional clangd arguments (e.g. `-j` to limit indexing threads)
+    pub args: Vec<String>,
+    /// Environment variables set on top of the server's own environment
+    pub env: BTreeMap<String, String>,
+    /// Index directory and static index overrides
+    pub index: IndexStorageOptions,
+    /// Run clangd without background index, answering from the files it parses on demand
+    pub quiet_indexing: bool,
+}
+
+/// Settings of the clangd process a session was started with
+struct ClangdProcessSettings {
+    indexing_threads: u32,
+    workspace_symbol_limit: Option<u32>,
+    index_location: IndexLocation,
+    quiet_indexing: bool,
+    pch_storage: PchStorage,
+}
+
 /// Manages ClangdSession and ComponentIndexMonitor for a single project component
 ///
 /// `ComponentSession` encapsulates all resources needed for a specific build directory,
 /// including the clangd session, index monitoring, and component-specific operations.
 /// This provides a cleaner abstraction for component lifecycle management.
 pub struct ComponentSession {
+    /// Unique identifier of this session, stable for its lifetime
+    session_id: String,
     /// Build directory for this component
     build_dir: PathBuf,
     /// ClangdSession for LSP communication (wrapped for background task access)
@@ -43,6 +87,36 @@ pub struct ComponentSession {
     file_manager: Arc<tokio::sync::Mutex<ClangdFileManager>>,
     /// ComponentIndexMonitor for index state tracking
     index_monitor: Arc<ComponentIndexMonitor>,
+    /// Compilation database loaded for this component
+    compilation_database: Arc<CompilationDatabase>,
+    /// Shares results between identical concurrent LSP requests
+    request_coalescer: RequestCoalescer,
+    /// Advances whenever indexing results or file contents change
+    index_generation: Arc<AtomicU64>,
+    /// References results of the current index generation
+    reference_cache: ReferenceCache,
+    /// Number of threads clangd uses for background indexing
+    indexing_threads: u32,
+    /// Maximum number of `workspace/symbol` results clangd returns, if limited
+    workspace_symbol_limit: Option<u32>,
+    /// Whether clangd runs without background index (quiet indexing mode)
+    quiet_indexing: bool,
+    /// Where clangd stores the preambles of open files
+    pch_storage: PchStorage,
+    /// Backoff for empty query results right after indexing completes
+    empty_result_retry: EmptyResultRetry,
+    /// Most recent index snapshots, oldest first
+    index_snapshots: std::sync::Mutex<VecDeque<IndexSnapshot>>,
+    /// Identifier of the next index snapshot
+    next_snapshot_id: AtomicU64,
+    /// Version of the clangd binary serving this session
+    clangd_version: ClangdVersion,
+    /// Where clangd keeps the index of this component
+    index_location: IndexLocation,
+    /// Translation unit whose compile command clangd uses for a file, by file
+    translation_unit_contexts: std::sync::Mutex<HashMap<PathBuf, PathBuf>>,
+    /// Watches files opened by tools, so changes on disk reach clangd
+    file_watcher: Box<dyn FileWatcher>,
     /// Component metadata
     #[allow(dead_code)]
     component: ProjectComponent,
@@ -55,7 +129,10 @@ impl ComponentSession {
     /// * `component` - The project component this session represents
     /// * `clangd_path` - Path to the clangd executable
     /// * `clangd_version` - Detected clangd version information
-    /// * `project_root` - Project root directory for clangd working directory
+    /// * `root_uri` - LSP workspace root URI sent to clangd in `initialize`
+    /// * `working_directory` - Directory the clangd process runs in
+    /// * `global_compilation_database` - Compilation database overriding the component's own
+    /// * `clangd_process` - Additional clangd arguments and environment variables
     ///
     /// # Returns
     /// * `Ok(ComponentSession)` - Successfully created component session
@@ -65,29 +142,76 @@ impl ComponentSession {
         component: ProjectComponent,
         clangd_path: &str,
         clangd_version: &ClangdVersion,
-        project_root: PathBuf,
+        root_uri: String,
+        working_directory: PathBuf,
+        global_compilation_database: Option<&CompilationDatabase>,
+        clangd_process: &ClangdProcessOptions,
     ) -> Result<Self, ProjectError> {
         info!(
             "Creating ComponentSession for build dir: {}",
             component.build_dir_path.display()
         );
 
-        // Load the compilation database from the component path
-        let compilation_database = CompilationDatabase::new(
-            component.compilation_database_path.clone(),
-        )
-        .map_err(|_e| ProjectError::CompilationDatabaseNotFound {
-            path: component
-                .compilation_database_path
-                .to_string_lossy()
-                .to_string(),
-        })?;
+        // Use the global compilation database if configured, otherwise load the component's own
+        let compilation_database = match global_compilation_database {
+            Some(database) => {
+                info!(
+                    "Using global compilation database: {}",
+                    database.path().display()
+                );
+                database.clone()
+            }
+            // Fail before launching clangd, which would start fine and answer every query empty
+            None if !component.compilation_database_path.exists() => {
+                return Err(ProjectError::missing_compile_commands(
+                    &component.provider_type,
+                    &component.build_dir_path,
+                    &component.source_root_path,
+                ));
+            }
+            None => CompilationDatabase::new(component.compilation_database_path.clone()).map_err(
+                |_e| ProjectError::CompilationDatabaseNotFound {
+                    path: component
+                        .compilation_database_path
+                        .to_string_lossy()
+                        .to_string(),
+                },
+            )?,
+        };
         let compilation_database = Arc::new(compilation_database);
 
         // Build configuration using builder pattern
-        let config = ClangdConfigBuilder::new()
-            .working_directory(project_root)
-            .build_directory(component.build_dir_path.clone())
+        let mut config_builder = ClangdConfigBuilder::new()
+            .working_directory(working_directory)
+            .root_uri(root_uri)
+            .build_directory(component.build_dir_path.clone());
+        if let Some(compile_commands_dir) =
+            global_compilation_database.and_then(|database| database.path().parent())
+        {
+            // clangd resolves relative paths against its working directory, not ours
+            let compile_commands_dir = std::path::absolute(compile_commands_dir)
+                .unwrap_or_else(|_| compile_commands_dir.to_path_buf());
+            config_builder = config_builder.compile_commands_dir(compile_commands_dir);
+        }
+
+        // clangd keeps its background index next to the compilation database it reads
+        let compile_commands_dir = global_compilation_database
+            .and_then(|database| database.path().parent())
+            .unwrap_or(&component.build_dir_path);
+        let index_location = prepare_index_location(
+            &std::path::absolute(compile_commands_dir)
+                .unwrap_or_else(|_| compile_commands_dir.to_path_buf()),
+            &clangd_process.index,
+        )?;
+        if let Some(index_file) = &index_location.static_index_file {
+            config_builder =
+                config_builder.add_arg(format!("--index-file={}", index_file.display()));
+        }
+        if clangd_process.quiet_indexing {
+            info!("Quiet indexing: clangd background index disabled");
+            config_builder = config_builder.add_arg("--background-index=false");
+        }
+        let config = config_builder
             .clangd_path(clangd_path.to_string())
             .add_arg(format!(
                 "--limit-results={}",
@@ -95,11 +219,19 @@ impl ComponentSession {
             ))
             .add_arg("--query-driver=**")
             .add_arg("--log=verbose")
+            .add_args(clangd_process.args.iter().cloned())
+            .envs(clangd_process.env.clone())
             .build()
             .map_err(|e| ProjectError::SessionCreation(format!("Failed to build config: {}", e)))?;
 
+        let indexing_threads = config.indexing_threads();
+        info!("clangd indexing threads: {}", indexing_threads);
+        let workspace_symbol_limit = config.workspace_symbol_limit();
+        let pch_storage = config.pch_storage();
+        info!("clangd PCH storage: {}", pch_storage);
+
         // Initialize progress event channel for index state tracking
-        let (progress_tx, mut progress_rx) = mpsc::channel(PROGRESS_CHANNEL_BUFFER_SIZE);
+        let (progress_tx, progress_rx) = mpsc::channel(PROGRESS_CHANNEL_BUFFER_SIZE);
 
         // Construct ClangdSession with progress event integration
         let session = ClangdSessionBuilder::new()
@@ -111,6 +243,86 @@ impl ComponentSession {
                 ProjectError::SessionCreation(format!("Failed to create session: {}", e))
             })?;
 
+        Self::from_clangd_session(
+            component,
+            compilation_database,
+            clangd_version,
+            session,
+            progress_rx,
+            ClangdProcessSettings {
+                indexing_threads,
+                workspace_symbol_limit,
+                index_location,
+                quiet_indexing: clangd_process.quiet_indexing,
+                pch_storage,
+            },
+        )
+        .await
+    }
+
+    /// Create a ComponentSession around a scripted clangd session (for testing)
+    ///
+    /// Skips config building and process startup so tool logic can run against
+    /// `crate::clangd::testing::MockClangd` instead of a real clangd binary.
+    #[cfg(test)]
+    pub(crate) async fn with_clangd_session(
+        component: ProjectComponent,
+        clangd_version: &ClangdVersion,
+        session: ClangdSession,
+        progress_rx: mpsc::Receiver<ProgressEvent>,
+        quiet_indexing: bool,
+    ) -> Result<Self, ProjectError> {
+        let compilation_database = CompilationDatabase::new(
+            component.compilation_database_path.clone(),
+        )
+        .map_err(|_e| ProjectError::CompilationDatabaseNotFound {
+            path: component
+                .compilation_database_path
+                .to_string_lossy()
+                .to_string(),
+        })?;
+
+        let settings = ClangdProcessSettings {
+            indexing_threads: default_indexing_threads(),
+            workspace_symbol_limit: Some(CLANGD_BUILTIN_WORKSPACE_SYMBOL_LIMIT),
+            index_location: IndexLocation {
+                directory: default_index_directory(&component.build_dir_path),
+                source: IndexDirectorySource::Default,
+                read_only: false,
+                static_index_file: None,
+                warning: None,
+            },
+            quiet_indexing,
+            pch_storage: PchStorage::Disk,
+        };
+        Self::from_clangd_session(
+            component,
+            Arc::new(compilation_database),
+            clangd_version,
+            session,
+            progress_rx,
+            settings,
+        )
+        .await
+    }
+
+    /// Wire index monitoring around an already started clangd session
+    async fn from_clangd_session(
+        component: ProjectComponent,
+        compilation_database: Arc<CompilationDatabase>,
+        clangd_version: &ClangdVersion,
+        session: ClangdSession,
+        mut progress_rx: mpsc::Receiver<ProgressEvent>,
+        settings: ClangdProcessSettings,
+    ) -> Result<Self, ProjectError> {
+        let ClangdProcessSettings {
+            indexing_threads,
+            workspace_symbol_limit,
+            index_location,
+            quiet_indexing,
+            pch_storage,
+        } = settings;
+
         // Wrap in Arc<Mutex> for sharing with background tasks
         let clangd_session = Arc::new(tokio::sync::Mutex::new(session));
 
@@ -120,53 +332,188 @@ impl ComponentSession {
         // Create ComponentIndexMonitor for this component
         let index_monitor = Self::create_index_monitor(
             &component,
+            &index_location.directory,
             compilation_database.clone(),
             clangd_version,
             Arc::clone(&clangd_session),
             Arc::clone(&file_manager),
+            !quiet_indexing,
         )
         .await?;
 
         // Launch background processor for progress events
         let monitor_clone = Arc::clone(&index_monitor);
+        let index_generation = Arc::new(AtomicU64::new(0));
+        let generation_clone = Arc::clone(&index_generation);
         tokio::spawn(async move {
             while let Some(event) = progress_rx.recv().await {
+                if Self::changes_index(&event) {
+                    generation_clone.fetch_add(1, Ordering::SeqCst);
+                }
                 monitor_clone.handle_progress_event(event).await;
             }
         });
 
+        // Send changes of open files made outside tool calls to clangd
+        let file_watcher = file_watcher(RealFileSystem);
+        tokio::spawn(Self::sync_changed_files(
+            file_watcher.subscribe(),
+            Arc::clone(&clangd_session),
+            Arc::clone(&file_manager),
+            Arc::clone(&index_generation),
+        ));
+
         debug!(
             "ComponentSession created successfully for build dir: {}",
             component.build_dir_path.display()
         );
 
         Ok(Self {
+            session_id: uuid::Uuid::new_v4().to_string(),
             build_dir: component.build_dir_path.clone(),
             clangd_session,
             file_manager,
             index_monitor,
+            compilation_database,
+            request_coalescer: RequestCoalescer::new(),
+            index_generation,
+            reference_cache: ReferenceCache::new(),
+            indexing_threads,
+            workspace_symbol_limit,
+            quiet_indexing,
+            pch_storage,
+            empty_result_retry: EmptyResultRetry::default(),
+            index_snapshots: std::sync::Mutex::new(VecDeque::new()),
+            next_snapshot_id: AtomicU64::new(1),
+            clangd_version: clangd_version.clone(),
+            index_location,
+            translation_unit_contexts: std::sync::Mutex::new(HashMap::new()),
+            file_watcher,
             component,
         })
     }
 
+    /// Send the new content of open files changed on disk to clangd
+    ///
+    /// Runs until the file watcher is dropped with the session.
+    async fn sync_changed_files(
+        mut changes: tokio::sync::broadcast::Receiver<Vec<FileChange>>,
+        clangd_session: Arc<tokio::sync::Mutex<ClangdSession>>,
+        file_manager: Arc<tokio::sync::Mutex<ClangdFileManager>>,
+        index_generation: Arc<AtomicU64>,
+    ) {
+        loop {
+            let changes = match changes.recv().await {
+                Ok(changes) => changes,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Missed {} batches of file changes", skipped);
+                    continue;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+            for change in changes {
+                // Removed files stay open with their last content
+                if change.kind == FileChangeKind::Removed {
+                    continue;
+                }
+                let mut session = clangd_session.lock().await;
+                let mut file_manager = file_manager.lock().await;
+                if !file_manager.is_file_open(&change.path) {
+                    continue;
+                }
+                debug!("Open file {} changed on disk", change.path.display());
+                match file_manager
+                    .ensure_file_ready(&change.path, session.client_mut())
+                    .await
+                {
+                    Ok(true) => {
+                        index_generation.fetch_add(1, Ordering::SeqCst);
+                    }
+                    Ok(false) => {}
+                    Err(e) => warn!(
+                        "Failed to send changed file {}: {}",
+                        change.path.display(),
+                        e
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Watch a file opened in clangd for changes on disk
+    fn watch_open_file(&self, path: &Path) {
+        if let Ok(abs_path) = path.canonicalize() {
+            self.file_watcher.watch(&abs_path);
+        }
+    }
+
+    /// Whether a progress event means indexed symbols or references changed
+    fn changes_index(event: &ProgressEvent) -> bool {
+        matches!(
+            event,
+            ProgressEvent::FileIndexingCompleted { .. }
+                | ProgressEvent::FileAstIndexed { .. }
+                | ProgressEvent::StandardLibraryCompleted { .. }
+                | ProgressEvent::OverallCompleted
+        )
+    }
+
+    /// Configure retries of empty query results right after indexing completes
+    pub fn with_empty_result_retry(mut self, retry: EmptyResultRetry) -> Self {
+        self.empty_result_retry = retry;
+        self
+    }
+
     /// Create a ComponentIndexMonitor for the component
+    ///
+    /// Initial indexing of the compilation database is triggered unless
+    /// `trigger_indexing` is false (quiet indexing mode).
     async fn create_index_monitor(
         component: &ProjectComponent,
+        index_directory: &Path,
         compilation_database: Arc<CompilationDatabase>,
         clangd_version: &ClangdVersion,
         session: Arc<tokio::sync::Mutex<ClangdSession>>,
         file_manager: Arc<tokio::sync::Mutex<ClangdFileManager>>,
+        trigger_indexing: bool,
     ) -> Result<Arc<ComponentIndexMonitor>, ProjectError> {
         let build_dir = &component.build_dir_path;
 
-        // Create index reader with filesystem storage
-        let index_directory = build_dir.join(".cache/clangd/index");
-
         // Use the centralized version mapping from ClangdVersion
         let expected_version = clangd_version.index_format_version();
 
+        // An index written by another clangd format is rebuilt as a whole; skip
+        // validating it file by file, which would only report version mismatches
+        let scan_existing_index = match check_index_version(index_directory, clangd_version) {
+            IndexVersionCheck::Stale {
+                found,
+                expected,
+                written_by,
+            } => {
+                warn!(
+                    "clangd index in {} uses format v{}{} but clangd {} uses v{}: the whole index will be rebuilt",
+                    index_directory.display(),
+                    found,
+                    written_by
+                        .map(|version| format!(" (written by clangd {})", version))
+                        .unwrap_or_default(),
+                    clangd_version,
+                    expected
+                );
+                false
+            }
+            IndexVersionCheck::Empty | IndexVersionCheck::Compatible => true,
+        };
+        if let Err(e) = record_index_version(index_directory, clangd_version) {
+            warn!(
+                "Failed to record index version in {}: {}",
+                index_directory.display(),
+                e
+            );
+        }
+
         let storage: Arc<dyn IndexStorage> = Arc::new(FilesystemIndexStorage::new(
-            index_directory,
+            index_directory.to_path_buf(),
             expected_version,
             RealFileSystem,
         ));
@@ -184,11 +531,17 @@ impl ComponentSession {
             index_reader,
             clangd_version,
             Some(index_trigger),
+            scan_existing_index,
         )
         .await?;
 
         // Trigger initial indexing using the ComponentIndexMonitor
-        if let Err(e) = monitor
+        if !trigger_indexing {
+            info!(
+                "Quiet indexing: not triggering initial indexing for {}",
+                build_dir.display()
+            );
+        } else if let Err(e) = monitor
             .trigger_initial_indexing(compilation_database.clone())
             .await
         {
@@ -217,10 +570,226 @@ impl ComponentSession {
         let mut session = self.clangd_session.lock().await;
         let mut file_manager = self.file_manager.lock().await;
 
-        file_manager
+        let sent = file_manager
             .ensure_file_ready(path, session.client_mut())
             .await
-            .map_err(|e| ProjectError::SessionCreation(format!("File management failed: {}", e)))
+            .map_err(|e| ProjectError::SessionCreation(format!("File management failed: {}", e)))?;
+        self.watch_open_file(path);
+        if sent {
+            self.advance_index_generation();
+        }
+        Ok(())
+    }
+
+    /// Get the paths of the files currently open in clangd
+    pub async fn open_files(&self) -> Vec<PathBuf> {
+        self.file_manager.lock().await.open_files()
+    }
+
+    /// Get the LSP document version clangd holds for a file, `None` when it is not open
+    pub async fn document_version(&self, path: &std::path::Path) -> Option<i32> {
+        self.file_manager.lock().await.document_version(path)
+    }
+
+    /// Apply ranged edits to a file in clangd without writing them to disk
+    ///
+    /// Diagnostics cached for the file are dropped, so the next wait for
+    /// diagnostics returns those of the edited content. Call `restore_file()`
+    /// when done; other requests on the file see the edited content until then.
+    ///
+    /// Returns the document version of the edited content.
+    pub async fn apply_in_memory_changes(
+        &self,
+        path: &std::path::Path,
+        changes: Vec<lsp_types::TextDocumentContentChangeEvent>,
+    ) -> Result<i32, ProjectError> {
+        let mut session = self.clangd_session.lock().await;
+        let mut file_manager = self.file_manager.lock().await;
+
+        if let Ok(abs_path) = path.canonicalize() {
+            session
+                .diagnostics_monitor()
+                .invalidate(&uri_from_pathbuf(&abs_path));
+        }
+        let version = file_manager
+            .apply_changes(path, changes, session.client_mut())
+            .await
+            .map_err(|e| ProjectError::SessionCreation(format!("File management failed: {}", e)))?;
+        self.watch_open_file(path);
+        self.advance_index_generation();
+        Ok(version)
+    }
+
+    /// Discard in-memory edits of a file, sending its on-disk content to clangd again
+    ///
+    /// Returns whether the file had in-memory edits.
+    pub async fn restore_file(&self, path: &std::path::Path) -> Result<bool, ProjectError> {
+        let mut session = self.clangd_session.lock().await;
+        let mut file_manager = self.file_manager.lock().await;
+
+        let restored = file_manager
+            .restore_file(path, session.client_mut())
+            .await
+            .map_err(|e| ProjectError::SessionCreation(format!("File management failed: {}", e)))?;
+        if restored {
+            self.advance_index_generation();
+            if let Ok(abs_path) = path.canonicalize() {
+                session
+                    .diagnostics_monitor()
+                    .invalidate(&uri_from_pathbuf(&abs_path));
+            }
+        }
+        Ok(restored)
+    }
+
+    /// Wait until clangd has parsed the current content of a file
+    ///
+    /// Opens or updates the file like `ensure_file_ready()`, then waits for clangd
+    /// to finish building its AST. This readiness barrier is much cheaper than
+    /// `ensure_indexed()` and is all that single-file operations need.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The file is parsed
+    /// * `Ok(false)` - Timed out while clangd was still parsing the file
+    pub async fn wait_until_file_parsed(
+        &self,
+        path: &std::path::Path,
+        timeout: Duration,
+    ) -> Result<bool, ProjectError> {
+        let abs_path = path.canonicalize().map_err(|e| {
+            ProjectError::SessionCreation(format!("Invalid file path {}: {}", path.display(), e))
+        })?;
+        let uri = uri_from_pathbuf(&abs_path);
+
+        let (monitor, after) = {
+            let mut session = self.clangd_session.lock().await;
+            let mut file_manager = self.file_manager.lock().await;
+            let monitor = session.file_status_monitor().clone();
+
+            // Parses completed before a didOpen/didChange refer to older content
+            let parses_before = monitor.parse_count(&uri);
+            let sent = file_manager
+                .ensure_file_ready(&abs_path, session.client_mut())
+                .await
+                .map_err(|e| {
+                    ProjectError::SessionCreation(format!("File management failed: {}", e))
+                })?;
+            self.watch_open_file(&abs_path);
+            if sent {
+                self.advance_index_generation();
+            }
+            (monitor, if sent { parses_before } else { 0 })
+        };
+
+        let parsed = monitor.wait_until_parsed(&uri, after, timeout).await;
+        if !parsed {
+            debug!(
+                "File {} not parsed within {:?} (state: {:?}, preamble: {:?})",
+                abs_path.display(),
+                timeout,
+                monitor.state(&uri),
+                monitor.preamble_status(&uri)
+            );
+        }
+        Ok(parsed)
+    }
+
+    /// Parse a file in the preprocessor context of a translation unit
+    ///
+    /// clangd is told to compile `file` with the compile command of
+    /// `context_file`, so symbols that depend on its `-D` flags resolve as in
+    /// that translation unit. The context stays in effect for `file` until
+    /// another one is set. When the file is open, waits up to `timeout` for
+    /// clangd to parse it again.
+    ///
+    /// # Returns
+    /// * `Ok(())` - clangd uses the context for `file`
+    /// * `Err(ProjectError::NotInCompilationDatabase)` - `context_file` has no compile command
+    pub async fn set_translation_unit_context(
+        &self,
+        file: &Path,
+        context_file: &Path,
+        timeout: Duration,
+    ) -> Result<(), ProjectError> {
+        let file = file
+            .canonicalize()
+            .map_err(|_| ProjectError::PathNotFound {
+                path: file.display().to_string(),
+            })?;
+        let context_file = context_file
+            .canonicalize()
+            .map_err(|_| ProjectError::PathNotFound {
+                path: context_file.display().to_string(),
+            })?;
+        if self
+            .translation_unit_contexts
+            .lock()
+            .unwrap()
+            .get(&file)
+            .is_some_and(|current| *current == context_file)
+        {
+            return Ok(());
+        }
+
+        let entry = self
+            .compilation_database
+            .find_entry_for_file(&context_file)
+            .ok_or_else(|| ProjectError::NotInCompilationDatabase {
+                path: context_file.display().to_string(),
+            })?;
+        let command = context_compile_command(entry, &context_file, &file);
+        info!(
+            "Analyzing {} in the context of {}",
+            file.display(),
+            context_file.display()
+        );
+
+        let uri = uri_from_pathbuf(&file);
+        let mut session = self.clangd_session.lock().await;
+        let monitor = session.file_status_monitor().clone();
+        let parses_before = monitor.parse_count(&uri);
+        let is_open = self
+            .file_manager
+            .lock()
+            .await
+            .document_version(&file)
+            .is_some();
+
+        let settings = serde_json::json!({
+            "compilationDatabaseChanges": {
+                file.to_string_lossy(): {
+                    "workingDirectory": entry.directory,
+                    "compilationCommand": command,
+                }
+            }
+        });
+        session
+            .client_mut()
+            .change_configuration(settings)
+            .await
+            .map_err(|e| {
+                ProjectError::SessionCreation(format!("Failed to change compile command: {}", e))
+            })?;
+        drop(session);
+
+        self.translation_unit_contexts
+            .lock()
+            .unwrap()
+            .insert(file.clone(), context_file);
+
+        // clangd rebuilds open files whose compile command changed
+        if is_open
+            && !monitor
+                .wait_until_parsed(&uri, parses_before, timeout)
+                .await
+        {
+            debug!(
+                "File {} not parsed again within {:?} after changing its context",
+                file.display(),
+                timeout
+            );
+        }
+        Ok(())
     }
 
     /// Get mutable access to the LSP session
@@ -231,20 +800,176 @@ impl ComponentSession {
         self.clangd_session.lock().await
     }
 
+    /// Run an LSP request, sharing its result with identical concurrent requests
+    ///
+    /// Tools running in parallel on the same symbol issue the same requests; only
+    /// one of them reaches clangd while the others wait for its result. The
+    /// `request` closure should cover `ensure_file_ready()` and the `lsp_session()`
+    /// call so that waiting callers do not hold the session lock.
+    pub async fn coalesced<P, R, E, F, Fut>(
+        &self,
+        method: &str,
+        params: &P,
+        request: F,
+    ) -> Result<R, E>
+    where
+        P: serde::Serialize + ?Sized,
+        R: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<R, E>>,
+    {
+        self.request_coalescer.run(method, params, request).await
+    }
+
+    /// Get the current index generation
+    ///
+    /// The generation advances whenever clangd finishes indexing a file or the
+    /// content clangd sees for a file changes, so results computed at an older
+    /// generation may be outdated.
+    pub fn index_generation(&self) -> u64 {
+        self.index_generation.load(Ordering::SeqCst)
+    }
+
+    /// Record that indexing results or file contents changed
+    fn advance_index_generation(&self) {
+        self.index_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Get references cached for a request at the given index generation
+    pub fn cached_references(
+        &self,
+        generation: u64,
+        key: &ReferenceKey,
+    ) -> Option<Vec<lsp_types::Location>> {
+        self.reference_cache.get(generation, key)
+    }
+
+    /// Cache references computed at the given index generation
+    pub fn cache_references(
+        &self,
+        generation: u64,
+        key: ReferenceKey,
+        locations: Vec<lsp_types::Location>,
+    ) {
+        self.reference_cache.insert(generation, key, locations);
+    }
+
+    /// Get the position encoding negotiated with clangd
+    ///
+    /// Columns in positions exchanged with clangd are counted in this encoding.
+    pub async fn position_encoding(&self) -> PositionEncoding {
+        self.clangd_session
+            .lock()
+            .await
+            .client()
+            .position_encoding()
+    }
+
+    /// Get the unique identifier of this session
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
     /// Get the build directory for this component
     pub fn build_dir(&self) -> &PathBuf {
         &self.build_dir
     }
 
+    /// Whether clangd runs without background index (quiet indexing mode)
+    ///
+    /// Queries are then answered from the files clangd has parsed on demand only.
+    pub fn quiet_indexing(&self) -> bool {
+        self.quiet_indexing
+    }
+
+    /// Get the maximum number of `workspace/symbol` results clangd returns
+    ///
+    /// `None` when clangd was started with `--limit-results=0`.
+    pub fn workspace_symbol_limit(&self) -> Option<u32> {
+        self.workspace_symbol_limit
+    }
+
+    /// Get the version of the clangd binary serving this session
+    pub fn clangd_version(&self) -> &ClangdVersion {
+        &self.clangd_version
+    }
+
+    /// Get where clangd keeps the index of this component
+    pub fn index_location(&self) -> &IndexLocation {
+        &self.index_location
+    }
+
+    /// Get the compilation database loaded for this component
+    pub fn compilation_database(&self) -> &CompilationDatabase {
+        &self.compilation_database
+    }
+
     /// Wait for indexing completion before proceeding with LSP operations
     ///
     /// This method waits for clangd to complete indexing and ensures that all files
     /// in the compilation database have been indexed. This is what tools need to
     /// call before making LSP requests to ensure accurate results.
+    ///
+    /// Returns right away in quiet indexing mode, where clangd builds no
+    /// background index to wait for.
     pub async fn ensure_indexed(&self, timeout: Duration) -> Result<(), ProjectError> {
+        if self.quiet_indexing {
+            return Ok(());
+        }
         self.wait_for_indexing_completion(timeout).await
     }
 
+    /// Run a query, retrying it while it is empty shortly after indexing completed
+    ///
+    /// clangd can answer workspace queries from a partially loaded index right
+    /// after it reports indexing completion; see `EmptyResultRetry`.
+    pub async fn retry_while_empty<T, E, F, Fut>(
+        &self,
+        is_empty: impl Fn(&T) -> bool,
+        query: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        self.empty_result_retry
+            .run(
+                || self.index_monitor.time_since_completion(),
+                is_empty,
+                query,
+            )
+            .await
+    }
+
+    /// Snapshot the current index state and keep it for later diffs
+    pub async fn take_index_snapshot(&self) -> IndexSnapshot {
+        let id = self
+            .next_snapshot_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string();
+        let snapshot = self.index_monitor.snapshot(id).await;
+
+        let mut snapshots = self.index_snapshots.lock().unwrap();
+        if snapshots.len() >= MAX_INDEX_SNAPSHOTS {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot.clone());
+        snapshot
+    }
+
+    /// Get a previously taken index snapshot by its id
+    ///
+    /// Returns None for unknown ids and for snapshots discarded to make room
+    /// for newer ones.
+    pub fn index_snapshot(&self, id: &str) -> Option<IndexSnapshot> {
+        self.index_snapshots
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|snapshot| snapshot.id == id)
+            .cloned()
+    }
+
     /// Get component indexing state
     #[cfg(all(test, feature = "clangd-integration-tests"))]
     pub async fn get_index_state(&self) -> ComponentIndexState {
@@ -278,7 +1003,7 @@ impl ComponentSession {
     /// Creates IndexStatusView on retrieval with comprehensive progress data
     /// including ETA calculation if applicable.
     pub async fn get_index_status(&self) -> IndexStatusView {
-        let (component_state, start_time) = self.index_monitor.get_progress_data().await;
+        let (component_state, start) = self.index_monitor.get_progress_data().await;
 
         // Determine if indexing is in progress
         let in_progress = matches!(component_state.state, ComponentIndexingState::InProgress(_));
@@ -293,19 +1018,238 @@ impl ComponentSession {
 
         // Format state as human-readable string
         let state_str = match component_state.state {
+            _ if self.quiet_indexing => "Disabled".to_string(),
+            _ if component_state.model_building => "BuildingIndexModel".to_string(),
             ComponentIndexingState::Init => "Init".to_string(),
             ComponentIndexingState::InProgress(percent) => format!("InProgress({:.1}%)", percent),
             ComponentIndexingState::Partial => "Partial".to_string(),
             ComponentIndexingState::Completed => "Completed".to_string(),
         };
 
+        let queue = IndexQueueView::new(
+            component_state.pending_cdb_files,
+            component_state.in_progress_cdb_files,
+            component_state.failed_cdb_files,
+            component_state.indexed_cdb_files,
+            start,
+        );
+
         IndexStatusView::new(
             in_progress,
             progress_percentage,
             component_state.indexed_cdb_files,
             component_state.total_cdb_files,
-            start_time,
+            start.map(|(start_time, _)| start_time),
             state_str,
         )
+        .with_queue(queue)
+        .with_indexing_threads(self.indexing_threads)
+        .with_pch_storage(self.pch_storage)
+        .with_model_building(component_state.model_building)
+    }
+
+    /// Gracefully shut down the clangd session of this component
+    ///
+    /// Other holders of this session keep a valid handle, but any further LSP
+    /// request through it fails.
+    pub async fn shutdown(&self) {
+        info!(
+            "Shutting down ComponentSession {} for build dir: {}",
+            self.session_id,
+            self.build_dir.display()
+        );
+
+        let mut session = self.clangd_session.lock().await;
+        if let Err(e) = session.shutdown().await {
+            warn!("Clangd session shutdown reported an error: {}", e);
+        }
+    }
+}
+
+/// Compile command of `entry` with its source file replaced by `file`
+fn context_compile_command(
+    entry: &json_compilation_db::Entry,
+    context_file: &Path,
+    file: &Path,
+) -> Vec<String> {
+    let is_context_file = |argument: &str| {
+        let path = entry.directory.join(argument);
+        path.canonicalize().is_ok_and(|path| path == context_file)
+    };
+
+    let mut replaced = false;
+    let mut command: Vec<String> = entry
+        .arguments
+        .iter()
+        .map(|argument| {
+            if !replaced && !argument.starts_with('-') && is_context_file(argument) {
+                replaced = true;
+                file.to_string_lossy().to_string()
+            } else {
+                argument.clone()
+            }
+        })
+        .collect();
+    if !replaced {
+        command.push(file.to_string_lossy().to_string());
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::MockClangd;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+
+    /// Params of the configuration changes received so far, after waiting for `count`
+    async fn configuration_changes(server: &MockClangd, count: usize) -> Vec<serde_json::Value> {
+        for _ in 0..200 {
+            let received = server.received_params("workspace/didChangeConfiguration");
+            if received.len() >= count {
+                return received.into_iter().flatten().collect();
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("expected {} configuration changes", count);
+    }
+
+    #[tokio::test]
+    async fn test_quiet_indexing_skips_trigger_and_wait() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let opened = |server: MockClangd| async move {
+            // Notifications are recorded by the mock server asynchronously
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            server.received_params("textDocument/didOpen").len()
+        };
+
+        // The initial indexing trigger opens the first source file
+        let server = MockClangd::new();
+        let session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        assert!(!session.quiet_indexing());
+        assert_eq!(opened(server.clone()).await, 1);
+
+        let server = MockClangd::new();
+        let options = ClangdProcessOptions {
+            quiet_indexing: true,
+            ..Default::default()
+        };
+        let session = server
+            .component_session_with_options(&workspace.components[0], &options)
+            .await
+            .unwrap();
+        assert!(session.quiet_indexing());
+        assert_eq!(opened(server.clone()).await, 0);
+
+        // Nothing to wait for: returns at once instead of timing out
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            session.ensure_indexed(Duration::from_secs(60)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let status = session.get_index_status().await;
+        assert_eq!(status.state, "Disabled");
+        assert!(status.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_open_files_changed_on_disk_are_sent_to_clangd() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let source = workspace.project_root_path.join("src/main.cpp");
+        let server = MockClangd::new();
+        let session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        session.ensure_file_ready(&source).await.unwrap();
+        let generation = session.index_generation();
+
+        // Edited outside of any tool call
+        std::fs::write(&source, "int main() { return 1; }\n").unwrap();
+        for _ in 0..500 {
+            if !server.received_params("textDocument/didChange").is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let changes = server.received_params("textDocument/didChange");
+        assert_eq!(changes.len(), 1);
+        assert!(
+            changes[0]
+                .as_ref()
+                .unwrap()
+                .to_string()
+                .contains("return 1;")
+        );
+        assert!(session.index_generation() > generation);
+    }
+
+    #[tokio::test]
+    async fn test_translation_unit_context_uses_context_compile_command() {
+        let (_temp_dir, workspace) = create_mock_project(&[
+            ("src/backend.cpp", "#include \"backend.h\"\n"),
+            ("src/backend.h", "#ifdef USE_GPU\nvoid run();\n#endif\n"),
+        ]);
+        let root = &workspace.project_root_path;
+        let server = MockClangd::new();
+        let session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+        let header = root.join("src/backend.h");
+        let source = root.join("src/backend.cpp");
+
+        session
+            .set_translation_unit_context(&header, &source, Duration::ZERO)
+            .await
+            .unwrap();
+        // Setting the same context again does not reconfigure clangd
+        session
+            .set_translation_unit_context(&header, &source, Duration::ZERO)
+            .await
+            .unwrap();
+
+        let changes = configuration_changes(&server, 1).await;
+        assert_eq!(changes.len(), 1);
+        let change = &changes[0]["settings"]["compilationDatabaseChanges"]
+            [header.to_string_lossy().as_ref()];
+        assert_eq!(
+            change["compilationCommand"],
+            serde_json::json!(["c++", "-c", header])
+        );
+        assert_eq!(
+            change["workingDirectory"],
+            serde_json::json!(workspace.components[0].build_dir_path)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translation_unit_context_requires_compile_command() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let root = &workspace.project_root_path;
+        std::fs::write(root.join("src/tool.cpp"), "").unwrap();
+        let server = MockClangd::new();
+        let session = server
+            .component_session(&workspace.components[0])
+            .await
+            .unwrap();
+
+        let result = session
+            .set_translation_unit_context(
+                &root.join("src/main.cpp"),
+                &root.join("src/tool.cpp"),
+                Duration::ZERO,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ProjectError::NotInCompilationDatabase { .. })
+        ));
     }
 }
@@ -12,6 +12,9 @@ pub enum ProjectError {
     #[error("Compilation database not found: {path}")]
     CompilationDatabaseNotFound { path: String },
 
+    #[error("Build directory {build_dir} has no compile_commands.json; {hint}")]
+    MissingCompileCommands { build_dir: String, hint: String },
+
     #[error("Invalid build directory: {reason}")]
     InvalidBuildDirectory { reason: String },
 
@@ -33,6 +36,9 @@ pub enum ProjectError {
     #[error("Compilation database is empty")]
     CompilationDatabaseEmpty,
 
+    #[error("File is not in the compilation database: {path}")]
+    NotInCompilationDatabase { path: String },
+
     #[error("Session creation failed: {0}")]
     SessionCreation(String),
 
@@ -44,4 +50,36 @@ pub enum ProjectError {
 
     #[error("Indexing trigger failed: {0}")]
     IndexingTrigger(String),
+
+    #[error("Invalid project configuration {path}: {reason}")]
+    InvalidConfig { path: String, reason: String },
+}
+
+impl ProjectError {
+    /// Missing compilation database, with instructions to generate it for the build system
+    pub fn missing_compile_commands(
+        provider_type: &str,
+        build_dir: &std::path::Path,
+        source_root: &std::path::Path,
+    ) -> Self {
+        let hint = match provider_type {
+            "cmake" => format!(
+                "configure with CMAKE_EXPORT_COMPILE_COMMANDS=ON: \
+                 cmake -S {} -B {} -DCMAKE_EXPORT_COMPILE_COMMANDS=ON",
+                source_root.display(),
+                build_dir.display()
+            ),
+            "meson" => format!(
+                "meson writes it on setup, regenerate it with: meson setup --reconfigure {}",
+                build_dir.display()
+            ),
+            _ => "generate it with the build system, or use --compile-commands-dir \
+                  to point at an existing one"
+                .to_string(),
+        };
+        ProjectError::MissingCompileCommands {
+            build_dir: build_dir.to_string_lossy().to_string(),
+            hint,
+        }
+    }
 }
@@ -0,0 +1,297 @@
+//! Build directory freshness relative to the sources
+//!
+//! The compilation database is written when a build directory is configured.
+//! Build system files (CMakeLists.txt, meson.build, ...) edited afterwards may
+//! add sources or change flags the database does not know about yet, and
+//! sources edited afterwards may depend on generated headers that are only
+//! refreshed by a build. Comparing modification times tells which of the two
+//! is likely, without running the build system.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::project::{CompilationDatabase, ProjectComponent};
+
+/// Maximum number of directories visited while looking for build system files
+const MAX_DIRECTORIES_VISITED: usize = 10_000;
+
+/// File names of build system files
+const BUILD_FILE_NAMES: &[&str] = &[
+    "CMakeLists.txt",
+    "CMakePresets.json",
+    "CMakeUserPresets.json",
+    "meson.build",
+    "meson_options.txt",
+    "meson.options",
+];
+
+/// How current a build directory is compared to the sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreshnessStatus {
+    /// Nothing changed since the build directory was configured
+    Fresh,
+    /// Sources changed since configuration; generated files may be outdated
+    SourcesChanged,
+    /// Build system files changed or compiled sources disappeared since configuration
+    ReconfigureNeeded,
+    /// The compilation database could not be read
+    Unknown,
+}
+
+/// Modification times of a build directory and the sources it compiles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildFreshness {
+    pub status: FreshnessStatus,
+    /// Why the status was chosen and what to do about it
+    pub reason: String,
+    pub compile_commands: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub compile_commands_modified: Option<DateTime<Utc>>,
+    /// File the build system writes when configuring (CMakeCache.txt, meson-info)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub configure_stamp: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub configure_modified: Option<DateTime<Utc>>,
+    /// Most recently modified source of the compilation database
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub newest_source: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub newest_source_modified: Option<DateTime<Utc>>,
+    /// Number of sources modified after the compilation database was written
+    pub sources_changed: usize,
+    /// Number of sources of the compilation database that no longer exist
+    pub sources_missing: usize,
+    /// Most recently modified build system file under the source root
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub newest_build_file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub newest_build_file_modified: Option<DateTime<Utc>>,
+}
+
+/// Modification time of a file, if it exists
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// File the provider writes when configuring a build directory
+fn configure_stamp(component: &ProjectComponent) -> Option<PathBuf> {
+    let stamp = match component.provider_type.as_str() {
+        "cmake" => component.build_dir_path.join("CMakeCache.txt"),
+        "meson" => component.build_dir_path.join("meson-info/meson-info.json"),
+        _ => return None,
+    };
+    stamp.exists().then_some(stamp)
+}
+
+/// Whether a file is a build system file
+fn is_build_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| BUILD_FILE_NAMES.contains(&name) || name.ends_with(".cmake"))
+}
+
+/// Whether a directory holds a configured build rather than sources
+fn is_build_directory(dir: &Path) -> bool {
+    dir.join("CMakeCache.txt").exists() || dir.join("meson-info").is_dir()
+}
+
+/// Find the most recently modified build system file under `source_root`
+///
+/// Hidden directories and configured build directories are skipped.
+fn newest_build_file(source_root: &Path) -> Option<(PathBuf, SystemTime)> {
+    let mut newest: Option<(PathBuf, SystemTime)> = None;
+    let mut pending = vec![source_root.to_path_buf()];
+    let mut visited = 0;
+    while let Some(dir) = pending.pop() {
+        visited += 1;
+        if visited > MAX_DIRECTORIES_VISITED {
+            break;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !hidden && !is_build_directory(&path) {
+                    pending.push(path);
+                }
+            } else if is_build_file(&path)
+                && let Some(time) = modified(&path)
+                && newest.as_ref().is_none_or(|(_, newest)| time > *newest)
+            {
+                newest = Some((path, time));
+            }
+        }
+    }
+    newest
+}
+
+/// Compare a build directory's compilation database with its sources
+///
+/// `compile_commands` is the database clangd uses for the component: its own
+/// or a global one.
+pub fn check_build_freshness(
+    component: &ProjectComponent,
+    compile_commands: &Path,
+) -> BuildFreshness {
+    let compile_commands_time = modified(compile_commands);
+    let configure_stamp = configure_stamp(component);
+    let configure_time = configure_stamp.as_deref().and_then(modified);
+    let build_file = newest_build_file(&component.source_root_path);
+
+    let mut freshness = BuildFreshness {
+        status: FreshnessStatus::Unknown,
+        reason: String::new(),
+        compile_commands: compile_commands.to_path_buf(),
+        compile_commands_modified: compile_commands_time.map(DateTime::from),
+        configure_stamp,
+        configure_modified: configure_time.map(DateTime::from),
+        newest_source: None,
+        newest_source_modified: None,
+        sources_changed: 0,
+        sources_missing: 0,
+        newest_build_file: build_file.as_ref().map(|(path, _)| path.clone()),
+        newest_build_file_modified: build_file.as_ref().map(|(_, time)| DateTime::from(*time)),
+    };
+
+    let (Some(compile_commands_time), Ok(database)) = (
+        compile_commands_time,
+        CompilationDatabase::new(compile_commands.to_path_buf()),
+    ) else {
+        freshness.reason = format!(
+            "{} cannot be read; configure the build directory.",
+            compile_commands.display()
+        );
+        return freshness;
+    };
+
+    let mut newest_source: Option<(PathBuf, SystemTime)> = None;
+    for entry in database.entries() {
+        let source = if entry.file.is_relative() {
+            entry.directory.join(&entry.file)
+        } else {
+            entry.file.clone()
+        };
+        let Some(time) = modified(&source) else {
+            freshness.sources_missing += 1;
+            continue;
+        };
+        if time > compile_commands_time {
+            freshness.sources_changed += 1;
+        }
+        if newest_source
+            .as_ref()
+            .is_none_or(|(_, newest)| time > *newest)
+        {
+            newest_source = Some((source, time));
+        }
+    }
+    if let Some((path, time)) = newest_source {
+        freshness.newest_source = Some(path);
+        freshness.newest_source_modified = Some(DateTime::from(time));
+    }
+
+    // The database is only as current as the older of it and the configure stamp
+    let configured = configure_time.map_or(compile_commands_time, |time| {
+        time.min(compile_commands_time)
+    });
+    let build_file_changed = build_file.as_ref().filter(|(_, time)| *time > configured);
+
+    (freshness.status, freshness.reason) = if let Some((path, _)) = build_file_changed {
+        (
+            FreshnessStatus::ReconfigureNeeded,
+            format!(
+                "{} changed after the build directory was configured; reconfigure it so the \
+                 compilation database picks up new sources and flags.",
+                path.display()
+            ),
+        )
+    } else if freshness.sources_missing > 0 {
+        (
+            FreshnessStatus::ReconfigureNeeded,
+            format!(
+                "{} compiled sources no longer exist; reconfigure the build directory.",
+                freshness.sources_missing
+            ),
+        )
+    } else if freshness.sources_changed > 0 {
+        (
+            FreshnessStatus::SourcesChanged,
+            format!(
+                "{} sources changed after the build directory was configured. clangd re-parses \
+                 them, but generated headers they depend on are only updated by a build; \
+                 rebuild if results look outdated.",
+                freshness.sources_changed
+            ),
+        )
+    } else {
+        (
+            FreshnessStatus::Fresh,
+            "No sources or build files changed since the build directory was configured."
+                .to_string(),
+        )
+    };
+    freshness
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+    use std::fs::File;
+    use std::time::Duration;
+
+    fn set_modified(path: &Path, time: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_freshness_status() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "int main() {}\n")]);
+        let component = &workspace.components[0];
+        let root = &workspace.project_root_path;
+        let database = &component.compilation_database_path;
+        let cmake_lists = root.join("CMakeLists.txt");
+        std::fs::write(&cmake_lists, "project(app)\n").unwrap();
+        std::fs::write(component.build_dir_path.join("CMakeCache.txt"), "").unwrap();
+
+        let configured = SystemTime::now() - Duration::from_secs(3600);
+        let earlier = configured - Duration::from_secs(60);
+        let later = configured + Duration::from_secs(60);
+        set_modified(database, configured);
+        set_modified(&component.build_dir_path.join("CMakeCache.txt"), configured);
+        set_modified(&cmake_lists, earlier);
+        set_modified(&root.join("src/main.cpp"), earlier);
+
+        let freshness = check_build_freshness(component, database);
+        assert_eq!(freshness.status, FreshnessStatus::Fresh);
+        assert!(freshness.configure_stamp.is_some());
+        assert!(freshness.newest_source.unwrap().ends_with("src/main.cpp"));
+
+        set_modified(&root.join("src/main.cpp"), later);
+        let freshness = check_build_freshness(component, database);
+        assert_eq!(freshness.status, FreshnessStatus::SourcesChanged);
+        assert_eq!(freshness.sources_changed, 1);
+
+        set_modified(&cmake_lists, later);
+        let freshness = check_build_freshness(component, database);
+        assert_eq!(freshness.status, FreshnessStatus::ReconfigureNeeded);
+        assert_eq!(freshness.newest_build_file.unwrap(), cmake_lists);
+
+        let freshness = check_build_freshness(component, &root.join("missing.json"));
+        assert_eq!(freshness.status, FreshnessStatus::Unknown);
+    }
+}
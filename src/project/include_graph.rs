@@ -0,0 +1,565 @@
+//! Transitive include graph construction
+//!
+//! Follows the `#include` directives of a file and of every header it pulls in,
+//! resolving each directive with the include search paths of the translation
+//! unit's compile command. Directives are read textually: preprocessor
+//! conditionals are not evaluated, so headers included under an inactive `#if`
+//! branch are part of the graph too.
+//!
+//! The reverse question - which files include a header - is answered by
+//! building the graph of every translation unit in the compilation database
+//! and collecting the edges that lead to the header.
+
+use json_compilation_db::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use crate::io::file_buffer::read_source_file;
+use crate::project::include_paths::{IncludeDirective, IncludeDirectiveKind, IncludeSearchPaths};
+
+/// Search path origins that make clang treat headers as system headers
+const SYSTEM_ORIGINS: &[&str] = &["-isystem", "-idirafter"];
+
+/// Category of a file in the include graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncludeNodeKind {
+    /// File the graph was built from
+    Root,
+    /// Header inside the project root found through a non-system search path
+    Project,
+    /// Header found through `-isystem`/`-idirafter` or located outside the project root
+    System,
+}
+
+/// A file of the include graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncludeNode {
+    /// Absolute file path
+    pub path: PathBuf,
+    /// Category of the file
+    pub kind: IncludeNodeKind,
+    /// Shortest include distance from the root file
+    pub depth: u32,
+    /// Whether the includes of this file were followed (false past the depth limit)
+    pub expanded: bool,
+}
+
+/// An include relationship between two files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncludeEdge {
+    /// Including file
+    pub from: PathBuf,
+    /// Included file
+    pub to: PathBuf,
+    /// 1-based line of the directive in the including file
+    pub line: u32,
+    /// Whether this include closes a cycle
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub cycle: bool,
+}
+
+/// An include directive that could not be resolved to a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedInclude {
+    /// Including file
+    pub from: PathBuf,
+    /// 1-based line of the directive
+    pub line: u32,
+    /// Header name as written in the directive
+    pub header: String,
+    /// Delimiter style of the directive
+    pub kind: IncludeDirectiveKind,
+}
+
+/// Transitive include graph of a file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncludeGraph {
+    /// Files reachable from the root, in breadth-first order
+    pub nodes: Vec<IncludeNode>,
+    /// Resolved include relationships
+    pub edges: Vec<IncludeEdge>,
+    /// Directives whose header was not found in the known search paths
+    pub unresolved: Vec<UnresolvedInclude>,
+    /// Include cycles, each listed from the first file of the cycle back to itself
+    pub cycles: Vec<Vec<PathBuf>>,
+    /// Whether some files were not expanded because of the depth limit
+    pub depth_limit_reached: bool,
+    /// Whether expansion stopped because the node limit was reached
+    pub truncated: bool,
+}
+
+/// Builds include graphs with the search paths of one translation unit
+pub struct IncludeGraphBuilder<'a> {
+    search_paths: &'a IncludeSearchPaths,
+    project_root: &'a Path,
+    max_depth: u32,
+    max_nodes: usize,
+}
+
+impl<'a> IncludeGraphBuilder<'a> {
+    /// Create a builder
+    ///
+    /// # Arguments
+    /// * `search_paths` - Include directories of the translation unit
+    /// * `project_root` - Headers outside this directory are system headers
+    /// * `max_depth` - Include distance up to which files are expanded
+    /// * `max_nodes` - Maximum number of files in the graph
+    pub fn new(
+        search_paths: &'a IncludeSearchPaths,
+        project_root: &'a Path,
+        max_depth: u32,
+        max_nodes: usize,
+    ) -> Self {
+        Self {
+            search_paths,
+            project_root,
+            max_depth,
+            max_nodes,
+        }
+    }
+
+    /// Build the include graph of a file
+    pub fn build(&self, root: &Path) -> IncludeGraph {
+        let mut graph = IncludeGraph::default();
+        let mut index: HashMap<PathBuf, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        let root = normalize(root);
+        index.insert(root.clone(), 0);
+        graph.nodes.push(IncludeNode {
+            path: root,
+            kind: IncludeNodeKind::Root,
+            depth: 0,
+            expanded: false,
+        });
+        queue.push_back(0);
+
+        while let Some(current) = queue.pop_front() {
+            let depth = graph.nodes[current].depth;
+            if depth >= self.max_depth {
+                graph.depth_limit_reached = true;
+                continue;
+            }
+
+            let from = graph.nodes[current].path.clone();
+            let Ok(content) = read_source_file(&from) else {
+                continue;
+            };
+            graph.nodes[current].expanded = true;
+
+            for (line_index, line) in content.lines().enumerate() {
+                let Some(directive) = IncludeDirective::parse(line) else {
+                    continue;
+                };
+                let line = line_index as u32 + 1;
+
+                let Some((to, kind)) = self.resolve(&directive, &from) else {
+                    graph.unresolved.push(UnresolvedInclude {
+                        from: from.clone(),
+                        line,
+                        header: directive.header,
+                        kind: directive.kind,
+                    });
+                    continue;
+                };
+
+                if !index.contains_key(&to) {
+                    if graph.nodes.len() >= self.max_nodes {
+                        graph.truncated = true;
+                        continue;
+                    }
+                    index.insert(to.clone(), graph.nodes.len());
+                    queue.push_back(graph.nodes.len());
+                    graph.nodes.push(IncludeNode {
+                        path: to.clone(),
+                        kind,
+                        depth: depth + 1,
+                        expanded: false,
+                    });
+                }
+
+                graph.edges.push(IncludeEdge {
+                    from: from.clone(),
+                    to,
+                    line,
+                    cycle: false,
+                });
+            }
+        }
+
+        mark_cycles(&mut graph, &index);
+        graph
+    }
+
+    /// Find the file a directive includes
+    fn resolve(
+        &self,
+        directive: &IncludeDirective,
+        includer: &Path,
+    ) -> Option<(PathBuf, IncludeNodeKind)> {
+        let search_path = self
+            .search_paths
+            .search_order(directive.kind, includer.parent())
+            .into_iter()
+            .find(|search_path| search_path.path.join(&directive.header).is_file())?;
+
+        let path = normalize(&search_path.path.join(&directive.header));
+        let kind = if SYSTEM_ORIGINS.contains(&search_path.origin.as_str())
+            || !path.starts_with(self.project_root)
+        {
+            IncludeNodeKind::System
+        } else {
+            IncludeNodeKind::Project
+        };
+        Some((path, kind))
+    }
+}
+
+/// A file with an `#include` directive that resolves to a given header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Includer {
+    /// File containing the directive
+    pub file: PathBuf,
+    /// 1-based line of the directive
+    pub line: u32,
+}
+
+/// Files that include a header, found across all translation units
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncluderSearch {
+    /// Files including the header directly, with the line of the directive
+    pub includers: Vec<Includer>,
+    /// Translation units that include the header directly or transitively
+    pub translation_units: Vec<PathBuf>,
+    /// Number of translation units whose include graph was searched
+    pub translation_units_scanned: usize,
+    /// Whether an include graph hit its depth or node limit, so includers may be missing
+    pub incomplete: bool,
+}
+
+/// Find the files that include `header`
+///
+/// Builds the include graph of every compilation database entry with its own
+/// search paths, so a directive only counts when it resolves to `header` the
+/// way that translation unit's compiler would resolve it. Headers that no
+/// translation unit reaches are not searched.
+pub fn find_includers(
+    entries: &[Entry],
+    header: &Path,
+    project_root: &Path,
+    max_depth: u32,
+    max_nodes: usize,
+) -> IncluderSearch {
+    let header = normalize(header);
+    let mut includers = BTreeSet::new();
+    let mut translation_units = BTreeSet::new();
+    let mut scanned = BTreeSet::new();
+    let mut incomplete = false;
+
+    for entry in entries {
+        let source = normalize(&entry.directory.join(&entry.file));
+        if !scanned.insert(source.clone()) {
+            continue;
+        }
+
+        let search_paths = IncludeSearchPaths::from_entry(entry);
+        let graph = IncludeGraphBuilder::new(&search_paths, project_root, max_depth, max_nodes)
+            .build(&source);
+        incomplete |= graph.depth_limit_reached || graph.truncated;
+
+        let mut reached = false;
+        for edge in graph.edges.into_iter().filter(|edge| edge.to == header) {
+            reached = true;
+            includers.insert(Includer {
+                file: edge.from,
+                line: edge.line,
+            });
+        }
+        if reached {
+            translation_units.insert(source);
+        }
+    }
+
+    IncluderSearch {
+        includers: includers.into_iter().collect(),
+        translation_units: translation_units.into_iter().collect(),
+        translation_units_scanned: scanned.len(),
+        incomplete,
+    }
+}
+
+/// List the source and header files of the project, sorted
+///
+/// Translation units of the compilation database inside `project_root`, plus
+/// every project header reachable from them through their include graphs.
+/// Headers no translation unit includes are not listed.
+pub fn project_files(
+    entries: &[Entry],
+    project_root: &Path,
+    max_depth: u32,
+    max_nodes: usize,
+) -> Vec<PathBuf> {
+    let mut files = BTreeSet::new();
+    let mut scanned = BTreeSet::new();
+
+    for entry in entries {
+        let source = normalize(&entry.directory.join(&entry.file));
+        if !source.starts_with(project_root) || !scanned.insert(source.clone()) {
+            continue;
+        }
+
+        let search_paths = IncludeSearchPaths::from_entry(entry);
+        let graph = IncludeGraphBuilder::new(&search_paths, project_root, max_depth, max_nodes)
+            .build(&source);
+        files.extend(
+            graph
+                .nodes
+                .into_iter()
+                .filter(|node| node.kind == IncludeNodeKind::Project)
+                .map(|node| node.path),
+        );
+        files.insert(source);
+    }
+
+    files.into_iter().collect()
+}
+
+/// Resolve `.`/`..` components and symlinks so each file has one node
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Mark back edges of a depth-first traversal from the root and record their cycles
+fn mark_cycles(graph: &mut IncludeGraph, index: &HashMap<PathBuf, usize>) {
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes.len()];
+    for (edge_index, edge) in graph.edges.iter().enumerate() {
+        adjacency[index[&edge.from]].push(edge_index);
+    }
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        New,
+        OnStack,
+        Done,
+    }
+    let mut visits = vec![Visit::New; graph.nodes.len()];
+    let mut stack: Vec<usize> = vec![0];
+    // Per stack entry, position in its adjacency list
+    let mut cursors: Vec<usize> = vec![0];
+    visits[0] = Visit::OnStack;
+
+    while let Some(&node) = stack.last() {
+        let cursor = cursors.last_mut().unwrap();
+        let Some(&edge_index) = adjacency[node].get(*cursor) else {
+            visits[node] = Visit::Done;
+            stack.pop();
+            cursors.pop();
+            continue;
+        };
+        *cursor += 1;
+
+        let target = index[&graph.edges[edge_index].to];
+        match visits[target] {
+            Visit::New => {
+                visits[target] = Visit::OnStack;
+                stack.push(target);
+                cursors.push(0);
+            }
+            Visit::OnStack => {
+                graph.edges[edge_index].cycle = true;
+                let start = stack.iter().position(|&n| n == target).unwrap();
+                let mut cycle: Vec<PathBuf> = stack[start..]
+                    .iter()
+                    .map(|&n| graph.nodes[n].path.clone())
+                    .collect();
+                cycle.push(graph.nodes[target].path.clone());
+                graph.cycles.push(cycle);
+            }
+            Visit::Done => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) -> PathBuf {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path.canonicalize().unwrap()
+    }
+
+    fn search_paths(project: &Path, system: &Path) -> IncludeSearchPaths {
+        IncludeSearchPaths::from_arguments(
+            &[
+                "c++".to_string(),
+                format!("-I{}", project.join("include").display()),
+                "-isystem".to_string(),
+                system.display().to_string(),
+            ],
+            project,
+        )
+    }
+
+    #[test]
+    fn test_graph_nodes_edges_and_cycles() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        let system = temp_dir.path().join("sysroot");
+        let main = write(
+            &project,
+            "src/main.cpp",
+            "#include \"local.h\"\n#include <lib/api.h>\n#include <vector>\n",
+        );
+        let local = write(
+            &project,
+            "src/local.h",
+            "#pragma once\n#include <lib/api.h>\n",
+        );
+        let api = write(
+            &project,
+            "include/lib/api.h",
+            "#include \"detail.h\"\n#include <sys.h>\n",
+        );
+        let detail = write(&project, "include/lib/detail.h", "#include \"api.h\"\n");
+        let sys = write(&system, "sys.h", "");
+
+        let paths = search_paths(&project, &system);
+        let project = project.canonicalize().unwrap();
+        let graph = IncludeGraphBuilder::new(&paths, &project, 10, 100).build(&main);
+
+        let kinds: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.path.clone(), node.kind, node.depth))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (main.clone(), IncludeNodeKind::Root, 0),
+                (local.clone(), IncludeNodeKind::Project, 1),
+                (api.clone(), IncludeNodeKind::Project, 1),
+                (detail.clone(), IncludeNodeKind::Project, 2),
+                (sys.clone(), IncludeNodeKind::System, 2),
+            ]
+        );
+        assert_eq!(graph.edges.len(), 6);
+
+        assert_eq!(graph.unresolved.len(), 1);
+        assert_eq!(graph.unresolved[0].header, "vector");
+
+        assert_eq!(graph.cycles, vec![vec![api.clone(), detail.clone(), api]]);
+        let cycle_edges: Vec<_> = graph.edges.iter().filter(|edge| edge.cycle).collect();
+        assert_eq!(cycle_edges.len(), 1);
+        assert_eq!(cycle_edges[0].from, detail);
+        assert!(!graph.depth_limit_reached);
+    }
+
+    #[test]
+    fn test_depth_and_node_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let a = write(root, "a.h", "#include \"b.h\"\n#include \"c.h\"\n");
+        write(root, "b.h", "#include \"d.h\"\n");
+        write(root, "c.h", "");
+        write(root, "d.h", "");
+        let paths = IncludeSearchPaths::default();
+        let root = root.canonicalize().unwrap();
+
+        let graph = IncludeGraphBuilder::new(&paths, &root, 1, 100).build(&a);
+        assert_eq!(graph.nodes.len(), 3);
+        assert!(graph.depth_limit_reached);
+        assert!(graph.nodes[0].expanded);
+        assert!(!graph.nodes[1].expanded);
+
+        let graph = IncludeGraphBuilder::new(&paths, &root, 10, 2).build(&a);
+        assert_eq!(graph.nodes.len(), 2);
+        assert!(graph.truncated);
+    }
+
+    #[test]
+    fn test_find_includers() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let a = write(root, "a.cpp", "#include \"util.h\"\n#include \"api.h\"\n");
+        let b = write(root, "b.cpp", "// b\n#include \"api.h\"\n");
+        write(root, "c.cpp", "#include \"other.h\"\n");
+        let util = write(root, "util.h", "#pragma once\n\n#include \"api.h\"\n");
+        let api = write(root, "api.h", "");
+        write(root, "other.h", "");
+        let root = root.canonicalize().unwrap();
+
+        let entries: Vec<Entry> = ["a.cpp", "b.cpp", "c.cpp", "a.cpp"]
+            .into_iter()
+            .map(|file| Entry {
+                directory: root.clone(),
+                file: PathBuf::from(file),
+                arguments: vec!["c++".to_string(), "-c".to_string(), file.to_string()],
+                output: None,
+            })
+            .collect();
+
+        let search = find_includers(&entries, &api, &root, 10, 100);
+        assert_eq!(
+            search.includers,
+            vec![
+                Includer {
+                    file: a.clone(),
+                    line: 2
+                },
+                Includer {
+                    file: b.clone(),
+                    line: 2
+                },
+                Includer {
+                    file: util,
+                    line: 3
+                },
+            ]
+        );
+        assert_eq!(search.translation_units, vec![a, b]);
+        assert_eq!(search.translation_units_scanned, 3);
+        assert!(!search.incomplete);
+    }
+
+    #[test]
+    fn test_project_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("project");
+        let system = temp_dir.path().join("sysroot");
+        let main = write(
+            &root,
+            "src/main.cpp",
+            "#include \"util.h\"\n#include <vector>\n",
+        );
+        let util = write(&root, "src/util.h", "#include \"detail.h\"\n");
+        let detail = write(&root, "src/detail.h", "");
+        write(&root, "src/unused.h", "");
+        write(&system, "vector", "");
+        let outside = write(temp_dir.path(), "generated/gen.cpp", "");
+        let root = root.canonicalize().unwrap();
+
+        let entry = |file: &Path| Entry {
+            directory: root.clone(),
+            file: file.to_path_buf(),
+            arguments: vec![
+                "c++".to_string(),
+                "-isystem".to_string(),
+                system.display().to_string(),
+                file.display().to_string(),
+            ],
+            output: None,
+        };
+        let entries = vec![entry(&main), entry(&outside)];
+
+        assert_eq!(
+            project_files(&entries, &root, 10, 100),
+            vec![detail, main, util]
+        );
+    }
+}
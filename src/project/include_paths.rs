@@ -0,0 +1,415 @@
+//! Include search path extraction from compile commands
+//!
+//! Derives the header search order clang uses for a translation unit from the
+//! include flags recorded in its compilation database entry, and parses
+//! `#include` directives so tools can explain how a header was (not) found.
+//! The builtin system directories are not in the compile command; they are
+//! queried from the compiler driver the way clangd's `--query-driver` does.
+
+use json_compilation_db::Entry;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+// ============================================================================
+// Include Directive
+// ============================================================================
+
+/// Delimiter style of an `#include` directive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncludeDirectiveKind {
+    /// `#include "header.h"` - searched relative to the includer first
+    Quoted,
+    /// `#include <header.h>` - searched only in include directories
+    Angled,
+}
+
+/// A parsed `#include` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeDirective {
+    /// Header name as written between the delimiters
+    pub header: String,
+    /// Delimiter style
+    pub kind: IncludeDirectiveKind,
+    /// 0-based column of the first character of the header name
+    pub header_column: u32,
+}
+
+impl IncludeDirective {
+    /// Parse an `#include` (or `#import`/`#include_next`) directive from a source line
+    ///
+    /// Returns `None` if the line is not an include directive or the header
+    /// name is not delimited by quotes or angle brackets (e.g. macro includes).
+    pub fn parse(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let rest = trimmed.strip_prefix('#')?.trim_start();
+        let rest = ["include_next", "include", "import"]
+            .iter()
+            .find_map(|keyword| rest.strip_prefix(keyword))?;
+        let rest = rest.trim_start();
+
+        let (kind, closing) = match rest.chars().next()? {
+            '"' => (IncludeDirectiveKind::Quoted, '"'),
+            '<' => (IncludeDirectiveKind::Angled, '>'),
+            _ => return None,
+        };
+
+        let body = &rest[1..];
+        let end = body.find(closing)?;
+        let header = body[..end].to_string();
+        if header.is_empty() {
+            return None;
+        }
+
+        let prefix_len = line.len() - rest.len() + 1;
+        let header_column = line[..prefix_len].chars().count() as u32;
+
+        Some(Self {
+            header,
+            kind,
+            header_column,
+        })
+    }
+}
+
+// ============================================================================
+// Include Search Paths
+// ============================================================================
+
+/// A single include directory together with the flag that introduced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncludeSearchPath {
+    /// Absolute include directory
+    pub path: PathBuf,
+    /// Origin of the directory: the compiler flag, or "includer" for the
+    /// directory of the including file
+    pub origin: String,
+}
+
+/// Include directories grouped the way clang groups them during lookup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncludeSearchPaths {
+    /// `-iquote` directories (quoted includes only)
+    pub quote: Vec<IncludeSearchPath>,
+    /// `-I` directories
+    pub angled: Vec<IncludeSearchPath>,
+    /// `-isystem` directories
+    pub system: Vec<IncludeSearchPath>,
+    /// `-idirafter` directories
+    pub after: Vec<IncludeSearchPath>,
+}
+
+impl IncludeSearchPaths {
+    /// Extract include directories from a compilation database entry
+    pub fn from_entry(entry: &Entry) -> Self {
+        Self::from_arguments(&entry.arguments, &entry.directory)
+    }
+
+    /// Extract include directories from compiler arguments
+    ///
+    /// Relative directories are resolved against `directory`, the working
+    /// directory of the compile command. Both joined (`-Idir`) and separated
+    /// (`-I dir`) spellings are recognized.
+    pub fn from_arguments(arguments: &[String], directory: &Path) -> Self {
+        const FLAGS: &[&str] = &[
+            "--include-directory=",
+            "-iquote",
+            "-isystem",
+            "-idirafter",
+            "-I",
+        ];
+
+        let mut paths = Self::default();
+        let mut args = arguments.iter();
+
+        while let Some(arg) = args.next() {
+            let Some(flag) = FLAGS.iter().find(|flag| arg.starts_with(*flag)) else {
+                continue;
+            };
+
+            let value = match &arg[flag.len()..] {
+                "" => match args.next() {
+                    Some(next) => next.as_str(),
+                    None => break,
+                },
+                joined => joined,
+            };
+
+            let path = if Path::new(value).is_relative() {
+                directory.join(value)
+            } else {
+                PathBuf::from(value)
+            };
+            let origin = flag.trim_end_matches('=').to_string();
+            let search_path = IncludeSearchPath { path, origin };
+
+            match *flag {
+                "-iquote" => paths.quote.push(search_path),
+                "-isystem" => paths.system.push(search_path),
+                "-idirafter" => paths.after.push(search_path),
+                _ => paths.angled.push(search_path),
+            }
+        }
+
+        paths
+    }
+
+    /// Get the directories searched for a directive, in lookup order
+    ///
+    /// Quoted includes start with the includer's directory followed by the
+    /// `-iquote` directories; both kinds then continue with `-I`, `-isystem`
+    /// and `-idirafter` directories. Builtin compiler directories are not known
+    /// from the compile command and are therefore not listed.
+    pub fn search_order(
+        &self,
+        kind: IncludeDirectiveKind,
+        includer_dir: Option<&Path>,
+    ) -> Vec<IncludeSearchPath> {
+        let mut order = Vec::new();
+
+        if kind == IncludeDirectiveKind::Quoted {
+            if let Some(dir) = includer_dir {
+                order.push(IncludeSearchPath {
+                    path: dir.to_path_buf(),
+                    origin: "includer".to_string(),
+                });
+            }
+            order.extend(self.quote.iter().cloned());
+        }
+
+        order.extend(self.angled.iter().cloned());
+        order.extend(self.system.iter().cloned());
+        order.extend(self.after.iter().cloned());
+        order
+    }
+}
+
+// ============================================================================
+// Compiler Driver Query
+// ============================================================================
+
+/// Time allowed for the compiler driver to print its search list
+const DRIVER_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Flags forwarded to the driver because they change its system directories
+const DRIVER_TARGET_FLAGS: &[&str] = &[
+    "--target=",
+    "-target",
+    "--sysroot",
+    "-isysroot",
+    "-stdlib=",
+    "-specs=",
+    "--gcc-toolchain=",
+    "-nostdinc",
+    "-nostdinc++",
+];
+
+/// Ask the compiler of a compile command for its system include directories
+///
+/// Runs `<compiler> -E -v -x <language> -` with the command's target and
+/// sysroot flags and returns the `#include <...>` search list it prints, the
+/// same information clangd extracts for drivers allowed by `--query-driver`.
+pub async fn query_driver_include_paths(entry: &Entry) -> Result<Vec<PathBuf>, String> {
+    let compiler = entry
+        .arguments
+        .first()
+        .ok_or_else(|| "Compile command has no compiler".to_string())?;
+    let language = match entry.file.extension().and_then(|ext| ext.to_str()) {
+        Some("c") => "c",
+        Some("m") => "objective-c",
+        Some("mm") => "objective-c++",
+        _ => "c++",
+    };
+
+    let mut args = vec![
+        "-E".to_string(),
+        "-v".to_string(),
+        "-x".to_string(),
+        language.to_string(),
+    ];
+    let mut entry_args = entry.arguments.iter().skip(1);
+    while let Some(arg) = entry_args.next() {
+        let Some(flag) = DRIVER_TARGET_FLAGS
+            .iter()
+            .find(|flag| arg.starts_with(*flag))
+        else {
+            continue;
+        };
+        args.push(arg.clone());
+        // Separated spelling: the value is the next argument
+        if arg == flag
+            && !flag.ends_with('=')
+            && !flag.starts_with("-nostdinc")
+            && let Some(value) = entry_args.next()
+        {
+            args.push(value.clone());
+        }
+    }
+    args.push("-".to_string());
+
+    let output = tokio::process::Command::new(compiler)
+        .args(&args)
+        .current_dir(&entry.directory)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(DRIVER_QUERY_TIMEOUT, output)
+        .await
+        .map_err(|_| {
+            format!(
+                "{} did not answer within {:?}",
+                compiler, DRIVER_QUERY_TIMEOUT
+            )
+        })?
+        .map_err(|e| format!("Failed to run {}: {}", compiler, e))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let paths = parse_driver_search_list(&stderr);
+    if paths.is_empty() && !output.status.success() {
+        return Err(format!(
+            "{} failed ({}): {}",
+            compiler,
+            output.status,
+            stderr.lines().last().unwrap_or_default()
+        ));
+    }
+    Ok(paths)
+}
+
+/// Extract the `#include <...>` search list from verbose driver output
+pub fn parse_driver_search_list(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("#include <...> search starts here:"))
+        .skip(1)
+        .take_while(|line| !line.starts_with("End of search list."))
+        // macOS marks framework directories with a suffix
+        .map(|line| line.trim().trim_end_matches(" (framework directory)"))
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_driver_search_list() {
+        let output = "clang version 17.0.6\n\
+                      #include \"...\" search starts here:\n\
+                      #include <...> search starts here:\n \
+                      /usr/include/c++/13\n \
+                      /usr/lib/clang/17/include\n \
+                      /Library/Frameworks (framework directory)\n\
+                      End of search list.\n";
+        assert_eq!(
+            parse_driver_search_list(output),
+            vec![
+                PathBuf::from("/usr/include/c++/13"),
+                PathBuf::from("/usr/lib/clang/17/include"),
+                PathBuf::from("/Library/Frameworks"),
+            ]
+        );
+        assert!(parse_driver_search_list("error: no such file").is_empty());
+    }
+
+    #[test]
+    fn test_parse_quoted_include() {
+        let directive = IncludeDirective::parse("#include \"foo/bar.h\"").unwrap();
+        assert_eq!(directive.header, "foo/bar.h");
+        assert_eq!(directive.kind, IncludeDirectiveKind::Quoted);
+        assert_eq!(directive.header_column, 10);
+    }
+
+    #[test]
+    fn test_parse_angled_include_with_spacing() {
+        let directive = IncludeDirective::parse("  #  include   <vector> // comment").unwrap();
+        assert_eq!(directive.header, "vector");
+        assert_eq!(directive.kind, IncludeDirectiveKind::Angled);
+        assert_eq!(directive.header_column, 16);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_include_lines() {
+        assert!(IncludeDirective::parse("int main() {}").is_none());
+        assert!(IncludeDirective::parse("#define FOO 1").is_none());
+        assert!(IncludeDirective::parse("#include HEADER_MACRO").is_none());
+        assert!(IncludeDirective::parse("#include \"\"").is_none());
+    }
+
+    #[test]
+    fn test_from_arguments_joined_and_separated() {
+        let paths = IncludeSearchPaths::from_arguments(
+            &args(&[
+                "clang++",
+                "-Iinclude",
+                "-I",
+                "/abs/include",
+                "-isystem",
+                "/usr/include/foo",
+                "-iquote",
+                "quoted",
+                "-idirafter/after",
+                "-c",
+                "main.cpp",
+            ]),
+            Path::new("/project/build"),
+        );
+
+        assert_eq!(
+            paths.angled,
+            vec![
+                IncludeSearchPath {
+                    path: PathBuf::from("/project/build/include"),
+                    origin: "-I".to_string(),
+                },
+                IncludeSearchPath {
+                    path: PathBuf::from("/abs/include"),
+                    origin: "-I".to_string(),
+                },
+            ]
+        );
+        assert_eq!(paths.system[0].path, PathBuf::from("/usr/include/foo"));
+        assert_eq!(paths.quote[0].path, PathBuf::from("/project/build/quoted"));
+        assert_eq!(paths.after[0].path, PathBuf::from("/after"));
+    }
+
+    #[test]
+    fn test_search_order_quoted_vs_angled() {
+        let paths = IncludeSearchPaths::from_arguments(
+            &args(&["c++", "-iquote", "/q", "-I/i", "-isystem", "/s"]),
+            Path::new("/build"),
+        );
+
+        let quoted: Vec<PathBuf> = paths
+            .search_order(IncludeDirectiveKind::Quoted, Some(Path::new("/src")))
+            .into_iter()
+            .map(|p| p.path)
+            .collect();
+        assert_eq!(
+            quoted,
+            vec![
+                PathBuf::from("/src"),
+                PathBuf::from("/q"),
+                PathBuf::from("/i"),
+                PathBuf::from("/s"),
+            ]
+        );
+
+        let angled: Vec<PathBuf> = paths
+            .search_order(IncludeDirectiveKind::Angled, Some(Path::new("/src")))
+            .into_iter()
+            .map(|p| p.path)
+            .collect();
+        assert_eq!(angled, vec![PathBuf::from("/i"), PathBuf::from("/s")]);
+    }
+}
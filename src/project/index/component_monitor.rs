@@ -6,16 +6,31 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
 use tracing::{debug, info, trace, warn};
 
 use crate::clangd::index::{ComponentIndex, IndexLatch, ProgressEvent};
 use crate::clangd::version::ClangdVersion;
 use crate::project::compilation_database::PathMappings;
 use crate::project::index::reader::IndexReaderTrait;
+use crate::project::index::snapshot::IndexSnapshot;
 use crate::project::index::trigger::IndexTrigger;
 use crate::project::{CompilationDatabase, ProjectError};
 
+/// Maximum number of index files read at the same time while validating pending files
+const INDEX_VALIDATION_CONCURRENCY: usize = 16;
+
+/// Compilation database size from which the index model is built in the background
+///
+/// Canonicalizing every entry of a very large compilation database delays the
+/// session becoming usable, so above this many entries the session starts
+/// right away and tracks indexing once the model is ready.
+const BACKGROUND_MODEL_BUILD_THRESHOLD: usize = 5000;
+
+/// Progress events received while the index model is still being built
+type DeferredEvents = Arc<std::sync::Mutex<Option<Vec<ProgressEvent>>>>;
+
 /// Result of validating a single index entry
 enum IndexValidationResult {
     /// Index is valid and file should be marked as indexed
@@ -53,6 +68,14 @@ pub struct ComponentIndexState {
     /// Number of CDB files currently indexed
     #[allow(dead_code)]
     pub indexed_cdb_files: usize,
+    /// Number of CDB files waiting to be indexed
+    pub pending_cdb_files: usize,
+    /// Number of CDB files clangd is currently indexing
+    pub in_progress_cdb_files: usize,
+    /// Number of CDB files that failed to index
+    pub failed_cdb_files: usize,
+    /// Whether the index model of the compilation database is still being built
+    pub model_building: bool,
     /// Last updated timestamp
     #[allow(dead_code)]
     pub last_updated: std::time::SystemTime,
@@ -68,6 +91,10 @@ impl ComponentIndexState {
             state,
             total_cdb_files: component_index.total_files_count(),
             indexed_cdb_files: component_index.indexed_count(),
+            pending_cdb_files: component_index.pending_count(),
+            in_progress_cdb_files: component_index.in_progress_count(),
+            failed_cdb_files: component_index.failed_count(),
+            model_building: false,
             last_updated: std::time::SystemTime::now(),
         }
     }
@@ -114,6 +141,12 @@ struct IndexMonitorState {
     /// When indexing started, None if not started or completed
     indexing_start_time: Option<std::time::SystemTime>,
 
+    /// Number of files already indexed when indexing started
+    indexed_files_at_start: usize,
+
+    /// When the latest indexing pass ended, None if none has ended yet
+    completed_at: Option<std::time::Instant>,
+
     /// Last updated timestamp
     last_updated: std::time::SystemTime,
 
@@ -127,6 +160,9 @@ struct IndexMonitorState {
 /// ComponentIndexMonitor consolidates index state management, progress tracking,
 /// and completion coordination for individual project components. This eliminates
 /// the need for multiple hashmaps in WorkspaceSession and provides better encapsulation.
+///
+/// Clones share the same state.
+#[derive(Clone)]
 pub struct ComponentIndexMonitor {
     /// Build directory this monitor tracks
     build_directory: PathBuf,
@@ -136,6 +172,10 @@ pub struct ComponentIndexMonitor {
 
     /// Optional index trigger for initiating indexing operations
     index_trigger: Option<Arc<dyn IndexTrigger>>,
+
+    /// Events buffered while the index model is built in the background,
+    /// None once the model is ready
+    deferred_events: DeferredEvents,
 }
 
 impl ComponentIndexMonitor {
@@ -149,30 +189,37 @@ impl ComponentIndexMonitor {
     ) -> Result<Self, ProjectError> {
         Self::create_monitor(
             build_directory,
-            compilation_db,
+            Arc::new(compilation_db.clone()),
             index_reader,
             clangd_version,
             None,
             false, // perform_scan = false for non-test version
+            BACKGROUND_MODEL_BUILD_THRESHOLD,
         )
         .await
     }
 
     /// Create monitor for specific build directory with optional index trigger
+    ///
+    /// With `scan_existing_index`, index files already on disk are validated to
+    /// find files indexed by earlier sessions. Without it every file starts as
+    /// pending, e.g. when the existing index must be rebuilt anyway.
     pub async fn new_with_trigger(
         build_directory: PathBuf,
         compilation_db: Arc<CompilationDatabase>,
         index_reader: Arc<dyn IndexReaderTrait>,
         clangd_version: &ClangdVersion,
         index_trigger: Option<Arc<dyn IndexTrigger>>,
+        scan_existing_index: bool,
     ) -> Result<Self, ProjectError> {
         Self::create_monitor(
             build_directory,
-            &compilation_db,
+            compilation_db,
             index_reader,
             clangd_version,
             index_trigger,
-            true, // perform_scan = true for production version
+            scan_existing_index,
+            BACKGROUND_MODEL_BUILD_THRESHOLD,
         )
         .await
     }
@@ -195,16 +242,31 @@ impl ComponentIndexMonitor {
     }
 
     /// Common monitor creation logic
+    ///
+    /// Compilation databases with at least `background_build_threshold` entries
+    /// get an empty index model first; the real one is built in the background.
     async fn create_monitor(
         build_directory: PathBuf,
-        compilation_db: &CompilationDatabase,
+        compilation_db: Arc<CompilationDatabase>,
         index_reader: Arc<dyn IndexReaderTrait>,
         clangd_version: &ClangdVersion,
         index_trigger: Option<Arc<dyn IndexTrigger>>,
         perform_scan: bool,
+        background_build_threshold: usize,
     ) -> Result<Self, ProjectError> {
+        if compilation_db.entries().len() >= background_build_threshold {
+            return Ok(Self::create_monitor_with_background_model(
+                build_directory,
+                compilation_db,
+                index_reader,
+                clangd_version,
+                index_trigger,
+                perform_scan,
+            ));
+        }
+
         let monitor_state = Self::create_monitor_state(
-            compilation_db,
+            &compilation_db,
             index_reader,
             clangd_version,
             &build_directory,
@@ -214,6 +276,7 @@ impl ComponentIndexMonitor {
             build_directory,
             state: Arc::new(Mutex::new(monitor_state)),
             index_trigger,
+            deferred_events: Arc::new(std::sync::Mutex::new(None)),
         };
 
         debug!(
@@ -229,6 +292,166 @@ impl ComponentIndexMonitor {
         Ok(monitor)
     }
 
+    /// Create a monitor whose index model is built in a background task
+    ///
+    /// Progress events received in the meantime are buffered and replayed once
+    /// the model is ready, followed by the initial disk scan and indexing trigger.
+    fn create_monitor_with_background_model(
+        build_directory: PathBuf,
+        compilation_db: Arc<CompilationDatabase>,
+        index_reader: Arc<dyn IndexReaderTrait>,
+        clangd_version: &ClangdVersion,
+        index_trigger: Option<Arc<dyn IndexTrigger>>,
+        perform_scan: bool,
+    ) -> Self {
+        info!(
+            "Building index model for {} compilation database entries in the background for build dir: {}",
+            compilation_db.entries().len(),
+            build_directory.display()
+        );
+
+        let monitor_state = IndexMonitorState {
+            component_index: ComponentIndex::empty(&compilation_db, clangd_version),
+            index_reader: Arc::clone(&index_reader),
+            current_indexing_state: ComponentIndexingState::Init,
+            completion_latch: IndexLatch::new(),
+            indexing_start_time: None,
+            indexed_files_at_start: 0,
+            completed_at: None,
+            last_updated: std::time::SystemTime::now(),
+            path_mappings: (
+                std::collections::HashMap::new(),
+                std::collections::HashMap::new(),
+            ),
+        };
+
+        let monitor = Self {
+            build_directory,
+            state: Arc::new(Mutex::new(monitor_state)),
+            index_trigger,
+            deferred_events: Arc::new(std::sync::Mutex::new(Some(Vec::new()))),
+        };
+
+        let background = monitor.clone();
+        let clangd_version = clangd_version.clone();
+        tokio::spawn(async move {
+            background
+                .build_model_in_background(
+                    compilation_db,
+                    index_reader,
+                    clangd_version,
+                    perform_scan,
+                )
+                .await;
+        });
+
+        monitor
+    }
+
+    /// Build the index model, install it and catch up on what happened meanwhile
+    async fn build_model_in_background(
+        &self,
+        compilation_db: Arc<CompilationDatabase>,
+        index_reader: Arc<dyn IndexReaderTrait>,
+        clangd_version: ClangdVersion,
+        perform_scan: bool,
+    ) {
+        let started = std::time::Instant::now();
+        let build_directory = self.build_directory.clone();
+        let db = Arc::clone(&compilation_db);
+        let built = tokio::task::spawn_blocking(move || {
+            Self::create_monitor_state(&db, index_reader, &clangd_version, &build_directory)
+        })
+        .await;
+
+        match built {
+            Ok(Ok(model)) => {
+                let mut state = self.state.lock().await;
+                state.component_index = model.component_index;
+                state.path_mappings = model.path_mappings;
+                state.last_updated = std::time::SystemTime::now();
+                info!(
+                    "Built index model of {} files in {:?} for build dir: {}",
+                    state.component_index.total_files_count(),
+                    started.elapsed(),
+                    self.build_directory.display()
+                );
+            }
+            Ok(Err(e)) => warn!("{}", e),
+            Err(e) => warn!(
+                "Index model build task failed for {}: {}",
+                self.build_directory.display(),
+                e
+            ),
+        }
+
+        self.replay_deferred_events().await;
+
+        if perform_scan {
+            self.perform_initial_scan().await;
+        }
+        if let Err(e) = self.trigger_initial_indexing(compilation_db).await {
+            warn!(
+                "Failed to trigger initial indexing for {}: {}",
+                self.build_directory.display(),
+                e
+            );
+        }
+    }
+
+    /// Replay buffered progress events until none are left, then stop buffering
+    async fn replay_deferred_events(&self) {
+        loop {
+            let events = {
+                let mut deferred = self
+                    .deferred_events
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                match deferred.as_mut() {
+                    Some(events) if !events.is_empty() => std::mem::take(events),
+                    _ => {
+                        *deferred = None;
+                        return;
+                    }
+                }
+            };
+
+            debug!(
+                "Replaying {} progress events received while building the index model for {}",
+                events.len(),
+                self.build_directory.display()
+            );
+            for event in events {
+                self.dispatch_progress_event(event).await;
+            }
+        }
+    }
+
+    /// Buffer an event while the index model is being built
+    ///
+    /// Returns the event back if the model is ready and it should be handled now.
+    fn defer_event(&self, event: ProgressEvent) -> Option<ProgressEvent> {
+        let mut deferred = self
+            .deferred_events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match deferred.as_mut() {
+            Some(events) => {
+                events.push(event);
+                None
+            }
+            None => Some(event),
+        }
+    }
+
+    /// Whether the index model is still being built in the background
+    pub fn is_model_building(&self) -> bool {
+        self.deferred_events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_some()
+    }
+
     /// Create monitor state for testing
     #[cfg(test)]
     async fn create_monitor_for_test(
@@ -255,6 +478,8 @@ impl ComponentIndexMonitor {
             current_indexing_state: ComponentIndexingState::Init,
             completion_latch,
             indexing_start_time: None,
+            indexed_files_at_start: 0,
+            completed_at: None,
             last_updated: std::time::SystemTime::now(),
             path_mappings,
         };
@@ -268,6 +493,7 @@ impl ComponentIndexMonitor {
             build_directory,
             state: Arc::new(Mutex::new(monitor_state)),
             index_trigger: None,
+            deferred_events: Arc::new(std::sync::Mutex::new(None)),
         })
     }
 
@@ -305,6 +531,8 @@ impl ComponentIndexMonitor {
             current_indexing_state: ComponentIndexingState::Init,
             completion_latch,
             indexing_start_time: None,
+            indexed_files_at_start: 0,
+            completed_at: None,
             last_updated: std::time::SystemTime::now(),
             path_mappings,
         })
@@ -335,7 +563,17 @@ impl ComponentIndexMonitor {
     }
 
     /// Handle progress event (single lock, focused responsibility)
+    ///
+    /// Events received while the index model is still being built are buffered
+    /// and handled once it is ready.
     pub async fn handle_progress_event(&self, event: ProgressEvent) {
+        if let Some(event) = self.defer_event(event) {
+            self.dispatch_progress_event(event).await;
+        }
+    }
+
+    /// Dispatch a progress event to its handler
+    async fn dispatch_progress_event(&self, event: ProgressEvent) {
         match event {
             ProgressEvent::FileIndexingStarted { path, digest } => {
                 self.handle_file_indexing_started(path, digest).await;
@@ -629,6 +867,7 @@ impl ComponentIndexMonitor {
         // Transition component state from Init to InProgress and set start time
         state.current_indexing_state = ComponentIndexingState::InProgress(0.0);
         state.indexing_start_time = Some(std::time::SystemTime::now());
+        state.indexed_files_at_start = state.component_index.indexed_count();
         state.last_updated = std::time::SystemTime::now();
         debug!(
             "Component state transitioned to InProgress for {}",
@@ -799,13 +1038,14 @@ impl ComponentIndexMonitor {
     /// Finalize completion by triggering the completion latch
     async fn finalize_completion(&self) {
         // Re-acquire state lock for latch triggering
-        let state = match self.state.try_lock() {
+        let mut state = match self.state.try_lock() {
             Ok(state) => state,
             Err(_) => {
                 warn!("Could not acquire state lock for latch triggering");
                 return;
             }
         };
+        state.completed_at = Some(std::time::Instant::now());
 
         // Trigger latch now that initial indexing has ended (either Partial or Completed)
         let latch = state.completion_latch.clone();
@@ -854,10 +1094,12 @@ impl ComponentIndexMonitor {
     #[cfg(test)]
     pub async fn get_component_state(&self) -> ComponentIndexState {
         let state = self.state.lock().await;
-        ComponentIndexState::from_component_index(
+        let mut component_state = ComponentIndexState::from_component_index(
             &state.component_index,
             state.current_indexing_state.clone(),
-        )
+        );
+        component_state.model_building = self.is_model_building();
+        component_state
     }
 
     /// Get comprehensive indexing summary with detailed state information
@@ -867,14 +1109,39 @@ impl ComponentIndexMonitor {
         state.component_index.get_indexing_summary()
     }
 
-    /// Get progress tracking data including start time for ETA calculation
-    pub async fn get_progress_data(&self) -> (ComponentIndexState, Option<std::time::SystemTime>) {
+    /// Get progress tracking data for ETA and throughput calculation
+    ///
+    /// Returns the component state together with the start time of the running
+    /// indexing pass and the number of files already indexed at that time.
+    pub async fn get_progress_data(
+        &self,
+    ) -> (ComponentIndexState, Option<(std::time::SystemTime, usize)>) {
         let state = self.state.lock().await;
-        let component_state = ComponentIndexState::from_component_index(
+        let mut component_state = ComponentIndexState::from_component_index(
             &state.component_index,
             state.current_indexing_state.clone(),
         );
-        (component_state, state.indexing_start_time)
+        component_state.model_building = self.is_model_building();
+        let start = state
+            .indexing_start_time
+            .map(|start_time| (start_time, state.indexed_files_at_start));
+        (component_state, start)
+    }
+
+    /// Capture the current indexing state of every compilation database file
+    pub async fn snapshot(&self, id: String) -> IndexSnapshot {
+        let state = self.state.lock().await;
+        IndexSnapshot::capture(id, &state.component_index)
+    }
+
+    /// Time elapsed since the latest indexing pass ended
+    ///
+    /// Returns None while no indexing pass has ended yet.
+    pub async fn time_since_completion(&self) -> Option<Duration> {
+        let state = self.state.lock().await;
+        state
+            .completed_at
+            .map(|completed_at| completed_at.elapsed())
     }
 
     /// Wait for indexing completion with timeout
@@ -938,9 +1205,11 @@ impl ComponentIndexMonitor {
     ///
     /// This method:
     /// 1. Identifies files currently in Pending state
-    /// 2. Uses IndexReader to check if index files exist and are valid
+    /// 2. Reads their index files concurrently through the IndexReader, without
+    ///    holding the state lock, bounded by `INDEX_VALIDATION_CONCURRENCY`
     /// 3. Validates format version and index content
-    /// 4. Updates ComponentIndex state only for valid files
+    /// 4. Updates ComponentIndex state in a single locked pass, only for valid
+    ///    files that are still pending
     /// 5. Provides detailed logging about discovered/rejected files
     async fn rescan_and_validate_untracked_files(&self) -> Result<(), ProjectError> {
         debug!(
@@ -948,13 +1217,16 @@ impl ComponentIndexMonitor {
             self.build_directory.display()
         );
 
-        let mut state = self.state.lock().await;
-        let pending_files: Vec<_> = state
-            .component_index
-            .get_pending_files()
-            .iter()
-            .map(|p| p.to_path_buf())
-            .collect();
+        let (pending_files, index_reader) = {
+            let state = self.state.lock().await;
+            let pending_files: Vec<_> = state
+                .component_index
+                .get_pending_files()
+                .iter()
+                .map(|p| p.to_path_buf())
+                .collect();
+            (pending_files, Arc::clone(&state.index_reader))
+        };
 
         if pending_files.is_empty() {
             debug!(
@@ -970,18 +1242,45 @@ impl ComponentIndexMonitor {
             self.build_directory.display()
         );
 
+        let limiter = Arc::new(Semaphore::new(INDEX_VALIDATION_CONCURRENCY));
+        let mut reads = JoinSet::new();
+        for source_file in pending_files {
+            let index_reader = Arc::clone(&index_reader);
+            let limiter = Arc::clone(&limiter);
+            reads.spawn(async move {
+                let _permit = limiter.acquire_owned().await;
+                let index_entry = index_reader
+                    .read_index_for_file(&source_file)
+                    .await
+                    .map_err(|e| e.to_string());
+                (source_file, index_entry)
+            });
+        }
+
+        let mut read_results = Vec::new();
+        while let Some(joined) = reads.join_next().await {
+            match joined {
+                Ok(read_result) => read_results.push(read_result),
+                Err(e) => warn!("Index validation task failed: {}", e),
+            }
+        }
+
         let mut files_validated = 0;
         let mut files_invalid = 0;
         let mut validation_errors = Vec::new();
 
-        for source_file in &pending_files {
-            match state.index_reader.read_index_for_file(source_file).await {
+        let mut state = self.state.lock().await;
+        for (source_file, index_entry) in read_results {
+            match index_entry {
                 Ok(index_entry) => {
-                    let validation_result = self.validate_index_entry(source_file, &index_entry);
+                    let validation_result = self.validate_index_entry(&source_file, &index_entry);
                     match validation_result {
                         IndexValidationResult::Valid => {
-                            state.component_index.mark_file_indexed(source_file);
-                            files_validated += 1;
+                            // clangd may have picked the file up while the index was being read
+                            if state.component_index.is_file_pending(&source_file) {
+                                state.component_index.mark_file_indexed(&source_file);
+                                files_validated += 1;
+                            }
                         }
                         IndexValidationResult::Invalid(error_msg) => {
                             files_invalid += 1;
@@ -1007,6 +1306,7 @@ impl ComponentIndexMonitor {
                 }
             }
         }
+        drop(state);
 
         // Log summary of validation results
         if files_validated > 0 || files_invalid > 0 {
@@ -1081,7 +1381,8 @@ impl ComponentIndexMonitor {
     ///
     /// This method selects the first source file from the compilation database and
     /// triggers indexing for it. This is typically called after monitor creation
-    /// to initiate the indexing process.
+    /// to initiate the indexing process. While the index model is built in the
+    /// background this is a no-op; the build triggers indexing once it is done.
     ///
     /// # Arguments
     /// * `compilation_db` - The compilation database to get source files from
@@ -1093,7 +1394,9 @@ impl ComponentIndexMonitor {
         &self,
         compilation_db: Arc<CompilationDatabase>,
     ) -> Result<(), ProjectError> {
-        if self.index_trigger.is_some() {
+        if self.is_model_building() {
+            debug!("Index model still building, deferring initial indexing trigger");
+        } else if self.index_trigger.is_some() {
             // Use canonical files from the single source of truth
             let canonical_files = compilation_db.canonical_source_files().map_err(|e| {
                 ProjectError::SessionCreation(format!(
@@ -1397,6 +1700,66 @@ mod tests {
         assert!((state.coverage() - 0.5).abs() < 0.001);
     }
 
+    #[tokio::test]
+    async fn test_background_model_build_replays_deferred_events() {
+        let mut mock_reader = MockIndexReaderTrait::new();
+        mock_reader.expect_read_index_for_file().returning(|path| {
+            let path = path.to_path_buf();
+            Box::pin(async move {
+                Ok(crate::project::index::reader::IndexEntry {
+                    absolute_path: path,
+                    status: crate::project::index::reader::FileIndexStatus::None,
+                    index_format_version: None,
+                    expected_format_version: 19,
+                    index_content_hash: None,
+                    current_file_hash: None,
+                    symbols: vec![],
+                    index_file_size: None,
+                    index_created_at: None,
+                })
+            })
+        });
+
+        let monitor = ComponentIndexMonitor::create_monitor(
+            PathBuf::from("/test/project/build"),
+            Arc::new(create_test_compilation_db()),
+            Arc::new(mock_reader),
+            &create_test_clangd_version(),
+            None,
+            true,
+            0, // build every model in the background
+        )
+        .await
+        .expect("Failed to create ComponentIndexMonitor");
+
+        monitor
+            .handle_progress_event(ProgressEvent::OverallIndexingStarted)
+            .await;
+        monitor
+            .handle_progress_event(ProgressEvent::FileIndexingCompleted {
+                path: PathBuf::from("/test/project/src/main.cpp"),
+                symbols: 10,
+                refs: 20,
+            })
+            .await;
+        monitor
+            .handle_progress_event(ProgressEvent::OverallCompleted)
+            .await;
+
+        for _ in 0..100 {
+            if !monitor.is_model_building() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let state = monitor.get_component_state().await;
+        assert!(!state.model_building);
+        assert_eq!(state.total_cdb_files, 1);
+        assert_eq!(state.indexed_cdb_files, 1);
+        assert_eq!(state.state, ComponentIndexingState::Completed);
+    }
+
     #[tokio::test]
     async fn test_indexing_failure_handling() {
         let mock_reader = Arc::new(MockIndexReaderTrait::new()) as Arc<dyn IndexReaderTrait>;
@@ -1617,6 +1980,70 @@ mod tests {
         assert_eq!(final_state.indexed_cdb_files, 1);
     }
 
+    #[tokio::test]
+    async fn test_rescan_validates_pending_files_concurrently() {
+        let file_count = INDEX_VALIDATION_CONCURRENCY * 2 + 1;
+        let files: Vec<PathBuf> = (0..file_count)
+            .map(|i| PathBuf::from(format!("/test/project/src/file{i}.cpp")))
+            .collect();
+
+        // Every file except the first has a valid index on disk
+        let mut mock_reader = MockIndexReaderTrait::new();
+        let unindexed = files[0].clone();
+        mock_reader
+            .expect_read_index_for_file()
+            .returning(move |path| {
+                let status = if path == unindexed {
+                    crate::project::index::reader::FileIndexStatus::None
+                } else {
+                    crate::project::index::reader::FileIndexStatus::Done
+                };
+                let path = path.to_path_buf();
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    Ok(crate::project::index::reader::IndexEntry {
+                        absolute_path: path,
+                        status,
+                        index_format_version: Some(19),
+                        expected_format_version: 19,
+                        index_content_hash: None,
+                        current_file_hash: None,
+                        symbols: vec![],
+                        index_file_size: None,
+                        index_created_at: None,
+                    })
+                })
+            })
+            .times(file_count);
+        let mock_reader = Arc::new(mock_reader) as Arc<dyn IndexReaderTrait>;
+
+        use json_compilation_db::Entry;
+        let entries = files
+            .iter()
+            .map(|file| Entry {
+                directory: PathBuf::from("/test/project"),
+                file: file.clone(),
+                arguments: vec!["clang++".to_string(), file.display().to_string()],
+                output: None,
+            })
+            .collect();
+
+        let monitor = ComponentIndexMonitor::new_for_test(
+            PathBuf::from("/test/project/build"),
+            Arc::new(CompilationDatabase::from_entries(entries)),
+            mock_reader,
+            &create_test_clangd_version(),
+        )
+        .await
+        .expect("Failed to create ComponentIndexMonitor");
+
+        monitor.rescan_and_validate_untracked_files().await.unwrap();
+
+        let state = monitor.get_component_state().await;
+        assert_eq!(state.indexed_cdb_files, file_count - 1);
+        assert_eq!(state.pending_cdb_files, 1);
+    }
+
     #[tokio::test]
     async fn test_get_indexing_summary() {
         let mock_reader = Arc::new(MockIndexReaderTrait::new()) as Arc<dyn IndexReaderTrait>;
@@ -1756,6 +2183,7 @@ mod tests {
             mock_reader,
             &create_test_clangd_version(),
             Some(trigger),
+            true,
         )
         .await
         .expect("Failed to create ComponentIndexMonitor");
@@ -1841,6 +2269,7 @@ mod tests {
             mock_reader,
             &create_test_clangd_version(),
             Some(trigger),
+            true,
         )
         .await
         .expect("Failed to create ComponentIndexMonitor");
@@ -1873,6 +2302,7 @@ mod tests {
             mock_reader,
             &create_test_clangd_version(),
             Some(trigger),
+            true,
         )
         .await
         .expect("Failed to create ComponentIndexMonitor");
@@ -0,0 +1,142 @@
+//! Retry of empty index queries right after indexing completes
+//!
+//! clangd reports the end of background indexing before every index shard has
+//! been loaded into the symbol index it serves queries from. Workspace queries
+//! sent in that window can come back empty even though the symbol exists. This
+//! module retries such queries a few times while indexing has only just ended.
+
+use std::future::Future;
+use std::time::Duration;
+use tracing::debug;
+
+/// Default number of retries of an empty result
+pub const DEFAULT_EMPTY_RESULT_RETRIES: u32 = 5;
+
+/// Default delay between retries in milliseconds
+pub const DEFAULT_EMPTY_RESULT_RETRY_INTERVAL_MS: u64 = 1000;
+
+/// Default time after indexing completion during which empty results are retried
+pub const DEFAULT_POST_INDEXING_WINDOW_SECS: u64 = 5;
+
+/// Backoff parameters for retrying empty results after indexing completes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyResultRetry {
+    /// Maximum number of retries of an empty result
+    pub retries: u32,
+    /// Delay between retries
+    pub interval: Duration,
+    /// Time after indexing completion during which empty results are retried
+    pub window: Duration,
+}
+
+impl Default for EmptyResultRetry {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_EMPTY_RESULT_RETRIES,
+            interval: Duration::from_millis(DEFAULT_EMPTY_RESULT_RETRY_INTERVAL_MS),
+            window: Duration::from_secs(DEFAULT_POST_INDEXING_WINDOW_SECS),
+        }
+    }
+}
+
+impl EmptyResultRetry {
+    /// Run a query, repeating it while it is empty within the post-indexing window
+    ///
+    /// # Arguments
+    /// * `since_completion` - Time elapsed since indexing completed, None if it has not
+    /// * `is_empty` - Whether a successful result should be retried
+    /// * `query` - Sends the query; errors are returned without retrying
+    pub async fn run<T, E, S, SFut, F, Fut>(
+        &self,
+        since_completion: S,
+        is_empty: impl Fn(&T) -> bool,
+        mut query: F,
+    ) -> Result<T, E>
+    where
+        S: Fn() -> SFut,
+        SFut: Future<Output = Option<Duration>>,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut result = query().await?;
+
+        for attempt in 1..=self.retries {
+            if !is_empty(&result) {
+                break;
+            }
+            match since_completion().await {
+                Some(elapsed) if elapsed < self.window => {}
+                _ => break,
+            }
+
+            debug!(
+                "Empty result shortly after indexing completed, retry {}/{} in {:?}",
+                attempt, self.retries, self.interval
+            );
+            tokio::time::sleep(self.interval).await;
+            result = query().await?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn retry(retries: u32) -> EmptyResultRetry {
+        EmptyResultRetry {
+            retries,
+            interval: Duration::from_millis(1),
+            window: Duration::from_secs(5),
+        }
+    }
+
+    async fn query(calls: &AtomicU32, empty_calls: u32) -> Result<Vec<u32>, String> {
+        let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(if call > empty_calls {
+            vec![call]
+        } else {
+            vec![]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retries_empty_results_within_window() {
+        let calls = AtomicU32::new(0);
+        let just_completed = || async { Some(Duration::from_secs(1)) };
+
+        let result = retry(5)
+            .run(just_completed, Vec::is_empty, || query(&calls, 2))
+            .await;
+        assert_eq!(result, Ok(vec![3]));
+
+        // Gives up after the configured number of retries
+        calls.store(0, Ordering::SeqCst);
+        let result = retry(2)
+            .run(just_completed, Vec::is_empty, || query(&calls, 10))
+            .await;
+        assert_eq!(result, Ok(vec![]));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_outside_window() {
+        let calls = AtomicU32::new(0);
+
+        let long_ago = || async { Some(Duration::from_secs(60)) };
+        let result = retry(5)
+            .run(long_ago, Vec::is_empty, || query(&calls, 10))
+            .await;
+        assert_eq!(result, Ok(vec![]));
+
+        let not_completed = || async { None };
+        let result = retry(5)
+            .run(not_completed, Vec::is_empty, || query(&calls, 10))
+            .await;
+        assert_eq!(result, Ok(vec![]));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
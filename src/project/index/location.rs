@@ -0,0 +1,331 @@
+//! Location of the clangd index
+//!
+//! clangd writes its background index to `.cache/clangd/index` next to the
+//! compilation database it uses and has no option to move it. A configured
+//! index directory is therefore linked into that place before clangd starts,
+//! so clangd, the index reader and the status report all use the same
+//! directory. A static index built elsewhere (e.g. a shared index of a large
+//! code base) is loaded with clangd's `--index-file` instead.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::project::ProjectError;
+
+/// File next to the default index directory recording the link target this
+/// server created, so a link it made for another configuration can be replaced
+const INDEX_LINK_MARKER: &str = ".mcp-cpp-index-link";
+
+/// Index settings applied to every build directory
+#[derive(Debug, Clone, Default)]
+pub struct IndexStorageOptions {
+    /// Directory of the background index instead of clangd's default
+    pub directory: Option<PathBuf>,
+    /// Static index file loaded by clangd with `--index-file`
+    pub static_index_file: Option<PathBuf>,
+}
+
+/// Where the background index of a build directory comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexDirectorySource {
+    /// `.cache/clangd/index` next to the compilation database
+    Default,
+    /// Configured index directory
+    Configured,
+}
+
+/// Effective index location of a build directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexLocation {
+    /// Directory holding the background index shards
+    pub directory: PathBuf,
+    pub source: IndexDirectorySource,
+    /// Whether clangd cannot write the directory: shards are only loaded, and
+    /// files changed since the index was built are not persisted
+    pub read_only: bool,
+    /// Static index loaded by clangd in addition to the background index
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub static_index_file: Option<PathBuf>,
+    /// Why the configured index directory is not used
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub warning: Option<String>,
+}
+
+/// clangd's background index directory for a compilation database directory
+pub fn default_index_directory(compile_commands_dir: &Path) -> PathBuf {
+    compile_commands_dir
+        .join(".cache")
+        .join("clangd")
+        .join("index")
+}
+
+/// Resolve a configured index path against the project root
+///
+/// Relative paths are taken relative to the project root.
+pub fn resolve_index_path(path: &Path, project_root: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        project_root.join(path)
+    }
+}
+
+/// Check that a static index file can be loaded by clangd
+pub fn validate_static_index_file(path: &Path) -> Result<(), String> {
+    match std::fs::File::open(path) {
+        Ok(_) if path.is_file() => Ok(()),
+        Ok(_) => Err(format!("index file {} is not a file", path.display())),
+        Err(e) => Err(format!("cannot read index file {}: {}", path.display(), e)),
+    }
+}
+
+/// Whether files can be created in a directory
+///
+/// A missing directory is writable when its nearest existing ancestor is, as
+/// clangd creates it on first use. Nothing is created by the check.
+fn is_writable_directory(directory: &Path) -> bool {
+    directory
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .is_some_and(can_write)
+}
+
+#[cfg(unix)]
+fn can_write(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: `path` is a valid NUL-terminated string for the duration of the call
+    unsafe { libc::access(path.as_ptr(), libc::W_OK | libc::X_OK) == 0 }
+}
+
+#[cfg(not(unix))]
+fn can_write(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+/// Point clangd's default index directory at the configured one
+///
+/// Nothing is changed when the default directory already links there. A link
+/// this server created for another directory is replaced. Any other entry at
+/// the default place is left alone, since it may hold an index the user still
+/// wants.
+fn link_index_directory(default: &Path, configured: &Path) -> Result<(), String> {
+    let current_target = std::fs::read_link(default).ok();
+    if current_target.as_deref() == Some(configured) {
+        return Ok(());
+    }
+
+    let marker = default.with_file_name(INDEX_LINK_MARKER);
+    match current_target {
+        Some(target)
+            if std::fs::read_to_string(&marker)
+                .is_ok_and(|recorded| Path::new(&recorded) == target) =>
+        {
+            info!(
+                "Replacing index link {} -> {} with {}",
+                default.display(),
+                target.display(),
+                configured.display()
+            );
+            remove_symlink_dir(default)
+                .map_err(|e| format!("cannot remove {}: {}", default.display(), e))?;
+        }
+        Some(target) => {
+            return Err(format!(
+                "{} links to {}; remove it to let clangd use {}",
+                default.display(),
+                target.display(),
+                configured.display()
+            ));
+        }
+        None if default.symlink_metadata().is_ok() => {
+            return Err(format!(
+                "{} already exists; remove it to let clangd use {}",
+                default.display(),
+                configured.display()
+            ));
+        }
+        None => {}
+    }
+
+    std::fs::create_dir_all(configured)
+        .map_err(|e| format!("cannot create {}: {}", configured.display(), e))?;
+    if let Some(parent) = default.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    symlink_dir(configured, default)
+        .map_err(|e| format!("cannot link {}: {}", default.display(), e))?;
+    std::fs::write(&marker, configured.to_string_lossy().as_bytes())
+        .map_err(|e| format!("cannot write {}: {}", marker.display(), e))
+}
+
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+#[cfg(unix)]
+fn remove_symlink_dir(link: &Path) -> std::io::Result<()> {
+    std::fs::remove_file(link)
+}
+
+#[cfg(windows)]
+fn remove_symlink_dir(link: &Path) -> std::io::Result<()> {
+    std::fs::remove_dir(link)
+}
+
+/// Set up the index location of a build directory before clangd starts
+///
+/// `compile_commands_dir` is the directory clangd reads compile_commands.json
+/// from. When the configured directory cannot be linked into clangd's default
+/// place, the default directory is used instead and the reason reported as a
+/// warning.
+pub fn prepare_index_location(
+    compile_commands_dir: &Path,
+    options: &IndexStorageOptions,
+) -> Result<IndexLocation, ProjectError> {
+    if let Some(file) = &options.static_index_file {
+        validate_static_index_file(file).map_err(ProjectError::SessionCreation)?;
+    }
+
+    let default = default_index_directory(compile_commands_dir);
+    let (directory, source, warning) = match &options.directory {
+        Some(configured) => match link_index_directory(&default, configured) {
+            Ok(()) => (configured.clone(), IndexDirectorySource::Configured, None),
+            Err(e) => {
+                let warning = format!(
+                    "Index directory {} not used, falling back to {}: {}",
+                    configured.display(),
+                    default.display(),
+                    e
+                );
+                warn!("{}", warning);
+                (default, IndexDirectorySource::Default, Some(warning))
+            }
+        },
+        None => (default, IndexDirectorySource::Default, None),
+    };
+
+    let read_only = !is_writable_directory(&directory);
+    if read_only {
+        warn!(
+            "Index directory {} is not writable: existing index shards are used but not updated",
+            directory.display()
+        );
+    }
+    info!("clangd index directory: {}", directory.display());
+
+    Ok(IndexLocation {
+        directory,
+        source,
+        read_only,
+        static_index_file: options.static_index_file.clone(),
+        warning,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_index_location() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let location =
+            prepare_index_location(temp_dir.path(), &IndexStorageOptions::default()).unwrap();
+        assert_eq!(
+            location.directory,
+            temp_dir.path().join(".cache/clangd/index")
+        );
+        assert_eq!(location.source, IndexDirectorySource::Default);
+        assert!(!location.read_only);
+        assert!(location.warning.is_none());
+        // Checking writability leaves the directory for clangd to create
+        assert!(!temp_dir.path().join(".cache").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_configured_index_directory_is_linked() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let build_dir = temp_dir.path().join("build");
+        let shared = temp_dir.path().join("shared-index");
+        let options = IndexStorageOptions {
+            directory: Some(shared.clone()),
+            static_index_file: None,
+        };
+
+        let location = prepare_index_location(&build_dir, &options).unwrap();
+        assert_eq!(location.directory, shared);
+        assert_eq!(location.source, IndexDirectorySource::Configured);
+        assert_eq!(
+            std::fs::read_link(build_dir.join(".cache/clangd/index")).unwrap(),
+            shared
+        );
+
+        // Linking again is a no-op
+        assert_eq!(
+            prepare_index_location(&build_dir, &options).unwrap(),
+            location
+        );
+
+        // A link created for a previously configured directory is replaced
+        let moved = temp_dir.path().join("moved-index");
+        let moved_options = IndexStorageOptions {
+            directory: Some(moved.clone()),
+            static_index_file: None,
+        };
+        let location = prepare_index_location(&build_dir, &moved_options).unwrap();
+        assert_eq!(location.source, IndexDirectorySource::Configured);
+        assert_eq!(
+            std::fs::read_link(build_dir.join(".cache/clangd/index")).unwrap(),
+            moved
+        );
+
+        // An existing index at the default place is kept and reported
+        let other_build = temp_dir.path().join("other");
+        std::fs::create_dir_all(other_build.join(".cache/clangd/index")).unwrap();
+        let location = prepare_index_location(&other_build, &options).unwrap();
+        assert_eq!(location.source, IndexDirectorySource::Default);
+        assert_eq!(location.directory, other_build.join(".cache/clangd/index"));
+        assert!(location.warning.unwrap().contains("already exists"));
+
+        // So is a link the user made
+        let linked_build = temp_dir.path().join("linked");
+        std::fs::create_dir_all(linked_build.join(".cache/clangd")).unwrap();
+        symlink_dir(&moved, &linked_build.join(".cache/clangd/index")).unwrap();
+        let location = prepare_index_location(&linked_build, &options).unwrap();
+        assert_eq!(location.source, IndexDirectorySource::Default);
+        assert!(location.warning.is_some());
+        assert_eq!(
+            std::fs::read_link(linked_build.join(".cache/clangd/index")).unwrap(),
+            moved
+        );
+    }
+
+    #[test]
+    fn test_static_index_file_must_be_readable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index_file = temp_dir.path().join("project.dex");
+        let options = IndexStorageOptions {
+            directory: None,
+            static_index_file: Some(index_file.clone()),
+        };
+        assert!(prepare_index_location(temp_dir.path(), &options).is_err());
+
+        std::fs::write(&index_file, b"RIFF").unwrap();
+        let location = prepare_index_location(temp_dir.path(), &options).unwrap();
+        assert_eq!(location.static_index_file, Some(index_file));
+        assert!(validate_static_index_file(temp_dir.path()).is_err());
+    }
+}
@@ -11,22 +11,32 @@
 //! - `state`: IndexState for tracking compilation database indexing status
 //! - `storage`: Storage trait and implementations for index backends
 //! - `component_monitor`: ComponentIndexMonitor for managing index state per build directory
+//! - `empty_result_retry`: Backoff for empty query results right after indexing completes
+//! - `snapshot`: Point-in-time index state snapshots and their diffs
+//! - `version_check`: Detection of index files written by another clangd version
+//! - `location`: Configured index directory and static index of each build directory
 
 pub mod component_monitor;
+pub mod empty_result_retry;
+pub mod location;
 #[allow(dead_code)]
 pub mod reader;
+pub mod snapshot;
 #[allow(dead_code)]
 pub mod state;
 pub mod status;
 #[allow(dead_code)]
 pub mod storage;
 pub mod trigger;
+pub mod version_check;
 
 // Public exports
 #[cfg(all(test, feature = "clangd-integration-tests"))]
 pub use component_monitor::ComponentIndexState;
 pub use component_monitor::{ComponentIndexMonitor, ComponentIndexingState};
-pub use status::IndexStatusView;
+pub use empty_result_retry::EmptyResultRetry;
+pub use snapshot::{IndexSnapshot, IndexSnapshotDiff};
+pub use status::{IndexQueueView, IndexStatusView, WorkspaceIndexStatusView};
 pub use trigger::ClangdIndexTrigger;
 
 #[cfg(all(test, feature = "clangd-integration-tests"))]
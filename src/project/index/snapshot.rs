@@ -0,0 +1,217 @@
+//! Index state snapshots
+//!
+//! A snapshot records the indexing state of every compilation database file of
+//! a component together with the modification time of its index file. Diffing
+//! two snapshots shows the effect of a rebuild or of ongoing background indexing
+//! without re-querying every file: which files were newly indexed, re-indexed,
+//! dropped from the index or failed in between.
+//!
+//! Index files are compared by modification time only; the symbols they
+//! contain are not parsed, so symbol-level changes are not reported.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::clangd::index::ComponentIndex;
+use crate::clangd::index::component_index::FileIndexState;
+
+/// Indexing state of a file as recorded in a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotFileState {
+    Pending,
+    InProgress,
+    Indexed,
+    Failed,
+}
+
+impl From<&FileIndexState> for SnapshotFileState {
+    fn from(state: &FileIndexState) -> Self {
+        match state {
+            FileIndexState::Pending => Self::Pending,
+            FileIndexState::InProgress => Self::InProgress,
+            FileIndexState::Indexed => Self::Indexed,
+            FileIndexState::Failed(_) => Self::Failed,
+        }
+    }
+}
+
+/// A file of an index snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSnapshot {
+    /// Indexing state of the file
+    pub state: SnapshotFileState,
+    /// Modification time of the file's index shard, None if it does not exist
+    pub index_modified: Option<SystemTime>,
+}
+
+/// Indexing state of all compilation database files at one point in time
+#[derive(Debug, Clone)]
+pub struct IndexSnapshot {
+    /// Identifier used to refer to this snapshot in later diffs
+    pub id: String,
+    /// When the snapshot was taken
+    pub taken_at: SystemTime,
+    /// Files keyed by source path
+    pub files: BTreeMap<PathBuf, FileSnapshot>,
+}
+
+/// Changes between two index snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexSnapshotDiff {
+    /// Files indexed in the later snapshot but not in the earlier one
+    pub newly_indexed: Vec<PathBuf>,
+    /// Files indexed in both snapshots whose index shard was rewritten
+    pub reindexed: Vec<PathBuf>,
+    /// Files indexed in the earlier snapshot but not in the later one
+    pub no_longer_indexed: Vec<PathBuf>,
+    /// Files failed in the later snapshot but not in the earlier one
+    pub newly_failed: Vec<PathBuf>,
+    /// Files added to the compilation database
+    pub added_files: Vec<PathBuf>,
+    /// Files removed from the compilation database
+    pub removed_files: Vec<PathBuf>,
+}
+
+impl IndexSnapshotDiff {
+    /// Whether no change was found
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl IndexSnapshot {
+    /// Capture the current state of a component index
+    pub fn capture(id: String, index: &ComponentIndex) -> Self {
+        let files = index
+            .source_files()
+            .into_iter()
+            .map(|source| {
+                let state = index
+                    .get_file_state(source)
+                    .map_or(SnapshotFileState::Pending, SnapshotFileState::from);
+                let index_modified = index
+                    .get_index_file(source)
+                    .and_then(|index_file| std::fs::metadata(index_file).ok())
+                    .and_then(|metadata| metadata.modified().ok());
+                (
+                    source.to_path_buf(),
+                    FileSnapshot {
+                        state,
+                        index_modified,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            id,
+            taken_at: SystemTime::now(),
+            files,
+        }
+    }
+
+    /// Report what changed from this snapshot to a later one
+    pub fn diff(&self, later: &IndexSnapshot) -> IndexSnapshotDiff {
+        let mut diff = IndexSnapshotDiff::default();
+
+        for (path, after) in &later.files {
+            let Some(before) = self.files.get(path) else {
+                diff.added_files.push(path.clone());
+                if after.state == SnapshotFileState::Indexed {
+                    diff.newly_indexed.push(path.clone());
+                }
+                continue;
+            };
+
+            let was_indexed = before.state == SnapshotFileState::Indexed;
+            let is_indexed = after.state == SnapshotFileState::Indexed;
+            match (was_indexed, is_indexed) {
+                (false, true) => diff.newly_indexed.push(path.clone()),
+                (true, false) => diff.no_longer_indexed.push(path.clone()),
+                (true, true) if after.index_modified != before.index_modified => {
+                    diff.reindexed.push(path.clone())
+                }
+                _ => {}
+            }
+            if after.state == SnapshotFileState::Failed && before.state != after.state {
+                diff.newly_failed.push(path.clone());
+            }
+        }
+
+        diff.removed_files = self
+            .files
+            .keys()
+            .filter(|path| !later.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn snapshot(id: &str, files: &[(&str, SnapshotFileState, u64)]) -> IndexSnapshot {
+        IndexSnapshot {
+            id: id.to_string(),
+            taken_at: SystemTime::now(),
+            files: files
+                .iter()
+                .map(|(path, state, modified)| {
+                    (
+                        PathBuf::from(path),
+                        FileSnapshot {
+                            state: *state,
+                            index_modified: (*modified > 0)
+                                .then(|| SystemTime::UNIX_EPOCH + Duration::from_secs(*modified)),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_state_changes() {
+        use SnapshotFileState::*;
+        let before = snapshot(
+            "1",
+            &[
+                ("/p/a.cpp", Pending, 0),
+                ("/p/b.cpp", Indexed, 10),
+                ("/p/c.cpp", Indexed, 10),
+                ("/p/d.cpp", Indexed, 10),
+                ("/p/e.cpp", InProgress, 0),
+                ("/p/gone.cpp", Indexed, 10),
+            ],
+        );
+        let after = snapshot(
+            "2",
+            &[
+                ("/p/a.cpp", Indexed, 20),
+                ("/p/b.cpp", Indexed, 20),
+                ("/p/c.cpp", Indexed, 10),
+                ("/p/d.cpp", Pending, 0),
+                ("/p/e.cpp", Failed, 0),
+                ("/p/new.cpp", Indexed, 20),
+            ],
+        );
+
+        let diff = before.diff(&after);
+        let paths = |names: &[&str]| names.iter().map(PathBuf::from).collect::<Vec<_>>();
+        assert_eq!(diff.newly_indexed, paths(&["/p/a.cpp", "/p/new.cpp"]));
+        assert_eq!(diff.reindexed, paths(&["/p/b.cpp"]));
+        assert_eq!(diff.no_longer_indexed, paths(&["/p/d.cpp"]));
+        assert_eq!(diff.newly_failed, paths(&["/p/e.cpp"]));
+        assert_eq!(diff.added_files, paths(&["/p/new.cpp"]));
+        assert_eq!(diff.removed_files, paths(&["/p/gone.cpp"]));
+
+        assert!(after.diff(&after).is_empty());
+    }
+}
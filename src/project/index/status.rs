@@ -6,6 +6,17 @@
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, SystemTime};
 
+use crate::clangd::config::PchStorage;
+
+/// Minimum time indexing must have run before an ETA is extrapolated
+const MIN_ETA_ELAPSED: Duration = Duration::from_secs(2);
+
+/// Minimum completed fraction before an ETA is extrapolated
+const MIN_ETA_PROGRESS: f64 = 0.01;
+
+/// Upper bound for reported ETAs
+const MAX_ETA: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// High-level indexing status view for tools and end users
 ///
 /// This struct provides comprehensive information about the current indexing
@@ -34,8 +45,90 @@ pub struct IndexStatusView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_time_remaining: Option<Duration>,
 
+    /// Estimated time remaining in whole seconds (same estimate as `estimated_time_remaining`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_completion_seconds: Option<u64>,
+
     /// Human-readable state description
     pub state: String,
+
+    /// Background index queue depth and throughput, None if not available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<IndexQueueView>,
+
+    /// Number of threads clangd uses for indexing, None if not known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexing_threads: Option<u32>,
+
+    /// Where clangd stores the preambles of open files, None if not known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pch_storage: Option<PchStorage>,
+
+    /// Whether the index model of a large compilation database is still being
+    /// built; file counts are incomplete until it is ready
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub model_building: bool,
+}
+
+/// Background index queue depth and indexing throughput
+///
+/// Lets users tell a large index that is still progressing from one that has
+/// stalled: the queue shrinks and throughput stays above zero while clangd works.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexQueueView {
+    /// Files waiting to be indexed
+    pub pending_files: usize,
+
+    /// Files clangd is currently indexing
+    pub in_progress_files: usize,
+
+    /// Files that failed to index
+    pub failed_files: usize,
+
+    /// Files left before the component is fully indexed (pending + in progress)
+    pub remaining_files: usize,
+
+    /// Files indexed since the running indexing pass started
+    pub indexed_since_start: usize,
+
+    /// Files indexed per second since the running indexing pass started,
+    /// None if no pass is running or it started too recently to measure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_per_second: Option<f64>,
+}
+
+impl IndexQueueView {
+    /// Create a queue view from file counts and the running indexing pass
+    ///
+    /// `start` is the start time of the running pass together with the number
+    /// of files that were already indexed at that time.
+    pub fn new(
+        pending_files: usize,
+        in_progress_files: usize,
+        failed_files: usize,
+        indexed_files: usize,
+        start: Option<(SystemTime, usize)>,
+    ) -> Self {
+        let indexed_since_start = start.map_or(0, |(_, indexed_at_start)| {
+            indexed_files.saturating_sub(indexed_at_start)
+        });
+        let files_per_second = start
+            .and_then(|(start_time, _)| SystemTime::now().duration_since(start_time).ok())
+            .filter(|elapsed| *elapsed >= MIN_ETA_ELAPSED)
+            .map(|elapsed| {
+                let rate = indexed_since_start as f64 / elapsed.as_secs_f64();
+                (rate * 100.0).round() / 100.0
+            });
+
+        Self {
+            pending_files,
+            in_progress_files,
+            failed_files,
+            remaining_files: pending_files + in_progress_files,
+            indexed_since_start,
+            files_per_second,
+        }
+    }
 }
 
 impl IndexStatusView {
@@ -48,8 +141,13 @@ impl IndexStatusView {
         start_time: Option<SystemTime>,
         state: String,
     ) -> Self {
-        let estimated_time_remaining =
-            Self::calculate_eta(indexed_files, total_files, start_time.as_ref(), in_progress);
+        let estimated_time_remaining = Self::calculate_eta(
+            progress_percentage,
+            indexed_files,
+            total_files,
+            start_time.as_ref(),
+            in_progress,
+        );
 
         Self {
             in_progress,
@@ -58,53 +156,93 @@ impl IndexStatusView {
             total_files,
             start_time,
             estimated_time_remaining,
+            estimated_completion_seconds: estimated_time_remaining.map(|eta| eta.as_secs()),
             state,
+            queue: None,
+            indexing_threads: None,
+            pch_storage: None,
+            model_building: false,
         }
     }
 
+    /// Attach background index queue information
+    pub fn with_queue(mut self, queue: IndexQueueView) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Attach the number of threads clangd uses for indexing
+    pub fn with_indexing_threads(mut self, threads: u32) -> Self {
+        self.indexing_threads = Some(threads);
+        self
+    }
+
+    /// Attach where clangd stores the preambles of open files
+    pub fn with_pch_storage(mut self, storage: PchStorage) -> Self {
+        self.pch_storage = Some(storage);
+        self
+    }
+
+    /// Mark whether the index model is still being built
+    pub fn with_model_building(mut self, model_building: bool) -> Self {
+        self.model_building = model_building;
+        self
+    }
+
+    /// Whether clangd has finished indexing, including when some files failed
+    ///
+    /// Queries answered before then may miss symbols and references of the
+    /// files not indexed yet. Without background index (quiet indexing mode)
+    /// there is nothing to wait for.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state.as_str(), "Completed" | "Partial" | "Disabled")
+    }
+
     /// Calculate estimated time remaining based on current progress
     ///
-    /// Formula: ETA = (total_files - indexed_files) * elapsed_time / indexed_files
+    /// Linear extrapolation from the time elapsed since indexing started:
+    /// ETA = elapsed * (1 - done) / done, where `done` is clangd's reported progress
+    /// for the running index pass, or the indexed file ratio when clangd reports none.
+    /// The estimate refines itself as progress accrues.
     ///
     /// Returns None if:
-    /// - No files indexed yet (can't divide by zero)
-    /// - Not in progress
-    /// - No start time available
+    /// - Not in progress or no start time available
+    /// - Fewer than `MIN_ETA_ELAPSED` elapsed or less than `MIN_ETA_PROGRESS` done
+    ///   (too small a sample to extrapolate from)
+    /// - The extrapolation is not a finite number
+    ///
+    /// Estimates are clamped to `MAX_ETA`.
     fn calculate_eta(
+        progress_percentage: Option<f32>,
         indexed_files: usize,
         total_files: usize,
         start_time: Option<&SystemTime>,
         in_progress: bool,
     ) -> Option<Duration> {
-        // Can't calculate ETA if not in progress or no start time
-        if !in_progress || start_time.is_none() {
+        if !in_progress {
             return None;
         }
+        let elapsed = SystemTime::now().duration_since(*start_time?).ok()?;
 
-        // Can't calculate ETA if no files indexed (division by zero)
-        if indexed_files == 0 {
-            return None;
-        }
+        let done = match progress_percentage {
+            Some(percentage) if percentage > 0.0 => f64::from(percentage) / 100.0,
+            _ if total_files > 0 => indexed_files as f64 / total_files as f64,
+            _ => return None,
+        };
 
-        // Already completed
-        if indexed_files >= total_files {
+        if done >= 1.0 {
             return Some(Duration::ZERO);
         }
-
-        let start = start_time?;
-        let elapsed = SystemTime::now().duration_since(*start).ok()?;
-
-        // Avoid division by zero or negative elapsed time
-        if elapsed.is_zero() {
+        if elapsed < MIN_ETA_ELAPSED || done < MIN_ETA_PROGRESS {
             return None;
         }
 
-        // ETA formula: (total_files - indexed_files) * elapsed_time / indexed_files
-        let remaining_files = total_files.saturating_sub(indexed_files);
-        let elapsed_secs = elapsed.as_secs_f64();
-        let eta_seconds = (remaining_files as f64 * elapsed_secs) / (indexed_files as f64);
+        let eta_seconds = elapsed.as_secs_f64() * (1.0 - done) / done;
+        if !eta_seconds.is_finite() || eta_seconds < 0.0 {
+            return None;
+        }
 
-        Some(Duration::from_secs_f64(eta_seconds))
+        Some(Duration::from_secs_f64(eta_seconds).min(MAX_ETA))
     }
 
     /// Check if indexing is complete
@@ -124,6 +262,104 @@ impl IndexStatusView {
     }
 }
 
+/// Indexing status aggregated across several components
+///
+/// Each component is indexed by its own clangd process, so components progress in
+/// parallel: the workspace-wide estimate is the longest component estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceIndexStatusView {
+    /// Whether any component is currently indexing
+    pub in_progress: bool,
+
+    /// Number of files indexed across all components
+    pub indexed_files: usize,
+
+    /// Total number of files to be indexed across all components
+    pub total_files: usize,
+
+    /// Workspace-wide coverage percentage (0-100)
+    pub coverage_percentage: f32,
+
+    /// Files left to index across all components
+    pub remaining_files: usize,
+
+    /// Combined indexing throughput of all components, None if not measurable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_per_second: Option<f64>,
+
+    /// Estimated time until all components finish, None if cannot calculate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_time_remaining: Option<Duration>,
+
+    /// Estimated time until all components finish in whole seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_completion_seconds: Option<u64>,
+
+    /// Whether any component is still building its index model
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub model_building: bool,
+}
+
+impl WorkspaceIndexStatusView {
+    /// Aggregate per-component status views
+    pub fn aggregate<'a>(components: impl IntoIterator<Item = &'a IndexStatusView>) -> Self {
+        let mut in_progress = false;
+        let mut indexed_files = 0;
+        let mut total_files = 0;
+        let mut remaining_files = 0;
+        let mut files_per_second: Option<f64> = None;
+        let mut estimated_time_remaining = Some(Duration::ZERO);
+        let mut model_building = false;
+
+        for component in components {
+            model_building |= component.model_building;
+            indexed_files += component.indexed_files;
+            total_files += component.total_files;
+            match &component.queue {
+                Some(queue) => {
+                    remaining_files += queue.remaining_files;
+                    // Components index in parallel, so their throughputs add up
+                    if let Some(rate) = queue.files_per_second {
+                        files_per_second = Some(files_per_second.unwrap_or(0.0) + rate);
+                    }
+                }
+                None => {
+                    remaining_files += component
+                        .total_files
+                        .saturating_sub(component.indexed_files)
+                }
+            }
+            if component.in_progress {
+                in_progress = true;
+                // An unknown component estimate makes the workspace estimate unknown
+                estimated_time_remaining = estimated_time_remaining
+                    .zip(component.estimated_time_remaining)
+                    .map(|(longest, eta)| longest.max(eta));
+            }
+        }
+
+        let coverage_percentage = if total_files == 0 {
+            100.0
+        } else {
+            indexed_files as f32 * 100.0 / total_files as f32
+        };
+
+        let estimated_time_remaining = estimated_time_remaining.filter(|_| in_progress);
+
+        Self {
+            in_progress,
+            indexed_files,
+            total_files,
+            coverage_percentage,
+            remaining_files,
+            files_per_second,
+            estimated_time_remaining,
+            estimated_completion_seconds: estimated_time_remaining.map(|eta| eta.as_secs()),
+            model_building,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,13 +367,13 @@ mod tests {
 
     #[test]
     fn test_index_status_view_creation() {
-        let now = SystemTime::now();
+        let started = SystemTime::now() - Duration::from_secs(10);
         let view = IndexStatusView::new(
             true,
             Some(50.0),
             10,
             20,
-            Some(now),
+            Some(started),
             "InProgress".to_string(),
         );
 
@@ -145,10 +381,64 @@ mod tests {
         assert_eq!(view.progress_percentage, Some(50.0));
         assert_eq!(view.indexed_files, 10);
         assert_eq!(view.total_files, 20);
-        assert_eq!(view.start_time, Some(now));
+        assert_eq!(view.start_time, Some(started));
         assert_eq!(view.state, "InProgress");
         // ETA should be calculated since we have indexed files
         assert!(view.estimated_time_remaining.is_some());
+        assert_eq!(view.estimated_completion_seconds, Some(10));
+    }
+
+    #[test]
+    fn test_eta_linear_extrapolation() {
+        let started = SystemTime::now() - Duration::from_secs(30);
+
+        // clangd progress is preferred over the indexed file ratio
+        let view = IndexStatusView::new(
+            true,
+            Some(25.0),
+            90,
+            100,
+            Some(started),
+            "InProgress".to_string(),
+        );
+        assert_eq!(view.estimated_completion_seconds, Some(90));
+
+        // Without clangd progress the file ratio is used
+        let view = IndexStatusView::new(true, None, 75, 100, Some(started), "InProgress".into());
+        assert_eq!(view.estimated_completion_seconds, Some(10));
+    }
+
+    #[test]
+    fn test_eta_requires_minimum_sample() {
+        // Indexing just started
+        let view = IndexStatusView::new(
+            true,
+            Some(50.0),
+            10,
+            20,
+            Some(SystemTime::now()),
+            "InProgress".to_string(),
+        );
+        assert!(view.estimated_time_remaining.is_none());
+        assert!(view.estimated_completion_seconds.is_none());
+
+        // Too little progress to extrapolate from
+        let started = SystemTime::now() - Duration::from_secs(60);
+        let view = IndexStatusView::new(true, Some(0.5), 0, 20, Some(started), "InProgress".into());
+        assert!(view.estimated_time_remaining.is_none());
+    }
+
+    #[test]
+    fn test_eta_is_clamped() {
+        let started = SystemTime::now() - Duration::from_secs(3 * 24 * 60 * 60);
+        let view = IndexStatusView::new(true, Some(2.0), 1, 50, Some(started), "InProgress".into());
+        assert_eq!(view.estimated_time_remaining, Some(MAX_ETA));
+
+        // A start time in the future yields no estimate
+        let started = SystemTime::now() + Duration::from_secs(60);
+        let view =
+            IndexStatusView::new(true, Some(50.0), 5, 10, Some(started), "InProgress".into());
+        assert!(view.estimated_time_remaining.is_none());
     }
 
     #[test]
@@ -198,6 +488,27 @@ mod tests {
         assert!(view.estimated_time_remaining.is_none());
     }
 
+    #[test]
+    fn test_queue_depth_and_throughput() {
+        let started = SystemTime::now() - Duration::from_secs(10);
+        let queue = IndexQueueView::new(30, 2, 1, 45, Some((started, 5)));
+        assert_eq!(queue.remaining_files, 32);
+        assert_eq!(queue.indexed_since_start, 40);
+        assert_eq!(queue.files_per_second, Some(4.0));
+
+        // Too short a pass to measure, and no pass at all
+        let queue = IndexQueueView::new(30, 2, 1, 45, Some((SystemTime::now(), 5)));
+        assert!(queue.files_per_second.is_none());
+        let queue = IndexQueueView::new(0, 0, 0, 45, None);
+        assert_eq!(queue.indexed_since_start, 0);
+        assert!(queue.files_per_second.is_none());
+
+        let view = IndexStatusView::new(true, None, 45, 78, Some(started), "InProgress".into())
+            .with_queue(IndexQueueView::new(30, 2, 1, 45, Some((started, 5))));
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(json["queue"]["remaining_files"], 32);
+    }
+
     #[test]
     fn test_is_complete() {
         let view1 = IndexStatusView::new(false, Some(100.0), 20, 20, None, "Completed".to_string());
@@ -216,4 +527,40 @@ mod tests {
         let view2 = IndexStatusView::new(false, None, 0, 0, None, "Completed".to_string());
         assert_eq!(view2.completion_ratio(), 1.0);
     }
+
+    #[test]
+    fn test_workspace_aggregate() {
+        let completed = IndexStatusView::new(false, None, 30, 30, None, "Completed".to_string());
+        let mut indexing =
+            IndexStatusView::new(true, Some(25.0), 10, 40, None, "InProgress".to_string());
+        indexing.estimated_time_remaining = Some(Duration::from_secs(60));
+        let mut slower = indexing.clone();
+        slower.estimated_time_remaining = Some(Duration::from_secs(90));
+
+        let workspace = WorkspaceIndexStatusView::aggregate([&completed, &indexing, &slower]);
+        assert!(workspace.in_progress);
+        assert_eq!(workspace.indexed_files, 50);
+        assert_eq!(workspace.total_files, 110);
+        assert_eq!(workspace.remaining_files, 60);
+        assert!(workspace.files_per_second.is_none());
+        assert!((workspace.coverage_percentage - 45.45).abs() < 0.01);
+        assert_eq!(
+            workspace.estimated_time_remaining,
+            Some(Duration::from_secs(90))
+        );
+
+        // Unknown component ETA makes the workspace ETA unknown
+        slower.estimated_time_remaining = None;
+        let workspace = WorkspaceIndexStatusView::aggregate([&indexing, &slower]);
+        assert!(workspace.estimated_time_remaining.is_none());
+
+        let workspace = WorkspaceIndexStatusView::aggregate([&completed]);
+        assert!(!workspace.in_progress);
+        assert_eq!(workspace.coverage_percentage, 100.0);
+        assert!(workspace.estimated_time_remaining.is_none());
+
+        let workspace = WorkspaceIndexStatusView::aggregate([]);
+        assert_eq!(workspace.total_files, 0);
+        assert_eq!(workspace.coverage_percentage, 100.0);
+    }
 }
@@ -0,0 +1,204 @@
+//! Detection of index files left behind by another clangd version
+//!
+//! clangd changes its on-disk index format between major releases, and index
+//! files written in another format cannot be loaded. After a clangd upgrade the
+//! whole index of a build directory has to be rebuilt. The format version is
+//! recorded in a small file next to the index files, so that the mismatch is
+//! detected once at session startup instead of failing for every file.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::clangd::index::idx_parser::{IdxParseError, IdxParser};
+use crate::clangd::version::ClangdVersion;
+
+/// Name of the file recording which clangd wrote an index directory
+pub const INDEX_VERSION_FILE: &str = "mcp-cpp-index-version.json";
+
+/// Contents of `INDEX_VERSION_FILE`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexVersionRecord {
+    /// Index format version of the clangd that last used the directory
+    format_version: u32,
+    /// Version of the clangd that last used the directory
+    clangd_version: String,
+}
+
+/// Outcome of comparing an index directory with the running clangd
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexVersionCheck {
+    /// No index files exist yet
+    Empty,
+    /// Index files match the format of the running clangd
+    Compatible,
+    /// Index files were written in another format and must all be rebuilt
+    Stale {
+        /// Format version found in the index directory
+        found: u32,
+        /// Format version of the running clangd
+        expected: u32,
+        /// clangd version that wrote the index, if recorded
+        written_by: Option<String>,
+    },
+}
+
+/// Compare the index format in `index_dir` with the format of `clangd_version`
+///
+/// The format is taken from `INDEX_VERSION_FILE` when present, otherwise from
+/// the header of one of the index files. Unreadable files count as compatible:
+/// clangd reports those itself, file by file.
+pub fn check_index_version(index_dir: &Path, clangd_version: &ClangdVersion) -> IndexVersionCheck {
+    let expected = clangd_version.index_format_version();
+
+    let (found, written_by) = match read_record(index_dir) {
+        Some(record) => (record.format_version, Some(record.clangd_version)),
+        None => match first_index_file(index_dir) {
+            Some(index_file) => match probe_format_version(&index_file) {
+                Some(found) => (found, None),
+                None => return IndexVersionCheck::Compatible,
+            },
+            None => return IndexVersionCheck::Empty,
+        },
+    };
+
+    if found == expected {
+        IndexVersionCheck::Compatible
+    } else {
+        IndexVersionCheck::Stale {
+            found,
+            expected,
+            written_by,
+        }
+    }
+}
+
+/// Record the running clangd as the writer of `index_dir`
+///
+/// Creates the index directory if clangd has not done so yet.
+pub fn record_index_version(
+    index_dir: &Path,
+    clangd_version: &ClangdVersion,
+) -> std::io::Result<()> {
+    let record = IndexVersionRecord {
+        format_version: clangd_version.index_format_version(),
+        clangd_version: clangd_version.to_string(),
+    };
+    if read_record(index_dir).as_ref() == Some(&record) {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(index_dir)?;
+    let content = serde_json::to_string_pretty(&record).map_err(std::io::Error::other)?;
+    std::fs::write(index_dir.join(INDEX_VERSION_FILE), content)
+}
+
+/// Read the version record of an index directory, if any
+fn read_record(index_dir: &Path) -> Option<IndexVersionRecord> {
+    let content = std::fs::read_to_string(index_dir.join(INDEX_VERSION_FILE)).ok()?;
+    serde_json::from_str(&content)
+        .inspect_err(|e| debug!("Ignoring malformed {}: {}", INDEX_VERSION_FILE, e))
+        .ok()
+}
+
+/// Any `.idx` file of the index directory, picked deterministically
+fn first_index_file(index_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(index_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "idx"))
+        .min()
+}
+
+/// Format version stored in the header of an index file
+fn probe_format_version(index_file: &Path) -> Option<u32> {
+    let data = std::fs::read(index_file).ok()?;
+    match IdxParser::parse_format_version(&data) {
+        Ok(version) | Err(IdxParseError::UnsupportedVersion(version)) => Some(version),
+        Err(e) => {
+            debug!("Cannot read format version of {:?}: {}", index_file, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn clangd(major: u32) -> ClangdVersion {
+        ClangdVersion {
+            major,
+            minor: 1,
+            patch: 0,
+            variant: None,
+            date: None,
+        }
+    }
+
+    /// Minimal index file holding only the meta chunk with the format version
+    fn index_file(format_version: u32) -> Vec<u8> {
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&16u32.to_le_bytes());
+        data.extend_from_slice(b"CdIx");
+        data.extend_from_slice(b"meta");
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(&format_version.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_empty_and_matching_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_dir = temp_dir.path().join("index");
+        assert_eq!(
+            check_index_version(&index_dir, &clangd(18)),
+            IndexVersionCheck::Empty
+        );
+
+        std::fs::create_dir(&index_dir).unwrap();
+        std::fs::write(index_dir.join("main.cpp.0123.idx"), index_file(19)).unwrap();
+        assert_eq!(
+            check_index_version(&index_dir, &clangd(18)),
+            IndexVersionCheck::Compatible
+        );
+    }
+
+    #[test]
+    fn test_index_written_by_older_clangd_is_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.cpp.0123.idx"), index_file(17)).unwrap();
+
+        assert_eq!(
+            check_index_version(temp_dir.path(), &clangd(18)),
+            IndexVersionCheck::Stale {
+                found: 17,
+                expected: 19,
+                written_by: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_recorded_version_takes_precedence() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_dir = temp_dir.path().join("index");
+        record_index_version(&index_dir, &clangd(20)).unwrap();
+        // A leftover file in the old format does not matter once a version is recorded
+        std::fs::write(index_dir.join("main.cpp.0123.idx"), index_file(19)).unwrap();
+
+        assert_eq!(
+            check_index_version(&index_dir, &clangd(20)),
+            IndexVersionCheck::Compatible
+        );
+        assert_eq!(
+            check_index_version(&index_dir, &clangd(18)),
+            IndexVersionCheck::Stale {
+                found: 20,
+                expected: 19,
+                written_by: Some("20.1.0".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Macro definitions of compile commands
+//!
+//! Extracts the `-D`/`-U` flags recorded in a compilation database entry and
+//! queries the compiler driver for the macros it predefines, so tools can show
+//! which macros are in effect for a translation unit and why an `#ifdef`
+//! branch is or is not taken.
+
+use json_compilation_db::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::time::Duration;
+
+/// Time allowed for the compiler driver to print its macro definitions
+const DRIVER_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// Command Line Macros
+// ============================================================================
+
+/// A `-D` or `-U` flag of a compile command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroFlag {
+    /// Macro name, including the parameter list of function-like macros
+    pub name: String,
+    /// Replacement text; `None` for `-U`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<String>,
+    /// Whether the flag undefines the macro
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub undefine: bool,
+}
+
+impl MacroFlag {
+    /// Parse the argument of a `-D` flag: `NAME` defines NAME as 1
+    fn define(argument: &str) -> Self {
+        let (name, value) = match argument.split_once('=') {
+            Some((name, value)) => (name, value),
+            None => (argument, "1"),
+        };
+        Self {
+            name: name.to_string(),
+            value: Some(value.to_string()),
+            undefine: false,
+        }
+    }
+
+    fn undefine(argument: &str) -> Self {
+        Self {
+            name: argument.to_string(),
+            value: None,
+            undefine: true,
+        }
+    }
+}
+
+/// Extract `-D`/`-U` flags from compiler arguments, in command line order
+///
+/// Both joined (`-DNAME`) and separated (`-D NAME`) spellings are recognized,
+/// as well as the long `--define-macro`/`--undefine-macro` forms.
+pub fn macro_flags(arguments: &[String]) -> Vec<MacroFlag> {
+    const DEFINE_FLAGS: &[&str] = &["--define-macro=", "--define-macro", "-D"];
+    const UNDEFINE_FLAGS: &[&str] = &["--undefine-macro=", "--undefine-macro", "-U"];
+
+    let mut flags = Vec::new();
+    let mut args = arguments.iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, undefine) = match DEFINE_FLAGS.iter().find(|flag| arg.starts_with(*flag)) {
+            Some(flag) => (*flag, false),
+            None => match UNDEFINE_FLAGS.iter().find(|flag| arg.starts_with(*flag)) {
+                Some(flag) => (*flag, true),
+                None => continue,
+            },
+        };
+
+        let argument = match &arg[flag.len()..] {
+            "" => match args.next() {
+                Some(next) => next.as_str(),
+                None => break,
+            },
+            joined => joined,
+        };
+
+        flags.push(if undefine {
+            MacroFlag::undefine(argument)
+        } else {
+            MacroFlag::define(argument)
+        });
+    }
+
+    flags
+}
+
+/// A macro in effect for a translation unit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    /// Macro name, including the parameter list of function-like macros
+    pub name: String,
+    /// Replacement text
+    pub value: String,
+}
+
+/// Name of a macro without the parameter list of function-like macros
+fn bare_name(name: &str) -> &str {
+    name.split('(').next().unwrap_or(name)
+}
+
+/// Apply command line flags on top of a macro set, later flags winning
+///
+/// Macros are keyed by their name without parameter list.
+pub fn apply_macro_flags(macros: &mut BTreeMap<String, MacroDefinition>, flags: &[MacroFlag]) {
+    for flag in flags {
+        let key = bare_name(&flag.name).to_string();
+        match &flag.value {
+            Some(value) if !flag.undefine => {
+                macros.insert(
+                    key,
+                    MacroDefinition {
+                        name: flag.name.clone(),
+                        value: value.clone(),
+                    },
+                );
+            }
+            _ => {
+                macros.remove(&key);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Compiler Driver Query
+// ============================================================================
+
+/// Flags dropped from the compile command before asking the driver for its
+/// macros: they name outputs or inputs that do not apply to the query
+const DROPPED_FLAGS_WITH_VALUE: &[&str] = &["-o", "-MF", "-MT", "-MQ", "-MJ"];
+
+/// Ask the compiler of a compile command for the macros in effect
+///
+/// Runs `<compiler> -dM -E -x <language> -` with the flags of the command, so
+/// the result covers the predefined macros of the target and language
+/// standard as well as `-D`/`-U` flags and forced includes. Macros are keyed
+/// by their name without parameter list.
+pub async fn query_driver_macros(
+    entry: &Entry,
+) -> Result<BTreeMap<String, MacroDefinition>, String> {
+    let compiler = entry
+        .arguments
+        .first()
+        .ok_or_else(|| "Compile command has no compiler".to_string())?;
+    let language = match entry.file.extension().and_then(|ext| ext.to_str()) {
+        Some("c") => "c",
+        Some("m") => "objective-c",
+        Some("mm") => "objective-c++",
+        _ => "c++",
+    };
+    let file_name = entry.file.file_name();
+
+    let mut args = Vec::new();
+    let mut entry_args = entry.arguments.iter().skip(1);
+    while let Some(arg) = entry_args.next() {
+        if DROPPED_FLAGS_WITH_VALUE.contains(&arg.as_str()) {
+            entry_args.next();
+            continue;
+        }
+        let is_input = file_name.is_some_and(|name| arg.ends_with(&*name.to_string_lossy()));
+        if is_input
+            || arg == "-c"
+            || arg.starts_with("-o")
+            || arg.starts_with("-M")
+            || arg.starts_with("-x")
+        {
+            continue;
+        }
+        args.push(arg.clone());
+    }
+    args.extend(
+        ["-dM", "-E", "-x", language, "-"]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+
+    let output = tokio::process::Command::new(compiler)
+        .args(&args)
+        .current_dir(&entry.directory)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(DRIVER_QUERY_TIMEOUT, output)
+        .await
+        .map_err(|_| {
+            format!(
+                "{} did not answer within {:?}",
+                compiler, DRIVER_QUERY_TIMEOUT
+            )
+        })?
+        .map_err(|e| format!("Failed to run {}: {}", compiler, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "{} failed ({}): {}",
+            compiler,
+            output.status,
+            stderr.lines().last().unwrap_or_default()
+        ));
+    }
+    Ok(parse_macro_dump(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the `#define` lines printed by `-dM -E`
+pub fn parse_macro_dump(output: &str) -> BTreeMap<String, MacroDefinition> {
+    let mut macros = BTreeMap::new();
+    for line in output.lines() {
+        let Some(definition) = line.strip_prefix("#define ") else {
+            continue;
+        };
+        // Function-like macros: the name ends with the parameter list
+        let name_end = match definition.find(['(', ' ']) {
+            Some(i) if definition.as_bytes()[i] == b'(' => definition[i..]
+                .find(')')
+                .map_or(definition.len(), |j| i + j + 1),
+            Some(i) => i,
+            None => definition.len(),
+        };
+        let name = &definition[..name_end];
+        let value = definition[name_end..].trim_start();
+        macros.insert(
+            bare_name(name).to_string(),
+            MacroDefinition {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+        );
+    }
+    macros
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_macro_flags_in_command_line_order() {
+        let flags = macro_flags(&args(&[
+            "clang++",
+            "-DDEBUG",
+            "-D",
+            "VERSION=2",
+            "-UNDEBUG",
+            "--define-macro=MAX(a,b)=((a)>(b)?(a):(b))",
+            "-Iinclude",
+            "-c",
+            "main.cpp",
+        ]));
+
+        assert_eq!(flags.len(), 4);
+        assert_eq!(flags[0].name, "DEBUG");
+        assert_eq!(flags[0].value.as_deref(), Some("1"));
+        assert_eq!(flags[1].name, "VERSION");
+        assert_eq!(flags[1].value.as_deref(), Some("2"));
+        assert!(flags[2].undefine);
+        assert_eq!(flags[2].name, "NDEBUG");
+        assert_eq!(flags[3].name, "MAX(a,b)");
+        assert_eq!(flags[3].value.as_deref(), Some("((a)>(b)?(a):(b))"));
+    }
+
+    #[test]
+    fn test_apply_flags_on_macro_dump() {
+        let mut macros = parse_macro_dump(
+            "#define __cplusplus 201703L\n\
+             #define __GNUC__ 4\n\
+             #define __has_feature(x) 0\n\
+             #define EMPTY\n",
+        );
+        assert_eq!(macros["__cplusplus"].value, "201703L");
+        assert_eq!(macros["__has_feature"].name, "__has_feature(x)");
+        assert_eq!(macros["__has_feature"].value, "0");
+        assert_eq!(macros["EMPTY"].value, "");
+
+        apply_macro_flags(
+            &mut macros,
+            &macro_flags(&args(&["cc", "-U__GNUC__", "-DLOG=3", "-DLOG=4"])),
+        );
+        assert!(!macros.contains_key("__GNUC__"));
+        assert_eq!(macros["LOG"].value, "4");
+    }
+}
@@ -174,14 +174,9 @@ impl ProjectComponentProvider for MesonProvider {
         };
 
         // Find compilation database
-        let compilation_database_path = self.find_compilation_database(path).ok_or_else(|| {
-            ProjectError::CompilationDatabaseNotFound {
-                path: path
-                    .join("compile_commands.json")
-                    .to_string_lossy()
-                    .to_string(),
-            }
-        })?;
+        let compilation_database_path = self
+            .find_compilation_database(path)
+            .ok_or_else(|| ProjectError::missing_compile_commands("meson", path, &source_root))?;
 
         // Extract generator and build type for structured fields
         let generator = build_options
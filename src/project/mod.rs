@@ -4,23 +4,34 @@
 //! through a provider pattern. Each provider can detect and parse project components
 //! for their respective build system.
 
+pub mod build_targets;
+pub mod cmake_presets;
 pub mod cmake_provider;
 pub mod compilation_database;
 pub mod component;
 pub mod component_session;
 pub mod error;
+pub mod freshness;
+pub mod include_graph;
+pub mod include_paths;
 pub mod index;
+pub mod macro_definitions;
 pub mod meson_provider;
+pub mod project_config;
 pub mod provider;
+pub mod result_exclusions;
 pub mod scanner;
+pub mod setup_check;
+pub mod test_files;
 pub mod workspace;
 pub mod workspace_session;
+pub mod xcode_provider;
 
 pub use cmake_provider::CmakeProvider;
 
 pub use compilation_database::CompilationDatabase;
 
-pub use component::ProjectComponent;
+pub use component::{BuildPreset, ProjectComponent};
 
 pub use component_session::ComponentSession;
 
@@ -28,10 +39,18 @@ pub use error::ProjectError;
 
 pub use meson_provider::MesonProvider;
 
+pub use project_config::ProjectConfig;
+
 pub use provider::{ProjectComponentProvider, ProjectProviderRegistry};
 
+pub use result_exclusions::ResultExclusions;
+
 pub use scanner::ProjectScanner;
 
+pub use test_files::TestFilePatterns;
+
 pub use workspace::ProjectWorkspace;
 
 pub use workspace_session::WorkspaceSession;
+
+pub use xcode_provider::XcodeProvider;
@@ -0,0 +1,476 @@
+//! Project configuration file support
+//!
+//! A `.mcp-cpp.toml` file at the project root provides checked-in defaults for
+//! settings that would otherwise be passed on every run. Command line arguments
+//! take precedence over the file.
+//!
+//! ```toml
+//! clangd_args = ["--header-insertion=never"]
+//! clangd_jobs = 4
+//! scan_depth = 5
+//! ignore = ["third_party/**", "**/node_modules"]
+//! extra_exclude_results = ["**/moc_*"]
+//! test_file_patterns = ["tests/**", "**/*_test.cpp"]
+//! build_directory = "build/debug"
+//! index_wait_timeout = 60
+//! max_concurrent_tools = 8
+//! clangd_working_directory = "tools/clangd"
+//! lsp_root_uri = "file:///workspace/project"
+//! resource_threshold = 65536
+//! max_context_file_size = 2097152
+//! index_directory = "/var/cache/clangd-index/project"
+//! index_file = "/shared/project.dex"
+//! quiet_indexing = true
+//! clangd_version = ">= 20"
+//! pch_storage = "disk"
+//!
+//! [clangd_env]
+//! PATH = "/opt/cross/bin:${PATH}"
+//!
+//! [empty_result_retry]
+//! retries = 5
+//! interval_ms = 1000
+//! window_secs = 5
+//! ```
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::clangd::config::PchStorage;
+use crate::clangd::discovery::VersionRequirement;
+use crate::project::index::EmptyResultRetry;
+use crate::project::{ProjectError, ResultExclusions, TestFilePatterns};
+use crate::symbol::path_from_file_uri;
+
+/// Name of the project configuration file
+pub const CONFIG_FILE_NAME: &str = ".mcp-cpp.toml";
+
+/// Deepest directory level the project scanner can be configured to visit
+pub const MAX_SCAN_DEPTH: usize = 32;
+
+/// Settings read from `.mcp-cpp.toml`
+///
+/// Every setting is optional; unset settings keep the command line or built-in
+/// defaults.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Additional arguments passed to every clangd process
+    pub clangd_args: Vec<String>,
+
+    /// Environment variables set for every clangd process; `${VAR}` in a value
+    /// expands to the server's own value of `VAR`
+    pub clangd_env: BTreeMap<String, String>,
+
+    /// Number of clangd background indexing threads
+    pub clangd_jobs: Option<u32>,
+
+    /// Directory depth scanned for build directories
+    pub scan_depth: Option<usize>,
+
+    /// Glob patterns of directories skipped by the scanner, relative to the project root
+    pub ignore: Vec<String>,
+
+    /// Glob patterns of files left out of tool results, replacing the defaults
+    pub exclude_results: Option<Vec<String>>,
+
+    /// Glob patterns of files left out of tool results in addition to the others
+    pub extra_exclude_results: Vec<String>,
+
+    /// Glob patterns recognizing test files, replacing the defaults
+    pub test_file_patterns: Option<Vec<String>>,
+
+    /// Build directory used when a tool call does not specify one
+    pub build_directory: Option<PathBuf>,
+
+    /// Default timeout in seconds to wait for indexing completion
+    pub index_wait_timeout: Option<u64>,
+
+    /// Maximum number of clangd-backed tool calls executed at the same time
+    pub max_concurrent_tools: Option<usize>,
+
+    /// Directory clangd processes run in, relative to the project root if not absolute
+    pub clangd_working_directory: Option<PathBuf>,
+
+    /// LSP workspace root URI sent to clangd instead of the project root
+    pub lsp_root_uri: Option<String>,
+
+    /// Size in bytes above which tool results are returned as MCP resources
+    pub resource_threshold: Option<usize>,
+
+    /// Size in bytes above which files are not read for source context
+    pub max_context_file_size: Option<u64>,
+
+    /// Retries of empty workspace query results right after indexing completes
+    pub empty_result_retry: Option<EmptyResultRetryConfig>,
+
+    /// Directory of the clangd background index, relative to the project root if
+    /// not absolute
+    pub index_directory: Option<PathBuf>,
+
+    /// Static index loaded by clangd (`--index-file`), relative to the project
+    /// root if not absolute
+    pub index_file: Option<PathBuf>,
+
+    /// Run clangd without background index, for quick lookups in huge projects
+    pub quiet_indexing: Option<bool>,
+
+    /// Version requirement for the clangd picked from `PATH`, e.g. `">= 20"`
+    pub clangd_version: Option<String>,
+
+    /// Where clangd stores the preambles of open files, instead of choosing by
+    /// available memory
+    pub pch_storage: Option<PchStorage>,
+}
+
+/// `[empty_result_retry]` table of `.mcp-cpp.toml`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmptyResultRetryConfig {
+    /// Maximum number of retries of an empty result
+    pub retries: Option<u32>,
+
+    /// Delay between retries in milliseconds
+    pub interval_ms: Option<u64>,
+
+    /// Seconds after indexing completion during which empty results are retried
+    pub window_secs: Option<u64>,
+}
+
+impl EmptyResultRetryConfig {
+    /// Apply the configured values over the built-in defaults
+    pub fn to_retry(&self) -> EmptyResultRetry {
+        let defaults = EmptyResultRetry::default();
+        EmptyResultRetry {
+            retries: self.retries.unwrap_or(defaults.retries),
+            interval: self
+                .interval_ms
+                .map_or(defaults.interval, Duration::from_millis),
+            window: self
+                .window_secs
+                .map_or(defaults.window, Duration::from_secs),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Load the configuration file of a project, if it has one
+    pub fn load(project_root: &Path) -> Result<Option<Self>, ProjectError> {
+        let path = project_root.join(CONFIG_FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Self::parse(&content)
+            .map(Some)
+            .map_err(|reason| ProjectError::InvalidConfig {
+                path: path.to_string_lossy().to_string(),
+                reason,
+            })
+    }
+
+    /// Parse and validate configuration file content
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check settings that TOML types alone cannot constrain
+    fn validate(&self) -> Result<(), String> {
+        if self.clangd_jobs == Some(0) {
+            return Err("clangd_jobs must be at least 1".to_string());
+        }
+        if self.max_concurrent_tools == Some(0) {
+            return Err("max_concurrent_tools must be at least 1".to_string());
+        }
+        if let Some(depth) = self.scan_depth
+            && depth > MAX_SCAN_DEPTH
+        {
+            return Err(format!(
+                "scan_depth must not exceed {MAX_SCAN_DEPTH}, got {depth}"
+            ));
+        }
+        if let Some(arg) = self.clangd_args.iter().find(|arg| !arg.starts_with('-')) {
+            return Err(format!("clangd_args entry '{arg}' is not an option"));
+        }
+        if let Some(name) = self
+            .clangd_env
+            .keys()
+            .find(|name| name.is_empty() || name.contains(['=', '\0']))
+        {
+            return Err(format!(
+                "clangd_env name '{name}' is not a valid variable name"
+            ));
+        }
+        if let Some(retry) = &self.empty_result_retry
+            && retry.interval_ms == Some(0)
+        {
+            return Err("empty_result_retry.interval_ms must be at least 1".to_string());
+        }
+        if let Some(requirement) = &self.clangd_version {
+            requirement.parse::<VersionRequirement>()?;
+        }
+        build_ignore_set(&self.ignore)?;
+        ResultExclusions::from_config(
+            self.exclude_results.as_deref(),
+            &self.extra_exclude_results,
+            Path::new(""),
+        )?;
+        TestFilePatterns::from_config(self.test_file_patterns.as_deref(), Path::new(""))?;
+        Ok(())
+    }
+}
+
+/// Expand `${VAR}` references in configured clangd environment variables
+/// against the server's environment
+pub fn expand_clangd_env(env: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    env.iter()
+        .map(|(name, value)| {
+            let value = expand_env_references(value, |var| std::env::var(var).ok());
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// Replace `${VAR}` references in `value` using `lookup`
+///
+/// Unset variables expand to an empty string; text without a closing brace is
+/// kept as is.
+fn expand_env_references(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + 2 + length];
+        result.push_str(&lookup(name).unwrap_or_default());
+        rest = &rest[start + 3 + length..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Resolve the clangd working directory override against the project root
+///
+/// Relative paths are taken relative to the project root. The directory must
+/// exist, since clangd would otherwise fail to start for every build directory.
+pub fn resolve_clangd_working_directory(
+    directory: &Path,
+    project_root: &Path,
+) -> Result<PathBuf, String> {
+    let resolved = if directory.is_absolute() {
+        directory.to_path_buf()
+    } else {
+        project_root.join(directory)
+    };
+    if !resolved.is_dir() {
+        return Err(format!(
+            "clangd working directory {} does not exist or is not a directory",
+            resolved.display()
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Validate an LSP workspace root URI override
+///
+/// The URI must be an absolute `file:` URI. The directory it names is not
+/// checked: it is where clangd sees the project, which may not exist on the
+/// server's side of a path mapping.
+pub fn validate_lsp_root_uri(uri: &str) -> Result<String, String> {
+    if uri.parse::<lsp_types::Uri>().is_err() {
+        return Err(format!("'{uri}' is not a valid URI"));
+    }
+    match path_from_file_uri(uri) {
+        Some(path) if path.is_absolute() => Ok(uri.to_string()),
+        _ => Err(format!(
+            "'{uri}' is not an absolute file URI (expected e.g. file:///workspace/project)"
+        )),
+    }
+}
+
+/// Compile scanner ignore patterns into a glob set
+pub fn build_ignore_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern.trim_end_matches('/'))
+            .map_err(|e| format!("invalid ignore pattern '{pattern}': {e}"))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("invalid ignore patterns: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_all_settings() {
+        let config = ProjectConfig::parse(
+            r#"
+clangd_args = ["--header-insertion=never"]
+clangd_jobs = 4
+scan_depth = 5
+ignore = ["third_party/**"]
+exclude_results = ["vendor/**"]
+extra_exclude_results = ["**/moc_*"]
+test_file_patterns = ["spec/**"]
+build_directory = "build/debug"
+index_wait_timeout = 60
+max_concurrent_tools = 2
+clangd_working_directory = "tools"
+lsp_root_uri = "file:///workspace/project"
+resource_threshold = 4096
+max_context_file_size = 8192
+index_directory = "/cache/index"
+index_file = "shared.dex"
+quiet_indexing = true
+clangd_version = ">= 20"
+pch_storage = "memory"
+
+[clangd_env]
+CLANGD_FLAGS = "--background-index"
+
+[empty_result_retry]
+retries = 3
+interval_ms = 250
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.clangd_args, vec!["--header-insertion=never"]);
+        assert_eq!(
+            config.clangd_env,
+            BTreeMap::from([("CLANGD_FLAGS".to_string(), "--background-index".to_string())])
+        );
+        assert_eq!(config.clangd_jobs, Some(4));
+        assert_eq!(config.scan_depth, Some(5));
+        assert_eq!(config.ignore, vec!["third_party/**"]);
+        assert_eq!(config.exclude_results, Some(vec!["vendor/**".to_string()]));
+        assert_eq!(config.extra_exclude_results, vec!["**/moc_*"]);
+        assert_eq!(config.test_file_patterns, Some(vec!["spec/**".to_string()]));
+        assert_eq!(config.build_directory, Some(PathBuf::from("build/debug")));
+        assert_eq!(config.index_wait_timeout, Some(60));
+        assert_eq!(config.max_concurrent_tools, Some(2));
+        assert_eq!(
+            config.clangd_working_directory,
+            Some(PathBuf::from("tools"))
+        );
+        assert_eq!(
+            config.lsp_root_uri.as_deref(),
+            Some("file:///workspace/project")
+        );
+        assert_eq!(config.max_context_file_size, Some(8192));
+        assert_eq!(config.index_directory, Some(PathBuf::from("/cache/index")));
+        assert_eq!(config.index_file, Some(PathBuf::from("shared.dex")));
+        assert_eq!(config.quiet_indexing, Some(true));
+        assert_eq!(config.clangd_version.as_deref(), Some(">= 20"));
+        assert_eq!(config.pch_storage, Some(PchStorage::Memory));
+        assert_eq!(
+            config.empty_result_retry.unwrap().to_retry(),
+            EmptyResultRetry {
+                retries: 3,
+                interval: Duration::from_millis(250),
+                ..EmptyResultRetry::default()
+            }
+        );
+
+        assert_eq!(ProjectConfig::parse("").unwrap(), ProjectConfig::default());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_settings() {
+        for content in [
+            "scan_depth = \"deep\"",
+            "unknown_setting = 1",
+            "clangd_jobs = 0",
+            "max_concurrent_tools = 0",
+            "scan_depth = 100",
+            "clangd_args = [\"clangd\"]",
+            "clangd_version = \"newest\"",
+            "pch_storage = \"ram\"",
+            "[clangd_env]\n\"A=B\" = \"1\"",
+            "[clangd_env]\nPATH = 1",
+            "ignore = [\"third_party/[\"]",
+            "extra_exclude_results = [\"gen/[\"]",
+            "test_file_patterns = [\"tests/[\"]",
+            "[empty_result_retry]\ninterval_ms = 0",
+            "[empty_result_retry]\nbackoff = 2",
+        ] {
+            assert!(ProjectConfig::parse(content).is_err(), "{content}");
+        }
+    }
+
+    #[test]
+    fn test_expand_env_references() {
+        let lookup = |name: &str| (name == "PATH").then(|| "/usr/bin".to_string());
+
+        assert_eq!(
+            expand_env_references("/opt/cross/bin:${PATH}", lookup),
+            "/opt/cross/bin:/usr/bin"
+        );
+        assert_eq!(expand_env_references("${UNSET}x", lookup), "x");
+        assert_eq!(
+            expand_env_references("$PATH ${PATH", lookup),
+            "$PATH ${PATH"
+        );
+    }
+
+    #[test]
+    fn test_validate_lsp_root_uri() {
+        assert_eq!(
+            validate_lsp_root_uri("file:///workspace/my%20project").unwrap(),
+            "file:///workspace/my%20project"
+        );
+        assert!(validate_lsp_root_uri("/workspace/project").is_err());
+        assert!(validate_lsp_root_uri("https://example.com/project").is_err());
+        assert!(validate_lsp_root_uri("file://remote-host/project").is_err());
+        assert!(validate_lsp_root_uri("not a uri").is_err());
+    }
+
+    #[test]
+    fn test_resolve_clangd_working_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("tools")).unwrap();
+        std::fs::write(temp_dir.path().join("file.txt"), "").unwrap();
+
+        let relative =
+            resolve_clangd_working_directory(Path::new("tools"), temp_dir.path()).unwrap();
+        assert_eq!(relative, temp_dir.path().join("tools"));
+
+        let absolute = temp_dir.path().join("tools");
+        assert_eq!(
+            resolve_clangd_working_directory(&absolute, Path::new("/elsewhere")).unwrap(),
+            absolute
+        );
+
+        assert!(resolve_clangd_working_directory(Path::new("missing"), temp_dir.path()).is_err());
+        assert!(resolve_clangd_working_directory(Path::new("file.txt"), temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_from_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(ProjectConfig::load(temp_dir.path()).unwrap(), None);
+
+        std::fs::write(temp_dir.path().join(CONFIG_FILE_NAME), "scan_depth = 2\n").unwrap();
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(config.scan_depth, Some(2));
+
+        std::fs::write(temp_dir.path().join(CONFIG_FILE_NAME), "scan_depth = -1\n").unwrap();
+        assert!(matches!(
+            ProjectConfig::load(temp_dir.path()),
+            Err(ProjectError::InvalidConfig { .. })
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::project::{ProjectComponent, ProjectError};
+use crate::project::{BuildPreset, ProjectComponent, ProjectError};
 use std::path::Path;
 
 /// Trait for project component providers
@@ -14,6 +14,14 @@ pub trait ProjectComponentProvider: Send + Sync {
     /// - Ok(None) if this provider cannot handle the directory (not applicable)
     /// - Err(error) if this provider should handle the directory but parsing fails
     fn scan_path(&self, path: &Path) -> Result<Option<ProjectComponent>, ProjectError>;
+
+    /// Scan a source directory for build directories declared by presets
+    ///
+    /// Preset build directories may not be configured yet. Providers without
+    /// preset support return an empty list.
+    fn scan_presets(&self, _path: &Path) -> Result<Vec<BuildPreset>, ProjectError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Registry for managing multiple project component providers
@@ -52,6 +60,15 @@ impl ProjectProviderRegistry {
         }
         Ok(None)
     }
+
+    /// Collect the preset build directories declared in a directory by all providers
+    pub fn scan_presets(&self, path: &Path) -> Result<Vec<BuildPreset>, ProjectError> {
+        let mut presets = Vec::new();
+        for provider in &self.providers {
+            presets.extend(provider.scan_presets(path)?);
+        }
+        Ok(presets)
+    }
 }
 
 impl Default for ProjectProviderRegistry {
@@ -0,0 +1,163 @@
+//! Exclusion of generated and vendored files from tool results
+//!
+//! Symbols and references in generated code (protobuf headers, moc files,
+//! build artifacts) and in vendored third-party trees are rarely what an agent
+//! is looking for. Files matching the configured globs are left out of symbol
+//! searches and of the locations reported by symbol analysis. Unlike the
+//! external symbol filter, this applies to files inside the project.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+/// Patterns excluded when the configuration does not replace them
+pub const DEFAULT_RESULT_EXCLUDES: &[&str] = &["**/build/**", "**/third_party/**", "**/*.pb.*"];
+
+/// Glob patterns of files left out of tool results
+#[derive(Debug, Clone)]
+pub struct ResultExclusions {
+    patterns: Vec<String>,
+    set: GlobSet,
+    /// Paths under this root are matched relative to it
+    project_root: PathBuf,
+    canonical_project_root: Option<PathBuf>,
+    /// Whether files outside the project root are never excluded
+    inside_project_only: bool,
+}
+
+impl ResultExclusions {
+    /// Compile exclusion globs matched against paths relative to `project_root`
+    ///
+    /// Paths outside the project root are matched as absolute paths.
+    pub fn new(patterns: Vec<String>, project_root: &Path) -> Result<Self, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            let glob = Glob::new(pattern)
+                .map_err(|e| format!("invalid result exclusion pattern '{pattern}': {e}"))?;
+            builder.add(glob);
+        }
+        let set = builder
+            .build()
+            .map_err(|e| format!("invalid result exclusion patterns: {e}"))?;
+
+        Ok(Self {
+            patterns,
+            set,
+            project_root: project_root.to_path_buf(),
+            canonical_project_root: project_root.canonicalize().ok(),
+            inside_project_only: false,
+        })
+    }
+
+    /// Exclusions from the configuration: `exclude` replaces the defaults when
+    /// set, `extra` is added to whichever list applies
+    pub fn from_config(
+        exclude: Option<&[String]>,
+        extra: &[String],
+        project_root: &Path,
+    ) -> Result<Self, String> {
+        let mut patterns: Vec<String> = match exclude {
+            Some(patterns) => patterns.to_vec(),
+            None => DEFAULT_RESULT_EXCLUDES
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+        };
+        patterns.extend(extra.iter().cloned());
+        Self::new(patterns, project_root)
+    }
+
+    /// No exclusions
+    pub fn none() -> Self {
+        Self {
+            patterns: Vec::new(),
+            set: GlobSet::empty(),
+            project_root: PathBuf::new(),
+            canonical_project_root: None,
+            inside_project_only: false,
+        }
+    }
+
+    /// The same exclusions, keeping every file outside the project root
+    ///
+    /// Used when a file outside the project was explicitly asked for, so that
+    /// its dependency tree is not filtered out by patterns like `**/build/**`.
+    pub fn inside_project_only(mut self) -> Self {
+        self.inside_project_only = true;
+        self
+    }
+
+    /// Configured patterns
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Check whether results in a file are excluded
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if self.set.is_empty() {
+            return false;
+        }
+        let relative = path.strip_prefix(&self.project_root).ok().or_else(|| {
+            self.canonical_project_root
+                .as_ref()
+                .and_then(|root| path.strip_prefix(root).ok())
+        });
+        match relative {
+            Some(relative) => self.set.is_match(relative),
+            None if self.inside_project_only => false,
+            None => self.set.is_match(path),
+        }
+    }
+}
+
+impl Default for ResultExclusions {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_exclusions() {
+        let exclusions =
+            ResultExclusions::from_config(None, &[], Path::new("/work/build/app")).unwrap();
+
+        // The project root itself may sit under a directory named like a pattern
+        assert!(!exclusions.is_excluded(Path::new("/work/build/app/src/main.cpp")));
+        assert!(exclusions.is_excluded(Path::new("/work/build/app/build/gen/config.h")));
+        assert!(exclusions.is_excluded(Path::new("/work/build/app/third_party/zlib/zlib.h")));
+        assert!(exclusions.is_excluded(Path::new("/work/build/app/proto/msg.pb.h")));
+        assert!(exclusions.is_excluded(Path::new("/opt/third_party/lib.h")));
+    }
+
+    #[test]
+    fn test_configured_exclusions() {
+        let root = Path::new("/project");
+        let extra = vec!["**/moc_*".to_string()];
+
+        let extended = ResultExclusions::from_config(None, &extra, root).unwrap();
+        assert!(extended.is_excluded(Path::new("/project/ui/moc_window.cpp")));
+        assert!(extended.is_excluded(Path::new("/project/build/a.h")));
+
+        let replaced =
+            ResultExclusions::from_config(Some(&["vendor/**".to_string()]), &extra, root).unwrap();
+        assert_eq!(replaced.patterns(), ["vendor/**", "**/moc_*"]);
+        assert!(replaced.is_excluded(Path::new("/project/vendor/fmt/format.h")));
+        assert!(!replaced.is_excluded(Path::new("/project/build/a.h")));
+
+        assert!(ResultExclusions::from_config(Some(&["[".to_string()]), &[], root).is_err());
+        assert!(!ResultExclusions::none().is_excluded(Path::new("/project/build/a.h")));
+    }
+
+    #[test]
+    fn test_inside_project_only_exclusions() {
+        let exclusions = ResultExclusions::from_config(None, &[], Path::new("/project"))
+            .unwrap()
+            .inside_project_only();
+        assert!(exclusions.is_excluded(Path::new("/project/third_party/zlib/zlib.h")));
+        assert!(!exclusions.is_excluded(Path::new("/deps/third_party/lib.h")));
+        assert!(!exclusions.is_excluded(Path::new("/deps/build/gen/config.h")));
+    }
+}
@@ -1,7 +1,10 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use crate::project::{ProjectComponent, ProjectError, ProjectProviderRegistry, ProjectWorkspace};
+use crate::project::project_config::build_ignore_set;
+use crate::project::{
+    BuildPreset, ProjectComponent, ProjectError, ProjectProviderRegistry, ProjectWorkspace,
+};
 
 /// Options for configuring project scanning behavior
 #[derive(Debug, Clone)]
@@ -14,6 +17,9 @@ pub struct ScanOptions {
 
     /// Maximum number of components to discover (None = unlimited)
     pub max_components: Option<usize>,
+
+    /// Glob patterns of directories to skip, relative to the scanned root
+    pub ignore_patterns: Vec<String>,
 }
 
 impl Default for ScanOptions {
@@ -22,6 +28,7 @@ impl Default for ScanOptions {
             skip_hidden: true,
             follow_symlinks: false,
             max_components: None,
+            ignore_patterns: Vec::new(),
         }
     }
 }
@@ -40,13 +47,14 @@ impl ProjectScanner {
         Self { provider_registry }
     }
 
-    /// Create a scanner with default providers (CMake and Meson)
+    /// Create a scanner with default providers (CMake, Meson and Xcode)
     pub fn with_default_providers() -> Self {
-        use crate::project::{CmakeProvider, MesonProvider};
+        use crate::project::{CmakeProvider, MesonProvider, XcodeProvider};
 
         let registry = ProjectProviderRegistry::new()
             .with_provider(Box::new(CmakeProvider::new()))
-            .with_provider(Box::new(MesonProvider::new()));
+            .with_provider(Box::new(MesonProvider::new()))
+            .with_provider(Box::new(XcodeProvider::new()));
 
         Self::new(registry)
     }
@@ -123,6 +131,7 @@ impl ProjectScanner {
         }
 
         let mut components = Vec::new();
+        let mut presets = Vec::new();
         let mut scanned_paths = std::collections::HashSet::new();
 
         // Configure walkdir based on options
@@ -132,8 +141,19 @@ impl ProjectScanner {
             walk_builder = walk_builder.follow_links(true);
         }
 
-        // Traverse directory tree
-        for entry in walk_builder.into_iter() {
+        let ignore_set = build_ignore_set(&options.ignore_patterns)
+            .map_err(|reason| ProjectError::ParseError { reason })?;
+        let is_ignored = |entry: &walkdir::DirEntry| {
+            entry.path().strip_prefix(root_path).is_ok_and(|relative| {
+                !relative.as_os_str().is_empty() && ignore_set.is_match(relative)
+            })
+        };
+
+        // Traverse directory tree, pruning ignored directories
+        for entry in walk_builder
+            .into_iter()
+            .filter_entry(|entry| !is_ignored(entry))
+        {
             let entry = match entry {
                 Ok(entry) => entry,
                 Err(e) => {
@@ -163,6 +183,14 @@ impl ProjectScanner {
                 continue;
             }
 
+            // Collect build directories declared by presets in this directory
+            match self.provider_registry.scan_presets(path) {
+                Ok(found) => presets.extend(found),
+                Err(e) => {
+                    tracing::warn!("Error scanning presets in {}: {}", path.display(), e);
+                }
+            }
+
             // Try to discover a project component in this directory
             match self.provider_registry.scan_directory(path) {
                 Ok(Some(component)) => {
@@ -185,14 +213,71 @@ impl ProjectScanner {
             }
         }
 
-        Ok(ProjectWorkspace::new(
-            root_path.to_path_buf(),
-            components,
-            depth,
-        ))
+        let unconfigured_presets =
+            self.resolve_preset_build_dirs(presets, &mut components, &options);
+
+        let mut workspace = ProjectWorkspace::new(root_path.to_path_buf(), components, depth);
+        workspace.unconfigured_presets = unconfigured_presets;
+        Ok(workspace)
+    }
+
+    /// Match preset build directories against the discovered components
+    ///
+    /// Configured preset build directories outside the scanned depth are added
+    /// as components. Returns the presets whose build directories are not
+    /// configured yet.
+    fn resolve_preset_build_dirs(
+        &self,
+        presets: Vec<BuildPreset>,
+        components: &mut Vec<ProjectComponent>,
+        options: &ScanOptions,
+    ) -> Vec<BuildPreset> {
+        let mut unconfigured = Vec::new();
+
+        for preset in presets {
+            let preset_dir = canonical_or_self(&preset.build_dir_path);
+            if components
+                .iter()
+                .any(|component| canonical_or_self(&component.build_dir_path) == preset_dir)
+            {
+                continue;
+            }
+
+            let within_limit = options
+                .max_components
+                .is_none_or(|max| components.len() < max);
+            if within_limit && preset.build_dir_path.is_dir() {
+                match self
+                    .provider_registry
+                    .scan_directory(&preset.build_dir_path)
+                {
+                    Ok(Some(component)) => {
+                        components.push(component);
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            "Error scanning preset build directory {}: {}",
+                            preset.build_dir_path.display(),
+                            e
+                        );
+                    }
+                }
+            }
+
+            unconfigured.push(preset);
+        }
+
+        unconfigured
     }
 }
 
+/// Canonicalize a path, falling back to the path itself when it does not exist
+fn canonical_or_self(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
 impl Default for ProjectScanner {
     fn default() -> Self {
         Self::with_default_providers()
@@ -211,6 +296,114 @@ mod tests {
         crate::test_utils::logging::init();
     }
 
+    #[test]
+    fn test_cmake_preset_build_dirs() {
+        let workspace = tempfile::Builder::new()
+            .prefix("presets")
+            .tempdir()
+            .unwrap();
+        let root = workspace.path();
+        std::fs::write(
+            root.join("CMakePresets.json"),
+            r#"{"version": 3, "configurePresets": [
+                {"name": "debug", "binaryDir": "${sourceDir}/out/build/${presetName}"},
+                {"name": "release", "binaryDir": "${sourceDir}/out/build/${presetName}"}
+            ]}"#,
+        )
+        .unwrap();
+
+        // Configure only the debug preset, deeper than the scan depth
+        let debug_dir = root.join("out/build/debug");
+        std::fs::create_dir_all(&debug_dir).unwrap();
+        std::fs::write(
+            debug_dir.join("CMakeCache.txt"),
+            format!(
+                "CMAKE_SOURCE_DIR:INTERNAL={}\nCMAKE_BUILD_TYPE:STRING=Debug\n",
+                root.display()
+            ),
+        )
+        .unwrap();
+        std::fs::write(debug_dir.join("compile_commands.json"), "[]").unwrap();
+
+        let scanner = super::ProjectScanner::with_default_providers();
+        let scanned = scanner.scan_project(root, 0, None).unwrap();
+
+        assert_eq!(scanned.components.len(), 1);
+        assert_eq!(scanned.components[0].preset.as_deref(), Some("debug"));
+        assert_eq!(scanned.components[0].build_type, "Debug");
+
+        assert_eq!(scanned.unconfigured_presets.len(), 1);
+        assert_eq!(scanned.unconfigured_presets[0].name, "release");
+        assert!(
+            scanned.unconfigured_presets[0]
+                .build_dir_path
+                .ends_with("out/build/release")
+        );
+    }
+
+    #[test]
+    fn test_missing_compile_commands_is_actionable() {
+        let workspace = tempfile::Builder::new()
+            .prefix("unexported")
+            .tempdir()
+            .unwrap();
+        let root = workspace.path();
+        let build_dir = root.join("build");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        std::fs::write(
+            build_dir.join("CMakeCache.txt"),
+            format!("CMAKE_SOURCE_DIR:INTERNAL={}\n", root.display()),
+        )
+        .unwrap();
+
+        let scanner = super::ProjectScanner::with_default_providers();
+        let error = scanner.discover_component(&build_dir).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::project::ProjectError::MissingCompileCommands { .. }
+        ));
+        let message = error.to_string();
+        assert!(message.contains("has no compile_commands.json"));
+        assert!(message.contains(&format!(
+            "-B {} -DCMAKE_EXPORT_COMPILE_COMMANDS=ON",
+            build_dir.display()
+        )));
+    }
+
+    #[test]
+    fn test_ignore_patterns_prune_directories() {
+        let workspace = tempfile::Builder::new().prefix("ignore").tempdir().unwrap();
+        let root = workspace.path();
+        for build_dir in ["build", "third_party/zlib/build"] {
+            let build_dir = root.join(build_dir);
+            std::fs::create_dir_all(&build_dir).unwrap();
+            std::fs::write(
+                build_dir.join("CMakeCache.txt"),
+                format!("CMAKE_SOURCE_DIR:INTERNAL={}\n", root.display()),
+            )
+            .unwrap();
+            std::fs::write(build_dir.join("compile_commands.json"), "[]").unwrap();
+        }
+
+        let scanner = super::ProjectScanner::with_default_providers();
+        assert_eq!(
+            scanner
+                .scan_project(root, 3, None)
+                .unwrap()
+                .components
+                .len(),
+            2
+        );
+
+        let options = super::ScanOptions {
+            ignore_patterns: vec!["third_party/".to_string()],
+            ..Default::default()
+        };
+        let scanned = scanner.scan_project(root, 3, Some(options)).unwrap();
+        assert_eq!(scanned.components.len(), 1);
+        assert_eq!(scanned.components[0].build_dir_path, root.join("build"));
+    }
+
     #[tokio::test]
     #[cfg(feature = "project-integration-tests")]
     async fn test_cmake_project_scanning() {
@@ -0,0 +1,228 @@
+//! Setup validation for the `--check` command line mode
+//!
+//! Runs the same discovery the server performs at startup (project scanning,
+//! clangd version detection, compilation database loading) and collects the
+//! outcome in a report instead of starting a session, so configuration
+//! problems surface before the first tool call.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::clangd::version::ClangdVersion;
+use crate::project::{CompilationDatabase, ProjectWorkspace};
+
+/// Maximum number of missing source files listed per compilation database
+const MAX_MISSING_FILES_LISTED: usize = 10;
+
+/// Outcome of running clangd --version
+#[derive(Debug, Serialize)]
+pub struct ClangdCheck {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Validation of one build directory and its compilation database
+#[derive(Debug, Serialize)]
+pub struct ComponentCheck {
+    pub build_dir: PathBuf,
+    pub provider: String,
+    pub compilation_database: PathBuf,
+    /// Number of compile commands, when the database could be loaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entries: Option<usize>,
+    /// Number of compile commands whose source file does not exist
+    pub missing_files_count: usize,
+    /// First few missing source files
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_files: Vec<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Structured report printed by `--check`
+#[derive(Debug, Serialize)]
+pub struct SetupReport {
+    /// Whether the server can be started with this configuration
+    pub ok: bool,
+    pub project_root: PathBuf,
+    pub scan_depth: usize,
+    pub providers: Vec<String>,
+    pub clangd: ClangdCheck,
+    pub components: Vec<ComponentCheck>,
+    /// CMake presets whose build directory has not been configured yet
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unconfigured_presets: Vec<String>,
+    /// Problems that prevent the server from working
+    pub errors: Vec<String>,
+    /// Problems that degrade results but do not prevent startup
+    pub warnings: Vec<String>,
+}
+
+/// Detect the clangd version
+pub fn check_clangd(clangd_path: &str) -> ClangdCheck {
+    match ClangdVersion::detect(Path::new(clangd_path)) {
+        Ok(version) => ClangdCheck {
+            path: clangd_path.to_string(),
+            version: Some(version.to_string()),
+            error: None,
+        },
+        Err(e) => ClangdCheck {
+            path: clangd_path.to_string(),
+            version: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Load a compilation database and look for compile commands of missing files
+fn check_compilation_database(path: &Path) -> (Option<usize>, Vec<PathBuf>, Option<String>) {
+    match CompilationDatabase::new(path.to_path_buf()) {
+        Ok(database) => {
+            let missing = database
+                .entries()
+                .iter()
+                .map(|entry| {
+                    if entry.file.is_relative() {
+                        entry.directory.join(&entry.file)
+                    } else {
+                        entry.file.clone()
+                    }
+                })
+                .filter(|file| !file.exists())
+                .collect();
+            (Some(database.entries().len()), missing, None)
+        }
+        Err(e) => (None, Vec::new(), Some(e.to_string())),
+    }
+}
+
+/// Validate a discovered workspace against a clangd check
+pub fn check_workspace(workspace: &ProjectWorkspace, clangd: ClangdCheck) -> SetupReport {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    if let Some(error) = &clangd.error {
+        errors.push(format!(
+            "clangd at '{}' is not usable: {}",
+            clangd.path, error
+        ));
+    }
+    if workspace.components.is_empty() {
+        errors.push(format!(
+            "No build directories with compile_commands.json found under {} (scan depth {}); \
+             configure the project or pass --compile-commands-dir",
+            workspace.project_root_path.display(),
+            workspace.scan_depth
+        ));
+    }
+
+    let components = workspace
+        .components
+        .iter()
+        .map(|component| {
+            // An explicit --compile-commands-dir replaces every component's database
+            let database_path = workspace
+                .global_compilation_database
+                .as_ref()
+                .map(|database| database.path().clone())
+                .unwrap_or_else(|| component.compilation_database_path.clone());
+            let (entries, missing, error) = check_compilation_database(&database_path);
+            if let Some(error) = &error {
+                errors.push(format!("{}: {}", component.build_dir_path.display(), error));
+            }
+            if !missing.is_empty() {
+                warnings.push(format!(
+                    "{}: {} compile commands refer to missing source files",
+                    component.build_dir_path.display(),
+                    missing.len()
+                ));
+            }
+            ComponentCheck {
+                build_dir: component.build_dir_path.clone(),
+                provider: component.provider_type.clone(),
+                compilation_database: database_path,
+                entries,
+                missing_files_count: missing.len(),
+                missing_files: missing.into_iter().take(MAX_MISSING_FILES_LISTED).collect(),
+                error,
+            }
+        })
+        .collect();
+
+    let unconfigured_presets: Vec<String> = workspace
+        .unconfigured_presets
+        .iter()
+        .map(|preset| preset.name.clone())
+        .collect();
+    if !unconfigured_presets.is_empty() {
+        warnings.push(format!(
+            "{} CMake presets are not configured yet and cannot be analyzed",
+            unconfigured_presets.len()
+        ));
+    }
+
+    SetupReport {
+        ok: errors.is_empty(),
+        project_root: workspace.project_root_path.clone(),
+        scan_depth: workspace.scan_depth,
+        providers: workspace.get_provider_types(),
+        clangd,
+        components,
+        unconfigured_presets,
+        errors,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clangd::testing::test_helpers::create_mock_project;
+
+    fn working_clangd() -> ClangdCheck {
+        ClangdCheck {
+            path: "clangd".to_string(),
+            version: Some("18.1.8".to_string()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_reports_missing_files_and_unusable_clangd() {
+        let (_temp_dir, workspace) =
+            create_mock_project(&[("src/main.cpp", ""), ("src/gone.cpp", "")]);
+        std::fs::remove_file(workspace.project_root_path.join("src/gone.cpp")).unwrap();
+
+        let report = check_workspace(&workspace, working_clangd());
+        assert!(report.ok);
+        assert_eq!(report.components.len(), 1);
+        assert_eq!(report.components[0].entries, Some(2));
+        assert_eq!(report.components[0].missing_files_count, 1);
+        assert!(report.components[0].missing_files[0].ends_with("gone.cpp"));
+        assert_eq!(report.warnings.len(), 1);
+
+        let report = check_workspace(&workspace, check_clangd("/nonexistent/clangd"));
+        assert!(!report.ok);
+        assert!(report.clangd.version.is_none());
+        assert!(report.errors[0].contains("/nonexistent/clangd"));
+    }
+
+    #[test]
+    fn test_reports_unreadable_database_and_empty_workspace() {
+        let (_temp_dir, workspace) = create_mock_project(&[("src/main.cpp", "")]);
+        std::fs::write(&workspace.components[0].compilation_database_path, "{").unwrap();
+
+        let report = check_workspace(&workspace, working_clangd());
+        assert!(!report.ok);
+        assert!(report.components[0].entries.is_none());
+        assert!(report.components[0].error.is_some());
+
+        let empty = ProjectWorkspace::new(workspace.project_root_path.clone(), Vec::new(), 1);
+        let report = check_workspace(&empty, working_clangd());
+        assert!(!report.ok);
+        assert!(report.errors[0].contains("No build directories"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Recognition of test files by path
+//!
+//! Test sources are told apart from production code by naming conventions
+//! only: `foo_test.cpp`, `FooTest.cc`, `gtest_main.cc`, `tests/` and
+//! `unittests/` directories. The globs are matched case-insensitively against
+//! paths relative to the project root, one path component per `*`.
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+/// Patterns used when the configuration does not replace them: file and
+/// directory names containing "test"
+pub const DEFAULT_TEST_FILE_PATTERNS: &[&str] = &["**/*test*", "**/*test*/**"];
+
+/// Glob patterns of test files
+#[derive(Debug, Clone)]
+pub struct TestFilePatterns {
+    patterns: Vec<String>,
+    set: GlobSet,
+    /// Paths under this root are matched relative to it
+    project_root: PathBuf,
+    canonical_project_root: Option<PathBuf>,
+}
+
+impl TestFilePatterns {
+    /// Compile test file globs matched against paths relative to `project_root`
+    ///
+    /// Paths outside the project root are matched as absolute paths.
+    pub fn new(patterns: Vec<String>, project_root: &Path) -> Result<Self, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            let glob = GlobBuilder::new(pattern)
+                .case_insensitive(true)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| format!("invalid test file pattern '{pattern}': {e}"))?;
+            builder.add(glob);
+        }
+        let set = builder
+            .build()
+            .map_err(|e| format!("invalid test file patterns: {e}"))?;
+
+        Ok(Self {
+            patterns,
+            set,
+            project_root: project_root.to_path_buf(),
+            canonical_project_root: project_root.canonicalize().ok(),
+        })
+    }
+
+    /// Test file patterns from the configuration, or the defaults when unset
+    pub fn from_config(patterns: Option<&[String]>, project_root: &Path) -> Result<Self, String> {
+        match patterns {
+            Some(patterns) => Self::new(patterns.to_vec(), project_root),
+            None => Ok(Self::defaults(project_root)),
+        }
+    }
+
+    /// The default patterns
+    pub fn defaults(project_root: &Path) -> Self {
+        let patterns = DEFAULT_TEST_FILE_PATTERNS
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect();
+        Self::new(patterns, project_root).expect("default test file patterns are valid")
+    }
+
+    /// Configured patterns
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Check whether a file looks like a test file
+    pub fn is_test_file(&self, path: &Path) -> bool {
+        let relative = path
+            .strip_prefix(&self.project_root)
+            .ok()
+            .or_else(|| {
+                self.canonical_project_root
+                    .as_ref()
+                    .and_then(|root| path.strip_prefix(root).ok())
+            })
+            .unwrap_or(path);
+        self.set.is_match(relative)
+    }
+}
+
+impl Default for TestFilePatterns {
+    fn default() -> Self {
+        Self::defaults(Path::new(""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns() {
+        let patterns = TestFilePatterns::defaults(Path::new("/work/testbed/app"));
+
+        // The project root itself may contain "test"
+        assert!(!patterns.is_test_file(Path::new("/work/testbed/app/src/parser.cpp")));
+        assert!(patterns.is_test_file(Path::new("/work/testbed/app/src/parser_test.cpp")));
+        assert!(patterns.is_test_file(Path::new("/work/testbed/app/src/ParserTest.cc")));
+        assert!(patterns.is_test_file(Path::new("/work/testbed/app/tests/parse.cpp")));
+        assert!(patterns.is_test_file(Path::new("/work/testbed/app/gtest/main.cc")));
+        assert!(patterns.is_test_file(Path::new("/work/testbed/app/lib/unittests/a/b.cpp")));
+    }
+
+    #[test]
+    fn test_configured_patterns_replace_defaults() {
+        let patterns = TestFilePatterns::from_config(
+            Some(&["spec/**".to_string(), "**/*_spec.cpp".to_string()]),
+            Path::new("/work/app"),
+        )
+        .unwrap();
+        assert!(patterns.is_test_file(Path::new("/work/app/spec/parser.cpp")));
+        assert!(patterns.is_test_file(Path::new("/work/app/src/parser_spec.cpp")));
+        assert!(!patterns.is_test_file(Path::new("/work/app/src/parser_test.cpp")));
+
+        assert!(TestFilePatterns::new(vec!["tests/[".to_string()], Path::new("/")).is_err());
+    }
+}
@@ -3,7 +3,17 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::project::{CompilationDatabase, ProjectComponent};
+use crate::project::freshness::FreshnessStatus;
+use crate::project::{
+    BuildPreset, CompilationDatabase, ProjectComponent, ResultExclusions, TestFilePatterns,
+};
+
+/// Default size in bytes above which files are not read for source context
+pub const DEFAULT_MAX_CONTEXT_FILE_SIZE: u64 = 1024 * 1024;
+
+fn default_max_context_file_size() -> u64 {
+    DEFAULT_MAX_CONTEXT_FILE_SIZE
+}
 
 /// View of a project component with optional build options
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +44,14 @@ pub struct ProjectComponentView {
     /// Count of build options (present in short view when build_options is None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub build_options_count: Option<usize>,
+
+    /// Name of the build system preset that configures this build directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+
+    /// Whether the build directory is up to date with the sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<FreshnessStatus>,
 }
 
 /// View of a project workspace with optional detailed information
@@ -57,6 +75,10 @@ pub struct ProjectWorkspaceView {
         rename = "global_compilation_database_path"
     )]
     pub global_compilation_database: Option<CompilationDatabase>,
+
+    /// Build directories declared by presets that have not been configured yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unconfigured_presets: Vec<BuildPreset>,
 }
 
 /// Project workspace representing a workspace with multiple build configurations
@@ -84,6 +106,22 @@ pub struct ProjectWorkspace {
         rename = "global_compilation_database_path"
     )]
     pub global_compilation_database: Option<CompilationDatabase>,
+
+    /// Build directories declared by presets that have not been configured yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unconfigured_presets: Vec<BuildPreset>,
+
+    /// Files whose symbols and references are left out of tool results
+    #[serde(skip)]
+    pub result_exclusions: ResultExclusions,
+
+    /// Files recognized as tests
+    #[serde(skip)]
+    pub test_file_patterns: TestFilePatterns,
+
+    /// Files larger than this many bytes are reported without source context
+    #[serde(skip, default = "default_max_context_file_size")]
+    pub max_context_file_size: u64,
 }
 
 impl ProjectWorkspace {
@@ -93,12 +131,17 @@ impl ProjectWorkspace {
         components: Vec<ProjectComponent>,
         scan_depth: usize,
     ) -> Self {
+        let test_file_patterns = TestFilePatterns::defaults(&project_root_path);
         Self {
             project_root_path,
             components,
             scan_depth,
             discovered_at: Utc::now(),
             global_compilation_database: None,
+            unconfigured_presets: Vec::new(),
+            result_exclusions: ResultExclusions::none(),
+            test_file_patterns,
+            max_context_file_size: DEFAULT_MAX_CONTEXT_FILE_SIZE,
         }
     }
 
@@ -152,6 +195,35 @@ impl ProjectWorkspace {
         }
     }
 
+    /// Use a compilation database for all components instead of their own
+    ///
+    /// When no components were discovered, the directory containing the
+    /// database is added as a component so sessions can still be created.
+    pub fn set_global_compilation_database(
+        &mut self,
+        database: CompilationDatabase,
+    ) -> Result<(), crate::project::ProjectError> {
+        if self.components.is_empty() {
+            let database_dir = database
+                .path()
+                .parent()
+                .unwrap_or(&self.project_root_path)
+                .to_path_buf();
+            self.components.push(ProjectComponent::new(
+                database_dir,
+                self.project_root_path.clone(),
+                database.path().clone(),
+                "compilation_database".to_string(),
+                "Unknown".to_string(),
+                "Unknown".to_string(),
+                HashMap::new(),
+            )?);
+        }
+
+        self.global_compilation_database = Some(database);
+        Ok(())
+    }
+
     /// Get a short view of the workspace without detailed build options
     ///
     /// This method creates a view that includes essential information but excludes
@@ -170,6 +242,8 @@ impl ProjectWorkspace {
                 build_type: component.build_type.clone(),
                 build_options: None, // Excluded in short view
                 build_options_count: Some(component.build_options.len()),
+                preset: component.preset.clone(),
+                freshness: None,
             })
             .collect();
 
@@ -179,6 +253,7 @@ impl ProjectWorkspace {
             scan_depth: self.scan_depth,
             discovered_at: self.discovered_at,
             global_compilation_database: self.global_compilation_database.clone(),
+            unconfigured_presets: self.unconfigured_presets.clone(),
         }
     }
 
@@ -199,6 +274,8 @@ impl ProjectWorkspace {
                 build_type: component.build_type.clone(),
                 build_options: Some(component.build_options.clone()), // Included in full view
                 build_options_count: Some(component.build_options.len()),
+                preset: component.preset.clone(),
+                freshness: None,
             })
             .collect();
 
@@ -208,6 +285,41 @@ impl ProjectWorkspace {
             scan_depth: self.scan_depth,
             discovered_at: self.discovered_at,
             global_compilation_database: self.global_compilation_database.clone(),
+            unconfigured_presets: self.unconfigured_presets.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_compilation_database_without_components() {
+        let root = tempfile::tempdir().unwrap();
+        let database_path = root.path().join("compile_commands.json");
+        std::fs::write(
+            &database_path,
+            r#"[{"directory": "/src", "file": "main.cpp", "arguments": ["c++", "main.cpp"]}]"#,
+        )
+        .unwrap();
+
+        let mut workspace = ProjectWorkspace::new(root.path().to_path_buf(), Vec::new(), 3);
+        workspace
+            .set_global_compilation_database(
+                CompilationDatabase::new(database_path.clone()).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(workspace.components.len(), 1);
+        assert_eq!(workspace.components[0].build_dir_path, root.path());
+        assert_eq!(
+            workspace.components[0].provider_type,
+            "compilation_database"
+        );
+        assert_eq!(
+            workspace.global_compilation_database.unwrap().path(),
+            &database_path
+        );
+    }
+}
@@ -4,15 +4,19 @@
 //! build directories within a project workspace. This module handles pure session
 //! lifecycle management without build directory resolution policy.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::info;
 
+use crate::clangd::config::{PchStorage, indexing_threads_arg, pch_storage_arg};
 use crate::clangd::version::ClangdVersion;
-use crate::project::component_session::ComponentSession;
+use crate::project::component_session::{ClangdProcessOptions, ComponentSession};
+use crate::project::index::EmptyResultRetry;
+use crate::project::index::location::IndexStorageOptions;
 use crate::project::{ProjectError, ProjectScanner, ProjectWorkspace};
+use crate::symbol::uri_from_pathbuf;
 
 /// Manages ComponentSession instances for a project workspace
 ///
@@ -21,8 +25,11 @@ use crate::project::{ProjectError, ProjectScanner, ProjectWorkspace};
 /// This orchestrates component sessions while maintaining the same external API.
 /// Supports dynamic component discovery for build directories not found in initial scanning.
 pub struct WorkspaceSession {
-    /// Project workspace for determining project root and components (mutable for dynamic discovery)
-    workspace: Arc<Mutex<ProjectWorkspace>>,
+    /// Project workspace for determining project root and components
+    ///
+    /// Replaced copy-on-write by dynamic discovery, so tool calls can keep a snapshot
+    /// without holding the lock.
+    workspace: Mutex<Arc<ProjectWorkspace>>,
     /// Map of build directories to their ComponentSession instances
     component_sessions: Arc<Mutex<HashMap<PathBuf, Arc<ComponentSession>>>>,
     /// Path to clangd executable
@@ -31,6 +38,14 @@ pub struct WorkspaceSession {
     clangd_version: ClangdVersion,
     /// Project scanner for dynamic component discovery
     scanner: ProjectScanner,
+    /// Additional arguments and environment variables of every clangd process
+    clangd_process: ClangdProcessOptions,
+    /// Backoff for empty query results right after indexing completes
+    empty_result_retry: EmptyResultRetry,
+    /// Directory clangd processes run in instead of the project root
+    clangd_working_directory: Option<PathBuf>,
+    /// LSP workspace root URI sent to clangd instead of the project root
+    lsp_root_uri: Option<String>,
 }
 
 impl WorkspaceSession {
@@ -50,14 +65,134 @@ impl WorkspaceSession {
         let scanner = ProjectScanner::with_default_providers();
 
         Ok(Self {
-            workspace: Arc::new(Mutex::new(workspace)),
+            workspace: Mutex::new(Arc::new(workspace)),
             component_sessions: Arc::new(Mutex::new(HashMap::new())),
             clangd_path,
             clangd_version,
             scanner,
+            clangd_process: ClangdProcessOptions::default(),
+            empty_result_retry: EmptyResultRetry::default(),
+            clangd_working_directory: None,
+            lsp_root_uri: None,
         })
     }
 
+    /// Create a WorkspaceSession around already started component sessions (for testing)
+    ///
+    /// Skips clangd version detection so handlers can run against sessions backed by
+    /// `crate::clangd::testing::MockClangd`.
+    #[cfg(test)]
+    pub(crate) fn with_component_sessions(
+        workspace: ProjectWorkspace,
+        sessions: Vec<Arc<ComponentSession>>,
+    ) -> Self {
+        let component_sessions = sessions
+            .into_iter()
+            .map(|session| (session.build_dir().clone(), session))
+            .collect();
+        Self {
+            workspace: Mutex::new(Arc::new(workspace)),
+            component_sessions: Arc::new(Mutex::new(component_sessions)),
+            clangd_path: "clangd".to_string(),
+            clangd_version: ClangdVersion {
+                major: 18,
+                minor: 1,
+                patch: 8,
+                variant: None,
+                date: None,
+            },
+            scanner: ProjectScanner::with_default_providers(),
+            clangd_process: ClangdProcessOptions::default(),
+            empty_result_retry: EmptyResultRetry::default(),
+            clangd_working_directory: None,
+            lsp_root_uri: None,
+        }
+    }
+
+    /// Limit the number of threads clangd uses for background indexing
+    ///
+    /// Applies to sessions created afterwards; `None` keeps clangd's default of
+    /// one thread per hardware thread.
+    pub fn with_indexing_threads(mut self, threads: Option<u32>) -> Self {
+        if let Some(threads) = threads {
+            self.clangd_process.args.push(indexing_threads_arg(threads));
+        }
+        self
+    }
+
+    /// Select where clangd stores the preambles of open files
+    ///
+    /// Applies to sessions created afterwards. A `--pch-storage` in the extra
+    /// clangd arguments added later takes precedence.
+    pub fn with_pch_storage(mut self, storage: PchStorage) -> Self {
+        self.clangd_process.args.push(pch_storage_arg(storage));
+        self
+    }
+
+    /// Pass additional arguments to every clangd process
+    ///
+    /// Applies to sessions created afterwards.
+    pub fn with_clangd_args(mut self, args: Vec<String>) -> Self {
+        self.clangd_process.args.extend(args);
+        self
+    }
+
+    /// Set environment variables for every clangd process
+    ///
+    /// Entries override variables inherited from the server's environment.
+    /// Applies to sessions created afterwards.
+    pub fn with_clangd_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.clangd_process.env.extend(env);
+        self
+    }
+
+    /// Configure retries of empty query results right after indexing completes
+    ///
+    /// Applies to sessions created afterwards.
+    pub fn with_empty_result_retry(mut self, retry: EmptyResultRetry) -> Self {
+        self.empty_result_retry = retry;
+        self
+    }
+
+    /// Run clangd processes in the given directory instead of the project root
+    ///
+    /// Applies to sessions created afterwards. The LSP workspace root remains the
+    /// project root; only relative paths in clangd arguments and clangd's own
+    /// working-directory lookups are affected.
+    pub fn with_clangd_working_directory(mut self, directory: Option<PathBuf>) -> Self {
+        self.clangd_working_directory = directory;
+        self
+    }
+
+    /// Keep the background index in a configured directory and load a static index
+    ///
+    /// Applies to sessions created afterwards. Paths must be absolute.
+    pub fn with_index_storage(mut self, index: IndexStorageOptions) -> Self {
+        self.clangd_process.index = index;
+        self
+    }
+
+    /// Run clangd without background index (quiet indexing mode)
+    ///
+    /// Applies to sessions created afterwards. clangd then only knows the files
+    /// opened by tool calls and the files they include: lookups are fast and
+    /// cheap, but workspace-wide searches and cross-file references are
+    /// incomplete. Tools do not wait for indexing.
+    pub fn with_quiet_indexing(mut self, quiet: bool) -> Self {
+        self.clangd_process.quiet_indexing = quiet;
+        self
+    }
+
+    /// Send the given root URI to clangd instead of the project root
+    ///
+    /// Applies to sessions created afterwards. For environments where clangd
+    /// sees the sources under a different path than the server, such as a
+    /// container mount. The URI must already be validated.
+    pub fn with_lsp_root_uri(mut self, root_uri: Option<String>) -> Self {
+        self.lsp_root_uri = root_uri;
+        self
+    }
+
     /// Get or create a ComponentSession for the specified build directory
     pub async fn get_component_session(
         &self,
@@ -99,7 +234,7 @@ impl WorkspaceSession {
                     Some(discovered_component) => {
                         // Add the discovered component to the workspace
                         let mut workspace = self.workspace.lock().await;
-                        workspace.add_component(discovered_component.clone());
+                        Arc::make_mut(&mut workspace).add_component(discovered_component.clone());
                         info!(
                             "Successfully discovered and added component for build dir: {}",
                             build_dir.display()
@@ -120,16 +255,39 @@ impl WorkspaceSession {
             }
         };
 
-        // Determine project root from workspace
-        let project_root = {
+        // Determine project root and compilation database override from workspace
+        let (project_root, global_compilation_database) = {
             let workspace = self.workspace.lock().await;
-            if workspace.project_root_path.exists() {
+            let project_root = if workspace.project_root_path.exists() {
                 workspace.project_root_path.clone()
             } else {
                 std::env::current_dir().map_err(|e| {
                     ProjectError::SessionCreation(format!("Failed to get current directory: {}", e))
                 })?
+            };
+            (project_root, workspace.global_compilation_database.clone())
+        };
+
+        // clangd runs in the override directory when one is configured, but the LSP
+        // workspace root stays the project root (or the configured root URI) so URIs
+        // and index paths do not move
+        let working_directory = self
+            .clangd_working_directory
+            .clone()
+            .unwrap_or_else(|| project_root.clone());
+        if working_directory != project_root {
+            info!(
+                "Running clangd in working directory: {}",
+                working_directory.display()
+            );
+        }
+
+        let root_uri = match &self.lsp_root_uri {
+            Some(root_uri) => {
+                info!("Using LSP root URI: {}", root_uri);
+                root_uri.clone()
             }
+            None => uri_from_pathbuf(&project_root).to_string(),
         };
 
         // Create ComponentSession
@@ -137,9 +295,13 @@ impl WorkspaceSession {
             component,
             &self.clangd_path,
             &self.clangd_version,
-            project_root,
+            root_uri,
+            working_directory,
+            global_compilation_database.as_ref(),
+            &self.clangd_process,
         )
-        .await?;
+        .await?
+        .with_empty_result_retry(self.empty_result_retry);
 
         let component_session_arc = Arc::new(component_session);
 
@@ -152,13 +314,89 @@ impl WorkspaceSession {
         Ok(component_session_arc)
     }
 
-    /// Get a non-mutable reference to the project workspace
+    /// Get an existing ComponentSession by its session id
+    ///
+    /// Unlike `get_component_session`, this never creates a session: ids are only
+    /// known for sessions that have already been created.
+    pub async fn get_component_session_by_id(
+        &self,
+        session_id: &str,
+    ) -> Result<Arc<ComponentSession>, ProjectError> {
+        let sessions = self.component_sessions.lock().await;
+
+        sessions
+            .values()
+            .find(|session| session.session_id() == session_id)
+            .cloned()
+            .ok_or_else(|| {
+                let active: Vec<String> = sessions
+                    .values()
+                    .map(|session| {
+                        format!(
+                            "{} ({})",
+                            session.session_id(),
+                            session.build_dir().display()
+                        )
+                    })
+                    .collect();
+                ProjectError::SessionNotFound(format!(
+                    "No active session with id '{}'. Active sessions: {:?}",
+                    session_id, active
+                ))
+            })
+    }
+
+    /// Get all ComponentSession instances created so far, ordered by build directory
+    pub async fn active_component_sessions(&self) -> Vec<Arc<ComponentSession>> {
+        let sessions = self.component_sessions.lock().await;
+        let mut active: Vec<Arc<ComponentSession>> = sessions.values().cloned().collect();
+        active.sort_by(|a, b| a.build_dir().cmp(b.build_dir()));
+        active
+    }
+
+    /// Replace the ComponentSession of a build directory with a freshly started one
+    ///
+    /// The existing session, if any, is removed and its clangd process shut down
+    /// gracefully before the new session is created, so the replacement starts without
+    /// open documents or in-memory index state.
+    ///
+    /// # Returns
+    /// * `Ok((previous_session_id, session))` - Id of the replaced session and the new session
+    /// * `Err(ProjectError)` - If the new session cannot be created
+    pub async fn restart_component_session(
+        &self,
+        build_dir: PathBuf,
+    ) -> Result<(Option<String>, Arc<ComponentSession>), ProjectError> {
+        let previous = self.component_sessions.lock().await.remove(&build_dir);
+
+        let previous_session_id = match previous {
+            Some(previous) => {
+                info!(
+                    "Restarting ComponentSession {} for build dir: {}",
+                    previous.session_id(),
+                    build_dir.display()
+                );
+                previous.shutdown().await;
+                Some(previous.session_id().to_string())
+            }
+            None => None,
+        };
+
+        let session = self.get_component_session(build_dir).await?;
+        Ok((previous_session_id, session))
+    }
+
+    /// Get the detected clangd version
+    pub fn clangd_version(&self) -> &ClangdVersion {
+        &self.clangd_version
+    }
+
+    /// Get a snapshot of the project workspace
     ///
-    /// Note: This now returns an Arc<Mutex<ProjectWorkspace>> since the workspace
-    /// can be mutated during dynamic component discovery. Callers should lock
-    /// the mutex to access workspace data.
-    pub fn get_workspace(&self) -> &Arc<Mutex<ProjectWorkspace>> {
-        &self.workspace
+    /// The lock is only held while taking the snapshot, so callers may keep it
+    /// across clangd requests. Components discovered later are not part of it.
+    pub async fn workspace(&self) -> Arc<ProjectWorkspace> {
+        Arc::clone(&*self.workspace.lock().await)
     }
 }
 
@@ -230,7 +468,7 @@ mod tests {
 
         // Verify the component was added to the workspace
         {
-            let workspace = workspace_session.get_workspace().lock().await;
+            let workspace = workspace_session.workspace().await;
             assert_eq!(workspace.component_count(), 1);
             assert!(
                 workspace
@@ -309,7 +547,7 @@ mod tests {
 
         // Verify workspace still has exactly one component (not duplicated)
         {
-            let workspace = workspace_session.get_workspace().lock().await;
+            let workspace = workspace_session.workspace().await;
             assert_eq!(workspace.component_count(), 1);
         }
     }
@@ -0,0 +1,276 @@
+use crate::project::{ProjectComponent, ProjectComponentProvider, ProjectError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Xcode project component provider
+///
+/// Xcode does not emit a compilation database by itself. This provider detects
+/// directories containing an `.xcworkspace` or `.xcodeproj` bundle together with
+/// a `compile_commands.json` generated from an `xcodebuild` log, either through
+/// `xcpretty -r json-compilation-database` or by `xcode-build-server`. The
+/// directory holding the bundles is used as both source root and build directory.
+pub struct XcodeProvider;
+
+/// Xcode bundles found in a directory
+#[derive(Debug, Default)]
+struct XcodeContainers {
+    /// `.xcworkspace` bundles, sorted by name
+    workspaces: Vec<PathBuf>,
+    /// `.xcodeproj` bundles, sorted by name
+    projects: Vec<PathBuf>,
+}
+
+impl XcodeProvider {
+    /// Create a new Xcode provider
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Collect the Xcode bundles located directly in a directory
+    fn find_containers(&self, dir: &Path) -> Result<XcodeContainers, ProjectError> {
+        let mut containers = XcodeContainers::default();
+
+        for entry in fs::read_dir(dir).map_err(ProjectError::Io)? {
+            let path = entry.map_err(ProjectError::Io)?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("xcworkspace") => containers.workspaces.push(path),
+                Some("xcodeproj") => containers.projects.push(path),
+                _ => {}
+            }
+        }
+
+        containers.workspaces.sort();
+        containers.projects.sort();
+        Ok(containers)
+    }
+
+    /// Parse `contents.xcworkspacedata` to list the projects of a workspace
+    ///
+    /// Workspace file references are XML attributes of the form
+    /// `location = "group:App/App.xcodeproj"`. `group:` and `container:` paths
+    /// are relative to the directory holding the workspace, `absolute:` paths
+    /// are used as-is.
+    fn parse_workspace_projects(&self, workspace: &Path) -> Result<Vec<PathBuf>, ProjectError> {
+        let contents_file = workspace.join("contents.xcworkspacedata");
+        if !contents_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&contents_file).map_err(ProjectError::Io)?;
+        let location_regex = regex::Regex::new(r#"location\s*=\s*"([^"]*)""#).map_err(|e| {
+            ProjectError::ParseError {
+                reason: format!("Invalid workspace location pattern: {e}"),
+            }
+        })?;
+        let base_dir = workspace.parent().unwrap_or(workspace);
+
+        let mut projects = Vec::new();
+        for captures in location_regex.captures_iter(&content) {
+            let location = &captures[1];
+            let path = if let Some(relative) = location
+                .strip_prefix("group:")
+                .or_else(|| location.strip_prefix("container:"))
+            {
+                base_dir.join(relative)
+            } else if let Some(absolute) = location.strip_prefix("absolute:") {
+                PathBuf::from(absolute)
+            } else {
+                // `self:` references point back at the enclosing project
+                continue;
+            };
+
+            if path.extension().and_then(|ext| ext.to_str()) == Some("xcodeproj")
+                && !projects.contains(&path)
+            {
+                projects.push(path);
+            }
+        }
+
+        Ok(projects)
+    }
+
+    /// Find compilation database path in the project directory
+    fn find_compilation_database(&self, dir: &Path) -> Option<PathBuf> {
+        let compile_commands = dir.join("compile_commands.json");
+        if compile_commands.exists() {
+            Some(compile_commands)
+        } else {
+            None
+        }
+    }
+
+    /// Shell command generating `compile_commands.json` for an Xcode bundle
+    ///
+    /// The command builds the given scheme and converts the `xcodebuild` log with
+    /// `xcpretty`. Every compile command must be captured, so it performs a clean build.
+    pub fn compilation_database_command(container: &Path, scheme: &str) -> String {
+        let flag = match container.extension().and_then(|ext| ext.to_str()) {
+            Some("xcworkspace") => "-workspace",
+            _ => "-project",
+        };
+        format!(
+            "xcodebuild {flag} '{}' -scheme '{scheme}' -configuration Debug clean build \
+             | xcpretty -r json-compilation-database --output compile_commands.json",
+            container.display()
+        )
+    }
+}
+
+/// Bundle names joined into a `;`-separated list
+fn bundle_names(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+impl ProjectComponentProvider for XcodeProvider {
+    fn scan_path(&self, path: &Path) -> Result<Option<ProjectComponent>, ProjectError> {
+        let containers = self.find_containers(path)?;
+
+        // A workspace groups the projects it references; standalone projects
+        // are only used when there is no workspace
+        let (container, projects) = match containers.workspaces.first() {
+            Some(workspace) => (workspace.clone(), self.parse_workspace_projects(workspace)?),
+            None => match containers.projects.first() {
+                Some(project) => (project.clone(), containers.projects.clone()),
+                None => return Ok(None),
+            },
+        };
+
+        let compilation_database_path = self.find_compilation_database(path).ok_or_else(|| {
+            tracing::info!(
+                "Xcode project {} has no compile_commands.json, generate it with: {}",
+                container.display(),
+                Self::compilation_database_command(&container, "<scheme>")
+            );
+            ProjectError::CompilationDatabaseNotFound {
+                path: path
+                    .join("compile_commands.json")
+                    .to_string_lossy()
+                    .to_string(),
+            }
+        })?;
+
+        let mut build_options = HashMap::new();
+        build_options.insert("BUILD_SYSTEM".to_string(), "xcode".to_string());
+        if containers.workspaces.contains(&container) {
+            build_options.insert(
+                "XCODE_WORKSPACE".to_string(),
+                bundle_names(std::slice::from_ref(&container)),
+            );
+        }
+        build_options.insert("XCODE_PROJECTS".to_string(), bundle_names(&projects));
+
+        let component = ProjectComponent::new(
+            path.to_path_buf(),
+            path.to_path_buf(),
+            compilation_database_path,
+            "xcode".to_string(),
+            "Xcode".to_string(),
+            "Debug".to_string(),
+            build_options,
+        )?;
+
+        Ok(Some(component))
+    }
+}
+
+impl Default for XcodeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const COMPILE_COMMANDS: &str = r#"[{"directory": "/tmp", "file": "main.mm", "command": "clang++ -x objective-c++ -c main.mm"}]"#;
+
+    fn create_dir(root: &Path, rel: &str) -> PathBuf {
+        let path = root.join(rel);
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_scan_workspace_lists_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let workspace = create_dir(root, "App.xcworkspace");
+        create_dir(root, "App/App.xcodeproj");
+        create_dir(root, "Pods/Pods.xcodeproj");
+        fs::write(
+            workspace.join("contents.xcworkspacedata"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Workspace
+   version = "1.0">
+   <FileRef
+      location = "group:App/App.xcodeproj">
+   </FileRef>
+   <FileRef
+      location = "group:Pods/Pods.xcodeproj">
+   </FileRef>
+   <FileRef
+      location = "group:README.md">
+   </FileRef>
+</Workspace>
+"#,
+        )
+        .unwrap();
+        fs::write(root.join("compile_commands.json"), COMPILE_COMMANDS).unwrap();
+
+        let component = XcodeProvider::new().scan_path(root).unwrap().unwrap();
+        assert_eq!(component.provider_type, "xcode");
+        assert_eq!(component.build_dir_path, root);
+        assert_eq!(
+            component.build_options.get("XCODE_WORKSPACE").unwrap(),
+            "App.xcworkspace"
+        );
+        assert_eq!(
+            component.build_options.get("XCODE_PROJECTS").unwrap(),
+            "App.xcodeproj;Pods.xcodeproj"
+        );
+    }
+
+    #[test]
+    fn test_scan_project_requires_compilation_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        create_dir(root, "Tool.xcodeproj");
+
+        let provider = XcodeProvider::new();
+        assert!(matches!(
+            provider.scan_path(root),
+            Err(ProjectError::CompilationDatabaseNotFound { .. })
+        ));
+
+        fs::write(root.join("compile_commands.json"), COMPILE_COMMANDS).unwrap();
+        let component = provider.scan_path(root).unwrap().unwrap();
+        assert!(!component.build_options.contains_key("XCODE_WORKSPACE"));
+        assert_eq!(
+            component.build_options.get("XCODE_PROJECTS").unwrap(),
+            "Tool.xcodeproj"
+        );
+
+        // Directories without Xcode bundles are not handled
+        let other = create_dir(root, "src");
+        assert!(provider.scan_path(&other).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_compilation_database_command() {
+        let command =
+            XcodeProvider::compilation_database_command(Path::new("/work/App.xcworkspace"), "App");
+        assert!(command.starts_with("xcodebuild -workspace '/work/App.xcworkspace' -scheme 'App'"));
+        assert!(command.ends_with("--output compile_commands.json"));
+    }
+}
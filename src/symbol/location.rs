@@ -1,6 +1,7 @@
 use crate::io::file_buffer::{FileBufferError, FilePosition as FileBufPosition};
 use crate::io::file_manager::FileBufferManager;
 use crate::io::file_system::FileSystemTrait;
+use crate::lsp::PositionEncoding;
 
 use std::fmt;
 use std::path::{Path, PathBuf};
@@ -201,6 +202,36 @@ impl fmt::Display for FileLine {
     }
 }
 
+impl FromStr for FileLine {
+    type Err = String;
+
+    /// Parse `/path/file.cpp:line` with a 1-based line number, as printed by grep -n
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (file_path, line) = s.rsplit_once(':').ok_or_else(|| {
+            format!(
+                "Invalid format: expected '/path/file.cpp:line', got '{}'",
+                s
+            )
+        })?;
+
+        let line: u32 = line
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid line number: '{}'", line))?;
+        if line == 0 {
+            return Err("Line numbers must be 1-based (> 0)".to_string());
+        }
+        if file_path.is_empty() {
+            return Err(format!("Missing file path in '{}'", s));
+        }
+
+        Ok(FileLine {
+            file_path: PathBuf::from(file_path),
+            line_number: line - 1,
+        })
+    }
+}
+
 impl FileLocation {
     /// Get the start line number (0-based) from the range
     #[allow(dead_code)]
@@ -219,13 +250,7 @@ impl FileLocation {
 
     /// Get the LSP URI for this file location
     pub fn get_uri(&self) -> lsp_types::Uri {
-        let path_str = self.file_path.to_string_lossy();
-        let uri_str = if path_str.starts_with('/') {
-            format!("file://{}", path_str)
-        } else {
-            format!("file:///{}", path_str)
-        };
-        uri_str.parse().expect("Failed to parse URI from file path")
+        uri_from_pathbuf(&self.file_path)
     }
 
     /// Convert FileLocation to compact LSP-style range format
@@ -311,19 +336,40 @@ impl From<LspLocation> for FilePosition {
     fn from(location: LspLocation) -> Self {
         FilePosition {
             position: location.range.start.into(),
-            file_path: location.uri.path().to_string().into(),
+            file_path: pathbuf_from_uri(&location.uri),
         }
     }
 }
 
+/// Convert a file path to a `file://` URI
+///
+/// Relative paths are resolved against the current directory. Characters that
+/// are not allowed in URIs, such as spaces, `#`, `%` or non-ASCII characters,
+/// are percent-encoded.
 pub fn uri_from_pathbuf(path: &Path) -> lsp_types::Uri {
-    use std::str::FromStr;
-    let uri_string = format!("file://{}", path.to_string_lossy());
-    lsp_types::Uri::from_str(&uri_string).expect("Failed to convert PathBuf to Uri")
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let url = url::Url::from_file_path(&absolute).expect("Failed to convert PathBuf to Uri");
+    lsp_types::Uri::from_str(url.as_str()).expect("Failed to convert PathBuf to Uri")
 }
 
+/// Convert a `file:` URI to a file path
+///
+/// Accepts the `file:///path`, `file://localhost/path` and `file:/path` forms,
+/// decodes percent-encoded characters and ignores query strings and fragments.
+/// Returns None for other schemes, remote hosts and malformed URIs.
+pub fn path_from_file_uri(uri: &str) -> Option<PathBuf> {
+    let url = url::Url::parse(uri).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    url.to_file_path().ok()
+}
+
+/// Convert an LSP URI to a file path
+///
+/// URIs that are not local `file:` URIs fall back to their raw path component.
 pub fn pathbuf_from_uri(uri: &lsp_types::Uri) -> PathBuf {
-    uri.path().to_string().into()
+    path_from_file_uri(uri.as_str()).unwrap_or_else(|| uri.path().as_str().into())
 }
 
 impl From<FilePosition> for LspLocation {
@@ -369,21 +415,24 @@ pub struct FileLineWithContents {
 }
 
 impl FileLocationWithContents {
-    #[allow(dead_code)]
+    /// Create FileLocationWithContents from a location reported by the language server
     pub fn new_from_location<T: FileSystemTrait>(
         location: &FileLocation,
+        position_encoding: PositionEncoding,
         file_buf_manager: &mut FileBufferManager<T>,
     ) -> Result<Self, FileBufferError> {
         let file_buffer = file_buf_manager.get_buffer(&location.file_path)?;
         Ok(FileLocationWithContents {
             location: location.clone(),
-            contents: file_buffer
-                .text_between(location.range.start.into(), location.range.end.into())?,
+            contents: file_buffer.text_between_encoded(
+                location.range.start.into(),
+                location.range.end.into(),
+                position_encoding,
+            )?,
         })
     }
 
     /// Create FileLocationWithContents using the full line at the location, trimmed on both ends
-    #[allow(dead_code)]
     pub fn new_from_location_full_line<T: FileSystemTrait>(
         location: &FileLocation,
         file_buf_manager: &mut FileBufferManager<T>,
@@ -415,6 +464,34 @@ impl FileLineWithContents {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_path_from_file_uri_forms() {
+        let expected = Some(PathBuf::from("/src/my project/a#b.cpp"));
+        for uri in [
+            "file:///src/my%20project/a%23b.cpp",
+            "file://localhost/src/my%20project/a%23b.cpp",
+            "file:/src/my%20project/a%23b.cpp",
+            "file:///src/my%20project/a%23b.cpp?version=2#L10",
+        ] {
+            assert_eq!(path_from_file_uri(uri), expected, "{uri}");
+        }
+
+        assert_eq!(path_from_file_uri("https://example.com/a.cpp"), None);
+        assert_eq!(path_from_file_uri("file://remote-host/a.cpp"), None);
+        assert_eq!(path_from_file_uri("/not/a/uri.cpp"), None);
+    }
+
+    #[test]
+    fn test_uri_path_round_trip() {
+        let path = PathBuf::from("/src/my project/ünïcode%.cpp");
+        let uri = uri_from_pathbuf(&path);
+        assert_eq!(
+            uri.as_str(),
+            "file:///src/my%20project/%C3%BCn%C3%AFcode%25.cpp"
+        );
+        assert_eq!(pathbuf_from_uri(&uri), path);
+    }
+
     #[test]
     fn test_compact_range_point_location() {
         let loc = FileLocation {
@@ -556,4 +633,15 @@ mod tests {
 
         assert_eq!(original, deserialized);
     }
+
+    #[test]
+    fn test_file_line_from_str() {
+        let file_line: FileLine = "src/foo.cpp:42".parse().unwrap();
+        assert_eq!(file_line.file_path, PathBuf::from("src/foo.cpp"));
+        assert_eq!(file_line.line_number, 41);
+
+        assert!("src/foo.cpp".parse::<FileLine>().is_err());
+        assert!("src/foo.cpp:0".parse::<FileLine>().is_err());
+        assert!(":42".parse::<FileLine>().is_err());
+    }
 }
@@ -7,5 +7,8 @@ mod location;
 #[allow(clippy::module_inception)]
 mod symbol;
 
-pub use location::{FileLocation, uri_from_pathbuf};
+pub use location::{
+    FileLine, FileLocation, FileLocationWithContents, path_from_file_uri, pathbuf_from_uri,
+    uri_from_pathbuf,
+};
 pub use symbol::Symbol;